@@ -112,6 +112,9 @@ impl AnyCollider for CircleCollider {
                     // Impulses are computed by the constraint solver
                     normal_impulse: 0.0,
                     tangent_impulse: 0.0,
+                    // Circles only have one feature
+                    feature_id1: PackedFeatureId::face(0),
+                    feature_id2: PackedFeatureId::face(0),
                 }],
             }]
         } else {
@@ -5,6 +5,8 @@
 //! - Basic directional movement and jumping
 //! - Support for both keyboard and gamepad input
 //! - A configurable maximum slope angle
+//! - Snapping to the ground when walking down steps and slopes
+//! - Gravity with a terminal velocity
 //! - Collision response for kinematic bodies
 //!
 //! The character controller logic is contained within the `plugin` module.
@@ -48,7 +50,9 @@ fn setup(
             ..default()
         },
         CharacterControllerBundle::new(Collider::capsule(20.0, 12.5), Vector::NEG_Y * 1500.0)
-            .with_movement(1250.0, 0.92, 400.0, (30.0 as Scalar).to_radians()),
+            .with_movement(1250.0, 0.92, 400.0, (30.0 as Scalar).to_radians())
+            .with_terminal_velocity(2000.0)
+            .with_ground_snap_distance(15.0),
     ));
 
     // A cube to move around
@@ -14,6 +14,7 @@ impl Plugin for CharacterControllerPlugin {
                     update_grounded,
                     apply_gravity,
                     movement,
+                    snap_to_ground,
                     apply_movement_damping,
                 )
                     .chain(),
@@ -57,6 +58,17 @@ pub struct JumpImpulse(Scalar);
 #[derive(Component)]
 pub struct ControllerGravity(Vector);
 
+/// The maximum speed a character controller can reach when falling.
+#[derive(Component)]
+pub struct TerminalVelocity(Scalar);
+
+/// The maximum distance a grounded character controller can be pulled down
+/// to stay on the ground, for example when walking down stairs or over small bumps.
+///
+/// This should not be larger than the maximum time of impact of the ground caster.
+#[derive(Component)]
+pub struct GroundSnapDistance(Scalar);
+
 /// The maximum angle a slope can have for a character controller
 /// to be able to climb and jump. If the slope is steeper than this angle,
 /// the character will slide down.
@@ -72,6 +84,8 @@ pub struct CharacterControllerBundle {
     collider: Collider,
     ground_caster: ShapeCaster,
     gravity: ControllerGravity,
+    terminal_velocity: TerminalVelocity,
+    ground_snap_distance: GroundSnapDistance,
     movement: MovementBundle,
 }
 
@@ -119,6 +133,8 @@ impl CharacterControllerBundle {
             ground_caster: ShapeCaster::new(caster_shape, Vector::ZERO, 0.0, Direction2d::NEG_Y)
                 .with_max_time_of_impact(10.0),
             gravity: ControllerGravity(gravity),
+            terminal_velocity: TerminalVelocity(2000.0),
+            ground_snap_distance: GroundSnapDistance(10.0),
             movement: MovementBundle::default(),
         }
     }
@@ -133,6 +149,18 @@ impl CharacterControllerBundle {
         self.movement = MovementBundle::new(acceleration, damping, jump_impulse, max_slope_angle);
         self
     }
+
+    pub fn with_terminal_velocity(mut self, terminal_velocity: Scalar) -> Self {
+        self.terminal_velocity = TerminalVelocity(terminal_velocity);
+        self
+    }
+
+    pub fn with_ground_snap_distance(mut self, distance: Scalar) -> Self {
+        self.ground_snap_distance = GroundSnapDistance(distance);
+        // The ground can only be snapped to if the ground caster reaches far enough to hit it
+        self.ground_caster.max_time_of_impact = self.ground_caster.max_time_of_impact.max(distance);
+        self
+    }
 }
 
 /// Sends [`MovementAction`] events based on keyboard input.
@@ -243,17 +271,86 @@ fn movement(
     }
 }
 
-/// Applies [`ControllerGravity`] to character controllers.
+/// Applies [`ControllerGravity`] to character controllers, limiting the fall speed
+/// to [`TerminalVelocity`].
+///
+/// Grounded characters don't accumulate gravity, so they don't build up
+/// downward velocity while walking.
 fn apply_gravity(
     time: Res<Time>,
-    mut controllers: Query<(&ControllerGravity, &mut LinearVelocity)>,
+    mut controllers: Query<(
+        &ControllerGravity,
+        Option<&TerminalVelocity>,
+        &mut LinearVelocity,
+        Has<Grounded>,
+    )>,
 ) {
     // Precision is adjusted so that the example works with
     // both the `f32` and `f64` features. Otherwise you don't need this.
     let delta_time = time.delta_seconds_f64().adjust_precision();
 
-    for (gravity, mut linear_velocity) in &mut controllers {
+    for (gravity, terminal_velocity, mut linear_velocity, is_grounded) in &mut controllers {
+        let gravity_direction = gravity.0.normalize_or_zero();
+        let fall_speed = linear_velocity.dot(gravity_direction);
+
+        if is_grounded && fall_speed >= 0.0 {
+            linear_velocity.0 -= gravity_direction * fall_speed;
+            continue;
+        }
+
         linear_velocity.0 += gravity.0 * delta_time;
+
+        if let Some(terminal_velocity) = terminal_velocity {
+            let fall_speed = linear_velocity.dot(gravity_direction);
+            if fall_speed > terminal_velocity.0 {
+                linear_velocity.0 -= gravity_direction * (fall_speed - terminal_velocity.0);
+            }
+        }
+    }
+}
+
+/// Keeps grounded character controllers on the ground when walking down steps,
+/// slopes and small bumps by moving them down to the closest walkable hit
+/// within the [`GroundSnapDistance`].
+#[allow(clippy::type_complexity)]
+fn snap_to_ground(
+    mut controllers: Query<
+        (
+            &ShapeHits,
+            &Rotation,
+            &GroundSnapDistance,
+            Option<&MaxSlopeAngle>,
+            &mut Position,
+            &mut LinearVelocity,
+        ),
+        (With<CharacterController>, With<Grounded>),
+    >,
+) {
+    for (hits, rotation, snap_distance, max_slope_angle, mut position, mut linear_velocity) in
+        &mut controllers
+    {
+        // Don't snap characters that are moving upwards, for example when jumping
+        if linear_velocity.y > 0.0 {
+            continue;
+        }
+
+        // Find the closest hit that is within the snap distance and isn't too steep
+        let closest_hit = hits
+            .iter()
+            .filter(|hit| {
+                let is_walkable = if let Some(angle) = max_slope_angle {
+                    rotation.rotate(-hit.normal2).angle_between(Vector::Y).abs() <= angle.0
+                } else {
+                    true
+                };
+                is_walkable && hit.time_of_impact > 0.0 && hit.time_of_impact <= snap_distance.0
+            })
+            .min_by(|a, b| a.time_of_impact.total_cmp(&b.time_of_impact));
+
+        if let Some(hit) = closest_hit {
+            position.y -= hit.time_of_impact;
+            linear_velocity.y = 0.0;
+        }
     }
 }
 
@@ -271,20 +368,14 @@ fn apply_movement_damping(mut query: Query<(&MovementDampingFactor, &mut LinearV
 /// This system performs very basic collision response for kinematic
 /// character controllers by pushing them along their contact normals
 /// by the current penetration depths.
-#[allow(clippy::type_complexity)]
+///
+/// The bodies are accessed through the solver's buffers,
+/// since the system runs while the constraints are being solved.
 fn kinematic_controller_collisions(
     collisions: Res<Collisions>,
     collider_parents: Query<&ColliderParent, Without<Sensor>>,
-    mut character_controllers: Query<
-        (
-            &RigidBody,
-            &mut Position,
-            &Rotation,
-            &mut LinearVelocity,
-            Option<&MaxSlopeAngle>,
-        ),
-        With<CharacterController>,
-    >,
+    character_controllers: Query<Option<&MaxSlopeAngle>, With<CharacterController>>,
+    mut bodies: SolverBodiesMut,
 ) {
     // Iterate through collisions and move the kinematic body to resolve penetration
     for contacts in collisions.iter() {
@@ -303,19 +394,23 @@ fn kinematic_controller_collisions(
         // Get the body of the character controller and whether it is the first
         // or second entity in the collision.
         let is_first: bool;
-        let (rb, mut position, rotation, mut linear_velocity, max_slope_angle) =
-            if let Ok(character) = character_controllers.get_mut(collider_parent1.get()) {
+        let (character, max_slope_angle) =
+            if let Ok(max_slope_angle) = character_controllers.get(collider_parent1.get()) {
                 is_first = true;
-                character
-            } else if let Ok(character) = character_controllers.get_mut(collider_parent2.get()) {
+                (collider_parent1.get(), max_slope_angle)
+            } else if let Ok(max_slope_angle) = character_controllers.get(collider_parent2.get()) {
                 is_first = false;
-                character
+                (collider_parent2.get(), max_slope_angle)
             } else {
                 continue;
             };
 
+        let Some((mut body, _)) = bodies.get_mut(character) else {
+            continue;
+        };
+
         // This system only handles collision response for kinematic character controllers
-        if !rb.is_kinematic() {
+        if !body.rb.is_kinematic() {
             continue;
         }
 
@@ -323,22 +418,22 @@ fn kinematic_controller_collisions(
         // Each contact in a single manifold shares the same contact normal.
         for manifold in contacts.manifolds.iter() {
             let normal = if is_first {
-                -manifold.global_normal1(rotation)
+                -manifold.global_normal1(&body.rotation)
             } else {
-                -manifold.global_normal2(rotation)
+                -manifold.global_normal2(&body.rotation)
             };
 
             // Solve each penetrating contact in the manifold
             for contact in manifold.contacts.iter().filter(|c| c.penetration > 0.0) {
-                position.0 += normal * contact.penetration;
+                body.position.0 += normal * contact.penetration;
             }
 
             // If the slope isn't too steep to walk on but the character
             // is falling, reset vertical velocity.
             if max_slope_angle.is_some_and(|angle| normal.angle_between(Vector::Y).abs() <= angle.0)
-                && linear_velocity.y < 0.0
+                && body.linear_velocity.y < 0.0
             {
-                linear_velocity.y = linear_velocity.y.max(0.0);
+                body.linear_velocity.y = body.linear_velocity.y.max(0.0);
             }
         }
     }
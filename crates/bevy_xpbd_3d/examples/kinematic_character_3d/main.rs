@@ -5,6 +5,8 @@
 //! - Basic directional movement and jumping
 //! - Support for both keyboard and gamepad input
 //! - A configurable maximum slope angle
+//! - Snapping to the ground when walking down steps and slopes
+//! - Gravity with a terminal velocity
 //! - Collision response for kinematic bodies
 //! - Loading a platformer environment from a glTF
 //!
@@ -44,7 +46,9 @@ fn setup(
             ..default()
         },
         CharacterControllerBundle::new(Collider::capsule(1.0, 0.4), Vector::NEG_Y * 9.81 * 2.0)
-            .with_movement(30.0, 0.92, 7.0, (30.0 as Scalar).to_radians()),
+            .with_movement(30.0, 0.92, 7.0, (30.0 as Scalar).to_radians())
+            .with_terminal_velocity(50.0)
+            .with_ground_snap_distance(0.3),
     ));
 
     // A cube to move around
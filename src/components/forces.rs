@@ -17,6 +17,85 @@ impl FloatZero for Scalar {
     const ZERO: Self = 0.0;
 }
 
+/// A constant force applied continuously to a dynamic [rigid body](RigidBody) at its center of mass.
+///
+/// The force is stored in world space and applied during every [substep](SubstepSchedule). Unlike [`ExternalForce`],
+/// it is never cleared, so it is well suited for things like wind, fans and other persistent forces
+/// that don't need to be re-applied every frame. For forces that follow the rotation of the body,
+/// like thrusters and propellers, see [`ConstantLocalForce`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // Push the body in the world-space X direction with a constant force.
+///     commands.spawn((RigidBody::Dynamic, ConstantForce(Vec3::X)));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ConstantForce(pub Vector);
+
+/// A constant force applied continuously to a dynamic [rigid body](RigidBody) at its center of mass,
+/// in the local space of the body.
+///
+/// The force is rotated by the body's [`Rotation`] and applied during every [substep](SubstepSchedule),
+/// so it always points in the same direction relative to the body. Unlike [`ExternalForce`], it is never cleared,
+/// which makes it well suited for thrusters and propellers. For forces in world space, see [`ConstantForce`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A rocket that is always pushed in its local up direction.
+///     commands.spawn((RigidBody::Dynamic, ConstantLocalForce(Vec3::Y * 20.0)));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ConstantLocalForce(pub Vector);
+
+/// A constant torque applied continuously to a dynamic [rigid body](RigidBody).
+///
+/// The torque is stored in world space and applied during every [substep](SubstepSchedule).
+/// Unlike [`ExternalTorque`], it is never cleared.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // Spin the body around the Y axis with a constant torque.
+///     commands.spawn((RigidBody::Dynamic, ConstantTorque(Vec3::Y)));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ConstantTorque(pub Torque);
+
 /// An external force applied continuously to a dynamic [rigid body](RigidBody).
 ///
 /// The force is stored in world space. If you want to apply forces in local space, you need to
@@ -0,0 +1,308 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use std::{fmt, sync::Arc};
+
+/// A gravity field that accelerates nearby dynamic [rigid bodies](RigidBody), centered at the
+/// [`Position`] of its entity.
+///
+/// Gravity fields can be used for things like planetary gravity, black holes and zones with
+/// different gravity. The acceleration of each field is evaluated for every dynamic body during
+/// integration, so no systems are needed for applying forces each frame.
+/// Like the global [`Gravity`], the acceleration is multiplied by the [`GravityScale`] of the body.
+///
+/// By default, bodies affected by a field are not affected by the global [`Gravity`], so a planet doesn't need
+/// the global gravity to be set to zero. This can be changed using [`with_global_gravity`](Self::with_global_gravity).
+/// Bodies affected by several fields are accelerated by all of them.
+///
+/// The field is typically added to a static body like a planet, but it can also be attached to
+/// dynamic bodies. A field never affects the body it is attached to.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A planet that pulls bodies towards its center, with an acceleration of
+///     // 9.81 m/s^2 at its surface 10 meters away from the center.
+///     commands.spawn((
+///         RigidBody::Static,
+///         Collider::sphere(10.0),
+///         GravityField::point(9.81 * 10.0 * 10.0, GravityFalloff::InverseSquare),
+///     ));
+///
+///     // A zone with sideways gravity.
+///     commands.spawn((
+///         Position(Vec3::new(50.0, 0.0, 0.0)),
+///         GravityField::directional(Vec3::X * 5.0, Vec3::splat(10.0)),
+///     ));
+///
+///     // A custom field that pulls bodies towards a plane.
+///     commands.spawn((
+///         Position(Vec3::new(-50.0, 0.0, 0.0)),
+///         GravityField::custom(|offset| Vec3::new(0.0, -offset.y.signum() * 9.81, 0.0)),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct GravityField {
+    /// The type and shape of the field.
+    pub kind: GravityFieldKind,
+    /// If `true`, the global [`Gravity`] is not applied to bodies affected by the field. True by default.
+    pub replaces_global_gravity: bool,
+}
+
+/// The type and shape of a [`GravityField`].
+#[derive(Clone)]
+pub enum GravityFieldKind {
+    /// Pulls bodies towards the center of the field.
+    Point {
+        /// The magnitude of the acceleration at a distance of one unit from the center.
+        strength: Scalar,
+        /// How the acceleration changes with the distance from the center.
+        falloff: GravityFalloff,
+        /// The maximum distance from the center at which bodies are affected.
+        radius: Scalar,
+    },
+    /// Accelerates bodies inside an axis-aligned box around the center of the field
+    /// in a constant direction.
+    Directional {
+        /// The acceleration applied to bodies inside the box.
+        acceleration: Vector,
+        /// The half extents of the box.
+        half_extents: Vector,
+    },
+    /// Accelerates bodies with a custom function that returns the acceleration for the offset
+    /// of a body from the center of the field. Every body is affected by the field.
+    Custom(Arc<dyn Fn(Vector) -> Vector + Send + Sync>),
+}
+
+impl fmt::Debug for GravityFieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Point {
+                strength,
+                falloff,
+                radius,
+            } => f
+                .debug_struct("Point")
+                .field("strength", strength)
+                .field("falloff", falloff)
+                .field("radius", radius)
+                .finish(),
+            Self::Directional {
+                acceleration,
+                half_extents,
+            } => f
+                .debug_struct("Directional")
+                .field("acceleration", acceleration)
+                .field("half_extents", half_extents)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// How the acceleration of a [point gravity field](GravityFieldKind::Point) changes
+/// with the distance from its center.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum GravityFalloff {
+    /// The acceleration is the same at all distances.
+    Constant,
+    /// The acceleration is inversely proportional to the distance.
+    Linear,
+    /// The acceleration is inversely proportional to the square of the distance, like real gravity.
+    #[default]
+    InverseSquare,
+}
+
+impl GravityField {
+    /// Creates a point gravity field that pulls bodies towards its center with the given `strength`
+    /// and `falloff`. The `strength` is the magnitude of the acceleration at a distance of one unit from the center.
+    ///
+    /// The field has an infinite radius. It can be limited using [`with_radius`](Self::with_radius).
+    pub fn point(strength: Scalar, falloff: GravityFalloff) -> Self {
+        Self {
+            kind: GravityFieldKind::Point {
+                strength,
+                falloff,
+                radius: Scalar::INFINITY,
+            },
+            replaces_global_gravity: true,
+        }
+    }
+
+    /// Creates a directional gravity field that applies the given `acceleration` to bodies inside
+    /// an axis-aligned box with the given `half_extents` around the center of the field.
+    pub fn directional(acceleration: Vector, half_extents: Vector) -> Self {
+        Self {
+            kind: GravityFieldKind::Directional {
+                acceleration,
+                half_extents,
+            },
+            replaces_global_gravity: true,
+        }
+    }
+
+    /// Creates a custom gravity field that computes the acceleration of bodies using the given function.
+    /// The function is given the offset of a body from the center of the field.
+    pub fn custom(acceleration: impl Fn(Vector) -> Vector + Send + Sync + 'static) -> Self {
+        Self {
+            kind: GravityFieldKind::Custom(Arc::new(acceleration)),
+            replaces_global_gravity: true,
+        }
+    }
+
+    /// Sets the maximum distance from the center at which bodies are affected by a
+    /// [point gravity field](GravityFieldKind::Point). Does nothing for other types of fields.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        if let GravityFieldKind::Point { radius: r, .. } = &mut self.kind {
+            *r = radius;
+        }
+        self
+    }
+
+    /// Makes the global [`Gravity`] also apply to bodies affected by the field.
+    pub fn with_global_gravity(mut self) -> Self {
+        self.replaces_global_gravity = false;
+        self
+    }
+
+    /// Computes the acceleration caused by the field at the given `point` when the center of the field
+    /// is at `center`. Returns `None` if the point is not affected by the field.
+    pub fn acceleration_at(&self, center: Vector, point: Vector) -> Option<Vector> {
+        let offset = point - center;
+        match &self.kind {
+            GravityFieldKind::Point {
+                strength,
+                falloff,
+                radius,
+            } => {
+                let distance = offset.length();
+                if distance > *radius || distance <= Scalar::EPSILON {
+                    return None;
+                }
+                let magnitude = match falloff {
+                    GravityFalloff::Constant => *strength,
+                    GravityFalloff::Linear => *strength / distance,
+                    GravityFalloff::InverseSquare => *strength / (distance * distance),
+                };
+                Some(-offset / distance * magnitude)
+            }
+            GravityFieldKind::Directional {
+                acceleration,
+                half_extents,
+            } => offset
+                .abs()
+                .cmple(*half_extents)
+                .all()
+                .then_some(*acceleration),
+            GravityFieldKind::Custom(acceleration) => Some(acceleration(offset)),
+        }
+    }
+}
+
+/// A volume that overrides the gravity of dynamic [rigid bodies](RigidBody) whose colliders
+/// are touching its [collider](Collider).
+///
+/// Gravity volumes are typically [sensors](Sensor) used for things like wall-walking sections
+/// and space station interiors. Bodies inside a volume use the gravity of the volume instead of
+/// the global [`Gravity`]. [Gravity fields](GravityField) and the [`GravityScale`] of the body are still applied.
+///
+/// When a body is inside several volumes, their gravity is combined according to the
+/// [`GravityVolumeBlending`] resource.
+///
+/// The volumes are updated at the end of each physics step based on the contacts during that step,
+/// so a body entering a volume uses its gravity starting from the next step.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A corridor where bodies are pulled towards the wall on the left.
+///     commands.spawn((
+///         Collider::cuboid(10.0, 5.0, 5.0),
+///         Sensor,
+///         GravityVolume::new(Vec3::NEG_X * 9.81),
+///     ));
+///
+///     // A room with low gravity that takes precedence over the corridor.
+///     commands.spawn((
+///         Collider::cuboid(4.0, 4.0, 4.0),
+///         Sensor,
+///         GravityVolume::scaled(0.2).with_priority(1),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Component, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct GravityVolume {
+    /// The gravitational acceleration inside the volume, or `None` to use the global [`Gravity`].
+    pub gravity: Option<Vector>,
+    /// The scaling factor applied to the [`gravity`](Self::gravity) of the volume.
+    pub scale: Scalar,
+    /// The priority of the volume when using [`GravityVolumeBlending::Priority`].
+    /// Volumes with a higher priority take precedence over volumes with a lower priority.
+    pub priority: i32,
+}
+
+impl GravityVolume {
+    /// Creates a gravity volume with the given gravitational acceleration.
+    pub fn new(gravity: Vector) -> Self {
+        Self {
+            gravity: Some(gravity),
+            scale: 1.0,
+            priority: 0,
+        }
+    }
+
+    /// Creates a gravity volume that scales the global [`Gravity`] by the given factor.
+    pub fn scaled(scale: Scalar) -> Self {
+        Self {
+            gravity: None,
+            scale,
+            priority: 0,
+        }
+    }
+
+    /// Sets the priority of the volume when using [`GravityVolumeBlending::Priority`].
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the gravitational acceleration inside the volume with the given global gravity.
+    pub fn acceleration(&self, global_gravity: Vector) -> Vector {
+        self.gravity.unwrap_or(global_gravity) * self.scale
+    }
+}
+
+/// Determines how the gravity of overlapping [gravity volumes](GravityVolume) is combined
+/// for bodies that are inside several volumes at once.
+#[derive(Reflect, Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub enum GravityVolumeBlending {
+    /// The volume with the highest [priority](GravityVolume::priority) is used.
+    /// The gravity of volumes with the same priority is averaged.
+    #[default]
+    Priority,
+    /// The gravity of the volumes is averaged.
+    Average,
+    /// The gravity of the volumes is added together.
+    Sum,
+}
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A target pose that a [kinematic](RigidBody::Kinematic) rigid body moves to over the next physics step.
+///
+/// Instead of teleporting the body by setting its [`Position`] and [`Rotation`], the engine sets its
+/// [`LinearVelocity`] and [`AngularVelocity`] so that it reaches the target at the end of the step.
+/// This gives animated platforms, doors and elevators correct velocities, so that friction carries
+/// bodies and characters standing on them, and contacts are resolved smoothly.
+///
+/// The target can be updated every frame, for example from an animation. Once the body has reached
+/// the target, its velocity is set to zero until the target is changed. If the [`rotation`](Self::rotation)
+/// is `None`, the angular velocity of the body is not modified.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Platform;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         Platform,
+///         RigidBody::Kinematic,
+///         Collider::cuboid(4.0, 0.5, 4.0),
+///         KinematicTarget::new(Vec3::ZERO),
+///     ));
+/// }
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn move_platforms(mut query: Query<&mut KinematicTarget, With<Platform>>, time: Res<Time>) {
+///     for mut target in &mut query {
+///         // Move the platform back and forth along the X axis
+///         target.position.x = (time.elapsed_seconds() * 0.5).sin() * 5.0;
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct KinematicTarget {
+    /// The target position of the body.
+    pub position: Vector,
+    /// The target rotation of the body, or `None` to leave the angular velocity unchanged.
+    pub rotation: Option<Rotation>,
+}
+
+impl KinematicTarget {
+    /// Creates a new [`KinematicTarget`] with the given target position.
+    pub fn new(position: Vector) -> Self {
+        Self {
+            position,
+            rotation: None,
+        }
+    }
+
+    /// Creates a new [`KinematicTarget`] with the given target position and rotation.
+    pub fn from_pose(position: Vector, rotation: Rotation) -> Self {
+        Self {
+            position,
+            rotation: Some(rotation),
+        }
+    }
+
+    /// Sets the target rotation of the body.
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = Some(rotation);
+        self
+    }
+
+    /// Computes the linear velocity needed to move from the given `position` to the target
+    /// in `delta_secs` seconds.
+    pub fn linear_velocity(&self, position: Vector, delta_secs: Scalar) -> Vector {
+        (self.position - position) / delta_secs
+    }
+
+    /// Computes the angular velocity needed to rotate from the given `rotation` to the target
+    /// in `delta_secs` seconds, or `None` if the target has no rotation.
+    #[cfg(feature = "2d")]
+    pub fn angular_velocity(&self, rotation: &Rotation, delta_secs: Scalar) -> Option<Scalar> {
+        let target = self.rotation?;
+        Some(target.mul(rotation.inverse()).as_radians() / delta_secs)
+    }
+
+    /// Computes the angular velocity needed to rotate from the given `rotation` to the target
+    /// in `delta_secs` seconds, or `None` if the target has no rotation.
+    #[cfg(feature = "3d")]
+    pub fn angular_velocity(&self, rotation: &Rotation, delta_secs: Scalar) -> Option<Vector> {
+        let target = self.rotation?;
+        let mut delta = target.0 * rotation.0.inverse();
+        // Rotate along the shortest path
+        if delta.w < 0.0 {
+            delta = -delta;
+        }
+        let (axis, angle) = delta.to_axis_angle();
+        Some(axis * angle / delta_secs)
+    }
+}
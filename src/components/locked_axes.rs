@@ -1,5 +1,4 @@
 use bevy::prelude::*;
-use derive_more::From;
 
 use crate::prelude::*;
 
@@ -9,6 +8,10 @@ use crate::prelude::*;
 /// specific axes is to use methods like [`lock_translation_x`](Self::lock_translation_x), but you can also
 /// use bits directly with the [`from_bits`](Self::from_bits) and [`to_bits`](Self::to_bits) methods.
 ///
+/// By default, the axes are locked in world space. They can also be locked in the local space of the body
+/// or in an arbitrary frame using [`with_frame`](Self::with_frame), for example to prevent a vehicle
+/// from rolling over around its own forward axis. See [`LockedAxesFrame`] for more information.
+///
 /// ## Example
 ///
 /// ```
@@ -25,22 +28,48 @@ use crate::prelude::*;
 ///     ));
 /// }
 /// ```
-#[derive(Component, Reflect, Clone, Copy, Debug, Default, From)]
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Component)]
-pub struct LockedAxes(u8);
+pub struct LockedAxes {
+    bits: u8,
+    frame: LockedAxesFrame,
+}
+
+/// The frame in which the axes of [`LockedAxes`] are locked.
+///
+/// The locks of bodies using the [`World`](Self::World) frame are taken into account in the effective masses
+/// of the bodies. For the other frames, the motion along and around the locked axes is removed from the positions
+/// and velocities of the bodies after the constraints have been solved.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum LockedAxesFrame {
+    /// The axes are locked in world space.
+    #[default]
+    World,
+    /// The axes are locked in the local space of the body, so they rotate with the body.
+    Local,
+    /// The axes are locked in a frame with the given world-space rotation.
+    Custom(Rotation),
+}
+
+impl From<u8> for LockedAxes {
+    fn from(bits: u8) -> Self {
+        Self::from_bits(bits)
+    }
+}
 
 impl LockedAxes {
     /// All translational axes are locked, but all rotational axes are unlocked.
-    pub const TRANSLATION_LOCKED: Self = Self(0b111_000);
+    pub const TRANSLATION_LOCKED: Self = Self::from_bits(0b111_000);
     /// All rotational axes are locked, but all translational axes are unlocked.
-    pub const ROTATION_LOCKED: Self = Self(0b000_111);
+    pub const ROTATION_LOCKED: Self = Self::from_bits(0b000_111);
     /// All translational and rotational axes are locked.
-    pub const ALL_LOCKED: Self = Self(0b111_111);
+    pub const ALL_LOCKED: Self = Self::from_bits(0b111_111);
 
     /// Creates a new [`LockedAxes`] configuration with all axes unlocked by default.
     pub const fn new() -> Self {
-        Self(0)
+        Self::from_bits(0)
     }
 
     /// Creates a new [`LockedAxes`] configuration using bits.
@@ -48,7 +77,10 @@ impl LockedAxes {
     /// The first three bits correspond to translational axes, while the last three bits correspond to rotational
     /// axes. For example, `0b100_010` would lock translation along the `X` axis and rotation around the `Y` axis.
     pub const fn from_bits(bits: u8) -> Self {
-        Self(bits)
+        Self {
+            bits,
+            frame: LockedAxesFrame::World,
+        }
     }
 
     /// Returns the locked axes as bits.
@@ -57,145 +89,183 @@ impl LockedAxes {
     /// axes. For example, `0b100_010` would mean that translation along the `X` axis and rotation around the `Y` axis
     /// are locked.
     pub const fn to_bits(&self) -> u8 {
-        self.0
+        self.bits
+    }
+
+    /// Sets the [frame](LockedAxesFrame) in which the axes are locked.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    #[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+    #[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+    ///
+    /// // Prevent movement along the local Y axis of the body
+    /// let locked_axes = LockedAxes::new()
+    ///     .lock_translation_y()
+    ///     .with_frame(LockedAxesFrame::Local);
+    /// ```
+    pub const fn with_frame(mut self, frame: LockedAxesFrame) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    /// Returns the [frame](LockedAxesFrame) in which the axes are locked.
+    pub const fn frame(&self) -> LockedAxesFrame {
+        self.frame
+    }
+
+    /// Returns the world-space rotation of the frame in which the axes are locked,
+    /// or `None` if the axes are locked in world space.
+    pub(crate) fn frame_rotation(&self, body_rotation: &Rotation) -> Option<Rotation> {
+        match self.frame {
+            LockedAxesFrame::World => None,
+            LockedAxesFrame::Local => Some(*body_rotation),
+            LockedAxesFrame::Custom(rotation) => Some(rotation),
+        }
     }
 
     /// Locks translation along the `X` axis.
     pub const fn lock_translation_x(mut self) -> Self {
-        self.0 |= 0b100_000;
+        self.bits |= 0b100_000;
         self
     }
 
     /// Locks translation along the `Y` axis.
     pub const fn lock_translation_y(mut self) -> Self {
-        self.0 |= 0b010_000;
+        self.bits |= 0b010_000;
         self
     }
 
     /// Locks translation along the `Z` axis.
     #[cfg(feature = "3d")]
     pub const fn lock_translation_z(mut self) -> Self {
-        self.0 |= 0b001_000;
+        self.bits |= 0b001_000;
         self
     }
 
     /// Locks rotation around the `X` axis.
     #[cfg(feature = "3d")]
     pub const fn lock_rotation_x(mut self) -> Self {
-        self.0 |= 0b000_100;
+        self.bits |= 0b000_100;
         self
     }
 
     /// Locks rotation around the `Y` axis.
     #[cfg(feature = "3d")]
     pub const fn lock_rotation_y(mut self) -> Self {
-        self.0 |= 0b000_010;
+        self.bits |= 0b000_010;
         self
     }
 
     /// Locks rotation around the `Z` axis.
     #[cfg(feature = "3d")]
     pub const fn lock_rotation_z(mut self) -> Self {
-        self.0 |= 0b000_001;
+        self.bits |= 0b000_001;
         self
     }
 
     /// Locks all rotation.
     #[cfg(feature = "2d")]
     pub const fn lock_rotation(mut self) -> Self {
-        self.0 |= 0b000_001;
+        self.bits |= 0b000_001;
         self
     }
 
     /// Unlocks translation along the `X` axis.
     pub const fn unlock_translation_x(mut self) -> Self {
-        self.0 &= !0b100_000;
+        self.bits &= !0b100_000;
         self
     }
 
     /// Unlocks translation along the `Y` axis.
     pub const fn unlock_translation_y(mut self) -> Self {
-        self.0 &= !0b010_000;
+        self.bits &= !0b010_000;
         self
     }
 
     /// Unlocks translation along the `Z` axis.
     #[cfg(feature = "3d")]
     pub const fn unlock_translation_z(mut self) -> Self {
-        self.0 &= !0b001_000;
+        self.bits &= !0b001_000;
         self
     }
 
     /// Unlocks rotation around the `X` axis.
     #[cfg(feature = "3d")]
     pub const fn unlock_rotation_x(mut self) -> Self {
-        self.0 &= !0b000_100;
+        self.bits &= !0b000_100;
         self
     }
 
     /// Unlocks rotation around the `Y` axis.
     #[cfg(feature = "3d")]
     pub const fn unlock_rotation_y(mut self) -> Self {
-        self.0 &= !0b000_010;
+        self.bits &= !0b000_010;
         self
     }
 
     /// Unlocks rotation around the `Z` axis.
     #[cfg(feature = "3d")]
     pub const fn unlock_rotation_z(mut self) -> Self {
-        self.0 &= !0b000_001;
+        self.bits &= !0b000_001;
         self
     }
 
     /// Unlocks all rotation.
     #[cfg(feature = "2d")]
     pub const fn unlock_rotation(mut self) -> Self {
-        self.0 &= !0b000_001;
+        self.bits &= !0b000_001;
         self
     }
 
     /// Returns true if translation is locked along the `X` axis.
     pub const fn is_translation_x_locked(&self) -> bool {
-        (self.0 & 0b100_000) != 0
+        (self.bits & 0b100_000) != 0
     }
 
     /// Returns true if translation is locked along the `X` axis.
     pub const fn is_translation_y_locked(&self) -> bool {
-        (self.0 & 0b010_000) != 0
+        (self.bits & 0b010_000) != 0
     }
 
     /// Returns true if translation is locked along the `X` axis.
     #[cfg(feature = "3d")]
     pub const fn is_translation_z_locked(&self) -> bool {
-        (self.0 & 0b001_000) != 0
+        (self.bits & 0b001_000) != 0
     }
 
     /// Returns true if rotation is locked around the `X` axis.
     #[cfg(feature = "3d")]
     pub const fn is_rotation_x_locked(&self) -> bool {
-        (self.0 & 0b000_100) != 0
+        (self.bits & 0b000_100) != 0
     }
 
     /// Returns true if rotation is locked around the `Y` axis.
     #[cfg(feature = "3d")]
     pub const fn is_rotation_y_locked(&self) -> bool {
-        (self.0 & 0b000_010) != 0
+        (self.bits & 0b000_010) != 0
     }
 
     /// Returns true if rotation is locked around the `Z` axis.
     #[cfg(feature = "3d")]
     pub const fn is_rotation_z_locked(&self) -> bool {
-        (self.0 & 0b000_001) != 0
+        (self.bits & 0b000_001) != 0
     }
 
     /// Returns true if all rotation is locked.
     #[cfg(feature = "2d")]
     pub const fn is_rotation_locked(&self) -> bool {
-        (self.0 & 0b000_001) != 0
+        (self.bits & 0b000_001) != 0
     }
 
     /// Sets translational axes of the given vector to zero based on the [`LockedAxes`] configuration.
+    ///
+    /// Locks in frames other than [`LockedAxesFrame::World`] are ignored.
     pub(crate) fn apply_to_vec(&self, mut vector: Vector) -> Vector {
+        if self.frame != LockedAxesFrame::World {
+            return vector;
+        }
         if self.is_translation_x_locked() {
             vector.x = 0.0;
         }
@@ -219,8 +289,13 @@ impl LockedAxes {
     }
 
     /// Sets rotational axes of the given 3x3 matrix to zero based on the [`LockedAxes`] configuration.
+    ///
+    /// Locks in frames other than [`LockedAxesFrame::World`] are ignored.
     #[cfg(feature = "3d")]
     pub(crate) fn apply_to_rotation(&self, mut rotation: Matrix3) -> Matrix3 {
+        if self.frame != LockedAxesFrame::World {
+            return rotation;
+        }
         if self.is_rotation_x_locked() {
             rotation.x_axis = Vector::ZERO;
         }
@@ -243,8 +318,13 @@ impl LockedAxes {
     }
 
     /// Sets axes of the given angular velocity to zero based on the [`LockedAxes`] configuration.
+    ///
+    /// Locks in frames other than [`LockedAxesFrame::World`] are ignored.
     #[cfg(feature = "3d")]
     pub(crate) fn apply_to_angular_velocity(&self, mut angular_velocity: Vector) -> Vector {
+        if self.frame != LockedAxesFrame::World {
+            return angular_velocity;
+        }
         if self.is_rotation_x_locked() {
             angular_velocity.x = 0.0;
         }
@@ -256,4 +336,25 @@ impl LockedAxes {
         }
         angular_velocity
     }
+
+    /// Sets the translational axes of the given world-space vector to zero in the frame of the [`LockedAxes`].
+    pub(crate) fn project_vec(&self, vector: Vector, body_rotation: &Rotation) -> Vector {
+        let Some(frame) = self.frame_rotation(body_rotation) else {
+            return self.apply_to_vec(vector);
+        };
+        let local = Self::from_bits(self.bits).apply_to_vec(frame.inverse().rotate(vector));
+        frame.rotate(local)
+    }
+
+    /// Sets the rotational axes of the given world-space angular vector, like an angular velocity
+    /// or a scaled rotation axis, to zero in the frame of the [`LockedAxes`].
+    #[cfg(feature = "3d")]
+    pub(crate) fn project_angular_vec(&self, vector: Vector, body_rotation: &Rotation) -> Vector {
+        let Some(frame) = self.frame_rotation(body_rotation) else {
+            return self.apply_to_angular_velocity(vector);
+        };
+        let local =
+            Self::from_bits(self.bits).apply_to_angular_velocity(frame.inverse().rotate(vector));
+        frame.rotate(local)
+    }
 }
@@ -182,6 +182,10 @@ impl From<Inertia> for InverseInertia {
 }
 
 /// The local center of mass of a body.
+///
+/// The center of mass can be changed at runtime, for example for shifting cargo or sloshing fuel.
+/// The velocity of the body is then adjusted so that the body keeps moving and rotating the same way,
+/// but from then on it rotates around the new center of mass.
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Component)]
@@ -192,6 +196,110 @@ impl CenterOfMass {
     pub const ZERO: Self = Self(Vector::ZERO);
 }
 
+/// The local center of mass of a body when it was last handled by the solver.
+/// Used for detecting and handling changes to the [`CenterOfMass`] during the simulation.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq)]
+#[reflect(Component)]
+pub(crate) struct PreviousCenterOfMass(pub Vector);
+
+/// A marker component that makes the mass properties of a [rigid body](RigidBody) be recomputed
+/// from all of its attached [colliders](Collider) in the next frame. The component is removed afterwards.
+///
+/// The mass properties are already updated automatically when the [`Collider`], [`ColliderDensity`]
+/// or [`ColliderTransform`] of a collider changes, or when a collider is attached to another body.
+/// This can be used to force a recomputation when a change was not detected, for example when a collider
+/// was modified without triggering change detection. Mass properties that were added to the body manually are kept.
+///
+/// The component is typically inserted using [`PhysicsCommands::recompute_mass_properties`].
+/// Sleeping bodies are woken up when their mass properties change.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct RecomputeMassProperties;
+
+/// Changes the [`Mass`] of a dynamic [rigid body](RigidBody) over time, for example when a rocket burns fuel,
+/// or all at once, for example when a body absorbs another object.
+///
+/// The change is applied once per physics step before the substepping loop, so the solver
+/// never sees inconsistent mass properties. The [`Inertia`] is scaled along with the mass,
+/// so the mass distribution of the body stays the same. The [`MassChangeMode`] determines
+/// whether the velocities or the momentum of the body are conserved.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A rocket that burns 2 kg of fuel per second until only 10 kg of mass is left
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::capsule(4.0, 0.5),
+///         MassChange::new(-2.0).with_min_mass(10.0),
+///     ));
+/// }
+///
+/// fn absorb(mut query: Query<&mut MassChange>) {
+///     for mut mass_change in &mut query {
+///         // Absorb an object with a mass of 5 kg during the next physics step
+///         mass_change.add(5.0);
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct MassChange {
+    /// The rate at which the mass changes per second. Negative values decrease the mass.
+    pub rate: Scalar,
+    /// A change in mass that is applied during the next physics step, after which it is reset to zero.
+    pub pending: Scalar,
+    /// The minimum mass of the body. The mass is never decreased below this or below zero.
+    pub min_mass: Scalar,
+    /// Determines whether the velocities or the momentum of the body are conserved when its mass changes.
+    pub mode: MassChangeMode,
+}
+
+impl MassChange {
+    /// Creates a new [`MassChange`] that changes the mass at the given rate per second.
+    pub fn new(rate: Scalar) -> Self {
+        Self { rate, ..default() }
+    }
+
+    /// Sets the minimum mass of the body.
+    pub fn with_min_mass(self, min_mass: Scalar) -> Self {
+        Self { min_mass, ..self }
+    }
+
+    /// Sets the [`MassChangeMode`] that determines what is conserved when the mass changes.
+    pub fn with_mode(self, mode: MassChangeMode) -> Self {
+        Self { mode, ..self }
+    }
+
+    /// Adds a change in mass that is applied during the next physics step.
+    pub fn add(&mut self, mass: Scalar) {
+        self.pending += mass;
+    }
+}
+
+/// Determines what is conserved when the mass of a body is changed with [`MassChange`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum MassChangeMode {
+    /// The linear and angular velocities of the body stay the same.
+    ///
+    /// This is the behavior of a rocket ejecting fuel, where the fuel carries its own momentum away.
+    #[default]
+    ConserveVelocity,
+    /// The linear and angular momentum of the body stay the same, so the velocities are scaled
+    /// inversely to the change in mass.
+    ///
+    /// This is the behavior of a body absorbing objects that are at rest.
+    ConserveMomentum,
+}
+
 /// A bundle containing mass properties.
 ///
 /// ## Example
@@ -356,6 +464,118 @@ impl ColliderMassProperties {
     pub fn new<C: AnyCollider>(collider: &C, density: Scalar) -> Self {
         collider.mass_properties(density)
     }
+
+    /// Computes the mass properties of a solid described by a closed triangle mesh with the given density.
+    ///
+    /// The volume, center of mass and inertia tensor are integrated exactly over the tetrahedra
+    /// formed by the triangles and the origin, so the result is accurate for concave meshes
+    /// as long as they are closed. Both winding orders are supported.
+    ///
+    /// Returns `None` if the mesh isn't closed, i.e. if some edge isn't shared by exactly two triangles
+    /// with opposite orientations, or if the mesh has no volume.
+    #[cfg(feature = "3d")]
+    pub fn from_trimesh(
+        vertices: &[Vector],
+        indices: &[[u32; 3]],
+        density: Scalar,
+    ) -> Option<Self> {
+        if !is_closed_trimesh(indices) {
+            return None;
+        }
+
+        let mut volume = 0.0;
+        let mut first_moment = Vector::ZERO;
+        let mut covariance = Matrix3::ZERO;
+
+        for triangle in indices {
+            let [a, b, c] = triangle.map(|index| vertices[index as usize]);
+
+            // The signed volume of the tetrahedron formed by the triangle and the origin
+            let tetrahedron_volume = a.dot(b.cross(c)) / 6.0;
+            let sum = a + b + c;
+
+            volume += tetrahedron_volume;
+            first_moment += sum * tetrahedron_volume / 4.0;
+            covariance += (outer_product(a, a)
+                + outer_product(b, b)
+                + outer_product(c, c)
+                + outer_product(sum, sum))
+                * (tetrahedron_volume / 20.0);
+        }
+
+        // Inward-facing triangles produce a negative volume, but the other integrals flip with it.
+        if volume.abs() <= Scalar::EPSILON {
+            return None;
+        }
+
+        let center_of_mass = first_moment / volume;
+
+        // Move the covariance to the center of mass and convert it to an inertia tensor.
+        let covariance = covariance * volume.signum()
+            - outer_product(center_of_mass, center_of_mass) * volume.abs();
+        let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+        let inertia =
+            Inertia((Matrix3::from_diagonal(Vector::splat(trace)) - covariance) * density);
+
+        let mass = volume.abs() * density;
+
+        Some(Self {
+            mass: Mass(mass),
+            inverse_mass: InverseMass(1.0 / mass),
+            inertia,
+            inverse_inertia: inertia.inverse(),
+            center_of_mass: CenterOfMass(center_of_mass),
+        })
+    }
+
+    /// Computes the mass properties of the area covered by a triangle mesh with the given density.
+    ///
+    /// The area, center of mass and angular inertia are integrated exactly over the triangles,
+    /// so the result is accurate for concave shapes. Both winding orders are supported.
+    ///
+    /// Returns `None` if the mesh has no area.
+    #[cfg(feature = "2d")]
+    pub fn from_trimesh(
+        vertices: &[Vector],
+        indices: &[[u32; 3]],
+        density: Scalar,
+    ) -> Option<Self> {
+        let mut area = 0.0;
+        let mut first_moment = Vector::ZERO;
+        let mut polar_moment = 0.0;
+
+        for triangle in indices {
+            let [a, b, c] = triangle.map(|index| vertices[index as usize]);
+            let triangle_area = (b - a).perp_dot(c - a).abs() / 2.0;
+
+            area += triangle_area;
+            first_moment += (a + b + c) * triangle_area / 3.0;
+            polar_moment += (a.length_squared()
+                + b.length_squared()
+                + c.length_squared()
+                + a.dot(b)
+                + b.dot(c)
+                + c.dot(a))
+                * (triangle_area / 6.0);
+        }
+
+        if area <= Scalar::EPSILON {
+            return None;
+        }
+
+        let center_of_mass = first_moment / area;
+        let inertia = Inertia((polar_moment - center_of_mass.length_squared() * area) * density);
+
+        let mass = area * density;
+
+        Some(Self {
+            mass: Mass(mass),
+            inverse_mass: InverseMass(1.0 / mass),
+            inertia,
+            inverse_inertia: inertia.inverse(),
+            center_of_mass: CenterOfMass(center_of_mass),
+        })
+    }
 }
 
 impl Default for ColliderMassProperties {
@@ -363,3 +583,29 @@ impl Default for ColliderMassProperties {
         Self::ZERO
     }
 }
+
+/// Returns the outer product `a * bᵀ` of the given vectors.
+#[cfg(feature = "3d")]
+fn outer_product(a: Vector, b: Vector) -> Matrix3 {
+    Matrix3::from_cols(a * b.x, a * b.y, a * b.z)
+}
+
+/// Returns `true` if each edge of the triangle mesh is shared by exactly two triangles with opposite orientations.
+#[cfg(feature = "3d")]
+fn is_closed_trimesh(indices: &[[u32; 3]]) -> bool {
+    let mut edges = bevy::utils::HashMap::<(u32, u32), i32>::default();
+
+    for &[a, b, c] in indices {
+        for (start, end) in [(a, b), (b, c), (c, a)] {
+            // Count edges in one direction as positive and edges in the other direction as negative.
+            let (key, sign) = if start < end {
+                ((start, end), 1)
+            } else {
+                ((end, start), -1)
+            };
+            *edges.entry(key).or_default() += sign;
+        }
+    }
+
+    !indices.is_empty() && edges.values().all(|count| *count == 0)
+}
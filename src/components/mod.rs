@@ -1,17 +1,23 @@
 //! Commonly used components.
 
 mod forces;
+mod gravity_field;
+mod kinematic_target;
 mod layers;
 mod locked_axes;
 mod mass_properties;
 mod rotation;
+mod wind_zone;
 mod world_queries;
 
 pub use forces::*;
+pub use gravity_field::*;
+pub use kinematic_target::*;
 pub use layers::*;
 pub use locked_axes::*;
 pub use mass_properties::*;
 pub use rotation::*;
+pub use wind_zone::*;
 pub use world_queries::*;
 
 use crate::prelude::*;
@@ -108,7 +114,8 @@ use derive_more::From;
 /// ```
 ///
 /// For applying forces and impulses to dynamic bodies, see the [`ExternalForce`], [`ExternalTorque`],
-/// [`ExternalImpulse`] and [`ExternalAngularImpulse`] components.
+/// [`ExternalImpulse`] and [`ExternalAngularImpulse`] components. Forces that are applied continuously,
+/// like thrusters, can also use the [`ConstantForce`], [`ConstantLocalForce`] and [`ConstantTorque`] components.
 ///
 /// Bevy XPBD does not have a built-in character controller, so if you need one,
 /// you will need to implement it yourself or use a third party option.
@@ -213,11 +220,23 @@ use derive_more::From;
 ///
 /// ```
 ///
+/// ## Changing the type at runtime
+///
+/// The type of a rigid body can be changed at runtime by modifying the [`RigidBody`] component.
+///
+/// - When a body becomes [static](RigidBody::Static), its velocities are set to zero.
+/// - When switching between [dynamic](RigidBody::Dynamic) and [kinematic](RigidBody::Kinematic),
+/// the velocities are kept.
+/// - The body and the bodies it is in contact with are woken up if they are [sleeping](Sleeping).
+/// - The stored contact impulses of the body are reset, and joints that can no longer
+/// be solved stop reporting forces.
+///
 /// ## See more
 ///
 /// - [Colliders](Collider)
 /// - [Gravity] and [gravity scale](GravityScale)
 /// - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
+/// - [Speed-dependent drag](Drag)
 /// - [Lock translational and rotational axes](LockedAxes)
 /// - [Dominance]
 /// - [Automatic deactivation with sleeping](Sleeping)
@@ -293,6 +312,66 @@ pub struct TimeSleeping(pub Scalar);
 #[reflect(Component)]
 pub struct SleepingDisabled;
 
+/// Removes a [rigid body](RigidBody) from the simulation without despawning it or removing its components.
+///
+/// A disabled body is not integrated, it is excluded from the broad phase and collisions, and it is ignored
+/// by [joints](joints) and other constraints. This can be used to cheaply park pooled entities like projectiles
+/// or inventory items and bring them back later by removing the component.
+///
+/// By default, the [`LinearVelocity`] and [`AngularVelocity`] of the body are kept intact, so the body continues
+/// moving as before once it is enabled again. Use [`RigidBodyDisabled::RESET_VELOCITY`] to zero the velocities
+/// when the body is disabled instead.
+///
+/// To only disable collisions for a specific collider, use [`ColliderDisabled`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(Component)]
+/// struct Projectile;
+///
+/// fn park_projectiles(mut commands: Commands, query: Query<Entity, With<Projectile>>) {
+///     for entity in &query {
+///         commands.entity(entity).insert(RigidBodyDisabled::RESET_VELOCITY);
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct RigidBodyDisabled {
+    /// If `true`, the [`LinearVelocity`] and [`AngularVelocity`] of the body are set to zero
+    /// when the body is disabled. Otherwise, they are kept intact.
+    pub reset_velocity: bool,
+}
+
+impl RigidBodyDisabled {
+    /// Disables the body and keeps its velocities intact.
+    pub const KEEP_VELOCITY: Self = Self {
+        reset_velocity: false,
+    };
+
+    /// Disables the body and sets its velocities to zero.
+    pub const RESET_VELOCITY: Self = Self {
+        reset_velocity: true,
+    };
+}
+
+/// Disables collision detection for a [collider](Collider) without despawning it or removing its components.
+///
+/// Disabled colliders are excluded from the broad phase, so they don't generate contacts or collision events.
+/// The collider still contributes to the mass properties of its rigid body.
+///
+/// To disable a whole rigid body along with all of its colliders, use [`RigidBodyDisabled`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ColliderDisabled;
+
 /// The global position of a [rigid body](RigidBody) or a [collider](Collider).
 ///
 /// ## Relation to `Transform` and `GlobalTransform`
@@ -523,6 +602,45 @@ pub(crate) struct PreSolveAngularVelocity(pub Vector);
 #[reflect(Component)]
 pub struct GravityScale(pub Scalar);
 
+/// Scales the passage of time for a specific [rigid body](RigidBody), for example
+/// for slow-motion enemies or bullet time that only affects some entities.
+///
+/// The time step used for integrating the velocity and position of the body is multiplied by the time scale,
+/// so a time scale of `0.5` makes the body move and react to forces at half speed, while `0.0` freezes it in place.
+/// The [`LinearVelocity`] and [`AngularVelocity`] are still expressed in the time of the body,
+/// so they are kept intact when the time scale changes.
+///
+/// [Joints](joints) use the largest time scale of the bodies they connect. Contacts are
+/// resolved on a best-effort basis, as the bodies in contact can have different time scales.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// // Slow down all enemies to a quarter of their normal speed
+/// fn enable_bullet_time(mut commands: Commands, enemies: Query<Entity, With<Enemy>>) {
+///     for entity in &enemies {
+///         commands.entity(entity).insert(TimeScale(0.25));
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, PartialOrd, Deref, DerefMut, From)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct TimeScale(pub Scalar);
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
 /// Determines how coefficients are combined for [`Restitution`] and [`Friction`].
 /// The default is `Average`.
 ///
@@ -543,6 +661,129 @@ pub enum CoefficientCombine {
     Max = 4,
 }
 
+/// Custom rules for combining the [`Friction`] and [`Restitution`] of two colliders in contact,
+/// used instead of the [`CoefficientCombine`] rules when registered with a [`CoefficientCombiner`] resource.
+///
+/// Both methods fall back to the default combine rules, so only the coefficients that need custom behavior
+/// have to be implemented. The [`CoefficientPair`] identifies the colliders and their [material IDs](MaterialId),
+/// which can be used for things like table-driven combination by material pair.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::{prelude::*, utils::HashMap};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// const RUBBER: MaterialId = MaterialId(0);
+/// const ICE: MaterialId = MaterialId(1);
+///
+/// struct FrictionTable(HashMap<(MaterialId, MaterialId), Friction>);
+///
+/// impl CombineCoefficients for FrictionTable {
+///     fn combine_friction(
+///         &self,
+///         pair: &CoefficientPair,
+///         friction1: Friction,
+///         friction2: Friction,
+///     ) -> Friction {
+///         // Use the friction from the table if it has an entry for the materials
+///         pair.materials()
+///             .and_then(|(material1, material2)| {
+///                 self.0
+///                     .get(&(material1, material2))
+///                     .or_else(|| self.0.get(&(material2, material1)))
+///             })
+///             .copied()
+///             .unwrap_or_else(|| friction1.combine(friction2))
+///     }
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     let mut table = HashMap::new();
+///     table.insert((RUBBER, ICE), Friction::new(0.15));
+///     commands.insert_resource(CoefficientCombiner::new(FrictionTable(table)));
+/// }
+/// ```
+pub trait CombineCoefficients: Send + Sync + 'static {
+    /// Combines the friction of two colliders in contact.
+    fn combine_friction(
+        &self,
+        pair: &CoefficientPair,
+        friction1: Friction,
+        friction2: Friction,
+    ) -> Friction {
+        let _ = pair;
+        friction1.combine(friction2)
+    }
+
+    /// Combines the restitution of two colliders in contact.
+    fn combine_restitution(
+        &self,
+        pair: &CoefficientPair,
+        restitution1: Restitution,
+        restitution2: Restitution,
+    ) -> Restitution {
+        let _ = pair;
+        restitution1.combine(restitution2)
+    }
+}
+
+/// A resource for registering custom rules for combining [`Friction`] and [`Restitution`] coefficients.
+/// See [`CombineCoefficients`].
+///
+/// If the resource doesn't exist, the [`CoefficientCombine`] rules are used.
+#[derive(Resource)]
+pub struct CoefficientCombiner(pub Box<dyn CombineCoefficients>);
+
+impl CoefficientCombiner {
+    /// Creates a new [`CoefficientCombiner`] with the given combine rules.
+    pub fn new(combiner: impl CombineCoefficients) -> Self {
+        Self(Box::new(combiner))
+    }
+}
+
+impl std::fmt::Debug for CoefficientCombiner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CoefficientCombiner").finish_non_exhaustive()
+    }
+}
+
+/// The colliders in contact whose coefficients are being combined by [`CombineCoefficients`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoefficientPair {
+    /// The first collider.
+    pub collider1: Entity,
+    /// The second collider.
+    pub collider2: Entity,
+    /// The rigid body of the first collider.
+    pub body1: Entity,
+    /// The rigid body of the second collider.
+    pub body2: Entity,
+    /// The [`MaterialId`] of the first collider.
+    pub material1: Option<MaterialId>,
+    /// The [`MaterialId`] of the second collider.
+    pub material2: Option<MaterialId>,
+}
+
+impl CoefficientPair {
+    /// Returns the [material IDs](MaterialId) of both colliders if they both have one.
+    pub fn materials(&self) -> Option<(MaterialId, MaterialId)> {
+        self.material1.zip(self.material2)
+    }
+}
+
+/// An identifier for the material of a [collider](Collider), used by custom [`CombineCoefficients`] rules
+/// for combining coefficients based on the materials of the colliders in contact.
+///
+/// The ID is not used by the physics engine otherwise.
+#[derive(
+    Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, From,
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct MaterialId(pub u32);
+
 /// A component for the [coefficient of restitution](https://en.wikipedia.org/wiki/Coefficient_of_restitution).
 /// This controls how bouncy a [rigid body](RigidBody) is.
 ///
@@ -816,6 +1057,353 @@ impl From<Scalar> for Friction {
     }
 }
 
+/// [Friction] with different coefficients along and across an axis in the local space of a [collider](Collider).
+///
+/// This can be used for things like skis and skates that slide easily forwards but resist sliding sideways,
+/// or wheels that are modeled without rotation. The [`along`](Self::along) friction is used when sliding
+/// along the axis, and the [`across`](Self::across) friction when sliding perpendicular to it.
+/// For other directions, the coefficients are interpolated elliptically.
+///
+/// The coefficients are combined with the [`Friction`] of the other collider in a contact like normal friction,
+/// and they replace the [`Friction`] of the collider itself. If both colliders in a contact have
+/// anisotropic friction, the friction of the first collider is used.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A ski that slides easily along its length, but not sideways
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "         Collider::rectangle(2.0, 0.1),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::cuboid(0.1, 0.05, 2.0),")]
+#[cfg_attr(
+    feature = "2d",
+    doc = "         AnisotropicFriction::new(Vec2::X, 0.05, 0.8),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "         AnisotropicFriction::new(Vec3::Z, 0.05, 0.8),"
+)]
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct AnisotropicFriction {
+    /// The axis in the local space of the collider along which the [`along`](Self::along) friction is used.
+    pub axis: Vector,
+    /// The friction when sliding along the [`axis`](Self::axis).
+    pub along: Friction,
+    /// The friction when sliding perpendicular to the [`axis`](Self::axis).
+    pub across: Friction,
+}
+
+impl AnisotropicFriction {
+    /// Creates a new `AnisotropicFriction` component with the given local `axis` and the friction
+    /// used when sliding `along` and `across` the axis.
+    pub fn new(axis: Vector, along: impl Into<Friction>, across: impl Into<Friction>) -> Self {
+        Self {
+            axis: axis.normalize_or_zero(),
+            along: along.into(),
+            across: across.into(),
+        }
+    }
+
+    /// Combines the friction with the [`Friction`] of another collider, returning the friction
+    /// used along and across the axis.
+    pub fn combine(&self, other: Friction) -> (Friction, Friction) {
+        (self.along.combine(other), self.across.combine(other))
+    }
+}
+
+/// A slip-based tire model that replaces the Coulomb friction of a wheel [collider](Collider), like a cylinder or a ball.
+///
+/// With plain [`Friction`], a wheel either grips the ground perfectly or slides over it with constant friction,
+/// which makes vehicles handle like hockey pucks. Real tires instead produce a force that depends on how much
+/// they slip: the force grows with small amounts of slip, peaks, and then falls off when the tire loses grip
+/// and starts spinning or skidding.
+///
+/// The tire model measures the slip of each contact of the wheel in two directions:
+///
+/// - The **longitudinal** slip is the slip ratio in the rolling direction of the wheel, the difference between
+/// the speed of the surface of the wheel and the speed of the wheel over the ground, divided by the latter.
+/// It is caused by accelerating and braking.
+#[cfg_attr(
+    feature = "3d",
+    doc = "- The **lateral** slip is the slip angle in radians between the direction the wheel is pointing in
+and the direction it is moving in. It is caused by steering."
+)]
+#[cfg_attr(
+    feature = "2d",
+    doc = "- The **lateral** slip is the slip angle between the direction the wheel is pointing in
+and the direction it is moving in. In 2D, wheels always roll in the plane, so there is no lateral slip."
+)]
+///
+/// The friction coefficients of the contact are then scaled by the corresponding [`SlipCurve`]. The coefficients
+/// themselves come from the [`Friction`] of the wheel combined with the friction of the ground like normal,
+/// so a wheel loses grip on icy surfaces. When slipping in both directions at once, the slips are combined
+/// so that the tire can't produce its peak force in both directions at the same time.
+///
+/// The slip is ill-defined when the wheel is barely moving over the ground, so below
+/// the [`min_speed`](Self::min_speed), the wheel uses normal static and dynamic friction.
+/// This lets parked vehicles stay in place on slopes.
+///
+/// The wheel is assumed to rotate around its [center of mass](CenterOfMass), which is typically
+/// the case for wheels attached to a chassis with a [`RevoluteJoint`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A wheel with rubber-like grip
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "         Collider::circle(0.4),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::cylinder(0.2, 0.4),")]
+///         Friction::new(1.0),
+#[cfg_attr(feature = "2d", doc = "         TireFriction::default(),")]
+#[cfg_attr(
+    feature = "3d",
+    doc = "         // The wheel rolls around the axis of the cylinder
+         TireFriction::new(Vec3::Y),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "         Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2)),"
+)]
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct TireFriction {
+    /// The axis in the local space of the collider that the wheel rolls around.
+    #[cfg(feature = "3d")]
+    pub axle: Vector,
+    /// The curve for the slip ratio in the rolling direction of the wheel.
+    pub longitudinal: SlipCurve,
+    /// The curve for the slip angle in radians, perpendicular to the rolling direction of the wheel.
+    #[cfg(feature = "3d")]
+    pub lateral: SlipCurve,
+    /// The speed of the wheel over the ground below which normal friction is used instead of the tire model.
+    ///
+    /// Default: `0.5`
+    pub min_speed: Scalar,
+}
+
+#[cfg(feature = "2d")]
+impl Default for TireFriction {
+    fn default() -> Self {
+        Self {
+            longitudinal: SlipCurve::LONGITUDINAL,
+            min_speed: 0.5,
+        }
+    }
+}
+
+impl TireFriction {
+    /// Creates a new `TireFriction` component for a wheel that rolls around the given `axle`
+    /// in the local space of the collider, using the default slip curves.
+    #[cfg(feature = "3d")]
+    pub fn new(axle: Vector) -> Self {
+        Self {
+            axle: axle.normalize_or_zero(),
+            longitudinal: SlipCurve::LONGITUDINAL,
+            lateral: SlipCurve::LATERAL,
+            min_speed: 0.5,
+        }
+    }
+
+    /// Sets the curve for the slip ratio in the rolling direction of the wheel.
+    pub fn with_longitudinal(self, curve: SlipCurve) -> Self {
+        Self {
+            longitudinal: curve,
+            ..self
+        }
+    }
+
+    /// Sets the curve for the slip angle in radians, perpendicular to the rolling direction of the wheel.
+    #[cfg(feature = "3d")]
+    pub fn with_lateral(self, curve: SlipCurve) -> Self {
+        Self {
+            lateral: curve,
+            ..self
+        }
+    }
+
+    /// Sets the speed of the wheel over the ground below which normal friction is used instead of the tire model.
+    pub fn with_min_speed(self, min_speed: Scalar) -> Self {
+        Self { min_speed, ..self }
+    }
+}
+
+/// A curve that maps the slip of a tire to a multiplier for the friction coefficient of a contact.
+/// Used by [`TireFriction`].
+///
+/// The curve rises linearly from zero to the [`extremum_value`](Self::extremum_value) at the
+/// [`extremum_slip`](Self::extremum_slip), where the tire has the most grip. After that, it blends smoothly
+/// to the [`asymptote_value`](Self::asymptote_value) at the [`asymptote_slip`](Self::asymptote_slip),
+/// and stays constant for larger slips.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlipCurve {
+    /// The slip at which the curve reaches its peak.
+    pub extremum_slip: Scalar,
+    /// The friction multiplier at the peak of the curve.
+    pub extremum_value: Scalar,
+    /// The slip after which the curve stays constant.
+    pub asymptote_slip: Scalar,
+    /// The friction multiplier for slips larger than the [`asymptote_slip`](Self::asymptote_slip).
+    pub asymptote_value: Scalar,
+}
+
+impl SlipCurve {
+    /// A curve for the slip ratio of a typical road tire, peaking at a slip ratio of `0.1`.
+    pub const LONGITUDINAL: Self = Self {
+        extremum_slip: 0.1,
+        extremum_value: 1.0,
+        asymptote_slip: 0.8,
+        asymptote_value: 0.75,
+    };
+
+    /// A curve for the slip angle of a typical road tire, peaking at a slip angle of about 8 degrees.
+    pub const LATERAL: Self = Self {
+        extremum_slip: 0.14,
+        extremum_value: 1.0,
+        asymptote_slip: 0.5,
+        asymptote_value: 0.75,
+    };
+
+    /// Creates a new `SlipCurve` with the given peak and asymptote.
+    pub fn new(
+        extremum_slip: Scalar,
+        extremum_value: Scalar,
+        asymptote_slip: Scalar,
+        asymptote_value: Scalar,
+    ) -> Self {
+        Self {
+            extremum_slip,
+            extremum_value,
+            asymptote_slip,
+            asymptote_value,
+        }
+    }
+
+    /// Evaluates the friction multiplier for the given slip. The sign of the slip is ignored.
+    pub fn evaluate(&self, slip: Scalar) -> Scalar {
+        let slip = slip.abs();
+
+        if slip < self.extremum_slip {
+            self.extremum_value * slip / self.extremum_slip
+        } else if slip < self.asymptote_slip {
+            // Blend from the peak to the asymptote with a smoothstep
+            let t = (slip - self.extremum_slip) / (self.asymptote_slip - self.extremum_slip);
+            let t = t * t * (3.0 - 2.0 * t);
+            self.extremum_value + (self.asymptote_value - self.extremum_value) * t
+        } else {
+            self.asymptote_value
+        }
+    }
+}
+
+/// The velocity of the surface of a [collider](Collider), used for things like conveyor belts,
+/// treadmills and moving walkways.
+///
+/// Friction drags bodies in contact with the collider along with the surface, as if the surface was moving,
+/// but the collider itself stays in place. The velocities are in the local space of the collider,
+/// so the direction of a conveyor belt follows the rotation of the collider.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A conveyor belt that moves bodies along the X axis
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "         Collider::rectangle(10.0, 0.5),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::cuboid(10.0, 0.5, 2.0),")]
+#[cfg_attr(
+    feature = "2d",
+    doc = "         SurfaceVelocity::linear(Vec2::X * 2.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "         SurfaceVelocity::linear(Vec3::X * 2.0),"
+)]
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SurfaceVelocity {
+    /// The linear velocity of the surface in the local space of the collider.
+    pub linear: Vector,
+    /// The angular velocity of the surface around the origin of the collider, like a turntable.
+    #[cfg(feature = "2d")]
+    pub angular: Scalar,
+    /// The angular velocity of the surface around the origin of the collider in the local space
+    /// of the collider, like a turntable.
+    #[cfg(feature = "3d")]
+    pub angular: Vector,
+}
+
+impl SurfaceVelocity {
+    /// Creates a surface velocity with the given linear velocity in the local space of the collider.
+    pub fn linear(linear: Vector) -> Self {
+        Self {
+            linear,
+            ..default()
+        }
+    }
+
+    /// Creates a surface velocity with the given angular velocity around the origin of the collider.
+    #[cfg(feature = "2d")]
+    pub fn angular(angular: Scalar) -> Self {
+        Self {
+            angular,
+            ..default()
+        }
+    }
+
+    /// Creates a surface velocity with the given angular velocity around the origin of the collider
+    /// in the local space of the collider.
+    #[cfg(feature = "3d")]
+    pub fn angular(angular: Vector) -> Self {
+        Self {
+            angular,
+            ..default()
+        }
+    }
+
+    /// Computes the velocity of the surface at the given point in the local space of the collider.
+    pub fn velocity_at(&self, point: Vector) -> Vector {
+        #[cfg(feature = "2d")]
+        {
+            self.linear + self.angular * point.perp()
+        }
+        #[cfg(feature = "3d")]
+        {
+            self.linear + self.angular.cross(point)
+        }
+    }
+}
+
 /// Automatically slows down a dynamic [rigid body](RigidBody), decreasing its
 /// [linear velocity](LinearVelocity) each frame. This can be used to simulate air resistance.
 ///
@@ -868,6 +1456,107 @@ pub struct LinearDamping(pub Scalar);
 #[reflect(Component)]
 pub struct AngularDamping(pub Scalar);
 
+/// Applies speed-dependent drag to a dynamic [rigid body](RigidBody), slowing it down like air resistance.
+///
+/// Unlike [`LinearDamping`], which removes a fixed fraction of the velocity, drag is a force that grows
+/// with the speed of the body, so falling bodies and projectiles reach a terminal velocity that depends on their mass.
+///
+/// The magnitude of the drag force is `linear_coefficient * speed + quadratic_coefficient * cross_section_area * speed^2`,
+/// and it acts in the opposite direction of the [linear velocity](LinearVelocity). The angular drag torque is
+/// `angular * angular_velocity` in the opposite direction of the [angular velocity](AngularVelocity).
+/// Drag can slow a body down, but it never reverses its direction of motion.
+///
+/// The velocity used for drag is relative to the global [`Wind`] and any [wind zones](WindZone)
+/// the body is in, so bodies with drag are also pushed by wind.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A projectile with mostly quadratic drag, like an object moving quickly through air
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Drag::new(0.0, 0.5).with_angular(0.1).with_cross_section_area(0.05),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Drag {
+    /// The coefficient of the drag force that is proportional to the speed of the body.
+    pub linear_coefficient: Scalar,
+    /// The coefficient of the drag force that is proportional to the square of the speed of the body.
+    /// It is multiplied by the [`cross_section_area`](Self::cross_section_area).
+    pub quadratic_coefficient: Scalar,
+    /// The coefficient of the drag torque that is proportional to the angular velocity of the body.
+    pub angular: Scalar,
+    /// The cross-sectional area of the body used for the quadratic drag force. `1.0` by default.
+    pub cross_section_area: Scalar,
+}
+
+impl Default for Drag {
+    fn default() -> Self {
+        Self {
+            linear_coefficient: 0.0,
+            quadratic_coefficient: 0.0,
+            angular: 0.0,
+            cross_section_area: 1.0,
+        }
+    }
+}
+
+impl Drag {
+    /// Creates a new [`Drag`] with the given linear and quadratic coefficients.
+    pub fn new(linear_coefficient: Scalar, quadratic_coefficient: Scalar) -> Self {
+        Self {
+            linear_coefficient,
+            quadratic_coefficient,
+            ..default()
+        }
+    }
+
+    /// Sets the coefficient of the angular drag torque.
+    pub fn with_angular(mut self, angular: Scalar) -> Self {
+        self.angular = angular;
+        self
+    }
+
+    /// Sets the cross-sectional area used for the quadratic drag force.
+    pub fn with_cross_section_area(mut self, area: Scalar) -> Self {
+        self.cross_section_area = area;
+        self
+    }
+
+    /// Sets the cross-sectional area used for the quadratic drag force based on the shape of the given collider.
+    ///
+    /// The area is approximated by the average area of the faces of the local bounding box of the collider,
+    /// or the average extent of the box in 2D.
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub fn with_collider_cross_section(self, collider: &Collider) -> Self {
+        let aabb = collider.shape_scaled().compute_local_aabb();
+        let extents = Vector::from(aabb.extents());
+        #[cfg(feature = "2d")]
+        let area = (extents.x + extents.y) / 2.0;
+        #[cfg(feature = "3d")]
+        let area = (extents.x * extents.y + extents.y * extents.z + extents.x * extents.z) / 3.0;
+        self.with_cross_section_area(area)
+    }
+
+    /// Computes the magnitude of the drag force for the given speed.
+    pub fn force_magnitude(&self, speed: Scalar) -> Scalar {
+        self.linear_coefficient * speed
+            + self.quadratic_coefficient * self.cross_section_area * speed * speed
+    }
+}
+
 /// **Dominance** allows [dynamic rigid bodies](RigidBody::Dynamic) to dominate
 /// each other during physical interactions.
 /// 
@@ -900,6 +1589,163 @@ pub struct AngularDamping(pub Scalar);
 #[reflect(Component)]
 pub struct Dominance(pub i8);
 
+/// A resource for overriding the [`Dominance`] of [dynamic bodies](RigidBody::Dynamic) in contacts
+/// between specific pairs of bodies or bodies on specific [collision layers](CollisionLayers).
+///
+/// A single dominance value per body can't express asymmetric relationships. For example, a player
+/// might dominate crates, which dominate boulders, while boulders dominate the player. The overrides
+/// take precedence over the [`Dominance`] components, and pair overrides take precedence over layer overrides.
+/// The layer overrides are checked in the order they were added, using the [`CollisionLayers`] of the colliders.
+///
+/// The overrides only affect contacts between two dynamic bodies. Static and kinematic bodies always dominate
+/// dynamic bodies, and [joints](joints) use the [`Dominance`] components.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// #[derive(PhysicsLayer)]
+/// enum Layer {
+///     Player,
+///     Crate,
+///     Boulder,
+/// }
+///
+/// fn setup(mut overrides: ResMut<DominanceOverrides>) {
+///     // The player pushes crates around, but is pushed by boulders
+///     overrides.set_layer_dominant(Layer::Player, Layer::Crate);
+///     overrides.set_layer_dominant(Layer::Boulder, Layer::Player);
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct DominanceOverrides {
+    /// The dominant entity of each overridden pair of bodies, or `None` if neither body dominates.
+    pairs: bevy::utils::HashMap<(Entity, Entity), Option<Entity>>,
+    /// The dominant and dominated layers of each layer override.
+    layers: Vec<(LayerMask, LayerMask)>,
+}
+
+impl DominanceOverrides {
+    /// Makes the `dominant` body dominate the `other` body, regardless of their [`Dominance`].
+    pub fn set_dominant(&mut self, dominant: Entity, other: Entity) {
+        self.pairs
+            .insert(Self::pair_key(dominant, other), Some(dominant));
+    }
+
+    /// Makes the given bodies affect each other normally, regardless of their [`Dominance`].
+    pub fn set_equal(&mut self, entity1: Entity, entity2: Entity) {
+        self.pairs.insert(Self::pair_key(entity1, entity2), None);
+    }
+
+    /// Removes the override for the given pair of bodies.
+    pub fn remove_pair(&mut self, entity1: Entity, entity2: Entity) {
+        self.pairs.remove(&Self::pair_key(entity1, entity2));
+    }
+
+    /// Makes bodies with colliders that are members of the `dominant` layers dominate bodies
+    /// with colliders that are members of the `other` layers.
+    pub fn set_layer_dominant(
+        &mut self,
+        dominant: impl Into<LayerMask>,
+        other: impl Into<LayerMask>,
+    ) {
+        self.layers.push((dominant.into(), other.into()));
+    }
+
+    /// Removes all pair and layer overrides.
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+        self.layers.clear();
+    }
+
+    /// Returns `true` if there are no overrides.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty() && self.layers.is_empty()
+    }
+
+    /// Returns the overridden dominances of two bodies in contact, or `None` if there is no override
+    /// for them. The layers are the [`CollisionLayers`] of the colliders that are in contact.
+    pub fn dominances(
+        &self,
+        (entity1, layers1): (Entity, CollisionLayers),
+        (entity2, layers2): (Entity, CollisionLayers),
+    ) -> Option<[i8; 2]> {
+        let dominances_for = |dominant: Option<Entity>| match dominant {
+            Some(entity) if entity == entity1 => [1, 0],
+            Some(_) => [0, 1],
+            None => [0, 0],
+        };
+
+        if let Some(&dominant) = self.pairs.get(&Self::pair_key(entity1, entity2)) {
+            return Some(dominances_for(dominant));
+        }
+
+        let is_member =
+            |layers: CollisionLayers, mask: LayerMask| (layers.memberships & mask).0 != 0;
+        self.layers.iter().find_map(|&(dominant, other)| {
+            if is_member(layers1, dominant) && is_member(layers2, other) {
+                Some(dominances_for(Some(entity1)))
+            } else if is_member(layers2, dominant) && is_member(layers1, other) {
+                Some(dominances_for(Some(entity2)))
+            } else {
+                None
+            }
+        })
+    }
+
+    fn pair_key(entity1: Entity, entity2: Entity) -> (Entity, Entity) {
+        if entity1 <= entity2 {
+            (entity1, entity2)
+        } else {
+            (entity2, entity1)
+        }
+    }
+}
+
+/// A stable identifier that determines the order in which contacts and [joints](joints) involving
+/// the entity are generated and solved.
+///
+/// By default, constraints are ordered by [`Entity`], so the same scene produces identical simulations
+/// regardless of query iteration order. However, entity IDs depend on the order in which entities are spawned,
+/// so for example a scene that is loaded in a different order on another peer or in another session
+/// can still be simulated differently. Adding a [`StableId`] to colliders and joints makes
+/// the order independent of spawn order.
+///
+/// Entities with a [`StableId`] are ordered after entities without one, so for full determinism,
+/// every collider and joint in the scene should have a unique ID.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // The ID could come from a level file or a server, for example
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::capsule(1.0, 0.4),
+///         StableId(42),
+///     ));
+/// }
+/// ```
+#[rustfmt::skip]
+#[derive(Component, Reflect, Debug, Clone, Copy, Default, Deref, DerefMut, From, PartialEq, PartialOrd, Eq, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct StableId(pub u64);
+
+impl StableId {
+    /// Returns the key used for ordering constraints involving the given entity.
+    pub(crate) fn sort_key(id: Option<&StableId>, entity: Entity) -> (Option<u64>, Entity) {
+        (id.map(|id| id.0), entity)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -0,0 +1,98 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A volume that adds local wind to the global [`Wind`] for dynamic [rigid bodies](RigidBody) whose colliders
+/// are touching its [collider](Collider).
+///
+/// Wind zones are typically [sensors](Sensor) used for things like fans, vents and windy mountain passes.
+/// Like the global [`Wind`], they only affect bodies that have a [`Drag`] component.
+/// When a body is inside several wind zones, the wind of all of them is added together.
+///
+/// The zones are updated at the end of each physics step based on the contacts during that step,
+/// so a body entering a zone is affected starting from the next step.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A fan that blows bodies upwards
+///     commands.spawn((
+///         Collider::cylinder(5.0, 1.0),
+///         Sensor,
+///         WindZone::new(Vec3::Y * 15.0).with_turbulence(3.0, 2.0),
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Component, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct WindZone {
+    /// The average velocity of the wind inside the zone.
+    pub velocity: Vector,
+    /// The maximum speed of the gusts added to the [`velocity`](Self::velocity).
+    pub turbulence: Scalar,
+    /// How many times per second the gusts change on average.
+    pub turbulence_frequency: Scalar,
+}
+
+impl WindZone {
+    /// Creates a new wind zone with the given wind velocity and no turbulence.
+    pub fn new(velocity: Vector) -> Self {
+        Self {
+            velocity,
+            ..default()
+        }
+    }
+
+    /// Sets the maximum speed of the gusts and how many times per second they change on average.
+    pub fn with_turbulence(mut self, turbulence: Scalar, frequency: Scalar) -> Self {
+        self.turbulence = turbulence;
+        self.turbulence_frequency = frequency;
+        self
+    }
+
+    /// Returns the velocity of the wind in the zone at the given `point` at the given elapsed time in seconds,
+    /// including turbulence.
+    pub fn velocity_at(&self, point: Vector, elapsed_seconds: Scalar) -> Vector {
+        self.velocity
+            + wind_turbulence(
+                point,
+                elapsed_seconds,
+                self.turbulence,
+                self.turbulence_frequency,
+            )
+    }
+}
+
+/// Computes smooth pseudo-random gusts of wind at the given `point` and time, with each component
+/// of the result between `-strength` and `strength`.
+///
+/// The gusts are a sum of sine waves with different phases for each axis, so they are deterministic
+/// and continuous in both time and space.
+pub(crate) fn wind_turbulence(
+    point: Vector,
+    elapsed_seconds: Scalar,
+    strength: Scalar,
+    frequency: Scalar,
+) -> Vector {
+    if strength == 0.0 {
+        return Vector::ZERO;
+    }
+
+    let phase = 2.0 * PI * frequency * elapsed_seconds;
+    let mut gust = Vector::ZERO;
+    for (i, component) in gust.as_mut().iter_mut().enumerate() {
+        // Offset each axis by the golden angle so that the axes don't change in sync
+        let offset = i as Scalar * 2.4;
+        *component = 0.6 * (phase + 0.31 * point.dot(Vector::ONE) + offset).sin()
+            + 0.4 * (2.3 * phase - 0.17 * point.length() + 1.7 * offset).sin();
+    }
+    gust * strength
+}
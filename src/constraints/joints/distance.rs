@@ -12,9 +12,13 @@ use bevy::{
 /// A distance joint keeps the attached bodies at a certain distance from each other while while allowing rotation around all axes.
 ///
 /// Distance joints can be useful for things like springs, muscles, and mass-spring networks.
+///
+/// With a minimum distance of zero, a distance joint acts like a rope that only limits how far apart the bodies
+/// can be. The maximum length of such a rope can be reeled in and out by a [`Winch`],
+/// for things like grappling hooks, cranes and winches.
 #[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
-#[reflect(MapEntities)]
+#[reflect(Component, MapEntities)]
 pub struct DistanceJoint {
     /// First entity constrained by the joint.
     pub entity1: Entity,
@@ -28,6 +32,11 @@ pub struct DistanceJoint {
     pub rest_length: Scalar,
     /// The extents of the allowed relative translation between the attached bodies.
     pub length_limits: Option<DistanceLimit>,
+    /// Plastic deformation of the [rest length](Self::rest_length) when the joint is stretched or compressed
+    /// too far. Ignored when [length limits](Self::length_limits) are used.
+    pub plasticity: Option<Plasticity>,
+    /// A motor that reels the maximum length of the joint in and out.
+    pub winch: Option<Winch>,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
@@ -49,7 +58,18 @@ impl XpbdConstraint<2> for DistanceJoint {
         self.lagrange = 0.0;
     }
 
+    fn reset(&mut self) {
+        self.clear_lagrange_multipliers();
+        self.force = Vector::ZERO;
+    }
+
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        if let Some(winch) = self.winch {
+            let limits = self
+                .length_limits
+                .get_or_insert(DistanceLimit::new(0.0, self.rest_length));
+            limits.max = winch.reel(limits.max, dt).max(limits.min);
+        }
         self.force = self.constrain_length(bodies, dt);
     }
 }
@@ -63,6 +83,8 @@ impl Joint for DistanceJoint {
             local_anchor2: Vector::ZERO,
             rest_length: 0.0,
             length_limits: None,
+            plasticity: None,
+            winch: None,
             damping_linear: 0.0,
             damping_angular: 0.0,
             lagrange: 0.0,
@@ -129,6 +151,14 @@ impl DistanceJoint {
         let world_r1 = body1.rotation.rotate(self.local_anchor1);
         let world_r2 = body2.rotation.rotate(self.local_anchor2);
 
+        // Permanently deform the rest length if the joint has been stretched or compressed beyond its yield strain
+        if let (None, Some(plasticity)) = (self.length_limits, self.plasticity) {
+            let length =
+                (body2.current_position() + world_r2 - body1.current_position() - world_r1)
+                    .length();
+            self.rest_length = plasticity.deform(self.rest_length, length, dt);
+        }
+
         // If min and max limits aren't specified, use rest length
         // TODO: Remove rest length, just use min/max limits.
         let limits = self
@@ -157,7 +187,7 @@ impl DistanceJoint {
         let gradients = [dir, -dir];
 
         // Compute Lagrange multiplier update, essentially the signed magnitude of the correction
-        let delta_lagrange = self.compute_lagrange_update(
+        let mut delta_lagrange = self.compute_lagrange_update(
             self.lagrange,
             distance,
             &gradients,
@@ -165,11 +195,38 @@ impl DistanceJoint {
             self.compliance,
             dt,
         );
+
+        // A winch can only hold the rope with its maximum force.
+        // Beyond that, the tension is limited and the winch pays out rope.
+        let slipping_winch = self.winch.filter(|winch| {
+            distance > 0.0
+                && self
+                    .length_limits
+                    .is_some_and(|limits| limits.max < winch.max_length)
+        });
+        let mut is_slipping = false;
+        if let Some(winch) = slipping_winch {
+            let max_lagrange = winch.max_force.max(0.0) * dt * dt;
+            let lagrange = (self.lagrange + delta_lagrange).clamp(-max_lagrange, max_lagrange);
+            is_slipping = lagrange != self.lagrange + delta_lagrange;
+            delta_lagrange = lagrange - self.lagrange;
+        }
+
         self.lagrange += delta_lagrange;
 
         // Apply positional correction (method from PositionConstraint)
         self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
 
+        if let (true, Some(winch), Some(limits)) =
+            (is_slipping, self.winch, self.length_limits.as_mut())
+        {
+            let length = (body2.current_position() + body2.rotation.rotate(self.local_anchor2)
+                - body1.current_position()
+                - body1.rotation.rotate(self.local_anchor1))
+            .length();
+            limits.max = length.clamp(limits.max, winch.max_length);
+        }
+
         // Return constraint force
         self.compute_force(self.lagrange, dir, dt)
     }
@@ -189,6 +246,26 @@ impl DistanceJoint {
             ..self
         }
     }
+
+    /// Attaches a [`Winch`] that reels the maximum length of the joint in and out.
+    ///
+    /// If the joint has no [length limits](Self::length_limits), they are set from zero to the current
+    /// [rest length](Self::rest_length) when the joint is first solved, so the joint acts like a rope.
+    pub fn with_winch(self, winch: Winch) -> Self {
+        Self {
+            winch: Some(winch),
+            ..self
+        }
+    }
+
+    /// Lets the joint deform permanently when it is stretched or compressed beyond the yield strain
+    /// of the given [`Plasticity`].
+    pub fn with_plasticity(self, plasticity: Plasticity) -> Self {
+        Self {
+            plasticity: Some(plasticity),
+            ..self
+        }
+    }
 }
 
 impl PositionConstraint for DistanceJoint {}
@@ -201,3 +278,108 @@ impl MapEntities for DistanceJoint {
         self.entity2 = entity_mapper.map_entity(self.entity2);
     }
 }
+
+/// A motor that reels the maximum length of a [`DistanceJoint`] in and out, like the winch of a crane
+/// or the reel of a grappling hook.
+///
+/// The joint acts like a rope: it only pulls the bodies together when the rope is taut, and the rope goes slack
+/// when they move closer to each other. The winch changes the maximum length of the rope at its
+/// [`speed`](Self::speed) until the length reaches the [`min_length`](Self::min_length) or
+/// [`max_length`](Self::max_length).
+///
+/// The tension of the rope is limited by the [`max_force`](Self::max_force) of the winch. If a heavy load or a fast
+/// body pulls on the rope harder than that, the winch slips and pays out rope instead of stopping the body
+/// instantly, so a grappling hook that goes taut at high speed decelerates smoothly. Once the rope is fully paid out
+/// to the [`max_length`](Self::max_length), it holds the bodies with unlimited force like a normal distance limit.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let crane = commands.spawn(RigidBody::Static).id();
+///     let load = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Lift the load at 0.5 m/s with a force of up to 5000 N
+///     commands.spawn(
+///         DistanceJoint::new(crane, load)
+///             .with_limits(0.0, 10.0)
+///             .with_winch(Winch::new(5000.0).with_speed(-0.5).with_length_range(1.0, 20.0)),
+///     );
+/// }
+///
+/// // Stop reeling when the player releases the button
+/// fn stop_winches(mut joints: Query<&mut DistanceJoint>) {
+///     for mut joint in &mut joints {
+///         if let Some(winch) = joint.winch.as_mut() {
+///             winch.speed = 0.0;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Winch {
+    /// The speed at which the rope is reeled in meters per second.
+    /// Negative values reel the rope in and positive values pay it out.
+    ///
+    /// Default: `0.0`
+    pub speed: Scalar,
+    /// The maximum tension that the winch can hold the rope with in Newtons.
+    /// When the rope is pulled harder than this, the winch slips and pays out rope.
+    pub max_force: Scalar,
+    /// The length that the rope can't be reeled in beyond.
+    ///
+    /// Default: `0.0`
+    pub min_length: Scalar,
+    /// The length that the rope can't be paid out beyond. At this length,
+    /// the rope holds the bodies regardless of the [`max_force`](Self::max_force).
+    ///
+    /// Default: `Scalar::MAX`
+    pub max_length: Scalar,
+}
+
+impl Default for Winch {
+    fn default() -> Self {
+        Self::new(Scalar::MAX)
+    }
+}
+
+impl Winch {
+    /// Creates a new stationary winch that can hold the rope with the given maximum force.
+    pub fn new(max_force: Scalar) -> Self {
+        Self {
+            speed: 0.0,
+            max_force,
+            min_length: 0.0,
+            max_length: Scalar::MAX,
+        }
+    }
+
+    /// Sets the speed at which the rope is reeled. Negative values reel the rope in
+    /// and positive values pay it out.
+    pub fn with_speed(mut self, speed: Scalar) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Sets the range of lengths that the rope can be reeled to.
+    pub fn with_length_range(mut self, min_length: Scalar, max_length: Scalar) -> Self {
+        self.min_length = min_length;
+        self.max_length = max_length;
+        self
+    }
+
+    /// Returns the length of a rope with the given `length` after it has been reeled for `dt` seconds.
+    pub fn reel(&self, length: Scalar, dt: Scalar) -> Scalar {
+        let reeled = length + self.speed * dt;
+        if self.speed < 0.0 {
+            reeled.max(self.min_length.min(length))
+        } else {
+            reeled.min(self.max_length.max(length))
+        }
+    }
+}
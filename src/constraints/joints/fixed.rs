@@ -2,7 +2,10 @@
 
 use crate::prelude::*;
 use bevy::{
-    ecs::entity::{EntityMapper, MapEntities},
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
     prelude::*,
 };
 
@@ -10,8 +13,9 @@ use bevy::{
 ///
 /// You should generally prefer using a single body instead of multiple bodies fixed together,
 /// but fixed joints can be useful for things like rigid structures where a force can dynamically break the joints connecting individual bodies.
-#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
 pub struct FixedJoint {
     /// First entity constrained by the joint.
     pub entity1: Entity,
@@ -47,6 +51,12 @@ impl XpbdConstraint<2> for FixedJoint {
         self.align_lagrange = 0.0;
     }
 
+    fn reset(&mut self) {
+        self.clear_lagrange_multipliers();
+        self.force = Vector::ZERO;
+        self.align_torque = Torque::default();
+    }
+
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
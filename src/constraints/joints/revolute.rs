@@ -2,15 +2,19 @@
 
 use crate::prelude::*;
 use bevy::{
-    ecs::entity::{EntityMapper, MapEntities},
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
     prelude::*,
 };
 
 /// A revolute joint prevents relative movement of the attached bodies, except for rotation around one `aligned_axis`.
 ///
 /// Revolute joints can be useful for things like wheels, fans, revolving doors etc.
-#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
 pub struct RevoluteJoint {
     /// First entity constrained by the joint.
     pub entity1: Entity,
@@ -61,6 +65,13 @@ impl XpbdConstraint<2> for RevoluteJoint {
         self.angle_limit_lagrange = 0.0;
     }
 
+    fn reset(&mut self) {
+        self.clear_lagrange_multipliers();
+        self.force = Vector::ZERO;
+        self.align_torque = Torque::default();
+        self.angle_limit_torque = Torque::default();
+    }
+
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
@@ -2,15 +2,19 @@
 
 use crate::prelude::*;
 use bevy::{
-    ecs::entity::{EntityMapper, MapEntities},
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
     prelude::*,
 };
 
 /// A spherical joint prevents relative translation of the attached bodies while allowing rotation around all axes.
 ///
 /// Spherical joints can be useful for things like pendula, chains, ragdolls etc.
-#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
 pub struct SphericalJoint {
     /// First entity constrained by the joint.
     pub entity1: Entity,
@@ -59,6 +63,13 @@ impl XpbdConstraint<2> for SphericalJoint {
         self.twist_lagrange = 0.0;
     }
 
+    fn reset(&mut self) {
+        self.clear_lagrange_multipliers();
+        self.force = Vector::ZERO;
+        self.swing_torque = Torque::default();
+        self.twist_torque = Torque::default();
+    }
+
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
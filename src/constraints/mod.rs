@@ -22,6 +22,11 @@
 //!     - [`RevoluteJoint`]
 //!     - [`PrismaticJoint`]
 //!
+//! Distance joints and the distance and bending constraints of ropes and cloth can also deform permanently
+//! when they are stretched beyond a limit. See [`Plasticity`].
+//!
+//! The maximum length of distance joints can be reeled in and out by a [`Winch`], for grappling hooks, cranes and winches.
+//!
 //! More constraint types will be added in future releases. If you need more constraints now, consider
 //! [creating your own constraints](#custom-constraints).
 //!
@@ -202,6 +207,7 @@
 
 pub mod joints;
 pub mod penetration;
+pub mod plasticity;
 
 mod angular_constraint;
 mod position_constraint;
@@ -209,6 +215,7 @@ mod position_constraint;
 pub use angular_constraint::AngularConstraint;
 pub use joints::*;
 pub use penetration::*;
+pub use plasticity::*;
 pub use position_constraint::PositionConstraint;
 
 use crate::prelude::*;
@@ -277,4 +284,14 @@ pub trait XpbdConstraint<const ENTITY_COUNT: usize>: MapEntities {
 
     /// Sets the constraint's [Lagrange multipliers](constraints#lagrange-multipliers) to 0.
     fn clear_lagrange_multipliers(&mut self);
+
+    /// Resets the state of the constraint when it isn't solved, for example when none of
+    /// the participating bodies are dynamic anymore. By default, this only clears
+    /// the [Lagrange multipliers](constraints#lagrange-multipliers).
+    ///
+    /// Constraints that store the forces they exert should also reset them here,
+    /// so that they don't report forces from a previous step.
+    fn reset(&mut self) {
+        self.clear_lagrange_multipliers();
+    }
 }
@@ -34,10 +34,38 @@ pub struct PenetrationConstraint {
     pub tangent_lagrange: Scalar,
     /// The constraint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
-    /// The effective [friction](Friction) of the contact.
+    /// The effective [friction](Friction) of the contact. With [anisotropic friction](AnisotropicFriction),
+    /// this is the friction along the [`friction_axis`](Self::friction_axis).
     pub friction: Friction,
+    /// The axis of [anisotropic friction](AnisotropicFriction) in world space, or `None` if the friction is the same
+    /// in all directions.
+    pub friction_axis: Option<Vector>,
+    /// The effective [friction](Friction) perpendicular to the [`friction_axis`](Self::friction_axis).
+    pub friction_across: Friction,
     /// The effective [restitution](Restitution) of the contact.
     pub restitution: Restitution,
+    /// The relative [surface velocity](SurfaceVelocity) of the first body with respect to the second body
+    /// at the contact point in world space. Friction drives the relative tangential velocity of the bodies
+    /// towards the negative of this velocity.
+    pub surface_velocity: Vector,
+    /// The [dominances](Dominance) of the first and second body overridden for this contact
+    /// using [`DominanceOverrides`], or `None` if the dominances of the bodies are used.
+    pub dominance_override: Option<[i8; 2]>,
+    /// The [tire friction](TireFriction) of a wheel in the contact, or `None` if neither collider is a wheel.
+    pub tire: Option<TireContact>,
+}
+
+/// The [tire friction](TireFriction) of a wheel in a [`PenetrationConstraint`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TireContact {
+    /// The tire model of the wheel.
+    pub friction: TireFriction,
+    /// The axle of the wheel in world space.
+    #[cfg(feature = "3d")]
+    pub axle: Vector,
+    /// Whether the wheel is the first body in the constraint.
+    pub wheel_is_first: bool,
 }
 
 impl XpbdConstraint<2> for PenetrationConstraint {
@@ -96,7 +124,41 @@ impl PenetrationConstraint {
             tangent_lagrange: 0.0,
             compliance: 0.0,
             friction: body1.friction.combine(*body2.friction),
+            friction_axis: None,
+            friction_across: body1.friction.combine(*body2.friction),
             restitution: body1.restitution.combine(*body2.restitution),
+            surface_velocity: Vector::ZERO,
+            dominance_override: None,
+            tire: None,
+        }
+    }
+
+    /// Returns the effective [friction](Friction) when sliding along the given `tangent` direction
+    /// on the surface with the given `normal`, taking [anisotropic friction](AnisotropicFriction) into account.
+    pub fn friction_in_direction(&self, tangent: Vector, normal: Vector) -> Friction {
+        let Some(axis) = self.friction_axis else {
+            return self.friction;
+        };
+
+        // Project the axis onto the contact surface
+        let axis = (axis - normal * normal.dot(axis)).normalize_or_zero();
+        let along = tangent.dot(axis).powi(2);
+        let across = 1.0 - along;
+
+        // Interpolate the coefficients elliptically
+        let blend = |along_coefficient: Scalar, across_coefficient: Scalar| {
+            (along * along_coefficient.powi(2) + across * across_coefficient.powi(2)).sqrt()
+        };
+        Friction {
+            dynamic_coefficient: blend(
+                self.friction.dynamic_coefficient,
+                self.friction_across.dynamic_coefficient,
+            ),
+            static_coefficient: blend(
+                self.friction.static_coefficient,
+                self.friction_across.static_coefficient,
+            ),
+            combine_rule: self.friction.combine_rule,
         }
     }
 
@@ -158,9 +220,24 @@ impl PenetrationConstraint {
         let delta_p2 = body2.current_position() - body2.previous_position.0
             + body2.rotation.rotate(self.contact.point2)
             - body2.previous_rotation.rotate(self.contact.point2);
-        let delta_p = delta_p1 - delta_p2;
+        // The surfaces of conveyor belts and similar colliders move relative to the bodies
+        let delta_p = delta_p1 - delta_p2 + self.surface_velocity * dt;
         let delta_p_tangent = delta_p - delta_p.dot(normal) * normal;
 
+        // Wheels with tire friction only use static friction when they are barely moving over the ground.
+        // Otherwise, the tire model handles friction in the velocity solve.
+        if let Some(tire) = self.tire {
+            let delta_hub = if tire.wheel_is_first {
+                body1.current_position() - body1.previous_position.0 - delta_p2
+            } else {
+                body2.current_position() - body2.previous_position.0 - delta_p1
+            };
+            let delta_hub_tangent = delta_hub - delta_hub.dot(normal) * normal;
+            if delta_hub_tangent.length() >= tire.friction.min_speed * dt {
+                return;
+            }
+        }
+
         // Compute magnitude of relative tangential movement and get normalized tangent vector
         let sliding_len = delta_p_tangent.length();
         if sliding_len <= Scalar::EPSILON {
@@ -177,7 +254,10 @@ impl PenetrationConstraint {
         let w = [w1, w2];
 
         // Apply static friction if |delta_x_perp| < mu_s * d
-        if sliding_len < self.friction.static_coefficient * penetration {
+        let static_coefficient = self
+            .friction_in_direction(tangent, normal)
+            .static_coefficient;
+        if sliding_len < static_coefficient * penetration {
             // Compute Lagrange multiplier update for static friction
             let delta_lagrange =
                 self.compute_lagrange_update(lagrange, sliding_len, &gradients, &w, compliance, dt);
@@ -194,7 +274,12 @@ impl PenetrationConstraint {
     }
 }
 
-impl PositionConstraint for PenetrationConstraint {}
+impl PositionConstraint for PenetrationConstraint {
+    fn dominances(&self, body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) -> [i8; 2] {
+        self.dominance_override
+            .unwrap_or_else(|| [body1.dominance(), body2.dominance()])
+    }
+}
 
 impl MapEntities for PenetrationConstraint {
     fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
@@ -0,0 +1,85 @@
+//! Plastic deformation of length constraints.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Plastic deformation parameters for constraints that keep things at a rest length, like [`DistanceJoint`]s
+/// and the distance and bending constraints of [ropes](Rope) and [cloth](Cloth).
+///
+/// When a constraint is stretched or compressed beyond the [yield strain](Self::yield_strain), its rest length
+/// permanently moves towards the current length at the rate given by the [creep](Self::creep), so the constraint
+/// no longer springs back fully. This can be used for bendable wires, dented metal structures and cloth that keeps creases.
+///
+/// The strain is measured from the length before each substep's correction, so plasticity works best with
+/// compliant constraints that can actually stretch under load.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let body1 = commands.spawn(RigidBody::Static).id();
+///     let body2 = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // A metal bar that yields when stretched by more than 10%
+///     commands.spawn(
+///         DistanceJoint::new(body1, body2)
+///             .with_rest_length(1.0)
+///             .with_compliance(0.0001)
+///             .with_plasticity(Plasticity::new(0.1, 5.0)),
+///     );
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plasticity {
+    /// The strain above which the constraint deforms permanently, as a fraction of the rest length.
+    ///
+    /// For example, a value of `0.1` lets the constraint stretch and compress by 10% before it starts to yield.
+    pub yield_strain: Scalar,
+    /// How fast the rest length moves towards the current length once the constraint yields,
+    /// as the fraction of the strain beyond the [yield strain](Self::yield_strain) that is removed per second.
+    ///
+    /// `Scalar::INFINITY` removes the excess strain immediately.
+    pub creep: Scalar,
+}
+
+impl Default for Plasticity {
+    fn default() -> Self {
+        Self {
+            yield_strain: 0.1,
+            creep: Scalar::INFINITY,
+        }
+    }
+}
+
+impl Plasticity {
+    /// Creates new plasticity parameters with the given yield strain and creep rate.
+    pub fn new(yield_strain: Scalar, creep: Scalar) -> Self {
+        Self {
+            yield_strain,
+            creep,
+        }
+    }
+
+    /// Returns the rest length after a constraint with the given `rest_length` has been held
+    /// at the given `length` for `dt` seconds.
+    pub fn deform(&self, rest_length: Scalar, length: Scalar, dt: Scalar) -> Scalar {
+        if rest_length <= Scalar::EPSILON {
+            return rest_length;
+        }
+
+        let strain = (length - rest_length) / rest_length;
+        let excess = strain.abs() - self.yield_strain.max(0.0);
+
+        if excess <= 0.0 {
+            return rest_length;
+        }
+
+        let fraction = (self.creep.max(0.0) * dt).min(1.0);
+        rest_length * (1.0 + strain.signum() * excess * fraction)
+    }
+}
@@ -30,8 +30,10 @@ pub trait PositionConstraint: XpbdConstraint<2> {
         let inv_inertia1 = body1.effective_world_inv_inertia();
         let inv_inertia2 = body2.effective_world_inv_inertia();
 
+        let [dominance1, dominance2] = self.dominances(body1, body2);
+
         // Apply positional and rotational updates
-        if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
+        if body1.rb.is_dynamic() && dominance1 <= dominance2 {
             body1.accumulated_translation.0 += p * inv_mass1;
             *body1.rotation += Self::get_delta_rot(rot1, inv_inertia1, r1, p);
 
@@ -44,7 +46,7 @@ pub trait PositionConstraint: XpbdConstraint<2> {
                 body1.rotation.0 = body1.rotation.0.normalize();
             }
         }
-        if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
+        if body2.rb.is_dynamic() && dominance2 <= dominance1 {
             body2.accumulated_translation.0 -= p * inv_mass2;
             *body2.rotation -= Self::get_delta_rot(rot2, inv_inertia2, r2, p);
 
@@ -58,6 +60,13 @@ pub trait PositionConstraint: XpbdConstraint<2> {
         p
     }
 
+    /// Returns the [dominances](Dominance) of the bodies used for applying positional corrections.
+    ///
+    /// By default, the dominances of the bodies are used.
+    fn dominances(&self, body1: &RigidBodyQueryItem, body2: &RigidBodyQueryItem) -> [i8; 2] {
+        [body1.dominance(), body2.dominance()]
+    }
+
     /// Computes the generalized inverse mass of a body when applying a positional correction
     /// at point `r` along the vector `n`.
     #[cfg(feature = "2d")]
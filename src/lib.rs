@@ -63,10 +63,25 @@
     feature = "3d",
     doc = "| `async-collider`       | Allows you to generate [`Collider`]s from mesh handles and scenes.                                                               | Yes                     |"
 )]
+//! | `cloth-mesh`           | Allows creating `Mesh`es for [`Cloth`]. Also enables the `deformable-mesh` feature.                                              | No                      |
 //! | `debug-plugin`         | Enables physics debug rendering using the [`PhysicsDebugPlugin`]. The plugin must be added separately.                           | Yes                     |
+//! | `debug-mesh`           | Enables rendering translucent collider meshes with the [`PhysicsDebugPlugin`]. Also enables the `debug-plugin` feature.          | No                      |
+//! | `deformable-mesh`      | Writes ropes, cloth and soft bodies to their `Mesh`es every frame using the [`DeformableMeshPlugin`].                            | No                      |
+//! | `egui`                 | Enables the [`PhysicsInspectorPlugin`] for tuning the simulation at runtime using egui. The plugin must be added separately.     | No                      |
 //! | `enhanced-determinism` | Enables increased determinism.                                                                                                   | No                      |
+#![cfg_attr(
+    feature = "2d",
+    doc = "| `fluid`                | Enables the [`FluidPlugin`] for simulating 2D liquids. The plugin is added by [`PhysicsExtrasPlugins`].                          | No                      |"
+)]
+//! | `gpu-broad-phase`      | Enables the experimental [`GpuBroadPhasePlugin`] for collecting collision pairs on the GPU. The plugin must be added separately. | No                      |
+#![cfg_attr(
+    feature = "2d",
+    doc = "| `granular`             | Enables the [`GranularPlugin`] for simulating 2D sand and granular materials. The plugin is added by [`PhysicsExtrasPlugins`].   | No                      |"
+)]
 //! | `parallel`             | Enables some extra multithreading, which improves performance for larger simulations but can add some overhead for smaller ones. | Yes                     |
+//! | `physics-material`     | Enables shared [`PhysicsMaterial`] assets for colliders. The plugin is added by [`PhysicsPlugins`].                              | No                      |
 //! | `simd`                 | Enables [SIMD] optimizations.                                                                                                    | No                      |
+//! | `soft-body-mesh`       | Allows creating [`SoftBody`]s and [`ShapeMatchingBody`]s from `Mesh`es. Also enables the `deformable-mesh` feature.              | No                      |
 //! | `serialize`            | Enables support for serialization and deserialization using Serde.                                                               | No                      |
 //!
 //! [SIMD]: https://en.wikipedia.org/wiki/Single_instruction,_multiple_data
@@ -127,21 +142,51 @@
 //! - [Movement](RigidBody#movement)
 //!     - [Linear](LinearVelocity) and [angular](AngularVelocity) velocity
 //!     - [Forces](ExternalForce), [torque](ExternalTorque), and [linear](ExternalImpulse) and [angular](ExternalAngularImpulse) impulses
-//! - [Gravity] and [gravity scale](GravityScale)
-//! - [Mass properties](RigidBody#mass-properties)
+//!     - [Constant forces](ConstantForce), [local forces](ConstantLocalForce) and [torque](ConstantTorque)
+//!     - [Kinematic targets](KinematicTarget) for moving kinematic bodies with velocities
+//!     - [Teleporting](PhysicsCommands::teleport) bodies without leaving behind stale solver state
+//! - [Gravity], [gravity scale](GravityScale), [gravity fields](GravityField) and [gravity volumes](GravityVolume)
+//! - [Mass properties](RigidBody#mass-properties) that are [kept up to date](RecomputeMassProperties) when colliders change
+//! - [Time-varying mass](MassChange) that conserves either velocity or momentum
 //! - [Linear](LinearDamping) and [angular](AngularDamping) velocity damping
-//! - [Lock translational and rotational axes](LockedAxes)
-//! - [Dominance]
+//! - [Speed-dependent drag](Drag), [wind](Wind) and [wind zones](WindZone)
+//! - [Buoyancy and fluid drag](FluidVolume)
+//! - [Custom force fields](ForceField) evaluated every substep
+//! - [Magnets](Magnet) that attract and repel each other
+//! - [Hovering](Hover) at a target height above the ground, for hovercraft and sci-fi bikes
+//! - [Projectiles](Projectile) with swept motion and [trajectory prediction](Ballistics) for aiming arcs
+//! - Lightweight [particles](Particle) for debris and effects that collide with colliders
+//! - [Ropes and cables](Rope) simulated as particle chains attached to bodies
+//! - [Cloth](Cloth) simulated as particle grids, with results written to meshes for rendering
+//! - Volumetric [soft bodies](SoftBody) built from tetrahedral meshes or surface meshes
+//! - Squishy [shape matching bodies](ShapeMatchingBody) that are cheap and stable
+#![cfg_attr(
+    all(feature = "2d", feature = "fluid"),
+    doc = "- 2D [liquids](Fluid) simulated as position-based fluids that push bodies around"
+)]
+#![cfg_attr(
+    all(feature = "2d", feature = "granular"),
+    doc = "- 2D [granular materials](Granular) like sand that form piles and can be dug through"
+)]
+//! - [Lock translational and rotational axes](LockedAxes) in world space or [other frames](LockedAxesFrame)
+//! - [Dominance] and [dominance overrides](DominanceOverrides) for specific pairs and layers
 //! - [Automatic deactivation with sleeping](Sleeping)
+//! - [Disabling rigid bodies](RigidBodyDisabled) without despawning them
+//! - [Per-body time scale](TimeScale) for slow motion and bullet time
 //!
 //! ### Collision detection
 //!
 //! - [Colliders](Collider)
 //!     - [Creation](Collider#creation)
 //!     - [Density](ColliderDensity)
-//!     - [Friction] and [restitution](Restitution) (bounciness)
+//!     - [Friction], [anisotropic friction](AnisotropicFriction) and [restitution](Restitution) (bounciness)
+//!     - [Custom combine rules](CombineCoefficients) for friction and restitution
+//!     - [Surface velocity](SurfaceVelocity) for conveyor belts
+//!     - [Slip-based tire friction](TireFriction) for wheels
+//!     - Shared [physics materials](PhysicsMaterial) (with `physics-material` feature)
 //!     - [Collision layers](CollisionLayers)
 //!     - [Sensors](Sensor)
+//!     - [Disabling colliders](ColliderDisabled)
 #![cfg_attr(
     feature = "3d",
     doc = "    - Creating colliders from meshes with [`AsyncCollider`] and [`AsyncSceneCollider`]"
@@ -156,7 +201,7 @@
 //! - [Constraints](constraints) (advanced)
 //! - [Joints](joints)
 //!     - [Fixed joint](FixedJoint)
-//!     - [Distance joint](DistanceJoint)
+//!     - [Distance joint](DistanceJoint), with a [winch](Winch) for reeling ropes in and out
 //!     - [Prismatic joint](PrismaticJoint)
 //!     - [Revolute joint](RevoluteJoint)
 //!     - [Spherical joint](SphericalJoint)
@@ -382,9 +427,25 @@
 //! on both the server and the client to make sure the physics simulation is only advanced by one step
 //! each time the schedule runs.
 //!
+//! Dedicated servers usually don't need rendering or windowing. In that case, you can use Bevy's `MinimalPlugins`
+//! together with [`PhysicsPlugins::headless`], and drive the simulation one tick at a time using
+//! [`step_physics`](plugins::headless::PhysicsAppExt::step_physics). See the [`headless`](plugins::headless)
+//! module for more information.
+//!
 //! Note that while Bevy XPBD should be locally deterministic, it can produce slightly different results on different
 //! machines.
 //!
+//! Local determinism also holds with the `parallel` feature. Work that is split across threads, like the narrow phase,
+//! sorts its input by entity and merges its results in that order regardless of thread scheduling, and the constraint
+//! solver accumulates impulses for each body sequentially in the same sorted order. You can verify that your own simulation is deterministic using
+//! the [`check_determinism`](plugins::checksum::check_determinism) test harness, and detect desyncs at runtime
+//! using the [`PhysicsChecksum`] resource.
+//!
+//! Contacts and joints are generated and solved in the order of their entities instead of query iteration order.
+//! Entity IDs depend on spawn order however, so if the same scene can be spawned in a different order,
+//! for example when replaying a recording in another session, add a [`StableId`] to colliders and joints
+//! to make the simulation independent of spawn order.
+//!
 //! ### Something else?
 //!
 //! Physics engines are very large and Bevy XPBD is young, so stability issues and bugs are to be expected.
@@ -559,22 +620,96 @@ pub mod resources;
 
 /// Re-exports common components, bundles, resources, plugins and types.
 pub mod prelude {
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::ballistics::{
+        launch_velocity, Ballistics, LaunchArc, Projectile, ProjectileBundle, ProjectileProperties,
+        TrajectoryHit, TrajectoryPoint,
+    };
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::buoyancy::{BuoyancyConfig, FluidBoundary, FluidVolume};
     #[cfg(feature = "debug-plugin")]
     pub use crate::plugins::debug::*;
+    #[cfg(all(
+        feature = "deformable-mesh",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::deformable_mesh::{DeformableMesh, DeformableMeshConfig};
+    #[cfg(all(
+        feature = "2d",
+        feature = "fluid",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::fluid::Fluid;
+    #[cfg(all(
+        feature = "2d",
+        feature = "granular",
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::granular::Granular;
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::hover::{Hover, HoverHit};
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::magnetism::{Magnet, MagnetFalloff};
+    #[cfg(feature = "physics-material")]
+    pub use crate::plugins::physics_material::PhysicsMaterial;
+    #[cfg(feature = "serialize")]
+    pub use crate::plugins::physics_scene::*;
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub use crate::plugins::{
+        cloth::{Cloth, ClothConstraint, ClothConstraintKind, ClothTorn},
+        particles::{Particle, ParticleAttachment, ParticleRadius, ParticleSet},
+        rope::{Rope, RopeTorn},
+        shape_matching::{ShapeMatchingBody, ShapeMatchingCluster},
+        soft_body::{SoftBody, SoftBodyEdge, SoftBodyElement},
+    };
     pub use crate::{
         components::*,
         constraints::{joints::*, *},
         plugins::{
+            checksum::PhysicsChecksum,
             collision::{
                 broad_phase::BroadCollisionPairs,
                 contact_reporting::{Collision, CollisionEnded, CollisionStarted},
-                narrow_phase::NarrowPhaseConfig,
+                narrow_phase::{ContactManifoldCaches, NarrowPhaseConfig},
                 *,
             },
+            constraint_graph::*,
+            correction::{RemoteBodyState, StateCorrection},
+            force_field::{ForceField, ForceFieldBody, ForceFieldFunction, ForceFieldScope},
+            headless::PhysicsAppExt,
+            integrator::{
+                Explosion, ExplosionFalloff, PhysicsCommands, QueuedExplosions, QueuedImpulse,
+                QueuedImpulses, Teleport,
+            },
+            memory::PhysicsMemoryUsage,
+            prediction::*,
             prepare::{init_transforms, update_mass_properties, PrepareConfig, PreparePlugin},
+            replay::{PhysicsRecorder, PhysicsRecording, PhysicsReplay},
             setup::*,
-            solver::solve_constraint,
+            sleeping::PhysicsIslands,
+            snapshot::*,
+            snapshot_delta::{BodyDelta, SnapshotDelta},
+            solver::{solve_constraint, SolverBodies, SolverBodiesMut, SolverBodyState},
             spatial_query::*,
+            stepper::{PhysicsStepUnit, PhysicsStepper},
             *,
         },
         resources::*,
@@ -760,6 +895,7 @@ pub enum SubstepSet {
     ///
     /// You can [create new constraints](constraints#custom-constraints) by implementing [`XpbdConstraint`]
     /// for a component and adding the [constraint system](solve_constraint) to this set.
+    /// Custom systems in this set should access rigid bodies using [`SolverBodiesMut`].
     ///
     /// See [`SolverPlugin`].
     SolveUserConstraints,
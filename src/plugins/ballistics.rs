@@ -0,0 +1,761 @@
+//! Helpers for fast-moving projectiles like bullets, arrows and cannonballs.
+//!
+//! See [`BallisticsPlugin`], [`Projectile`] and [`Ballistics`].
+
+use std::time::Duration;
+
+use crate::{
+    plugins::integrator::{damp_linear_velocity, gravity_acceleration},
+    prelude::*,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+
+/// Prevents fast [projectiles](Projectile) from tunneling through thin geometry.
+///
+/// Contacts are only detected between bodies that overlap at the end of a substep, so a body that moves
+/// further than its own thickness during a substep can pass through walls and floors without ever touching them.
+/// For each [`Projectile`], the plugin stores its position at the start of the physics step, and after
+/// [`PhysicsStepSet::Substeps`], sweeps its collider along the path it travelled. If the path hits something,
+/// the projectile is moved back to the point of impact and bounces off the surface.
+///
+/// The sweep uses the [`SpatialQueryPipeline`], which is updated at the end of each physics step,
+/// so colliders that were added after the previous step can't be hit yet.
+///
+/// Trajectories can be predicted ahead of time with the [`Ballistics`] system parameter.
+pub struct BallisticsPlugin;
+
+impl Plugin for BallisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectileStartPositions>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems((
+                store_projectile_start_positions
+                    .after(PhysicsStepSet::BroadPhase)
+                    .before(PhysicsStepSet::Substeps),
+                sweep_projectiles
+                    .after(PhysicsStepSet::Substeps)
+                    .before(PhysicsStepSet::ReportContacts),
+            ));
+    }
+}
+
+/// Marks a dynamic [rigid body](RigidBody) as a fast-moving projectile like a bullet, an arrow or a cannonball.
+///
+/// The motion of projectiles is swept every physics step to prevent them from tunneling through thin geometry.
+/// To keep the cost low, the sweep is only done when the projectile moves further than a fraction of its
+/// thickness during the step, so slow and resting projectiles are handled by the normal contact solver.
+///
+/// When a swept projectile hits a collider, it is moved back to the point of impact, and its velocity into
+/// the surface is reflected using the combined [`Restitution`] of the projectile and the hit collider.
+/// The hit collider is treated as stationary. [Sensors](Sensor) and the colliders of the projectile itself
+/// are ignored.
+///
+/// Projectiles without a [`Collider`] on the same entity are swept using a ray.
+///
+/// Requires the [`BallisticsPlugin`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn shoot(mut commands: Commands) {
+///     commands.spawn((
+///         ProjectileBundle::new(
+#[cfg_attr(feature = "2d", doc = "             Collider::circle(0.05),")]
+#[cfg_attr(feature = "3d", doc = "             Collider::sphere(0.05),")]
+#[cfg_attr(feature = "2d", doc = "             Vec2::Y,")]
+#[cfg_attr(feature = "3d", doc = "             Vec3::Y,")]
+///             // Fire a bullet at 400 m/s
+#[cfg_attr(feature = "2d", doc = "             Vec2::X * 400.0,")]
+#[cfg_attr(feature = "3d", doc = "             Vec3::NEG_Z * 400.0,")]
+///         ),
+///         // Bullets slow down because of air resistance
+///         Drag::new(0.0, 0.001),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Projectile {
+    /// Whether the motion of the projectile is swept to prevent tunneling.
+    ///
+    /// Default: `true`
+    pub swept: bool,
+    /// How far the projectile must move during a physics step for the motion to be swept,
+    /// as a fraction of the thickness of its collider.
+    ///
+    /// Default: `0.5`
+    pub sweep_threshold: Scalar,
+    /// Rules that determine which colliders can stop the projectile.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl Default for Projectile {
+    fn default() -> Self {
+        Self {
+            swept: true,
+            sweep_threshold: 0.5,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl Projectile {
+    /// Sets whether the motion of the projectile is swept to prevent tunneling.
+    pub fn with_swept(mut self, swept: bool) -> Self {
+        self.swept = swept;
+        self
+    }
+
+    /// Sets how far the projectile must move during a physics step for the motion to be swept,
+    /// as a fraction of the thickness of its collider.
+    pub fn with_sweep_threshold(mut self, threshold: Scalar) -> Self {
+        self.sweep_threshold = threshold;
+        self
+    }
+
+    /// Sets the rules that determine which colliders can stop the projectile.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+}
+
+/// A bundle for spawning a dynamic [`Projectile`] with an initial position and velocity.
+///
+/// The mass properties are computed from the collider like for any other rigid body.
+#[allow(missing_docs)]
+#[derive(Bundle, Clone)]
+pub struct ProjectileBundle {
+    pub rigid_body: RigidBody,
+    pub collider: Collider,
+    pub position: Position,
+    pub linear_velocity: LinearVelocity,
+    pub projectile: Projectile,
+}
+
+impl ProjectileBundle {
+    /// Creates a new [`ProjectileBundle`] with the given collider, initial position and initial velocity.
+    pub fn new(collider: Collider, position: Vector, velocity: Vector) -> Self {
+        Self {
+            rigid_body: RigidBody::Dynamic,
+            collider,
+            position: Position(position),
+            linear_velocity: LinearVelocity(velocity),
+            projectile: Projectile::default(),
+        }
+    }
+
+    /// Creates a new [`ProjectileBundle`] that is launched from `position` towards `target`
+    /// with the given `speed` under the given `gravity`, or `None` if the target is out of range.
+    ///
+    /// See [`launch_velocity`] for more information.
+    pub fn aimed(
+        collider: Collider,
+        position: Vector,
+        target: Vector,
+        speed: Scalar,
+        gravity: Vector,
+        arc: LaunchArc,
+    ) -> Option<Self> {
+        let velocity = launch_velocity(position, target, speed, gravity, arc)?;
+        Some(Self::new(collider, position, velocity))
+    }
+
+    /// Sets the configuration of the [`Projectile`].
+    pub fn with_projectile(mut self, projectile: Projectile) -> Self {
+        self.projectile = projectile;
+        self
+    }
+}
+
+/// Which of the two possible arcs is used for hitting a target with a given launch speed.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum LaunchArc {
+    /// The flatter arc that reaches the target sooner.
+    #[default]
+    Low,
+    /// The higher, lobbed arc that reaches the target later.
+    High,
+}
+
+/// Computes the velocity for launching a projectile from `origin` so that it passes through `target`
+/// with the given `speed` under a constant `gravity`.
+///
+/// Returns `None` if the target can't be reached with the given speed.
+///
+/// The result is exact for projectiles that are only affected by gravity. Drag, damping and
+/// [gravity fields](GravityField) are not taken into account, but the result can be refined by
+/// [predicting the trajectory](Ballistics::trajectory) and correcting the aim.
+pub fn launch_velocity(
+    origin: Vector,
+    target: Vector,
+    speed: Scalar,
+    gravity: Vector,
+    arc: LaunchArc,
+) -> Option<Vector> {
+    let offset = target - origin;
+    let distance_squared = offset.length_squared();
+
+    if speed <= 0.0 || distance_squared == 0.0 {
+        return None;
+    }
+
+    let gravity_squared = gravity.length_squared();
+
+    if gravity_squared < Scalar::EPSILON {
+        return Some(offset.normalize() * speed);
+    }
+
+    // The flight time t solves |offset / t - gravity * t / 2| = speed,
+    // which is a quadratic equation in t².
+    let a = 0.25 * gravity_squared;
+    let b = -(speed * speed + offset.dot(gravity));
+    let c = distance_squared;
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let sqrt_discriminant = discriminant.sqrt();
+    let time_squared = match arc {
+        LaunchArc::Low => (-b - sqrt_discriminant) / (2.0 * a),
+        LaunchArc::High => (-b + sqrt_discriminant) / (2.0 * a),
+    };
+
+    if time_squared <= 0.0 {
+        return None;
+    }
+
+    let time = time_squared.sqrt();
+    Some(offset / time - 0.5 * gravity * time)
+}
+
+/// The properties of a body that affect its ballistic trajectory, used for [predicting trajectories](Ballistics).
+///
+/// Use [`Ballistics::properties`] to get the properties of an existing body.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectileProperties {
+    /// The mass of the body. Only affects the trajectory when the body has [`Drag`].
+    ///
+    /// Default: `1.0`
+    pub mass: Scalar,
+    /// The [`GravityScale`] of the body.
+    ///
+    /// Default: `1.0`
+    pub gravity_scale: Scalar,
+    /// The [`LinearDamping`] of the body.
+    ///
+    /// Default: `0.0`
+    pub linear_damping: Scalar,
+    /// The [`Drag`] of the body.
+    ///
+    /// Default: `None`
+    pub drag: Option<Drag>,
+    /// The [`LockedAxes`] of the body.
+    pub locked_axes: LockedAxes,
+}
+
+impl Default for ProjectileProperties {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl ProjectileProperties {
+    /// Creates new [`ProjectileProperties`] for a body with the given mass that is only affected by gravity.
+    pub fn new(mass: Scalar) -> Self {
+        Self {
+            mass,
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            drag: None,
+            locked_axes: LockedAxes::default(),
+        }
+    }
+
+    /// Sets the [`GravityScale`] of the body.
+    pub fn with_gravity_scale(mut self, gravity_scale: Scalar) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    /// Sets the [`LinearDamping`] of the body.
+    pub fn with_linear_damping(mut self, linear_damping: Scalar) -> Self {
+        self.linear_damping = linear_damping;
+        self
+    }
+
+    /// Sets the [`Drag`] of the body.
+    pub fn with_drag(mut self, drag: Drag) -> Self {
+        self.drag = Some(drag);
+        self
+    }
+
+    /// Sets the [`LockedAxes`] of the body.
+    pub fn with_locked_axes(mut self, locked_axes: LockedAxes) -> Self {
+        self.locked_axes = locked_axes;
+        self
+    }
+}
+
+/// A point on a predicted trajectory, at the end of a physics step.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrajectoryPoint {
+    /// The time from the start of the trajectory in seconds.
+    pub time: Scalar,
+    /// The position of the body.
+    pub position: Vector,
+    /// The linear velocity of the body.
+    pub velocity: Vector,
+}
+
+/// The first collider hit by a predicted trajectory, returned by [`Ballistics::trajectory_hit`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrajectoryHit {
+    /// The entity of the collider that was hit.
+    pub entity: Entity,
+    /// The time from the start of the trajectory at which the collider is hit, in seconds.
+    pub time: Scalar,
+    /// The point of impact in world space.
+    pub point: Vector,
+    /// The normal of the hit collider at the [`point`](Self::point) in world space.
+    pub normal: Vector,
+    /// The velocity of the body at the time of impact.
+    pub velocity: Vector,
+}
+
+/// A [`SystemParam`] for predicting the trajectories of projectiles, for example for drawing aiming arcs.
+///
+/// The trajectories are integrated with the same substepped integration as the simulation, using the current
+/// [timestep](TimestepMode) of [`Time<Physics>`](Physics), [`SubstepCount`], [`Gravity`], [gravity fields](GravityField)
+/// and [`Wind`]. A body that only moves freely follows the predicted trajectory exactly. For a variable timestep,
+/// the maximum delta time is used. [Gravity volumes](GravityVolume), [wind zones](WindZone) and contacts are not
+/// taken into account.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn aim(ballistics: Ballistics) {
+#[cfg_attr(feature = "2d", doc = "     let origin = Vec2::ZERO;")]
+#[cfg_attr(feature = "3d", doc = "     let origin = Vec3::ZERO;")]
+#[cfg_attr(feature = "2d", doc = "     let velocity = Vec2::new(10.0, 10.0);")]
+#[cfg_attr(
+    feature = "3d",
+    doc = "     let velocity = Vec3::new(10.0, 10.0, 0.0);"
+)]
+///     let properties = ProjectileProperties::new(2.0).with_drag(Drag::new(0.0, 0.05));
+///
+///     // Sample the predicted path for the next two seconds, for example for drawing an aiming arc
+///     let trajectory = ballistics.trajectory(origin, velocity, &properties, 2.0);
+///     for point in trajectory.iter() {
+///         println!("{}: {}", point.time, point.position);
+///     }
+///
+///     // Find out where the projectile would land
+///     if let Some(hit) = ballistics.trajectory_hit(
+///         origin,
+///         velocity,
+///         &properties,
+///         10.0,
+///         SpatialQueryFilter::default(),
+///     ) {
+///         println!("Hit {:?} after {} seconds", hit.entity, hit.time);
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct Ballistics<'w, 's> {
+    gravity: Res<'w, Gravity>,
+    wind: Res<'w, Wind>,
+    substep_count: Res<'w, SubstepCount>,
+    physics_time: Res<'w, Time<Physics>>,
+    gravity_fields: Query<'w, 's, (Entity, &'static GravityField, &'static Position)>,
+    #[allow(clippy::type_complexity)]
+    bodies: Query<
+        'w,
+        's,
+        (
+            &'static Position,
+            &'static LinearVelocity,
+            &'static Mass,
+            Option<&'static GravityScale>,
+            Option<&'static LinearDamping>,
+            Option<&'static Drag>,
+            Option<&'static LockedAxes>,
+        ),
+    >,
+    pipeline: Option<Res<'w, SpatialQueryPipeline>>,
+}
+
+impl<'w, 's> Ballistics<'w, 's> {
+    /// Returns the [`ProjectileProperties`] of the given rigid body, or `None` if the entity is not a rigid body.
+    pub fn properties(&self, entity: Entity) -> Option<ProjectileProperties> {
+        let (_, _, mass, gravity_scale, damping, drag, locked_axes) =
+            self.bodies.get(entity).ok()?;
+        Some(ProjectileProperties {
+            mass: mass.0,
+            gravity_scale: gravity_scale.map_or(1.0, |scale| scale.0),
+            linear_damping: damping.map_or(0.0, |damping| damping.0),
+            drag: drag.copied(),
+            locked_axes: locked_axes.copied().unwrap_or_default(),
+        })
+    }
+
+    /// Computes the velocity for launching a projectile from `origin` so that it passes through `target`
+    /// with the given `speed` under the current [`Gravity`] scaled by the [`gravity_scale`](ProjectileProperties::gravity_scale).
+    ///
+    /// See [`launch_velocity`] for more information.
+    pub fn launch_velocity(
+        &self,
+        origin: Vector,
+        target: Vector,
+        speed: Scalar,
+        properties: &ProjectileProperties,
+        arc: LaunchArc,
+    ) -> Option<Vector> {
+        launch_velocity(
+            origin,
+            target,
+            speed,
+            self.gravity.0 * properties.gravity_scale,
+            arc,
+        )
+    }
+
+    /// Predicts the trajectory of a body launched from `origin` with the given `velocity` for `duration` seconds.
+    ///
+    /// The returned points start with the initial state and contain the state at the end of every physics step.
+    pub fn trajectory(
+        &self,
+        origin: Vector,
+        velocity: Vector,
+        properties: &ProjectileProperties,
+        duration: Scalar,
+    ) -> Vec<TrajectoryPoint> {
+        let step_count = (duration / self.step_duration().as_secs_adjusted()).ceil() as u32;
+        let mut points = Vec::with_capacity(step_count as usize + 1);
+        self.integrate(origin, velocity, properties, step_count, |point| {
+            points.push(point);
+            true
+        });
+        points
+    }
+
+    /// Predicts the trajectory of an existing rigid body from its current position and velocity
+    /// for `duration` seconds, or returns `None` if the entity is not a rigid body.
+    ///
+    /// See [`trajectory`](Self::trajectory) for more information.
+    pub fn body_trajectory(
+        &self,
+        entity: Entity,
+        duration: Scalar,
+    ) -> Option<Vec<TrajectoryPoint>> {
+        let properties = self.properties(entity)?;
+        let (pos, lin_vel, ..) = self.bodies.get(entity).ok()?;
+        Some(self.trajectory(pos.0, lin_vel.0, &properties, duration))
+    }
+
+    /// Predicts the trajectory of a body launched from `origin` with the given `velocity` and returns
+    /// the first collider that it hits within `max_duration` seconds.
+    ///
+    /// The trajectory is tested with a ray cast between each pair of consecutive points, so the size of the body
+    /// is not taken into account. Returns `None` if nothing is hit or if there is no [`SpatialQueryPipeline`].
+    pub fn trajectory_hit(
+        &self,
+        origin: Vector,
+        velocity: Vector,
+        properties: &ProjectileProperties,
+        max_duration: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<TrajectoryHit> {
+        let pipeline = self.pipeline.as_ref()?;
+        let step_count = (max_duration / self.step_duration().as_secs_adjusted()).ceil() as u32;
+        let mut previous = TrajectoryPoint {
+            time: 0.0,
+            position: origin,
+            velocity,
+        };
+        let mut trajectory_hit = None;
+
+        self.integrate(origin, velocity, properties, step_count, |point| {
+            let segment = point.position - previous.position;
+            let length = segment.length();
+
+            if let Ok(direction) = Dir::new(segment.f32()) {
+                if let Some(hit) = pipeline.cast_ray(
+                    previous.position,
+                    direction,
+                    length,
+                    true,
+                    query_filter.clone(),
+                ) {
+                    let fraction = hit.time_of_impact / length;
+                    trajectory_hit = Some(TrajectoryHit {
+                        entity: hit.entity,
+                        time: previous.time + fraction * (point.time - previous.time),
+                        point: previous.position
+                            + direction.adjust_precision() * hit.time_of_impact,
+                        normal: hit.normal,
+                        velocity: previous.velocity.lerp(point.velocity, fraction),
+                    });
+                    return false;
+                }
+            }
+
+            previous = point;
+            true
+        });
+
+        trajectory_hit
+    }
+
+    /// The duration of a physics step with the current timestep.
+    fn step_duration(&self) -> Duration {
+        match self.physics_time.timestep_mode() {
+            TimestepMode::Fixed { delta, .. } | TimestepMode::FixedOnce { delta } => {
+                delta.mul_f64(self.physics_time.relative_speed_f64())
+            }
+            TimestepMode::Variable { max_delta } => max_delta,
+        }
+    }
+
+    /// Integrates a trajectory for the given number of physics steps like the [`IntegratorPlugin`] and
+    /// [`SolverPlugin`] do, calling `callback` with the initial state and the state after every step
+    /// until it returns `false`.
+    fn integrate(
+        &self,
+        origin: Vector,
+        velocity: Vector,
+        properties: &ProjectileProperties,
+        step_count: u32,
+        mut callback: impl FnMut(TrajectoryPoint) -> bool,
+    ) {
+        let step_duration = self.step_duration();
+        let substeps = self.substep_count.0.max(1);
+        let delta_secs = step_duration.div_f64(substeps as f64).as_secs_adjusted();
+
+        let mass = properties.mass;
+        let inv_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+        let effective_mass = properties.locked_axes.apply_to_vec(Vector::splat(mass));
+        let effective_inv_mass = properties.locked_axes.apply_to_vec(Vector::splat(inv_mass));
+        let damping = LinearDamping(properties.linear_damping);
+
+        let mut position = origin;
+        let mut velocity = velocity;
+        let mut time = 0.0;
+
+        if !callback(TrajectoryPoint {
+            time,
+            position,
+            velocity,
+        }) {
+            return;
+        }
+
+        for step in 1..=step_count {
+            // The physics clock is advanced before the substeps are run
+            let elapsed_secs =
+                (self.physics_time.elapsed() + step_duration * step).as_secs_adjusted();
+
+            for _ in 0..substeps {
+                velocity = damp_linear_velocity(
+                    velocity,
+                    Some(&damping),
+                    properties.drag.as_ref(),
+                    || self.wind.velocity_at(position, elapsed_secs),
+                    inv_mass,
+                    delta_secs,
+                );
+
+                let gravitation_force = effective_mass
+                    * gravity_acceleration(
+                        Entity::PLACEHOLDER,
+                        position,
+                        self.gravity.0,
+                        &self.gravity_fields,
+                    )
+                    * properties.gravity_scale;
+                velocity += delta_secs * gravitation_force * effective_inv_mass;
+
+                // The velocity is derived from the change in position like in the solver
+                let translation = properties.locked_axes.apply_to_vec(delta_secs * velocity);
+                velocity = translation / delta_secs;
+                position += translation;
+            }
+
+            time += step_duration.as_secs_adjusted();
+
+            if !callback(TrajectoryPoint {
+                time,
+                position,
+                velocity,
+            }) {
+                return;
+            }
+        }
+    }
+}
+
+/// The positions of [projectiles](Projectile) at the start of the current physics step.
+#[derive(Resource, Default)]
+struct ProjectileStartPositions(HashMap<Entity, Vector>);
+
+/// Stores the positions of [projectiles](Projectile) at the start of the physics step for sweeping their motion.
+#[allow(clippy::type_complexity)]
+fn store_projectile_start_positions(
+    projectiles: Query<
+        (Entity, &Projectile, &Position),
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
+    >,
+    mut start_positions: ResMut<ProjectileStartPositions>,
+) {
+    start_positions.0.clear();
+    for (entity, projectile, pos) in &projectiles {
+        if projectile.swept {
+            start_positions.0.insert(entity, pos.0);
+        }
+    }
+}
+
+/// Sweeps the motion of [projectiles](Projectile) during the physics step and moves them back
+/// to the first point of impact.
+#[allow(clippy::type_complexity)]
+fn sweep_projectiles(
+    mut projectiles: Query<(
+        Entity,
+        &Projectile,
+        &RigidBody,
+        &mut Position,
+        &Rotation,
+        &mut LinearVelocity,
+        Option<&Restitution>,
+        Option<&Collider>,
+    )>,
+    colliders: Query<(
+        &Rotation,
+        Option<&ColliderParent>,
+        Option<&Restitution>,
+        Has<Sensor>,
+    )>,
+    restitutions: Query<&Restitution, With<RigidBody>>,
+    start_positions: Res<ProjectileStartPositions>,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+) {
+    let Some(pipeline) = pipeline.filter(|_| !start_positions.0.is_empty()) else {
+        return;
+    };
+
+    for (entity, projectile, rb, mut pos, rot, mut lin_vel, restitution, collider) in
+        &mut projectiles
+    {
+        let Some(start) = start_positions.0.get(&entity).copied() else {
+            continue;
+        };
+
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let motion = pos.0 - start;
+        let distance = motion.length();
+        let thickness = collider.map_or(0.0, |collider| collider.shape_scaled().ccd_thickness());
+
+        if distance <= thickness * projectile.sweep_threshold {
+            continue;
+        }
+
+        let Ok(direction) = Dir::new(motion.f32()) else {
+            continue;
+        };
+        let direction_vector = direction.adjust_precision();
+
+        // Sensors and the colliders of the projectile itself can't stop it
+        let can_hit = |hit_entity: Entity| {
+            colliders
+                .get(hit_entity)
+                .is_ok_and(|(_, parent, _, is_sensor)| {
+                    !is_sensor && parent.map_or(hit_entity, |p| p.get()) != entity
+                })
+        };
+
+        // Find the first surface that the projectile moves into
+        let hit = if let Some(collider) = collider {
+            #[cfg(feature = "2d")]
+            let shape_rotation = rot.as_radians();
+            #[cfg(feature = "3d")]
+            let shape_rotation = rot.0;
+
+            pipeline
+                .shape_hits_sorted(
+                    collider,
+                    start,
+                    shape_rotation,
+                    direction,
+                    distance,
+                    true,
+                    projectile.query_filter.clone(),
+                )
+                .filter(|hit| can_hit(hit.entity))
+                .find_map(|hit| {
+                    let (hit_rot, ..) = colliders.get(hit.entity).ok()?;
+                    let normal = hit_rot.rotate(hit.normal1).normalize_or_zero();
+                    (normal.dot(direction_vector) < 0.0).then_some((
+                        hit.entity,
+                        hit.time_of_impact,
+                        normal,
+                    ))
+                })
+        } else {
+            pipeline
+                .cast_ray_predicate(
+                    start,
+                    direction,
+                    distance,
+                    true,
+                    projectile.query_filter.clone(),
+                    &can_hit,
+                )
+                .map(|hit| (hit.entity, hit.time_of_impact, hit.normal))
+        };
+
+        let Some((hit_entity, time_of_impact, normal)) = hit else {
+            continue;
+        };
+
+        pos.0 = start + direction_vector * time_of_impact;
+
+        // Reflect the velocity into the surface using the combined restitution
+        let normal_speed = lin_vel.dot(normal);
+        if normal_speed < 0.0 {
+            let hit_restitution = colliders
+                .get(hit_entity)
+                .ok()
+                .and_then(|(_, parent, hit_restitution, _)| {
+                    hit_restitution.or_else(|| parent.and_then(|p| restitutions.get(p.get()).ok()))
+                })
+                .copied()
+                .unwrap_or_default();
+            let restitution = restitution
+                .copied()
+                .unwrap_or_default()
+                .combine(hit_restitution)
+                .coefficient;
+            lin_vel.0 -= (1.0 + restitution) * normal_speed * normal;
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! Applies buoyancy and fluid drag to bodies submerged in fluid volumes.
+//!
+//! See [`BuoyancyPlugin`] and [`FluidVolume`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use parry::math::Point;
+
+/// Applies buoyant forces and fluid drag to dynamic [rigid bodies](RigidBody) whose [colliders](Collider)
+/// are submerged in a [`FluidVolume`].
+///
+/// The submerged part of each collider is estimated by sampling points inside the collider on a grid,
+/// with the resolution given by the [`BuoyancyConfig`]. The buoyant force is proportional to the submerged volume
+/// and the [density](FluidVolume::density) of the fluid, and it is applied at the centroid of the submerged samples,
+/// so partially submerged bodies like boats are also rotated upright.
+///
+/// The forces are computed once per physics step, before [`PhysicsStepSet::Substeps`], using the global [`Gravity`].
+/// Sensors and colliders that are fluid volumes themselves are not affected.
+pub struct BuoyancyPlugin;
+
+impl Plugin for BuoyancyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BuoyancyConfig>()
+            .register_type::<BuoyancyConfig>()
+            .register_type::<FluidVolume>()
+            .register_type::<FluidBoundary>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                apply_buoyancy
+                    .after(PhysicsStepSet::BroadPhase)
+                    .after(crate::plugins::integrator::track_kinematic_targets)
+                    .before(crate::plugins::integrator::apply_impulses),
+            );
+    }
+}
+
+/// Configures the [`BuoyancyPlugin`].
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct BuoyancyConfig {
+    /// The number of sample points along each axis of the bounding box of a collider
+    /// used for estimating its submerged volume. Higher values are more accurate, but slower.
+    ///
+    /// Defaults to `8` in 2D and `4` in 3D.
+    pub samples_per_axis: u32,
+}
+
+impl Default for BuoyancyConfig {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "2d")]
+            samples_per_axis: 8,
+            #[cfg(feature = "3d")]
+            samples_per_axis: 4,
+        }
+    }
+}
+
+/// A volume of fluid, like water, that makes submerged bodies float and slows them down.
+///
+/// The fluid is either the space below a [surface plane](FluidBoundary::SurfacePlane), which is useful for
+/// oceans and lakes, or the [collider](FluidBoundary::Collider) of the entity, which should typically be a [`Sensor`].
+///
+/// Requires the [`BuoyancyPlugin`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // An ocean below the plane at the origin
+#[cfg_attr(
+    feature = "2d",
+    doc = "     commands.spawn(FluidVolume::surface_plane(Vec2::ZERO, Vec2::Y).with_density(1000.0));"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "     commands.spawn(FluidVolume::surface_plane(Vec3::ZERO, Vec3::Y).with_density(1000.0));"
+)]
+///
+///     // A river that carries bodies along with its flow
+///     commands.spawn((
+#[cfg_attr(feature = "2d", doc = "         Collider::rectangle(20.0, 2.0),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::cuboid(20.0, 2.0, 4.0),")]
+///         Sensor,
+#[cfg_attr(
+    feature = "2d",
+    doc = "         FluidVolume::collider().with_flow_velocity(Vec2::X * 2.0),"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "         FluidVolume::collider().with_flow_velocity(Vec3::X * 2.0),"
+)]
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct FluidVolume {
+    /// The region that is filled by the fluid.
+    pub boundary: FluidBoundary,
+    /// The density of the fluid. Bodies with a lower density than the fluid float, while denser bodies sink.
+    /// Defaults to `1000.0`, the density of water in kg/m³.
+    pub density: Scalar,
+    /// The velocity of the fluid. Submerged bodies are dragged along with the flow.
+    pub flow_velocity: Vector,
+    /// The rate at which the velocity of a fully submerged body relative to the fluid is reduced, per second.
+    pub linear_drag: Scalar,
+    /// The rate at which the angular velocity of a fully submerged body is reduced, per second.
+    pub angular_drag: Scalar,
+}
+
+/// The region that is filled by a [`FluidVolume`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum FluidBoundary {
+    /// The fluid fills the space below a plane in world space.
+    SurfacePlane {
+        /// A point on the surface of the fluid.
+        point: Vector,
+        /// The normal of the surface, pointing out of the fluid.
+        normal: Vector,
+    },
+    /// The fluid fills the [collider](Collider) of the entity.
+    Collider,
+}
+
+impl FluidVolume {
+    /// Creates a fluid volume that fills the space below the plane defined by the given `point`
+    /// and `normal`, which points out of the fluid.
+    pub fn surface_plane(point: Vector, normal: Vector) -> Self {
+        Self::new(FluidBoundary::SurfacePlane {
+            point,
+            normal: normal.normalize_or_zero(),
+        })
+    }
+
+    /// Creates a fluid volume that fills the [collider](Collider) of the entity.
+    pub fn collider() -> Self {
+        Self::new(FluidBoundary::Collider)
+    }
+
+    fn new(boundary: FluidBoundary) -> Self {
+        Self {
+            boundary,
+            density: 1000.0,
+            flow_velocity: Vector::ZERO,
+            linear_drag: 1.0,
+            angular_drag: 1.0,
+        }
+    }
+
+    /// Sets the density of the fluid.
+    pub fn with_density(mut self, density: Scalar) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the velocity of the fluid.
+    pub fn with_flow_velocity(mut self, flow_velocity: Vector) -> Self {
+        self.flow_velocity = flow_velocity;
+        self
+    }
+
+    /// Sets the linear and angular drag of the fluid.
+    pub fn with_drag(mut self, linear_drag: Scalar, angular_drag: Scalar) -> Self {
+        self.linear_drag = linear_drag;
+        self.angular_drag = angular_drag;
+        self
+    }
+}
+
+type BuoyancyBodyComponents = (
+    &'static RigidBody,
+    &'static Position,
+    &'static Rotation,
+    &'static mut LinearVelocity,
+    &'static mut AngularVelocity,
+    &'static CenterOfMass,
+    &'static InverseMass,
+    &'static InverseInertia,
+    Option<&'static LockedAxes>,
+);
+
+/// Applies buoyant forces and drag to bodies with colliders that are submerged in [fluid volumes](FluidVolume).
+#[allow(clippy::type_complexity)]
+pub(crate) fn apply_buoyancy(
+    fluids: Query<(
+        Entity,
+        &FluidVolume,
+        Option<(&Collider, &Position, &Rotation, &ColliderAabb)>,
+    )>,
+    colliders: Query<
+        (
+            Entity,
+            &Collider,
+            &Position,
+            &Rotation,
+            &ColliderAabb,
+            Option<&ColliderParent>,
+        ),
+        (Without<Sensor>, Without<FluidVolume>),
+    >,
+    mut bodies: Query<BuoyancyBodyComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    config: Res<BuoyancyConfig>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    if fluids.is_empty() {
+        return;
+    }
+
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (fluid_entity, fluid, fluid_collider) in &fluids {
+        if fluid.boundary == FluidBoundary::Collider && fluid_collider.is_none() {
+            continue;
+        }
+
+        let contains = |point: Vector| match fluid.boundary {
+            FluidBoundary::SurfacePlane {
+                point: surface_point,
+                normal,
+            } => (point - surface_point).dot(normal) <= 0.0,
+            FluidBoundary::Collider => fluid_collider
+                .is_some_and(|(collider, pos, rot, _)| collider.contains_point(*pos, *rot, point)),
+        };
+
+        for (entity, collider, collider_pos, collider_rot, aabb, collider_parent) in &colliders {
+            // Skip colliders that are clearly outside of the fluid
+            let may_be_submerged = match fluid.boundary {
+                FluidBoundary::SurfacePlane { point, normal } => {
+                    let center = (aabb.min + aabb.max) / 2.0;
+                    let half_size = (aabb.max - aabb.min) / 2.0;
+                    (center - point).dot(normal) - half_size.dot(normal.abs()) <= 0.0
+                }
+                FluidBoundary::Collider => {
+                    fluid_collider.is_some_and(|(.., fluid_aabb)| aabb.intersects(fluid_aabb))
+                }
+            };
+            let body_entity = collider_parent.map_or(entity, |parent| parent.get());
+            if !may_be_submerged || body_entity == fluid_entity {
+                continue;
+            }
+
+            let Ok((
+                rb,
+                body_pos,
+                body_rot,
+                mut lin_vel,
+                mut ang_vel,
+                center_of_mass,
+                inv_mass,
+                inv_inertia,
+                locked_axes,
+            )) = bodies.get_mut(body_entity)
+            else {
+                continue;
+            };
+
+            if !rb.is_dynamic() {
+                continue;
+            }
+
+            let Some((submerged_fraction, centroid)) = submerged_part(
+                collider,
+                collider_pos.0,
+                collider_rot,
+                config.samples_per_axis,
+                contains,
+            ) else {
+                continue;
+            };
+            let volume = collider.shape_scaled().mass_properties(1.0).mass();
+
+            let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+            let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
+            #[cfg(feature = "2d")]
+            let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.0);
+            #[cfg(feature = "3d")]
+            let effective_inv_inertia =
+                locked_axes.apply_to_rotation(inv_inertia.rotated(body_rot).0);
+
+            // Apply the buoyant force at the centroid of the submerged part
+            let buoyant_force = -gravity.0 * fluid.density * volume * submerged_fraction;
+            let offset = centroid - (body_pos.0 + body_rot.rotate(center_of_mass.0));
+            #[cfg(feature = "2d")]
+            let buoyant_torque = offset.perp_dot(buoyant_force);
+            #[cfg(feature = "3d")]
+            let buoyant_torque = offset.cross(buoyant_force);
+
+            lin_vel.0 += delta_secs * buoyant_force * effective_inv_mass;
+            ang_vel.0 += delta_secs * effective_inv_inertia * buoyant_torque;
+
+            // Apply drag relative to the flow of the fluid at the centroid of the submerged part
+            #[cfg(feature = "2d")]
+            let point_velocity = lin_vel.0 + ang_vel.0 * offset.perp();
+            #[cfg(feature = "3d")]
+            let point_velocity = lin_vel.0 + ang_vel.0.cross(offset);
+            let relative_velocity = point_velocity - fluid.flow_velocity;
+            let linear_drag = (fluid.linear_drag * submerged_fraction * delta_secs).min(1.0);
+            lin_vel.0 -= locked_axes.apply_to_vec(relative_velocity * linear_drag);
+            ang_vel.0 *= 1.0 / (1.0 + fluid.angular_drag * submerged_fraction * delta_secs);
+        }
+    }
+}
+
+/// Estimates the fraction of the given collider that is inside of a fluid, and the centroid of the submerged part
+/// in world space, by sampling points inside of the collider on a grid.
+///
+/// Returns `None` if no part of the collider is submerged.
+fn submerged_part(
+    collider: &Collider,
+    position: Vector,
+    rotation: &Rotation,
+    samples_per_axis: u32,
+    contains: impl Fn(Vector) -> bool,
+) -> Option<(Scalar, Vector)> {
+    let shape = collider.shape_scaled();
+    let aabb = shape.compute_local_aabb();
+    let (mins, extents) = (Vector::from(aabb.mins), Vector::from(aabb.extents()));
+    let samples_per_axis = samples_per_axis.max(1);
+    let cell_size = extents / samples_per_axis as Scalar;
+
+    let mut sample_count = 0;
+    let mut submerged_count = 0;
+    let mut submerged_sum = Vector::ZERO;
+
+    #[cfg(feature = "2d")]
+    let cells = (0..samples_per_axis)
+        .flat_map(|x| (0..samples_per_axis).map(move |y| Vector::new(x as Scalar, y as Scalar)));
+    #[cfg(feature = "3d")]
+    let cells = (0..samples_per_axis).flat_map(|x| {
+        (0..samples_per_axis).flat_map(move |y| {
+            (0..samples_per_axis).map(move |z| Vector::new(x as Scalar, y as Scalar, z as Scalar))
+        })
+    });
+
+    for cell in cells {
+        let local_point = mins + (cell + 0.5) * cell_size;
+        if !shape.contains_local_point(&Point::from(local_point)) {
+            continue;
+        }
+        sample_count += 1;
+
+        let point = position + rotation.rotate(local_point);
+        if contains(point) {
+            submerged_count += 1;
+            submerged_sum += point;
+        }
+    }
+
+    (submerged_count > 0).then(|| {
+        (
+            submerged_count as Scalar / sample_count as Scalar,
+            submerged_sum / submerged_count as Scalar,
+        )
+    })
+}
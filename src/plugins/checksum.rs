@@ -0,0 +1,281 @@
+//! Computes a [`PhysicsChecksum`] of the simulation state after each physics step for detecting desyncs.
+//!
+//! See [`PhysicsChecksumPlugin`].
+
+use std::{fmt, hash::Hasher, time::Duration};
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use fxhash::FxHasher64;
+
+/// Computes a [`PhysicsChecksum`] of the positions, rotations and velocities of rigid bodies after each physics step.
+///
+/// The checksum is only computed if the [`PhysicsChecksum`] resource exists, so this plugin has no cost
+/// unless the resource is inserted.
+///
+/// The checksum system runs after [`PhysicsStepSet::Sleeping`] and before [`PhysicsStepSet::SpatialQuery`].
+pub struct PhysicsChecksumPlugin;
+
+impl Plugin for PhysicsChecksumPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PhysicsChecksum>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                update_checksum
+                    .after(PhysicsStepSet::Sleeping)
+                    .before(PhysicsStepSet::SpatialQuery)
+                    .run_if(resource_exists::<PhysicsChecksum>),
+            );
+    }
+}
+
+/// A resource containing a hash of the state of the physics simulation, updated after each physics step
+/// by the [`PhysicsChecksumPlugin`] if the resource exists.
+///
+/// This can be used by networked games to cheaply detect desyncs between peers by comparing checksums
+/// instead of serializing the full state every frame.
+///
+/// The checksum contains the [`Entity`], [`Position`], [`Rotation`], [`LinearVelocity`] and [`AngularVelocity`]
+/// of each [rigid body](RigidBody). By default, the exact bits of the values are hashed, so even a difference
+/// of a single ulp changes the checksum. A [`quantization`](Self::quantization) step can be used to make the checksum
+/// robust against tiny differences that don't affect the simulation in a meaningful way.
+///
+/// The bodies are hashed in the order of their entities, so the checksum is stable regardless of query iteration order,
+/// but peers must use the same entities for the same bodies, for example by spawning them in the same order.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsExtrasPlugins::default(),
+///         ))
+///         // Enable checksum computation
+///         .insert_resource(PhysicsChecksum::default())
+///         .add_systems(PostUpdate, send_checksum.after(PhysicsSet::StepSimulation))
+///         .run();
+/// }
+///
+/// fn send_checksum(checksum: Res<PhysicsChecksum>) {
+///     if checksum.is_changed() {
+///         println!("Step {}: {:x}", checksum.step, checksum.value);
+///     }
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct PhysicsChecksum {
+    /// The checksum computed after the latest physics step.
+    pub value: u64,
+    /// The number of physics steps for which the checksum has been computed.
+    pub step: u64,
+    /// The quantization step used for values before hashing. Values that round to the same multiple
+    /// of this step produce the same checksum. If `None`, the exact bits of the values are hashed.
+    ///
+    /// Defaults to `None`.
+    pub quantization: Option<Scalar>,
+}
+
+impl Default for PhysicsChecksum {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl PhysicsChecksum {
+    /// Creates a new [`PhysicsChecksum`] with the given quantization step.
+    /// If the step is `None`, the exact bits of the values are hashed.
+    pub const fn new(quantization: Option<Scalar>) -> Self {
+        Self {
+            value: 0,
+            step: 0,
+            quantization,
+        }
+    }
+
+    /// Computes the hash of the state of a single rigid body using the given quantization step.
+    /// If the step is `None`, the exact bits of the values are hashed.
+    pub fn hash_body(
+        position: &Position,
+        rotation: &Rotation,
+        linear_velocity: &LinearVelocity,
+        angular_velocity: &AngularVelocity,
+        quantization: Option<Scalar>,
+    ) -> u64 {
+        let mut hasher = FxHasher64::default();
+        let mut write = |value: Scalar| match quantization {
+            Some(quantization) => hasher.write_i64((value / quantization).round() as i64),
+            None => hasher.write_u64(u64::from(value.to_bits())),
+        };
+
+        position.to_array().into_iter().for_each(&mut write);
+        linear_velocity.to_array().into_iter().for_each(&mut write);
+
+        #[cfg(feature = "2d")]
+        {
+            write(rotation.cos());
+            write(rotation.sin());
+            write(angular_velocity.0);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation.to_array().into_iter().for_each(&mut write);
+            angular_velocity.to_array().into_iter().for_each(&mut write);
+        }
+
+        hasher.finish()
+    }
+}
+
+fn update_checksum(
+    bodies: Query<
+        (
+            Entity,
+            &Position,
+            &Rotation,
+            &LinearVelocity,
+            &AngularVelocity,
+        ),
+        With<RigidBody>,
+    >,
+    mut checksum: ResMut<PhysicsChecksum>,
+    mut body_hashes: Local<Vec<(Entity, u64)>>,
+) {
+    let quantization = checksum.quantization;
+
+    body_hashes.clear();
+    body_hashes.extend(bodies.iter().map(
+        |(entity, position, rotation, linear_velocity, angular_velocity)| {
+            let hash = PhysicsChecksum::hash_body(
+                position,
+                rotation,
+                linear_velocity,
+                angular_velocity,
+                quantization,
+            );
+            (entity, hash)
+        },
+    ));
+
+    // Hash the bodies in the order of their entities so that the result doesn't depend on the iteration order.
+    body_hashes.sort_unstable_by_key(|(entity, _)| *entity);
+
+    let mut hasher = FxHasher64::default();
+    for (entity, hash) in body_hashes.iter() {
+        hasher.write_u64(entity.to_bits());
+        hasher.write_u64(*hash);
+    }
+
+    checksum.value = hasher.finish();
+    checksum.step += 1;
+}
+
+/// An error returned by [`check_determinism`] when two runs of a simulation produced different results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeterminismError {
+    /// The index of the run that diverged from the first run.
+    pub run: usize,
+    /// The physics step at which the run diverged.
+    pub step: usize,
+    /// The [`PhysicsChecksum`] of the first run at the step.
+    pub expected: u64,
+    /// The [`PhysicsChecksum`] of the diverging run at the step.
+    pub found: u64,
+}
+
+impl fmt::Display for DeterminismError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "run {} diverged at step {}: expected checksum {:x}, found {:x}",
+            self.run, self.step, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for DeterminismError {}
+
+/// A test harness that checks if a simulation is deterministic across runs.
+///
+/// The app returned by `create_app` is created `runs` times, and each one is stepped `steps` times
+/// with a fixed `delta` time using [`step_physics`](crate::plugins::headless::PhysicsAppExt::step_physics).
+/// The [`PhysicsChecksum`] is compared after each step, and the first step where a run diverges
+/// from the first run is returned as an error.
+///
+/// The app should contain the [`PhysicsPlugins`] and [`PhysicsExtrasPlugins`] and set up the scene,
+/// for example in a `Startup` system.
+///
+/// Returns the final checksum if all runs produced identical results.
+///
+/// ## Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::{plugins::checksum::check_determinism, prelude::*};
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::{plugins::checksum::check_determinism, prelude::*};
+///
+/// fn create_app() -> App {
+///     let mut app = App::new();
+///     app.add_plugins((
+///         MinimalPlugins,
+///         PhysicsPlugins::default(),
+///         PhysicsExtrasPlugins::default(),
+///     ));
+///     app.add_systems(Startup, |mut commands: Commands| {
+///         commands.spawn((RigidBody::Dynamic, LinearVelocity::default()));
+///     });
+///     app
+/// }
+///
+/// let result = check_determinism(create_app, 3, 60, Duration::from_secs_f64(1.0 / 60.0));
+/// assert!(result.is_ok());
+/// ```
+pub fn check_determinism(
+    create_app: impl Fn() -> App,
+    runs: usize,
+    steps: usize,
+    delta: Duration,
+) -> Result<u64, DeterminismError> {
+    let mut expected = Vec::with_capacity(steps);
+    let mut checksum = 0;
+
+    for run in 0..runs {
+        let mut app = create_app();
+        app.insert_resource(PhysicsChecksum::default());
+
+        for step in 0..steps {
+            app.step_physics(delta);
+
+            checksum = app.world.resource::<PhysicsChecksum>().value;
+
+            if run == 0 {
+                expected.push(checksum);
+            } else if expected[step] != checksum {
+                return Err(DeterminismError {
+                    run,
+                    step,
+                    expected: expected[step],
+                    found: checksum,
+                });
+            }
+        }
+    }
+
+    Ok(checksum)
+}
@@ -0,0 +1,672 @@
+//! Simulates cloth as grids of particles.
+//!
+//! See [`ClothPlugin`] and [`Cloth`].
+
+#[cfg(feature = "cloth-mesh")]
+use crate::plugins::deformable_mesh::write_deformable_mesh;
+use crate::{
+    plugins::particles::{
+        collide_particles, deform_particle_distance, integrate_particles, solve_attachment,
+        solve_particle_distance, update_particle_velocities, ParticleBodies, ParticleContacts,
+    },
+    prelude::*,
+};
+#[cfg(feature = "cloth-mesh")]
+use bevy::render::{
+    mesh::{Indices, PrimitiveTopology},
+    render_asset::RenderAssetUsages,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::intern::Interned,
+};
+
+/// Simulates [cloth](Cloth) as grids of particles connected by stretch, shear and bending constraints.
+///
+/// The cloth is simulated in the [`SubstepSchedule`] in the same way as [ropes](Rope):
+///
+/// 1. The particles are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The [constraints](ClothConstraint) and [attachments](ParticleAttachment) are solved after the [joints]
+/// and ropes in [`ParticleSet::Cloth`].
+/// 3. The particles are pushed out of [colliders](Collider) with friction in [`ParticleSet::Cloth`].
+/// 4. The velocities of the particles are updated from their change in position in [`SubstepSet::UpdateVelocities`].
+///
+/// With the `deformable-mesh` feature, the particle positions and normals of the cloth are written to
+/// the `Mesh` of the cloth entity by the [`DeformableMeshPlugin`]. With the `cloth-mesh` feature,
+/// the mesh can be created with [`Cloth::mesh`].
+pub struct ClothPlugin {
+    #[cfg_attr(not(feature = "deformable-mesh"), allow(dead_code))]
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl ClothPlugin {
+    /// Creates a [`ClothPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for ClothPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for ClothPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Cloth>()
+            .register_type::<ClothConstraint>()
+            .register_type::<ClothConstraintKind>()
+            .register_type::<Particle>()
+            .register_type::<ParticleAttachment>()
+            .add_event::<ClothTorn>();
+
+        #[cfg(feature = "deformable-mesh")]
+        app.add_plugins(DeformableMeshPlugin::<Cloth>::new(self.schedule));
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_cloth.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(
+            (solve_cloth_constraints, collide_cloth)
+                .chain()
+                .in_set(ParticleSet::Cloth),
+        );
+
+        substeps.add_systems(update_cloth_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// A piece of cloth simulated as a grid of [particles](Particle) by the [`ClothPlugin`].
+///
+/// The particles are stored row by row, and each particle is connected to its neighbors by
+/// [constraints](ClothConstraint) that resist stretching, shearing and bending. Particles can be
+/// [pinned](Cloth::with_pinned) in place or [attached](ParticleAttachment) to [rigid bodies](RigidBody),
+/// which makes it possible to build flags, capes and tents.
+///
+/// The particles are simulated in world space, so the `Transform` of the cloth entity has no effect on the simulation.
+/// The particles collide with [colliders](Collider) whose [layers](CollisionLayers) interact with the
+/// [`layers`](Self::layers) of the cloth, and dynamic [rigid bodies](RigidBody) are pushed back by the particles,
+/// so a crate can rest on a hammock or a trampoline. Cloth doesn't collide with itself,
+/// and colliders of attached bodies are ignored.
+///
+/// ## Rendering
+///
+/// With the `cloth-mesh` feature, [`Cloth::mesh`] creates a `Mesh` for the cloth. When the handle of the mesh
+/// is added to the cloth entity, the vertices and normals of the mesh are updated to match the particles
+/// every frame by the [`DeformableMeshPlugin`]. The vertices are written relative to the `GlobalTransform` of the entity.
+///
+/// ## Tearing and cutting
+///
+/// When a [tear strain](Self::tear_strain) is set, stretch and shear constraints that are stretched too far
+/// are removed along with the [triangles](Self::triangles) containing them, and a [`ClothTorn`] event is sent.
+/// Cloth can also be cut at runtime with [`Cloth::cut`], which can be used for destructible sails and nets.
+/// The indices of the render mesh are updated to match the remaining triangles.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let pole = commands
+///         .spawn((
+///             RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "             Collider::rectangle(0.1, 4.0),")]
+#[cfg_attr(feature = "3d", doc = "             Collider::cylinder(4.0, 0.05),")]
+///         ))
+///         .id();
+///
+///     // A flag with 20x10 particles attached to the pole at the two corners next to it
+///     let cloth = Cloth::new(Vector::Y * 2.0, Vector::X * 2.0, Vector::NEG_Y, 20, 10, 0.5);
+///     let top = cloth.index(0, 0);
+///     let bottom = cloth.index(0, 9);
+///     commands.spawn(
+///         cloth
+///             .with_attachment(top, ParticleAttachment::new(pole).with_local_anchor(Vector::Y * 2.0))
+///             .with_attachment(bottom, ParticleAttachment::new(pole).with_local_anchor(Vector::Y)),
+///     );
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Cloth {
+    /// The particles of the cloth, stored row by row.
+    pub particles: Vec<Particle>,
+    /// The number of particles in each row.
+    pub columns: usize,
+    /// The number of rows.
+    pub rows: usize,
+    /// The distance constraints between the particles.
+    pub constraints: Vec<ClothConstraint>,
+    /// The triangles covering the cloth, given by the indices of their particles.
+    /// Triangles are removed when the cloth is [torn](Self::tear).
+    pub triangles: Vec<[usize; 3]>,
+    /// The compliance of the [stretch](ClothConstraintKind::Stretch) constraints, the inverse of stiffness.
+    /// Has the unit meters / Newton.
+    pub stretch_compliance: Scalar,
+    /// The compliance of the [shear](ClothConstraintKind::Shear) constraints, the inverse of stiffness.
+    /// Has the unit meters / Newton.
+    pub shear_compliance: Scalar,
+    /// The compliance of the [bending](ClothConstraintKind::Bend) constraints, the inverse of stiffness.
+    /// Has the unit meters / Newton.
+    pub bending_compliance: Scalar,
+    /// The radius of the particles used for collisions.
+    pub radius: Scalar,
+    /// The friction of the particles against colliders, between `0.0` and `1.0`.
+    pub friction: Scalar,
+    /// The linear damping of the particles.
+    pub damping: Scalar,
+    /// The layers of the cloth. The particles only collide with colliders whose layers [interact](CollisionLayers::interacts_with)
+    /// with these layers.
+    pub layers: CollisionLayers,
+    /// The particles attached to bodies, given by their indices and attachments.
+    pub attachments: Vec<(usize, ParticleAttachment)>,
+    /// Plastic deformation of the [constraints](Self::constraints). When the cloth is stretched or folded
+    /// beyond the yield strain, the rest lengths of the constraints change permanently.
+    ///
+    /// With plasticity, bending constraints also resist straightening, so creases are kept.
+    pub plasticity: Option<Plasticity>,
+    /// The strain at which the [stretch](ClothConstraintKind::Stretch) and [shear](ClothConstraintKind::Shear)
+    /// constraints tear, as a fraction of their rest length. A [`ClothTorn`] event is sent for each torn constraint.
+    ///
+    /// The default is `None`, which means that the cloth never tears.
+    pub tear_strain: Option<Scalar>,
+}
+
+/// An event that is sent when a constraint of a [`Cloth`] tears because its strain has exceeded
+/// the [tear strain](Cloth::tear_strain) of the cloth.
+///
+/// Cutting the cloth with [`Cloth::cut`] doesn't send events.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClothTorn {
+    /// The cloth entity.
+    pub cloth: Entity,
+    /// The indices of the particles that were connected by the torn constraint.
+    pub particles: [usize; 2],
+}
+
+/// A distance constraint between two particles of a [`Cloth`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClothConstraint {
+    /// The indices of the constrained particles.
+    pub particles: [usize; 2],
+    /// The distance that the particles are kept at.
+    pub rest_length: Scalar,
+    /// The kind of the constraint, which determines its compliance.
+    pub kind: ClothConstraintKind,
+}
+
+/// The kind of a [`ClothConstraint`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClothConstraintKind {
+    /// Connects adjacent particles in a row or column.
+    Stretch,
+    /// Connects diagonally adjacent particles.
+    Shear,
+    /// Connects particles two apart in a row or column. Bending constraints only push the particles apart,
+    /// unless the cloth has [plasticity](Cloth::plasticity).
+    Bend,
+}
+
+impl Cloth {
+    /// Creates a flat cloth with the given number of `columns` and `rows` of particles.
+    ///
+    /// The first particle is placed at `corner`, and the rows and columns span the `width` and `height` vectors.
+    /// The total `mass` of the cloth is divided evenly between the particles.
+    pub fn new(
+        corner: Vector,
+        width: Vector,
+        height: Vector,
+        columns: usize,
+        rows: usize,
+        mass: Scalar,
+    ) -> Self {
+        let columns = columns.max(2);
+        let rows = rows.max(2);
+        let particle_mass = mass / (columns * rows) as Scalar;
+
+        let mut particles = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let u = column as Scalar / (columns - 1) as Scalar;
+                let v = row as Scalar / (rows - 1) as Scalar;
+                particles.push(Particle::new(
+                    corner + width * u + height * v,
+                    particle_mass,
+                ));
+            }
+        }
+
+        let index = |column: usize, row: usize| row * columns + column;
+        let mut constraints = vec![];
+        let mut add_constraint = |i1: usize, i2: usize, kind: ClothConstraintKind| {
+            constraints.push(ClothConstraint {
+                particles: [i1, i2],
+                rest_length: particles[i1].position.distance(particles[i2].position),
+                kind,
+            });
+        };
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let i = index(column, row);
+                if column + 1 < columns {
+                    add_constraint(i, index(column + 1, row), ClothConstraintKind::Stretch);
+                }
+                if row + 1 < rows {
+                    add_constraint(i, index(column, row + 1), ClothConstraintKind::Stretch);
+                }
+                if column + 1 < columns && row + 1 < rows {
+                    add_constraint(i, index(column + 1, row + 1), ClothConstraintKind::Shear);
+                    add_constraint(
+                        index(column + 1, row),
+                        index(column, row + 1),
+                        ClothConstraintKind::Shear,
+                    );
+                }
+                if column + 2 < columns {
+                    add_constraint(i, index(column + 2, row), ClothConstraintKind::Bend);
+                }
+                if row + 2 < rows {
+                    add_constraint(i, index(column, row + 2), ClothConstraintKind::Bend);
+                }
+            }
+        }
+
+        let mut triangles = Vec::with_capacity(2 * (columns - 1) * (rows - 1));
+        for row in 0..rows - 1 {
+            for column in 0..columns - 1 {
+                let i = index(column, row);
+                let right = index(column + 1, row);
+                let below = index(column, row + 1);
+                let diagonal = index(column + 1, row + 1);
+                triangles.push([i, below, right]);
+                triangles.push([right, below, diagonal]);
+            }
+        }
+
+        Self {
+            particles,
+            columns,
+            rows,
+            constraints,
+            triangles,
+            stretch_compliance: 0.0,
+            shear_compliance: 0.0001,
+            bending_compliance: 0.1,
+            radius: 0.05,
+            friction: 0.3,
+            damping: 0.0,
+            layers: CollisionLayers::default(),
+            attachments: vec![],
+            plasticity: None,
+            tear_strain: None,
+        }
+    }
+
+    /// Returns the index of the particle at the given `column` and `row`.
+    pub fn index(&self, column: usize, row: usize) -> usize {
+        row * self.columns + column
+    }
+
+    /// Pins the particle at the given index in place.
+    pub fn with_pinned(mut self, index: usize) -> Self {
+        if let Some(particle) = self.particles.get_mut(index) {
+            particle.inverse_mass = 0.0;
+        }
+        self
+    }
+
+    /// Attaches the particle at the given index to a body.
+    pub fn with_attachment(mut self, index: usize, attachment: ParticleAttachment) -> Self {
+        self.attachments.push((index, attachment));
+        self
+    }
+
+    /// Sets the compliance of the stretch constraints, the inverse of stretch stiffness.
+    pub fn with_stretch_compliance(mut self, compliance: Scalar) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the shear constraints, the inverse of shear stiffness.
+    pub fn with_shear_compliance(mut self, compliance: Scalar) -> Self {
+        self.shear_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the bending constraints, the inverse of bending stiffness.
+    pub fn with_bending_compliance(mut self, compliance: Scalar) -> Self {
+        self.bending_compliance = compliance;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the friction of the particles against colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the layers of the cloth used for collisions.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Lets the cloth deform permanently when it is stretched or folded beyond the yield strain
+    /// of the given [`Plasticity`].
+    pub fn with_plasticity(mut self, plasticity: Plasticity) -> Self {
+        self.plasticity = Some(plasticity);
+        self
+    }
+
+    /// Lets the stretch and shear constraints of the cloth tear when their strain exceeds the given `tear_strain`,
+    /// as a fraction of their rest length.
+    pub fn with_tear_strain(mut self, tear_strain: Scalar) -> Self {
+        self.tear_strain = Some(tear_strain);
+        self
+    }
+
+    /// Removes the [stretch](ClothConstraintKind::Stretch) or [shear](ClothConstraintKind::Shear) constraint
+    /// between the given particles, along with the triangles containing the edge and the bending constraints across it.
+    ///
+    /// Returns `false` if the particles are not connected.
+    pub fn tear(&mut self, particles: [usize; 2]) -> bool {
+        let [a, b] = particles;
+        let is_edge = |[i1, i2]: [usize; 2]| (i1 == a && i2 == b) || (i1 == b && i2 == a);
+
+        let Some(index) = self
+            .constraints
+            .iter()
+            .position(|c| c.kind != ClothConstraintKind::Bend && is_edge(c.particles))
+        else {
+            return false;
+        };
+        let torn = self.constraints.remove(index);
+
+        // Bending constraints pass through the particle in the middle of the two particles they connect,
+        // so the ones crossing a torn stretch constraint no longer hold anything together.
+        if torn.kind == ClothConstraintKind::Stretch {
+            self.constraints.retain(|c| {
+                let [i1, i2] = c.particles;
+                let middle = (i1 + i2) / 2;
+                c.kind != ClothConstraintKind::Bend
+                    || !(is_edge([i1, middle]) || is_edge([middle, i2]))
+            });
+        }
+
+        self.triangles
+            .retain(|triangle| !(triangle.contains(&a) && triangle.contains(&b)));
+
+        true
+    }
+
+    /// Cuts the cloth along the plane going through the given `point` with the given `normal`,
+    /// which is a line in 2D. The [stretch](ClothConstraintKind::Stretch) and [shear](ClothConstraintKind::Shear)
+    /// constraints between particles on opposite sides of the plane are [torn](Self::tear).
+    ///
+    /// Returns the pairs of particles that were disconnected.
+    pub fn cut(&mut self, point: Vector, normal: Vector) -> Vec<[usize; 2]> {
+        let side = |i: usize| (self.particles[i].position - point).dot(normal) >= 0.0;
+        let edges: Vec<[usize; 2]> = self
+            .constraints
+            .iter()
+            .filter(|c| c.kind != ClothConstraintKind::Bend)
+            .map(|c| c.particles)
+            .filter(|&[i1, i2]| side(i1) != side(i2))
+            .collect();
+
+        for &edge in edges.iter() {
+            self.tear(edge);
+        }
+        edges
+    }
+
+    /// Cuts the cloth along the line segment from `start` to `end`. The [stretch](ClothConstraintKind::Stretch)
+    /// and [shear](ClothConstraintKind::Shear) constraints crossing the segment are [torn](Self::tear).
+    ///
+    /// Returns the pairs of particles that were disconnected.
+    #[cfg(feature = "2d")]
+    pub fn cut_segment(&mut self, start: Vector, end: Vector) -> Vec<[usize; 2]> {
+        let direction = end - start;
+        let crosses = |p1: Vector, p2: Vector| {
+            let edge = p2 - p1;
+            let denominator = direction.perp_dot(edge);
+            if denominator.abs() <= Scalar::EPSILON {
+                return false;
+            }
+            let t = (p1 - start).perp_dot(edge) / denominator;
+            let u = (p1 - start).perp_dot(direction) / denominator;
+            (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)
+        };
+        let edges: Vec<[usize; 2]> = self
+            .constraints
+            .iter()
+            .filter(|c| c.kind != ClothConstraintKind::Bend)
+            .map(|c| c.particles)
+            .filter(|&[i1, i2]| crosses(self.particles[i1].position, self.particles[i2].position))
+            .collect();
+
+        for &edge in edges.iter() {
+            self.tear(edge);
+        }
+        edges
+    }
+
+    /// Returns the compliance used for constraints of the given kind.
+    pub fn compliance(&self, kind: ClothConstraintKind) -> Scalar {
+        match kind {
+            ClothConstraintKind::Stretch => self.stretch_compliance,
+            ClothConstraintKind::Shear => self.shear_compliance,
+            ClothConstraintKind::Bend => self.bending_compliance,
+        }
+    }
+
+    /// Returns an iterator over the positions of the particles, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vector> + '_ {
+        self.particles.iter().map(|particle| particle.position)
+    }
+
+    /// Returns the indices of the [triangles](Self::triangles) covering the cloth.
+    /// Initially, there are two triangles for each quad of the grid.
+    pub fn triangle_indices(&self) -> Vec<[u32; 3]> {
+        self.triangles
+            .iter()
+            .map(|triangle| triangle.map(|i| i as u32))
+            .collect()
+    }
+
+    /// Computes the normals of the particles by averaging the normals of the adjacent triangles.
+    #[cfg(feature = "3d")]
+    pub fn normals(&self) -> Vec<Vector> {
+        let mut normals = vec![Vector::ZERO; self.particles.len()];
+        for [i1, i2, i3] in self.triangle_indices() {
+            let [p1, p2, p3] = [i1, i2, i3].map(|i| self.particles[i as usize].position);
+            let normal = (p2 - p1).cross(p3 - p1);
+            for i in [i1, i2, i3] {
+                normals[i as usize] += normal;
+            }
+        }
+        normals
+            .into_iter()
+            .map(|normal| normal.normalize_or_zero())
+            .collect()
+    }
+
+    /// Creates a `Mesh` for rendering the cloth, with the vertices at the current positions of the particles.
+    ///
+    /// When the handle of the mesh is added to the cloth entity, the mesh is kept in sync with the particles.
+    #[cfg(feature = "cloth-mesh")]
+    pub fn mesh(&self) -> Mesh {
+        let uvs: Vec<[f32; 2]> = (0..self.particles.len())
+            .map(|i| {
+                let column = i % self.columns;
+                let row = i / self.columns;
+                [
+                    column as f32 / (self.columns - 1) as f32,
+                    row as f32 / (self.rows - 1) as f32,
+                ]
+            })
+            .collect();
+
+        let mut mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(
+            self.triangle_indices().into_iter().flatten().collect(),
+        ));
+        let positions: Vec<Vector> = self.positions().collect();
+        write_deformable_mesh(&mut mesh, &positions, &GlobalTransform::IDENTITY, true);
+        mesh
+    }
+}
+
+impl MapEntities for Cloth {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for (_, attachment) in self.attachments.iter_mut() {
+            attachment.entity = entity_mapper.map_entity(attachment.entity);
+        }
+    }
+}
+
+/// Moves the particles of cloth by their velocities and gravity.
+fn integrate_cloth(mut cloths: Query<&mut Cloth>, gravity: Res<Gravity>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut cloth in &mut cloths {
+        let damping = cloth.damping;
+        integrate_particles(&mut cloth.particles, gravity.0, damping, delta_secs);
+    }
+}
+
+/// Solves the stretch, shear, bending and attachment constraints of cloth,
+/// and tears the constraints that are stretched beyond the tear strain.
+fn solve_cloth_constraints(
+    mut cloths: Query<(Entity, &mut Cloth)>,
+    mut bodies: ParticleBodies,
+    mut torn_events: EventWriter<ClothTorn>,
+    time: Res<Time>,
+    mut torn: Local<Vec<[usize; 2]>>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (entity, mut cloth) in &mut cloths {
+        let cloth = &mut *cloth;
+
+        for i in 0..cloth.constraints.len() {
+            let constraint = cloth.constraints[i];
+
+            if let Some(tear_strain) = cloth.tear_strain {
+                let [i1, i2] = constraint.particles;
+                let length = cloth.particles[i1]
+                    .position
+                    .distance(cloth.particles[i2].position);
+                if constraint.kind != ClothConstraintKind::Bend
+                    && length > constraint.rest_length * (1.0 + tear_strain)
+                {
+                    torn.push(constraint.particles);
+                    continue;
+                }
+            }
+
+            if let Some(plasticity) = cloth.plasticity {
+                cloth.constraints[i].rest_length = deform_particle_distance(
+                    &cloth.particles,
+                    constraint.particles,
+                    constraint.rest_length,
+                    &plasticity,
+                    delta_secs,
+                );
+            }
+
+            let compliance = cloth.compliance(constraint.kind);
+            solve_particle_distance(
+                &mut cloth.particles,
+                constraint.particles,
+                cloth.constraints[i].rest_length,
+                constraint.kind == ClothConstraintKind::Bend && cloth.plasticity.is_none(),
+                compliance,
+                delta_secs,
+            );
+        }
+
+        for (index, attachment) in cloth.attachments.iter_mut() {
+            if let Some(particle) = cloth.particles.get_mut(*index) {
+                solve_attachment(particle, attachment, &mut bodies, delta_secs);
+            }
+        }
+
+        for particles in torn.drain(..) {
+            if cloth.tear(particles) {
+                torn_events.send(ClothTorn {
+                    cloth: entity,
+                    particles,
+                });
+            }
+        }
+    }
+}
+
+/// Pushes the particles of cloth out of colliders and applies friction.
+pub(crate) fn collide_cloth(mut cloths: Query<&mut Cloth>, mut contacts: ParticleContacts) {
+    for mut cloth in &mut cloths {
+        let cloth = &mut *cloth;
+        let attached: Vec<Entity> = cloth
+            .attachments
+            .iter()
+            .map(|(_, attachment)| attachment.entity)
+            .collect();
+
+        collide_particles(
+            &mut cloth.particles,
+            cloth.radius,
+            cloth.friction,
+            cloth.layers,
+            &attached,
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of the particles of cloth based on their change in position.
+fn update_cloth_velocities(mut cloths: Query<&mut Cloth>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut cloth in &mut cloths {
+        update_particle_velocities(&mut cloth.particles, delta_secs);
+    }
+}
@@ -5,22 +5,38 @@
 
 use crate::prelude::*;
 use bevy::{
-    ecs::entity::{EntityMapper, MapEntities},
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        query::Has,
+    },
     prelude::*,
+    utils::{HashMap, HashSet},
 };
 
 /// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
 /// [AABB](ColliderAabb) intersection checks. This speeds up narrow phase collision detection,
 /// as the number of precise collision checks required is greatly reduced.
 ///
-/// Currently, the broad phase uses the [sweep and prune](https://en.wikipedia.org/wiki/Sweep_and_prune) algorithm.
+/// Currently, the broad phase uses the [sweep and prune](https://en.wikipedia.org/wiki/Sweep_and_prune) algorithm
+/// for colliders attached to dynamic and kinematic bodies.
+///
+/// Colliders attached to [static](RigidBody::Static) bodies are stored separately in a bounding volume hierarchy
+/// that is only updated when static geometry is added, changed or removed. This way, worlds with a large amount
+/// of static geometry like tiles or level pieces don't pay a per-frame cost proportional to the number of static colliders.
+///
+/// Colliders attached to [sleeping](Sleeping) bodies are also moved into the tree until the bodies wake up,
+/// as they don't move either, and they only need to be tested against moving colliders.
+///
+/// Colliders with [`ColliderDisabled`] and colliders attached to bodies with [`RigidBodyDisabled`]
+/// are not stored in either structure, so they don't produce any collision pairs.
 ///
 /// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
 pub struct BroadPhasePlugin;
 
 impl Plugin for BroadPhasePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<AabbIntervals>();
+        app.init_resource::<AabbIntervals>()
+            .init_resource::<StaticAabbTree>();
 
         app.configure_sets(
             PhysicsSchedule,
@@ -39,11 +55,21 @@ impl Plugin for BroadPhasePlugin {
             .expect("add PhysicsSchedule first");
 
         physics_schedule.add_systems(
-            (update_aabb_intervals, add_new_aabb_intervals)
+            (
+                update_aabb_intervals,
+                add_new_aabb_intervals,
+                update_static_aabb_tree,
+            )
                 .chain()
                 .in_set(BroadPhaseSet::UpdateStructures),
         );
 
+        // The GPU broad phase collects the collision pairs itself when it is enabled.
+        #[cfg(feature = "gpu-broad-phase")]
+        let collect_collision_pairs = collect_collision_pairs.run_if(not(resource_exists::<
+            super::gpu_broad_phase::GpuBroadPhase,
+        >));
+
         physics_schedule
             .add_systems(collect_collision_pairs.in_set(BroadPhaseSet::CollectCollisions));
     }
@@ -69,18 +95,21 @@ pub enum BroadPhaseSet {
 pub struct BroadCollisionPairs(pub Vec<(Entity, Entity)>);
 
 /// True if the rigid body hasn't moved.
-type IsBodyInactive = bool;
+pub(crate) type IsBodyInactive = bool;
 
 /// Entities with [`ColliderAabb`]s sorted along an axis by their extents.
+///
+/// Colliders attached to static or sleeping bodies are not included, as they are stored in the [`StaticAabbTree`].
 #[derive(Resource, Default)]
-struct AabbIntervals(
-    Vec<(
-        Entity,
-        ColliderParent,
-        ColliderAabb,
-        CollisionLayers,
-        IsBodyInactive,
-    )>,
+pub(crate) struct AabbIntervals(pub(crate) Vec<AabbInterval>);
+
+/// The data of a collider stored in [`AabbIntervals`].
+pub(crate) type AabbInterval = (
+    Entity,
+    ColliderParent,
+    ColliderAabb,
+    CollisionLayers,
+    IsBodyInactive,
 );
 
 impl MapEntities for AabbIntervals {
@@ -91,7 +120,346 @@ impl MapEntities for AabbIntervals {
     }
 }
 
+/// A static collider stored in the [`StaticAabbTree`].
+#[derive(Clone, Copy)]
+struct StaticCollider {
+    entity: Entity,
+    parent: ColliderParent,
+    aabb: ColliderAabb,
+    layers: CollisionLayers,
+}
+
+/// A node in the [`StaticAabbTree`].
+struct StaticAabbNode {
+    aabb: ColliderAabb,
+    /// The index of the parent node, or `None` for the root node.
+    parent: Option<usize>,
+    kind: StaticAabbNodeKind,
+}
+
+enum StaticAabbNodeKind {
+    /// A node containing a single collider.
+    Leaf(StaticCollider),
+    /// A node with two child nodes.
+    Internal([usize; 2]),
+    /// A node that has been removed from the tree and can be reused.
+    Free,
+}
+
+/// A bounding volume hierarchy over the [`ColliderAabb`]s of colliders attached to static or [sleeping](Sleeping) bodies.
+///
+/// These colliders don't move, so only the leaves of colliders that are added, changed or removed are updated,
+/// for example when bodies fall asleep or wake up. The tree is only rebuilt when the type of a rigid body changes
+/// or colliders are attached to different bodies. It is queried separately from the [`AabbIntervals`] of moving colliders.
+#[derive(Resource, Default)]
+pub(crate) struct StaticAabbTree {
+    /// The nodes of the tree, including free nodes that can be reused.
+    nodes: Vec<StaticAabbNode>,
+    /// The index of the root node, or `None` if the tree is empty.
+    root: Option<usize>,
+    /// The indices of the free nodes.
+    free_nodes: Vec<usize>,
+    /// The leaf node of each collider in the tree.
+    leaves: HashMap<Entity, usize>,
+}
+
+impl StaticAabbTree {
+    /// Returns `true` if the tree contains the given collider entity.
+    #[cfg(test)]
+    pub(crate) fn contains(&self, entity: Entity) -> bool {
+        self.leaves.contains_key(&entity)
+    }
+
+    /// Returns the number of nodes allocated for the tree, including free nodes.
+    #[cfg(test)]
+    pub(crate) fn allocated_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the memory allocated by the tree in bytes.
+    pub(crate) fn memory_usage(&self) -> usize {
+        self.nodes.capacity() * std::mem::size_of::<StaticAabbNode>()
+            + self.free_nodes.capacity() * std::mem::size_of::<usize>()
+            + self.leaves.capacity() * std::mem::size_of::<(Entity, usize)>()
+    }
+
+    /// Calls the given `callback` with the AABB and depth of each node in the tree.
+    /// The root node has a depth of zero.
+    pub(crate) fn for_each_node(&self, mut callback: impl FnMut(&ColliderAabb, usize)) {
+        let Some(root) = self.root else {
+            return;
+        };
+
+        let mut stack = vec![(root, 0)];
+
+        while let Some((index, depth)) = stack.pop() {
+            let node = &self.nodes[index];
+            callback(&node.aabb, depth);
+
+            if let StaticAabbNodeKind::Internal(children) = node.kind {
+                stack.extend(children.map(|child| (child, depth + 1)));
+            }
+        }
+    }
+
+    /// Clears the tree and rebuilds it from the given static colliders.
+    fn rebuild(&mut self, colliders: impl Iterator<Item = StaticCollider>) {
+        let mut colliders: Vec<StaticCollider> = colliders.collect();
+
+        // Sort by entity to make the structure of the tree independent of query iteration order.
+        colliders.sort_by_key(|collider| collider.entity);
+
+        self.nodes.clear();
+        self.free_nodes.clear();
+        self.leaves.clear();
+        self.root = None;
+        if !colliders.is_empty() {
+            self.root = Some(self.build_node(&mut colliders, None));
+        }
+    }
+
+    /// Recursively builds the node for the given colliders by splitting them
+    /// at the median along the axis where their centers are spread out the most.
+    fn build_node(&mut self, colliders: &mut [StaticCollider], parent: Option<usize>) -> usize {
+        let aabb = colliders
+            .iter()
+            .skip(1)
+            .fold(colliders[0].aabb, |aabb, collider| {
+                aabb.merged(collider.aabb)
+            });
+
+        let index = self.nodes.len();
+
+        if let [collider] = colliders {
+            self.leaves.insert(collider.entity, index);
+            self.nodes.push(StaticAabbNode {
+                aabb,
+                parent,
+                kind: StaticAabbNodeKind::Leaf(*collider),
+            });
+            return index;
+        }
+
+        let (min_center, max_center) = colliders.iter().fold(
+            (Vector::splat(Scalar::MAX), Vector::splat(Scalar::MIN)),
+            |(min, max), collider| {
+                let center = collider.aabb.center();
+                (min.min(center), max.max(center))
+            },
+        );
+        let extents = (max_center - min_center).to_array();
+        let axis = (0..extents.len())
+            .max_by(|&a, &b| extents[a].total_cmp(&extents[b]))
+            .unwrap_or(0);
+
+        let mid = colliders.len() / 2;
+        colliders.select_nth_unstable_by(mid, |a, b| {
+            a.aabb.center().to_array()[axis].total_cmp(&b.aabb.center().to_array()[axis])
+        });
+
+        // Reserve the node before building the children, so that it can be referenced as their parent.
+        self.nodes.push(StaticAabbNode {
+            aabb,
+            parent,
+            kind: StaticAabbNodeKind::Internal([0, 0]),
+        });
+        let (left, right) = colliders.split_at_mut(mid);
+        let left = self.build_node(left, Some(index));
+        let right = self.build_node(right, Some(index));
+        self.nodes[index].kind = StaticAabbNodeKind::Internal([left, right]);
+
+        index
+    }
+
+    /// Inserts the given collider into the tree, or updates it if it is already in the tree.
+    ///
+    /// The new leaf is paired with the existing node whose AABB grows the least when merged
+    /// with the collider's AABB, and only the ancestors of the leaf are refitted.
+    fn insert(&mut self, collider: StaticCollider) {
+        if let Some(&leaf) = self.leaves.get(&collider.entity) {
+            if self.nodes[leaf].aabb == collider.aabb {
+                self.nodes[leaf].kind = StaticAabbNodeKind::Leaf(collider);
+                return;
+            }
+            self.remove(collider.entity);
+        }
+
+        let leaf = self.allocate_node(StaticAabbNode {
+            aabb: collider.aabb,
+            parent: None,
+            kind: StaticAabbNodeKind::Leaf(collider),
+        });
+        self.leaves.insert(collider.entity, leaf);
+
+        let Some(mut sibling) = self.root else {
+            self.root = Some(leaf);
+            return;
+        };
+
+        let growth = |node: &StaticAabbNode| {
+            aabb_cost(node.aabb.merged(collider.aabb)) - aabb_cost(node.aabb)
+        };
+        while let StaticAabbNodeKind::Internal([left, right]) = self.nodes[sibling].kind {
+            sibling = if growth(&self.nodes[left]) <= growth(&self.nodes[right]) {
+                left
+            } else {
+                right
+            };
+        }
+
+        let parent = self.nodes[sibling].parent;
+        let internal = self.allocate_node(StaticAabbNode {
+            aabb: self.nodes[sibling].aabb.merged(collider.aabb),
+            parent,
+            kind: StaticAabbNodeKind::Internal([sibling, leaf]),
+        });
+        self.nodes[sibling].parent = Some(internal);
+        self.nodes[leaf].parent = Some(internal);
+        self.replace_child(parent, sibling, internal);
+        self.refit_ancestors(parent);
+    }
+
+    /// Removes the given collider from the tree. The sibling of its leaf takes the place of their parent,
+    /// and only the ancestors of the leaf are refitted.
+    fn remove(&mut self, entity: Entity) {
+        let Some(leaf) = self.leaves.remove(&entity) else {
+            return;
+        };
+
+        let parent = self.nodes[leaf].parent;
+        self.free_node(leaf);
+
+        let Some(parent) = parent else {
+            self.root = None;
+            return;
+        };
+
+        let StaticAabbNodeKind::Internal(children) = self.nodes[parent].kind else {
+            unreachable!("the parent of a node must be an internal node");
+        };
+        let sibling = if children[0] == leaf {
+            children[1]
+        } else {
+            children[0]
+        };
+
+        let grandparent = self.nodes[parent].parent;
+        self.nodes[sibling].parent = grandparent;
+        self.replace_child(grandparent, parent, sibling);
+        self.free_node(parent);
+        self.refit_ancestors(grandparent);
+    }
+
+    /// Replaces the child `old` of the given `parent` node with `new`.
+    /// If `parent` is `None`, `new` becomes the root node.
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        let Some(parent) = parent else {
+            self.root = Some(new);
+            return;
+        };
+
+        if let StaticAabbNodeKind::Internal(children) = &mut self.nodes[parent].kind {
+            for child in children.iter_mut().filter(|child| **child == old) {
+                *child = new;
+            }
+        }
+    }
+
+    /// Recomputes the AABBs of the given node and its ancestors from their children.
+    fn refit_ancestors(&mut self, mut index: Option<usize>) {
+        while let Some(current) = index {
+            if let StaticAabbNodeKind::Internal([left, right]) = self.nodes[current].kind {
+                self.nodes[current].aabb = self.nodes[left].aabb.merged(self.nodes[right].aabb);
+            }
+            index = self.nodes[current].parent;
+        }
+    }
+
+    /// Stores the given node in a free node if there is one, or in a new node otherwise,
+    /// and returns its index.
+    fn allocate_node(&mut self, node: StaticAabbNode) -> usize {
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn free_node(&mut self, index: usize) {
+        self.nodes[index].parent = None;
+        self.nodes[index].kind = StaticAabbNodeKind::Free;
+        self.free_nodes.push(index);
+    }
+
+    /// Calls the given `callback` for each static collider whose AABB intersects the given `aabb`.
+    fn for_each_intersecting(
+        &self,
+        aabb: &ColliderAabb,
+        stack: &mut Vec<usize>,
+        mut callback: impl FnMut(&StaticCollider),
+    ) {
+        let Some(root) = self.root else {
+            return;
+        };
+
+        stack.clear();
+        stack.push(root);
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+
+            if !node.aabb.intersects(aabb) {
+                continue;
+            }
+
+            match &node.kind {
+                StaticAabbNodeKind::Leaf(collider) => callback(collider),
+                StaticAabbNodeKind::Internal(children) => stack.extend(children),
+                StaticAabbNodeKind::Free => {}
+            }
+        }
+    }
+}
+
+/// Returns the cost of the given AABB for building the [`StaticAabbTree`], which is the sum of its extents.
+fn aabb_cost(aabb: ColliderAabb) -> Scalar {
+    aabb.size().to_array().iter().sum()
+}
+
+/// Returns `true` if the collider with the given parent is attached to a static or sleeping rigid body,
+/// which means that it is stored in the [`StaticAabbTree`].
+fn is_static_or_sleeping_collider(
+    parent: Option<&ColliderParent>,
+    rbs: &Query<(&RigidBody, Has<Sleeping>)>,
+) -> bool {
+    parent.is_some_and(|p| {
+        rbs.get(p.get())
+            .is_ok_and(|(rb, is_sleeping)| rb.is_static() || is_sleeping)
+    })
+}
+
+/// Returns `true` if the collider has [`ColliderDisabled`] or is attached to a rigid body with [`RigidBodyDisabled`].
+fn is_disabled_collider(
+    entity: Entity,
+    parent: Option<&ColliderParent>,
+    disabled: &Query<(Has<ColliderDisabled>, Has<RigidBodyDisabled>)>,
+) -> bool {
+    disabled
+        .get(entity)
+        .is_ok_and(|(is_collider_disabled, _)| is_collider_disabled)
+        || parent.is_some_and(|p| {
+            disabled
+                .get(p.get())
+                .is_ok_and(|(_, is_body_disabled)| is_body_disabled)
+        })
+}
+
 /// Updates [`AabbIntervals`] to keep them in sync with the [`ColliderAabb`]s.
+///
+/// Colliders that have been attached to static bodies, whose bodies have fallen asleep,
+/// or that have been disabled are removed from the intervals.
 #[allow(clippy::type_complexity)]
 fn update_aabb_intervals(
     aabbs: Query<(
@@ -101,7 +469,8 @@ fn update_aabb_intervals(
         Ref<Position>,
         Ref<Rotation>,
     )>,
-    rbs: Query<&RigidBody>,
+    rbs: Query<(&RigidBody, Has<Sleeping>)>,
+    disabled: Query<(Has<ColliderDisabled>, Has<RigidBodyDisabled>)>,
     mut intervals: ResMut<AabbIntervals>,
 ) {
     intervals.0.retain_mut(
@@ -109,13 +478,20 @@ fn update_aabb_intervals(
             if let Ok((new_aabb, new_parent, new_layers, position, rotation)) =
                 aabbs.get(*collider_entity)
             {
+                // Static and sleeping colliders are stored in the `StaticAabbTree`.
+                if is_static_or_sleeping_collider(new_parent, &rbs) {
+                    return false;
+                }
+
+                // Disabled colliders are not stored at all until they are enabled again.
+                if is_disabled_collider(*collider_entity, new_parent, &disabled) {
+                    return false;
+                }
+
                 *aabb = *new_aabb;
                 *collider_parent = new_parent.map_or(ColliderParent(*collider_entity), |p| *p);
                 *layers = new_layers.map_or(CollisionLayers::default(), |layers| *layers);
-
-                let is_static =
-                    new_parent.is_some_and(|p| rbs.get(p.get()).is_ok_and(RigidBody::is_static));
-                *is_inactive = is_static || (!position.is_changed() && !rotation.is_changed());
+                *is_inactive = !position.is_changed() && !rotation.is_changed();
 
                 true
             } else {
@@ -125,47 +501,187 @@ fn update_aabb_intervals(
     );
 }
 
-/// Adds new [`ColliderAabb`]s to [`AabbIntervals`].
-#[allow(clippy::type_complexity)]
+/// Adds new [`ColliderAabb`]s of colliders that aren't attached to static or sleeping bodies to [`AabbIntervals`].
+///
+/// Colliders of bodies that have woken up are moved back from the [`StaticAabbTree`] to the intervals,
+/// and colliders that have been re-enabled are added back to the intervals.
+/// If the type of an existing rigid body has changed or a collider has been attached to a different body,
+/// colliders can move between the intervals and the [`StaticAabbTree`], so all intervals are collected again.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn add_new_aabb_intervals(
-    aabbs: Query<
-        (
-            Entity,
-            Option<&ColliderParent>,
-            &ColliderAabb,
-            Option<&RigidBody>,
-            Option<&CollisionLayers>,
-        ),
-        Added<ColliderAabb>,
-    >,
+    aabbs: Query<(
+        Entity,
+        Option<&ColliderParent>,
+        Ref<ColliderAabb>,
+        Option<&CollisionLayers>,
+    )>,
+    rbs: Query<(&RigidBody, Has<Sleeping>)>,
+    disabled: Query<(Has<ColliderDisabled>, Has<RigidBodyDisabled>)>,
+    changed_rbs: Query<Ref<RigidBody>, Changed<RigidBody>>,
+    changed_parents: Query<Ref<ColliderParent>, Changed<ColliderParent>>,
+    mut woken_bodies: RemovedComponents<Sleeping>,
+    mut enabled_bodies: RemovedComponents<RigidBodyDisabled>,
+    mut enabled_colliders: RemovedComponents<ColliderDisabled>,
     mut intervals: ResMut<AabbIntervals>,
 ) {
-    let aabbs = aabbs.iter().map(|(ent, parent, aabb, rb, layers)| {
-        (
-            ent,
-            parent.map_or(ColliderParent(ent), |p| *p),
-            *aabb,
-            // Default to treating collider as immovable/static for filtering unnecessary collision checks
-            layers.map_or(CollisionLayers::default(), |layers| *layers),
-            rb.map_or(false, |rb| rb.is_static()),
-        )
-    });
+    let is_resync_needed = changed_rbs.iter().any(|rb| !rb.is_added())
+        || changed_parents.iter().any(|parent| !parent.is_added());
+
+    if is_resync_needed {
+        intervals.0.clear();
+    }
+
+    // Bodies can fall asleep and wake up again before the intervals are updated,
+    // so the colliders of woken bodies can already be in the intervals.
+    // The same applies to bodies and colliders that are disabled and enabled again.
+    let woken: HashSet<Entity> = woken_bodies
+        .read()
+        .chain(enabled_bodies.read())
+        .chain(enabled_colliders.read())
+        .collect();
+    let existing: HashSet<Entity> = if woken.is_empty() || is_resync_needed {
+        HashSet::default()
+    } else {
+        intervals.0.iter().map(|interval| interval.0).collect()
+    };
+    let is_woken = |entity: Entity, parent: Option<&ColliderParent>| {
+        (woken.contains(&entity) || parent.is_some_and(|parent| woken.contains(&parent.get())))
+            && !existing.contains(&entity)
+    };
+
+    let aabbs = aabbs
+        .iter()
+        .filter(|(entity, parent, aabb, _)| {
+            (is_resync_needed || aabb.is_added() || is_woken(*entity, *parent))
+                && !is_static_or_sleeping_collider(*parent, &rbs)
+                && !is_disabled_collider(*entity, *parent, &disabled)
+        })
+        .map(|(ent, parent, aabb, layers)| {
+            (
+                ent,
+                parent.map_or(ColliderParent(ent), |p| *p),
+                *aabb,
+                layers.map_or(CollisionLayers::default(), |layers| *layers),
+                false,
+            )
+        });
     intervals.0.extend(aabbs);
 }
 
+/// Updates the [`StaticAabbTree`] when colliders attached to static or sleeping bodies have been added, changed or removed,
+/// or when bodies have fallen asleep, woken up, or been disabled or enabled.
+///
+/// Only the leaves of the affected colliders are inserted or removed. The tree is only rebuilt
+/// when the type of an existing rigid body has changed or a collider has been attached to a different body.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_static_aabb_tree(
+    aabbs: Query<(
+        Entity,
+        Option<&ColliderParent>,
+        &ColliderAabb,
+        Option<&CollisionLayers>,
+    )>,
+    changed_aabbs: Query<
+        Entity,
+        Or<(
+            Changed<ColliderAabb>,
+            Changed<ColliderParent>,
+            Changed<CollisionLayers>,
+            Added<ColliderDisabled>,
+        )>,
+    >,
+    rbs: Query<(&RigidBody, Has<Sleeping>)>,
+    changed_rbs: Query<Ref<RigidBody>, Changed<RigidBody>>,
+    changed_parents: Query<Ref<ColliderParent>, Changed<ColliderParent>>,
+    fallen_asleep: Query<Entity, Added<Sleeping>>,
+    disabled: Query<(Has<ColliderDisabled>, Has<RigidBodyDisabled>)>,
+    newly_disabled_bodies: Query<Entity, Added<RigidBodyDisabled>>,
+    mut woken_bodies: RemovedComponents<Sleeping>,
+    mut enabled_bodies: RemovedComponents<RigidBodyDisabled>,
+    mut enabled_colliders: RemovedComponents<ColliderDisabled>,
+    mut removed_aabbs: RemovedComponents<ColliderAabb>,
+    mut tree: ResMut<StaticAabbTree>,
+) {
+    let static_collider = |(entity, parent, aabb, layers): (
+        Entity,
+        Option<&ColliderParent>,
+        &ColliderAabb,
+        Option<&CollisionLayers>,
+    )| {
+        (is_static_or_sleeping_collider(parent, &rbs)
+            && !is_disabled_collider(entity, parent, &disabled))
+        .then(|| StaticCollider {
+            entity,
+            parent: *parent.unwrap(),
+            aabb: *aabb,
+            layers: layers.map_or(CollisionLayers::default(), |layers| *layers),
+        })
+    };
+
+    // If the type of an existing body has changed or colliders have been attached to different bodies,
+    // colliders can move between the intervals and the tree in ways that are not tracked here.
+    if changed_rbs.iter().any(|rb| !rb.is_added())
+        || changed_parents.iter().any(|parent| !parent.is_added())
+    {
+        tree.rebuild(aabbs.iter().filter_map(static_collider));
+        return;
+    }
+
+    for entity in removed_aabbs.read() {
+        tree.remove(entity);
+    }
+
+    // The colliders of bodies that have fallen asleep, woken up, or been disabled or enabled
+    // are moved in or out of the tree.
+    let changed_bodies: HashSet<Entity> = fallen_asleep
+        .iter()
+        .chain(woken_bodies.read())
+        .chain(newly_disabled_bodies.iter())
+        .chain(enabled_bodies.read())
+        .collect();
+    let mut affected: Vec<Entity> = changed_aabbs
+        .iter()
+        .chain(enabled_colliders.read())
+        .collect();
+    if !changed_bodies.is_empty() {
+        affected.extend(
+            aabbs
+                .iter()
+                .filter(|(_, parent, ..)| {
+                    parent.is_some_and(|parent| changed_bodies.contains(&parent.get()))
+                })
+                .map(|(entity, ..)| entity),
+        );
+    }
+
+    // Sort to make the structure of the tree independent of query iteration order.
+    affected.sort();
+    affected.dedup();
+
+    for item in aabbs.iter_many(&affected) {
+        if let Some(collider) = static_collider(item) {
+            tree.insert(collider);
+        } else {
+            tree.remove(item.0);
+        }
+    }
+}
+
 /// Collects bodies that are potentially colliding.
 fn collect_collision_pairs(
-    intervals: ResMut<AabbIntervals>,
+    mut intervals: ResMut<AabbIntervals>,
+    static_tree: Res<StaticAabbTree>,
     mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
 ) {
-    sweep_and_prune(intervals, &mut broad_collision_pairs.0);
+    sweep_and_prune(&mut intervals, &mut broad_collision_pairs.0);
+    collect_static_collision_pairs(&intervals, &static_tree, &mut broad_collision_pairs.0);
 }
 
 /// Sorts the entities by their minimum extents along an axis and collects the entity pairs that have intersecting AABBs.
 ///
 /// Sweep and prune exploits temporal coherence, as bodies are unlikely to move significantly between two simulation steps. Insertion sort is used, as it is good at sorting nearly sorted lists efficiently.
-fn sweep_and_prune(
-    mut intervals: ResMut<AabbIntervals>,
+pub(crate) fn sweep_and_prune(
+    intervals: &mut AabbIntervals,
     broad_collision_pairs: &mut Vec<(Entity, Entity)>,
 ) {
     // Sort bodies along the x-axis using insertion sort, a sorting algorithm great for sorting nearly sorted lists.
@@ -204,10 +720,33 @@ fn sweep_and_prune(
     }
 }
 
+/// Collects the entity pairs of moving colliders and static colliders that have intersecting AABBs
+/// by querying the [`StaticAabbTree`] with the AABB of each moving collider.
+pub(crate) fn collect_static_collision_pairs(
+    intervals: &AabbIntervals,
+    static_tree: &StaticAabbTree,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+) {
+    let mut stack = Vec::new();
+
+    for (ent1, parent1, aabb1, layers1, inactive1) in intervals.0.iter() {
+        // No collisions between static bodies and bodies that haven't moved
+        if *inactive1 {
+            continue;
+        }
+
+        static_tree.for_each_intersecting(aabb1, &mut stack, |collider| {
+            if layers1.interacts_with(collider.layers) && *parent1 != collider.parent {
+                broad_collision_pairs.push((*ent1, collider.entity));
+            }
+        });
+    }
+}
+
 /// Sorts a list iteratively using comparisons. In an ascending sort order, when a smaller value is encountered, it is moved lower in the list until it is larger than the item before it.
 ///
 /// This is relatively slow for large lists, but very efficient in cases where the list is already mostly sorted.
-fn insertion_sort<T>(items: &mut [T], comparison: fn(&T, &T) -> bool) {
+pub(crate) fn insertion_sort<T>(items: &mut [T], comparison: fn(&T, &T) -> bool) {
     for i in 1..items.len() {
         let mut j = i;
         while j > 0 && comparison(&items[j - 1], &items[j]) {
@@ -70,6 +70,98 @@ pub trait AnyCollider: Component {
         rotation2: impl Into<Rotation>,
         prediction_distance: Scalar,
     ) -> Vec<ContactManifold>;
+
+    /// Computes all [`ContactManifold`]s between two colliders, reusing data computed for the same pair
+    /// on previous frames that is stored in the given [`ContactManifoldCache`].
+    ///
+    /// Backends can use the cache to warm start their collision algorithms, for example by reusing
+    /// separating axes, closest features or GJK simplices. By default, the cache is ignored
+    /// and [`contact_manifolds`](AnyCollider::contact_manifolds) is used.
+    #[allow(clippy::too_many_arguments)]
+    fn contact_manifolds_with_cache(
+        &self,
+        other: &Self,
+        position1: Vector,
+        rotation1: impl Into<Rotation>,
+        position2: Vector,
+        rotation2: impl Into<Rotation>,
+        prediction_distance: Scalar,
+        cache: &mut ContactManifoldCache,
+    ) -> Vec<ContactManifold> {
+        let _ = cache;
+        self.contact_manifolds(
+            other,
+            position1,
+            rotation1,
+            position2,
+            rotation2,
+            prediction_distance,
+        )
+    }
+}
+
+/// Backend-specific narrow phase data stored for a pair of colliders across frames.
+///
+/// The narrow phase keeps one cache for each pair of colliders that it processes, and passes it to
+/// [`AnyCollider::contact_manifolds_with_cache`] so that the collision algorithms can be warm started
+/// using the results of the previous frame. The cache is dropped when the pair stops being processed.
+///
+/// The caches are stored in the [`ContactManifoldCaches`] resource, and they are cloned
+/// when capturing a [`PhysicsSnapshot`].
+#[derive(Default)]
+pub struct ContactManifoldCache(Option<Box<dyn CachedData>>);
+
+impl Clone for ContactManifoldCache {
+    fn clone(&self) -> Self {
+        Self(self.0.as_ref().map(|data| (**data).clone_box()))
+    }
+}
+
+impl ContactManifoldCache {
+    /// Returns a mutable reference to the cached data of type `T`, inserting the value returned
+    /// by `f` if the cache is empty or contains data of a different type.
+    pub fn get_or_insert_with<T: std::any::Any + Clone + Send + Sync>(
+        &mut self,
+        f: impl FnOnce() -> T,
+    ) -> &mut T {
+        if !self
+            .0
+            .as_ref()
+            .is_some_and(|data| (**data).as_any().is::<T>())
+        {
+            self.0 = Some(Box::new(f()));
+        }
+        self.0
+            .as_mut()
+            .and_then(|data| (**data).as_any_mut().downcast_mut::<T>())
+            .expect("cache should contain data of type `T`")
+    }
+
+    /// Clears the cached data.
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+/// Type-erased data stored in a [`ContactManifoldCache`] that can be cloned.
+trait CachedData: std::any::Any + Send + Sync {
+    fn clone_box(&self) -> Box<dyn CachedData>;
+    fn as_any(&self) -> &dyn std::any::Any;
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+}
+
+impl<T: std::any::Any + Clone + Send + Sync> CachedData for T {
+    fn clone_box(&self) -> Box<dyn CachedData> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
 }
 
 /// A trait for colliders that support scaling.
@@ -114,6 +206,7 @@ pub trait ScalableCollider: AnyCollider {
 /// ```
 #[cfg(all(feature = "3d", feature = "async-collider"))]
 #[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsyncCollider(pub ComputedCollider);
 
 /// A component that will automatically generate colliders for the meshes in a scene
@@ -163,6 +256,7 @@ pub struct AsyncCollider(pub ComputedCollider);
 /// ```
 #[cfg(all(feature = "3d", feature = "async-collider"))]
 #[derive(Component, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsyncSceneCollider {
     /// The default collider type used for each mesh that isn't included in [`meshes_by_name`](#structfield.meshes_by_name).
     /// If `None`, all meshes except the ones in [`meshes_by_name`](#structfield.meshes_by_name) will be skipped.
@@ -245,6 +339,7 @@ impl AsyncSceneCollider {
 /// Configuration for a specific collider generated from a scene using [`AsyncSceneCollider`].
 #[cfg(all(feature = "3d", feature = "async-collider"))]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct AsyncSceneColliderData {
     /// The type of collider generated for the mesh.
     pub shape: ComputedCollider,
@@ -276,6 +371,7 @@ impl Default for AsyncSceneColliderData {
 /// - [`Collider::convex_decomposition_from_mesh`]
 #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
 #[derive(Component, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComputedCollider {
     /// A triangle mesh.
     #[default]
@@ -177,7 +177,11 @@ pub type TriMeshFlags = parry::shape::TriMeshFlags;
 /// To get a reference to the internal [`SharedShape`], you can use the [`Collider::shape()`]
 /// or [`Collider::shape_scaled()`] methods.
 #[derive(Clone, Component)]
-#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize, Reflect),
+    reflect_value(Component, Debug, Default, Serialize, Deserialize)
+)]
 pub struct Collider {
     /// The raw unscaled collider shape.
     shape: SharedShape,
@@ -261,7 +265,7 @@ impl AnyCollider for Collider {
     }
 
     fn mass_properties(&self, density: Scalar) -> ColliderMassProperties {
-        let props = self.shape_scaled().mass_properties(density);
+        let props = shape_mass_properties(self.shape_scaled().as_ref(), density);
 
         ColliderMassProperties {
             mass: Mass(props.mass()),
@@ -300,6 +304,76 @@ impl AnyCollider for Collider {
             prediction_distance,
         )
     }
+
+    fn contact_manifolds_with_cache(
+        &self,
+        other: &Self,
+        position1: Vector,
+        rotation1: impl Into<Rotation>,
+        position2: Vector,
+        rotation2: impl Into<Rotation>,
+        prediction_distance: Scalar,
+        cache: &mut ContactManifoldCache,
+    ) -> Vec<ContactManifold> {
+        let isometry1 = utils::make_isometry(position1, rotation1.into());
+        let isometry2 = utils::make_isometry(position2, rotation2.into());
+        let isometry12 = isometry1.inv_mul(&isometry2);
+
+        contact_query::persistent_contact_manifolds_with_relative_isometry(
+            self,
+            other,
+            &isometry12,
+            prediction_distance,
+            cache
+                .get_or_insert_with(|| contact_query::PersistentContactManifolds::new(self, other)),
+        )
+    }
+}
+
+/// Computes the mass properties of a shape with the given density.
+///
+/// Triangle meshes are integrated exactly with [`ColliderMassProperties::from_trimesh`]
+/// instead of relying on parry's approximations, and the parts of compound shapes,
+/// like the ones created by convex decomposition, are handled recursively.
+fn shape_mass_properties(
+    shape: &dyn parry::shape::Shape,
+    density: Scalar,
+) -> parry::mass_properties::MassProperties {
+    match shape.as_typed_shape() {
+        TypedShape::TriMesh(trimesh) => {
+            let vertices: Vec<Vector> = trimesh.vertices().iter().map(|v| (*v).into()).collect();
+            let Some(props) =
+                ColliderMassProperties::from_trimesh(&vertices, trimesh.indices(), density)
+            else {
+                return shape.mass_properties(density);
+            };
+
+            #[cfg(feature = "2d")]
+            {
+                parry::mass_properties::MassProperties::new(
+                    props.center_of_mass.0.into(),
+                    props.mass.0,
+                    props.inertia.0,
+                )
+            }
+            #[cfg(feature = "3d")]
+            {
+                parry::mass_properties::MassProperties::with_inertia_matrix(
+                    props.center_of_mass.0.into(),
+                    props.mass.0,
+                    props.inertia.0.into(),
+                )
+            }
+        }
+        TypedShape::Compound(compound) => compound
+            .shapes()
+            .iter()
+            .map(|(isometry, shape)| {
+                shape_mass_properties(shape.as_ref(), density).transform_by(isometry)
+            })
+            .sum(),
+        _ => shape.mass_properties(density),
+    }
 }
 
 impl ScalableCollider for Collider {
@@ -637,6 +711,9 @@ impl Collider {
     }
 
     /// Creates a collider with a triangle mesh shape defined by its vertex and index buffers.
+    ///
+    /// The mass properties of the shape are integrated exactly over the triangles.
+    /// In 3D, this requires the mesh to be closed. See [`ColliderMassProperties::from_trimesh`].
     pub fn trimesh(vertices: Vec<Vector>, indices: Vec<[u32; 3]>) -> Self {
         let vertices = vertices.into_iter().map(|v| v.into()).collect();
         SharedShape::trimesh(vertices, indices).into()
@@ -16,6 +16,8 @@ use crate::{
     feature = "default-collider"
 ))]
 use bevy::scene::SceneInstance;
+#[cfg(feature = "parallel")]
+use bevy::tasks::{ComputeTaskPool, ParallelSlice};
 use bevy::{
     prelude::*,
     utils::{intern::Interned, HashMap},
@@ -357,10 +359,10 @@ pub fn init_async_scene_colliders(
 fn update_aabb<C: AnyCollider>(
     mut colliders: Query<
         (
-            &C,
+            Ref<C>,
             &mut ColliderAabb,
-            &Position,
-            &Rotation,
+            Ref<Position>,
+            Ref<Rotation>,
             Option<&ColliderParent>,
             Option<&LinearVelocity>,
             Option<&AngularVelocity>,
@@ -377,6 +379,7 @@ fn update_aabb<C: AnyCollider>(
         (&Position, Option<&LinearVelocity>, Option<&AngularVelocity>),
         With<Children>,
     >,
+    sleeping: Query<(), With<Sleeping>>,
     dt: Res<Time>,
     narrow_phase_config: Option<Res<NarrowPhaseConfig>>,
 ) {
@@ -384,6 +387,14 @@ fn update_aabb<C: AnyCollider>(
     let safety_margin_factor = 2.0 * dt.delta_seconds_adjusted();
 
     for (collider, mut aabb, pos, rot, collider_parent, lin_vel, ang_vel) in &mut colliders {
+        // Sleeping bodies don't move, so their AABBs only need to be recomputed
+        // if the collider itself has changed.
+        if collider_parent.is_some_and(|parent| sleeping.contains(parent.get()))
+            && !(collider.is_changed() || pos.is_changed() || rot.is_changed())
+        {
+            continue;
+        }
+
         let (lin_vel, ang_vel) = if let (Some(lin_vel), Some(ang_vel)) = (lin_vel, ang_vel) {
             (*lin_vel, *ang_vel)
         } else if let Some(Ok((parent_pos, Some(lin_vel), Some(ang_vel)))) =
@@ -470,7 +481,8 @@ fn update_collider_parents<C: AnyCollider>(
     for (entity, collider_parent, has_collider) in &mut bodies {
         if has_collider {
             if let Some(mut collider_parent) = collider_parent {
-                collider_parent.0 = entity;
+                // Only trigger change detection if the parent has actually changed
+                collider_parent.set_if_neq(ColliderParent(entity));
             } else {
                 commands.entity(entity).try_insert((
                     ColliderParent(entity),
@@ -483,7 +495,7 @@ fn update_collider_parents<C: AnyCollider>(
         for child in children.iter_descendants(entity) {
             if let Ok(collider_parent) = child_colliders.get_mut(child) {
                 if let Some(mut collider_parent) = collider_parent {
-                    collider_parent.0 = entity;
+                    collider_parent.set_if_neq(ColliderParent(entity));
                 } else {
                     commands.entity(child).insert((
                         ColliderParent(entity),
@@ -579,16 +591,22 @@ pub(crate) fn update_child_collider_position(
             continue;
         };
 
-        position.0 = parent_pos.0 + parent_rot.rotate(collider_transform.translation);
+        // Avoid triggering bevy's change detection unnecessarily, so that colliders of bodies
+        // that haven't moved, like static geometry, aren't treated as changed.
+        position.set_if_neq(Position(
+            parent_pos.0 + parent_rot.rotate(collider_transform.translation),
+        ));
         #[cfg(feature = "2d")]
         {
-            *rotation = *parent_rot + collider_transform.rotation;
+            rotation.set_if_neq(*parent_rot + collider_transform.rotation);
         }
         #[cfg(feature = "3d")]
         {
-            *rotation = (parent_rot.0 * collider_transform.rotation.0)
-                .normalize()
-                .into();
+            rotation.set_if_neq(
+                (parent_rot.0 * collider_transform.rotation.0)
+                    .normalize()
+                    .into(),
+            );
         }
     }
 }
@@ -806,41 +824,126 @@ unsafe fn propagate_collider_transforms_recursive(
 }
 
 /// Updates the mass properties of [`Collider`]s and [collider parents](ColliderParent).
-#[allow(clippy::type_complexity)]
+///
+/// The mass properties of a collider are recomputed when its shape, [`ColliderDensity`] or [`ColliderTransform`]
+/// changes, and moved to the new parent when it is attached to another body. All colliders of bodies with
+/// [`RecomputeMassProperties`] are recomputed. Sleeping bodies whose mass properties change are woken up.
+///
+/// Computing the mass properties of colliders can be expensive for complex shapes, so with the `parallel` feature,
+/// they are computed in parallel. This reduces the cost of large batches of changed colliders,
+/// for example when an object fractures into hundreds of pieces in a single frame.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn update_collider_mass_properties<C: AnyCollider>(
+    mut commands: Commands,
     mut mass_props: Query<(Entity, MassPropertiesQuery)>,
-    mut colliders: Query<
-        (
-            &ColliderTransform,
-            &mut PreviousColliderTransform,
-            &ColliderParent,
-            Ref<C>,
-            &ColliderDensity,
-            &mut ColliderMassProperties,
-        ),
-        Or<(
-            Changed<C>,
-            Changed<ColliderTransform>,
-            Changed<ColliderDensity>,
-            Changed<ColliderMassProperties>,
-        )>,
-    >,
-    collider_map: Res<ColliderStorageMap<C>>,
+    mut colliders: Query<(
+        Entity,
+        Ref<ColliderTransform>,
+        &mut PreviousColliderTransform,
+        Ref<ColliderParent>,
+        Ref<C>,
+        Ref<ColliderDensity>,
+        &mut ColliderMassProperties,
+    )>,
+    recomputed_bodies: Query<Entity, With<RecomputeMassProperties>>,
+    mut sleeping_bodies: Query<(Ref<RigidBody>, &mut TimeSleeping), With<Sleeping>>,
+    mut collider_map: ResMut<ColliderStorageMap<C>>,
     mut removed_colliders: RemovedComponents<C>,
+    mut changed_colliders: Local<Vec<Entity>>,
 ) {
-    for (
-        collider_transform,
-        mut previous_collider_transform,
-        collider_parent,
-        collider,
-        density,
-        mut collider_mass_properties,
-    ) in &mut colliders
-    {
-        if let Ok((_, mut mass_properties)) = mass_props.get_mut(collider_parent.0) {
-            // Subtract previous collider mass props from the body's own mass props,
-            // If the collider is new, it doesn't have previous mass props, so we shouldn't subtract anything.
-            if !collider.is_added() {
+    changed_colliders.clear();
+    changed_colliders.extend(colliders.iter_mut().filter_map(
+        |(
+            entity,
+            collider_transform,
+            _,
+            collider_parent,
+            collider,
+            density,
+            collider_mass_properties,
+        )| {
+            let changed = collider.is_changed()
+                || collider_transform.is_changed()
+                || collider_parent.is_changed()
+                || density.is_changed()
+                || collider_mass_properties.is_changed()
+                // Recompute all colliders attached to bodies with `RecomputeMassProperties`
+                || recomputed_bodies.contains(collider_parent.get());
+            changed.then_some(entity)
+        },
+    ));
+
+    for entity in &recomputed_bodies {
+        commands.entity(entity).remove::<RecomputeMassProperties>();
+    }
+
+    let compute_mass_properties = |entity: Entity| {
+        colliders
+            .get(entity)
+            .ok()
+            .map(|(_, _, _, _, collider, density, _)| {
+                collider.mass_properties(density.max(Scalar::EPSILON))
+            })
+    };
+
+    // The results are in the same order as the changed colliders, so the body mass properties
+    // are updated in the same order regardless of how the work is split between threads.
+    #[cfg(feature = "parallel")]
+    let new_mass_properties = changed_colliders
+        .par_splat_map(ComputeTaskPool::get(), None, |chunk| {
+            chunk
+                .iter()
+                .map(|&entity| compute_mass_properties(entity))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    #[cfg(not(feature = "parallel"))]
+    let new_mass_properties = changed_colliders
+        .iter()
+        .map(|&entity| compute_mass_properties(entity))
+        .collect::<Vec<_>>();
+
+    // Wakes up sleeping bodies whose mass properties have changed,
+    // unless the body was just added, like a body that is spawned asleep
+    let mut wake_up = |entity: Entity| {
+        if let Ok((rb, mut time_sleeping)) = sleeping_bodies.get_mut(entity) {
+            if !rb.is_added() {
+                commands.entity(entity).remove::<Sleeping>();
+                time_sleeping.0 = 0.0;
+            }
+        }
+    };
+
+    for (&entity, new_mass_properties) in changed_colliders.iter().zip(new_mass_properties) {
+        let Some(new_mass_properties) = new_mass_properties else {
+            continue;
+        };
+        let Ok((
+            _,
+            collider_transform,
+            mut previous_collider_transform,
+            collider_parent,
+            collider,
+            _,
+            mut collider_mass_properties,
+        )) = colliders.get_mut(entity)
+        else {
+            continue;
+        };
+
+        // The collider may have been attached to a different body since its mass properties
+        // were last added, so they need to be subtracted from the previous body.
+        let previous_parent = collider_map
+            .map
+            .get(&entity)
+            .map_or(collider_parent.get(), |(parent, ..)| parent.get());
+
+        // Subtract previous collider mass props from the body's own mass props,
+        // If the collider is new, it doesn't have previous mass props, so we shouldn't subtract anything.
+        if !collider.is_added() {
+            if let Ok((_, mut mass_properties)) = mass_props.get_mut(previous_parent) {
                 mass_properties -= ColliderMassProperties {
                     center_of_mass: CenterOfMass(
                         previous_collider_transform
@@ -848,21 +951,36 @@ fn update_collider_mass_properties<C: AnyCollider>(
                     ),
                     ..*collider_mass_properties
                 };
+                wake_up(previous_parent);
             }
+        }
 
-            previous_collider_transform.0 = *collider_transform;
+        previous_collider_transform.0 = *collider_transform;
 
-            // Update collider mass props
-            *collider_mass_properties = collider.mass_properties(density.max(Scalar::EPSILON));
+        // Update collider mass props
+        *collider_mass_properties = new_mass_properties;
 
-            // Add new collider mass props to the body's mass props
+        // Add new collider mass props to the body's mass props
+        if let Ok((_, mut mass_properties)) = mass_props.get_mut(collider_parent.get()) {
             mass_properties += ColliderMassProperties {
                 center_of_mass: CenterOfMass(
                     collider_transform.transform_point(collider_mass_properties.center_of_mass.0),
                 ),
                 ..*collider_mass_properties
             };
+            wake_up(collider_parent.get());
         }
+
+        // Store the current parent so that the mass properties are subtracted from the right body
+        // if the collider changes again before the storage is updated in the physics schedule
+        collider_map.map.insert(
+            entity,
+            (
+                *collider_parent,
+                *collider_mass_properties,
+                *collider_transform,
+            ),
+        );
     }
 
     // Subtract mass properties of removed colliders
@@ -10,9 +10,11 @@
 //! | [`distance`]          | Computes the minimum distance separating two [`Collider`]s.               |
 //! | [`intersection_test`] | Tests whether two [`Collider`]s are intersecting each other.              |
 //! | [`time_of_impact`]    | Computes when two moving [`Collider`]s hit each other for the first time. |
+//! | [`nonlinear_time_of_impact`] | Like [`time_of_impact`], but also takes rotational motion into account. |
 //!
 //! For geometric queries that query the entire world for intersections, like raycasting, shapecasting
-//! and point projection, see [spatial queries](spatial_query).
+//! and point projection, see [spatial queries](spatial_query). To compute the closest points or distance
+//! between the colliders of specific entities, see [`SpatialQuery::closest_points`] and [`SpatialQuery::distance`].
 
 use crate::prelude::*;
 use parry::query::{PersistentQueryDispatcher, Unsupported};
@@ -113,9 +115,6 @@ pub fn contact(
     })
 }
 
-// TODO: Add a persistent version of this that tries to reuse previous contact manifolds
-// by exploiting spatial and temporal coherence. This is supported by Parry's contact_manifolds,
-// but requires using Parry's ContactManifold type.
 /// Computes all [`ContactManifold`]s between two [`Collider`]s.
 ///
 /// Returns an empty vector if the colliders are separated by a distance greater than `prediction_distance`
@@ -165,16 +164,83 @@ pub fn contact_manifolds(
     let isometry2 = utils::make_isometry(position2.into(), rotation2.into());
     let isometry12 = isometry1.inv_mul(&isometry2);
 
-    // TODO: Reuse manifolds from previous frame to improve performance
-    let mut manifolds: Vec<parry::query::ContactManifold<(), ()>> = vec![];
+    contact_manifolds_with_relative_isometry(collider1, collider2, &isometry12, prediction_distance)
+}
+
+/// Computes all [`ContactManifold`]s between two [`Collider`]s, given the isometry
+/// of the second collider relative to the first collider.
+pub(crate) fn contact_manifolds_with_relative_isometry(
+    collider1: &Collider,
+    collider2: &Collider,
+    isometry12: &parry::math::Isometry<Scalar>,
+    prediction_distance: Scalar,
+) -> Vec<ContactManifold> {
+    persistent_contact_manifolds_with_relative_isometry(
+        collider1,
+        collider2,
+        isometry12,
+        prediction_distance,
+        &mut PersistentContactManifolds::new(collider1, collider2),
+    )
+}
+
+/// Parry's contact manifolds and collision detection workspace for a pair of [`Collider`]s,
+/// reused across frames to warm start the collision algorithms.
+#[derive(Clone)]
+pub(crate) struct PersistentContactManifolds {
+    /// Identifies the shapes the data was computed for, so that it can be reset if either shape changes.
+    shapes: [usize; 2],
+    manifolds: Vec<parry::query::ContactManifold<(), ()>>,
+    workspace: Option<parry::query::ContactManifoldsWorkspace>,
+}
+
+impl PersistentContactManifolds {
+    pub(crate) fn new(collider1: &Collider, collider2: &Collider) -> Self {
+        Self {
+            shapes: Self::shape_ids(collider1, collider2),
+            manifolds: vec![],
+            workspace: None,
+        }
+    }
+
+    fn shape_ids(collider1: &Collider, collider2: &Collider) -> [usize; 2] {
+        [
+            std::sync::Arc::as_ptr(&collider1.shape_scaled().0) as *const () as usize,
+            std::sync::Arc::as_ptr(&collider2.shape_scaled().0) as *const () as usize,
+        ]
+    }
+}
+
+/// Computes all [`ContactManifold`]s between two [`Collider`]s like [`contact_manifolds_with_relative_isometry`],
+/// but reuses the manifolds and workspace computed for the same pair previously.
+/// Parry uses them to warm start the collision algorithms, for example with cached
+/// separating axes, closest features and GJK simplices.
+pub(crate) fn persistent_contact_manifolds_with_relative_isometry(
+    collider1: &Collider,
+    collider2: &Collider,
+    isometry12: &parry::math::Isometry<Scalar>,
+    prediction_distance: Scalar,
+    persistent: &mut PersistentContactManifolds,
+) -> Vec<ContactManifold> {
+    // The cached data is only valid for the shapes it was computed for.
+    let shapes = PersistentContactManifolds::shape_ids(collider1, collider2);
+    if persistent.shapes != shapes {
+        *persistent = PersistentContactManifolds::new(collider1, collider2);
+    }
+
+    let PersistentContactManifolds {
+        manifolds,
+        workspace,
+        ..
+    } = persistent;
 
     let result = parry::query::DefaultQueryDispatcher.contact_manifolds(
-        &isometry12,
+        isometry12,
         collider1.shape_scaled().0.as_ref(),
         collider2.shape_scaled().0.as_ref(),
         prediction_distance,
-        &mut manifolds,
-        &mut None,
+        manifolds,
+        workspace,
     );
 
     // Fall back to support map contacts for unsupported (custom) shapes.
@@ -184,7 +250,7 @@ pub fn contact_manifolds(
             collider2.shape_scaled().as_support_map(),
         ) {
             if let Some(contact) = parry::query::contact::contact_support_map_support_map(
-                &isometry12,
+                isometry12,
                 shape1,
                 shape2,
                 prediction_distance,
@@ -253,6 +319,7 @@ pub fn contact_manifolds(
                             -contact.dist,
                             contact_index,
                         )
+                        .with_feature_ids(contact.fid1.into(), contact.fid2.into())
                     })
                     .collect(),
                 index: manifold_index,
@@ -283,6 +350,18 @@ pub enum ClosestPoints {
     OutsideMargin,
 }
 
+impl From<parry::query::ClosestPoints> for ClosestPoints {
+    fn from(closest_points: parry::query::ClosestPoints) -> Self {
+        match closest_points {
+            parry::query::ClosestPoints::Intersecting => Self::Intersecting,
+            parry::query::ClosestPoints::WithinMargin(point1, point2) => {
+                Self::WithinMargin(point1.into(), point2.into())
+            }
+            parry::query::ClosestPoints::Disjoint => Self::OutsideMargin,
+        }
+    }
+}
+
 /// Computes the [`ClosestPoints`] between two [`Collider`]s.
 ///
 /// Returns `Err(UnsupportedShape)` if either of the collider shapes is not supported.
@@ -368,13 +447,7 @@ pub fn closest_points(
         collider2.shape_scaled().0.as_ref(),
         max_distance,
     )
-    .map(|closest_points| match closest_points {
-        parry::query::ClosestPoints::Intersecting => ClosestPoints::Intersecting,
-        parry::query::ClosestPoints::WithinMargin(point1, point2) => {
-            ClosestPoints::WithinMargin(point1.into(), point2.into())
-        }
-        parry::query::ClosestPoints::Disjoint => ClosestPoints::OutsideMargin,
-    })
+    .map(ClosestPoints::from)
 }
 
 /// Computes the minimum distance separating two [`Collider`]s.
@@ -615,3 +688,133 @@ pub fn time_of_impact(
         })
     })
 }
+
+/// The rigid motion of a [`Collider`] used for [nonlinear time of impact](nonlinear_time_of_impact) computation.
+///
+/// The collider moves with a constant linear and angular velocity, rotating around its local center of mass.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColliderMotion {
+    /// The position of the collider at time zero.
+    pub position: Vector,
+    /// The rotation of the collider at time zero.
+    pub rotation: Rotation,
+    /// The local point around which the collider rotates, typically the center of mass.
+    pub local_center: Vector,
+    /// The linear velocity of the collider.
+    pub linear_velocity: LinearVelocity,
+    /// The angular velocity of the collider around `local_center`.
+    pub angular_velocity: AngularVelocity,
+}
+
+impl ColliderMotion {
+    /// Creates a new [`ColliderMotion`] with the given position and rotation and no velocity.
+    pub fn new(position: impl Into<Position>, rotation: impl Into<Rotation>) -> Self {
+        Self {
+            position: position.into().0,
+            rotation: rotation.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the local point around which the collider rotates.
+    pub fn with_local_center(mut self, local_center: Vector) -> Self {
+        self.local_center = local_center;
+        self
+    }
+
+    /// Sets the linear velocity of the collider.
+    pub fn with_linear_velocity(mut self, velocity: impl Into<LinearVelocity>) -> Self {
+        self.linear_velocity = velocity.into();
+        self
+    }
+
+    /// Sets the angular velocity of the collider.
+    pub fn with_angular_velocity(mut self, velocity: impl Into<AngularVelocity>) -> Self {
+        self.angular_velocity = velocity.into();
+        self
+    }
+
+    fn to_parry(self) -> parry::query::NonlinearRigidMotion {
+        #[cfg(feature = "2d")]
+        let angular_velocity = self.angular_velocity.0;
+        #[cfg(feature = "3d")]
+        let angular_velocity = self.angular_velocity.0.into();
+
+        parry::query::NonlinearRigidMotion::new(
+            utils::make_isometry(self.position, self.rotation),
+            self.local_center.into(),
+            self.linear_velocity.0.into(),
+            angular_velocity,
+        )
+    }
+}
+
+/// Computes when two [`Collider`]s undergoing [rigid motion](ColliderMotion) with both linear
+/// and angular velocity hit each other for the first time in the time interval `[start_time, end_time]`.
+///
+/// Unlike [`time_of_impact`], this also takes the rotation of the colliders into account, which makes it
+/// suitable for things like custom continuous collision detection or predicting collisions for AI.
+/// It uses conservative advancement, so it is more expensive than [`time_of_impact`].
+///
+/// If `stop_at_penetration` is false and the colliders are already penetrating at `start_time`,
+/// the penetration is ignored if the colliders are moving apart.
+///
+/// Returns `Ok(None)` if the colliders don't hit each other in the time interval
+/// and `Err(UnsupportedShape)` if either of the collider shapes is not supported.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::{contact_query::*, *};
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::{contact_query::*, *};
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// # {
+/// let car1 = Collider::cuboid(2.0, 1.0, 4.0);
+/// let car2 = Collider::cuboid(2.0, 1.0, 4.0);
+///
+/// let motion1 = ColliderMotion::new(Vec3::NEG_X * 10.0, Quat::default())
+///     .with_linear_velocity(Vec3::X * 5.0)
+///     .with_angular_velocity(Vec3::Y * 0.5);
+/// let motion2 = ColliderMotion::new(Vec3::X * 10.0, Quat::default())
+///     .with_linear_velocity(Vec3::NEG_X * 5.0);
+///
+/// // Will the cars collide within the next 2 seconds?
+/// let result = nonlinear_time_of_impact(&car1, &motion1, &car2, &motion2, 0.0, 2.0, true)
+///     .expect("Unsupported collider shape");
+///
+/// assert!(result.is_some());
+/// # }
+/// ```
+pub fn nonlinear_time_of_impact(
+    collider1: &Collider,
+    motion1: &ColliderMotion,
+    collider2: &Collider,
+    motion2: &ColliderMotion,
+    start_time: Scalar,
+    end_time: Scalar,
+    stop_at_penetration: bool,
+) -> Result<Option<TimeOfImpact>, UnsupportedShape> {
+    parry::query::nonlinear_time_of_impact(
+        &motion1.to_parry(),
+        collider1.shape_scaled().0.as_ref(),
+        &motion2.to_parry(),
+        collider2.shape_scaled().0.as_ref(),
+        start_time,
+        end_time,
+        stop_at_penetration,
+    )
+    .map(|toi| {
+        toi.map(|toi| TimeOfImpact {
+            time_of_impact: toi.toi,
+            point1: toi.witness1.into(),
+            point2: toi.witness2.into(),
+            normal1: toi.normal1.into(),
+            normal2: toi.normal2.into(),
+            status: toi.status,
+        })
+    })
+}
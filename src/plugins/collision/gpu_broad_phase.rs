@@ -0,0 +1,507 @@
+//! An experimental broad phase that collects pairs of potentially colliding moving colliders
+//! using a compute shader.
+//!
+//! See [`GpuBroadPhasePlugin`].
+
+use super::broad_phase::{
+    collect_static_collision_pairs, insertion_sort, sweep_and_prune, AabbInterval, AabbIntervals,
+    BroadPhaseSet, StaticAabbTree,
+};
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{
+            BindGroup, BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry, BindingType, Buffer,
+            BufferBindingType, BufferDescriptor, BufferUsages, CommandEncoderDescriptor,
+            ComputePassDescriptor, ComputePipeline, Maintain, MapMode, PipelineLayoutDescriptor,
+            RawComputePipelineDescriptor, ShaderModuleDescriptor, ShaderSource, ShaderStages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+    },
+};
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] on the GPU.
+///
+/// Like the [`BroadPhasePlugin`], the colliders attached to dynamic and kinematic bodies are sorted
+/// along the x-axis on the CPU, but the sweep that finds intersecting [AABBs](ColliderAabb) runs
+/// in a compute shader with one invocation per collider. The candidate pairs are read back
+/// for the narrow phase on the CPU. Pairs with colliders attached to [static](RigidBody::Static) bodies
+/// are still collected on the CPU using the static bounding volume hierarchy.
+///
+/// This is mainly useful for massive scenes with tens of thousands of moving bodies, like particles or debris,
+/// where the sweep and prune on the CPU becomes the bottleneck. For smaller scenes, the overhead of
+/// uploading the AABBs and waiting for the results is usually larger than the cost of the CPU broad phase.
+///
+/// The plugin requires the [`BroadPhasePlugin`] and replaces the collision pair collection in
+/// [`BroadPhaseSet::CollectCollisions`]. The GPU is accessed through the [`RenderDevice`] and [`RenderQueue`]
+/// resources of the main world, so Bevy's `RenderPlugin` must be added. If they are not available,
+/// the broad phase falls back to the CPU.
+///
+/// ## Caveats
+///
+/// - This plugin is experimental and requires the `gpu-broad-phase` feature.
+/// - The results are read back synchronously, so the physics step waits for the GPU.
+/// This requires a native backend; on the web, reading the results back would never complete.
+/// - The AABBs are converted to `f32` precision on the GPU, even with the `f64` feature.
+///
+/// ## Example
+///
+/// ```ignore
+/// app.add_plugins((DefaultPlugins, PhysicsPlugins::default(), GpuBroadPhasePlugin));
+/// ```
+pub struct GpuBroadPhasePlugin;
+
+impl Plugin for GpuBroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GpuBroadPhase>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule
+            .add_systems(collect_collision_pairs_gpu.in_set(BroadPhaseSet::CollectCollisions));
+    }
+}
+
+/// The compute shader that finds the pairs of intersecting AABBs.
+///
+/// The AABBs are sorted by their minimum x, so each invocation only needs to sweep forward
+/// until it finds an AABB that starts after the end of its own AABB.
+const SWEEP_SHADER: &str = r"
+struct Aabb {
+    min: vec4<f32>,
+    max: vec4<f32>,
+    // Memberships, filters, parent index and whether the body is inactive.
+    data: vec4<u32>,
+}
+
+struct Params {
+    count: u32,
+    capacity: u32,
+    _padding: vec2<u32>,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> aabbs: array<Aabb>;
+@group(0) @binding(2) var<storage, read_write> pair_count: atomic<u32>;
+@group(0) @binding(3) var<storage, read_write> pairs: array<vec2<u32>>;
+
+@compute @workgroup_size(64)
+fn sweep(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if i >= params.count {
+        return;
+    }
+
+    let aabb1 = aabbs[i];
+
+    for (var j = i + 1u; j < params.count; j++) {
+        let aabb2 = aabbs[j];
+
+        // x doesn't intersect, and neither do the AABBs after this one
+        if aabb2.min.x > aabb1.max.x {
+            break;
+        }
+
+        // No collisions between bodies that haven't moved or colliders with incompatible layers or colliders with the same parent
+        if (aabb1.data.w != 0u && aabb2.data.w != 0u)
+            || (aabb1.data.x & aabb2.data.y) == 0u
+            || (aabb2.data.x & aabb1.data.y) == 0u
+            || aabb1.data.z == aabb2.data.z {
+            continue;
+        }
+
+        // y or z doesn't intersect
+        if any(aabb1.min.yz > aabb2.max.yz) || any(aabb1.max.yz < aabb2.min.yz) {
+            continue;
+        }
+
+        let index = atomicAdd(&pair_count, 1u);
+        if index < params.capacity {
+            pairs[index] = vec2<u32>(i, j);
+        }
+    }
+}
+";
+
+/// The number of invocations in a workgroup of the [`SWEEP_SHADER`].
+const WORKGROUP_SIZE: u32 = 64;
+
+/// The size of an AABB in the GPU buffer in bytes.
+const AABB_SIZE: u64 = 48;
+
+/// The size of a collision pair in the GPU buffer in bytes.
+const PAIR_SIZE: u64 = 8;
+
+/// The GPU resources used by the [`GpuBroadPhasePlugin`]. They are created lazily when the broad phase first runs.
+#[derive(Resource, Default)]
+pub(crate) struct GpuBroadPhase {
+    pipeline: Option<SweepPipeline>,
+    buffers: Option<SweepBuffers>,
+    /// The AABB data that is uploaded to the GPU, reused across frames.
+    aabb_data: Vec<u8>,
+    /// The interval indices of the pairs that were read back from the GPU, reused across frames.
+    pair_indices: Vec<(u32, u32)>,
+}
+
+struct SweepPipeline {
+    layout: BindGroupLayout,
+    pipeline: ComputePipeline,
+}
+
+struct SweepBuffers {
+    aabb_capacity: u64,
+    pair_capacity: u64,
+    params: Buffer,
+    aabbs: Buffer,
+    pair_count: Buffer,
+    pairs: Buffer,
+    staging: Buffer,
+    bind_group: BindGroup,
+}
+
+impl GpuBroadPhase {
+    /// Finds the pairs of intersecting AABBs in the given intervals, which must be sorted by their minimum x.
+    fn sweep(
+        &mut self,
+        device: &RenderDevice,
+        queue: &RenderQueue,
+        intervals: &AabbIntervals,
+        broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    ) {
+        if intervals.0.len() < 2 {
+            return;
+        }
+
+        self.aabb_data.clear();
+        for interval in intervals.0.iter() {
+            write_aabb(&mut self.aabb_data, interval);
+        }
+
+        let pipeline = self
+            .pipeline
+            .get_or_insert_with(|| SweepPipeline::new(device));
+
+        let count = intervals.0.len() as u64;
+        let mut pair_capacity = self
+            .buffers
+            .as_ref()
+            .map_or(4 * count, |buffers| buffers.pair_capacity.max(4 * count));
+
+        // If there are more pairs than fit in the buffer, grow it and sweep again.
+        let pair_count = loop {
+            let buffers = match self.buffers.take() {
+                Some(buffers)
+                    if buffers.aabb_capacity >= count && buffers.pair_capacity >= pair_capacity =>
+                {
+                    buffers
+                }
+                _ => SweepBuffers::new(device, pipeline, count.next_power_of_two(), pair_capacity),
+            };
+
+            let pair_count = buffers.dispatch(device, queue, pipeline, &self.aabb_data, count);
+            pair_capacity = buffers.pair_capacity;
+            self.buffers = Some(buffers);
+
+            if pair_count <= pair_capacity {
+                break pair_count;
+            }
+
+            pair_capacity = pair_count.next_power_of_two();
+        };
+
+        let Some(buffers) = &self.buffers else {
+            return;
+        };
+
+        self.pair_indices.clear();
+        buffers.read_pairs(device, queue, pair_count, |index1, index2| {
+            self.pair_indices.push((index1, index2));
+        });
+
+        // The pairs are written in an arbitrary order on the GPU, so sort them to get the same order
+        // as the sweep and prune on the CPU. This keeps the simulation deterministic.
+        self.pair_indices.sort_unstable();
+
+        broad_collision_pairs.extend(self.pair_indices.iter().map(|&(index1, index2)| {
+            (
+                intervals.0[index1 as usize].0,
+                intervals.0[index2 as usize].0,
+            )
+        }));
+    }
+}
+
+impl SweepPipeline {
+    fn new(device: &RenderDevice) -> Self {
+        let storage_entry = |binding: u32, read_only: bool| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let layout = device.create_bind_group_layout(
+            "gpu_broad_phase_layout",
+            &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                storage_entry(1, true),
+                storage_entry(2, false),
+                storage_entry(3, false),
+            ],
+        );
+
+        let shader = device
+            .wgpu_device()
+            .create_shader_module(ShaderModuleDescriptor {
+                label: Some("gpu_broad_phase_shader"),
+                source: ShaderSource::Wgsl(SWEEP_SHADER.into()),
+            });
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("gpu_broad_phase_pipeline_layout"),
+            bind_group_layouts: &[&*layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&RawComputePipelineDescriptor {
+            label: Some("gpu_broad_phase_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "sweep",
+        });
+
+        Self { layout, pipeline }
+    }
+}
+
+impl SweepBuffers {
+    fn new(
+        device: &RenderDevice,
+        pipeline: &SweepPipeline,
+        aabb_capacity: u64,
+        pair_capacity: u64,
+    ) -> Self {
+        let buffer = |label: &str, size: u64, usage: BufferUsages| {
+            device.create_buffer(&BufferDescriptor {
+                label: Some(label),
+                size,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+
+        let params = buffer(
+            "gpu_broad_phase_params",
+            16,
+            BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        );
+        let aabbs = buffer(
+            "gpu_broad_phase_aabbs",
+            aabb_capacity * AABB_SIZE,
+            BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        );
+        let pair_count = buffer(
+            "gpu_broad_phase_pair_count",
+            4,
+            BufferUsages::STORAGE | BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
+        );
+        let pairs = buffer(
+            "gpu_broad_phase_pairs",
+            pair_capacity * PAIR_SIZE,
+            BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+        );
+        let staging = buffer(
+            "gpu_broad_phase_staging",
+            pair_capacity * PAIR_SIZE,
+            BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        );
+
+        let bind_group = device.create_bind_group(
+            "gpu_broad_phase_bind_group",
+            &pipeline.layout,
+            &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: params.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: aabbs.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: pair_count.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: pairs.as_entire_binding(),
+                },
+            ],
+        );
+
+        Self {
+            aabb_capacity,
+            pair_capacity,
+            params,
+            aabbs,
+            pair_count,
+            pairs,
+            staging,
+            bind_group,
+        }
+    }
+
+    /// Uploads the AABBs, runs the sweep and returns the number of pairs that were found.
+    ///
+    /// The number can be larger than the capacity of the pair buffer, in which case only some of the pairs were stored.
+    fn dispatch(
+        &self,
+        device: &RenderDevice,
+        queue: &RenderQueue,
+        pipeline: &SweepPipeline,
+        aabb_data: &[u8],
+        count: u64,
+    ) -> u64 {
+        let params = [count as u32, self.pair_capacity as u32, 0, 0];
+        queue.write_buffer(&self.params, 0, &u32s_to_bytes(&params));
+        queue.write_buffer(&self.aabbs, 0, aabb_data);
+        queue.write_buffer(&self.pair_count, 0, &0u32.to_le_bytes());
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("gpu_broad_phase_encoder"),
+        });
+        {
+            let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+                label: Some("gpu_broad_phase_sweep"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&pipeline.pipeline);
+            pass.set_bind_group(0, &self.bind_group, &[]);
+            pass.dispatch_workgroups((count as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&self.pair_count, 0, &self.staging, 0, 4);
+        queue.submit([encoder.finish()]);
+
+        let mut pair_count = 0;
+        self.read_staging(device, 4, |bytes| {
+            pair_count = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+        });
+        pair_count
+    }
+
+    /// Reads back the given number of pairs and calls `callback` with the interval indices of each pair.
+    fn read_pairs(
+        &self,
+        device: &RenderDevice,
+        queue: &RenderQueue,
+        pair_count: u64,
+        mut callback: impl FnMut(u32, u32),
+    ) {
+        if pair_count == 0 {
+            return;
+        }
+
+        let size = pair_count * PAIR_SIZE;
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("gpu_broad_phase_read_pairs"),
+        });
+        encoder.copy_buffer_to_buffer(&self.pairs, 0, &self.staging, 0, size);
+        queue.submit([encoder.finish()]);
+
+        self.read_staging(device, size, |bytes| {
+            for pair in bytes.chunks_exact(PAIR_SIZE as usize) {
+                callback(
+                    u32::from_le_bytes([pair[0], pair[1], pair[2], pair[3]]),
+                    u32::from_le_bytes([pair[4], pair[5], pair[6], pair[7]]),
+                );
+            }
+        });
+    }
+
+    /// Maps the first `size` bytes of the staging buffer, blocking until the GPU has finished,
+    /// and calls `callback` with the mapped bytes.
+    fn read_staging(&self, device: &RenderDevice, size: u64, callback: impl FnOnce(&[u8])) {
+        let slice = self.staging.slice(0..size);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        device.wgpu_device().poll(Maintain::Wait);
+
+        if let Ok(Ok(())) = receiver.recv() {
+            callback(&slice.get_mapped_range()[..]);
+            self.staging.unmap();
+        }
+    }
+}
+
+/// Appends the GPU representation of the given AABB interval to `data`.
+fn write_aabb(data: &mut Vec<u8>, interval: &AabbInterval) {
+    let (_, parent, aabb, layers, is_inactive) = interval;
+
+    #[cfg(feature = "2d")]
+    let (min, max) = (
+        aabb.min.f32().extend(0.0).extend(0.0),
+        aabb.max.f32().extend(0.0).extend(0.0),
+    );
+    #[cfg(feature = "3d")]
+    let (min, max) = (aabb.min.f32().extend(0.0), aabb.max.f32().extend(0.0));
+
+    for value in min.to_array().into_iter().chain(max.to_array()) {
+        data.extend(value.to_le_bytes());
+    }
+    data.extend(u32s_to_bytes(&[
+        layers.memberships.0,
+        layers.filters.0,
+        // Entity indices are unique among live entities, so they can be used to compare the parents.
+        parent.get().index(),
+        *is_inactive as u32,
+    ]));
+}
+
+fn u32s_to_bytes(values: &[u32]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+/// Collects bodies that are potentially colliding, using the GPU for the colliders in the [`AabbIntervals`].
+///
+/// Falls back to the sweep and prune on the CPU if the [`RenderDevice`] or [`RenderQueue`] is not available.
+fn collect_collision_pairs_gpu(
+    mut intervals: ResMut<AabbIntervals>,
+    static_tree: Res<StaticAabbTree>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut gpu_broad_phase: ResMut<GpuBroadPhase>,
+    device: Option<Res<RenderDevice>>,
+    queue: Option<Res<RenderQueue>>,
+) {
+    if let (Some(device), Some(queue)) = (device, queue) {
+        // Sort on the CPU like the sweep and prune. The intervals are nearly sorted from the previous frame,
+        // so this is cheap compared to the sweep itself.
+        insertion_sort(&mut intervals.0, |a, b| a.2.min.x > b.2.min.x);
+        broad_collision_pairs.0.clear();
+        gpu_broad_phase.sweep(&device, &queue, &intervals, &mut broad_collision_pairs.0);
+    } else {
+        sweep_and_prune(&mut intervals, &mut broad_collision_pairs.0);
+    }
+
+    collect_static_collision_pairs(&intervals, &static_tree, &mut broad_collision_pairs.0);
+}
@@ -21,6 +21,8 @@ pub mod collider_backend;
 ))]
 pub mod contact_query;
 pub mod contact_reporting;
+#[cfg(feature = "gpu-broad-phase")]
+pub mod gpu_broad_phase;
 pub mod narrow_phase;
 
 use crate::prelude::*;
@@ -86,6 +88,7 @@ use indexmap::IndexMap;
 /// However, the public methods only use the current frame's collisions. To access the internal data structure,
 /// you can use [`get_internal`](Self::get_internal) or [`get_internal_mut`](Self::get_internal_mut).
 #[derive(Resource, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Collisions(IndexMap<(Entity, Entity), Contacts, fxhash::FxBuildHasher>);
 
 impl Collisions {
@@ -438,6 +441,18 @@ pub struct ContactData {
     pub tangent_impulse: Scalar,
     /// The index of the contact in a contact manifold if it is in one.
     pub index: usize,
+    /// The feature of the first collider's shape that is in contact, like a vertex, an edge or a face.
+    ///
+    /// Together with [`feature_id2`](Self::feature_id2), this identifies the contact in a way
+    /// that stays the same across frames as long as the same features of the shapes are in contact.
+    /// This can be used to track individual contact points for gameplay purposes, for example to trigger
+    /// effects only when a new contact point is created.
+    pub feature_id1: PackedFeatureId,
+    /// The feature of the second collider's shape that is in contact, like a vertex, an edge or a face.
+    ///
+    /// Together with [`feature_id1`](Self::feature_id1), this identifies the contact in a way
+    /// that stays the same across frames as long as the same features of the shapes are in contact.
+    pub feature_id2: PackedFeatureId,
 }
 
 impl ContactData {
@@ -461,9 +476,31 @@ impl ContactData {
             normal_impulse: 0.0,
             tangent_impulse: 0.0,
             index,
+            feature_id1: PackedFeatureId::UNKNOWN,
+            feature_id2: PackedFeatureId::UNKNOWN,
         }
     }
 
+    /// Sets the [feature IDs](PackedFeatureId) of the shapes that are in contact.
+    pub fn with_feature_ids(
+        mut self,
+        feature_id1: PackedFeatureId,
+        feature_id2: PackedFeatureId,
+    ) -> Self {
+        self.feature_id1 = feature_id1;
+        self.feature_id2 = feature_id2;
+        self
+    }
+
+    /// Returns an identifier of the contact that is stable across frames, computed from the
+    /// [feature IDs](PackedFeatureId) of the shapes in contact.
+    ///
+    /// Returns `None` if either of the features is unknown.
+    pub fn id(&self) -> Option<(PackedFeatureId, PackedFeatureId)> {
+        (!self.feature_id1.is_unknown() && !self.feature_id2.is_unknown())
+            .then_some((self.feature_id1, self.feature_id2))
+    }
+
     /// The force corresponding to the normal impulse applied over `delta_time`.
     ///
     /// Because contacts are solved over several substeps, `delta_time` should
@@ -503,3 +540,70 @@ impl ContactData {
         rotation.rotate(self.normal2)
     }
 }
+
+/// An identifier of a geometric feature of a shape, like a vertex, an edge or a face,
+/// packed into a single `u32`.
+///
+/// The two most significant bits store the type of the feature, and the rest of the bits store
+/// the index of the feature in the shape. The layout matches Parry's `PackedFeatureId`.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PackedFeatureId(pub u32);
+
+impl PackedFeatureId {
+    /// An unknown feature.
+    pub const UNKNOWN: Self = Self(0);
+
+    const CODE_MASK: u32 = 0x3fff_ffff;
+    const HEADER_MASK: u32 = !Self::CODE_MASK;
+    const HEADER_VERTEX: u32 = 0b01 << 30;
+    const HEADER_EDGE: u32 = 0b10 << 30;
+    const HEADER_FACE: u32 = 0b11 << 30;
+
+    /// Creates a feature ID for the vertex with the given index.
+    pub const fn vertex(index: u32) -> Self {
+        Self(Self::HEADER_VERTEX | (index & Self::CODE_MASK))
+    }
+
+    /// Creates a feature ID for the edge with the given index.
+    pub const fn edge(index: u32) -> Self {
+        Self(Self::HEADER_EDGE | (index & Self::CODE_MASK))
+    }
+
+    /// Creates a feature ID for the face with the given index.
+    pub const fn face(index: u32) -> Self {
+        Self(Self::HEADER_FACE | (index & Self::CODE_MASK))
+    }
+
+    /// Returns the index of the feature in the shape.
+    pub const fn index(&self) -> u32 {
+        self.0 & Self::CODE_MASK
+    }
+
+    /// Returns `true` if the feature is a vertex.
+    pub const fn is_vertex(&self) -> bool {
+        self.0 & Self::HEADER_MASK == Self::HEADER_VERTEX
+    }
+
+    /// Returns `true` if the feature is an edge.
+    pub const fn is_edge(&self) -> bool {
+        self.0 & Self::HEADER_MASK == Self::HEADER_EDGE
+    }
+
+    /// Returns `true` if the feature is a face.
+    pub const fn is_face(&self) -> bool {
+        self.0 & Self::HEADER_MASK == Self::HEADER_FACE
+    }
+
+    /// Returns `true` if the feature is unknown.
+    pub const fn is_unknown(&self) -> bool {
+        self.0 == Self::UNKNOWN.0
+    }
+}
+
+#[cfg(any(feature = "parry-f32", feature = "parry-f64"))]
+impl From<parry::shape::PackedFeatureId> for PackedFeatureId {
+    fn from(value: parry::shape::PackedFeatureId) -> Self {
+        Self(value.0)
+    }
+}
@@ -5,9 +5,9 @@
 use std::marker::PhantomData;
 
 use crate::prelude::*;
-use bevy::ecs::query::Has;
 #[cfg(feature = "parallel")]
-use bevy::tasks::{ComputeTaskPool, ParallelSlice};
+use bevy::tasks::{ComputeTaskPool, ParallelSliceMut};
+use bevy::{ecs::query::Has, utils::HashMap};
 
 /// Computes contacts between entities.
 ///
@@ -40,6 +40,7 @@ impl<C: AnyCollider> Plugin for NarrowPhasePlugin<C> {
         app.init_resource::<NarrowPhaseInitialized>()
             .init_resource::<NarrowPhaseConfig>()
             .init_resource::<Collisions>()
+            .init_resource::<ContactManifoldCaches<C>>()
             .register_type::<NarrowPhaseConfig>();
 
         app.configure_sets(
@@ -129,6 +130,59 @@ impl Default for NarrowPhaseConfig {
     }
 }
 
+/// The [`ContactManifoldCache`]s of the collision pairs processed by the [narrow phase](NarrowPhasePlugin)
+/// for colliders of type `C`, used to warm start collision detection on the next substep.
+///
+/// Only the caches of the pairs that were processed on the latest substep are kept.
+/// The caches are part of [`PhysicsSnapshot`]s so that restoring a snapshot also restores the warm starting data.
+#[derive(Resource)]
+pub struct ContactManifoldCaches<C: AnyCollider> {
+    caches: HashMap<(Entity, Entity), ContactManifoldCache>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: AnyCollider> Default for ContactManifoldCaches<C> {
+    fn default() -> Self {
+        Self {
+            caches: HashMap::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: AnyCollider> Clone for ContactManifoldCaches<C> {
+    fn clone(&self) -> Self {
+        Self {
+            caches: self.caches.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<C: AnyCollider> ContactManifoldCaches<C> {
+    /// Returns the cache of the given collision pair, if it was processed on the latest substep.
+    ///
+    /// The entities of a pair are ordered by their [`StableId`] sort keys.
+    pub fn get(&self, entity1: Entity, entity2: Entity) -> Option<&ContactManifoldCache> {
+        self.caches.get(&(entity1, entity2))
+    }
+
+    /// Returns the number of cached collision pairs.
+    pub fn len(&self) -> usize {
+        self.caches.len()
+    }
+
+    /// Returns `true` if no collision pairs are cached.
+    pub fn is_empty(&self) -> bool {
+        self.caches.is_empty()
+    }
+
+    /// Removes all caches, so that collision detection for all pairs starts from scratch on the next substep.
+    pub fn clear(&mut self) {
+        self.caches.clear();
+    }
+}
+
 /// System sets for systems running in [`SubstepSet::NarrowPhase`].
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum NarrowPhaseSet {
@@ -141,6 +195,9 @@ pub enum NarrowPhaseSet {
 }
 
 /// Computes contacts based on [`BroadCollisionPairs`] and adds them to [`Collisions`].
+///
+/// A [`ContactManifoldCache`] is kept for each processed pair across substeps and frames in [`ContactManifoldCaches`]
+/// so that the collision algorithms can be warm started using the results of the previous step.
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 pub fn collect_collisions<C: AnyCollider>(
@@ -150,9 +207,15 @@ pub fn collect_collisions<C: AnyCollider>(
         Ref<Rotation>,
         &C,
     )>,
+    stable_ids: Query<&StableId>,
+    collider_parents: Query<&ColliderParent>,
+    bodies: Query<(&RigidBody, Has<Sleeping>)>,
     broad_collision_pairs: Res<BroadCollisionPairs>,
     mut collisions: ResMut<Collisions>,
     narrow_phase_config: Res<NarrowPhaseConfig>,
+    mut collision_pairs: Local<Vec<(Entity, Entity, ContactManifoldCache)>>,
+    mut manifold_caches: ResMut<ContactManifoldCaches<C>>,
+    #[cfg(not(feature = "parallel"))] mut new_collisions: Local<Vec<Contacts>>,
 ) {
     if query.is_empty() {
         return;
@@ -172,25 +235,68 @@ pub fn collect_collisions<C: AnyCollider>(
             false
         }
     });
-    let broad_collision_pairs = stationary_collisions
-        .chain(broad_collision_pairs.0.iter())
-        .collect::<Vec<_>>();
+    let sort_key = |entity: Entity| StableId::sort_key(stable_ids.get(entity).ok(), entity);
+
+    // Colliders are inactive if the bodies they are attached to are static or sleeping.
+    let is_inactive = |entity: Entity| {
+        let body = collider_parents
+            .get(entity)
+            .map_or(entity, |parent| parent.get());
+        bodies
+            .get(body)
+            .is_ok_and(|(rb, is_sleeping)| rb.is_static() || is_sleeping)
+    };
+
+    // Order the entities of each pair by their stable keys so that the contact data
+    // doesn't depend on the order in which the broad phase found the pair.
+    // Contacts between inactive colliders are kept as they are, so they are skipped.
+    // The pairs are collected into a buffer that is reused across frames to avoid reallocating it.
+    collision_pairs.clear();
+    collision_pairs.extend(
+        stationary_collisions
+            .chain(broad_collision_pairs.0.iter())
+            .filter(|&&(entity1, entity2)| !(is_inactive(entity1) && is_inactive(entity2)))
+            .map(|&(entity1, entity2)| {
+                let (entity1, entity2) = if sort_key(entity2) < sort_key(entity1) {
+                    (entity2, entity1)
+                } else {
+                    (entity1, entity2)
+                };
+                (entity1, entity2, ContactManifoldCache::default())
+            }),
+    );
+
+    // Sort the pairs by their stable keys so that they are processed and merged in a fixed order,
+    // regardless of the order of the broad phase pairs. Pairs that are both stationary and found
+    // by the broad phase are only processed once.
+    collision_pairs
+        .sort_unstable_by_key(|&(entity1, entity2, _)| (sort_key(entity1), sort_key(entity2)));
+    collision_pairs.dedup_by_key(|&mut (entity1, entity2, _)| (entity1, entity2));
+
+    // Warm start the pairs using their manifold caches from the previous step.
+    for (entity1, entity2, cache) in collision_pairs.iter_mut() {
+        if let Some(previous) = manifold_caches.caches.remove(&(*entity1, *entity2)) {
+            *cache = previous;
+        }
+    }
 
     #[cfg(feature = "parallel")]
     {
         let pool = ComputeTaskPool::get();
-        // TODO: Verify if `par_splat_map` is deterministic. If not, sort the collisions.
-        let new_collisions = broad_collision_pairs
-            .iter()
-            .par_splat_map(pool, None, |chunks| {
+        // `par_splat_map` returns the results of the chunks in the order of the input,
+        // so the merged contacts are in the same order as the collision pairs
+        // regardless of the number of threads or how the tasks are scheduled.
+        let new_collisions = collision_pairs
+            .par_splat_map_mut(pool, None, |chunks| {
                 let mut new_collisions: Vec<Contacts> = vec![];
-                for &(entity1, entity2) in chunks {
+                for (entity1, entity2, cache) in chunks {
                     process_collision_pair(
                         *entity1,
                         *entity2,
                         &query,
                         &collisions,
                         &narrow_phase_config,
+                        cache,
                         |contacts| {
                             new_collisions.push(contacts);
                         },
@@ -205,25 +311,44 @@ pub fn collect_collisions<C: AnyCollider>(
     }
     #[cfg(not(feature = "parallel"))]
     {
-        let mut new_collisions = vec![];
-        for &(entity1, entity2) in broad_collision_pairs {
+        new_collisions.clear();
+        for (entity1, entity2, cache) in collision_pairs.iter_mut() {
             process_collision_pair(
-                entity1,
-                entity2,
+                *entity1,
+                *entity2,
                 &query,
                 &collisions,
                 &narrow_phase_config,
+                cache,
                 |contacts| {
                     new_collisions.push(contacts);
                 },
             );
         }
 
-        collisions.extend(new_collisions);
+        collisions.extend(new_collisions.drain(..));
     }
+
+    // Keep the caches of the processed pairs for the next step.
+    // Caches of pairs that were not processed are dropped.
+    manifold_caches.caches.clear();
+    manifold_caches.caches.extend(
+        collision_pairs
+            .drain(..)
+            .map(|(entity1, entity2, cache)| ((entity1, entity2), cache)),
+    );
+
+    // Sort the collisions by the stable keys of the entity pairs so that contacts are solved
+    // in the same order regardless of spawn order or query iteration order.
+    collisions
+        .get_internal_mut()
+        .sort_by(|&(a1, a2), _, &(b1, b2), _| {
+            (sort_key(a1), sort_key(a2)).cmp(&(sort_key(b1), sort_key(b2)))
+        });
 }
 
 /// Helper method that calculates the intersection between two colliders to determine if they are in contact.
+#[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 fn process_collision_pair<C: AnyCollider, F>(
     entity1: Entity,
@@ -236,6 +361,7 @@ fn process_collision_pair<C: AnyCollider, F>(
     )>,
     collisions: &ResMut<Collisions>,
     narrow_phase_config: &Res<NarrowPhaseConfig>,
+    cache: &mut ContactManifoldCache,
     mut handle_collision: F,
 ) where
     F: FnMut(Contacts),
@@ -255,13 +381,14 @@ fn process_collision_pair<C: AnyCollider, F>(
             during_current_frame: true,
             during_current_substep: true,
             during_previous_frame: previous_contact.map_or(false, |c| c.during_previous_frame),
-            manifolds: collider1.contact_manifolds(
+            manifolds: collider1.contact_manifolds_with_cache(
                 collider2,
                 position1,
                 *rotation1,
                 position2,
                 *rotation2,
                 narrow_phase_config.prediction_distance,
+                cache,
             ),
             total_normal_impulse: 0.0,
             total_tangent_impulse: 0.0,
@@ -0,0 +1,323 @@
+//! Exporting the graph of contacts and joints between bodies for offline inspection.
+//!
+//! See [`ConstraintGraph`].
+
+use std::fmt::Write;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A graph of the contacts and [joints](joints) between bodies at a given point in time,
+/// with bodies as nodes and constraints as edges.
+///
+/// The graph can be [captured](ConstraintGraph::capture) from a world and exported in the
+/// [DOT](ConstraintGraph::to_dot) format used by Graphviz or as [JSON](ConstraintGraph::to_json).
+/// This is useful for inspecting pathological configurations like huge piles or tangled joint chains
+/// offline, for example when reporting issues.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn export_graph(world: &mut World) {
+///     let graph = ConstraintGraph::capture(world);
+///     std::fs::write("constraints.dot", graph.to_dot()).unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstraintGraph {
+    /// The bodies that are connected by at least one constraint, sorted by entity.
+    pub nodes: Vec<ConstraintGraphNode>,
+    /// The contacts and joints between the bodies.
+    pub edges: Vec<ConstraintGraphEdge>,
+}
+
+/// A body in a [`ConstraintGraph`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstraintGraphNode {
+    /// The entity of the body.
+    pub entity: Entity,
+    /// The type of the body, or `None` if the entity is a [collider](Collider) that isn't attached to a body.
+    pub rigid_body: Option<RigidBody>,
+    /// True if the body is [`Sleeping`].
+    pub sleeping: bool,
+    /// The [`Name`] of the entity, if it has one.
+    pub name: Option<String>,
+}
+
+/// A contact or [joint](joints) between two bodies in a [`ConstraintGraph`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstraintGraphEdge {
+    /// The first body.
+    pub entity1: Entity,
+    /// The second body.
+    pub entity2: Entity,
+    /// The type of the constraint.
+    pub kind: ConstraintKind,
+    /// The magnitude of the impulse applied by the constraint.
+    ///
+    /// For contacts, this is computed from the [total normal and tangent impulses](Contacts::total_normal_impulse)
+    /// of the contacts between the colliders. For joints, this is the magnitude of the force exerted by the joint
+    /// multiplied by the delta time of a substep, which is the impulse applied during the latest substep.
+    pub impulse: Scalar,
+}
+
+/// The type of a [`ConstraintGraphEdge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConstraintKind {
+    /// Contacts between two [colliders](Collider).
+    Contact,
+    /// A [`FixedJoint`].
+    FixedJoint,
+    /// A [`DistanceJoint`].
+    DistanceJoint,
+    /// A [`PrismaticJoint`].
+    PrismaticJoint,
+    /// A [`RevoluteJoint`].
+    RevoluteJoint,
+    /// A [`SphericalJoint`].
+    SphericalJoint,
+}
+
+impl ConstraintKind {
+    /// Returns the name of the constraint type in snake case.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Contact => "contact",
+            Self::FixedJoint => "fixed_joint",
+            Self::DistanceJoint => "distance_joint",
+            Self::PrismaticJoint => "prismatic_joint",
+            Self::RevoluteJoint => "revolute_joint",
+            Self::SphericalJoint => "spherical_joint",
+        }
+    }
+}
+
+impl ConstraintGraph {
+    /// Captures the contacts and joints between bodies in the given `world`.
+    ///
+    /// Only contacts that are touching during the current frame are included.
+    /// Contacts are attached to the bodies of the colliders, so a contact between two colliders
+    /// that belong to the same body doesn't exist and contacts of child colliders connect their parent bodies.
+    pub fn capture(world: &mut World) -> Self {
+        let delta_secs = world
+            .get_resource::<Time<Substeps>>()
+            .map_or(0.0, |time| time.delta().as_secs_adjusted());
+        let mut edges = vec![];
+
+        if let Some(collisions) = world.get_resource::<Collisions>() {
+            let contacts = collisions
+                .iter()
+                .filter(|contacts| contacts.during_current_frame)
+                .map(|contacts| {
+                    let impulse = contacts
+                        .total_normal_impulse
+                        .hypot(contacts.total_tangent_impulse);
+                    (contacts.entity1, contacts.entity2, impulse)
+                })
+                .collect::<Vec<_>>();
+
+            for (collider1, collider2, impulse) in contacts {
+                let body_of = |collider: Entity| {
+                    world
+                        .get::<ColliderParent>(collider)
+                        .map_or(collider, |parent| parent.get())
+                };
+                edges.push(ConstraintGraphEdge {
+                    entity1: body_of(collider1),
+                    entity2: body_of(collider2),
+                    kind: ConstraintKind::Contact,
+                    impulse,
+                });
+            }
+        }
+
+        capture_joints::<FixedJoint>(
+            world,
+            ConstraintKind::FixedJoint,
+            delta_secs,
+            &mut edges,
+            |j| j.force,
+        );
+        capture_joints::<DistanceJoint>(
+            world,
+            ConstraintKind::DistanceJoint,
+            delta_secs,
+            &mut edges,
+            |j| j.force,
+        );
+        capture_joints::<PrismaticJoint>(
+            world,
+            ConstraintKind::PrismaticJoint,
+            delta_secs,
+            &mut edges,
+            |j| j.force,
+        );
+        capture_joints::<RevoluteJoint>(
+            world,
+            ConstraintKind::RevoluteJoint,
+            delta_secs,
+            &mut edges,
+            |j| j.force,
+        );
+        capture_joints::<SphericalJoint>(
+            world,
+            ConstraintKind::SphericalJoint,
+            delta_secs,
+            &mut edges,
+            |j| j.force,
+        );
+
+        let mut entities = edges
+            .iter()
+            .flat_map(|edge| [edge.entity1, edge.entity2])
+            .collect::<Vec<_>>();
+        entities.sort();
+        entities.dedup();
+
+        let nodes = entities
+            .into_iter()
+            .map(|entity| ConstraintGraphNode {
+                entity,
+                rigid_body: world.get::<RigidBody>(entity).copied(),
+                sleeping: world.get::<Sleeping>(entity).is_some(),
+                name: world.get::<Name>(entity).map(|name| name.to_string()),
+            })
+            .collect();
+
+        Self { nodes, edges }
+    }
+
+    /// Returns the graph in the [DOT](https://graphviz.org/doc/info/lang.html) format used by Graphviz.
+    ///
+    /// Sleeping bodies are drawn with dashed outlines, and joints are drawn with bold lines.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph constraints {\n");
+
+        for node in &self.nodes {
+            let mut label = format!("{:?}", node.entity);
+            if let Some(name) = &node.name {
+                label = format!("{} ({label})", escape_string(name));
+            }
+            if let Some(rigid_body) = node.rigid_body {
+                label = format!("{label}\\n{rigid_body:?}");
+            }
+            let style = if node.sleeping { "dashed" } else { "solid" };
+            let _ = writeln!(
+                dot,
+                "    \"{:?}\" [label=\"{label}\", style={style}];",
+                node.entity,
+            );
+        }
+
+        for edge in &self.edges {
+            let style = if edge.kind == ConstraintKind::Contact {
+                "solid"
+            } else {
+                "bold"
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{:?}\" -- \"{:?}\" [label=\"{} {:.4}\", style={style}];",
+                edge.entity1,
+                edge.entity2,
+                edge.kind.name(),
+                edge.impulse,
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns the graph as a JSON object with `nodes` and `edges` arrays.
+    ///
+    /// Entities are represented by their [bits](Entity::to_bits), and the constraint types by their [names](ConstraintKind::name).
+    pub fn to_json(&self) -> String {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                format!(
+                    "{{\"entity\":{},\"rigid_body\":{},\"sleeping\":{},\"name\":{}}}",
+                    node.entity.to_bits(),
+                    node.rigid_body
+                        .map_or("null".to_string(), |rb| format!("\"{rb:?}\"")),
+                    node.sleeping,
+                    node.name.as_ref().map_or("null".to_string(), |name| {
+                        format!("\"{}\"", escape_string(name))
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let edges = self
+            .edges
+            .iter()
+            .map(|edge| {
+                format!(
+                    "{{\"entity1\":{},\"entity2\":{},\"kind\":\"{}\",\"impulse\":{}}}",
+                    edge.entity1.to_bits(),
+                    edge.entity2.to_bits(),
+                    edge.kind.name(),
+                    if edge.impulse.is_finite() {
+                        edge.impulse.to_string()
+                    } else {
+                        "null".to_string()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "{{\"nodes\":[{}],\"edges\":[{}]}}",
+            nodes.join(","),
+            edges.join(",")
+        )
+    }
+}
+
+/// Adds an edge for each joint of type `T` in the `world` to `edges`.
+fn capture_joints<T: Joint + XpbdConstraint<2>>(
+    world: &mut World,
+    kind: ConstraintKind,
+    delta_secs: Scalar,
+    edges: &mut Vec<ConstraintGraphEdge>,
+    force: impl Fn(&T) -> Vector,
+) {
+    let mut query = world.query::<&T>();
+    for joint in query.iter(world) {
+        let [entity1, entity2] = joint.entities();
+        edges.push(ConstraintGraphEdge {
+            entity1,
+            entity2,
+            kind,
+            impulse: force(joint).length() * delta_secs,
+        });
+    }
+}
+
+/// Escapes quotes, backslashes and control characters in a string for DOT and JSON strings.
+fn escape_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,295 @@
+//! Applies remotely received body states by blending them in over time instead of teleporting.
+//!
+//! See [`StateCorrectionPlugin`] and [`StateCorrection`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Applies [`StateCorrection`]s to rigid bodies, blending the [`Position`] and [`Rotation`] of bodies
+/// towards remotely received states over a configurable window.
+///
+/// The corrections are applied at the start of each physics step, before [`PhysicsStepSet::BroadPhase`].
+pub struct StateCorrectionPlugin;
+
+impl Plugin for StateCorrectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<StateCorrection>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(apply_state_corrections.before(PhysicsStepSet::BroadPhase));
+    }
+}
+
+/// The state of a rigid body received from a remote peer, like a server in a networked game.
+///
+/// The state can be from the past, for example because of network latency. In that case, the
+/// [`age`](Self::age) can be set so that the state is extrapolated to the present time using its velocity.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoteBodyState {
+    /// The [`Position`] of the body.
+    pub position: Position,
+    /// The [`Rotation`] of the body.
+    pub rotation: Rotation,
+    /// The [`LinearVelocity`] of the body.
+    pub linear_velocity: LinearVelocity,
+    /// The [`AngularVelocity`] of the body.
+    pub angular_velocity: AngularVelocity,
+    /// How long ago the state was simulated in seconds. Defaults to `0.0`.
+    pub age: Scalar,
+}
+
+impl RemoteBodyState {
+    /// Creates a new [`RemoteBodyState`] for the present time.
+    pub fn new(
+        position: Position,
+        rotation: Rotation,
+        linear_velocity: LinearVelocity,
+        angular_velocity: AngularVelocity,
+    ) -> Self {
+        Self {
+            position,
+            rotation,
+            linear_velocity,
+            angular_velocity,
+            age: 0.0,
+        }
+    }
+
+    /// Sets how long ago the state was simulated in seconds.
+    pub fn with_age(mut self, age: Scalar) -> Self {
+        self.age = age;
+        self
+    }
+
+    /// Returns the position and rotation of the state extrapolated to the present time
+    /// using the velocity of the state.
+    pub fn extrapolate(&self) -> (Position, Rotation) {
+        let position = Position(self.position.0 + self.linear_velocity.0 * self.age);
+
+        #[cfg(feature = "2d")]
+        let rotation =
+            Rotation::from_radians(self.rotation.as_radians() + self.angular_velocity.0 * self.age);
+        #[cfg(feature = "3d")]
+        let rotation = Rotation(
+            (Quaternion::from_scaled_axis(self.angular_velocity.0 * self.age) * self.rotation.0)
+                .normalize(),
+        );
+
+        (position, rotation)
+    }
+}
+
+/// A component that blends the state of a rigid body towards a [`RemoteBodyState`] over a window of time
+/// instead of teleporting the body, so that corrections from a server don't visibly pop.
+///
+/// When the correction is added, the velocity of the body is set to the velocity of the remote state,
+/// and the remote state is [extrapolated](RemoteBodyState::extrapolate) to the present time. The remaining
+/// position and rotation error is then applied gradually at the start of each physics step over the
+/// [`blend_duration`](Self::blend_duration), after which the component is removed.
+///
+/// The error is applied to [`Position`] and [`Rotation`] directly while the [`AccumulatedTranslation`]
+/// of the body is empty, so the blending doesn't interfere with the solver, and the `Transform` that is
+/// synchronized with the body follows the blended state smoothly.
+///
+/// If the position error is larger than the [`snap_distance`](Self::snap_distance),
+/// the body is teleported to the remote state immediately.
+///
+/// Inserting a new correction replaces the one in progress, and the error is recomputed
+/// relative to the current state of the body.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// #[derive(Event)]
+/// struct ServerState {
+///     entity: Entity,
+///     state: RemoteBodyState,
+/// }
+///
+/// fn apply_server_states(mut commands: Commands, mut events: EventReader<ServerState>) {
+///     for event in events.read() {
+///         commands.entity(event.entity).insert(
+///             StateCorrection::new(event.state)
+///                 .with_blend_duration(0.2)
+///                 .with_snap_distance(5.0),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct StateCorrection {
+    /// The remote state that the body is blended towards.
+    pub target: RemoteBodyState,
+    /// The duration of the blending window in seconds. Defaults to `0.1`.
+    pub blend_duration: Scalar,
+    /// The position error above which the body is teleported to the remote state immediately.
+    /// Defaults to `Scalar::MAX`.
+    pub snap_distance: Scalar,
+    /// The position error that hasn't been applied yet.
+    position_error: Vector,
+    /// The rotation error that hasn't been applied yet.
+    rotation_error: Rotation,
+    /// The remaining time of the blending window in seconds.
+    remaining_time: Scalar,
+    /// True if the error has been computed.
+    started: bool,
+}
+
+impl StateCorrection {
+    /// Creates a new [`StateCorrection`] that blends the body towards the given remote state.
+    pub fn new(target: RemoteBodyState) -> Self {
+        Self {
+            target,
+            blend_duration: 0.1,
+            snap_distance: Scalar::MAX,
+            position_error: Vector::ZERO,
+            rotation_error: Rotation::default(),
+            remaining_time: 0.0,
+            started: false,
+        }
+    }
+
+    /// Sets the duration of the blending window in seconds.
+    pub fn with_blend_duration(mut self, duration: Scalar) -> Self {
+        self.blend_duration = duration;
+        self
+    }
+
+    /// Sets the position error above which the body is teleported to the remote state immediately.
+    pub fn with_snap_distance(mut self, distance: Scalar) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    /// Returns the position error that hasn't been applied yet.
+    pub fn position_error(&self) -> Vector {
+        self.position_error
+    }
+
+    /// Returns the remaining time of the blending window in seconds.
+    pub fn remaining_time(&self) -> Scalar {
+        self.remaining_time
+    }
+}
+
+type StateCorrectionQueryComponents = (
+    Entity,
+    &'static mut StateCorrection,
+    &'static mut Position,
+    &'static mut Rotation,
+    &'static mut LinearVelocity,
+    &'static mut AngularVelocity,
+    Option<&'static mut AccumulatedTranslation>,
+);
+
+/// Blends bodies with a [`StateCorrection`] towards their remote states.
+pub fn apply_state_corrections(
+    mut commands: Commands,
+    mut bodies: Query<StateCorrectionQueryComponents>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (
+        entity,
+        mut correction,
+        mut position,
+        mut rotation,
+        mut lin_vel,
+        mut ang_vel,
+        accumulated_translation,
+    ) in &mut bodies
+    {
+        if !correction.started {
+            let (target_position, target_rotation) = correction.target.extrapolate();
+
+            // Any pending translation would be applied on top of the corrected position.
+            if let Some(mut translation) = accumulated_translation {
+                position.0 += translation.0;
+                translation.0 = Vector::ZERO;
+            }
+
+            lin_vel.0 = correction.target.linear_velocity.0;
+            ang_vel.0 = correction.target.angular_velocity.0;
+
+            correction.position_error = target_position.0 - position.0;
+            correction.rotation_error = rotation_error(&rotation, &target_rotation);
+            correction.remaining_time = correction.blend_duration;
+            correction.started = true;
+
+            if correction.position_error.length() > correction.snap_distance {
+                *position = target_position;
+                *rotation = target_rotation;
+                correction.position_error = Vector::ZERO;
+                correction.rotation_error = Rotation::default();
+                correction.remaining_time = 0.0;
+            }
+        }
+
+        if correction.remaining_time > delta_secs {
+            // Apply the fraction of the error that corresponds to this step.
+            let fraction = delta_secs / correction.remaining_time;
+            let position_step = correction.position_error * fraction;
+            position.0 += position_step;
+            correction.position_error -= position_step;
+
+            let rotation_step = blend_rotation(&correction.rotation_error, fraction);
+            *rotation = compose_rotations(&rotation_step, &rotation);
+            correction.rotation_error = blend_rotation(&correction.rotation_error, 1.0 - fraction);
+
+            correction.remaining_time -= delta_secs;
+        } else {
+            // Apply the rest of the error and finish the correction.
+            position.0 += correction.position_error;
+            *rotation = compose_rotations(&correction.rotation_error, &rotation);
+            commands.entity(entity).remove::<StateCorrection>();
+        }
+    }
+}
+
+/// Computes the rotation that rotates `from` to `to`.
+#[cfg(feature = "2d")]
+fn rotation_error(from: &Rotation, to: &Rotation) -> Rotation {
+    to.mul(from.inverse())
+}
+
+/// Computes the rotation that rotates `from` to `to` along the shortest arc.
+#[cfg(feature = "3d")]
+fn rotation_error(from: &Rotation, to: &Rotation) -> Rotation {
+    let error = to.0 * from.0.inverse();
+    Rotation(if error.w < 0.0 { -error } else { error }.normalize())
+}
+
+/// Returns the given fraction of a rotation error.
+#[cfg(feature = "2d")]
+fn blend_rotation(error: &Rotation, fraction: Scalar) -> Rotation {
+    Rotation::from_radians(error.as_radians() * fraction)
+}
+
+/// Returns the given fraction of a rotation error.
+#[cfg(feature = "3d")]
+fn blend_rotation(error: &Rotation, fraction: Scalar) -> Rotation {
+    Rotation(Quaternion::IDENTITY.slerp(error.0, fraction))
+}
+
+/// Applies the rotation `a` after the rotation `b`.
+#[cfg(feature = "2d")]
+fn compose_rotations(a: &Rotation, b: &Rotation) -> Rotation {
+    a.mul(*b)
+}
+
+/// Applies the rotation `a` after the rotation `b`.
+#[cfg(feature = "3d")]
+fn compose_rotations(a: &Rotation, b: &Rotation) -> Rotation {
+    Rotation((a.0 * b.0).normalize())
+}
@@ -0,0 +1,309 @@
+use super::{passes_filters, PhysicsGizmos};
+use crate::prelude::*;
+#[cfg(feature = "2d")]
+use bevy::sprite::{ColorMaterial, MaterialMesh2dBundle, Mesh2dHandle};
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+use parry::{
+    math::{Isometry, Point},
+    shape::TypedShape,
+};
+
+#[cfg(feature = "2d")]
+type DebugMaterial = ColorMaterial;
+#[cfg(feature = "3d")]
+type DebugMaterial = StandardMaterial;
+
+/// A translucent mesh rendered for a [collider](Collider) when [`PhysicsGizmos::collider_mesh_color`] is set.
+#[derive(Component)]
+pub(super) struct ColliderDebugMesh {
+    collider: Entity,
+    mesh: Handle<Mesh>,
+}
+
+/// The entity of the [`ColliderDebugMesh`] of a collider.
+#[derive(Component)]
+pub(super) struct ColliderDebugMeshEntity(Entity);
+
+/// Spawns, updates and despawns translucent debug meshes for colliders.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(super) fn update_collider_debug_meshes(
+    mut commands: Commands,
+    colliders: Query<(
+        Entity,
+        Ref<Collider>,
+        &Position,
+        &Rotation,
+        Option<&DebugRender>,
+        Option<&CollisionLayers>,
+        Option<&ColliderDebugMeshEntity>,
+    )>,
+    mut debug_meshes: Query<(Entity, &ColliderDebugMesh, &mut Transform, &mut Visibility)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<DebugMaterial>>,
+    mut material: Local<Option<Handle<DebugMaterial>>>,
+    store: Res<GizmoConfigStore>,
+) {
+    let (gizmo_config, config) = store.config::<PhysicsGizmos>();
+
+    // Despawn all debug meshes if they are disabled
+    let Some(color) = config.collider_mesh_color.filter(|_| gizmo_config.enabled) else {
+        for (entity, debug_mesh, ..) in &debug_meshes {
+            commands.entity(entity).despawn();
+            if let Some(mut collider) = commands.get_entity(debug_mesh.collider) {
+                collider.remove::<ColliderDebugMeshEntity>();
+            }
+        }
+        return;
+    };
+
+    let material = material
+        .get_or_insert_with(|| materials.add(debug_material(color)))
+        .clone();
+    if materials
+        .get(&material)
+        .is_some_and(|material| material_color(material) != color)
+    {
+        materials.insert(&material, debug_material(color));
+    }
+
+    // Despawn debug meshes whose colliders have been removed
+    for (entity, debug_mesh, ..) in &debug_meshes {
+        if !colliders.contains(debug_mesh.collider) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (entity, collider, position, rotation, render_config, layers, mesh_entity) in &colliders {
+        #[cfg(feature = "2d")]
+        let transform = Transform::from_translation(position.f32().extend(0.0))
+            .with_rotation(Quaternion::from(*rotation).f32());
+        #[cfg(feature = "3d")]
+        let transform = Transform::from_translation(position.f32()).with_rotation(rotation.f32());
+        let visibility = if passes_filters(config, render_config, layers) {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+
+        let Some(mesh_entity) = mesh_entity else {
+            let Some(mesh) = collider_mesh(&collider) else {
+                continue;
+            };
+            let mesh = meshes.add(mesh);
+            let mesh_entity = commands
+                .spawn((
+                    ColliderDebugMesh {
+                        collider: entity,
+                        mesh: mesh.clone(),
+                    },
+                    #[cfg(feature = "2d")]
+                    MaterialMesh2dBundle {
+                        mesh: Mesh2dHandle(mesh),
+                        material: material.clone(),
+                        transform,
+                        visibility,
+                        ..default()
+                    },
+                    #[cfg(feature = "3d")]
+                    PbrBundle {
+                        mesh,
+                        material: material.clone(),
+                        transform,
+                        visibility,
+                        ..default()
+                    },
+                ))
+                .id();
+            commands
+                .entity(entity)
+                .insert(ColliderDebugMeshEntity(mesh_entity));
+            continue;
+        };
+
+        let Ok((_, debug_mesh, mut mesh_transform, mut mesh_visibility)) =
+            debug_meshes.get_mut(mesh_entity.0)
+        else {
+            // The debug mesh was despawned elsewhere, so spawn a new one next time
+            commands.entity(entity).remove::<ColliderDebugMeshEntity>();
+            continue;
+        };
+
+        // Regenerate the mesh if the shape of the collider has changed
+        if collider.is_changed() {
+            if let Some(mesh) = collider_mesh(&collider) {
+                meshes.insert(&debug_mesh.mesh, mesh);
+            } else {
+                commands.entity(mesh_entity.0).despawn();
+                commands.entity(entity).remove::<ColliderDebugMeshEntity>();
+                continue;
+            }
+        }
+
+        if *mesh_transform != transform {
+            *mesh_transform = transform;
+        }
+        if *mesh_visibility != visibility {
+            *mesh_visibility = visibility;
+        }
+    }
+}
+
+#[cfg(feature = "2d")]
+fn debug_material(color: Color) -> DebugMaterial {
+    ColorMaterial::from(color)
+}
+
+#[cfg(feature = "3d")]
+fn debug_material(color: Color) -> DebugMaterial {
+    StandardMaterial {
+        base_color: color,
+        alpha_mode: AlphaMode::Blend,
+        double_sided: true,
+        cull_mode: None,
+        ..default()
+    }
+}
+
+#[cfg(feature = "2d")]
+fn material_color(material: &DebugMaterial) -> Color {
+    material.color
+}
+
+#[cfg(feature = "3d")]
+fn material_color(material: &DebugMaterial) -> Color {
+    material.base_color
+}
+
+/// Creates a triangle mesh for the given collider, or returns `None` if the shape has no area or volume
+/// or is not supported.
+///
+/// The rounded borders of round shapes are ignored in 3D.
+fn collider_mesh(collider: &Collider) -> Option<Mesh> {
+    let mut vertices = vec![];
+    let mut indices = vec![];
+    append_triangles(
+        collider.shape_scaled().as_typed_shape(),
+        &Isometry::identity(),
+        &mut vertices,
+        &mut indices,
+    );
+
+    if indices.is_empty() {
+        return None;
+    }
+
+    let positions = vertices
+        .iter()
+        .map(|point| {
+            #[cfg(feature = "2d")]
+            let position = Vector::from(*point).f32().extend(0.0);
+            #[cfg(feature = "3d")]
+            let position = Vector::from(*point).f32();
+            position.to_array()
+        })
+        .collect::<Vec<_>>();
+
+    #[allow(unused_mut)]
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_indices(Indices::U32(indices.into_iter().flatten().collect()));
+
+    // Flat normals make the faces of complex shapes easier to tell apart
+    #[cfg(feature = "3d")]
+    {
+        mesh.duplicate_vertices();
+        mesh.compute_flat_normals();
+    }
+
+    Some(mesh)
+}
+
+/// Appends the triangles of the given shape transformed by the `isometry` to the `vertices` and `indices`.
+fn append_triangles(
+    shape: TypedShape,
+    isometry: &Isometry<Scalar>,
+    vertices: &mut Vec<Point<Scalar>>,
+    indices: &mut Vec<[u32; 3]>,
+) {
+    let (points, triangles) = match shape {
+        TypedShape::Compound(s) => {
+            for (sub_pos, shape) in s.shapes() {
+                append_triangles(
+                    shape.as_typed_shape(),
+                    &(isometry * sub_pos),
+                    vertices,
+                    indices,
+                );
+            }
+            return;
+        }
+        TypedShape::TriMesh(s) => (s.vertices().to_vec(), s.indices().to_vec()),
+        TypedShape::Triangle(s) => (vec![s.a, s.b, s.c], vec![[0, 1, 2]]),
+        TypedShape::RoundTriangle(s) => (
+            vec![s.inner_shape.a, s.inner_shape.b, s.inner_shape.c],
+            vec![[0, 1, 2]],
+        ),
+        #[cfg(feature = "2d")]
+        TypedShape::Ball(s) => convex_polygon(s.to_polyline(32)),
+        #[cfg(feature = "2d")]
+        TypedShape::Cuboid(s) => convex_polygon(s.to_polyline()),
+        #[cfg(feature = "2d")]
+        TypedShape::Capsule(s) => convex_polygon(s.to_polyline(32)),
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(s) => convex_polygon(s.points().to_vec()),
+        #[cfg(feature = "2d")]
+        TypedShape::RoundCuboid(s) => convex_polygon(s.to_polyline(32)),
+        #[cfg(feature = "2d")]
+        TypedShape::RoundConvexPolygon(s) => convex_polygon(s.to_polyline(32)),
+        #[cfg(feature = "3d")]
+        TypedShape::Ball(s) => s.to_trimesh(16, 16),
+        #[cfg(feature = "3d")]
+        TypedShape::Cuboid(s) => s.to_trimesh(),
+        #[cfg(feature = "3d")]
+        TypedShape::Capsule(s) => s.to_trimesh(16, 16),
+        #[cfg(feature = "3d")]
+        TypedShape::Cylinder(s) => s.to_trimesh(32),
+        #[cfg(feature = "3d")]
+        TypedShape::Cone(s) => s.to_trimesh(32),
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(s) => s.to_trimesh(),
+        #[cfg(feature = "3d")]
+        TypedShape::HeightField(s) => s.to_trimesh(),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCuboid(s) => s.inner_shape.to_trimesh(),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundConvexPolyhedron(s) => s.inner_shape.to_trimesh(),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCylinder(s) => s.inner_shape.to_trimesh(32),
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCone(s) => s.inner_shape.to_trimesh(32),
+        // Segments, polylines and half-spaces have no area or volume to fill
+        _ => return,
+    };
+
+    let offset = vertices.len() as u32;
+    vertices.extend(points.iter().map(|point| isometry * point));
+    indices.extend(
+        triangles
+            .iter()
+            .map(|triangle| triangle.map(|index| index + offset)),
+    );
+}
+
+/// Triangulates a convex polygon as a triangle fan.
+#[cfg(feature = "2d")]
+fn convex_polygon(points: Vec<Point<Scalar>>) -> (Vec<Point<Scalar>>, Vec<[u32; 3]>) {
+    let triangles = (1..points.len().saturating_sub(1) as u32)
+        .map(|i| [0, i, i + 1])
+        .collect();
+    (points, triangles)
+}
@@ -51,23 +51,101 @@ use bevy::prelude::*;
 pub struct PhysicsGizmos {
     /// The lengths of the axes drawn for an entity at the center of mass.
     pub axis_lengths: Option<Vector>,
+    /// The color of the [`CenterOfMass`] markers of dynamic bodies and the lines from the body origins to them.
+    /// If `None`, the centers of mass will not be rendered.
+    pub center_of_mass_color: Option<Color>,
+    /// The color of the shapes that visualize the [`Inertia`] of dynamic bodies.
+    /// If `None`, the inertia will not be rendered.
+    ///
+    /// In 3D, this is a box along the principal axes of inertia that has the same mass and inertia as the body.
+    /// In 2D, it is a disk with the same mass and inertia as the body.
+    pub inertia_color: Option<Color>,
     /// The color of the [AABBs](ColliderAabb). If `None`, the AABBs will not be rendered.
     pub aabb_color: Option<Color>,
+    /// The color of the arrows drawn for the [`LinearVelocity`] of rigid bodies at their center of mass.
+    /// If `None`, the linear velocities will not be rendered.
+    pub linear_velocity_color: Option<Color>,
+    /// The color of the arcs drawn for the [`AngularVelocity`] of rigid bodies around their center of mass.
+    /// If `None`, the angular velocities will not be rendered.
+    pub angular_velocity_color: Option<Color>,
+    /// The time in seconds that velocities are multiplied by to get the lengths of the velocity arrows and the angles of the arcs.
+    ///
+    /// The arrows show how far the bodies would move in this time, so they are
+    /// scaled consistently regardless of the length unit used by the simulation.
+    pub velocity_scale: Scalar,
     /// The color of the [collider](Collider) wireframes. If `None`, the colliders will not be rendered.
     pub collider_color: Option<Color>,
+    /// The color of translucent meshes rendered for [colliders](Collider) in addition to the wireframes.
+    /// Filled shapes are easier to read than dense wireframes, especially for trimeshes and complex decomposed colliders.
+    /// If `None`, the meshes will not be rendered.
+    ///
+    /// Rendering the meshes requires the `debug-mesh` feature. The rounded borders of round shapes
+    /// are ignored in 3D, and segments, polylines and half-spaces have no meshes.
+    pub collider_mesh_color: Option<Color>,
     /// The colors (in HSLA) for [sleeping](Sleeping) bodies will be multiplied by this array.
     /// If `None`, sleeping will have no effect on the colors.
     pub sleeping_color_multiplier: Option<[f32; 4]>,
+    /// The colors used for [collider](Collider) wireframes based on the state of their rigid bodies,
+    /// like whether they are sleeping or static. If `None`, the [`collider_color`](Self::collider_color) is used.
+    ///
+    /// The state colors are not used for entities with a [`DebugRender`] component.
+    pub body_state_colors: Option<BodyStateColors>,
+    /// If `true`, the [collider](Collider) wireframes of dynamic bodies are tinted with a different color
+    /// for each [simulation island](PhysicsIslands). This takes precedence over the [`body_state_colors`](Self::body_state_colors),
+    /// and the [`sleeping_color_multiplier`](Self::sleeping_color_multiplier) is applied to sleeping islands.
+    ///
+    /// The island colors are not used for entities with a [`DebugRender`] component.
+    pub island_colors: bool,
+    /// The colors used for a heatmap of the contact stress on dynamic bodies, i.e. the magnitude of the contact forces
+    /// acting on each body relative to its weight. This shows which parts of a structure are carrying the most load
+    /// and are close to becoming unstable or breaking joints. This takes precedence over the
+    /// [`island_colors`](Self::island_colors) and [`body_state_colors`](Self::body_state_colors).
+    ///
+    /// The heatmap is not used for entities with a [`DebugRender`] component.
+    pub stress_heatmap: Option<StressHeatmap>,
+    /// The color of the bars drawn above [simulation islands](PhysicsIslands) that show how close each island
+    /// is to falling asleep, i.e. how long all of its bodies have been below the [`SleepingThreshold`]
+    /// relative to the [`DeactivationTime`]. The bounds of the islands are also drawn.
+    /// If `None`, the islands will not be rendered.
+    pub island_sleep_timer_color: Option<Color>,
+    /// The color of the nodes of the bounding volume hierarchy that the broad phase uses for colliders
+    /// attached to static or [sleeping](Sleeping) bodies. Deeper levels of the tree are drawn darker.
+    /// If `None`, the tree will not be rendered.
+    pub broad_phase_tree_color: Option<Color>,
+    /// The color of the grid cells that show how many [`BroadCollisionPairs`] there are in each region.
+    /// Cells with more pairs are drawn more opaque. If `None`, the pair counts will not be rendered.
+    pub broad_phase_pair_color: Option<Color>,
+    /// The size of the grid cells used for rendering the broad phase pair counts.
+    pub broad_phase_cell_size: Scalar,
     /// The color of the contact points. If `None`, the contact points will not be rendered.
     pub contact_point_color: Option<Color>,
     /// The color of the contact normals. If `None`, the contact normals will not be rendered.
     pub contact_normal_color: Option<Color>,
     /// The scale used for contact normals.
     pub contact_normal_scale: ContactGizmoScale,
+    /// The color of the arrows drawn for the [normal impulses](ContactData::normal_impulse) of individual contacts.
+    /// If `None`, the normal impulses will not be rendered.
+    pub contact_normal_impulse_color: Option<Color>,
+    /// The color of the arrows drawn for the [tangent impulses](ContactData::tangent_impulse) of individual contacts,
+    /// pointing in the direction of the friction applied to the first body. If `None`, the tangent impulses will not be rendered.
+    ///
+    /// The arrows are only drawn while the bodies are sliding relative to each other at the contact point,
+    /// because the direction of the friction is not known otherwise.
+    pub contact_tangent_impulse_color: Option<Color>,
+    /// The factor that the contact forces are multiplied by to get the lengths of the impulse arrows.
+    pub contact_impulse_scale: Scalar,
     /// The color of the lines drawn from the centers of bodies to their joint anchors.
     pub joint_anchor_color: Option<Color>,
     /// The color of the lines drawn between joint anchors, indicating the separation.
     pub joint_separation_color: Option<Color>,
+    /// The length of the axes drawn for the frames of both bodies at their joint anchors.
+    /// If `None`, the joint frames will not be rendered.
+    ///
+    /// This is also used as the radius of the arcs and cones drawn for angle limits.
+    pub joint_frame_length: Option<Scalar>,
+    /// The color of the joint limits, like the allowed distances of a [`DistanceJoint`]
+    /// or the angle limits of a [`RevoluteJoint`]. If `None`, the joint limits will not be rendered.
+    pub joint_limit_color: Option<Color>,
     /// The color used for the rays in [raycasts](spatial_query#raycasting).
     pub raycast_color: Option<Color>,
     /// The color used for the hit points in [raycasts](spatial_query#raycasting).
@@ -85,6 +163,15 @@ pub struct PhysicsGizmos {
     /// Determines if the visibility of entities with [colliders](Collider) should be set to `Visibility::Hidden`,
     /// which will only show the debug renders.
     pub hide_meshes: bool,
+    /// If set, only entities whose [`CollisionLayers`] have a membership in one of the given layers are rendered.
+    /// Entities without [`CollisionLayers`] belong to all layers.
+    ///
+    /// This applies to the axes, velocities, AABBs, colliders, contacts, [`RayCaster`]s and [`ShapeCaster`]s of entities.
+    pub layer_filter: Option<LayerMask>,
+    /// If `true`, only entities with a [`DebugRender`] component are rendered.
+    ///
+    /// This applies to the axes, velocities, AABBs, colliders, contacts, [`RayCaster`]s and [`ShapeCaster`]s of entities.
+    pub only_debug_render_entities: bool,
 }
 
 impl Default for PhysicsGizmos {
@@ -94,14 +181,35 @@ impl Default for PhysicsGizmos {
             axis_lengths: Some(Vector::new(5.0, 5.0)),
             #[cfg(feature = "3d")]
             axis_lengths: Some(Vector::new(0.5, 0.5, 0.5)),
+            center_of_mass_color: None,
+            inertia_color: None,
             aabb_color: None,
+            linear_velocity_color: None,
+            angular_velocity_color: None,
+            velocity_scale: 0.1,
             collider_color: Some(Color::ORANGE),
+            collider_mesh_color: None,
             sleeping_color_multiplier: Some([1.0, 1.0, 0.4, 1.0]),
+            body_state_colors: None,
+            island_colors: false,
+            stress_heatmap: None,
+            island_sleep_timer_color: None,
+            broad_phase_tree_color: None,
+            broad_phase_pair_color: None,
+            #[cfg(feature = "2d")]
+            broad_phase_cell_size: 100.0,
+            #[cfg(feature = "3d")]
+            broad_phase_cell_size: 4.0,
             contact_point_color: None,
             contact_normal_color: None,
             contact_normal_scale: ContactGizmoScale::default(),
+            contact_normal_impulse_color: None,
+            contact_tangent_impulse_color: None,
+            contact_impulse_scale: 0.025,
             joint_anchor_color: Some(Color::PINK),
             joint_separation_color: Some(Color::RED),
+            joint_frame_length: None,
+            joint_limit_color: None,
             raycast_color: Some(Color::RED),
             raycast_point_color: Some(Color::YELLOW),
             raycast_normal_color: Some(Color::PINK),
@@ -110,10 +218,104 @@ impl Default for PhysicsGizmos {
             shapecast_point_color: Some(Color::YELLOW),
             shapecast_normal_color: Some(Color::PINK),
             hide_meshes: false,
+            layer_filter: None,
+            only_debug_render_entities: false,
+        }
+    }
+}
+
+/// The colors used for [collider](Collider) wireframes based on the state of their rigid bodies.
+///
+/// This can be used to see why bodies are not falling asleep or why parts of a simulation are frozen.
+/// See [`PhysicsGizmos::body_state_colors`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BodyStateColors {
+    /// The color of dynamic bodies that are awake and moving.
+    pub awake: Color,
+    /// The color of dynamic bodies that are awake, but have been below the [`SleepingThreshold`]
+    /// for some time. They will fall asleep once they and the other bodies in their [island](PhysicsIslands)
+    /// have been still for the [`DeactivationTime`].
+    pub sleeping_pending: Color,
+    /// The color of [sleeping](Sleeping) bodies.
+    pub sleeping: Color,
+    /// The color of [kinematic](RigidBody::Kinematic) bodies.
+    pub kinematic: Color,
+    /// The color of [static](RigidBody::Static) bodies and colliders without a rigid body.
+    pub static_body: Color,
+}
+
+impl Default for BodyStateColors {
+    fn default() -> Self {
+        Self {
+            awake: Color::ORANGE,
+            sleeping_pending: Color::YELLOW,
+            sleeping: Color::rgb(0.4, 0.4, 0.8),
+            kinematic: Color::PURPLE,
+            static_body: Color::GRAY,
+        }
+    }
+}
+
+impl BodyStateColors {
+    /// Returns the color for a collider attached to a rigid body of the given type and state.
+    /// Colliders without a rigid body use the [`static_body`](Self::static_body) color.
+    pub fn color(
+        &self,
+        rigid_body: Option<&RigidBody>,
+        is_sleeping: bool,
+        time_sleeping: Option<&TimeSleeping>,
+    ) -> Color {
+        match rigid_body {
+            Some(RigidBody::Dynamic) if is_sleeping => self.sleeping,
+            Some(RigidBody::Dynamic) if time_sleeping.is_some_and(|t| t.0 > 0.0) => {
+                self.sleeping_pending
+            }
+            Some(RigidBody::Dynamic) => self.awake,
+            Some(RigidBody::Kinematic) => self.kinematic,
+            Some(RigidBody::Static) | None => self.static_body,
+        }
+    }
+}
+
+/// The colors used for rendering a heatmap of the contact stress on dynamic bodies.
+///
+/// The stress of a body is the sum of the magnitudes of the contact forces acting on it divided by its weight.
+/// For example, a box resting on the ground has a stress of `1.0`, while a box at the bottom of a stack
+/// of several boxes has a much higher stress. See [`PhysicsGizmos::stress_heatmap`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct StressHeatmap {
+    /// The color of bodies that have no contact stress.
+    pub low: Color,
+    /// The color of bodies with a contact stress of at least [`max_stress`](Self::max_stress).
+    pub high: Color,
+    /// The contact stress at which bodies are rendered using the [`high`](Self::high) color.
+    /// Colors for lower stresses are interpolated between [`low`](Self::low) and [`high`](Self::high).
+    pub max_stress: Scalar,
+}
+
+impl Default for StressHeatmap {
+    fn default() -> Self {
+        Self {
+            low: Color::BLUE,
+            high: Color::RED,
+            max_stress: 5.0,
         }
     }
 }
 
+impl StressHeatmap {
+    /// Returns the color for the given contact stress.
+    pub fn color(&self, stress: Scalar) -> Color {
+        let t = (stress / self.max_stress).clamp(0.0, 1.0) as f32;
+        let low = self.low.as_rgba_f32();
+        let high = self.high.as_rgba_f32();
+        let [r, g, b, a] = [0, 1, 2, 3].map(|i| low[i] + (high[i] - low[i]) * t);
+        Color::rgba(r, g, b, a)
+    }
+}
+
 /// The scale used for contact normals rendered using gizmos.
 #[derive(Reflect, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -138,14 +340,38 @@ impl PhysicsGizmos {
             axis_lengths: Some(Vector::new(5.0, 5.0)),
             #[cfg(feature = "3d")]
             axis_lengths: Some(Vector::new(0.5, 0.5, 0.5)),
+            center_of_mass_color: Some(Color::YELLOW),
+            inertia_color: Some(Color::rgb(0.6, 0.4, 1.0)),
             aabb_color: Some(Color::rgb(0.8, 0.8, 0.8)),
+            linear_velocity_color: Some(Color::LIME_GREEN),
+            angular_velocity_color: Some(Color::FUCHSIA),
+            velocity_scale: 0.1,
             collider_color: Some(Color::ORANGE),
+            collider_mesh_color: Some(Color::rgba(0.6, 0.8, 1.0, 0.2)),
             sleeping_color_multiplier: Some([1.0, 1.0, 0.4, 1.0]),
+            body_state_colors: Some(BodyStateColors::default()),
+            island_colors: true,
+            stress_heatmap: None,
+            island_sleep_timer_color: Some(Color::CYAN),
+            broad_phase_tree_color: Some(Color::GREEN),
+            broad_phase_pair_color: Some(Color::RED),
+            #[cfg(feature = "2d")]
+            broad_phase_cell_size: 100.0,
+            #[cfg(feature = "3d")]
+            broad_phase_cell_size: 4.0,
             contact_point_color: Some(Color::CYAN),
             contact_normal_color: Some(Color::RED),
             contact_normal_scale: ContactGizmoScale::default(),
+            contact_normal_impulse_color: Some(Color::GREEN),
+            contact_tangent_impulse_color: Some(Color::YELLOW),
+            contact_impulse_scale: 0.025,
             joint_anchor_color: Some(Color::PINK),
             joint_separation_color: Some(Color::RED),
+            #[cfg(feature = "2d")]
+            joint_frame_length: Some(10.0),
+            #[cfg(feature = "3d")]
+            joint_frame_length: Some(0.25),
+            joint_limit_color: Some(Color::CYAN),
             raycast_color: Some(Color::RED),
             raycast_point_color: Some(Color::YELLOW),
             raycast_normal_color: Some(Color::PINK),
@@ -154,6 +380,8 @@ impl PhysicsGizmos {
             shapecast_point_color: Some(Color::YELLOW),
             shapecast_normal_color: Some(Color::PINK),
             hide_meshes: true,
+            layer_filter: None,
+            only_debug_render_entities: false,
         }
     }
 
@@ -163,14 +391,35 @@ impl PhysicsGizmos {
     pub fn none() -> Self {
         Self {
             axis_lengths: None,
+            center_of_mass_color: None,
+            inertia_color: None,
             aabb_color: None,
+            linear_velocity_color: None,
+            angular_velocity_color: None,
+            velocity_scale: 0.1,
             collider_color: None,
+            collider_mesh_color: None,
             sleeping_color_multiplier: None,
+            body_state_colors: None,
+            island_colors: false,
+            stress_heatmap: None,
+            island_sleep_timer_color: None,
+            broad_phase_tree_color: None,
+            broad_phase_pair_color: None,
+            #[cfg(feature = "2d")]
+            broad_phase_cell_size: 100.0,
+            #[cfg(feature = "3d")]
+            broad_phase_cell_size: 4.0,
             contact_point_color: None,
             contact_normal_color: None,
             contact_normal_scale: ContactGizmoScale::default(),
+            contact_normal_impulse_color: None,
+            contact_tangent_impulse_color: None,
+            contact_impulse_scale: 0.025,
             joint_anchor_color: None,
             joint_separation_color: None,
+            joint_frame_length: None,
+            joint_limit_color: None,
             raycast_color: None,
             raycast_point_color: None,
             raycast_normal_color: None,
@@ -179,6 +428,8 @@ impl PhysicsGizmos {
             shapecast_point_color: None,
             shapecast_normal_color: None,
             hide_meshes: false,
+            layer_filter: None,
+            only_debug_render_entities: false,
         }
     }
 
@@ -209,6 +460,16 @@ impl PhysicsGizmos {
         }
     }
 
+    /// Creates a [`PhysicsGizmos`] configuration with given colors for the broad phase tree of static colliders
+    /// and the pair counts. Other debug rendering options will be disabled.
+    pub fn broad_phase(tree_color: Option<Color>, pair_color: Option<Color>) -> Self {
+        Self {
+            broad_phase_tree_color: tree_color,
+            broad_phase_pair_color: pair_color,
+            ..Self::none()
+        }
+    }
+
     /// Creates a [`PhysicsGizmos`] configuration with a given contact point color.
     /// Other debug rendering options will be disabled.
     pub fn contact_points(color: Color) -> Self {
@@ -227,6 +488,16 @@ impl PhysicsGizmos {
         }
     }
 
+    /// Creates a [`PhysicsGizmos`] configuration with given colors for the normal and tangent impulses
+    /// of contacts. Other debug rendering options will be disabled.
+    pub fn contact_impulses(normal_color: Option<Color>, tangent_color: Option<Color>) -> Self {
+        Self {
+            contact_normal_impulse_color: normal_color,
+            contact_tangent_impulse_color: tangent_color,
+            ..Self::none()
+        }
+    }
+
     /// Creates a [`PhysicsGizmos`] configuration with given colors for
     /// joint anchors and separation distances. Other debug rendering options will be disabled.
     pub fn joints(anchor_color: Option<Color>, separation_color: Option<Color>) -> Self {
@@ -243,24 +514,92 @@ impl PhysicsGizmos {
         self
     }
 
+    /// Sets the color of the center of mass markers.
+    pub fn with_center_of_mass_color(mut self, color: Color) -> Self {
+        self.center_of_mass_color = Some(color);
+        self
+    }
+
+    /// Sets the color of the shapes that visualize the inertia of dynamic bodies.
+    pub fn with_inertia_color(mut self, color: Color) -> Self {
+        self.inertia_color = Some(color);
+        self
+    }
+
     /// Sets the AABB color.
     pub fn with_aabb_color(mut self, color: Color) -> Self {
         self.aabb_color = Some(color);
         self
     }
 
+    /// Sets the colors used for debug rendering linear and angular velocities.
+    pub fn with_velocity_colors(mut self, linear: Option<Color>, angular: Option<Color>) -> Self {
+        self.linear_velocity_color = linear;
+        self.angular_velocity_color = angular;
+        self
+    }
+
+    /// Sets the time in seconds that velocities are multiplied by to get the lengths of the velocity arrows.
+    pub fn with_velocity_scale(mut self, scale: Scalar) -> Self {
+        self.velocity_scale = scale;
+        self
+    }
+
     /// Sets the collider color.
     pub fn with_collider_color(mut self, color: Color) -> Self {
         self.collider_color = Some(color);
         self
     }
 
+    /// Sets the color used for translucent collider meshes. Requires the `debug-mesh` feature.
+    pub fn with_collider_mesh_color(mut self, color: Color) -> Self {
+        self.collider_mesh_color = Some(color);
+        self
+    }
+
     /// Sets the multiplier used for the colors (in HSLA) of [sleeping](Sleeping) bodies.
     pub fn with_sleeping_color_multiplier(mut self, color_multiplier: [f32; 4]) -> Self {
         self.sleeping_color_multiplier = Some(color_multiplier);
         self
     }
 
+    /// Sets the colors used for collider wireframes based on the state of their rigid bodies.
+    pub fn with_body_state_colors(mut self, colors: BodyStateColors) -> Self {
+        self.body_state_colors = Some(colors);
+        self
+    }
+
+    /// Enables tinting collider wireframes of dynamic bodies based on their [simulation island](PhysicsIslands).
+    pub fn with_island_colors(mut self) -> Self {
+        self.island_colors = true;
+        self
+    }
+
+    /// Sets the colors used for the contact stress heatmap of dynamic bodies.
+    pub fn with_stress_heatmap(mut self, heatmap: StressHeatmap) -> Self {
+        self.stress_heatmap = Some(heatmap);
+        self
+    }
+
+    /// Sets the color used for the sleep timers and bounds of [simulation islands](PhysicsIslands).
+    pub fn with_island_sleep_timer_color(mut self, color: Color) -> Self {
+        self.island_sleep_timer_color = Some(color);
+        self
+    }
+
+    /// Sets the colors used for debug rendering the broad phase tree of static colliders and the pair counts.
+    pub fn with_broad_phase_colors(mut self, tree: Option<Color>, pairs: Option<Color>) -> Self {
+        self.broad_phase_tree_color = tree;
+        self.broad_phase_pair_color = pairs;
+        self
+    }
+
+    /// Sets the size of the grid cells used for rendering the broad phase pair counts.
+    pub fn with_broad_phase_cell_size(mut self, size: Scalar) -> Self {
+        self.broad_phase_cell_size = size;
+        self
+    }
+
     /// Sets the contact point color.
     pub fn with_contact_point_color(mut self, color: Color) -> Self {
         self.contact_point_color = Some(color);
@@ -279,6 +618,23 @@ impl PhysicsGizmos {
         self
     }
 
+    /// Sets the colors used for debug rendering the normal and tangent impulses of contacts.
+    pub fn with_contact_impulse_colors(
+        mut self,
+        normal: Option<Color>,
+        tangent: Option<Color>,
+    ) -> Self {
+        self.contact_normal_impulse_color = normal;
+        self.contact_tangent_impulse_color = tangent;
+        self
+    }
+
+    /// Sets the factor that the contact forces are multiplied by to get the lengths of the impulse arrows.
+    pub fn with_contact_impulse_scale(mut self, scale: Scalar) -> Self {
+        self.contact_impulse_scale = scale;
+        self
+    }
+
     /// Sets the colors used for debug rendering joints.
     pub fn with_joint_colors(anchor_color: Option<Color>, separation_color: Option<Color>) -> Self {
         Self {
@@ -288,6 +644,18 @@ impl PhysicsGizmos {
         }
     }
 
+    /// Sets the length of the axes drawn for joint frames, also used as the radius of angle limits.
+    pub fn with_joint_frame_length(mut self, length: Scalar) -> Self {
+        self.joint_frame_length = Some(length);
+        self
+    }
+
+    /// Sets the color used for debug rendering joint limits.
+    pub fn with_joint_limit_color(mut self, color: Color) -> Self {
+        self.joint_limit_color = Some(color);
+        self
+    }
+
     /// Sets the colors used for debug rendering raycasts.
     pub fn with_raycast_colors(
         mut self,
@@ -322,24 +690,82 @@ impl PhysicsGizmos {
         self
     }
 
+    /// Only renders entities whose [`CollisionLayers`] have a membership in one of the given layers.
+    pub fn with_layer_filter(mut self, layers: impl Into<LayerMask>) -> Self {
+        self.layer_filter = Some(layers.into());
+        self
+    }
+
+    /// Only renders entities with a [`DebugRender`] component.
+    pub fn with_only_debug_render_entities(mut self) -> Self {
+        self.only_debug_render_entities = true;
+        self
+    }
+
     /// Disables axis debug rendering.
     pub fn without_axes(mut self) -> Self {
         self.axis_lengths = None;
         self
     }
 
+    /// Disables center of mass and inertia debug rendering.
+    pub fn without_mass_properties(mut self) -> Self {
+        self.center_of_mass_color = None;
+        self.inertia_color = None;
+        self
+    }
+
     /// Disables AABB debug rendering.
     pub fn without_aabbs(mut self) -> Self {
         self.aabb_color = None;
         self
     }
 
+    /// Disables velocity debug rendering.
+    pub fn without_velocities(mut self) -> Self {
+        self.linear_velocity_color = None;
+        self.angular_velocity_color = None;
+        self
+    }
+
     /// Disables collider debug rendering.
     pub fn without_colliders(mut self) -> Self {
         self.collider_color = None;
         self
     }
 
+    /// Disables rendering translucent meshes for colliders.
+    pub fn without_collider_meshes(mut self) -> Self {
+        self.collider_mesh_color = None;
+        self
+    }
+
+    /// Disables coloring collider wireframes based on the state of their rigid bodies.
+    pub fn without_body_state_colors(mut self) -> Self {
+        self.body_state_colors = None;
+        self
+    }
+
+    /// Disables the contact stress heatmap.
+    pub fn without_stress_heatmap(mut self) -> Self {
+        self.stress_heatmap = None;
+        self
+    }
+
+    /// Disables island colors and the rendering of [simulation islands](PhysicsIslands).
+    pub fn without_islands(mut self) -> Self {
+        self.island_colors = false;
+        self.island_sleep_timer_color = None;
+        self
+    }
+
+    /// Disables broad phase debug rendering.
+    pub fn without_broad_phase(mut self) -> Self {
+        self.broad_phase_tree_color = None;
+        self.broad_phase_pair_color = None;
+        self
+    }
+
     /// Disables contact point debug rendering.
     pub fn without_contact_points(mut self) -> Self {
         self.contact_point_color = None;
@@ -352,10 +778,19 @@ impl PhysicsGizmos {
         self
     }
 
+    /// Disables contact impulse debug rendering.
+    pub fn without_contact_impulses(mut self) -> Self {
+        self.contact_normal_impulse_color = None;
+        self.contact_tangent_impulse_color = None;
+        self
+    }
+
     /// Disables joint debug rendering.
     pub fn without_joints(mut self) -> Self {
         self.joint_anchor_color = None;
         self.joint_separation_color = None;
+        self.joint_frame_length = None;
+        self.joint_limit_color = None;
         self
     }
 
@@ -418,6 +853,12 @@ pub struct DebugRender {
     pub axis_lengths: Option<Vector>,
     /// The color of the [AABB](ColliderAabb). If `None`, the AABB will not be rendered.
     pub aabb_color: Option<Color>,
+    /// The color of the arrow drawn for the [`LinearVelocity`] of the body.
+    /// If `None`, the linear velocity will not be rendered.
+    pub linear_velocity_color: Option<Color>,
+    /// The color of the arc drawn for the [`AngularVelocity`] of the body.
+    /// If `None`, the angular velocity will not be rendered.
+    pub angular_velocity_color: Option<Color>,
     /// The color of the [collider](Collider) wireframe. If `None`, the collider will not be rendered.
     pub collider_color: Option<Color>,
     /// If the entity is [sleeping](Sleeping), its colors (in HSLA) will be multiplied by this array.
@@ -425,6 +866,9 @@ pub struct DebugRender {
     pub sleeping_color_multiplier: Option<[f32; 4]>,
     /// Determines if the entity's visibility should be set to `Visibility::Hidden`, which will only show the debug render.
     pub hide_mesh: bool,
+    /// If `true`, the entity is rendered even if it is excluded by the
+    /// [`layer_filter`](PhysicsGizmos::layer_filter) of the [`PhysicsGizmos`] configuration.
+    pub ignore_filters: bool,
 }
 
 impl Default for DebugRender {
@@ -435,9 +879,12 @@ impl Default for DebugRender {
             #[cfg(feature = "3d")]
             axis_lengths: Some(Vector::new(0.5, 0.5, 0.5)),
             aabb_color: None,
+            linear_velocity_color: None,
+            angular_velocity_color: None,
             collider_color: Some(Color::ORANGE),
             sleeping_color_multiplier: Some([1.0, 1.0, 0.4, 1.0]),
             hide_mesh: false,
+            ignore_filters: false,
         }
     }
 }
@@ -451,9 +898,12 @@ impl DebugRender {
             #[cfg(feature = "3d")]
             axis_lengths: Some(Vector::new(0.5, 0.5, 0.5)),
             aabb_color: Some(Color::rgb(0.8, 0.8, 0.8)),
+            linear_velocity_color: Some(Color::LIME_GREEN),
+            angular_velocity_color: Some(Color::FUCHSIA),
             collider_color: Some(Color::ORANGE),
             sleeping_color_multiplier: Some([1.0, 1.0, 0.4, 1.0]),
             hide_mesh: true,
+            ignore_filters: false,
         }
     }
 
@@ -462,9 +912,12 @@ impl DebugRender {
         Self {
             axis_lengths: None,
             aabb_color: None,
+            linear_velocity_color: None,
+            angular_velocity_color: None,
             collider_color: None,
             sleeping_color_multiplier: None,
             hide_mesh: false,
+            ignore_filters: false,
         }
     }
 
@@ -507,6 +960,13 @@ impl DebugRender {
         self
     }
 
+    /// Sets the colors used for debug rendering the linear and angular velocity of the body.
+    pub fn with_velocity_colors(mut self, linear: Option<Color>, angular: Option<Color>) -> Self {
+        self.linear_velocity_color = linear;
+        self.angular_velocity_color = angular;
+        self
+    }
+
     /// Sets the collider color.
     pub fn with_collider_color(mut self, color: Color) -> Self {
         self.collider_color = Some(color);
@@ -525,6 +985,13 @@ impl DebugRender {
         self
     }
 
+    /// Renders the entity even if it is excluded by the [`layer_filter`](PhysicsGizmos::layer_filter)
+    /// of the [`PhysicsGizmos`] configuration.
+    pub fn ignoring_filters(mut self) -> Self {
+        self.ignore_filters = true;
+        self
+    }
+
     /// Disables axis debug rendering.
     pub fn without_axes(mut self) -> Self {
         self.axis_lengths = None;
@@ -537,6 +1004,13 @@ impl DebugRender {
         self
     }
 
+    /// Disables velocity debug rendering.
+    pub fn without_velocities(mut self) -> Self {
+        self.linear_velocity_color = None;
+        self.angular_velocity_color = None;
+        self
+    }
+
     /// Disables collider debug rendering.
     pub fn without_collider(mut self) -> Self {
         self.collider_color = None;
@@ -0,0 +1,178 @@
+//! Debug rendering for the limits of the different joint types.
+
+use super::{draw_arc, PhysicsGizmoExt};
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// The radius used for angle limits if [`PhysicsGizmos::joint_frame_length`] is `None`.
+#[cfg(feature = "2d")]
+pub(super) const DEFAULT_LIMIT_RADIUS: Scalar = 10.0;
+/// The radius used for angle limits if [`PhysicsGizmos::joint_frame_length`] is `None`.
+#[cfg(feature = "3d")]
+pub(super) const DEFAULT_LIMIT_RADIUS: Scalar = 0.25;
+
+/// Debug rendering for the limits of a [`Joint`].
+pub(super) trait JointGizmos: Joint {
+    /// Draws the limits of the joint, given the rotations of the attached bodies.
+    ///
+    /// `radius` is the radius used for angle limits, and `anchors` are the world-space anchor points.
+    fn draw_limits(
+        &self,
+        _gizmos: &mut Gizmos<PhysicsGizmos>,
+        _rotations: [&Rotation; 2],
+        _anchors: [Vector; 2],
+        _radius: Scalar,
+        _color: Color,
+    ) {
+    }
+}
+
+impl JointGizmos for FixedJoint {}
+
+impl JointGizmos for DistanceJoint {
+    fn draw_limits(
+        &self,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        _rotations: [&Rotation; 2],
+        anchors: [Vector; 2],
+        _radius: Scalar,
+        color: Color,
+    ) {
+        let limits = self
+            .length_limits
+            .unwrap_or(DistanceLimit::new(self.rest_length, self.rest_length));
+
+        // Draw the minimum and maximum distances from the first anchor.
+        for distance in [limits.min, limits.max] {
+            if distance <= Scalar::EPSILON {
+                continue;
+            }
+            #[cfg(feature = "2d")]
+            gizmos.circle_2d(anchors[0].f32(), distance as f32, color);
+            #[cfg(feature = "3d")]
+            gizmos.sphere(anchors[0].f32(), default(), distance as f32, color);
+        }
+    }
+}
+
+impl JointGizmos for PrismaticJoint {
+    fn draw_limits(
+        &self,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        rotations: [&Rotation; 2],
+        anchors: [Vector; 2],
+        radius: Scalar,
+        color: Color,
+    ) {
+        let Some(limits) = self.free_axis_limits else {
+            return;
+        };
+
+        let axis = rotations[0].rotate(self.free_axis);
+        let min = anchors[0] + axis * limits.min;
+        let max = anchors[0] + axis * limits.max;
+        gizmos.draw_line(min, max, color);
+
+        // Draw markers at the ends of the allowed range.
+        #[cfg(feature = "2d")]
+        let marker = axis.perp() * radius * 0.25;
+        #[cfg(feature = "3d")]
+        let marker = axis.any_orthonormal_vector() * radius * 0.25;
+        gizmos.draw_line(min - marker, min + marker, color);
+        gizmos.draw_line(max - marker, max + marker, color);
+    }
+}
+
+impl JointGizmos for RevoluteJoint {
+    fn draw_limits(
+        &self,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        rotations: [&Rotation; 2],
+        anchors: [Vector; 2],
+        radius: Scalar,
+        color: Color,
+    ) {
+        let Some(angle_limit) = self.angle_limit else {
+            return;
+        };
+
+        // The axis that the angle is measured from, as in the solver.
+        let limit_axis = Vector3::new(
+            self.aligned_axis.z,
+            self.aligned_axis.x,
+            self.aligned_axis.y,
+        );
+        #[cfg(feature = "2d")]
+        let (axis, limit_axis) = (Vector3::Z, limit_axis.truncate());
+
+        #[cfg(feature = "3d")]
+        let axis = rotations[0].rotate(self.aligned_axis);
+        let start = rotations[0].rotate(limit_axis);
+        let [min, max] = draw_arc(
+            gizmos,
+            anchors[0],
+            axis,
+            start,
+            angle_limit.alpha,
+            angle_limit.beta,
+            radius,
+            color,
+        );
+        gizmos.draw_line(anchors[0], min, color);
+        gizmos.draw_line(anchors[0], max, color);
+
+        // Draw the current orientation of the second body within the limits.
+        let current = rotations[1].rotate(limit_axis);
+        gizmos.draw_line(anchors[1], anchors[1] + current * radius, color);
+    }
+}
+
+impl JointGizmos for SphericalJoint {
+    #[cfg(feature = "3d")]
+    fn draw_limits(
+        &self,
+        gizmos: &mut Gizmos<PhysicsGizmos>,
+        rotations: [&Rotation; 2],
+        anchors: [Vector; 2],
+        radius: Scalar,
+        color: Color,
+    ) {
+        let swing1 = rotations[0].rotate(self.swing_axis);
+        let swing2 = rotations[1].rotate(self.swing_axis);
+
+        if let Some(swing_limit) = self.swing_limit {
+            // Draw the cone that the swing axis of the second body is limited to.
+            let angle = swing_limit.alpha.abs().max(swing_limit.beta.abs()).min(PI);
+            let center = anchors[0] + swing1 * radius * angle.cos();
+            let edge = swing1.any_orthonormal_vector() * radius * angle.sin();
+            draw_arc(gizmos, center, swing1, edge, 0.0, 2.0 * PI, 1.0, color);
+
+            for i in 0..4 {
+                let rotation = Quaternion::from_axis_angle(swing1, i as Scalar * PI * 0.5);
+                gizmos.draw_line(anchors[0], center + rotation * edge, color);
+            }
+
+            // Draw the current swing axis of the second body.
+            gizmos.draw_line(anchors[1], anchors[1] + swing2 * radius, color);
+        }
+
+        if let Some(twist_limit) = self.twist_limit {
+            // Draw the arc that the twist axis of the second body is limited to.
+            let twist1 = rotations[0].rotate(self.twist_axis);
+            let twist2 = rotations[1].rotate(self.twist_axis);
+            let [min, max] = draw_arc(
+                gizmos,
+                anchors[0],
+                swing1,
+                twist1,
+                twist_limit.alpha,
+                twist_limit.beta,
+                radius,
+                color,
+            );
+            gizmos.draw_line(anchors[0], min, color);
+            gizmos.draw_line(anchors[0], max, color);
+            gizmos.draw_line(anchors[1], anchors[1] + twist2 * radius, color);
+        }
+    }
+}
@@ -4,14 +4,39 @@
 
 #![allow(clippy::unnecessary_cast)]
 
+#[cfg(all(
+    feature = "debug-mesh",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+mod collider_meshes;
 mod configuration;
 mod gizmos;
+mod joint_gizmos;
+mod recording;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+mod spatial_query_gizmos;
 
 pub use configuration::*;
 pub use gizmos::*;
+pub use recording::*;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use spatial_query_gizmos::*;
+
+use joint_gizmos::JointGizmos;
 
-use crate::prelude::*;
-use bevy::{ecs::query::Has, prelude::*, utils::intern::Interned};
+use crate::{plugins::collision::broad_phase::StaticAabbTree, prelude::*};
+use bevy::{
+    ecs::query::Has,
+    prelude::*,
+    utils::{intern::Interned, HashMap},
+};
 
 /// A plugin that renders physics objects and properties for debugging purposes.
 /// It is not enabled by default and must be added manually.
@@ -19,19 +44,30 @@ use bevy::{ecs::query::Has, prelude::*, utils::intern::Interned};
 /// Currently, the following are supported for debug rendering:
 ///
 /// - The axes and center of mass of [rigid bodies](RigidBody)
+/// - The [`CenterOfMass`] and [`Inertia`] of dynamic bodies
+/// - The [`LinearVelocity`] and [`AngularVelocity`] of rigid bodies
 /// - [AABBs](ColliderAabb)
-/// - [Collider] wireframes
-/// - Using different colors for [sleeping](Sleeping) bodies
-/// - [Contacts]
-/// - [Joints](joints)
-/// - [`RayCaster`]
-/// - [`ShapeCaster`]
+/// - The broad phase tree of static colliders and the number of [`BroadCollisionPairs`] in different regions
+/// - [Collider] wireframes, and optionally translucent collider meshes with the `debug-mesh` feature
+/// - Using different colors for [sleeping](Sleeping) bodies, or coloring colliders based on the state of their bodies
+/// - [Simulation islands](PhysicsIslands), including a different color for each island and how close it is to falling asleep
+/// - A heatmap of the [contact](Contacts) stress on dynamic bodies relative to their weight, showing which parts of a structure are near instability
+/// - [Contacts], including contact points, normals and the normal and tangent impulses of each contact
+/// - [Joints](joints), including the frames of the bodies at the joint anchors and joint limits
+/// - [`RayCaster`] and [`ShapeCaster`], including their hits
+/// - One-shot spatial queries performed through [`DebugSpatialQuery`]
+/// - Replaying recorded physics steps frame by frame using the [`PhysicsDebugRecording`]
 /// - Changing the visibility of entities to only show debug rendering
 ///
 /// By default, [AABBs](ColliderAabb) and [contacts](Contacts) are not debug rendered.
 /// You can configure the [`PhysicsGizmos`] retrieved from `GizmoConfigStore` for the global configuration
 /// and the [`DebugRender`] component for entity-level configuration.
 ///
+/// In large scenes, rendering can be restricted to specific [collision layers](CollisionLayers)
+/// using [`PhysicsGizmos::layer_filter`], or to entities with a [`DebugRender`] component
+/// using [`PhysicsGizmos::only_debug_render_entities`]. The filters also apply to [`RayCaster`] and [`ShapeCaster`]
+/// entities, so individual casters can be rendered by adding a [`DebugRender`] component to them.
+///
 /// # Example
 ///
 /// ```no_run
@@ -107,11 +143,20 @@ impl Plugin for PhysicsDebugPlugin {
 
         app.register_type::<PhysicsGizmos>()
             .register_type::<DebugRender>()
+            .init_resource::<PhysicsDebugRecording>()
+            .add_systems(
+                PhysicsSchedule,
+                recording::record_debug_frame.after(PhysicsStepSet::SpatialQuery),
+            )
             .add_systems(
                 self.schedule,
                 (
                     debug_render_axes,
+                    debug_render_mass_properties,
                     debug_render_aabbs,
+                    debug_render_broad_phase,
+                    debug_render_velocities,
+                    debug_render_islands,
                     #[cfg(all(
                         feature = "default-collider",
                         any(feature = "parry-f32", feature = "parry-f64")
@@ -131,6 +176,15 @@ impl Plugin for PhysicsDebugPlugin {
                     ))]
                     debug_render_shapecasts,
                 )
+                    .after(PhysicsSet::StepSimulation)
+                    .run_if(|store: Res<GizmoConfigStore>| {
+                        store.config::<PhysicsGizmos>().0.enabled
+                    })
+                    .run_if(recording::is_not_replaying),
+            )
+            .add_systems(
+                self.schedule,
+                recording::debug_render_recording
                     .after(PhysicsSet::StepSimulation)
                     .run_if(|store: Res<GizmoConfigStore>| {
                         store.config::<PhysicsGizmos>().0.enabled
@@ -140,6 +194,17 @@ impl Plugin for PhysicsDebugPlugin {
                 self.schedule,
                 change_mesh_visibility.after(PhysicsSet::StepSimulation),
             );
+
+        // Runs even when the gizmos are disabled to despawn the meshes
+        #[cfg(all(
+            feature = "debug-mesh",
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        app.add_systems(
+            self.schedule,
+            collider_meshes::update_collider_debug_meshes.after(PhysicsSet::StepSimulation),
+        );
     }
 }
 
@@ -151,12 +216,17 @@ fn debug_render_axes(
         &CenterOfMass,
         Has<Sleeping>,
         Option<&DebugRender>,
+        Option<&CollisionLayers>,
     )>,
     mut gizmos: Gizmos<PhysicsGizmos>,
     store: Res<GizmoConfigStore>,
 ) {
     let config = store.config::<PhysicsGizmos>().1;
-    for (pos, rot, local_com, sleeping, render_config) in &bodies {
+    for (pos, rot, local_com, sleeping, render_config, layers) in &bodies {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
         // If the body is sleeping, the colors will be multiplied by the sleeping color multiplier
         if let Some(lengths) = render_config.map_or(config.axis_lengths, |c| c.axis_lengths) {
             let mul = if sleeping {
@@ -208,12 +278,148 @@ fn debug_render_axes(
     }
 }
 
+#[allow(clippy::type_complexity)]
+fn debug_render_mass_properties(
+    bodies: Query<(
+        &RigidBody,
+        &Position,
+        &Rotation,
+        &CenterOfMass,
+        &Mass,
+        &Inertia,
+        Option<&DebugRender>,
+        Option<&CollisionLayers>,
+    )>,
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    store: Res<GizmoConfigStore>,
+) {
+    let config = store.config::<PhysicsGizmos>().1;
+
+    if config.center_of_mass_color.is_none() && config.inertia_color.is_none() {
+        return;
+    }
+
+    for (rb, pos, rot, local_com, mass, inertia, render_config, layers) in &bodies {
+        if !rb.is_dynamic() || !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
+        let global_com = pos.0 + rot.rotate(local_com.0);
+
+        // Draw a cross at the center of mass and a line from the body's origin to it
+        if let Some(color) = config.center_of_mass_color {
+            #[cfg(feature = "2d")]
+            let size = 4.0;
+            #[cfg(feature = "3d")]
+            let size = 0.05;
+
+            gizmos.draw_line(pos.0, global_com, color);
+            gizmos.draw_line(
+                global_com - rot.rotate(Vector::X * size),
+                global_com + rot.rotate(Vector::X * size),
+                color,
+            );
+            gizmos.draw_line(
+                global_com - rot.rotate(Vector::Y * size),
+                global_com + rot.rotate(Vector::Y * size),
+                color,
+            );
+            #[cfg(feature = "3d")]
+            gizmos.draw_line(
+                global_com - rot.rotate(Vector::Z * size),
+                global_com + rot.rotate(Vector::Z * size),
+                color,
+            );
+        }
+
+        let Some(color) = config.inertia_color else {
+            continue;
+        };
+        if mass.0 <= Scalar::EPSILON {
+            continue;
+        }
+
+        // Draw a disk with the same mass and inertia as the body, I = 1/2 * m * r^2
+        #[cfg(feature = "2d")]
+        {
+            let radius = (2.0 * inertia.0 / mass.0).max(0.0).sqrt();
+            gizmos.circle_2d(global_com.f32(), radius as f32, color);
+        }
+
+        // Draw a box with the same mass and inertia as the body along the principal axes of inertia,
+        // I_x = 1/12 * m * (h^2 + d^2), and similarly for the other axes
+        #[cfg(feature = "3d")]
+        {
+            let (principal, axes) = principal_inertia(inertia.0);
+            let size = Vector::new(
+                principal.y + principal.z - principal.x,
+                principal.x + principal.z - principal.y,
+                principal.x + principal.y - principal.z,
+            ) * (6.0 / mass.0);
+            let size = size.max(Vector::ZERO).to_array().map(|s| s.sqrt());
+
+            gizmos.cuboid(
+                Transform::from_scale(Vector::from_array(size).f32())
+                    .with_translation(global_com.f32())
+                    .with_rotation((rot.0 * Quaternion::from_mat3(&axes)).f32()),
+                color,
+            );
+        }
+    }
+}
+
+/// Computes the principal moments of inertia and the principal axes of the given
+/// symmetric inertia tensor using the Jacobi eigenvalue algorithm.
+///
+/// The columns of the returned matrix are the principal axes, and it is always a proper rotation.
+#[cfg(feature = "3d")]
+pub(crate) fn principal_inertia(inertia: Matrix3) -> (Vector, Matrix3) {
+    let mut a = inertia;
+    let mut axes = Matrix3::IDENTITY;
+
+    for _ in 0..16 {
+        // Find the largest off-diagonal element
+        let (p, q) = [(0, 1), (0, 2), (1, 2)]
+            .into_iter()
+            .max_by(|&(p1, q1), &(p2, q2)| a.col(q1)[p1].abs().total_cmp(&a.col(q2)[p2].abs()))
+            .unwrap();
+        let a_pq = a.col(q)[p];
+
+        if a_pq.abs() <= Scalar::EPSILON * (a.col(p)[p].abs() + a.col(q)[q].abs()) {
+            break;
+        }
+
+        // Rotate to eliminate the off-diagonal element
+        let theta = (a.col(q)[q] - a.col(p)[p]) / (2.0 * a_pq);
+        let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+        let c = 1.0 / (t * t + 1.0).sqrt();
+        let s = t * c;
+
+        let mut rotation = Matrix3::IDENTITY;
+        rotation.col_mut(p)[p] = c;
+        rotation.col_mut(q)[q] = c;
+        rotation.col_mut(q)[p] = s;
+        rotation.col_mut(p)[q] = -s;
+
+        a = rotation.transpose() * a * rotation;
+        axes *= rotation;
+    }
+
+    if axes.determinant() < 0.0 {
+        axes.z_axis = -axes.z_axis;
+    }
+
+    (Vector::new(a.x_axis.x, a.y_axis.y, a.z_axis.z), axes)
+}
+
+#[allow(clippy::type_complexity)]
 fn debug_render_aabbs(
     aabbs: Query<(
         Entity,
         &ColliderAabb,
         Option<&ColliderParent>,
         Option<&DebugRender>,
+        Option<&CollisionLayers>,
     )>,
     sleeping: Query<(), With<Sleeping>>,
     mut gizmos: Gizmos<PhysicsGizmos>,
@@ -221,7 +427,11 @@ fn debug_render_aabbs(
 ) {
     let config = store.config::<PhysicsGizmos>().1;
     #[cfg(feature = "2d")]
-    for (entity, aabb, collider_parent, render_config) in &aabbs {
+    for (entity, aabb, collider_parent, render_config, layers) in &aabbs {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
         if let Some(mut color) = render_config.map_or(config.aabb_color, |c| c.aabb_color) {
             let collider_parent = collider_parent.map_or(entity, |p| p.get());
 
@@ -244,7 +454,11 @@ fn debug_render_aabbs(
     }
 
     #[cfg(feature = "3d")]
-    for (entity, aabb, collider_parent, render_config) in &aabbs {
+    for (entity, aabb, collider_parent, render_config, layers) in &aabbs {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
         if let Some(mut color) = render_config.map_or(config.aabb_color, |c| c.aabb_color) {
             let collider_parent = collider_parent.map_or(entity, |p| p.get());
 
@@ -267,11 +481,221 @@ fn debug_render_aabbs(
     }
 }
 
+#[allow(clippy::type_complexity)]
+fn debug_render_velocities(
+    bodies: Query<(
+        &Position,
+        &Rotation,
+        &CenterOfMass,
+        &LinearVelocity,
+        &AngularVelocity,
+        Option<&DebugRender>,
+        Option<&CollisionLayers>,
+    )>,
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    store: Res<GizmoConfigStore>,
+) {
+    let config = store.config::<PhysicsGizmos>().1;
+    let scale = config.velocity_scale;
+
+    for (pos, rot, local_com, lin_vel, ang_vel, render_config, layers) in &bodies {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
+        draw_velocities(
+            &mut gizmos,
+            pos.0 + rot.rotate(local_com.0),
+            rot,
+            lin_vel.0,
+            ang_vel,
+            scale,
+            render_config.map_or(config.linear_velocity_color, |c| c.linear_velocity_color),
+            render_config.map_or(config.angular_velocity_color, |c| c.angular_velocity_color),
+        );
+    }
+}
+
+/// Draws an arrow for the linear velocity and an arc for the angular velocity of a body
+/// with the given center of mass.
+#[allow(clippy::too_many_arguments)]
+fn draw_velocities(
+    gizmos: &mut Gizmos<PhysicsGizmos>,
+    global_com: Vector,
+    #[cfg_attr(feature = "3d", allow(unused_variables))] rot: &Rotation,
+    lin_vel: Vector,
+    ang_vel: &AngularVelocity,
+    scale: Scalar,
+    linear_velocity_color: Option<Color>,
+    angular_velocity_color: Option<Color>,
+) {
+    if let Some(color) = linear_velocity_color {
+        if lin_vel != Vector::ZERO {
+            #[cfg(feature = "2d")]
+            gizmos.draw_arrow(global_com, global_com + lin_vel * scale, 8.0, color);
+            #[cfg(feature = "3d")]
+            gizmos.draw_arrow(global_com, global_com + lin_vel * scale, 0.1, color);
+        }
+    }
+
+    if let Some(color) = angular_velocity_color {
+        // Draw an arc around the axis of rotation, covering the angle rotated during the scaled time
+        #[cfg(feature = "2d")]
+        let (axis, start, angle) = (Vector3::Z, rot.rotate(Vector::X), ang_vel.0 * scale);
+        #[cfg(feature = "3d")]
+        let (axis, angle) = (ang_vel.0.normalize_or_zero(), ang_vel.0.length() * scale);
+        #[cfg(feature = "3d")]
+        let start = axis.any_orthonormal_vector();
+
+        if angle.abs() > Scalar::EPSILON {
+            let angle = angle.clamp(-2.0 * PI, 2.0 * PI);
+            let [_, end] = draw_arc(
+                gizmos,
+                global_com,
+                axis,
+                start,
+                0.0,
+                angle,
+                ANGULAR_VELOCITY_RADIUS,
+                color,
+            );
+
+            // Draw an arrowhead at the end of the arc, pointing in the direction of rotation
+            #[cfg(feature = "2d")]
+            let tangent = Rotation::from_radians(angle).rotate(start).perp() * angle.signum();
+            #[cfg(feature = "3d")]
+            let tangent = axis.cross(Quaternion::from_axis_angle(axis, angle) * start);
+            #[cfg(feature = "2d")]
+            gizmos.draw_arrow(end - tangent * 0.01, end, 8.0, color);
+            #[cfg(feature = "3d")]
+            gizmos.draw_arrow(end - tangent * 0.01, end, 0.1, color);
+        }
+    }
+}
+
+fn debug_render_broad_phase(
+    static_tree: Option<Res<StaticAabbTree>>,
+    broad_collision_pairs: Option<Res<BroadCollisionPairs>>,
+    aabbs: Query<&ColliderAabb>,
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    store: Res<GizmoConfigStore>,
+) {
+    let config = store.config::<PhysicsGizmos>().1;
+
+    // Draw the nodes of the tree used for static and sleeping colliders, with deeper levels drawn darker
+    if let (Some(color), Some(static_tree)) = (config.broad_phase_tree_color, static_tree) {
+        let [h, s, l, a] = color.as_hsla_f32();
+        static_tree.for_each_node(|aabb, depth| {
+            let color = Color::hsla(h, s, l * 0.8_f32.powi(depth as i32), a);
+            draw_aabb(&mut gizmos, aabb, color);
+        });
+    }
+
+    // Draw a grid of cells where the opacity indicates the number of collision pairs in each cell
+    if let (Some(color), Some(pairs)) = (config.broad_phase_pair_color, broad_collision_pairs) {
+        let cell_size = config.broad_phase_cell_size;
+        if cell_size <= 0.0 {
+            return;
+        }
+
+        let mut cells = HashMap::<_, usize>::new();
+        for (entity1, entity2) in pairs.0.iter() {
+            let Ok([aabb1, aabb2]) = aabbs.get_many([*entity1, *entity2]) else {
+                continue;
+            };
+            let center = (aabb1.center() + aabb2.center()) * 0.5;
+            let cell = (center / cell_size).floor().to_array().map(|c| c as i64);
+            *cells.entry(cell).or_default() += 1;
+        }
+
+        let max_count = cells.values().copied().max().unwrap_or(0);
+        for (cell, count) in cells {
+            let min = Vector::from_array(cell.map(|c| c as Scalar)) * cell_size;
+            let aabb = ColliderAabb::new(
+                min + Vector::splat(cell_size * 0.5),
+                Vector::splat(cell_size * 0.5),
+            );
+            let alpha = color.a() * count as f32 / max_count as f32;
+            draw_aabb(&mut gizmos, &aabb, color.with_a(alpha));
+        }
+    }
+}
+
+fn debug_render_islands(
+    colliders: Query<(&ColliderAabb, &ColliderParent)>,
+    bodies: Query<(Option<&TimeSleeping>, Has<Sleeping>, Has<SleepingDisabled>)>,
+    islands: Option<Res<PhysicsIslands>>,
+    deactivation_time: Res<DeactivationTime>,
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    store: Res<GizmoConfigStore>,
+) {
+    let config = store.config::<PhysicsGizmos>().1;
+    let (Some(color), Some(islands)) = (config.island_sleep_timer_color, islands) else {
+        return;
+    };
+
+    // Compute the bounds of each island from the AABBs of the colliders attached to its bodies
+    let mut island_aabbs = HashMap::<usize, ColliderAabb>::new();
+    for (aabb, collider_parent) in &colliders {
+        if let Some(index) = islands.island_index(collider_parent.get()) {
+            island_aabbs
+                .entry(index)
+                .and_modify(|island_aabb| *island_aabb = island_aabb.merged(*aabb))
+                .or_insert(*aabb);
+        }
+    }
+
+    for (index, island) in islands.iter().enumerate() {
+        let Some(aabb) = island_aabbs.get(&index) else {
+            continue;
+        };
+
+        // The island falls asleep once all of its bodies have been still for long enough,
+        // so the progress is determined by the body that has been still for the shortest time.
+        let mut progress: Scalar = 1.0;
+        for entity in island {
+            let Ok((time_sleeping, is_sleeping, sleeping_disabled)) = bodies.get(*entity) else {
+                continue;
+            };
+            if is_sleeping {
+                progress = 1.0;
+                break;
+            }
+            let time_sleeping = if sleeping_disabled {
+                0.0
+            } else {
+                time_sleeping.map_or(0.0, |t| t.0)
+            };
+            progress = progress.min(time_sleeping / deactivation_time.0.max(Scalar::EPSILON));
+        }
+        let progress = progress.clamp(0.0, 1.0);
+
+        let outline_color = if config.island_colors {
+            island_color(island)
+        } else {
+            color
+        };
+        draw_aabb(&mut gizmos, aabb, outline_color);
+
+        // Draw a bar above the island that fills up as the island gets closer to falling asleep
+        let mut start = aabb.min;
+        start.y = aabb.max.y + ISLAND_SLEEP_BAR_OFFSET;
+        #[cfg(feature = "3d")]
+        {
+            start.z = aabb.center().z;
+        }
+        let end = start + Vector::X * aabb.size().x;
+        let filled_end = start.lerp(end, progress);
+        gizmos.draw_line(start, filled_end, color);
+        gizmos.draw_line(filled_end, end, color.with_a(color.a() * 0.25));
+    }
+}
+
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
 ))]
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn debug_render_colliders(
     mut colliders: Query<(
         Entity,
@@ -280,18 +704,101 @@ fn debug_render_colliders(
         &Rotation,
         Option<&ColliderParent>,
         Option<&DebugRender>,
+        Option<&CollisionLayers>,
     )>,
-    sleeping: Query<(), With<Sleeping>>,
+    bodies: Query<(&RigidBody, Has<Sleeping>, Option<&TimeSleeping>)>,
+    masses: Query<(&Mass, Option<&GravityScale>)>,
+    islands: Option<Res<PhysicsIslands>>,
+    collisions: Option<Res<Collisions>>,
+    gravity: Res<Gravity>,
+    time: Res<Time<Substeps>>,
     mut gizmos: Gizmos<PhysicsGizmos>,
     store: Res<GizmoConfigStore>,
 ) {
     let config = store.config::<PhysicsGizmos>().1;
-    for (entity, collider, position, rotation, collider_parent, render_config) in &mut colliders {
-        if let Some(mut color) = render_config.map_or(config.collider_color, |c| c.collider_color) {
-            let collider_parent = collider_parent.map_or(entity, |p| p.get());
 
+    // Sum up the magnitudes of the contact forces acting on each body for the stress heatmap
+    let mut contact_forces = HashMap::<Entity, Scalar>::new();
+    if let (Some(_), Some(collisions)) = (config.stress_heatmap, &collisions) {
+        let delta_secs = time.delta_seconds_f64().adjust_precision();
+        if delta_secs > 0.0 {
+            let body_of = |collider: Entity| {
+                colliders
+                    .get(collider)
+                    .ok()
+                    .and_then(|(.., parent, _, _)| parent)
+                    .map_or(collider, |parent| parent.get())
+            };
+            for contacts in collisions
+                .iter()
+                .filter(|contacts| contacts.during_current_frame)
+            {
+                let force = contacts.total_normal_impulse / delta_secs;
+                *contact_forces.entry(body_of(contacts.entity1)).or_default() += force;
+                *contact_forces.entry(body_of(contacts.entity2)).or_default() += force;
+            }
+        }
+    }
+
+    for (entity, collider, position, rotation, collider_parent, render_config, layers) in
+        &mut colliders
+    {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
+        let collider_parent = collider_parent.map_or(entity, |p| p.get());
+        let body = bodies.get(collider_parent).ok();
+        let is_sleeping = body.is_some_and(|(_, sleeping, _)| sleeping);
+
+        // Color the collider based on the contact stress of its body relative to its weight
+        if let (Some(heatmap), None, Some((RigidBody::Dynamic, ..))) =
+            (config.stress_heatmap, render_config, body)
+        {
+            let weight = masses.get(collider_parent).map_or(0.0, |(mass, scale)| {
+                mass.0 * gravity.0.length() * scale.map_or(1.0, |scale| scale.0)
+            });
+            if weight > 0.0 {
+                let force = contact_forces
+                    .get(&collider_parent)
+                    .copied()
+                    .unwrap_or_default();
+                let color = heatmap.color(force / weight);
+                gizmos.draw_collider(collider, *position, *rotation, color);
+                continue;
+            }
+        }
+
+        // Color the collider based on the island of its body
+        let island = islands
+            .as_ref()
+            .filter(|_| config.island_colors && render_config.is_none())
+            .and_then(|islands| islands.island(collider_parent));
+        if let Some(island) = island {
+            let mut color = island_color(island);
             // If the body is sleeping, multiply the color by the sleeping color multiplier
-            if sleeping.contains(collider_parent) {
+            if let (true, Some(mul)) = (is_sleeping, config.sleeping_color_multiplier) {
+                let [h, s, l, a] = color.as_hsla_f32();
+                color = Color::hsla(h * mul[0], s * mul[1], l * mul[2], a * mul[3]);
+            }
+            gizmos.draw_collider(collider, *position, *rotation, color);
+            continue;
+        }
+
+        // Color the collider based on the state of its body
+        if let (Some(colors), None) = (config.body_state_colors, render_config) {
+            let color = colors.color(
+                body.map(|(rb, ..)| rb),
+                is_sleeping,
+                body.and_then(|(.., time_sleeping)| time_sleeping),
+            );
+            gizmos.draw_collider(collider, *position, *rotation, color);
+            continue;
+        }
+
+        if let Some(mut color) = render_config.map_or(config.collider_color, |c| c.collider_color) {
+            // If the body is sleeping, multiply the color by the sleeping color multiplier
+            if is_sleeping {
                 let [h, s, l, a] = color.as_hsla_f32();
                 if let Some(mul) = render_config.map_or(config.sleeping_color_multiplier, |c| {
                     c.sleeping_color_multiplier
@@ -304,8 +811,22 @@ fn debug_render_colliders(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn debug_render_contacts(
-    colliders: Query<(&Position, &Rotation)>,
+    colliders: Query<(
+        &Position,
+        &Rotation,
+        Option<&ColliderParent>,
+        Option<&DebugRender>,
+        Option<&CollisionLayers>,
+    )>,
+    bodies: Query<(
+        &Position,
+        &Rotation,
+        &CenterOfMass,
+        &LinearVelocity,
+        &AngularVelocity,
+    )>,
     mut collisions: EventReader<Collision>,
     mut gizmos: Gizmos<PhysicsGizmos>,
     store: Res<GizmoConfigStore>,
@@ -313,18 +834,53 @@ fn debug_render_contacts(
 ) {
     let config = store.config::<PhysicsGizmos>().1;
 
-    if config.contact_point_color.is_none() && config.contact_normal_color.is_none() {
+    if config.contact_point_color.is_none()
+        && config.contact_normal_color.is_none()
+        && config.contact_normal_impulse_color.is_none()
+        && config.contact_tangent_impulse_color.is_none()
+    {
         return;
     }
 
+    let delta_secs = time.delta_seconds_f64().adjust_precision();
+
+    // Computes the velocity of the body that the given collider is attached to at a world-space point.
+    let point_velocity = |collider_parent: Option<&ColliderParent>, point: Vector| {
+        let Some(Ok((position, rotation, center_of_mass, lin_vel, ang_vel))) =
+            collider_parent.map(|parent| bodies.get(parent.get()))
+        else {
+            return Vector::ZERO;
+        };
+        let r = point - position.0 - rotation.rotate(center_of_mass.0);
+        #[cfg(feature = "2d")]
+        {
+            lin_vel.0 + ang_vel.0 * r.perp()
+        }
+        #[cfg(feature = "3d")]
+        {
+            lin_vel.0 + ang_vel.0.cross(r)
+        }
+    };
+
     for Collision(contacts) in collisions.read() {
-        let Ok((position1, rotation1)) = colliders.get(contacts.entity1) else {
+        let Ok((position1, rotation1, collider_parent1, render_config1, layers1)) =
+            colliders.get(contacts.entity1)
+        else {
             continue;
         };
-        let Ok((position2, rotation2)) = colliders.get(contacts.entity2) else {
+        let Ok((position2, rotation2, collider_parent2, render_config2, layers2)) =
+            colliders.get(contacts.entity2)
+        else {
             continue;
         };
 
+        // Render the contacts if either of the colliders passes the filters
+        if !passes_filters(config, render_config1, layers1)
+            && !passes_filters(config, render_config2, layers2)
+        {
+            continue;
+        }
+
         for manifold in contacts.manifolds.iter() {
             for contact in manifold.contacts.iter() {
                 let p1 = contact.global_point1(position1, rotation1);
@@ -361,8 +917,7 @@ fn debug_render_contacts(
                     let length = match config.contact_normal_scale {
                         ContactGizmoScale::Constant(length) => length,
                         ContactGizmoScale::Scaled(scale) => {
-                            scale * contacts.total_normal_impulse
-                                / time.delta_seconds_f64().adjust_precision()
+                            scale * contacts.total_normal_impulse / delta_secs
                         }
                     };
 
@@ -378,12 +933,43 @@ fn debug_render_contacts(
                         gizmos.draw_arrow(p2, p2 + normal2 * length, 0.1, color_dim);
                     }
                 }
+
+                // Draw the normal impulse applied to the first body, pushing it away from the second body
+                if let Some(color) = config.contact_normal_impulse_color {
+                    let length =
+                        config.contact_impulse_scale * contact.normal_force(delta_secs).abs();
+
+                    #[cfg(feature = "2d")]
+                    gizmos.draw_arrow(p1, p1 - normal1 * length, 8.0, color);
+                    #[cfg(feature = "3d")]
+                    gizmos.draw_arrow(p1, p1 - normal1 * length, 0.1, color);
+                }
+
+                // Draw the tangent impulse applied to the first body along the sliding direction.
+                // The friction impulse is negative, so the arrow points against the relative velocity.
+                if let Some(color) = config.contact_tangent_impulse_color {
+                    let relative_velocity =
+                        point_velocity(collider_parent1, p1) - point_velocity(collider_parent2, p2);
+                    let tangent_velocity =
+                        relative_velocity - normal1 * normal1.dot(relative_velocity);
+
+                    if let Some(tangent) = tangent_velocity.try_normalize() {
+                        let impulse = config.contact_impulse_scale
+                            * contact.tangent_force(delta_secs)
+                            * tangent;
+
+                        #[cfg(feature = "2d")]
+                        gizmos.draw_arrow(p1, p1 + impulse, 8.0, color);
+                        #[cfg(feature = "3d")]
+                        gizmos.draw_arrow(p1, p1 + impulse, 0.1, color);
+                    }
+                }
             }
         }
     }
 }
 
-fn debug_render_joints<T: Joint>(
+fn debug_render_joints<T: JointGizmos>(
     bodies: Query<(&Position, &Rotation, Has<Sleeping>)>,
     joints: Query<(&T, Option<&DebugRender>)>,
     mut gizmos: Gizmos<PhysicsGizmos>,
@@ -394,44 +980,59 @@ fn debug_render_joints<T: Joint>(
         if let Ok([(pos1, rot1, sleeping1), (pos2, rot2, sleeping2)]) =
             bodies.get_many(joint.entities())
         {
-            if let Some(mut anchor_color) = config.joint_anchor_color {
-                // If both bodies are sleeping, multiply the color by the sleeping color multiplier
+            // If both bodies are sleeping, multiply the colors by the sleeping color multiplier
+            let sleeping_color = |color: Color| {
                 if sleeping1 && sleeping2 {
-                    let [h, s, l, a] = anchor_color.as_hsla_f32();
+                    let [h, s, l, a] = color.as_hsla_f32();
                     if let Some(mul) = render_config.map_or(config.sleeping_color_multiplier, |c| {
                         c.sleeping_color_multiplier
                     }) {
-                        anchor_color = Color::hsla(h * mul[0], s * mul[1], l * mul[2], a * mul[3]);
+                        return Color::hsla(h * mul[0], s * mul[1], l * mul[2], a * mul[3]);
                     }
                 }
+                color
+            };
 
-                gizmos.draw_line(
-                    pos1.0,
-                    pos1.0 + rot1.rotate(joint.local_anchor_1()),
-                    anchor_color,
-                );
-                gizmos.draw_line(
-                    pos2.0,
-                    pos2.0 + rot2.rotate(joint.local_anchor_2()),
-                    anchor_color,
-                );
+            let anchor1 = pos1.0 + rot1.rotate(joint.local_anchor_1());
+            let anchor2 = pos2.0 + rot2.rotate(joint.local_anchor_2());
+
+            if let Some(anchor_color) = config.joint_anchor_color {
+                let anchor_color = sleeping_color(anchor_color);
+                gizmos.draw_line(pos1.0, anchor1, anchor_color);
+                gizmos.draw_line(pos2.0, anchor2, anchor_color);
             }
-            if let Some(mut separation_color) = config.joint_separation_color {
-                // If both bodies are sleeping, multiply the color by the sleeping color multiplier
-                if sleeping1 && sleeping2 {
-                    let [h, s, l, a] = separation_color.as_hsla_f32();
-                    if let Some(mul) = render_config.map_or(config.sleeping_color_multiplier, |c| {
-                        c.sleeping_color_multiplier
-                    }) {
-                        separation_color =
-                            Color::hsla(h * mul[0], s * mul[1], l * mul[2], a * mul[3]);
-                    }
+            if let Some(separation_color) = config.joint_separation_color {
+                gizmos.draw_line(anchor1, anchor2, sleeping_color(separation_color));
+            }
+
+            // Draw the frames of both bodies at their anchors.
+            // If the local anchors are configured correctly, the frames should be at the same point.
+            if let Some(length) = config.joint_frame_length {
+                let [x_color, y_color, _z_color] = [
+                    Color::hsla(0.0, 1.0, 0.5, 1.0),
+                    Color::hsla(120.0, 1.0, 0.4, 1.0),
+                    Color::hsla(220.0, 1.0, 0.6, 1.0),
+                ]
+                .map(&sleeping_color);
+
+                for (anchor, rot) in [(anchor1, rot1), (anchor2, rot2)] {
+                    gizmos.draw_line(anchor, anchor + rot.rotate(Vector::X * length), x_color);
+                    gizmos.draw_line(anchor, anchor + rot.rotate(Vector::Y * length), y_color);
+                    #[cfg(feature = "3d")]
+                    gizmos.draw_line(anchor, anchor + rot.rotate(Vector::Z * length), _z_color);
                 }
+            }
 
-                gizmos.draw_line(
-                    pos1.0 + rot1.rotate(joint.local_anchor_1()),
-                    pos2.0 + rot2.rotate(joint.local_anchor_2()),
-                    separation_color,
+            if let Some(limit_color) = config.joint_limit_color {
+                let radius = config
+                    .joint_frame_length
+                    .unwrap_or(joint_gizmos::DEFAULT_LIMIT_RADIUS);
+                joint.draw_limits(
+                    &mut gizmos,
+                    [rot1, rot2],
+                    [anchor1, anchor2],
+                    radius,
+                    sleeping_color(limit_color),
                 );
             }
         }
@@ -439,12 +1040,21 @@ fn debug_render_joints<T: Joint>(
 }
 
 fn debug_render_raycasts(
-    query: Query<(&RayCaster, &RayHits)>,
+    query: Query<(
+        &RayCaster,
+        &RayHits,
+        Option<&DebugRender>,
+        Option<&CollisionLayers>,
+    )>,
     mut gizmos: Gizmos<PhysicsGizmos>,
     store: Res<GizmoConfigStore>,
 ) {
     let config = store.config::<PhysicsGizmos>().1;
-    for (ray, hits) in &query {
+    for (ray, hits, render_config, layers) in &query {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
         let ray_color = config
             .raycast_color
             .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0));
@@ -473,12 +1083,21 @@ fn debug_render_raycasts(
     any(feature = "parry-f32", feature = "parry-f64")
 ))]
 fn debug_render_shapecasts(
-    query: Query<(&ShapeCaster, &ShapeHits)>,
+    query: Query<(
+        &ShapeCaster,
+        &ShapeHits,
+        Option<&DebugRender>,
+        Option<&CollisionLayers>,
+    )>,
     mut gizmos: Gizmos<PhysicsGizmos>,
     store: Res<GizmoConfigStore>,
 ) {
     let config = store.config::<PhysicsGizmos>().1;
-    for (shape_caster, hits) in &query {
+    for (shape_caster, hits, render_config, layers) in &query {
+        if !passes_filters(config, render_config, layers) {
+            continue;
+        }
+
         let ray_color = config
             .shapecast_color
             .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0));
@@ -530,3 +1149,104 @@ fn change_mesh_visibility(
         }
     }
 }
+
+/// Returns `true` if an entity with the given [`DebugRender`] configuration and [`CollisionLayers`]
+/// passes the entity and layer filters of the [`PhysicsGizmos`] configuration and should be rendered.
+pub(crate) fn passes_filters(
+    config: &PhysicsGizmos,
+    render_config: Option<&DebugRender>,
+    layers: Option<&CollisionLayers>,
+) -> bool {
+    if render_config.is_some_and(|c| c.ignore_filters) {
+        return true;
+    }
+    if config.only_debug_render_entities && render_config.is_none() {
+        return false;
+    }
+    config.layer_filter.is_none_or(|filter| {
+        layers.copied().unwrap_or_default().memberships & filter != LayerMask::NONE
+    })
+}
+
+/// Draws the outline of the given [`ColliderAabb`].
+fn draw_aabb(gizmos: &mut Gizmos<PhysicsGizmos>, aabb: &ColliderAabb, color: Color) {
+    #[cfg(feature = "2d")]
+    gizmos.cuboid(
+        Transform::from_scale(Vector::from(aabb.size()).extend(0.0).f32())
+            .with_translation(Vector::from(aabb.center()).extend(0.0).f32()),
+        color,
+    );
+    #[cfg(feature = "3d")]
+    gizmos.cuboid(
+        Transform::from_scale(Vector::from(aabb.size()).f32())
+            .with_translation(Vector::from(aabb.center()).f32()),
+        color,
+    );
+}
+
+/// Returns the color used for the given [simulation island](PhysicsIslands).
+///
+/// The hue is based on the first body of the island, so the color stays the same
+/// while the island is unchanged, even if the order of the islands changes.
+pub(crate) fn island_color(island: &[Entity]) -> Color {
+    // Consecutive entities are rotated by the golden angle to get well-separated hues.
+    let hue = island
+        .first()
+        .map_or(0.0, |entity| entity.index() as f32 * 137.507_77);
+    Color::hsl(hue % 360.0, 0.75, 0.6)
+}
+
+/// The number of line segments used for a full circle when drawing arcs.
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// The radius of the arcs drawn for angular velocities.
+#[cfg(feature = "2d")]
+const ANGULAR_VELOCITY_RADIUS: Scalar = 10.0;
+/// The radius of the arcs drawn for angular velocities.
+#[cfg(feature = "3d")]
+const ANGULAR_VELOCITY_RADIUS: Scalar = 0.4;
+
+/// The distance between the top of a simulation island's bounds and its sleep timer bar.
+#[cfg(feature = "2d")]
+const ISLAND_SLEEP_BAR_OFFSET: Scalar = 5.0;
+/// The distance between the top of a simulation island's bounds and its sleep timer bar.
+#[cfg(feature = "3d")]
+const ISLAND_SLEEP_BAR_OFFSET: Scalar = 0.1;
+
+/// Draws an arc around `axis` at `center`, starting from `start` rotated by `alpha` and ending at `start` rotated by `beta`.
+/// Returns the start and end points of the arc.
+///
+/// The `start` vector is scaled by `radius`. In 2D, the `axis` is always the Z axis.
+#[allow(clippy::too_many_arguments)]
+fn draw_arc(
+    gizmos: &mut Gizmos<PhysicsGizmos>,
+    center: Vector,
+    #[cfg_attr(feature = "2d", allow(unused_variables))] axis: Vector3,
+    start: Vector,
+    alpha: Scalar,
+    beta: Scalar,
+    radius: Scalar,
+    color: Color,
+) -> [Vector; 2] {
+    let segments =
+        ((CIRCLE_SEGMENTS as Scalar * (beta - alpha).abs() / (2.0 * PI)).ceil() as usize).max(1);
+
+    let point = |angle: Scalar| {
+        #[cfg(feature = "2d")]
+        let direction = Rotation::from_radians(angle).rotate(start);
+        #[cfg(feature = "3d")]
+        let direction = Quaternion::from_axis_angle(axis, angle) * start;
+        center + direction * radius
+    };
+
+    let first = point(alpha);
+    let mut previous = first;
+
+    for i in 1..=segments {
+        let next = point(alpha + (beta - alpha) * i as Scalar / segments as Scalar);
+        gizmos.draw_line(previous, next, color);
+        previous = next;
+    }
+
+    [first, previous]
+}
@@ -0,0 +1,348 @@
+use super::{draw_velocities, passes_filters, PhysicsGizmoExt, PhysicsGizmos};
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A recording of the state of the simulation over a number of physics steps, which can be replayed
+/// and scrubbed through frame by frame with the [`PhysicsDebugPlugin`].
+///
+/// Each [frame](PhysicsDebugFrame) stores the positions and velocities of bodies, the positions of colliders,
+/// and the contacts at the end of a physics step. This is useful for diagnosing glitches that only last
+/// a single frame and are impossible to see while the simulation is running.
+///
+/// While a frame is being [replayed](Self::replay), the live debug rendering is replaced by the recorded frame.
+/// Collider shapes are drawn using the current [`Collider`] of each entity.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn record(keys: Res<ButtonInput<KeyCode>>, mut recording: ResMut<PhysicsDebugRecording>) {
+///     if keys.just_pressed(KeyCode::KeyR) {
+///         // Record the next 120 physics steps
+///         recording.record(120);
+///     }
+///
+///     // Scrub through the recorded frames
+///     if keys.just_pressed(KeyCode::ArrowRight) {
+///         recording.scrub(1);
+///     }
+///     if keys.just_pressed(KeyCode::ArrowLeft) {
+///         recording.scrub(-1);
+///     }
+///     if keys.just_pressed(KeyCode::Escape) {
+///         recording.stop_replay();
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PhysicsDebugRecording {
+    frames: Vec<PhysicsDebugFrame>,
+    remaining_steps: usize,
+    replayed_frame: Option<usize>,
+}
+
+impl PhysicsDebugRecording {
+    /// Starts recording the next `steps` physics steps. Previously recorded frames are cleared,
+    /// and any replay is stopped.
+    pub fn record(&mut self, steps: usize) {
+        self.frames.clear();
+        self.remaining_steps = steps;
+        self.replayed_frame = None;
+    }
+
+    /// Stops recording. The frames recorded so far are kept.
+    pub fn stop_recording(&mut self) {
+        self.remaining_steps = 0;
+    }
+
+    /// Returns `true` if physics steps are currently being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.remaining_steps > 0
+    }
+
+    /// Returns the recorded frames, one for each recorded physics step.
+    pub fn frames(&self) -> &[PhysicsDebugFrame] {
+        &self.frames
+    }
+
+    /// Clears the recorded frames, stopping any recording or replay.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Replays the frame at the given `index`, clamped to the recorded frames.
+    /// Does nothing if no frames have been recorded.
+    pub fn replay(&mut self, index: usize) {
+        if !self.frames.is_empty() {
+            self.replayed_frame = Some(index.min(self.frames.len() - 1));
+        }
+    }
+
+    /// Moves the replay forward or backward by the given number of frames, clamped to the recorded frames.
+    /// If no frame is being replayed, the replay starts from the first frame.
+    pub fn scrub(&mut self, frames: isize) {
+        let index = self
+            .replayed_frame
+            .map_or(0, |index| index.saturating_add_signed(frames));
+        self.replay(index);
+    }
+
+    /// Stops the replay and returns to live debug rendering.
+    pub fn stop_replay(&mut self) {
+        self.replayed_frame = None;
+    }
+
+    /// Returns `true` if a recorded frame is being replayed.
+    pub fn is_replaying(&self) -> bool {
+        self.replayed_frame.is_some()
+    }
+
+    /// Returns the index of the frame that is being replayed.
+    pub fn replayed_frame_index(&self) -> Option<usize> {
+        self.replayed_frame
+    }
+
+    /// Returns the frame that is being replayed.
+    pub fn replayed_frame(&self) -> Option<&PhysicsDebugFrame> {
+        self.replayed_frame.and_then(|index| self.frames.get(index))
+    }
+}
+
+/// The state of the simulation at the end of a physics step in a [`PhysicsDebugRecording`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PhysicsDebugFrame {
+    /// The elapsed time of [`Time<Physics>`](Physics) in seconds at the end of the step.
+    pub elapsed_seconds: f64,
+    /// The rigid bodies in the simulation.
+    pub bodies: Vec<RecordedBody>,
+    /// The colliders in the simulation.
+    pub colliders: Vec<RecordedCollider>,
+    /// The contact points between colliders that were touching during the step.
+    pub contacts: Vec<RecordedContact>,
+}
+
+/// The state of a rigid body in a [`PhysicsDebugFrame`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordedBody {
+    /// The entity of the body.
+    pub entity: Entity,
+    /// The [`Position`] of the body.
+    pub position: Vector,
+    /// The [`Rotation`] of the body.
+    pub rotation: Rotation,
+    /// The center of mass of the body in world space.
+    pub global_center_of_mass: Vector,
+    /// The [`LinearVelocity`] of the body.
+    pub linear_velocity: Vector,
+    /// The [`AngularVelocity`] of the body.
+    pub angular_velocity: AngularVelocity,
+}
+
+/// The state of a collider in a [`PhysicsDebugFrame`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordedCollider {
+    /// The entity of the collider.
+    pub entity: Entity,
+    /// The [`Position`] of the collider.
+    pub position: Vector,
+    /// The [`Rotation`] of the collider.
+    pub rotation: Rotation,
+}
+
+/// A contact point in a [`PhysicsDebugFrame`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecordedContact {
+    /// The first collider in the contact.
+    pub entity1: Entity,
+    /// The second collider in the contact.
+    pub entity2: Entity,
+    /// The contact point on the first collider in world space.
+    pub point1: Vector,
+    /// The contact point on the second collider in world space.
+    pub point2: Vector,
+    /// The contact normal of the first collider in world space.
+    pub normal: Vector,
+    /// The magnitude of the total contact force between the colliders, computed from the
+    /// [total normal impulse](Contacts::total_normal_impulse).
+    pub normal_force: Scalar,
+}
+
+/// Returns `true` if no [`PhysicsDebugRecording`] frame is being replayed.
+pub(super) fn is_not_replaying(recording: Option<Res<PhysicsDebugRecording>>) -> bool {
+    !recording.is_some_and(|recording| recording.is_replaying())
+}
+
+/// Records the state of the simulation into the [`PhysicsDebugRecording`] at the end of each physics step.
+#[allow(clippy::type_complexity)]
+pub(super) fn record_debug_frame(
+    recording: Option<ResMut<PhysicsDebugRecording>>,
+    bodies: Query<
+        (
+            Entity,
+            &Position,
+            &Rotation,
+            &CenterOfMass,
+            &LinearVelocity,
+            &AngularVelocity,
+        ),
+        With<RigidBody>,
+    >,
+    colliders: Query<(Entity, &Position, &Rotation), With<ColliderAabb>>,
+    collisions: Option<Res<Collisions>>,
+    time: Res<Time<Physics>>,
+    substep_time: Res<Time<Substeps>>,
+) {
+    let Some(mut recording) = recording.filter(|recording| recording.is_recording()) else {
+        return;
+    };
+
+    let bodies = bodies
+        .iter()
+        .map(|(entity, pos, rot, com, lin_vel, ang_vel)| RecordedBody {
+            entity,
+            position: pos.0,
+            rotation: *rot,
+            global_center_of_mass: pos.0 + rot.rotate(com.0),
+            linear_velocity: lin_vel.0,
+            angular_velocity: *ang_vel,
+        })
+        .collect();
+
+    let recorded_colliders = colliders
+        .iter()
+        .map(|(entity, pos, rot)| RecordedCollider {
+            entity,
+            position: pos.0,
+            rotation: *rot,
+        })
+        .collect();
+
+    let delta_secs = substep_time.delta_seconds_f64().adjust_precision();
+    let mut contacts = vec![];
+    for collision in collisions
+        .iter()
+        .flat_map(|collisions| collisions.iter())
+        .filter(|contacts| contacts.during_current_frame)
+    {
+        let (Ok((_, position1, rotation1)), Ok((_, position2, rotation2))) = (
+            colliders.get(collision.entity1),
+            colliders.get(collision.entity2),
+        ) else {
+            continue;
+        };
+        let normal_force = if delta_secs > 0.0 {
+            collision.total_normal_impulse / delta_secs
+        } else {
+            0.0
+        };
+        for manifold in collision.manifolds.iter() {
+            for contact in manifold.contacts.iter() {
+                contacts.push(RecordedContact {
+                    entity1: collision.entity1,
+                    entity2: collision.entity2,
+                    point1: contact.global_point1(position1, rotation1),
+                    point2: contact.global_point2(position2, rotation2),
+                    normal: contact.global_normal1(rotation1),
+                    normal_force,
+                });
+            }
+        }
+    }
+
+    recording.frames.push(PhysicsDebugFrame {
+        elapsed_seconds: time.elapsed_seconds_f64(),
+        bodies,
+        colliders: recorded_colliders,
+        contacts,
+    });
+    recording.remaining_steps -= 1;
+}
+
+/// Debug renders the [`PhysicsDebugFrame`] that is being replayed.
+#[allow(clippy::type_complexity)]
+pub(super) fn debug_render_recording(
+    recording: Option<Res<PhysicsDebugRecording>>,
+    entities: Query<(Option<&DebugRender>, Option<&CollisionLayers>)>,
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    shapes: Query<&Collider>,
+    mut gizmos: Gizmos<PhysicsGizmos>,
+    store: Res<GizmoConfigStore>,
+) {
+    let Some(frame) = recording
+        .as_ref()
+        .and_then(|recording| recording.replayed_frame())
+    else {
+        return;
+    };
+    let config = store.config::<PhysicsGizmos>().1;
+    let is_visible = |entity: Entity| {
+        let (render_config, layers) = entities.get(entity).unwrap_or_default();
+        passes_filters(config, render_config, layers)
+    };
+
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    if let Some(color) = config.collider_color {
+        for collider in frame.colliders.iter() {
+            if let (Ok(shape), true) = (shapes.get(collider.entity), is_visible(collider.entity)) {
+                gizmos.draw_collider(shape, collider.position, collider.rotation, color);
+            }
+        }
+    }
+
+    for body in frame.bodies.iter() {
+        if is_visible(body.entity) {
+            draw_velocities(
+                &mut gizmos,
+                body.global_center_of_mass,
+                &body.rotation,
+                body.linear_velocity,
+                &body.angular_velocity,
+                config.velocity_scale,
+                config.linear_velocity_color,
+                config.angular_velocity_color,
+            );
+        }
+    }
+
+    for contact in frame.contacts.iter() {
+        if !is_visible(contact.entity1) && !is_visible(contact.entity2) {
+            continue;
+        }
+
+        if let Some(color) = config.contact_point_color {
+            #[cfg(feature = "2d")]
+            {
+                gizmos.circle_2d(contact.point1.f32(), 3.0, color);
+                gizmos.circle_2d(contact.point2.f32(), 3.0, color);
+            }
+            #[cfg(feature = "3d")]
+            {
+                gizmos.sphere(contact.point1.f32(), default(), 0.025, color);
+                gizmos.sphere(contact.point2.f32(), default(), 0.025, color);
+            }
+        }
+
+        if let Some(color) = config.contact_normal_color {
+            let length = match config.contact_normal_scale {
+                ContactGizmoScale::Constant(length) => length,
+                ContactGizmoScale::Scaled(scale) => scale * contact.normal_force,
+            };
+            let (start, end) = (contact.point1, contact.point1 + contact.normal * length);
+            #[cfg(feature = "2d")]
+            gizmos.draw_arrow(start, end, 8.0, color);
+            #[cfg(feature = "3d")]
+            gizmos.draw_arrow(start, end, 0.1, color);
+        }
+    }
+}
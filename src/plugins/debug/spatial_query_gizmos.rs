@@ -0,0 +1,215 @@
+use super::{PhysicsGizmoExt, PhysicsGizmos};
+use crate::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// A [`SystemParam`] that performs one-shot [spatial queries](spatial_query) like [`SpatialQuery`]
+/// and debug renders the rays, swept shapes and hits using the colors of the [`PhysicsGizmos`] configuration.
+///
+/// This can be used to debug things like AI sensors and hitscan weapons that use [`SpatialQuery`]
+/// instead of [`RayCaster`] or [`ShapeCaster`] components. The queries return the same results
+/// as the corresponding methods of [`SpatialQuery`], which can also be accessed directly
+/// through [`spatial_query`](Self::spatial_query) for queries that should not be rendered.
+///
+/// Nothing is rendered if the [`PhysicsGizmos`] are disabled or the corresponding colors are `None`.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn fire_hitscan(mut spatial_query: DebugSpatialQuery) {
+///     // Cast ray, draw it along with the hit, and print the first hit
+///     if let Some(first_hit) = spatial_query.cast_ray(
+///         Vec3::ZERO,                    // Origin
+///         Direction3d::X,                // Direction
+///         100.0,                         // Maximum time of impact (travel distance)
+///         true,                          // Does the ray treat colliders as "solid"
+///         SpatialQueryFilter::default(), // Query filter
+///     ) {
+///         println!("First hit: {:?}", first_hit);
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct DebugSpatialQuery<'w, 's> {
+    /// The [`SpatialQuery`] used for performing the queries.
+    pub spatial_query: SpatialQuery<'w, 's>,
+    gizmos: Gizmos<'w, 's, PhysicsGizmos>,
+    store: Res<'w, GizmoConfigStore>,
+}
+
+impl<'w, 's> DebugSpatialQuery<'w, 's> {
+    /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider
+    /// like [`SpatialQuery::cast_ray`], and debug renders the ray and the hit.
+    pub fn cast_ray(
+        &mut self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<RayHitData> {
+        let hit =
+            self.spatial_query
+                .cast_ray(origin, direction, max_time_of_impact, solid, query_filter);
+        self.draw_raycast(origin, direction, max_time_of_impact, hit.as_slice());
+        hit
+    }
+
+    /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData) until `max_hits` is reached
+    /// like [`SpatialQuery::ray_hits`], and debug renders the ray and the hits.
+    pub fn ray_hits(
+        &mut self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        max_hits: u32,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<RayHitData> {
+        let hits = self.spatial_query.ray_hits(
+            origin,
+            direction,
+            max_time_of_impact,
+            max_hits,
+            solid,
+            query_filter,
+        );
+        self.draw_raycast(origin, direction, max_time_of_impact, &hits);
+        hits
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest
+    /// [hit](ShapeHitData) with a collider like [`SpatialQuery::cast_shape`], and debug renders
+    /// the swept shape and the hit.
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape(
+        &mut self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        let hit = self.spatial_query.cast_shape(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        );
+        self.draw_shapecast(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            hit.as_slice(),
+        );
+        hit
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes all [hits](ShapeHitData)
+    /// until `max_hits` is reached like [`SpatialQuery::shape_hits`], and debug renders the swept shape
+    /// and the hits.
+    #[allow(clippy::too_many_arguments)]
+    pub fn shape_hits(
+        &mut self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        max_hits: u32,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<ShapeHitData> {
+        let hits = self.spatial_query.shape_hits(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            max_hits,
+            ignore_origin_penetration,
+            query_filter,
+        );
+        self.draw_shapecast(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            &hits,
+        );
+        hits
+    }
+
+    fn draw_raycast(
+        &mut self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        hits: &[RayHitData],
+    ) {
+        let config = self.store.config::<PhysicsGizmos>().1;
+        self.gizmos.draw_raycast(
+            origin,
+            direction,
+            // f32::MAX renders nothing, but this number seems to be fine :P
+            max_time_of_impact.min(1_000_000_000_000_000_000.0),
+            hits,
+            config
+                .raycast_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+            config
+                .raycast_point_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+            config
+                .raycast_normal_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+        );
+    }
+
+    fn draw_shapecast(
+        &mut self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        hits: &[ShapeHitData],
+    ) {
+        let config = self.store.config::<PhysicsGizmos>().1;
+        self.gizmos.draw_shapecast(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            // f32::MAX renders nothing, but this number seems to be fine :P
+            max_time_of_impact.min(1_000_000_000_000_000.0),
+            hits,
+            config
+                .shapecast_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+            config
+                .shapecast_shape_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+            config
+                .shapecast_point_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+            config
+                .shapecast_normal_color
+                .unwrap_or(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Writes the particles of deformable bodies like ropes, cloth and soft bodies to render meshes.
+//!
+//! See [`DeformableMeshPlugin`] and [`DeformableMesh`].
+
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+#[cfg(feature = "2d")]
+use bevy::sprite::Mesh2dHandle;
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+    utils::intern::Interned,
+};
+
+/// The handle of the render mesh of a deformable body.
+#[cfg(feature = "2d")]
+pub(crate) type DeformableMeshHandle = Mesh2dHandle;
+/// The handle of the render mesh of a deformable body.
+#[cfg(feature = "3d")]
+pub(crate) type DeformableMeshHandle = Handle<Mesh>;
+
+/// Writes the particles of [deformable bodies](DeformableMesh) of type `T` to the `Mesh` of the entity
+/// in [`PhysicsSet::Sync`], so that the simulation results can be rendered directly.
+///
+/// The vertex positions are written relative to the `GlobalTransform` of the entity, and smooth normals
+/// are recomputed from the triangles of the mesh. When a [fixed timestep](TimestepMode::Fixed) is used,
+/// the vertices are interpolated between the previous and current physics step based on the accumulated
+/// overstep, which keeps the motion smooth when the frame rate differs from the physics rate.
+/// The behavior can be configured with the [`DeformableMeshConfig`] resource.
+///
+/// The plugin is added for [ropes](Rope), [cloth](Cloth), [soft bodies](SoftBody) and [shape matching bodies](ShapeMatchingBody)
+/// by the [`RopePlugin`], [`ClothPlugin`], [`SoftBodyPlugin`] and [`ShapeMatchingPlugin`] when the `deformable-mesh`
+/// feature is enabled. It can be added
+/// for custom types that implement [`DeformableMesh`].
+pub struct DeformableMeshPlugin<T: DeformableMesh> {
+    schedule: Interned<dyn ScheduleLabel>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: DeformableMesh> DeformableMeshPlugin<T> {
+    /// Creates a [`DeformableMeshPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: DeformableMesh> Default for DeformableMeshPlugin<T> {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl<T: DeformableMesh> Plugin for DeformableMeshPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DeformableMeshConfig>()
+            .register_type::<DeformableMeshConfig>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(store_previous_vertex_positions::<T>.before(PhysicsStepSet::BroadPhase));
+
+        app.add_systems(
+            self.schedule,
+            update_deformable_meshes::<T>.in_set(PhysicsSet::Sync),
+        );
+    }
+}
+
+/// A deformable body whose particles can be written to a render `Mesh` by the [`DeformableMeshPlugin`].
+///
+/// The mesh is taken from the `Handle<Mesh>` of the entity in 3D and the `Mesh2dHandle` in 2D.
+pub trait DeformableMesh: Component {
+    /// Writes the world-space positions of the vertices of the render mesh into `positions`,
+    /// in the same order as the vertices of the mesh.
+    fn vertex_positions(&self, positions: &mut Vec<Vector>);
+
+    /// Returns the triangles of the render mesh, given by the indices of their vertices,
+    /// if the topology of the body can change at runtime.
+    ///
+    /// The indices of the mesh are rewritten whenever the number of triangles differs from the mesh,
+    /// for example after [cloth](Cloth) has been torn.
+    fn triangles(&self) -> Option<&[[usize; 3]]> {
+        None
+    }
+}
+
+impl DeformableMesh for Rope {
+    fn vertex_positions(&self, positions: &mut Vec<Vector>) {
+        positions.extend(self.positions());
+    }
+}
+
+impl DeformableMesh for Cloth {
+    fn vertex_positions(&self, positions: &mut Vec<Vector>) {
+        positions.extend(self.positions());
+    }
+
+    fn triangles(&self) -> Option<&[[usize; 3]]> {
+        Some(&self.triangles)
+    }
+}
+
+impl DeformableMesh for SoftBody {
+    fn vertex_positions(&self, positions: &mut Vec<Vector>) {
+        positions.extend(
+            self.surface_vertices
+                .iter()
+                .map(|&i| self.particles[i].position),
+        );
+    }
+}
+
+impl DeformableMesh for ShapeMatchingBody {
+    fn vertex_positions(&self, positions: &mut Vec<Vector>) {
+        if self.surface_vertices.is_empty() {
+            positions.extend(self.positions());
+        } else {
+            positions.extend(
+                self.surface_vertices
+                    .iter()
+                    .map(|&i| self.particles[i].position),
+            );
+        }
+    }
+}
+
+/// Configures how the [`DeformableMeshPlugin`] writes deformable bodies to meshes.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource)]
+pub struct DeformableMeshConfig {
+    /// If `true`, the vertices are interpolated between the previous and current physics step
+    /// when a [fixed timestep](TimestepMode::Fixed) is used. Otherwise, the current positions are written directly.
+    ///
+    /// Interpolation makes the rendered mesh lag behind the simulation by up to one physics step.
+    pub interpolate: bool,
+    /// If `true`, smooth normals are recomputed from the triangles of the mesh whenever the vertices are written.
+    /// In 2D, the normals always point along the Z axis.
+    pub recompute_normals: bool,
+}
+
+impl Default for DeformableMeshConfig {
+    fn default() -> Self {
+        Self {
+            interpolate: true,
+            recompute_normals: true,
+        }
+    }
+}
+
+/// The vertex positions of a deformable body of type `T` at the start of the latest physics step.
+#[derive(Component)]
+struct PreviousVertexPositions<T: DeformableMesh> {
+    positions: Vec<Vector>,
+    _phantom: PhantomData<T>,
+}
+
+/// Stores the vertex positions of deformable bodies at the start of each physics step for interpolation.
+fn store_previous_vertex_positions<T: DeformableMesh>(
+    mut commands: Commands,
+    mut bodies: Query<(Entity, &T, Option<&mut PreviousVertexPositions<T>>)>,
+    config: Res<DeformableMeshConfig>,
+) {
+    if !config.interpolate {
+        return;
+    }
+
+    for (entity, body, previous) in &mut bodies {
+        match previous {
+            Some(mut previous) => {
+                previous.positions.clear();
+                body.vertex_positions(&mut previous.positions);
+            }
+            None => {
+                let mut positions = vec![];
+                body.vertex_positions(&mut positions);
+                commands
+                    .entity(entity)
+                    .insert(PreviousVertexPositions::<T> {
+                        positions,
+                        _phantom: PhantomData,
+                    });
+            }
+        }
+    }
+}
+
+/// Writes the vertex positions and normals of deformable bodies to their meshes.
+#[allow(clippy::type_complexity)]
+fn update_deformable_meshes<T: DeformableMesh>(
+    bodies: Query<(
+        Ref<T>,
+        &DeformableMeshHandle,
+        Option<&GlobalTransform>,
+        Option<&PreviousVertexPositions<T>>,
+    )>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    config: Res<DeformableMeshConfig>,
+    time: Res<Time<Physics>>,
+    mut positions: Local<Vec<Vector>>,
+) {
+    let Some(mut meshes) = meshes else {
+        return;
+    };
+
+    // The fraction of a fixed timestep that has been accumulated but not simulated yet.
+    let alpha = match time.timestep_mode() {
+        TimestepMode::Fixed {
+            delta, overstep, ..
+        } if config.interpolate && !delta.is_zero() => {
+            (overstep.as_secs_f64() / delta.as_secs_f64()).clamp(0.0, 1.0) as Scalar
+        }
+        _ => 1.0,
+    };
+
+    for (body, handle, global_transform, previous) in &bodies {
+        let previous = previous.filter(|_| alpha < 1.0);
+
+        // Without interpolation, the mesh only needs to be updated when the body has changed.
+        if previous.is_none() && !body.is_changed() {
+            continue;
+        }
+
+        #[cfg(feature = "2d")]
+        let handle = &handle.0;
+
+        let Some(mesh) = meshes.get_mut(handle) else {
+            continue;
+        };
+
+        if let Some(triangles) = body.triangles() {
+            if mesh.indices().map_or(0, |indices| indices.len()) != 3 * triangles.len() {
+                mesh.insert_indices(Indices::U32(
+                    triangles.iter().flatten().map(|&i| i as u32).collect(),
+                ));
+            }
+        }
+
+        positions.clear();
+        body.vertex_positions(&mut positions);
+
+        if let Some(previous) = previous {
+            if previous.positions.len() == positions.len() {
+                for (position, previous) in positions.iter_mut().zip(previous.positions.iter()) {
+                    *position = previous.lerp(*position, alpha);
+                }
+            }
+        }
+
+        write_deformable_mesh(
+            mesh,
+            &positions,
+            global_transform.unwrap_or(&GlobalTransform::IDENTITY),
+            config.recompute_normals,
+        );
+    }
+}
+
+/// Writes the world-space vertex `positions` to the `mesh` relative to the `global_transform`,
+/// and recomputes smooth normals from the triangles of the mesh if `recompute_normals` is `true`.
+///
+/// The mesh is left unchanged if it already has a different number of vertices, unless it is a line strip
+/// like the meshes of [ropes](Rope), which are resized when a rope is torn.
+pub(crate) fn write_deformable_mesh(
+    mesh: &mut Mesh,
+    positions: &[Vector],
+    global_transform: &GlobalTransform,
+    recompute_normals: bool,
+) {
+    if mesh.primitive_topology() != PrimitiveTopology::LineStrip
+        && mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .is_some_and(|vertices| vertices.len() != positions.len())
+    {
+        return;
+    }
+
+    let inverse = global_transform.affine().inverse();
+    let positions: Vec<Vec3> = positions
+        .iter()
+        .map(|position| {
+            #[cfg(feature = "2d")]
+            let position = position.f32().extend(0.0);
+            #[cfg(feature = "3d")]
+            let position = position.f32();
+            inverse.transform_point3(position)
+        })
+        .collect();
+
+    if recompute_normals && mesh.primitive_topology() == PrimitiveTopology::TriangleList {
+        let mut normals = vec![Vec3::ZERO; positions.len()];
+        #[cfg(feature = "2d")]
+        normals.fill(Vec3::Z);
+        #[cfg(feature = "3d")]
+        {
+            let indices: Vec<usize> = match mesh.indices() {
+                Some(indices) => indices.iter().collect(),
+                None => (0..positions.len()).collect(),
+            };
+            for triangle in indices.chunks_exact(3) {
+                let [i1, i2, i3] = [triangle[0], triangle[1], triangle[2]];
+                let normal = (positions[i2] - positions[i1]).cross(positions[i3] - positions[i1]);
+                for i in [i1, i2, i3] {
+                    normals[i] += normal;
+                }
+            }
+        }
+
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            VertexAttributeValues::Float32x3(
+                normals
+                    .into_iter()
+                    .map(|normal| normal.normalize_or_zero().to_array())
+                    .collect(),
+            ),
+        );
+    }
+
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        VertexAttributeValues::Float32x3(
+            positions
+                .into_iter()
+                .map(|position| position.to_array())
+                .collect(),
+        ),
+    );
+}
@@ -0,0 +1,257 @@
+//! Records per-stage timings and counters of the physics simulation into Bevy's `DiagnosticsStore`.
+//!
+//! See [`PhysicsDiagnosticsPlugin`].
+
+use std::time::Duration;
+
+use crate::{plugins::sync::SyncSet, prelude::*};
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::{intern::Interned, Instant},
+};
+
+/// Records the time spent in each stage of the physics simulation along with counters
+/// of bodies, collision pairs, contacts and islands into Bevy's [`DiagnosticsStore`](bevy::diagnostic::DiagnosticsStore).
+///
+/// The diagnostics can be read from the store using the paths defined as associated constants
+/// of this plugin, or displayed with any tool that reads standard Bevy diagnostics,
+/// like the `LogDiagnosticsPlugin`.
+///
+/// Timings are measured in milliseconds and summed over all physics steps run during a frame:
+///
+/// - [`STEP`](Self::STEP): The whole [`PhysicsSet::StepSimulation`] set.
+/// - [`BROAD_PHASE`](Self::BROAD_PHASE): [`PhysicsStepSet::BroadPhase`].
+/// - [`NARROW_PHASE`](Self::NARROW_PHASE): [`SubstepSet::NarrowPhase`], summed over all substeps.
+/// - [`SOLVER`](Self::SOLVER): The rest of [`PhysicsStepSet::Substeps`], i.e. integration and constraint solving.
+/// - [`SYNC`](Self::SYNC): [`PhysicsSet::Sync`].
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added separately.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::{diagnostic::LogDiagnosticsPlugin, prelude::*};
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsDiagnosticsPlugin::default(),
+///             LogDiagnosticsPlugin::default(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct PhysicsDiagnosticsPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsDiagnosticsPlugin {
+    /// Time spent stepping the simulation in milliseconds.
+    pub const STEP: DiagnosticPath = DiagnosticPath::const_new("physics/step");
+    /// Time spent in the broad phase in milliseconds.
+    pub const BROAD_PHASE: DiagnosticPath = DiagnosticPath::const_new("physics/broad_phase");
+    /// Time spent in the narrow phase in milliseconds.
+    pub const NARROW_PHASE: DiagnosticPath = DiagnosticPath::const_new("physics/narrow_phase");
+    /// Time spent integrating and solving constraints in the substepping loop in milliseconds.
+    pub const SOLVER: DiagnosticPath = DiagnosticPath::const_new("physics/solver");
+    /// Time spent synchronizing physics components and transforms in milliseconds.
+    pub const SYNC: DiagnosticPath = DiagnosticPath::const_new("physics/sync");
+    /// The number of [rigid bodies](RigidBody).
+    pub const BODIES: DiagnosticPath = DiagnosticPath::const_new("physics/bodies");
+    /// The number of dynamic and kinematic [rigid bodies](RigidBody) that are not [`Sleeping`].
+    pub const ACTIVE_BODIES: DiagnosticPath = DiagnosticPath::const_new("physics/active_bodies");
+    /// The number of [`BroadCollisionPairs`].
+    pub const PAIRS: DiagnosticPath = DiagnosticPath::const_new("physics/pairs");
+    /// The number of contact points in [`Collisions`] that are touching during the current frame.
+    pub const CONTACTS: DiagnosticPath = DiagnosticPath::const_new("physics/contacts");
+    /// The number of [`PhysicsIslands`].
+    pub const ISLANDS: DiagnosticPath = DiagnosticPath::const_new("physics/islands");
+
+    /// Creates a [`PhysicsDiagnosticsPlugin`] using the given schedule for recording the diagnostics.
+    /// This should be the same schedule that [`PhysicsPlugins`] run in.
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsDiagnosticsPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for PhysicsDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsStageTimers>();
+
+        for path in [
+            Self::STEP,
+            Self::BROAD_PHASE,
+            Self::NARROW_PHASE,
+            Self::SOLVER,
+            Self::SYNC,
+        ] {
+            app.register_diagnostic(Diagnostic::new(path).with_suffix("ms"));
+        }
+        for path in [
+            Self::BODIES,
+            Self::ACTIVE_BODIES,
+            Self::PAIRS,
+            Self::CONTACTS,
+            Self::ISLANDS,
+        ] {
+            app.register_diagnostic(Diagnostic::new(path));
+        }
+
+        app.add_systems(
+            self.schedule,
+            (
+                start_timer(PhysicsStage::Step)
+                    .after(PhysicsSet::Prepare)
+                    .before(PhysicsSet::StepSimulation),
+                stop_timer(PhysicsStage::Step)
+                    .after(PhysicsSet::StepSimulation)
+                    .before(PhysicsSet::Sync),
+                start_timer(PhysicsStage::Sync).in_set(SyncSet::First),
+                stop_timer(PhysicsStage::Sync).in_set(SyncSet::Last),
+                update_diagnostics.after(PhysicsSet::Sync),
+            ),
+        );
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems((
+                start_timer(PhysicsStage::BroadPhase).before(PhysicsStepSet::BroadPhase),
+                (
+                    stop_timer(PhysicsStage::BroadPhase),
+                    start_timer(PhysicsStage::Substeps),
+                )
+                    .chain()
+                    .after(PhysicsStepSet::BroadPhase)
+                    .before(PhysicsStepSet::Substeps),
+                stop_timer(PhysicsStage::Substeps)
+                    .after(PhysicsStepSet::Substeps)
+                    .before(PhysicsStepSet::ReportContacts),
+            ));
+
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems((
+                start_timer(PhysicsStage::NarrowPhase)
+                    .after(SubstepSet::Integrate)
+                    .before(SubstepSet::NarrowPhase),
+                stop_timer(PhysicsStage::NarrowPhase)
+                    .after(SubstepSet::NarrowPhase)
+                    .before(SubstepSet::PostProcessCollisions),
+            ));
+    }
+}
+
+/// A stage of the simulation that is timed by the [`PhysicsDiagnosticsPlugin`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PhysicsStage {
+    Step,
+    BroadPhase,
+    Substeps,
+    NarrowPhase,
+    Sync,
+}
+
+impl PhysicsStage {
+    const COUNT: usize = 5;
+}
+
+/// Accumulates the time spent in each [`PhysicsStage`] until the diagnostics are recorded.
+#[derive(Resource, Default)]
+struct PhysicsStageTimers {
+    started: [Option<Instant>; PhysicsStage::COUNT],
+    elapsed: [Duration; PhysicsStage::COUNT],
+}
+
+impl PhysicsStageTimers {
+    /// Returns the accumulated time spent in the given stage in milliseconds.
+    fn millis(&self, stage: PhysicsStage) -> f64 {
+        self.elapsed[stage as usize].as_secs_f64() * 1000.0
+    }
+}
+
+fn start_timer(stage: PhysicsStage) -> impl FnMut(ResMut<PhysicsStageTimers>) {
+    move |mut timers: ResMut<PhysicsStageTimers>| {
+        timers.started[stage as usize] = Some(Instant::now());
+    }
+}
+
+fn stop_timer(stage: PhysicsStage) -> impl FnMut(ResMut<PhysicsStageTimers>) {
+    move |mut timers: ResMut<PhysicsStageTimers>| {
+        if let Some(start) = timers.started[stage as usize].take() {
+            timers.elapsed[stage as usize] += start.elapsed();
+        }
+    }
+}
+
+/// Records the accumulated timings and the current counters as diagnostic measurements
+/// and resets the timers for the next frame.
+fn update_diagnostics(
+    mut diagnostics: Diagnostics,
+    mut timers: ResMut<PhysicsStageTimers>,
+    bodies: Query<(&RigidBody, Has<Sleeping>)>,
+    broad_collision_pairs: Option<Res<BroadCollisionPairs>>,
+    collisions: Option<Res<Collisions>>,
+    islands: Option<Res<PhysicsIslands>>,
+) {
+    let substeps = timers.millis(PhysicsStage::Substeps);
+    let narrow_phase = timers.millis(PhysicsStage::NarrowPhase);
+
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::STEP, || {
+        timers.millis(PhysicsStage::Step)
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::BROAD_PHASE, || {
+        timers.millis(PhysicsStage::BroadPhase)
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::NARROW_PHASE, || narrow_phase);
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::SOLVER, || {
+        (substeps - narrow_phase).max(0.0)
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::SYNC, || {
+        timers.millis(PhysicsStage::Sync)
+    });
+
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::BODIES, || {
+        bodies.iter().count() as f64
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::ACTIVE_BODIES, || {
+        bodies
+            .iter()
+            .filter(|(rb, is_sleeping)| !rb.is_static() && !is_sleeping)
+            .count() as f64
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::PAIRS, || {
+        broad_collision_pairs.map_or(0, |pairs| pairs.0.len()) as f64
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::CONTACTS, || {
+        collisions.map_or(0, |collisions| {
+            collisions
+                .iter()
+                .filter(|contacts| contacts.during_current_frame)
+                .flat_map(|contacts| contacts.manifolds.iter())
+                .map(|manifold| manifold.contacts.len())
+                .sum()
+        }) as f64
+    });
+    diagnostics.add_measurement(&PhysicsDiagnosticsPlugin::ISLANDS, || {
+        islands.map_or(0, |islands| islands.len()) as f64
+    });
+
+    timers.elapsed = default();
+}
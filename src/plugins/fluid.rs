@@ -0,0 +1,391 @@
+//! Simulates 2D liquids using position-based fluids.
+//!
+//! See [`FluidPlugin`] and [`Fluid`].
+
+use crate::{
+    plugins::particles::{
+        collide_particles, find_particle_neighbors, integrate_particles,
+        update_particle_velocities, ParticleContacts,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Simulates 2D [fluids](Fluid) using position-based fluids (PBF), where the particles of the fluid
+/// are moved to keep the density around each particle at the rest density.
+///
+/// The fluids are simulated in the [`SubstepSchedule`]:
+///
+/// 1. The particles are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The neighbors of each particle are found using a spatial hash grid, and the density constraints are solved
+/// after the other particle systems in [`ParticleSet::Fluids`].
+/// 3. The particles are pushed out of [colliders](Collider). Dynamic [rigid bodies](RigidBody) are pushed back
+/// by the particles, so bodies can float, sink and splash.
+/// 4. The velocities of the particles are updated from their change in position, and viscosity is applied
+/// in [`SubstepSet::UpdateVelocities`].
+///
+/// Requires the `fluid` feature.
+pub struct FluidPlugin;
+
+impl Plugin for FluidPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Fluid>().register_type::<Particle>();
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_fluids.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(
+            (solve_fluid_density, couple_fluids)
+                .chain()
+                .in_set(ParticleSet::Fluids),
+        );
+
+        substeps.add_systems(update_fluid_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// A 2D liquid simulated as [particles](Particle) by the [`FluidPlugin`].
+///
+/// The particles are kept roughly [`spacing`](Self::spacing) apart by density constraints, which makes
+/// the fluid nearly incompressible. The fluid collides with [colliders](Collider) whose [layers](CollisionLayers)
+/// interact with the [`layers`](Self::layers) of the fluid, and it pushes dynamic [rigid bodies](RigidBody) around,
+/// so bodies lighter than the fluid float and heavier bodies sink. Surface tension is not simulated.
+///
+/// The particles are simulated in world space, so the `Transform` of the fluid entity has no effect.
+///
+/// Requires the `fluid` feature.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // A container
+///     commands.spawn((RigidBody::Static, Collider::rectangle(4.0, 0.2)));
+///     for x in [-2.0, 2.0] {
+///         commands.spawn((
+///             RigidBody::Static,
+///             Collider::rectangle(0.2, 4.0),
+///             TransformBundle::from_transform(Transform::from_xyz(x, 2.0, 0.0)),
+///         ));
+///     }
+///
+///     // A block of water with particles 0.1 units apart
+///     commands.spawn(Fluid::rectangle(Vector::Y, Vector::new(1.5, 0.8), 0.1, 1000.0));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Fluid {
+    /// The particles of the fluid.
+    pub particles: Vec<Particle>,
+    /// The distance between particles at rest. Half of the spacing is used as the radius of the particles for collisions.
+    pub spacing: Scalar,
+    /// The radius within which particles affect each other. The default is twice the [`spacing`](Self::spacing).
+    pub smoothing_radius: Scalar,
+    /// The mass of the fluid per unit of area, used for computing the masses of the particles.
+    pub density: Scalar,
+    /// How much of the density error is corrected in each substep, between `0.0` and `1.0`.
+    /// Lower values make the fluid more compressible but more stable.
+    pub relaxation: Scalar,
+    /// The strength of the viscosity, between `0.0` and `1.0`. Higher values make the fluid thicker.
+    pub viscosity: Scalar,
+    /// The friction of the particles against colliders, between `0.0` and `1.0`.
+    pub friction: Scalar,
+    /// The linear damping of the particles.
+    pub damping: Scalar,
+    /// The layers of the fluid. The particles only collide with colliders whose layers [interact](CollisionLayers::interacts_with)
+    /// with these layers.
+    pub layers: CollisionLayers,
+    /// The neighbors of each particle found in the last substep.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Fluid {
+    /// Creates an empty fluid with the given `spacing` between particles and `density` in mass per unit of area.
+    pub fn new(spacing: Scalar, density: Scalar) -> Self {
+        Self {
+            particles: vec![],
+            spacing,
+            smoothing_radius: 2.0 * spacing,
+            density,
+            relaxation: 0.5,
+            viscosity: 0.05,
+            friction: 0.1,
+            damping: 0.0,
+            layers: CollisionLayers::default(),
+            neighbors: vec![],
+        }
+    }
+
+    /// Creates a rectangular block of fluid with the given `center` and `half_extents`,
+    /// `spacing` between particles and `density` in mass per unit of area.
+    pub fn rectangle(
+        center: Vector,
+        half_extents: Vector,
+        spacing: Scalar,
+        density: Scalar,
+    ) -> Self {
+        let mut fluid = Self::new(spacing, density);
+        let counts = (2.0 * half_extents / spacing).floor().max(Vector::ONE);
+        let min = center - (counts - Vector::ONE) * spacing * 0.5;
+
+        for y in 0..counts.y as usize {
+            for x in 0..counts.x as usize {
+                let position = min + Vector::new(x as Scalar, y as Scalar) * spacing;
+                fluid.add_particle(position, Vector::ZERO);
+            }
+        }
+
+        fluid
+    }
+
+    /// Adds a particle with the given `position` and `velocity` to the fluid.
+    pub fn add_particle(&mut self, position: Vector, velocity: Vector) {
+        self.particles
+            .push(Particle::new(position, self.particle_mass()).with_velocity(velocity));
+    }
+
+    /// Sets the radius within which particles affect each other.
+    pub fn with_smoothing_radius(mut self, radius: Scalar) -> Self {
+        self.smoothing_radius = radius;
+        self
+    }
+
+    /// Sets how much of the density error is corrected in each substep.
+    pub fn with_relaxation(mut self, relaxation: Scalar) -> Self {
+        self.relaxation = relaxation;
+        self
+    }
+
+    /// Sets the strength of the viscosity.
+    pub fn with_viscosity(mut self, viscosity: Scalar) -> Self {
+        self.viscosity = viscosity;
+        self
+    }
+
+    /// Sets the friction of the particles against colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the layers of the fluid used for collisions.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Returns the mass of each particle based on the [`spacing`](Self::spacing) and [`density`](Self::density).
+    pub fn particle_mass(&self) -> Scalar {
+        self.density * self.spacing * self.spacing
+    }
+
+    /// Returns the radius of the particles used for collisions.
+    pub fn particle_radius(&self) -> Scalar {
+        0.5 * self.spacing
+    }
+
+    /// Computes the density that the fluid is kept at, which is the density computed with the smoothing kernel
+    /// for a particle surrounded by particles in a grid with the given [`spacing`](Self::spacing).
+    pub fn rest_density(&self) -> Scalar {
+        let h = self.smoothing_radius;
+        let cells = (h / self.spacing).ceil() as i32;
+        let mut density = 0.0;
+        for y in -cells..=cells {
+            for x in -cells..=cells {
+                let offset = Vector::new(x as Scalar, y as Scalar) * self.spacing;
+                density += poly6(offset.length_squared(), h);
+            }
+        }
+        density * self.particle_mass()
+    }
+
+    /// Computes the density around the particle at the given index using the neighbors found in the last substep.
+    pub fn density_at(&self, index: usize) -> Scalar {
+        let h = self.smoothing_radius;
+        let position = self.particles[index].position;
+        let neighbors = self.neighbors.get(index).map_or(&[][..], |n| n.as_slice());
+        let density = poly6(0.0, h)
+            + neighbors
+                .iter()
+                .map(|&j| poly6(position.distance_squared(self.particles[j].position), h))
+                .sum::<Scalar>();
+        density * self.particle_mass()
+    }
+
+    /// Finds the neighbors of each particle within the smoothing radius using a spatial hash grid.
+    fn find_neighbors(&mut self) {
+        find_particle_neighbors(&self.particles, self.smoothing_radius, &mut self.neighbors);
+    }
+}
+
+/// The poly6 smoothing kernel in 2D for the squared distance `r2`.
+fn poly6(r2: Scalar, h: Scalar) -> Scalar {
+    let h2 = h * h;
+    if r2 >= h2 {
+        return 0.0;
+    }
+    4.0 / (PI * h2.powi(4)) * (h2 - r2).powi(3)
+}
+
+/// The gradient of the spiky smoothing kernel in 2D for the offset `r` between two particles.
+fn spiky_gradient(r: Vector, h: Scalar) -> Vector {
+    let distance = r.length();
+    if distance >= h || distance <= Scalar::EPSILON {
+        return Vector::ZERO;
+    }
+    -30.0 / (PI * h.powi(5)) * (h - distance).powi(2) * r / distance
+}
+
+/// Moves the particles of fluids by their velocities and gravity.
+fn integrate_fluids(mut fluids: Query<&mut Fluid>, gravity: Res<Gravity>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut fluid in &mut fluids {
+        let damping = fluid.damping;
+        integrate_particles(&mut fluid.particles, gravity.0, damping, delta_secs);
+    }
+}
+
+/// Moves the particles of fluids to keep the density around them at the rest density.
+///
+/// The constraints of all particles are solved in parallel (Jacobi iteration), and the density is only
+/// corrected when it is above the rest density, which keeps the particles from clumping together.
+fn solve_fluid_density(mut fluids: Query<&mut Fluid>, mut lambdas: Local<Vec<Scalar>>) {
+    for mut fluid in &mut fluids {
+        fluid.find_neighbors();
+
+        let h = fluid.smoothing_radius;
+        let rest_density = fluid.rest_density();
+        let mass = fluid.particle_mass();
+
+        if rest_density <= Scalar::EPSILON || mass <= Scalar::EPSILON {
+            continue;
+        }
+
+        // Compute the Lagrange multiplier of the density constraint of each particle
+        lambdas.clear();
+        lambdas.extend((0..fluid.particles.len()).map(|i| {
+            let c = fluid.density_at(i) / rest_density - 1.0;
+            if c <= 0.0 {
+                return 0.0;
+            }
+
+            let position = fluid.particles[i].position;
+            let mut gradient_i = Vector::ZERO;
+            let mut sum_squared = 0.0;
+            for &j in fluid.neighbors[i].iter() {
+                let gradient = spiky_gradient(position - fluid.particles[j].position, h);
+                gradient_i += gradient;
+                sum_squared += gradient.length_squared();
+            }
+
+            // With equal masses, the inverse masses and the mass in the gradients reduce to this scale
+            let denominator =
+                mass / (rest_density * rest_density) * (gradient_i.length_squared() + sum_squared);
+            if denominator <= Scalar::EPSILON {
+                0.0
+            } else {
+                -fluid.relaxation * c / denominator
+            }
+        }));
+
+        // Apply the position corrections
+        let corrections: Vec<Vector> = (0..fluid.particles.len())
+            .map(|i| {
+                let position = fluid.particles[i].position;
+                fluid.neighbors[i]
+                    .iter()
+                    .map(|&j| {
+                        (lambdas[i] + lambdas[j])
+                            * spiky_gradient(position - fluid.particles[j].position, h)
+                    })
+                    .sum::<Vector>()
+                    / rest_density
+            })
+            .collect();
+
+        for (particle, correction) in fluid.particles.iter_mut().zip(corrections) {
+            if particle.inverse_mass > 0.0 {
+                particle.position += correction;
+            }
+        }
+    }
+}
+
+/// Pushes the particles of fluids out of colliders, and pushes dynamic bodies back.
+pub(crate) fn couple_fluids(mut fluids: Query<&mut Fluid>, mut contacts: ParticleContacts) {
+    for mut fluid in &mut fluids {
+        let fluid = &mut *fluid;
+        if fluid.particles.is_empty() {
+            continue;
+        }
+
+        collide_particles(
+            &mut fluid.particles,
+            fluid.particle_radius(),
+            fluid.friction,
+            fluid.layers,
+            &[],
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of the particles of fluids based on their change in position and applies viscosity.
+fn update_fluid_velocities(mut fluids: Query<&mut Fluid>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut fluid in &mut fluids {
+        update_particle_velocities(&mut fluid.particles, delta_secs);
+
+        let viscosity = fluid.viscosity.clamp(0.0, 1.0);
+        if viscosity <= 0.0 || fluid.neighbors.len() != fluid.particles.len() {
+            continue;
+        }
+
+        // XSPH viscosity blends the velocity of each particle with the velocities of its neighbors
+        let h = fluid.smoothing_radius;
+        let max_weight = poly6(0.0, h);
+        let velocities: Vec<Vector> = (0..fluid.particles.len())
+            .map(|i| {
+                let particle = fluid.particles[i];
+                let mut delta = Vector::ZERO;
+                let mut total_weight = 0.0;
+                for &j in fluid.neighbors[i].iter() {
+                    let other = fluid.particles[j];
+                    let weight =
+                        poly6(particle.position.distance_squared(other.position), h) / max_weight;
+                    delta += (other.velocity - particle.velocity) * weight;
+                    total_weight += weight;
+                }
+                if total_weight > 0.0 {
+                    particle.velocity + viscosity * delta / total_weight.max(1.0)
+                } else {
+                    particle.velocity
+                }
+            })
+            .collect();
+
+        for (particle, velocity) in fluid.particles.iter_mut().zip(velocities) {
+            particle.velocity = velocity;
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Applies user-defined force fields to bodies in every substep.
+//!
+//! See [`ForceFieldPlugin`] and [`ForceField`].
+
+use crate::{plugins::integrator::bodies_in_volumes, prelude::*};
+use bevy::{prelude::*, utils::HashMap};
+use std::{fmt, sync::Arc};
+
+/// Applies the forces of [`ForceField`]s to dynamic [rigid bodies](RigidBody).
+///
+/// The fields are evaluated at the start of every substep in [`SubstepSet::Integrate`], using the current
+/// position and velocity of each body, so fields like vortexes, magnets and tractor beams behave consistently
+/// at any [substep count](SubstepCount). Applying similar forces in a system that runs once per frame
+/// would use the same force for all substeps.
+///
+/// The bodies that are inside [volume fields](ForceFieldScope::Volume) are updated at the end of each
+/// physics step based on the contacts during that step.
+pub struct ForceFieldPlugin;
+
+impl Plugin for ForceFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ForceFieldVolumes>()
+            .register_type::<ForceFieldScope>()
+            .register_type::<ForceFieldBody>();
+
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems(
+                apply_force_fields
+                    .in_set(SubstepSet::Integrate)
+                    .before(crate::plugins::integrator::apply_queued_impulses),
+            );
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(update_force_field_volumes.after(PhysicsStepSet::SpatialQuery));
+    }
+}
+
+/// A function that computes the force applied to a body by a [`ForceField`].
+///
+/// The arguments are the position of the body's center of mass relative to the [`Position`] of the field,
+/// the linear velocity of the body, and information about the body.
+pub type ForceFieldFunction = dyn Fn(Vector, Vector, &ForceFieldBody) -> Vector + Send + Sync;
+
+/// A user-defined force field that applies forces to dynamic [rigid bodies](RigidBody) in every substep.
+///
+/// The force is computed by a function of the position and velocity of each body, and it is applied at the center of mass.
+/// The position is relative to the [`Position`] of the entity of the field, or the world origin if the entity has no position.
+///
+/// A field either affects all bodies [globally](ForceFieldScope::Global), or only bodies whose colliders are touching
+/// the [collider](Collider) of its entity, which should typically be a [`Sensor`]. A field never affects the body it is attached to.
+/// Sleeping bodies are not affected.
+///
+/// Requires the [`ForceFieldPlugin`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // A vortex that spins bodies around the Y axis
+///     commands.spawn((
+///         Position(Vec3::ZERO),
+///         ForceField::global(|offset, _velocity, body| Vec3::Y.cross(offset) * body.mass),
+///     ));
+///
+///     // A tractor beam that pulls bodies towards its center and damps their motion
+///     commands.spawn((
+///         Collider::cylinder(20.0, 1.0),
+///         Sensor,
+///         ForceField::volume(|offset, velocity, body| (-offset * 10.0 - velocity * 2.0) * body.mass),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+pub struct ForceField {
+    /// The function that computes the force applied to a body.
+    pub function: Arc<ForceFieldFunction>,
+    /// Determines which bodies are affected by the field.
+    pub scope: ForceFieldScope,
+}
+
+impl fmt::Debug for ForceField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForceField")
+            .field("scope", &self.scope)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ForceField {
+    /// Creates a force field that affects all dynamic bodies.
+    pub fn global(
+        function: impl Fn(Vector, Vector, &ForceFieldBody) -> Vector + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            function: Arc::new(function),
+            scope: ForceFieldScope::Global,
+        }
+    }
+
+    /// Creates a force field that only affects bodies whose colliders are touching the collider of its entity.
+    pub fn volume(
+        function: impl Fn(Vector, Vector, &ForceFieldBody) -> Vector + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            function: Arc::new(function),
+            scope: ForceFieldScope::Volume,
+        }
+    }
+
+    /// Computes the force applied to a body with the given position relative to the field and velocity.
+    pub fn force(&self, offset: Vector, velocity: Vector, body: &ForceFieldBody) -> Vector {
+        (self.function)(offset, velocity, body)
+    }
+}
+
+/// Determines which bodies are affected by a [`ForceField`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForceFieldScope {
+    /// The field affects all dynamic bodies.
+    #[default]
+    Global,
+    /// The field affects bodies whose colliders are touching the [collider](Collider) of its entity.
+    Volume,
+}
+
+/// Information about a body that a [`ForceField`] is evaluated for.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct ForceFieldBody {
+    /// The entity of the body.
+    pub entity: Entity,
+    /// The [`Mass`] of the body.
+    pub mass: Scalar,
+    /// The position of the center of mass of the body in world space.
+    pub position: Vector,
+    /// The [`AngularVelocity`] of the body.
+    pub angular_velocity: AngularVelocity,
+}
+
+/// The [volume fields](ForceFieldScope::Volume) that each body is inside.
+#[derive(Resource, Default)]
+pub(crate) struct ForceFieldVolumes(HashMap<Entity, Vec<Entity>>);
+
+type ForceFieldBodyComponents = (
+    Entity,
+    &'static RigidBody,
+    &'static Position,
+    &'static Rotation,
+    &'static CenterOfMass,
+    &'static mut LinearVelocity,
+    &'static AngularVelocity,
+    &'static Mass,
+    &'static InverseMass,
+    Option<&'static LockedAxes>,
+);
+
+/// Applies the forces of [`ForceField`]s to dynamic bodies for the current substep.
+pub(crate) fn apply_force_fields(
+    fields: Query<(Entity, &ForceField, Option<&Position>)>,
+    mut bodies: Query<ForceFieldBodyComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    volumes: Res<ForceFieldVolumes>,
+    time: Res<Time>,
+) {
+    if fields.is_empty() {
+        return;
+    }
+
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (entity, rb, pos, rot, com, mut lin_vel, ang_vel, mass, inv_mass, locked_axes) in
+        &mut bodies
+    {
+        if !rb.is_dynamic() {
+            continue;
+        }
+
+        let body = ForceFieldBody {
+            entity,
+            mass: mass.0,
+            position: pos.0 + rot.rotate(com.0),
+            angular_velocity: *ang_vel,
+        };
+        let body_volumes = volumes.0.get(&entity);
+
+        let mut force = Vector::ZERO;
+        for (field_entity, field, field_pos) in &fields {
+            let affects_body = field_entity != entity
+                && match field.scope {
+                    ForceFieldScope::Global => true,
+                    ForceFieldScope::Volume => {
+                        body_volumes.is_some_and(|volumes| volumes.contains(&field_entity))
+                    }
+                };
+            if affects_body {
+                let offset = body.position - field_pos.map_or(Vector::ZERO, |pos| pos.0);
+                force += field.force(offset, lin_vel.0, &body);
+            }
+        }
+
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        let delta_lin_vel =
+            locked_axes.apply_to_vec(delta_secs * force * Vector::splat(inv_mass.0));
+        // avoid triggering bevy's change detection unnecessarily
+        if delta_lin_vel != Vector::ZERO {
+            lin_vel.0 += delta_lin_vel;
+        }
+    }
+}
+
+/// Finds the bodies that are inside [volume fields](ForceFieldScope::Volume) using the contacts of the current step.
+fn update_force_field_volumes(
+    fields: Query<Entity, With<ForceField>>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Option<Res<Collisions>>,
+    mut volumes: ResMut<ForceFieldVolumes>,
+) {
+    if fields.is_empty() {
+        if !volumes.0.is_empty() {
+            volumes.0.clear();
+        }
+        return;
+    }
+
+    volumes.0 = bodies_in_volumes(&fields, &collider_parents, collisions.as_deref());
+}
@@ -0,0 +1,306 @@
+//! Simulates 2D granular materials like sand, gravel and grain.
+//!
+//! See [`GranularPlugin`] and [`Granular`].
+
+use crate::{
+    plugins::particles::{
+        collide_particles, find_particle_neighbors, integrate_particles,
+        update_particle_velocities, ParticleContacts,
+    },
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Simulates 2D [granular materials](Granular) like sand, where the grains are kept apart
+/// by contacts with strong friction, so they can form stable piles and stacks.
+///
+/// The granular materials are simulated in the [`SubstepSchedule`]:
+///
+/// 1. The grains are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The neighbors of each grain are found using a spatial hash grid, and the contacts between the grains
+/// are solved with static and dynamic friction after the other particle systems in [`ParticleSet::Granular`].
+/// 3. The grains are pushed out of [colliders](Collider). Dynamic [rigid bodies](RigidBody) are pushed back
+/// by the grains, so bodies can be buried and dug out.
+/// 4. The velocities of the grains are updated from their change in position in [`SubstepSet::UpdateVelocities`].
+///
+/// Requires the `granular` feature.
+pub struct GranularPlugin;
+
+impl Plugin for GranularPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Granular>().register_type::<Particle>();
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_granular.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(
+            (solve_granular_contacts, couple_granular)
+                .chain()
+                .in_set(ParticleSet::Granular),
+        );
+
+        substeps.add_systems(update_granular_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// A 2D granular material like sand simulated as round grains by the [`GranularPlugin`].
+///
+/// The grains are [particles](Particle) with the same [`radius`](Self::radius) that push each other apart.
+/// Friction between the grains is position-based Coulomb friction: the sliding of touching grains is stopped
+/// completely below the [static friction](Self::static_friction) and limited by the [dynamic friction](Self::dynamic_friction)
+/// above it, which lets the grains form piles with a natural angle of repose. This can be used for hourglasses,
+/// avalanches and digging.
+///
+/// The grains collide with [colliders](Collider) whose [layers](CollisionLayers) interact with the [`layers`](Self::layers)
+/// of the material, and they push dynamic [rigid bodies](RigidBody) around. Grains can be added and
+/// [removed](Self::remove_particles_within) at runtime.
+///
+/// The particles are simulated in world space, so the `Transform` of the entity has no effect.
+///
+/// Requires the `granular` feature.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_xpbd_2d::prelude::*;
+///
+/// fn setup(mut commands: Commands) {
+///     // The ground
+///     commands.spawn((RigidBody::Static, Collider::rectangle(10.0, 0.2)));
+///
+///     // A block of sand with grains 0.05 units in radius that collapses into a pile
+///     commands.spawn(Granular::rectangle(Vector::Y, Vector::new(0.5, 0.5), 0.05, 1600.0));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Granular {
+    /// The grains of the material.
+    pub particles: Vec<Particle>,
+    /// The radius of the grains.
+    pub radius: Scalar,
+    /// The mass of the material per unit of area, used for computing the masses of the grains.
+    pub density: Scalar,
+    /// The coefficient of static friction between the grains. Sliding is stopped completely when the tangential
+    /// movement is smaller than the penetration depth multiplied by this coefficient.
+    pub static_friction: Scalar,
+    /// The coefficient of dynamic friction between the grains, which limits how much of the tangential movement
+    /// is removed when the grains slide. It should not be larger than the [static friction](Self::static_friction).
+    pub dynamic_friction: Scalar,
+    /// The friction of the grains against colliders, between `0.0` and `1.0`.
+    pub friction: Scalar,
+    /// The linear damping of the grains.
+    pub damping: Scalar,
+    /// The layers of the material. The grains only collide with colliders whose layers [interact](CollisionLayers::interacts_with)
+    /// with these layers.
+    pub layers: CollisionLayers,
+    /// The neighbors of each grain found in the last substep.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl Granular {
+    /// Creates an empty granular material with the given grain `radius` and `density` in mass per unit of area.
+    pub fn new(radius: Scalar, density: Scalar) -> Self {
+        Self {
+            particles: vec![],
+            radius,
+            density,
+            static_friction: 0.8,
+            dynamic_friction: 0.5,
+            friction: 0.8,
+            damping: 0.0,
+            layers: CollisionLayers::default(),
+            neighbors: vec![],
+        }
+    }
+
+    /// Creates a rectangular block of grains with the given `center` and `half_extents`,
+    /// grain `radius` and `density` in mass per unit of area.
+    ///
+    /// Every other row is offset by the radius, so the grains settle instead of staying stacked in columns.
+    pub fn rectangle(
+        center: Vector,
+        half_extents: Vector,
+        radius: Scalar,
+        density: Scalar,
+    ) -> Self {
+        let mut granular = Self::new(radius, density);
+        let spacing = 2.0 * radius;
+        let counts = (2.0 * half_extents / spacing).floor().max(Vector::ONE);
+        let min = center - (counts - Vector::ONE) * spacing * 0.5;
+
+        for y in 0..counts.y as usize {
+            let offset = if y % 2 == 1 { 0.5 * spacing } else { 0.0 };
+            for x in 0..counts.x as usize {
+                let position =
+                    min + Vector::new(x as Scalar * spacing + offset, y as Scalar * spacing);
+                granular.add_particle(position, Vector::ZERO);
+            }
+        }
+
+        granular
+    }
+
+    /// Adds a grain with the given `position` and `velocity` to the material.
+    pub fn add_particle(&mut self, position: Vector, velocity: Vector) {
+        self.particles
+            .push(Particle::new(position, self.particle_mass()).with_velocity(velocity));
+    }
+
+    /// Removes the grains within the given `radius` of the `center`, for example when digging.
+    ///
+    /// Returns the number of removed grains.
+    pub fn remove_particles_within(&mut self, center: Vector, radius: Scalar) -> usize {
+        let count = self.particles.len();
+        self.particles
+            .retain(|particle| particle.position.distance_squared(center) > radius * radius);
+        self.neighbors.clear();
+        count - self.particles.len()
+    }
+
+    /// Sets the coefficients of static and dynamic friction between the grains.
+    pub fn with_grain_friction(
+        mut self,
+        static_friction: Scalar,
+        dynamic_friction: Scalar,
+    ) -> Self {
+        self.static_friction = static_friction;
+        self.dynamic_friction = dynamic_friction;
+        self
+    }
+
+    /// Sets the friction of the grains against colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the grains.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the layers of the material used for collisions.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Returns the mass of each grain based on the [`radius`](Self::radius) and [`density`](Self::density).
+    pub fn particle_mass(&self) -> Scalar {
+        self.density * 4.0 * self.radius * self.radius
+    }
+}
+
+/// Moves the grains of granular materials by their velocities and gravity.
+fn integrate_granular(mut materials: Query<&mut Granular>, gravity: Res<Gravity>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut granular in &mut materials {
+        let damping = granular.damping;
+        integrate_particles(&mut granular.particles, gravity.0, damping, delta_secs);
+    }
+}
+
+/// Pushes touching grains apart and applies static and dynamic friction between them.
+fn solve_granular_contacts(mut materials: Query<&mut Granular>) {
+    for mut granular in &mut materials {
+        let granular = &mut *granular;
+        let diameter = 2.0 * granular.radius;
+
+        find_particle_neighbors(&granular.particles, diameter, &mut granular.neighbors);
+
+        for (i, neighbors) in granular.neighbors.iter().enumerate() {
+            for &j in neighbors.iter().filter(|&&j| j > i) {
+                solve_grain_contact(
+                    &mut granular.particles,
+                    [i, j],
+                    diameter,
+                    granular.static_friction,
+                    granular.dynamic_friction,
+                );
+            }
+        }
+    }
+}
+
+/// Keeps two grains at least `diameter` apart and removes their relative tangential movement during the substep
+/// based on the coefficients of static and dynamic friction.
+fn solve_grain_contact(
+    particles: &mut [Particle],
+    [i1, i2]: [usize; 2],
+    diameter: Scalar,
+    static_friction: Scalar,
+    dynamic_friction: Scalar,
+) {
+    let (w1, w2) = (particles[i1].inverse_mass, particles[i2].inverse_mass);
+    let w_sum = w1 + w2;
+    let delta = particles[i2].position - particles[i1].position;
+    let distance = delta.length();
+
+    if w_sum <= Scalar::EPSILON || distance >= diameter || distance <= Scalar::EPSILON {
+        return;
+    }
+
+    let normal = delta / distance;
+    let penetration = diameter - distance;
+    particles[i1].position -= normal * penetration * w1 / w_sum;
+    particles[i2].position += normal * penetration * w2 / w_sum;
+
+    // The movement of the second grain relative to the first one during the substep
+    let displacement = (particles[i2].position - particles[i2].previous_position)
+        - (particles[i1].position - particles[i1].previous_position);
+    let tangential = displacement - normal * displacement.dot(normal);
+    let tangential_length = tangential.length();
+
+    if tangential_length <= Scalar::EPSILON {
+        return;
+    }
+
+    let fraction = if tangential_length < static_friction * penetration {
+        1.0
+    } else {
+        (dynamic_friction * penetration / tangential_length).min(1.0)
+    };
+    let correction = tangential * fraction;
+    particles[i1].position += correction * w1 / w_sum;
+    particles[i2].position -= correction * w2 / w_sum;
+}
+
+/// Pushes the grains of granular materials out of colliders, and pushes dynamic bodies back.
+fn couple_granular(mut materials: Query<&mut Granular>, mut contacts: ParticleContacts) {
+    for mut granular in &mut materials {
+        let granular = &mut *granular;
+        if granular.particles.is_empty() {
+            continue;
+        }
+
+        collide_particles(
+            &mut granular.particles,
+            granular.radius,
+            granular.friction,
+            granular.layers,
+            &[],
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of the grains of granular materials based on their change in position.
+fn update_granular_velocities(mut materials: Query<&mut Granular>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut granular in &mut materials {
+        update_particle_velocities(&mut granular.particles, delta_secs);
+    }
+}
@@ -0,0 +1,66 @@
+//! Running the physics simulation in headless apps, like dedicated servers, bots or tests,
+//! with manual tick-driven stepping.
+//!
+//! A headless app doesn't need rendering or windowing, so it can use Bevy's `MinimalPlugins`
+//! together with [`PhysicsPlugins::headless`], which excludes the [`SyncPlugin`] that keeps
+//! `Transform` in sync with the physics components.
+//!
+//! The simulation can then be advanced one tick at a time using [`PhysicsAppExt::step_physics`],
+//! and the results can be read directly from the [`World`], for example from the [`Position`],
+//! [`Rotation`] and velocity components of bodies or from the [`Collisions`] resource.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use bevy::prelude::*;
+//! # #[cfg(feature = "2d")]
+//! # use bevy_xpbd_2d::prelude::*;
+//! # #[cfg(feature = "3d")]
+//! use bevy_xpbd_3d::prelude::*;
+//!
+//! let mut app = App::new();
+//! app.add_plugins((MinimalPlugins, PhysicsPlugins::headless()));
+//!
+//! let body = app
+//!     .world
+//!     .spawn((RigidBody::Dynamic, Collider::capsule(1.0, 0.5)))
+//!     .id();
+//!
+//! // Run the server loop for one second at 60 Hz
+//! for _ in 0..60 {
+//!     app.step_physics(Duration::from_secs_f64(1.0 / 60.0));
+//! }
+//!
+//! // The body has fallen due to gravity
+//! let position = app.world.get::<Position>(body).unwrap();
+//! assert!(position.y < -4.0);
+//! ```
+
+use std::time::Duration;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// An extension trait for stepping the physics simulation of an [`App`] manually.
+///
+/// See the [module-level documentation](crate::plugins::headless) for more information.
+pub trait PhysicsAppExt {
+    /// Updates the app once and advances the physics simulation by exactly one step with the given `delta` time.
+    ///
+    /// The [`Time<Physics>`](Physics) clock is paused so that the simulation is only advanced by this method.
+    /// The app is updated before the step, so systems like the ones in [`PhysicsSet::Prepare`] and
+    /// user systems that apply inputs are run before the simulation is advanced with [`Physics::step`].
+    /// The results of the step can be accessed directly after this method returns.
+    fn step_physics(&mut self, delta: Duration) -> &mut Self;
+}
+
+impl PhysicsAppExt for App {
+    fn step_physics(&mut self, delta: Duration) -> &mut Self {
+        self.world.resource_mut::<Time<Physics>>().pause();
+        self.update();
+        Physics::step(&mut self.world, delta);
+        self
+    }
+}
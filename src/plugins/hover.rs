@@ -0,0 +1,447 @@
+//! Keeps hovering bodies at a target height above the ground.
+//!
+//! See [`HoverPlugin`] and [`Hover`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Applies PID-controlled spring forces to dynamic [rigid bodies](RigidBody) with a [`Hover`],
+/// keeping them at a target height above arbitrary terrain.
+///
+/// The plugin works in two parts:
+///
+/// 1. The ground below each sample point of a [`Hover`] is found by casting its shape along the hover direction
+/// using the [`SpatialQueryPipeline`]. This is done once per physics step, before [`PhysicsStepSet::Substeps`].
+/// 2. The hover forces are computed at the start of every substep in [`SubstepSet::Integrate`], using the current
+/// position and velocity of the body. The ground is treated as a plane through each hit, so the height is tracked
+/// accurately within a step even when the body is moving fast.
+///
+/// Computing the forces in every substep instead of once per step makes stiff and heavily damped hovers stable
+/// at any [substep count](SubstepCount) and speed.
+pub struct HoverPlugin;
+
+impl Plugin for HoverPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HoverHit>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                // The velocities of the ground are read after impulses have been applied to them.
+                update_hover_ground
+                    .after(crate::plugins::integrator::apply_impulses)
+                    .before(PhysicsStepSet::Substeps),
+            );
+
+        app.get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first")
+            .add_systems(
+                apply_hover_forces
+                    .in_set(SubstepSet::Integrate)
+                    .after(crate::plugins::force_field::apply_force_fields)
+                    .before(crate::plugins::integrator::apply_queued_impulses),
+            );
+    }
+}
+
+/// Keeps a dynamic [rigid body](RigidBody) hovering at a target [`height`](Self::height) above the ground,
+/// like a hovercraft or a sci-fi bike.
+///
+/// Each of the [`points`](Self::points) casts the [`shape`](Self::shape) along the hover [`direction`](Self::direction)
+/// to find the ground below it. A PID controller then pushes the point away from the ground with a force along
+/// the opposite direction:
+///
+/// - The **proportional** term acts like a spring, pushing harder the further below the target height the point is.
+/// - The **integral** term accumulates the error over time, so the body settles at the target height
+/// instead of sagging under gravity.
+/// - The **derivative** term damps the motion of the point towards and away from the ground.
+///
+/// The forces are applied at the sample points, so a hover with several points also keeps the body level
+/// with the terrain. Hovers only push away from the ground and never pull the body down, so bodies fly
+/// off ramps and fall normally when there is no ground within the [`max_distance`](Self::max_distance).
+///
+/// The gains and the [`max_acceleration`](Self::max_acceleration) are per unit of the body's mass,
+/// and the force is divided evenly between the points, so the same configuration works for light and heavy bodies.
+///
+/// The colliders of the body itself are ignored by the shapecasts. Sleeping bodies are not affected.
+///
+/// Requires the [`HoverPlugin`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A hovercraft that hovers one meter above the ground on four pads
+///     commands.spawn((
+///         RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "         Collider::rectangle(4.0, 0.5),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::cuboid(2.0, 0.5, 4.0),")]
+///         Hover::new(
+#[cfg_attr(feature = "2d", doc = "             Collider::circle(0.2),")]
+#[cfg_attr(feature = "3d", doc = "             Collider::sphere(0.2),")]
+#[cfg_attr(
+    feature = "2d",
+    doc = "             vec![Vec2::new(-1.5, 0.0), Vec2::new(1.5, 0.0)],"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "             vec![
+                 Vec3::new(-0.8, 0.0, -1.5),
+                 Vec3::new(0.8, 0.0, -1.5),
+                 Vec3::new(-0.8, 0.0, 1.5),
+                 Vec3::new(0.8, 0.0, 1.5),
+             ],"
+)]
+///             1.0,
+///         )
+///         .with_gains(150.0, 80.0, 20.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hover {
+    /// The shape that is cast from each sample point to find the ground.
+    pub shape: Collider,
+    /// The sample points in the local space of the body, relative to its [`Position`].
+    pub points: Vec<Vector>,
+    /// The direction of the ground in the local space of the body. The hover forces push the body
+    /// in the opposite direction.
+    ///
+    /// Default: negative Y
+    pub direction: Vector,
+    /// The target distance between the sample points and the ground along the [`direction`](Self::direction).
+    pub height: Scalar,
+    /// The maximum distance between a sample point and the ground at which the point is still affected.
+    ///
+    /// Default: twice the [`height`](Self::height)
+    pub max_distance: Scalar,
+    /// The proportional gain, or the stiffness of the spring, per unit of mass.
+    ///
+    /// Default: `100.0`
+    pub stiffness: Scalar,
+    /// The integral gain per unit of mass. Removes the sag caused by gravity and other constant forces.
+    ///
+    /// Default: `50.0`
+    pub integral: Scalar,
+    /// The derivative gain, or the damping of the spring, per unit of mass.
+    ///
+    /// Default: `15.0`
+    pub damping: Scalar,
+    /// The maximum acceleration that the hover can apply to the body. The accumulated error of the integral term
+    /// is also limited so that it can't exceed this acceleration on its own.
+    ///
+    /// Default: `50.0`
+    pub max_acceleration: Scalar,
+    /// Rules that determine which colliders are treated as ground.
+    pub query_filter: SpatialQueryFilter,
+    /// The ground below each sample point, found in the last physics step.
+    hits: Vec<Option<HoverHit>>,
+    /// The accumulated height error of each sample point for the integral term.
+    integral_errors: Vec<Scalar>,
+}
+
+impl Hover {
+    /// Creates a new hover that casts the given `shape` from the given local `points`
+    /// and keeps them at the given `height` above the ground.
+    pub fn new(shape: Collider, points: Vec<Vector>, height: Scalar) -> Self {
+        Self {
+            shape,
+            points,
+            direction: Vector::NEG_Y,
+            height,
+            max_distance: 2.0 * height,
+            stiffness: 100.0,
+            integral: 50.0,
+            damping: 15.0,
+            max_acceleration: 50.0,
+            query_filter: SpatialQueryFilter::default(),
+            hits: vec![],
+            integral_errors: vec![],
+        }
+    }
+
+    /// Sets the direction of the ground in the local space of the body.
+    pub fn with_direction(mut self, direction: Vector) -> Self {
+        self.direction = direction.normalize_or_zero();
+        self
+    }
+
+    /// Sets the maximum distance between a sample point and the ground at which the point is still affected.
+    pub fn with_max_distance(mut self, max_distance: Scalar) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Sets the proportional, integral and derivative gains per unit of mass.
+    pub fn with_gains(mut self, stiffness: Scalar, integral: Scalar, damping: Scalar) -> Self {
+        self.stiffness = stiffness;
+        self.integral = integral;
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the maximum acceleration that the hover can apply to the body.
+    pub fn with_max_acceleration(mut self, max_acceleration: Scalar) -> Self {
+        self.max_acceleration = max_acceleration;
+        self
+    }
+
+    /// Sets the rules that determine which colliders are treated as ground.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+
+    /// Returns the ground below each of the [`points`](Self::points) found in the last physics step,
+    /// or `None` for points that have no ground within the [`max_distance`](Self::max_distance).
+    pub fn hits(&self) -> &[Option<HoverHit>] {
+        &self.hits
+    }
+
+    /// Returns `true` if any of the [`points`](Self::points) had ground below it in the last physics step.
+    pub fn is_grounded(&self) -> bool {
+        self.hits.iter().any(Option::is_some)
+    }
+}
+
+/// The ground below a sample point of a [`Hover`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct HoverHit {
+    /// The entity of the collider that was hit.
+    pub entity: Entity,
+    /// The distance between the sample point and the ground along the hover direction
+    /// at the start of the physics step.
+    pub distance: Scalar,
+    /// The point where the cast shape touched the ground in world space.
+    pub point: Vector,
+    /// The normal of the ground at the [`point`](Self::point) in world space.
+    pub normal: Vector,
+    /// The velocity of the ground at the [`point`](Self::point).
+    pub ground_velocity: Vector,
+    /// The position of the sample point when the cast shape touches the ground in world space.
+    /// The ground is treated as a plane through this point with the [`normal`](Self::normal).
+    plane_point: Vector,
+}
+
+/// Finds the ground below the sample points of each [`Hover`] by casting their shapes.
+#[allow(clippy::type_complexity)]
+fn update_hover_ground(
+    mut hovers: Query<
+        (Entity, &mut Hover, &Position, &Rotation),
+        (Without<Sleeping>, Without<RigidBodyDisabled>),
+    >,
+    colliders: Query<(&Position, &Rotation, Option<&ColliderParent>), With<Collider>>,
+    bodies: Query<(
+        &Position,
+        &Rotation,
+        &CenterOfMass,
+        &LinearVelocity,
+        &AngularVelocity,
+    )>,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+) {
+    let Some(pipeline) = pipeline.filter(|_| !hovers.is_empty()) else {
+        return;
+    };
+
+    for (entity, mut hover, pos, rot) in &mut hovers {
+        let hover = &mut *hover;
+        let point_count = hover.points.len();
+        hover.hits.resize(point_count, None);
+        hover.integral_errors.resize(point_count, 0.0);
+
+        let Ok(direction) = Dir::new(rot.rotate(hover.direction).f32()) else {
+            hover.hits.fill(None);
+            continue;
+        };
+        let direction_vector = direction.adjust_precision();
+        #[cfg(feature = "2d")]
+        let shape_rotation = rot.as_radians();
+        #[cfg(feature = "3d")]
+        let shape_rotation = rot.0;
+
+        for (index, local_point) in hover.points.iter().enumerate() {
+            let origin = pos.0 + rot.rotate(*local_point);
+
+            // Find the closest hit that doesn't belong to the hovering body itself
+            let hit = pipeline
+                .shape_hits_sorted(
+                    &hover.shape,
+                    origin,
+                    shape_rotation,
+                    direction,
+                    hover.max_distance,
+                    true,
+                    hover.query_filter.clone(),
+                )
+                .find(|hit| {
+                    colliders
+                        .get(hit.entity)
+                        .is_ok_and(|(.., parent)| parent.map_or(hit.entity, |p| p.get()) != entity)
+                });
+
+            let hit = hit.and_then(|hit| {
+                let (collider_pos, collider_rot, parent) = colliders.get(hit.entity).ok()?;
+                let point = collider_pos.0 + collider_rot.rotate(hit.point1);
+                let normal = collider_rot.rotate(hit.normal1).normalize_or_zero();
+
+                // The velocity of the ground at the hit point
+                let ground_velocity = bodies.get(parent.map_or(hit.entity, |p| p.get())).map_or(
+                    Vector::ZERO,
+                    |(ground_pos, ground_rot, ground_com, lin_vel, ang_vel)| {
+                        let r = point - (ground_pos.0 + ground_rot.rotate(ground_com.0));
+                        #[cfg(feature = "2d")]
+                        let velocity = lin_vel.0 + ang_vel.0 * r.perp();
+                        #[cfg(feature = "3d")]
+                        let velocity = lin_vel.0 + ang_vel.0.cross(r);
+                        velocity
+                    },
+                );
+
+                Some(HoverHit {
+                    entity: hit.entity,
+                    distance: hit.time_of_impact,
+                    point,
+                    normal,
+                    ground_velocity,
+                    plane_point: origin + direction_vector * hit.time_of_impact,
+                })
+            });
+
+            // Reset the integral term when the point leaves the ground
+            if hit.is_none() {
+                hover.integral_errors[index] = 0.0;
+            }
+            hover.hits[index] = hit;
+        }
+    }
+}
+
+type HoverBodyComponents = (
+    &'static RigidBody,
+    &'static mut Hover,
+    &'static Position,
+    &'static Rotation,
+    &'static CenterOfMass,
+    &'static mut LinearVelocity,
+    &'static mut AngularVelocity,
+    &'static Mass,
+    &'static InverseMass,
+    &'static InverseInertia,
+    Option<&'static LockedAxes>,
+    Option<&'static TimeScale>,
+);
+
+/// Applies the forces of [hovers](Hover) to dynamic bodies for the current substep.
+fn apply_hover_forces(
+    mut bodies: Query<HoverBodyComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (
+        rb,
+        mut hover,
+        pos,
+        rot,
+        com,
+        mut lin_vel,
+        mut ang_vel,
+        mass,
+        inv_mass,
+        inv_inertia,
+        locked_axes,
+        time_scale,
+    ) in &mut bodies
+    {
+        if !rb.is_dynamic() || !hover.is_grounded() {
+            continue;
+        }
+
+        let delta_secs = delta_secs * time_scale.map_or(1.0, |scale| scale.0);
+        if delta_secs <= 0.0 {
+            continue;
+        }
+
+        let hover = &mut *hover;
+        let direction = rot.rotate(hover.direction);
+        let center_of_mass = pos.0 + rot.rotate(com.0);
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        #[cfg(feature = "2d")]
+        let inv_inertia = locked_axes.apply_to_rotation(inv_inertia.0);
+        #[cfg(feature = "3d")]
+        let inv_inertia = locked_axes.apply_to_rotation(inv_inertia.rotated(rot).0);
+        let max_integral_error = if hover.integral > 0.0 {
+            hover.max_acceleration / hover.integral
+        } else {
+            0.0
+        };
+
+        let mut delta_lin_vel = Vector::ZERO;
+        let mut delta_ang_vel = AngularVelocity::ZERO.0;
+
+        for (index, local_point) in hover.points.iter().enumerate() {
+            let Some(hit) = hover.hits[index] else {
+                continue;
+            };
+
+            // Skip ground that is parallel to the hover direction, like walls
+            let alignment = -direction.dot(hit.normal);
+            if alignment <= Scalar::EPSILON {
+                continue;
+            }
+
+            // Compute the current height of the point above the plane of the ground
+            let point = pos.0 + rot.rotate(*local_point);
+            let height = (point - hit.plane_point).dot(hit.normal) / alignment;
+            if height > hover.max_distance {
+                hover.integral_errors[index] = 0.0;
+                continue;
+            }
+
+            // Compute the rate at which the height changes
+            let r = point - center_of_mass;
+            #[cfg(feature = "2d")]
+            let point_velocity = lin_vel.0 + ang_vel.0 * r.perp();
+            #[cfg(feature = "3d")]
+            let point_velocity = lin_vel.0 + ang_vel.0.cross(r);
+            let height_rate = (point_velocity - hit.ground_velocity).dot(hit.normal) / alignment;
+
+            let error = hover.height - height;
+            let integral_error = (hover.integral_errors[index] + error * delta_secs)
+                .clamp(-max_integral_error, max_integral_error);
+            hover.integral_errors[index] = integral_error;
+
+            // The PID controller only pushes the body away from the ground
+            let acceleration = (hover.stiffness * error + hover.integral * integral_error
+                - hover.damping * height_rate)
+                .clamp(0.0, hover.max_acceleration);
+            let force = -direction * acceleration * mass.0 / hover.points.len() as Scalar;
+
+            delta_lin_vel += delta_secs * force * inv_mass.0;
+            #[cfg(feature = "2d")]
+            {
+                delta_ang_vel += delta_secs * inv_inertia * r.perp_dot(force);
+            }
+            #[cfg(feature = "3d")]
+            {
+                delta_ang_vel += delta_secs * (inv_inertia * r.cross(force));
+            }
+        }
+
+        let delta_lin_vel = locked_axes.apply_to_vec(delta_lin_vel);
+        // avoid triggering bevy's change detection unnecessarily
+        if delta_lin_vel != Vector::ZERO {
+            lin_vel.0 += delta_lin_vel;
+        }
+        if delta_ang_vel != AngularVelocity::ZERO.0 {
+            ang_vel.0 += delta_ang_vel;
+        }
+    }
+}
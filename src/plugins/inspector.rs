@@ -0,0 +1,161 @@
+//! An egui window for tuning the simulation at runtime.
+//!
+//! See [`PhysicsInspectorPlugin`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts, EguiPlugin};
+
+/// A plugin that shows an [egui](bevy_egui) window for tuning the simulation while the app is running.
+///
+/// The window exposes the [`SubstepCount`], [`Gravity`], [`SleepingThreshold`], [`DeactivationTime`],
+/// [`NarrowPhaseConfig`] and the speed of [`Time<Physics>`](Physics). Changes are applied immediately,
+/// so tuning the simulation doesn't require recompiling or restarting the app.
+///
+/// The resources are only modified when a value is actually changed in the window,
+/// so change detection for them is not triggered every frame.
+///
+/// This plugin is only available with the `egui` feature and must be added separately.
+/// It adds the [`EguiPlugin`] if it hasn't been added yet.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsInspectorPlugin,
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct PhysicsInspectorPlugin;
+
+impl Plugin for PhysicsInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.add_systems(Update, physics_inspector_ui);
+    }
+}
+
+/// The values shown in the inspector window, copied from the resources each frame.
+#[derive(Clone, PartialEq)]
+struct InspectorValues {
+    substep_count: u32,
+    gravity: Vector,
+    sleeping_threshold: SleepingThreshold,
+    deactivation_time: Scalar,
+    prediction_distance: Option<Scalar>,
+    relative_speed: f64,
+    paused: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn physics_inspector_ui(
+    mut contexts: EguiContexts,
+    mut substep_count: ResMut<SubstepCount>,
+    mut gravity: ResMut<Gravity>,
+    mut sleeping_threshold: ResMut<SleepingThreshold>,
+    mut deactivation_time: ResMut<DeactivationTime>,
+    mut narrow_phase_config: Option<ResMut<NarrowPhaseConfig>>,
+    mut time: ResMut<Time<Physics>>,
+) {
+    let old = InspectorValues {
+        substep_count: substep_count.0,
+        gravity: gravity.0,
+        sleeping_threshold: *sleeping_threshold,
+        deactivation_time: deactivation_time.0,
+        prediction_distance: narrow_phase_config
+            .as_ref()
+            .map(|config| config.prediction_distance),
+        relative_speed: time.relative_speed_f64(),
+        paused: time.is_paused(),
+    };
+    let mut new = old.clone();
+
+    egui::Window::new("Physics").show(contexts.ctx_mut(), |ui| {
+        ui.heading("Time");
+        ui.checkbox(&mut new.paused, "Paused");
+        ui.add(egui::Slider::new(&mut new.relative_speed, 0.0..=4.0).text("Relative speed"));
+
+        ui.heading("Solver");
+        ui.add(egui::Slider::new(&mut new.substep_count, 1..=50).text("Substeps"));
+
+        ui.heading("Gravity");
+        ui.horizontal(|ui| {
+            for (value, label) in new.gravity.as_mut().iter_mut().zip(["x", "y", "z"]) {
+                ui.label(label);
+                ui.add(egui::DragValue::new(value).speed(0.1));
+            }
+        });
+
+        ui.heading("Sleeping");
+        ui.horizontal(|ui| {
+            ui.label("Linear threshold");
+            ui.add(egui::DragValue::new(&mut new.sleeping_threshold.linear).speed(0.01));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Angular threshold");
+            ui.add(egui::DragValue::new(&mut new.sleeping_threshold.angular).speed(0.01));
+        });
+        ui.add(egui::Slider::new(&mut new.deactivation_time, 0.0..=5.0).text("Deactivation time"));
+
+        if let Some(prediction_distance) = new.prediction_distance.as_mut() {
+            ui.heading("Narrow phase");
+            ui.horizontal(|ui| {
+                ui.label("Prediction distance");
+                ui.add(
+                    egui::DragValue::new(prediction_distance)
+                        .speed(0.001)
+                        .clamp_range(0.0..=Scalar::MAX),
+                );
+            });
+        }
+    });
+
+    if new == old {
+        return;
+    }
+
+    // Only write the values that changed to avoid triggering change detection every frame.
+    if new.substep_count != old.substep_count {
+        substep_count.0 = new.substep_count;
+    }
+    if new.gravity != old.gravity {
+        gravity.0 = new.gravity;
+    }
+    if new.sleeping_threshold != old.sleeping_threshold {
+        *sleeping_threshold = new.sleeping_threshold;
+    }
+    if new.deactivation_time != old.deactivation_time {
+        deactivation_time.0 = new.deactivation_time;
+    }
+    if let (Some(config), Some(prediction_distance)) =
+        (narrow_phase_config.as_mut(), new.prediction_distance)
+    {
+        if new.prediction_distance != old.prediction_distance {
+            config.prediction_distance = prediction_distance;
+        }
+    }
+    if new.relative_speed != old.relative_speed {
+        time.set_relative_speed_f64(new.relative_speed);
+    }
+    if new.paused != old.paused {
+        if new.paused {
+            time.pause();
+        } else {
+            time.unpause();
+        }
+    }
+}
@@ -3,7 +3,14 @@
 //! See [`IntegratorPlugin`].
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    ecs::{
+        query::{QueryFilter, ROQueryItem, ReadOnlyQueryData},
+        system::SystemParam,
+    },
+    prelude::*,
+    utils::HashMap,
+};
 
 /// Integrates Newton's 2nd law of motion, applying forces and moving entities according to their velocities.
 ///
@@ -12,34 +19,77 @@ use bevy::prelude::*;
 ///
 /// The integration scheme used is very closely related to implicit Euler integration.
 ///
-/// The integration systems run in [`SubstepSet::Integrate`].
+/// The integration systems run in [`SubstepSet::Integrate`]. Impulses queued using [`PhysicsCommands`]
+/// are also applied there, at the start of the substep they were queued for.
 pub struct IntegratorPlugin;
 
 impl Plugin for IntegratorPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<QueuedImpulses>()
+            .init_resource::<QueuedExplosions>()
+            .init_resource::<GravityVolumeBlending>()
+            .init_resource::<GravityVolumeOverrides>()
+            .init_resource::<WindZoneVelocities>();
+
         app.get_schedule_mut(SubstepSchedule)
             .expect("add SubstepSchedule first")
-            .add_systems((integrate_pos, integrate_rot).in_set(SubstepSet::Integrate));
-        app.get_schedule_mut(PhysicsSchedule)
-            .expect("add PhysicsSchedule first")
             .add_systems(
-                apply_impulses
+                // The positions are integrated first so that local forces are rotated
+                // using the rotations from the start of the substep
+                (apply_queued_impulses, integrate_pos, integrate_rot)
+                    .chain()
+                    .in_set(SubstepSet::Integrate),
+            );
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule
+            .add_systems(
+                (apply_impulses, prepare_queued_impulses)
                     .after(PhysicsStepSet::BroadPhase)
                     .before(PhysicsStepSet::Substeps),
             )
-            .add_systems(clear_forces_and_impulses.after(PhysicsStepSet::SpatialQuery));
+            .add_systems(
+                (apply_mass_changes, track_kinematic_targets)
+                    .chain()
+                    .after(PhysicsStepSet::BroadPhase)
+                    .before(apply_impulses),
+            )
+            .add_systems(
+                (
+                    clear_forces_and_impulses,
+                    update_gravity_volumes,
+                    update_wind_zones,
+                )
+                    .after(PhysicsStepSet::SpatialQuery),
+            );
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        physics_schedule.add_systems(
+            apply_explosions
+                .after(PhysicsStepSet::BroadPhase)
+                .before(prepare_queued_impulses),
+        );
     }
 }
 
 type PosIntegrationComponents = (
-    &'static RigidBody,
+    Entity,
+    (&'static RigidBody, Option<&'static TimeScale>),
     &'static Position,
     &'static mut PreviousPosition,
     &'static mut AccumulatedTranslation,
     &'static mut LinearVelocity,
-    Option<&'static LinearDamping>,
+    (Option<&'static LinearDamping>, Option<&'static Drag>),
     Option<&'static GravityScale>,
     &'static ExternalForce,
+    Option<&'static ConstantForce>,
+    Option<&'static ConstantLocalForce>,
+    &'static Rotation,
     &'static Mass,
     &'static InverseMass,
     Option<&'static LockedAxes>,
@@ -47,22 +97,33 @@ type PosIntegrationComponents = (
 
 /// Explicitly integrates the positions and linear velocities of bodies taking only external forces
 /// like gravity into account. This acts as a prediction for the next positions of the bodies.
+#[allow(clippy::too_many_arguments)]
 fn integrate_pos(
-    mut bodies: Query<PosIntegrationComponents, Without<Sleeping>>,
+    mut bodies: Query<PosIntegrationComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    gravity_fields: Query<(Entity, &GravityField, &Position)>,
+    gravity_volumes: Res<GravityVolumeOverrides>,
     gravity: Res<Gravity>,
+    wind: Res<Wind>,
+    wind_zones: Res<WindZoneVelocities>,
     time: Res<Time>,
+    physics_time: Res<Time<Physics>>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
+    let elapsed_secs = physics_time.elapsed().as_secs_adjusted();
 
     for (
-        rb,
+        entity,
+        (rb, time_scale),
         pos,
         mut prev_pos,
         mut translation,
         mut lin_vel,
-        lin_damping,
+        (lin_damping, drag),
         gravity_scale,
         external_force,
+        constant_force,
+        constant_local_force,
+        rot,
         mass,
         inv_mass,
         locked_axes,
@@ -74,22 +135,40 @@ fn integrate_pos(
             continue;
         }
 
+        let delta_secs = delta_secs * time_scale.map_or(1.0, |scale| scale.0);
+
         let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
 
         // Apply damping, gravity and other external forces
         if rb.is_dynamic() {
-            // Apply damping
-            if let Some(damping) = lin_damping {
-                lin_vel.0 *= 1.0 / (1.0 + delta_secs * damping.0);
+            // Apply damping and drag relative to the wind
+            let damped_lin_vel = damp_linear_velocity(
+                lin_vel.0,
+                lin_damping,
+                drag,
+                || {
+                    wind.velocity_at(pos.0, elapsed_secs)
+                        + wind_zones.0.get(&entity).copied().unwrap_or(Vector::ZERO)
+                },
+                inv_mass.0,
+                delta_secs,
+            );
+            // avoid triggering bevy's change detection unnecessarily
+            if damped_lin_vel != lin_vel.0 {
+                lin_vel.0 = damped_lin_vel;
             }
 
             let effective_mass = locked_axes.apply_to_vec(Vector::splat(mass.0));
             let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
 
             // Apply forces
-            let gravitation_force =
-                effective_mass * gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
-            let external_forces = gravitation_force + external_force.force();
+            let base_gravity = gravity_volumes.0.get(&entity).copied().unwrap_or(gravity.0);
+            let gravitation_force = effective_mass
+                * gravity_acceleration(entity, pos.0, base_gravity, &gravity_fields)
+                * gravity_scale.map_or(1.0, |scale| scale.0);
+            let constant_forces = constant_force.map_or(Vector::ZERO, |force| force.0)
+                + constant_local_force.map_or(Vector::ZERO, |force| rot.rotate(force.0));
+            let external_forces = gravitation_force + external_force.force() + constant_forces;
             let delta_lin_vel = delta_secs * external_forces * effective_inv_mass;
             // avoid triggering bevy's change detection unnecessarily
             if delta_lin_vel != Vector::ZERO {
@@ -102,23 +181,201 @@ fn integrate_pos(
     }
 }
 
+/// Applies [`LinearDamping`] and [`Drag`] to the given linear velocity for a timestep of `delta_secs`.
+///
+/// The change in speed caused by drag is clamped so that drag never reverses the velocity
+/// relative to the wind. The wind velocity is only computed if the body has [`Drag`].
+///
+/// This is also used for predicting trajectories so that they match the simulation.
+pub(crate) fn damp_linear_velocity(
+    mut velocity: Vector,
+    damping: Option<&LinearDamping>,
+    drag: Option<&Drag>,
+    wind_velocity: impl FnOnce() -> Vector,
+    inv_mass: Scalar,
+    delta_secs: Scalar,
+) -> Vector {
+    if let Some(damping) = damping {
+        velocity *= 1.0 / (1.0 + delta_secs * damping.0);
+    }
+
+    if let Some(drag) = drag {
+        let relative_velocity = velocity - wind_velocity();
+        let speed = relative_velocity.length();
+        if speed > 0.0 {
+            let delta_speed = (delta_secs * drag.force_magnitude(speed) * inv_mass).min(speed);
+            if delta_speed > 0.0 {
+                velocity -= relative_velocity / speed * delta_speed;
+            }
+        }
+    }
+
+    velocity
+}
+
+/// Computes the gravitational acceleration of a body at the given position from the base gravity,
+/// which is the global [`Gravity`] or the gravity of the [volumes](GravityVolume) the body is in,
+/// and the [gravity fields](GravityField) that affect it.
+pub(crate) fn gravity_acceleration(
+    entity: Entity,
+    position: Vector,
+    base_gravity: Vector,
+    gravity_fields: &Query<(Entity, &GravityField, &Position)>,
+) -> Vector {
+    let mut acceleration = Vector::ZERO;
+    let mut replaces_global_gravity = false;
+
+    for (field_entity, field, field_position) in gravity_fields {
+        if field_entity == entity {
+            continue;
+        }
+        if let Some(field_acceleration) = field.acceleration_at(field_position.0, position) {
+            acceleration += field_acceleration;
+            replaces_global_gravity |= field.replaces_global_gravity;
+        }
+    }
+
+    if replaces_global_gravity {
+        acceleration
+    } else {
+        acceleration + base_gravity
+    }
+}
+
+/// The gravity of bodies that are inside [gravity volumes](GravityVolume), blended according to the
+/// [`GravityVolumeBlending`].
+#[derive(Resource, Default)]
+pub(crate) struct GravityVolumeOverrides(HashMap<Entity, Vector>);
+
+/// Finds the bodies that are inside [gravity volumes](GravityVolume) using the contacts of the current step
+/// and computes their gravity.
+fn update_gravity_volumes(
+    volumes: Query<&GravityVolume>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Option<Res<Collisions>>,
+    gravity: Res<Gravity>,
+    blending: Res<GravityVolumeBlending>,
+    mut overrides: ResMut<GravityVolumeOverrides>,
+) {
+    if volumes.is_empty() {
+        if !overrides.0.is_empty() {
+            overrides.0.clear();
+        }
+        return;
+    }
+
+    let body_volumes = bodies_in_volumes(&volumes, &collider_parents, collisions.as_deref());
+
+    overrides.0.clear();
+    for (body, volumes) in body_volumes {
+        let volumes: Vec<&GravityVolume> = match *blending {
+            GravityVolumeBlending::Priority => {
+                let max_priority = volumes.iter().map(|volume| volume.priority).max();
+                volumes
+                    .into_iter()
+                    .filter(|volume| Some(volume.priority) == max_priority)
+                    .collect()
+            }
+            _ => volumes,
+        };
+        let sum = volumes
+            .iter()
+            .map(|volume| volume.acceleration(gravity.0))
+            .sum::<Vector>();
+        let acceleration = match *blending {
+            GravityVolumeBlending::Sum => sum,
+            _ => sum / volumes.len() as Scalar,
+        };
+        overrides.0.insert(body, acceleration);
+    }
+}
+
+/// The velocity of the local wind for bodies that are inside [wind zones](WindZone).
+#[derive(Resource, Default)]
+pub(crate) struct WindZoneVelocities(HashMap<Entity, Vector>);
+
+/// Finds the bodies that are inside [wind zones](WindZone) using the contacts of the current step
+/// and computes the velocity of their local wind.
+fn update_wind_zones(
+    zones: Query<&WindZone>,
+    positions: Query<&Position>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Option<Res<Collisions>>,
+    time: Res<Time<Physics>>,
+    mut velocities: ResMut<WindZoneVelocities>,
+) {
+    if zones.is_empty() {
+        if !velocities.0.is_empty() {
+            velocities.0.clear();
+        }
+        return;
+    }
+
+    let elapsed_secs = time.elapsed().as_secs_adjusted();
+    let body_zones = bodies_in_volumes(&zones, &collider_parents, collisions.as_deref());
+
+    velocities.0.clear();
+    for (body, zones) in body_zones {
+        let position = positions.get(body).map_or(Vector::ZERO, |pos| pos.0);
+        let velocity = zones
+            .iter()
+            .map(|zone| zone.velocity_at(position, elapsed_secs))
+            .sum::<Vector>();
+        velocities.0.insert(body, velocity);
+    }
+}
+
+/// Collects the volumes that each body is inside, based on the contacts between the colliders
+/// of the volumes and the colliders of the bodies during the current step.
+pub(crate) fn bodies_in_volumes<'a, D: ReadOnlyQueryData, F: QueryFilter>(
+    volumes: &'a Query<D, F>,
+    collider_parents: &Query<&ColliderParent>,
+    collisions: Option<&Collisions>,
+) -> HashMap<Entity, Vec<ROQueryItem<'a, D>>> {
+    let mut body_volumes = HashMap::<Entity, Vec<ROQueryItem<'a, D>>>::new();
+    for contacts in collisions
+        .iter()
+        .flat_map(|collisions| collisions.iter())
+        .filter(|contacts| contacts.during_current_frame)
+    {
+        for (volume_entity, other) in [
+            (contacts.entity1, contacts.entity2),
+            (contacts.entity2, contacts.entity1),
+        ] {
+            if let Ok(volume) = volumes.get(volume_entity) {
+                let body = collider_parents
+                    .get(other)
+                    .map_or(other, |parent| parent.get());
+                body_volumes.entry(body).or_default().push(volume);
+            }
+        }
+    }
+    body_volumes
+}
+
 type RotIntegrationComponents = (
     &'static RigidBody,
     &'static mut Rotation,
     &'static mut PreviousRotation,
     &'static mut AngularVelocity,
     Option<&'static AngularDamping>,
+    Option<&'static Drag>,
     &'static ExternalForce,
     &'static ExternalTorque,
+    Option<&'static ConstantTorque>,
     &'static Inertia,
     &'static InverseInertia,
     Option<&'static LockedAxes>,
+    Option<&'static TimeScale>,
 );
 
 /// Explicitly integrates the rotations and angular velocities of bodies taking only external torque into account.
 /// This acts as a prediction for the next rotations of the bodies.
 #[cfg(feature = "2d")]
-fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>, time: Res<Time>) {
+fn integrate_rot(
+    mut bodies: Query<RotIntegrationComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    time: Res<Time>,
+) {
     let delta_secs = time.delta_seconds_adjusted();
 
     for (
@@ -127,11 +384,14 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
         mut prev_rot,
         mut ang_vel,
         ang_damping,
+        drag,
         external_force,
         external_torque,
+        constant_torque,
         _inertia,
         inv_inertia,
         locked_axes,
+        time_scale,
     ) in &mut bodies
     {
         prev_rot.0 = *rot;
@@ -140,6 +400,8 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
             continue;
         }
 
+        let delta_secs = delta_secs * time_scale.map_or(1.0, |scale| scale.0);
+
         let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
 
         // Apply damping and external torque
@@ -154,10 +416,22 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
 
             let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.0);
 
+            // Apply angular drag, clamping it so that it never reverses the angular velocity
+            let angular_drag = drag.map_or(0.0, |drag| drag.angular);
+            if angular_drag != 0.0 && ang_vel.0 != 0.0 {
+                let delta = delta_secs * effective_inv_inertia * angular_drag * ang_vel.0;
+                if delta.abs() >= ang_vel.0.abs() {
+                    ang_vel.0 = 0.0;
+                } else {
+                    ang_vel.0 -= delta;
+                }
+            }
+
             // Apply external torque
+            let constant_torque = constant_torque.map_or(0.0, |torque| torque.0);
             let delta_ang_vel = delta_secs
                 * effective_inv_inertia
-                * (external_torque.torque() + external_force.torque());
+                * (external_torque.torque() + external_force.torque() + constant_torque);
             // avoid triggering bevy's change detection unnecessarily
             if delta_ang_vel != 0.0 {
                 ang_vel.0 += delta_ang_vel;
@@ -174,7 +448,10 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
 /// Explicitly integrates the rotations and angular velocities of bodies taking only external torque into account.
 /// This acts as a prediction for the next rotations of the bodies.
 #[cfg(feature = "3d")]
-fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>, time: Res<Time>) {
+fn integrate_rot(
+    mut bodies: Query<RotIntegrationComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    time: Res<Time>,
+) {
     let delta_secs = time.delta_seconds_adjusted();
 
     for (
@@ -183,11 +460,14 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
         mut prev_rot,
         mut ang_vel,
         ang_damping,
+        drag,
         external_force,
         external_torque,
+        constant_torque,
         inertia,
         inv_inertia,
         locked_axes,
+        time_scale,
     ) in &mut bodies
     {
         prev_rot.0 = *rot;
@@ -196,6 +476,8 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
             continue;
         }
 
+        let delta_secs = delta_secs * time_scale.map_or(1.0, |scale| scale.0);
+
         let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
 
         // Apply damping and external torque
@@ -211,10 +493,22 @@ fn integrate_rot(mut bodies: Query<RotIntegrationComponents, Without<Sleeping>>,
             let effective_inertia = locked_axes.apply_to_rotation(inertia.rotated(&rot).0);
             let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.rotated(&rot).0);
 
+            // Apply angular drag, clamping it so that it never reverses the angular velocity
+            let angular_drag = drag.map_or(0.0, |drag| drag.angular);
+            if angular_drag != 0.0 && ang_vel.0 != Vector::ZERO {
+                let delta = delta_secs * effective_inv_inertia * (angular_drag * ang_vel.0);
+                if delta.length_squared() >= ang_vel.0.length_squared() {
+                    ang_vel.0 = Vector::ZERO;
+                } else {
+                    ang_vel.0 -= delta;
+                }
+            }
+
             // Apply external torque
+            let constant_torque = constant_torque.map_or(Vector::ZERO, |torque| torque.0);
             let delta_ang_vel = delta_secs
                 * effective_inv_inertia
-                * ((external_torque.torque() + external_force.torque())
+                * ((external_torque.torque() + external_force.torque() + constant_torque)
                     - ang_vel.0.cross(effective_inertia * ang_vel.0));
             // avoid triggering bevy's change detection unnecessarily
             if delta_ang_vel != Vector::ZERO {
@@ -246,7 +540,113 @@ type ImpulseQueryComponents = (
     Option<&'static LockedAxes>,
 );
 
-fn apply_impulses(mut bodies: Query<ImpulseQueryComponents, Without<Sleeping>>) {
+/// Sets the velocities of kinematic bodies with a [`KinematicTarget`] so that they reach
+/// the target at the end of the physics step.
+#[allow(clippy::type_complexity)]
+pub(crate) fn track_kinematic_targets(
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &Position,
+            &Rotation,
+            &KinematicTarget,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+    if delta_secs <= 0.0 {
+        return;
+    }
+
+    for (rb, pos, rot, target, mut lin_vel, mut ang_vel) in &mut bodies {
+        if !rb.is_kinematic() {
+            continue;
+        }
+
+        // Avoid triggering bevy's change detection unnecessarily
+        let target_lin_vel = target.linear_velocity(pos.0, delta_secs);
+        if lin_vel.0 != target_lin_vel {
+            lin_vel.0 = target_lin_vel;
+        }
+        if let Some(target_ang_vel) = target.angular_velocity(rot, delta_secs) {
+            if ang_vel.0 != target_ang_vel {
+                ang_vel.0 = target_ang_vel;
+            }
+        }
+    }
+}
+
+/// Applies the changes in mass described by [`MassChange`] components and scales the inertia accordingly.
+///
+/// Depending on the [`MassChangeMode`], the velocities are either kept or scaled to conserve momentum.
+#[allow(clippy::type_complexity)]
+fn apply_mass_changes(
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &mut MassChange,
+            &mut Mass,
+            &mut InverseMass,
+            &mut Inertia,
+            &mut InverseInertia,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (
+        rb,
+        mut mass_change,
+        mut mass,
+        mut inv_mass,
+        mut inertia,
+        mut inv_inertia,
+        mut lin_vel,
+        mut ang_vel,
+    ) in &mut bodies
+    {
+        let delta_mass = mass_change.rate * delta_secs + mass_change.pending;
+        if mass_change.pending != 0.0 {
+            mass_change.pending = 0.0;
+        }
+
+        if !rb.is_dynamic() || delta_mass == 0.0 || mass.0 <= 0.0 || !mass.0.is_finite() {
+            continue;
+        }
+
+        // Don't increase the mass if it was already below the minimum.
+        let new_mass = (mass.0 + delta_mass)
+            .max(mass_change.min_mass.min(mass.0))
+            .max(Scalar::EPSILON);
+        if new_mass == mass.0 {
+            continue;
+        }
+
+        // Scale the inertia along with the mass to keep the mass distribution the same.
+        let scale = new_mass / mass.0;
+        mass.0 = new_mass;
+        inv_mass.0 = 1.0 / new_mass;
+        inertia.0 *= scale;
+        inv_inertia.0 *= 1.0 / scale;
+
+        if mass_change.mode == MassChangeMode::ConserveMomentum {
+            lin_vel.0 /= scale;
+            ang_vel.0 /= scale;
+        }
+    }
+}
+
+pub(crate) fn apply_impulses(
+    mut bodies: Query<ImpulseQueryComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+) {
     for (
         rb,
         impulse,
@@ -282,6 +682,624 @@ fn apply_impulses(mut bodies: Query<ImpulseQueryComponents, Without<Sleeping>>)
     }
 }
 
+/// A [`SystemParam`] for queueing impulses that are applied inside the substepping loop
+/// instead of once per physics step like [`ExternalImpulse`].
+///
+/// Each impulse is applied at the start of the given substep, using the position and velocity
+/// of the body at that point in the step. This makes rapid-fire impulses and explosions interact
+/// correctly with high [substep counts](SubstepCount), as each impulse affects the body only from
+/// its own substep onwards. Impulses for substeps past the last one are applied in the last substep.
+///
+/// Queued impulses wake up [sleeping](Sleeping) bodies, and impulses for entities that are not
+/// dynamic rigid bodies are discarded. The queue is stored in the [`QueuedImpulses`] resource.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn fire_machine_gun(target: Query<(Entity, &Position)>, mut physics_commands: PhysicsCommands) {
+///     for (entity, position) in &target {
+///         // Hit the target with a bullet in the first, fourth and seventh substep
+///         for substep in [0, 3, 6] {
+///             physics_commands.apply_impulse_at(
+///                 entity,
+///                 Vec3::X * 0.5,
+///                 position.0 + Vec3::Y * 0.1,
+///                 substep,
+///             );
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct PhysicsCommands<'w, 's> {
+    commands: Commands<'w, 's>,
+    queue: ResMut<'w, QueuedImpulses>,
+    explosions: ResMut<'w, QueuedExplosions>,
+}
+
+impl PhysicsCommands<'_, '_> {
+    /// Queues a world-space `impulse` to be applied at the center of mass of the given `entity`
+    /// at the start of the substep with the index `at_substep`.
+    pub fn apply_impulse(&mut self, entity: Entity, impulse: Vector, at_substep: u32) {
+        self.queue.push(QueuedImpulse {
+            entity,
+            impulse,
+            point: None,
+            angular_impulse: Torque::default(),
+            substep: at_substep,
+        });
+    }
+
+    /// Queues a world-space `impulse` to be applied at a world-space `point` on the given `entity`
+    /// at the start of the substep with the index `at_substep`. This will also cause an angular impulse
+    /// to be applied if the point is not at the center of mass of the body at that substep.
+    pub fn apply_impulse_at(
+        &mut self,
+        entity: Entity,
+        impulse: Vector,
+        point: Vector,
+        at_substep: u32,
+    ) {
+        self.queue.push(QueuedImpulse {
+            entity,
+            impulse,
+            point: Some(point),
+            angular_impulse: Torque::default(),
+            substep: at_substep,
+        });
+    }
+
+    /// Queues a world-space angular `impulse` to be applied to the given `entity`
+    /// at the start of the substep with the index `at_substep`.
+    pub fn apply_angular_impulse(&mut self, entity: Entity, impulse: Torque, at_substep: u32) {
+        self.queue.push(QueuedImpulse {
+            entity,
+            impulse: Vector::ZERO,
+            point: None,
+            angular_impulse: impulse,
+            substep: at_substep,
+        });
+    }
+
+    /// Queues a radial explosion at `center` that pushes dynamic bodies within `radius` away from the center
+    /// at the start of the next physics step.
+    ///
+    /// The `impulse` is applied at the point on each body that is closest to the center, so bodies also start
+    /// spinning, and it is scaled by the distance of that point according to the `falloff`.
+    /// The affected bodies and their [simulation islands](PhysicsIslands) are woken up.
+    ///
+    /// To ignore bodies that are behind static geometry or to filter the affected colliders,
+    /// use [`explode_with`](Self::explode_with).
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub fn explode(
+        &mut self,
+        center: Vector,
+        radius: Scalar,
+        impulse: Scalar,
+        falloff: ExplosionFalloff,
+    ) {
+        self.explode_with(Explosion::new(center, radius, impulse, falloff));
+    }
+
+    /// Queues the given [`Explosion`] at the start of the next physics step.
+    ///
+    /// See [`explode`](Self::explode) for more information.
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub fn explode_with(&mut self, explosion: Explosion) {
+        self.explosions.0.push(explosion);
+    }
+
+    /// Recomputes the mass properties of the given rigid body `entity` from all of its attached
+    /// [colliders](Collider) in the next frame, waking the body up if it is sleeping.
+    ///
+    /// The mass properties are already updated automatically when colliders are changed, so this
+    /// is only needed when a change was not detected. See [`RecomputeMassProperties`].
+    pub fn recompute_mass_properties(&mut self, entity: Entity) {
+        self.commands.add(move |world: &mut World| {
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.insert(RecomputeMassProperties);
+            }
+        });
+    }
+
+    /// Teleports the given rigid body `entity` to the given `position` and `rotation`, keeping its velocity.
+    ///
+    /// Unlike modifying the [`Position`] and [`Rotation`] directly, this also clears the [`AccumulatedTranslation`],
+    /// resets the stored impulses of the contacts of the body and the state of its [joints],
+    /// and wakes up the body and its [simulation island](PhysicsIslands). This way, stale solver state
+    /// doesn't pull the body back or cause a sudden spike in velocity.
+    ///
+    /// To also reset the velocity of the body, use [`teleport_with`](Self::teleport_with).
+    pub fn teleport(&mut self, entity: Entity, position: Vector, rotation: impl Into<Rotation>) {
+        self.teleport_with(entity, Teleport::new(position, rotation));
+    }
+
+    /// Teleports the given rigid body `entity` as described by the given [`Teleport`].
+    ///
+    /// See [`teleport`](Self::teleport) for more information.
+    pub fn teleport_with(&mut self, entity: Entity, teleport: Teleport) {
+        self.commands.add(move |world: &mut World| {
+            teleport_body(world, entity, teleport);
+        });
+    }
+}
+
+/// A teleport of a rigid body that can be performed using [`PhysicsCommands::teleport_with`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Teleport {
+    /// The new position of the body.
+    pub position: Vector,
+    /// The new rotation of the body.
+    pub rotation: Rotation,
+    /// If `true`, the [`LinearVelocity`] and [`AngularVelocity`] of the body are set to zero.
+    pub reset_velocity: bool,
+}
+
+impl Teleport {
+    /// Creates a new [`Teleport`] to the given `position` and `rotation` that keeps the velocity of the body.
+    pub fn new(position: Vector, rotation: impl Into<Rotation>) -> Self {
+        Self {
+            position,
+            rotation: rotation.into(),
+            reset_velocity: false,
+        }
+    }
+
+    /// Sets whether the [`LinearVelocity`] and [`AngularVelocity`] of the body are set to zero.
+    pub fn with_reset_velocity(self, reset_velocity: bool) -> Self {
+        Self {
+            reset_velocity,
+            ..self
+        }
+    }
+}
+
+/// Moves the body to the position and rotation of the given [`Teleport`] and resets the solver state
+/// associated with the body, like the impulses of its contacts and the state of its joints.
+fn teleport_body(world: &mut World, entity: Entity, teleport: Teleport) {
+    let Some(mut body) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    if let Some(mut position) = body.get_mut::<Position>() {
+        position.0 = teleport.position;
+    }
+    if let Some(mut rotation) = body.get_mut::<Rotation>() {
+        *rotation = teleport.rotation;
+    }
+    if let Some(mut previous_position) = body.get_mut::<PreviousPosition>() {
+        previous_position.0 = teleport.position;
+    }
+    if let Some(mut previous_rotation) = body.get_mut::<PreviousRotation>() {
+        previous_rotation.0 = teleport.rotation;
+    }
+    if let Some(mut translation) = body.get_mut::<AccumulatedTranslation>() {
+        translation.0 = Vector::ZERO;
+    }
+    if teleport.reset_velocity {
+        if let Some(mut lin_vel) = body.get_mut::<LinearVelocity>() {
+            *lin_vel = LinearVelocity::ZERO;
+        }
+        if let Some(mut ang_vel) = body.get_mut::<AngularVelocity>() {
+            *ang_vel = AngularVelocity::ZERO;
+        }
+    }
+
+    // The impulses of the contacts of the body were computed for its old position.
+    let colliders: Vec<Entity> = world
+        .query::<(Entity, &ColliderParent)>()
+        .iter(world)
+        .filter(|(_, parent)| parent.get() == entity)
+        .map(|(collider, _)| collider)
+        .collect();
+    if let Some(mut collisions) = world.get_resource_mut::<Collisions>() {
+        for contacts in collisions.iter_mut().filter(|contacts| {
+            colliders.contains(&contacts.entity1) || colliders.contains(&contacts.entity2)
+        }) {
+            contacts.total_normal_impulse = 0.0;
+            contacts.total_tangent_impulse = 0.0;
+            for contact in contacts
+                .manifolds
+                .iter_mut()
+                .flat_map(|manifold| manifold.contacts.iter_mut())
+            {
+                contact.normal_impulse = 0.0;
+                contact.tangent_impulse = 0.0;
+            }
+        }
+    }
+
+    reset_joints::<FixedJoint>(world, entity);
+    reset_joints::<DistanceJoint>(world, entity);
+    reset_joints::<PrismaticJoint>(world, entity);
+    reset_joints::<RevoluteJoint>(world, entity);
+    reset_joints::<SphericalJoint>(world, entity);
+
+    // Wake up the body and the bodies in its island
+    let island = world
+        .get_resource::<PhysicsIslands>()
+        .and_then(|islands| islands.island(entity))
+        .map_or(vec![entity], |island| island.to_vec());
+    for entity in island {
+        if let Some(mut body) = world.get_entity_mut(entity) {
+            if body.contains::<Sleeping>() {
+                body.remove::<Sleeping>().insert(TimeSleeping(0.0));
+            }
+        }
+    }
+}
+
+/// Resets the state of the joints of type `T` that are attached to the given `entity`.
+fn reset_joints<T: Joint + XpbdConstraint<2>>(world: &mut World, entity: Entity) {
+    for mut joint in world.query::<&mut T>().iter_mut(world) {
+        if joint.entities().contains(&entity) {
+            joint.reset();
+        }
+    }
+}
+
+/// A radial explosion that can be queued using [`PhysicsCommands::explode_with`].
+#[derive(Clone)]
+pub struct Explosion {
+    /// The center of the explosion in world space.
+    pub center: Vector,
+    /// The maximum distance from the center at which bodies are affected.
+    pub radius: Scalar,
+    /// The magnitude of the impulse at the center of the explosion.
+    pub impulse: Scalar,
+    /// How the impulse decreases with the distance from the center.
+    pub falloff: ExplosionFalloff,
+    /// If `true`, bodies are not affected if the line from the center to the body is blocked by
+    /// a static body or a collider without a rigid body. False by default.
+    pub occlusion: bool,
+    /// A filter that determines which colliders are affected by the explosion.
+    pub filter: SpatialQueryFilter,
+}
+
+impl Explosion {
+    /// Creates a new [`Explosion`] without occlusion.
+    pub fn new(center: Vector, radius: Scalar, impulse: Scalar, falloff: ExplosionFalloff) -> Self {
+        Self {
+            center,
+            radius,
+            impulse,
+            falloff,
+            occlusion: false,
+            filter: SpatialQueryFilter::default(),
+        }
+    }
+
+    /// Makes static geometry block the explosion.
+    pub fn with_occlusion(mut self) -> Self {
+        self.occlusion = true;
+        self
+    }
+
+    /// Sets the filter that determines which colliders are affected by the explosion.
+    pub fn with_filter(mut self, filter: SpatialQueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Returns the magnitude of the impulse at the given distance from the center,
+    /// or zero outside of the radius.
+    pub fn impulse_at_distance(&self, distance: Scalar) -> Scalar {
+        if distance > self.radius {
+            return 0.0;
+        }
+        let remaining = if self.radius > 0.0 {
+            1.0 - distance / self.radius
+        } else {
+            1.0
+        };
+        match self.falloff {
+            ExplosionFalloff::Constant => self.impulse,
+            ExplosionFalloff::Linear => self.impulse * remaining,
+            ExplosionFalloff::Quadratic => self.impulse * remaining * remaining,
+        }
+    }
+}
+
+/// How the impulse of an [`Explosion`] decreases with the distance from its center.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExplosionFalloff {
+    /// The impulse is the same everywhere within the radius.
+    Constant,
+    /// The impulse decreases linearly to zero at the radius.
+    #[default]
+    Linear,
+    /// The impulse decreases quadratically to zero at the radius, so it drops off quickly near the center.
+    Quadratic,
+}
+
+/// The explosions queued using [`PhysicsCommands`] that haven't been applied yet.
+#[derive(Resource, Clone, Default)]
+pub struct QueuedExplosions(pub Vec<Explosion>);
+
+/// Converts the explosions queued using [`PhysicsCommands`] into [queued impulses](QueuedImpulses)
+/// for the first substep, and wakes up the affected bodies and their islands.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn apply_explosions(
+    mut commands: Commands,
+    mut explosions: ResMut<QueuedExplosions>,
+    mut impulses: ResMut<QueuedImpulses>,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    colliders: Query<(&Collider, &Position, &Rotation, Option<&ColliderParent>)>,
+    bodies: Query<(&RigidBody, Has<Sleeping>)>,
+    islands: Option<Res<PhysicsIslands>>,
+) {
+    if explosions.0.is_empty() {
+        return;
+    }
+    let Some(pipeline) = pipeline else {
+        explosions.0.clear();
+        return;
+    };
+
+    let body_of = |collider: Entity| {
+        colliders
+            .get(collider)
+            .ok()
+            .and_then(|(.., parent)| parent)
+            .map_or(collider, |parent| parent.get())
+    };
+    let is_static = |collider: Entity| {
+        bodies
+            .get(body_of(collider))
+            .map_or(true, |(rb, _)| rb.is_static())
+    };
+
+    for explosion in explosions.0.drain(..) {
+        #[cfg(feature = "2d")]
+        let ball = Collider::circle(explosion.radius);
+        #[cfg(feature = "3d")]
+        let ball = Collider::sphere(explosion.radius);
+
+        // Find the closest point on each dynamic body, so that bodies with several colliders
+        // are only pushed once
+        let mut closest_points = HashMap::<Entity, (Scalar, Vector, Entity)>::new();
+        for collider_entity in pipeline.shape_intersections(
+            &ball,
+            explosion.center,
+            default(),
+            explosion.filter.clone(),
+        ) {
+            let Ok((collider, position, rotation, _)) = colliders.get(collider_entity) else {
+                continue;
+            };
+            let body = body_of(collider_entity);
+            if !bodies.get(body).is_ok_and(|(rb, _)| rb.is_dynamic()) {
+                continue;
+            }
+            let (point, _) = collider.project_point(*position, *rotation, explosion.center, true);
+            let distance = point.distance(explosion.center);
+            if distance > explosion.radius
+                || closest_points
+                    .get(&body)
+                    .is_some_and(|(closest, ..)| *closest <= distance)
+            {
+                continue;
+            }
+            closest_points.insert(body, (distance, point, collider_entity));
+        }
+
+        for (body, (distance, point, collider_entity)) in closest_points {
+            // Skip bodies that are behind static geometry
+            if explosion.occlusion && distance > Scalar::EPSILON {
+                let direction = Dir::new(((point - explosion.center) / distance).f32());
+                let blocked = direction.is_ok_and(|direction| {
+                    pipeline
+                        .cast_ray_predicate(
+                            explosion.center,
+                            direction,
+                            distance,
+                            true,
+                            SpatialQueryFilter::default(),
+                            &|entity| entity != collider_entity && is_static(entity),
+                        )
+                        .is_some_and(|hit| hit.time_of_impact < distance - 1e-4)
+                });
+                if blocked {
+                    continue;
+                }
+            }
+
+            // Push the body away from the center, or upwards if the center is inside the body
+            let direction = if distance > Scalar::EPSILON {
+                (point - explosion.center) / distance
+            } else {
+                Vector::Y
+            };
+            impulses.push(QueuedImpulse {
+                entity: body,
+                impulse: direction * explosion.impulse_at_distance(distance),
+                point: Some(point),
+                angular_impulse: Torque::default(),
+                substep: 0,
+            });
+
+            // Wake up the body and the bodies in its island
+            let island = islands.as_ref().and_then(|islands| islands.island(body));
+            for entity in island.unwrap_or(&[body]) {
+                if bodies.get(*entity).is_ok_and(|(_, sleeping)| sleeping) {
+                    commands
+                        .entity(*entity)
+                        .remove::<Sleeping>()
+                        .insert(TimeSleeping(0.0));
+                }
+            }
+        }
+    }
+}
+
+/// The impulses queued using [`PhysicsCommands`] that haven't been applied yet.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct QueuedImpulses {
+    impulses: Vec<QueuedImpulse>,
+    /// The index of the next substep in the current physics step.
+    substep: u32,
+}
+
+impl QueuedImpulses {
+    /// Adds an impulse to the queue.
+    pub fn push(&mut self, impulse: QueuedImpulse) {
+        self.impulses.push(impulse);
+    }
+
+    /// Returns an iterator over the queued impulses.
+    pub fn iter(&self) -> impl Iterator<Item = &QueuedImpulse> {
+        self.impulses.iter()
+    }
+
+    /// Returns the number of queued impulses.
+    pub fn len(&self) -> usize {
+        self.impulses.len()
+    }
+
+    /// Returns `true` if no impulses are queued.
+    pub fn is_empty(&self) -> bool {
+        self.impulses.is_empty()
+    }
+
+    /// Removes all queued impulses without applying them.
+    pub fn clear(&mut self) {
+        self.impulses.clear();
+    }
+
+    /// Removes the queued impulses for which `f` returns `false` without applying them.
+    pub fn retain(&mut self, f: impl FnMut(&QueuedImpulse) -> bool) {
+        self.impulses.retain(f);
+    }
+}
+
+/// An impulse queued using [`PhysicsCommands`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueuedImpulse {
+    /// The entity of the rigid body that the impulse is applied to.
+    pub entity: Entity,
+    /// The world-space linear impulse.
+    pub impulse: Vector,
+    /// The world-space point that the linear impulse is applied at,
+    /// or `None` if it is applied at the center of mass.
+    pub point: Option<Vector>,
+    /// The world-space angular impulse.
+    pub angular_impulse: Torque,
+    /// The index of the substep that the impulse is applied at.
+    pub substep: u32,
+}
+
+/// Resets the substep counter of the [`QueuedImpulses`] and wakes up sleeping bodies that have impulses queued.
+fn prepare_queued_impulses(
+    mut commands: Commands,
+    mut queue: ResMut<QueuedImpulses>,
+    sleeping: Query<(), With<Sleeping>>,
+) {
+    queue.substep = 0;
+
+    for queued in queue.iter() {
+        if sleeping.contains(queued.entity) {
+            commands
+                .entity(queued.entity)
+                .remove::<Sleeping>()
+                .insert(TimeSleeping(0.0));
+        }
+    }
+}
+
+type QueuedImpulseComponents = (
+    &'static RigidBody,
+    &'static Position,
+    &'static Rotation,
+    &'static CenterOfMass,
+    &'static mut LinearVelocity,
+    &'static mut AngularVelocity,
+    &'static InverseMass,
+    &'static InverseInertia,
+    Option<&'static LockedAxes>,
+);
+
+/// Applies the impulses queued using [`PhysicsCommands`] for the current substep.
+pub(crate) fn apply_queued_impulses(
+    mut queue: ResMut<QueuedImpulses>,
+    mut bodies: Query<QueuedImpulseComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    substep_count: Res<SubstepCount>,
+) {
+    let substep = queue.substep;
+    queue.substep += 1;
+
+    if queue.is_empty() {
+        return;
+    }
+
+    let last_substep = substep_count.0.saturating_sub(1);
+
+    queue.impulses.retain(|queued| {
+        if queued.substep.min(last_substep) > substep {
+            return true;
+        }
+
+        let Ok((
+            rb,
+            position,
+            rotation,
+            center_of_mass,
+            mut lin_vel,
+            mut ang_vel,
+            inv_mass,
+            inv_inertia,
+            locked_axes,
+        )) = bodies.get_mut(queued.entity)
+        else {
+            return false;
+        };
+
+        if !rb.is_dynamic() {
+            return false;
+        }
+
+        let mut angular_impulse = queued.angular_impulse;
+        if let Some(point) = queued.point {
+            let r = point - position.0 - rotation.rotate(center_of_mass.0);
+            #[cfg(feature = "2d")]
+            {
+                angular_impulse += r.perp_dot(queued.impulse);
+            }
+            #[cfg(feature = "3d")]
+            {
+                angular_impulse += r.cross(queued.impulse);
+            }
+        }
+
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+
+        let effective_inv_mass = locked_axes.apply_to_vec(Vector::splat(inv_mass.0));
+        let effective_inv_inertia = locked_axes.apply_to_rotation(inv_inertia.rotated(rotation).0);
+
+        lin_vel.0 += queued.impulse * effective_inv_mass;
+        ang_vel.0 += effective_inv_inertia * angular_impulse;
+
+        false
+    });
+}
+
 type ForceComponents = (
     &'static mut ExternalForce,
     &'static mut ExternalTorque,
@@ -0,0 +1,221 @@
+//! Applies pairwise attraction and repulsion between magnets.
+//!
+//! See [`MagnetismPlugin`] and [`Magnet`].
+
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+
+/// Applies attractive and repulsive forces between dynamic [rigid bodies](RigidBody) with a [`Magnet`].
+///
+/// The magnets near each magnet are found using the acceleration structures of the [`SpatialQueryPipeline`],
+/// so the cost grows with the number of magnets that are actually within range of each other instead of
+/// the square of the number of magnets. Only magnets on bodies with [colliders](Collider) are found.
+///
+/// The forces are computed once per physics step, before [`PhysicsStepSet::Substeps`].
+pub struct MagnetismPlugin;
+
+impl Plugin for MagnetismPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Magnet>()
+            .register_type::<MagnetFalloff>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                apply_magnet_forces
+                    .after(PhysicsStepSet::BroadPhase)
+                    .after(crate::plugins::integrator::track_kinematic_targets)
+                    .before(crate::plugins::buoyancy::apply_buoyancy)
+                    .before(crate::plugins::integrator::apply_impulses),
+            );
+    }
+}
+
+/// A magnet that attracts or repels other magnets within its [`radius`](Self::radius).
+///
+/// The force between two magnets is the product of their [strengths](Self::strength) scaled by the [falloff](MagnetFalloff)
+/// of the magnet exerting the force. Magnets whose strengths have the same sign attract each other, and magnets
+/// whose strengths have opposite signs repel each other. For example, giving all magnets a positive strength
+/// makes them group together, while a magnet with a negative strength pushes them away.
+///
+/// The force is applied at the center of mass of the body, pointing along the line between the centers of mass
+/// of the magnets. Magnets only affect each other if their [`layers`](Self::layers) interact.
+///
+/// The magnet should be added to a dynamic [rigid body](RigidBody) with a [collider](Collider).
+/// Static and kinematic magnets still affect other magnets, but they are not moved themselves.
+/// Sleeping bodies are not affected.
+///
+/// Requires the [`MagnetismPlugin`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // Two magnets that attract each other
+///     for x in [-2.0, 2.0] {
+///         commands.spawn((
+///             RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "             Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "             Collider::sphere(0.5),")]
+///             TransformBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+///             Magnet::new(10.0, 5.0),
+///         ));
+///     }
+///
+///     // A static magnet that pushes the other magnets away
+///     commands.spawn((
+///         RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "         Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "         Collider::sphere(0.5),")]
+///         Magnet::new(-5.0, 10.0).with_falloff(MagnetFalloff::InverseSquare),
+///     ));
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Magnet {
+    /// The strength of the magnet. Magnets with strengths of the same sign attract each other,
+    /// and magnets with strengths of opposite signs repel each other.
+    pub strength: Scalar,
+    /// The maximum distance between the centers of mass at which the magnet affects other magnets.
+    pub radius: Scalar,
+    /// How the force of the magnet changes with distance.
+    pub falloff: MagnetFalloff,
+    /// The layers of the magnet. Magnets only affect each other if their layers [interact](CollisionLayers::interacts_with).
+    pub layers: CollisionLayers,
+}
+
+/// How the force of a [`Magnet`] changes with the distance from its center of mass.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum MagnetFalloff {
+    /// The force is the same at all distances within the radius.
+    Constant,
+    /// The force decreases linearly to zero at the radius.
+    #[default]
+    Linear,
+    /// The force is inversely proportional to the square of the distance.
+    /// The force can get very large when magnets are close to each other.
+    InverseSquare,
+}
+
+impl Magnet {
+    /// Creates a magnet with the given `strength` and `radius`.
+    pub fn new(strength: Scalar, radius: Scalar) -> Self {
+        Self {
+            strength,
+            radius,
+            falloff: MagnetFalloff::default(),
+            layers: CollisionLayers::default(),
+        }
+    }
+
+    /// Sets how the force of the magnet changes with distance.
+    pub fn with_falloff(mut self, falloff: MagnetFalloff) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    /// Sets the layers of the magnet. Magnets only affect each other if their layers interact.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Computes the magnitude of the force that the magnet exerts on another magnet with the given
+    /// `strength` at the given `distance`. Positive values attract and negative values repel.
+    pub fn force_magnitude(&self, strength: Scalar, distance: Scalar) -> Scalar {
+        if distance > self.radius || distance <= Scalar::EPSILON {
+            return 0.0;
+        }
+        let scale = match self.falloff {
+            MagnetFalloff::Constant => 1.0,
+            MagnetFalloff::Linear => 1.0 - distance / self.radius,
+            MagnetFalloff::InverseSquare => 1.0 / (distance * distance),
+        };
+        self.strength * strength * scale
+    }
+}
+
+type MagnetBodyComponents = (
+    &'static RigidBody,
+    &'static mut LinearVelocity,
+    &'static InverseMass,
+    Option<&'static LockedAxes>,
+);
+
+/// Applies the forces between [magnets](Magnet) that are within range of each other.
+#[allow(clippy::type_complexity)]
+pub(crate) fn apply_magnet_forces(
+    magnets: Query<(Entity, &Magnet, &Position, &Rotation, &CenterOfMass)>,
+    collider_parents: Query<&ColliderParent>,
+    mut bodies: Query<MagnetBodyComponents, (Without<Sleeping>, Without<RigidBodyDisabled>)>,
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    time: Res<Time>,
+) {
+    let Some(pipeline) = pipeline.filter(|_| !magnets.is_empty()) else {
+        return;
+    };
+
+    let global_center_of_mass =
+        |pos: &Position, rot: &Rotation, com: &CenterOfMass| pos.0 + rot.rotate(com.0);
+
+    let mut forces = HashMap::<Entity, Vector>::new();
+    let mut nearby_magnets = vec![];
+
+    for (entity, magnet, pos, rot, com) in &magnets {
+        if magnet.strength == 0.0 || magnet.radius <= 0.0 {
+            continue;
+        }
+        let center = global_center_of_mass(pos, rot, com);
+
+        // Find the bodies with colliders near the magnet, so that other magnets
+        // don't need to be checked
+        nearby_magnets.clear();
+        let aabb = ColliderAabb::new(center, Vector::splat(magnet.radius));
+        pipeline.aabb_intersections_with_aabb_callback(aabb, |collider| {
+            let body = collider_parents
+                .get(collider)
+                .map_or(collider, |parent| parent.get());
+            if body != entity && !nearby_magnets.contains(&body) {
+                nearby_magnets.push(body);
+            }
+            true
+        });
+
+        for &other_entity in nearby_magnets.iter() {
+            let Ok((_, other_magnet, other_pos, other_rot, other_com)) = magnets.get(other_entity)
+            else {
+                continue;
+            };
+            if !magnet.layers.interacts_with(other_magnet.layers) {
+                continue;
+            }
+
+            let offset = center - global_center_of_mass(other_pos, other_rot, other_com);
+            let distance = offset.length();
+            let magnitude = magnet.force_magnitude(other_magnet.strength, distance);
+            if magnitude != 0.0 {
+                *forces.entry(other_entity).or_default() += offset / distance * magnitude;
+            }
+        }
+    }
+
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (entity, force) in forces {
+        let Ok((rb, mut lin_vel, inv_mass, locked_axes)) = bodies.get_mut(entity) else {
+            continue;
+        };
+        if !rb.is_dynamic() {
+            continue;
+        }
+        let locked_axes = locked_axes.map_or(LockedAxes::default(), |locked_axes| *locked_axes);
+        lin_vel.0 += locked_axes.apply_to_vec(delta_secs * force * inv_mass.0);
+    }
+}
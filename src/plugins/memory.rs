@@ -0,0 +1,256 @@
+//! Reports the memory used by the physics engine's data structures in [`PhysicsMemoryUsage`].
+//!
+//! See [`PhysicsMemoryPlugin`].
+
+use std::{fmt, mem::size_of};
+
+use crate::{
+    plugins::collision::broad_phase::{AabbIntervals, StaticAabbTree},
+    prelude::*,
+};
+use bevy::prelude::*;
+
+/// Computes a [`PhysicsMemoryUsage`] report of the memory used by the physics engine after each physics step.
+///
+/// The report is only computed if the [`PhysicsMemoryUsage`] resource exists, so this plugin has no cost
+/// unless the resource is inserted.
+///
+/// The system runs after [`PhysicsStepSet::Sleeping`] and before [`PhysicsStepSet::SpatialQuery`].
+pub struct PhysicsMemoryPlugin;
+
+impl Plugin for PhysicsMemoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PhysicsMemoryUsage>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                update_memory_usage
+                    .after(PhysicsStepSet::Sleeping)
+                    .before(PhysicsStepSet::SpatialQuery)
+                    .run_if(resource_exists::<PhysicsMemoryUsage>),
+            );
+    }
+}
+
+/// A resource containing an estimate of the heap memory used by the physics engine's data structures in bytes,
+/// updated after each physics step by the [`PhysicsMemoryPlugin`] if the resource exists.
+///
+/// This can be used to find out which part of the engine is growing in large or procedurally generated worlds
+/// without having to use a heap profiler. The values are estimates based on the capacities of the buffers
+/// and the sizes of the stored types, and they don't include the memory used by components stored in the ECS,
+/// other than joints.
+///
+/// The [`spatial_query`](Self::spatial_query) structures are updated later in the physics step,
+/// so their usage is reported as it was at the end of the previous step.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsExtrasPlugins::default(),
+///         ))
+///         // Enable memory usage reporting
+///         .insert_resource(PhysicsMemoryUsage::default())
+///         .add_systems(Update, print_memory_usage)
+///         .run();
+/// }
+///
+/// fn print_memory_usage(memory_usage: Res<PhysicsMemoryUsage>) {
+///     println!("{memory_usage}");
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct PhysicsMemoryUsage {
+    /// The memory used by the broad phase, including [`BroadCollisionPairs`]
+    /// and the acceleration structures for moving and static colliders.
+    pub broad_phase: usize,
+    /// The memory used by the contacts stored in [`Collisions`].
+    pub contacts: usize,
+    /// The memory used by [joints](crate::constraints::joints).
+    pub joints: usize,
+    /// The memory used by the [`SpatialQueryPipeline`], not including the shapes of the colliders.
+    pub spatial_query: usize,
+    /// The memory used by the unique shapes of [colliders](Collider). Shapes that are shared
+    /// by several colliders are only counted once.
+    pub shapes: usize,
+    /// The number of unique shapes of [colliders](Collider).
+    pub shape_count: usize,
+}
+
+impl PhysicsMemoryUsage {
+    /// Returns the total memory used by the physics engine's data structures in bytes.
+    pub fn total(&self) -> usize {
+        self.broad_phase + self.contacts + self.joints + self.spatial_query + self.shapes
+    }
+}
+
+impl fmt::Display for PhysicsMemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kib = |bytes: usize| bytes as f64 / 1024.0;
+        write!(
+            f,
+            "physics memory: {:.1} KiB (broad phase {:.1} KiB, contacts {:.1} KiB, joints {:.1} KiB, \
+             spatial query {:.1} KiB, {} shapes {:.1} KiB)",
+            kib(self.total()),
+            kib(self.broad_phase),
+            kib(self.contacts),
+            kib(self.joints),
+            kib(self.spatial_query),
+            self.shape_count,
+            kib(self.shapes),
+        )
+    }
+}
+
+/// Returns the memory used by the allocated capacity of a vector.
+fn vec_memory<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * size_of::<T>()
+}
+
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+fn update_memory_usage(
+    broad_collision_pairs: Res<BroadCollisionPairs>,
+    intervals: Option<Res<AabbIntervals>>,
+    static_tree: Option<Res<StaticAabbTree>>,
+    collisions: Option<Res<Collisions>>,
+    joints: (
+        Query<(), With<FixedJoint>>,
+        Query<(), With<DistanceJoint>>,
+        Query<(), With<PrismaticJoint>>,
+        Query<(), With<RevoluteJoint>>,
+        Query<(), With<SphericalJoint>>,
+    ),
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pipeline: Option<Res<SpatialQueryPipeline>>,
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    colliders: Query<&Collider>,
+    mut memory_usage: ResMut<PhysicsMemoryUsage>,
+) {
+    let mut usage = PhysicsMemoryUsage {
+        broad_phase: vec_memory(&broad_collision_pairs.0)
+            + intervals.map_or(0, |intervals| vec_memory(&intervals.0))
+            + static_tree.map_or(0, |tree| tree.memory_usage()),
+        ..default()
+    };
+
+    if let Some(collisions) = collisions {
+        let collisions = collisions.get_internal();
+        usage.contacts = collisions.capacity() * size_of::<((Entity, Entity), Contacts)>()
+            + collisions
+                .values()
+                .map(|contacts| {
+                    vec_memory(&contacts.manifolds)
+                        + contacts
+                            .manifolds
+                            .iter()
+                            .map(|manifold| vec_memory(&manifold.contacts))
+                            .sum::<usize>()
+                })
+                .sum::<usize>();
+    }
+
+    let (fixed, distance, prismatic, revolute, spherical) = joints;
+    usage.joints = fixed.iter().len() * size_of::<FixedJoint>()
+        + distance.iter().len() * size_of::<DistanceJoint>()
+        + prismatic.iter().len() * size_of::<PrismaticJoint>()
+        + revolute.iter().len() * size_of::<RevoluteJoint>()
+        + spherical.iter().len() * size_of::<SphericalJoint>();
+
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    {
+        usage.spatial_query = pipeline.map_or(0, |pipeline| pipeline.memory_usage());
+
+        let mut shapes = bevy::utils::HashSet::new();
+        for collider in &colliders {
+            for shape in [collider.shape(), collider.shape_scaled()] {
+                if shapes.insert(std::sync::Arc::as_ptr(&shape.0) as *const () as usize) {
+                    usage.shapes += shape_memory(shape.0.as_ref());
+                }
+            }
+        }
+        usage.shape_count = shapes.len();
+    }
+
+    memory_usage.set_if_neq(usage);
+}
+
+/// Estimates the memory used by a shape, including the data it stores on the heap.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn shape_memory(shape: &dyn parry::shape::Shape) -> usize {
+    use parry::{partitioning::Qbvh, shape::TypedShape};
+    use std::mem::size_of_val;
+
+    let qbvh_memory =
+        |qbvh: &Qbvh<u32>| size_of_val(qbvh.raw_nodes()) + size_of_val(qbvh.raw_proxies());
+
+    let heap_memory = match shape.as_typed_shape() {
+        TypedShape::TriMesh(trimesh) => {
+            size_of_val(trimesh.vertices())
+                + size_of_val(trimesh.indices())
+                + qbvh_memory(trimesh.qbvh())
+        }
+        TypedShape::Polyline(polyline) => {
+            // The BVH of the polyline isn't publicly accessible, so it's left out of the estimate.
+            size_of_val(polyline.vertices()) + size_of_val(polyline.indices())
+        }
+        TypedShape::HeightField(heightfield) => heightfield.heights().len() * size_of::<Scalar>(),
+        TypedShape::Compound(compound) => {
+            size_of_val(compound.shapes())
+                + compound
+                    .shapes()
+                    .iter()
+                    .map(|(_, shape)| shape_memory(shape.0.as_ref()))
+                    .sum::<usize>()
+                + qbvh_memory(compound.qbvh())
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(polygon) => {
+            size_of_val(polygon.points()) + size_of_val(polygon.normals())
+        }
+        #[cfg(feature = "2d")]
+        TypedShape::RoundConvexPolygon(polygon) => {
+            size_of_val(polygon.inner_shape.points()) + size_of_val(polygon.inner_shape.normals())
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(polyhedron) => {
+            size_of_val(polyhedron.points())
+                + size_of_val(polyhedron.faces())
+                + size_of_val(polyhedron.edges())
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::RoundConvexPolyhedron(polyhedron) => {
+            size_of_val(polyhedron.inner_shape.points())
+                + size_of_val(polyhedron.inner_shape.faces())
+                + size_of_val(polyhedron.inner_shape.edges())
+        }
+        _ => 0,
+    };
+
+    size_of_val(shape) + heap_memory
+}
@@ -20,30 +20,191 @@
 //! - [`PhysicsSchedule`] and [`PhysicsStepSet`]
 //! - [`SubstepSchedule`] and [`SubstepSet`]
 
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod ballistics;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod buoyancy;
+pub mod checksum;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod cloth;
 pub mod collision;
+pub mod constraint_graph;
+pub mod correction;
 #[cfg(feature = "debug-plugin")]
 pub mod debug;
+#[cfg(all(
+    feature = "deformable-mesh",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod deformable_mesh;
+pub mod diagnostics;
+#[cfg(all(
+    feature = "2d",
+    feature = "fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod fluid;
+pub mod force_field;
+#[cfg(all(
+    feature = "2d",
+    feature = "granular",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod granular;
+pub mod headless;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod hover;
+#[cfg(feature = "egui")]
+pub mod inspector;
 pub mod integrator;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod magnetism;
+pub mod memory;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod particles;
+#[cfg(feature = "physics-material")]
+pub mod physics_material;
+#[cfg(feature = "serialize")]
+pub mod physics_scene;
+pub mod prediction;
 pub mod prepare;
+pub mod replay;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod rope;
 pub mod setup;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod shape_matching;
 pub mod sleeping;
+pub mod snapshot;
+pub mod snapshot_delta;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub mod soft_body;
 pub mod solver;
 pub mod spatial_query;
+pub mod stepper;
 pub mod sync;
 
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use ballistics::BallisticsPlugin;
 use bevy::utils::intern::Interned;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use buoyancy::BuoyancyPlugin;
+pub use checksum::PhysicsChecksumPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use cloth::ClothPlugin;
+#[cfg(feature = "gpu-broad-phase")]
+pub use collision::gpu_broad_phase::GpuBroadPhasePlugin;
 pub use collision::{
     broad_phase::BroadPhasePlugin, collider_backend::*, contact_reporting::ContactReportingPlugin,
     narrow_phase::NarrowPhasePlugin,
 };
+pub use correction::StateCorrectionPlugin;
 #[cfg(feature = "debug-plugin")]
 pub use debug::PhysicsDebugPlugin;
+#[cfg(all(
+    feature = "deformable-mesh",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use deformable_mesh::DeformableMeshPlugin;
+pub use diagnostics::PhysicsDiagnosticsPlugin;
+#[cfg(all(
+    feature = "2d",
+    feature = "fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use fluid::FluidPlugin;
+pub use force_field::ForceFieldPlugin;
+#[cfg(all(
+    feature = "2d",
+    feature = "granular",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use granular::GranularPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use hover::HoverPlugin;
+#[cfg(feature = "egui")]
+pub use inspector::PhysicsInspectorPlugin;
 pub use integrator::IntegratorPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use magnetism::MagnetismPlugin;
+pub use memory::PhysicsMemoryPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use particles::ParticlePlugin;
+#[cfg(feature = "physics-material")]
+pub use physics_material::PhysicsMaterialPlugin;
 pub use prepare::PreparePlugin;
+pub use replay::PhysicsReplayPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use rope::RopePlugin;
 pub use setup::PhysicsSetupPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use shape_matching::ShapeMatchingPlugin;
 pub use sleeping::SleepingPlugin;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+pub use soft_body::SoftBodyPlugin;
 pub use solver::SolverPlugin;
 pub use spatial_query::SpatialQueryPlugin;
+pub use stepper::PhysicsStepperPlugin;
 pub use sync::SyncPlugin;
 
 #[allow(unused_imports)]
@@ -68,10 +229,14 @@ use bevy::prelude::*;
 /// (dynamic [friction](Friction) and [restitution](Restitution)).
 /// - [`SleepingPlugin`]: Controls when bodies should be deactivated and marked as [`Sleeping`] to improve performance.
 /// - [`SpatialQueryPlugin`]: Handles spatial queries like [raycasting](RayCaster) and shapecasting.
+/// - `PhysicsMaterialPlugin`: Applies shared `PhysicsMaterial` assets to colliders
+/// (only with `physics-material` feature enabled).
 /// - [`SyncPlugin`]: Keeps [`Position`] and [`Rotation`] in sync with `Transform`.
 /// - `PhysicsDebugPlugin`: Renders physics objects and events like [AABBs](ColliderAabb) and [contacts](Collision)
 /// for debugging purposes (only with `debug-plugin` feature enabled).
 ///
+/// Optional features like ropes, cloth, force fields and replays are in the separate [`PhysicsExtrasPlugins`] group.
+///
 /// Refer to the documentation of the plugins for more information about their responsibilities and implementations.
 ///
 /// You can also find more information regarding the engine's general plugin architecture [here](plugins).
@@ -167,6 +332,7 @@ use bevy::prelude::*;
 /// [here](https://github.com/Jondolf/bevy_xpbd/blob/main/crates/bevy_xpbd_3d/examples/custom_broad_phase.rs).
 pub struct PhysicsPlugins {
     schedule: Interned<dyn ScheduleLabel>,
+    headless: bool,
 }
 
 impl PhysicsPlugins {
@@ -176,6 +342,22 @@ impl PhysicsPlugins {
     pub fn new(schedule: impl ScheduleLabel) -> Self {
         Self {
             schedule: schedule.intern(),
+            headless: false,
+        }
+    }
+
+    /// Creates a minimal [`PhysicsPlugins`] plugin group for headless apps, like dedicated servers
+    /// or simulations in tests, using `PostUpdate` for running the [`PhysicsSchedule`].
+    ///
+    /// The group contains all of the default plugins except for the [`SyncPlugin`], so `Transform`
+    /// is not kept in sync with [`Position`] and [`Rotation`], and the results of the simulation
+    /// should be read from the physics components directly.
+    ///
+    /// See the [`headless`] module for how to step the simulation manually.
+    pub fn headless() -> Self {
+        Self {
+            headless: true,
+            ..default()
         }
     }
 }
@@ -200,13 +382,145 @@ impl PluginGroup for PhysicsPlugins {
             .add(ColliderBackendPlugin::<Collider>::new(self.schedule))
             .add(NarrowPhasePlugin::<Collider>::default());
 
-        builder
+        let builder = builder
             .add(BroadPhasePlugin)
             .add(ContactReportingPlugin)
             .add(IntegratorPlugin)
             .add(SolverPlugin)
             .add(SleepingPlugin)
-            .add(SpatialQueryPlugin::new(self.schedule))
-            .add(SyncPlugin::new(self.schedule))
+            .add(SpatialQueryPlugin::new(self.schedule));
+
+        #[cfg(feature = "physics-material")]
+        let builder = builder.add(PhysicsMaterialPlugin::new(self.schedule));
+
+        if self.headless {
+            builder
+        } else {
+            builder.add(SyncPlugin::new(self.schedule))
+        }
+    }
+}
+
+/// A plugin group containing Bevy XPBD's optional plugins for effects and simulation types
+/// that aren't needed by every app. The group should be added after the [`PhysicsPlugins`],
+/// using the same schedule.
+///
+/// The following plugins will be added:
+///
+/// - [`ForceFieldPlugin`]: Applies user-defined [force fields](ForceField) to bodies in every substep.
+/// - [`StateCorrectionPlugin`]: Blends bodies towards remotely received states using [`StateCorrection`].
+/// - [`PhysicsChecksumPlugin`]: Computes a [`PhysicsChecksum`] of the simulation state for detecting desyncs
+/// (only if the resource exists).
+/// - [`PhysicsMemoryPlugin`]: Reports the memory used by the engine's data structures in [`PhysicsMemoryUsage`]
+/// (only if the resource exists).
+/// - [`PhysicsReplayPlugin`]: Records the inputs of the simulation and replays them
+/// (only if a [`PhysicsRecorder`] or [`PhysicsReplay`] exists).
+/// - `BuoyancyPlugin`: Applies buoyancy and drag to bodies submerged in a [`FluidVolume`]
+/// (only with the default collider).
+/// - `MagnetismPlugin`: Applies attraction and repulsion between bodies with a [`Magnet`]
+/// (only with the default collider).
+/// - `HoverPlugin`: Keeps bodies with a [`Hover`] at a target height above the ground
+/// (only with the default collider).
+/// - `BallisticsPlugin`: Sweeps the motion of fast [projectiles](Projectile) to prevent tunneling
+/// (only with the default collider).
+/// - `RopePlugin`: Simulates [ropes](Rope) as chains of particles attached to bodies
+/// (only with the default collider).
+/// - `ClothPlugin`: Simulates [cloth](Cloth) as grids of particles and writes the results to meshes
+/// (only with the default collider).
+/// - `SoftBodyPlugin`: Simulates volumetric [soft bodies](SoftBody) as triangle or tetrahedral meshes
+/// (only with the default collider).
+/// - `ShapeMatchingPlugin`: Simulates squishy [shape matching bodies](ShapeMatchingBody) that return to their rest shape
+/// (only with the default collider).
+/// - `DeformableMeshPlugin`: Writes the particles of ropes, cloth and soft bodies to their meshes for rendering
+/// (added by their plugins with the `deformable-mesh` feature).
+/// - `ParticlePlugin`: Simulates lightweight standalone [particles](Particle) that collide with colliders
+/// (only with the default collider).
+/// - `FluidPlugin`: Simulates 2D liquids as position-based fluids coupled with rigid bodies
+/// (only in 2D with the default collider and the `fluid` feature).
+/// - `GranularPlugin`: Simulates 2D granular materials like sand with friction-dominated contacts between grains
+/// (only in 2D with the default collider and the `granular` feature).
+///
+/// Like with [`PhysicsPlugins`], individual plugins can be disabled:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsExtrasPlugins::default()
+///                 .build()
+///                 .disable::<PhysicsReplayPlugin>(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct PhysicsExtrasPlugins {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsExtrasPlugins {
+    /// Creates a [`PhysicsExtrasPlugins`] plugin group using the given schedule for running the [`PhysicsSchedule`].
+    ///
+    /// The schedule should be the same as the one used for the [`PhysicsPlugins`].
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsExtrasPlugins {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl PluginGroup for PhysicsExtrasPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let builder = PluginGroupBuilder::start::<Self>()
+            .add(ForceFieldPlugin)
+            .add(StateCorrectionPlugin)
+            .add(PhysicsChecksumPlugin)
+            .add(PhysicsMemoryPlugin)
+            .add(PhysicsReplayPlugin::new(self.schedule));
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        let builder = builder
+            .add(BuoyancyPlugin)
+            .add(MagnetismPlugin)
+            .add(HoverPlugin)
+            .add(BallisticsPlugin)
+            .add(RopePlugin::new(self.schedule))
+            .add(ClothPlugin::new(self.schedule))
+            .add(SoftBodyPlugin::new(self.schedule))
+            .add(ShapeMatchingPlugin::new(self.schedule))
+            .add(ParticlePlugin::new(self.schedule));
+
+        #[cfg(all(
+            feature = "2d",
+            feature = "fluid",
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        let builder = builder.add(FluidPlugin);
+
+        #[cfg(all(
+            feature = "2d",
+            feature = "granular",
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        let builder = builder.add(GranularPlugin);
+
+        builder
     }
 }
@@ -0,0 +1,705 @@
+//! Lightweight particles that collide with colliders, and the particle constraints shared
+//! by [ropes](Rope), [cloth](Cloth) and [soft bodies](SoftBody).
+//!
+//! Particles are points with a mass and a radius but no rotation. They are integrated and constrained
+//! separately from [rigid bodies](RigidBody), but they can be [attached](ParticleAttachment) to bodies
+//! and collide with [colliders](Collider).
+//!
+//! See [`ParticlePlugin`] and [`Particle`].
+
+use crate::{
+    plugins::sync::{self, SyncSet},
+    prelude::*,
+};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::intern::Interned};
+
+/// Simulates standalone [particles](Particle), which are much cheaper than [rigid bodies](RigidBody)
+/// and useful for things like debris, sparks and fluids.
+///
+/// The particles are simulated in the [`SubstepSchedule`]:
+///
+/// 1. The particles are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The particles are pushed out of [colliders](Collider) with friction after the [joints], ropes, cloth
+/// and soft bodies in [`ParticleSet::Particles`].
+/// 3. The velocities of the particles are updated from their change in position in [`SubstepSet::UpdateVelocities`].
+///
+/// The `Transform` translations of particles are updated to match their positions in [`PhysicsSet::Sync`].
+pub struct ParticlePlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl ParticlePlugin {
+    /// Creates a [`ParticlePlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for ParticlePlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Particle>()
+            .register_type::<ParticleRadius>()
+            .register_type::<ParticleAttachment>();
+
+        app.add_systems(
+            self.schedule,
+            sync_particle_transforms
+                .in_set(SyncSet::PositionToTransform)
+                .after(sync::position_to_transform),
+        );
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_particle_bodies.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(collide_particle_bodies.in_set(ParticleSet::Particles));
+
+        substeps.add_systems(update_particle_body_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// System sets for solving particle constraints and collisions in the [`SubstepSchedule`].
+///
+/// The sets run in order after [`SubstepSet::SolveConstraints`], so that particles that are attached
+/// to bodies see the positions of the bodies after the [joints] have been solved,
+/// and before [`SubstepSet::SolveUserConstraints`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParticleSet {
+    /// Solves the constraints and collisions of [ropes](Rope).
+    Ropes,
+    /// Solves the constraints and collisions of [cloth](Cloth).
+    Cloth,
+    /// Solves the constraints and collisions of [soft bodies](SoftBody).
+    SoftBodies,
+    /// Solves the constraints and collisions of [shape matching bodies](ShapeMatchingBody).
+    ShapeMatching,
+    /// Solves the collisions of standalone [particles](Particle).
+    Particles,
+    /// Solves the density constraints of [fluids](Fluid) and couples them with bodies.
+    #[cfg(feature = "fluid")]
+    Fluids,
+    /// Solves the contacts of [granular materials](Granular) and couples them with bodies.
+    #[cfg(feature = "granular")]
+    Granular,
+}
+
+impl ParticleSet {
+    /// Configures the order of the particle sets in the given schedule.
+    ///
+    /// This is called by every plugin that adds systems to the sets, so the sets are ordered
+    /// regardless of which of the plugins are added.
+    pub(crate) fn configure(schedule: &mut Schedule) {
+        schedule.configure_sets(
+            (
+                ParticleSet::Ropes,
+                ParticleSet::Cloth,
+                ParticleSet::SoftBodies,
+                ParticleSet::ShapeMatching,
+                ParticleSet::Particles,
+                #[cfg(feature = "fluid")]
+                ParticleSet::Fluids,
+                #[cfg(feature = "granular")]
+                ParticleSet::Granular,
+            )
+                .chain()
+                .after(SubstepSet::SolveConstraints)
+                .before(SubstepSet::SolveUserConstraints),
+        );
+    }
+}
+
+/// A point mass without rotation.
+///
+/// Particles are used as the building blocks of [ropes](Rope), [cloth](Cloth) and [soft bodies](SoftBody),
+/// but they can also be added to entities as standalone particles simulated by the [`ParticlePlugin`].
+/// Standalone particles collide with [colliders](Collider) using their [`ParticleRadius`] and push dynamic
+/// [rigid bodies](RigidBody) back based on their masses, but particles don't collide with each other.
+///
+/// Standalone particles are affected by [`GravityScale`], and their [`CollisionLayers`] and the
+/// [dynamic coefficient](Friction::dynamic_coefficient) of their [`Friction`] are used for collisions.
+/// The position of the particle is authoritative, and the `Transform` translation of the entity is updated to match it.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn spawn_debris(mut commands: Commands) {
+///     for i in 0..100 {
+///         let direction = Vector::X * (i as Scalar * 0.1).cos() + Vector::Y * (i as Scalar * 0.1).sin();
+///         commands.spawn((
+///             SpatialBundle::default(),
+///             Particle::new(Vector::Y * 2.0, 0.1).with_velocity(direction * 5.0),
+///             ParticleRadius(0.05),
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Particle {
+    /// The position of the particle in world space.
+    pub position: Vector,
+    /// The position of the particle at the start of the substep.
+    pub previous_position: Vector,
+    /// The velocity of the particle.
+    pub velocity: Vector,
+    /// The inverse mass of the particle. A value of `0.0` pins the particle in place.
+    pub inverse_mass: Scalar,
+}
+
+impl Particle {
+    /// Creates a resting particle at the given `position` with the given `mass`.
+    pub fn new(position: Vector, mass: Scalar) -> Self {
+        Self {
+            position,
+            previous_position: position,
+            velocity: Vector::ZERO,
+            inverse_mass: if mass > 0.0 { 1.0 / mass } else { 0.0 },
+        }
+    }
+
+    /// Sets the velocity of the particle.
+    pub fn with_velocity(mut self, velocity: Vector) -> Self {
+        self.velocity = velocity;
+        self
+    }
+}
+
+/// The radius of a standalone [`Particle`] used for collisions. The default is `0.05`.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Deref, DerefMut)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ParticleRadius(pub Scalar);
+
+impl Default for ParticleRadius {
+    fn default() -> Self {
+        Self(0.05)
+    }
+}
+
+/// Attaches a [`Particle`] of a [`Rope`], [`Cloth`] or [`SoftBody`] to a [rigid body](RigidBody).
+///
+/// The particle and the body are pulled towards each other proportionally to their inverse masses,
+/// so a heavy rope can drag a light body around. [Static](RigidBody::Static), [kinematic](RigidBody::Kinematic),
+/// [sleeping](Sleeping) and [disabled](RigidBodyDisabled) bodies aren't moved by the particle.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParticleAttachment {
+    /// The body that the particle is attached to.
+    pub entity: Entity,
+    /// The attachment point on the body.
+    pub local_anchor: Vector,
+    /// The compliance of the attachment, the inverse of stiffness. Has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted on the body by the particle.
+    pub force: Vector,
+}
+
+impl ParticleAttachment {
+    /// Creates a rigid attachment to the center of the given body.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            local_anchor: Vector::ZERO,
+            compliance: 0.0,
+            force: Vector::ZERO,
+        }
+    }
+
+    /// Sets the attachment point on the body.
+    pub fn with_local_anchor(mut self, anchor: Vector) -> Self {
+        self.local_anchor = anchor;
+        self
+    }
+
+    /// Sets the compliance of the attachment, the inverse of stiffness.
+    pub fn with_compliance(mut self, compliance: Scalar) -> Self {
+        self.compliance = compliance;
+        self
+    }
+}
+
+/// The bodies that particles can be attached to.
+///
+/// The bodies are accessed through the [`SolverBodies`], since the particles are solved
+/// after the bodies have been gathered for the substep.
+pub(crate) type ParticleBodies<'w> = SolverBodiesMut<'w>;
+
+/// The colliders that particles collide with.
+pub(crate) type ParticleColliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Collider,
+        &'static ColliderAabb,
+        &'static Position,
+        &'static Rotation,
+        Option<&'static ColliderParent>,
+        Option<&'static ColliderTransform>,
+        Option<&'static CollisionLayers>,
+    ),
+    (Without<Sensor>, Without<ColliderDisabled>),
+>;
+
+/// The bodies and colliders that particles collide with. The colliders are read first,
+/// and the bodies are then moved by the contacts.
+#[derive(SystemParam)]
+pub(crate) struct ParticleContacts<'w, 's> {
+    bodies: ParticleBodies<'w>,
+    colliders: ParticleColliders<'w, 's>,
+}
+
+/// A collider near a group of particles.
+struct ParticleContactCollider {
+    collider: Collider,
+    /// The body that the collider is attached to, or the collider itself if it has no body.
+    body: Entity,
+    /// The transform of the collider relative to its body, if it has one.
+    transform: Option<ColliderTransform>,
+    position: Vector,
+    rotation: Rotation,
+    /// The AABB of the collider expanded by the particle radius.
+    aabb: ColliderAabb,
+}
+
+/// Moves particles by their velocities and gravity.
+pub(crate) fn integrate_particles(
+    particles: &mut [Particle],
+    gravity: Vector,
+    damping: Scalar,
+    dt: Scalar,
+) {
+    let damping = 1.0 / (1.0 + dt * damping);
+
+    for particle in particles.iter_mut() {
+        particle.previous_position = particle.position;
+
+        if particle.inverse_mass <= Scalar::EPSILON {
+            continue;
+        }
+
+        particle.velocity = (particle.velocity + gravity * dt) * damping;
+        particle.position += particle.velocity * dt;
+    }
+}
+
+/// Updates the velocities of particles based on their change in position.
+pub(crate) fn update_particle_velocities(particles: &mut [Particle], dt: Scalar) {
+    if dt <= Scalar::EPSILON {
+        return;
+    }
+
+    for particle in particles.iter_mut() {
+        particle.velocity = (particle.position - particle.previous_position) / dt;
+    }
+}
+
+/// Finds the particles within the given `radius` of each particle using a spatial hash grid
+/// with cells of the same size, and writes their indices to `neighbors`.
+#[cfg(all(feature = "2d", any(feature = "fluid", feature = "granular")))]
+pub(crate) fn find_particle_neighbors(
+    particles: &[Particle],
+    radius: Scalar,
+    neighbors: &mut Vec<Vec<usize>>,
+) {
+    use bevy::{math::IVec2, utils::HashMap};
+
+    let cell = |position: Vector| (position / radius).floor().as_ivec2();
+
+    let mut grid: HashMap<IVec2, Vec<usize>> = HashMap::default();
+    for (i, particle) in particles.iter().enumerate() {
+        grid.entry(cell(particle.position)).or_default().push(i);
+    }
+
+    neighbors.resize_with(particles.len(), Vec::new);
+    for (i, particle) in particles.iter().enumerate() {
+        let neighbors = &mut neighbors[i];
+        neighbors.clear();
+        let center = cell(particle.position);
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let Some(indices) = grid.get(&(center + IVec2::new(x, y))) else {
+                    continue;
+                };
+                neighbors.extend(indices.iter().copied().filter(|&j| {
+                    j != i
+                        && particle.position.distance_squared(particles[j].position)
+                            < radius * radius
+                }));
+            }
+        }
+    }
+}
+
+/// Returns the rest length of a distance constraint between two particles after plastic deformation,
+/// based on the current distance between the particles.
+pub(crate) fn deform_particle_distance(
+    particles: &[Particle],
+    [i1, i2]: [usize; 2],
+    rest_length: Scalar,
+    plasticity: &Plasticity,
+    dt: Scalar,
+) -> Scalar {
+    let length = particles[i1].position.distance(particles[i2].position);
+    plasticity.deform(rest_length, length, dt)
+}
+
+/// Keeps two particles at the given `rest_length` from each other.
+/// If `only_compression` is true, the particles are only pushed apart.
+pub(crate) fn solve_particle_distance(
+    particles: &mut [Particle],
+    [i1, i2]: [usize; 2],
+    rest_length: Scalar,
+    only_compression: bool,
+    compliance: Scalar,
+    dt: Scalar,
+) {
+    let (p1, w1) = (particles[i1].position, particles[i1].inverse_mass);
+    let (p2, w2) = (particles[i2].position, particles[i2].inverse_mass);
+
+    let delta = p2 - p1;
+    let distance = delta.length();
+    let c = distance - rest_length;
+
+    if distance <= Scalar::EPSILON || (only_compression && c >= 0.0) {
+        return;
+    }
+
+    let w_sum = w1 + w2 + compliance / dt.powi(2);
+    if w_sum <= Scalar::EPSILON {
+        return;
+    }
+
+    let dir = delta / distance;
+    let delta_lagrange = -c / w_sum;
+
+    particles[i1].position -= dir * delta_lagrange * w1;
+    particles[i2].position += dir * delta_lagrange * w2;
+}
+
+/// Pulls a particle and the body it is attached to towards each other,
+/// and stores the force exerted on the body in the `attachment`.
+pub(crate) fn solve_attachment(
+    particle: &mut Particle,
+    attachment: &mut ParticleAttachment,
+    bodies: &mut ParticleBodies,
+    dt: Scalar,
+) {
+    attachment.force = Vector::ZERO;
+
+    let Some((mut body, state)) = bodies.get_mut(attachment.entity) else {
+        return;
+    };
+
+    let world_r = body.rotation.rotate(attachment.local_anchor);
+    let delta = particle.position - (body.current_position() + world_r);
+    let distance = delta.length();
+
+    if distance <= Scalar::EPSILON {
+        return;
+    }
+
+    let dir = delta / distance;
+    let moves_body = body.rb.is_dynamic() && !state.is_sleeping && !state.is_disabled;
+    let w_body = if moves_body {
+        generalized_inverse_mass(&body, world_r, dir)
+    } else {
+        0.0
+    };
+
+    let w_sum = particle.inverse_mass + w_body + attachment.compliance / dt.powi(2);
+    if w_sum <= Scalar::EPSILON {
+        return;
+    }
+
+    let delta_lagrange = -distance / w_sum;
+    particle.position += dir * delta_lagrange * particle.inverse_mass;
+
+    // The body is pulled in the opposite direction.
+    let p = -delta_lagrange * dir;
+
+    if moves_body {
+        apply_body_correction(&mut body, world_r, p);
+    }
+
+    attachment.force = p / dt.powi(2);
+}
+
+/// Computes the generalized inverse mass of a body when applying a positional correction
+/// at point `r` along the vector `n`.
+#[cfg(feature = "2d")]
+fn generalized_inverse_mass(body: &RigidBodyQueryItem, r: Vector, n: Vector) -> Scalar {
+    body.inverse_mass.0 + body.effective_world_inv_inertia() * r.perp_dot(n).powi(2)
+}
+
+/// Computes the generalized inverse mass of a body when applying a positional correction
+/// at point `r` along the vector `n`.
+#[cfg(feature = "3d")]
+fn generalized_inverse_mass(body: &RigidBodyQueryItem, r: Vector, n: Vector) -> Scalar {
+    let r_cross_n = r.cross(n);
+    body.inverse_mass.0 + r_cross_n.dot(body.effective_world_inv_inertia() * r_cross_n)
+}
+
+/// Moves and rotates a body by a positional correction `p` applied at point `r`.
+fn apply_body_correction(body: &mut RigidBodyQueryItem, r: Vector, p: Vector) {
+    let inv_mass = body.effective_inv_mass();
+    body.accumulated_translation.0 += p * inv_mass;
+    apply_delta_rotation(body, r, p);
+}
+
+/// Rotates a body by a positional correction `p` applied at point `r`.
+#[cfg(feature = "2d")]
+fn apply_delta_rotation(body: &mut RigidBodyQueryItem, r: Vector, p: Vector) {
+    let inv_inertia = body.effective_world_inv_inertia();
+    *body.rotation += Rotation::from_radians(inv_inertia * r.perp_dot(p));
+}
+
+/// Rotates a body by a positional correction `p` applied at point `r`.
+#[cfg(feature = "3d")]
+fn apply_delta_rotation(body: &mut RigidBodyQueryItem, r: Vector, p: Vector) {
+    let inv_inertia = body.effective_world_inv_inertia();
+    let delta =
+        Quaternion::from_vec4(0.5 * (inv_inertia * r.cross(p)).extend(0.0)) * body.rotation.0;
+    body.rotation.0 = (body.rotation.0 + delta).normalize();
+}
+
+/// Computes the position and rotation of a collider attached to a body with the given `transform`,
+/// taking into account the movement of the body during the current substep.
+fn collider_pose(body: &RigidBodyQueryItem, transform: &ColliderTransform) -> (Vector, Rotation) {
+    let position = body.current_position() + body.rotation.rotate(transform.translation);
+    #[cfg(feature = "2d")]
+    let rotation = *body.rotation + transform.rotation;
+    #[cfg(feature = "3d")]
+    let rotation = (body.rotation.0 * transform.rotation.0).normalize().into();
+    (position, rotation)
+}
+
+/// Pushes particles with the given `radius` out of colliders and applies `friction`.
+///
+/// The contacts are solved like the contacts between rigid bodies, so dynamic bodies are pushed back
+/// by the particles based on their masses. This lets bodies rest on ropes, nets and cloth,
+/// and lets particles push bodies around. Sleeping and disabled bodies are treated as static.
+///
+/// Colliders attached to the `ignored` bodies and colliders whose layers don't interact
+/// with the given `layers` are skipped.
+pub(crate) fn collide_particles(
+    particles: &mut [Particle],
+    radius: Scalar,
+    friction: Scalar,
+    layers: CollisionLayers,
+    ignored: &[Entity],
+    contacts: &mut ParticleContacts,
+) {
+    let mut min = Vector::splat(Scalar::MAX);
+    let mut max = Vector::splat(Scalar::MIN);
+    for particle in particles.iter() {
+        min = min.min(particle.position);
+        max = max.max(particle.position);
+    }
+    let particles_aabb = ColliderAabb::from_min_max(min - radius, max + radius);
+    let friction = friction.clamp(0.0, 1.0);
+
+    // Collect the colliders near the particles before moving any bodies.
+    let colliders: Vec<ParticleContactCollider> = contacts
+        .colliders
+        .iter()
+        .filter_map(
+            |(entity, collider, aabb, position, rotation, parent, transform, collider_layers)| {
+                let body = parent.map_or(entity, |parent| parent.get());
+                if ignored.contains(&body)
+                    || !aabb.intersects(&particles_aabb)
+                    || !layers.interacts_with(collider_layers.copied().unwrap_or_default())
+                {
+                    return None;
+                }
+                Some(ParticleContactCollider {
+                    collider: collider.clone(),
+                    body,
+                    transform: parent.and(transform.copied()),
+                    position: position.0,
+                    rotation: *rotation,
+                    aabb: ColliderAabb::from_min_max(aabb.min - radius, aabb.max + radius),
+                })
+            },
+        )
+        .collect();
+
+    let bodies = &mut contacts.bodies;
+
+    for contact_collider in colliders.iter() {
+        // Only dynamic bodies that are awake are moved by the particles.
+        let mut body = bodies
+            .get_mut(contact_collider.body)
+            .filter(|(body, state)| {
+                body.rb.is_dynamic() && !state.is_sleeping && !state.is_disabled
+            })
+            .map(|(body, _)| body);
+
+        for particle in particles.iter_mut() {
+            if particle.inverse_mass <= Scalar::EPSILON
+                || !contact_collider
+                    .aabb
+                    .intersects(&ColliderAabb::from_min_max(
+                        particle.position,
+                        particle.position,
+                    ))
+            {
+                continue;
+            }
+
+            let (position, rotation) = match (&body, &contact_collider.transform) {
+                (Some(body), Some(transform)) => collider_pose(body, transform),
+                _ => (contact_collider.position, contact_collider.rotation),
+            };
+
+            let (projection, is_inside) = contact_collider.collider.project_point(
+                position,
+                rotation,
+                particle.position,
+                false,
+            );
+            let offset = particle.position - projection;
+            let distance = offset.length();
+
+            let (normal, penetration) = if is_inside {
+                (-offset.normalize_or_zero(), distance + radius)
+            } else if distance < radius && distance > Scalar::EPSILON {
+                (offset / distance, radius - distance)
+            } else {
+                continue;
+            };
+
+            // The contact point relative to the center of mass of the body
+            let r = body.as_ref().map(|body| {
+                projection - body.current_position() - body.rotation.rotate(body.center_of_mass.0)
+            });
+
+            // Push the particle and the body apart
+            let w_body = match (&body, r) {
+                (Some(body), Some(r)) => generalized_inverse_mass(body, r, normal),
+                _ => 0.0,
+            };
+            let delta_lagrange = penetration / (particle.inverse_mass + w_body);
+            particle.position += normal * delta_lagrange * particle.inverse_mass;
+
+            if let (Some(body), Some(r)) = (&mut body, r) {
+                apply_body_correction(body, r, -normal * delta_lagrange);
+            }
+
+            // Remove part of the tangential movement relative to the body during the substep.
+            let mut displacement = particle.position - particle.previous_position;
+            if let Some(body) = &body {
+                let local_point = body
+                    .rotation
+                    .inverse()
+                    .rotate(projection - body.current_position());
+                let previous_point =
+                    body.previous_position.0 + body.previous_rotation.rotate(local_point);
+                displacement -= projection - previous_point;
+            }
+            let tangential = displacement - normal * displacement.dot(normal);
+            let tangential_length = tangential.length();
+
+            if tangential_length <= Scalar::EPSILON {
+                continue;
+            }
+
+            let tangent = tangential / tangential_length;
+            let w_body = match (&body, r) {
+                (Some(body), Some(r)) => generalized_inverse_mass(body, r, tangent),
+                _ => 0.0,
+            };
+            let delta_lagrange = friction * tangential_length / (particle.inverse_mass + w_body);
+            particle.position -= tangent * delta_lagrange * particle.inverse_mass;
+
+            if let (Some(body), Some(r)) = (&mut body, r) {
+                apply_body_correction(body, r, tangent * delta_lagrange);
+            }
+        }
+    }
+}
+
+/// Moves standalone particles by their velocities and gravity.
+fn integrate_particle_bodies(
+    mut particles: Query<(&mut Particle, Option<&GravityScale>)>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (mut particle, gravity_scale) in &mut particles {
+        let gravity = gravity.0 * gravity_scale.map_or(1.0, |scale| scale.0);
+        integrate_particles(
+            std::slice::from_mut(&mut *particle),
+            gravity,
+            0.0,
+            delta_secs,
+        );
+    }
+}
+
+/// Pushes standalone particles out of colliders and applies friction.
+#[allow(clippy::type_complexity)]
+pub(crate) fn collide_particle_bodies(
+    mut particles: Query<(
+        Entity,
+        &mut Particle,
+        Option<&ParticleRadius>,
+        Option<&Friction>,
+        Option<&CollisionLayers>,
+    )>,
+    mut contacts: ParticleContacts,
+) {
+    for (entity, mut particle, radius, friction, layers) in &mut particles {
+        collide_particles(
+            std::slice::from_mut(&mut *particle),
+            radius.copied().unwrap_or_default().0,
+            friction.map_or(0.3, |friction| friction.dynamic_coefficient),
+            layers.copied().unwrap_or_default(),
+            &[entity],
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of standalone particles based on their change in position.
+fn update_particle_body_velocities(mut particles: Query<&mut Particle>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut particle in &mut particles {
+        update_particle_velocities(std::slice::from_mut(&mut *particle), delta_secs);
+    }
+}
+
+/// Updates the `Transform` translations of standalone particles to match their positions.
+fn sync_particle_transforms(mut particles: Query<(&Particle, &mut Transform), Changed<Particle>>) {
+    for (particle, mut transform) in &mut particles {
+        #[cfg(feature = "2d")]
+        {
+            transform.translation = particle.position.f32().extend(transform.translation.z);
+        }
+        #[cfg(feature = "3d")]
+        {
+            transform.translation = particle.position.f32();
+        }
+    }
+}
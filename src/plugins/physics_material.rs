@@ -0,0 +1,168 @@
+//! Shared physics materials stored as assets.
+//!
+//! See [`PhysicsMaterialPlugin`] and [`PhysicsMaterial`].
+
+use crate::{prelude::*, prepare::PrepareSet};
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*, utils::intern::Interned};
+
+/// Applies the properties of [`PhysicsMaterial`] assets to the entities that reference them
+/// with a `Handle<PhysicsMaterial>`.
+///
+/// The properties are applied when the handle of an entity is added or changed, and to all entities
+/// whenever the `Assets<PhysicsMaterial>` are modified, so editing a material affects every collider using it.
+///
+/// This plugin is only available with the `physics-material` feature. It registers the [`PhysicsMaterial`] asset
+/// with the `AssetServer` if the `AssetPlugin` has been added before it, and otherwise only initializes
+/// the `Assets<PhysicsMaterial>` resource.
+pub struct PhysicsMaterialPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsMaterialPlugin {
+    /// Creates a [`PhysicsMaterialPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsMaterialPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for PhysicsMaterialPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world.contains_resource::<AssetServer>() {
+            app.init_asset::<PhysicsMaterial>();
+        } else {
+            app.init_resource::<Assets<PhysicsMaterial>>();
+        }
+
+        app.register_type::<PhysicsMaterial>()
+            .register_type::<Handle<PhysicsMaterial>>();
+
+        app.add_systems(
+            self.schedule,
+            apply_physics_materials.in_set(PrepareSet::PreInit),
+        );
+    }
+}
+
+/// A material that describes the surface properties of [colliders](Collider), shared by all entities
+/// that reference it with a `Handle<PhysicsMaterial>`.
+///
+/// Instead of duplicating [`Friction`], [`Restitution`] and other components on hundreds of colliders,
+/// they can share a single material. The properties are copied into the components of each entity by
+/// the [`PhysicsMaterialPlugin`], and editing the material updates all of them.
+///
+/// The properties that are `None` are left unchanged, so they can still be configured per entity.
+///
+/// Requires the `physics-material` feature.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands, mut materials: ResMut<Assets<PhysicsMaterial>>) {
+///     let ice = materials.add(
+///         PhysicsMaterial::new(Friction::new(0.02), Restitution::new(0.1))
+///             .with_density(900.0)
+///             .with_tag("ice"),
+///     );
+///
+///     // Many colliders can share the same material
+///     for i in 0..100 {
+///         commands.spawn((
+///             RigidBody::Static,
+#[cfg_attr(feature = "2d", doc = "             Collider::rectangle(1.0, 1.0),")]
+#[cfg_attr(feature = "3d", doc = "             Collider::cuboid(1.0, 1.0, 1.0),")]
+///             TransformBundle::from_transform(Transform::from_xyz(i as f32, 0.0, 0.0)),
+///             ice.clone(),
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Asset, Reflect, Clone, Debug, Default, PartialEq)]
+pub struct PhysicsMaterial {
+    /// The [friction](Friction) of the material, including its combine rule.
+    pub friction: Friction,
+    /// The [restitution](Restitution) of the material, including its combine rule.
+    pub restitution: Restitution,
+    /// The [density](ColliderDensity) of the material, or `None` to keep the density of the collider.
+    pub density: Option<Scalar>,
+    /// The [surface velocity](SurfaceVelocity) of the material, or `None` to keep the surface velocity of the collider.
+    pub surface_velocity: Option<SurfaceVelocity>,
+    /// Arbitrary tags for the material, like the names of sounds or particle effects to use
+    /// for impacts with colliders using the material. The tags are not used by the physics engine.
+    pub tags: Vec<String>,
+}
+
+impl PhysicsMaterial {
+    /// Creates a new material with the given friction and restitution.
+    pub fn new(friction: impl Into<Friction>, restitution: impl Into<Restitution>) -> Self {
+        Self {
+            friction: friction.into(),
+            restitution: restitution.into(),
+            ..default()
+        }
+    }
+
+    /// Sets the [density](ColliderDensity) of the material.
+    pub fn with_density(mut self, density: Scalar) -> Self {
+        self.density = Some(density);
+        self
+    }
+
+    /// Sets the [surface velocity](SurfaceVelocity) of the material.
+    pub fn with_surface_velocity(mut self, surface_velocity: SurfaceVelocity) -> Self {
+        self.surface_velocity = Some(surface_velocity);
+        self
+    }
+
+    /// Adds a tag to the material.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Returns `true` if the material has the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+/// Copies the properties of [`PhysicsMaterial`]s into the components of the entities using them.
+fn apply_physics_materials(
+    mut commands: Commands,
+    materials: Res<Assets<PhysicsMaterial>>,
+    query: Query<(Entity, Ref<Handle<PhysicsMaterial>>)>,
+) {
+    // Apply materials to all entities if any of the materials has been added or modified
+    let apply_all = materials.is_changed();
+
+    for (entity, handle) in &query {
+        if !apply_all && !handle.is_changed() {
+            continue;
+        }
+        let Some(material) = materials.get(&*handle) else {
+            continue;
+        };
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert((material.friction, material.restitution));
+        if let Some(density) = material.density {
+            entity_commands.insert(ColliderDensity(density));
+        }
+        if let Some(surface_velocity) = material.surface_velocity {
+            entity_commands.insert(surface_velocity);
+        }
+    }
+}
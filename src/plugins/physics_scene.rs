@@ -0,0 +1,394 @@
+//! Saving and loading entire physics worlds in a versioned file format that is independent of Bevy scenes.
+//!
+//! See [`PhysicsScene`].
+
+use std::fmt;
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        world::EntityWorldMut,
+    },
+    prelude::*,
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+
+/// A physics world that can be saved to and loaded from a file, independently of Bevy scenes.
+///
+/// A physics scene contains:
+///
+/// - The [`Gravity`] of the world
+/// - Each entity with a [rigid body](RigidBody), [collider](Collider) or [joint](joints), along with its
+/// position, velocity, [collision layers](CollisionLayers), materials ([`Friction`], [`Restitution`],
+/// [`ColliderDensity`]) and other physics configuration
+/// - The hierarchy and local [`Transform`] of the entities, so that child colliders keep their offsets
+///
+/// Mass properties are not stored directly, as they are computed from the colliders when the scene is spawned.
+///
+/// Scenes can be stored in a human-readable [RON](https://github.com/ron-rs/ron) format using
+/// [`PhysicsScene::to_ron`] or in a compact binary format using [`PhysicsScene::to_bytes`].
+/// Both formats store the [version](PhysicsScene::VERSION) of the format, and loading a scene
+/// with an unsupported version returns an error.
+///
+/// This is useful for things like level baking pipelines and headless servers that
+/// load levels without the rest of the game's assets.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn save_level(world: &mut World) -> Result<String, PhysicsSceneError> {
+///     PhysicsScene::capture(world).to_ron()
+/// }
+///
+/// fn load_level(world: &mut World, level: &str) -> Result<(), PhysicsSceneError> {
+///     let scene = PhysicsScene::from_ron(level)?;
+///     scene.spawn(world);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PhysicsScene {
+    /// The version of the format that the scene was saved with.
+    pub version: u32,
+    /// The [`Gravity`] of the world, if it was set.
+    pub gravity: Option<Vector>,
+    /// The entities in the scene, sorted by their IDs.
+    pub entities: Vec<PhysicsSceneEntity>,
+}
+
+/// An entity in a [`PhysicsScene`]. Components that the entity didn't have are `None`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PhysicsSceneEntity {
+    /// The ID of the entity in the scene. Other entities and joints refer to the entity using this ID.
+    pub id: Entity,
+    /// The ID of the parent of the entity, if it has a parent in the scene.
+    pub parent: Option<Entity>,
+    /// The local [`Transform`] of the entity.
+    pub transform: Option<Transform>,
+    /// The [`RigidBody`] of the entity.
+    pub rigid_body: Option<RigidBody>,
+    /// The [`Position`] of the entity.
+    pub position: Option<Position>,
+    /// The [`Rotation`] of the entity.
+    pub rotation: Option<Rotation>,
+    /// The [`LinearVelocity`] of the entity.
+    pub linear_velocity: Option<LinearVelocity>,
+    /// The [`AngularVelocity`] of the entity.
+    pub angular_velocity: Option<AngularVelocity>,
+    /// The [`Collider`] of the entity.
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub collider: Option<Collider>,
+    /// True if the entity is a [`Sensor`].
+    pub sensor: bool,
+    /// The [`CollisionLayers`] of the entity.
+    pub collision_layers: Option<CollisionLayers>,
+    /// The [`Friction`] of the entity.
+    pub friction: Option<Friction>,
+    /// The [`Restitution`] of the entity.
+    pub restitution: Option<Restitution>,
+    /// The [`ColliderDensity`] of the entity.
+    pub collider_density: Option<ColliderDensity>,
+    /// The [`LinearDamping`] of the entity.
+    pub linear_damping: Option<LinearDamping>,
+    /// The [`AngularDamping`] of the entity.
+    pub angular_damping: Option<AngularDamping>,
+    /// The [`GravityScale`] of the entity.
+    pub gravity_scale: Option<GravityScale>,
+    /// The [`LockedAxes`] of the entity.
+    pub locked_axes: Option<LockedAxes>,
+    /// The [`Dominance`] of the entity.
+    pub dominance: Option<Dominance>,
+    /// The [joint](joints) of the entity.
+    pub joint: Option<PhysicsSceneJoint>,
+}
+
+/// A [joint](joints) stored in a [`PhysicsSceneEntity`].
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PhysicsSceneJoint {
+    /// A [`FixedJoint`].
+    Fixed(FixedJoint),
+    /// A [`DistanceJoint`].
+    Distance(DistanceJoint),
+    /// A [`PrismaticJoint`].
+    Prismatic(PrismaticJoint),
+    /// A [`RevoluteJoint`].
+    Revolute(RevoluteJoint),
+    /// A [`SphericalJoint`].
+    Spherical(SphericalJoint),
+}
+
+/// An error that can occur when saving or loading a [`PhysicsScene`].
+#[derive(Debug)]
+pub enum PhysicsSceneError {
+    /// The scene could not be serialized to RON.
+    RonSerialize(ron::Error),
+    /// The scene could not be deserialized from RON.
+    RonDeserialize(ron::error::SpannedError),
+    /// The scene could not be serialized to or deserialized from the binary format.
+    Binary(bincode::Error),
+    /// The scene was saved with a version of the format that is not supported.
+    UnsupportedVersion {
+        /// The version of the scene.
+        found: u32,
+        /// The version supported by this version of the engine.
+        supported: u32,
+    },
+}
+
+impl fmt::Display for PhysicsSceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RonSerialize(err) => write!(f, "failed to serialize physics scene: {err}"),
+            Self::RonDeserialize(err) => write!(f, "failed to deserialize physics scene: {err}"),
+            Self::Binary(err) => write!(f, "failed to encode or decode physics scene: {err}"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "unsupported physics scene version {found}, expected version {supported}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PhysicsSceneError {}
+
+/// Used for reading the version of a scene before deserializing the rest of it.
+#[derive(Deserialize)]
+#[serde(rename = "PhysicsScene")]
+struct PhysicsSceneHeader {
+    version: u32,
+}
+
+impl PhysicsScene {
+    /// The current version of the physics scene format.
+    pub const VERSION: u32 = 1;
+
+    /// Captures the physics world in the given `world`.
+    pub fn capture(world: &mut World) -> Self {
+        let mut query = world.query_filtered::<Entity, Or<(
+            With<RigidBody>,
+            With<ColliderParent>,
+            With<FixedJoint>,
+            With<DistanceJoint>,
+            With<PrismaticJoint>,
+            With<RevoluteJoint>,
+            With<SphericalJoint>,
+        )>>();
+        let mut ids = query.iter(world).collect::<Vec<_>>();
+        ids.sort();
+
+        let entities = ids
+            .iter()
+            .map(|&id| {
+                let entity = world.entity(id);
+                let joint = entity
+                    .get::<FixedJoint>()
+                    .map(|joint| PhysicsSceneJoint::Fixed(*joint))
+                    .or_else(|| {
+                        entity
+                            .get::<DistanceJoint>()
+                            .map(|joint| PhysicsSceneJoint::Distance(*joint))
+                    })
+                    .or_else(|| {
+                        entity
+                            .get::<PrismaticJoint>()
+                            .map(|joint| PhysicsSceneJoint::Prismatic(*joint))
+                    })
+                    .or_else(|| {
+                        entity
+                            .get::<RevoluteJoint>()
+                            .map(|joint| PhysicsSceneJoint::Revolute(*joint))
+                    })
+                    .or_else(|| {
+                        entity
+                            .get::<SphericalJoint>()
+                            .map(|joint| PhysicsSceneJoint::Spherical(*joint))
+                    });
+
+                PhysicsSceneEntity {
+                    id,
+                    parent: entity
+                        .get::<Parent>()
+                        .map(|parent| parent.get())
+                        .filter(|parent| ids.binary_search(parent).is_ok()),
+                    transform: entity.get::<Transform>().copied(),
+                    rigid_body: entity.get::<RigidBody>().copied(),
+                    position: entity.get::<Position>().copied(),
+                    rotation: entity.get::<Rotation>().copied(),
+                    linear_velocity: entity.get::<LinearVelocity>().copied(),
+                    angular_velocity: entity.get::<AngularVelocity>().copied(),
+                    #[cfg(all(
+                        feature = "default-collider",
+                        any(feature = "parry-f32", feature = "parry-f64")
+                    ))]
+                    collider: entity.get::<Collider>().cloned(),
+                    sensor: entity.contains::<Sensor>(),
+                    collision_layers: entity.get::<CollisionLayers>().copied(),
+                    friction: entity.get::<Friction>().copied(),
+                    restitution: entity.get::<Restitution>().copied(),
+                    collider_density: entity.get::<ColliderDensity>().copied(),
+                    linear_damping: entity.get::<LinearDamping>().cloned(),
+                    angular_damping: entity.get::<AngularDamping>().cloned(),
+                    gravity_scale: entity.get::<GravityScale>().cloned(),
+                    locked_axes: entity.get::<LockedAxes>().copied(),
+                    dominance: entity.get::<Dominance>().copied(),
+                    joint,
+                }
+            })
+            .collect();
+
+        Self {
+            version: Self::VERSION,
+            gravity: world.get_resource::<Gravity>().map(|gravity| gravity.0),
+            entities,
+        }
+    }
+
+    /// Spawns the entities of the scene in the given `world`, and sets the [`Gravity`] if it is stored in the scene.
+    ///
+    /// Returns a map from the entity IDs in the scene to the spawned entities.
+    pub fn spawn(&self, world: &mut World) -> HashMap<Entity, Entity> {
+        let mut entity_map = SceneEntityMap(
+            self.entities
+                .iter()
+                .map(|entity| (entity.id, world.spawn_empty().id()))
+                .collect(),
+        );
+
+        for scene_entity in self.entities.iter() {
+            let entity = entity_map.map_entity(scene_entity.id);
+            let mut entity_mut = world.entity_mut(entity);
+
+            entity_mut.insert(TransformBundle::from_transform(
+                scene_entity.transform.unwrap_or_default(),
+            ));
+
+            insert_if_some(&mut entity_mut, scene_entity.rigid_body);
+            insert_if_some(&mut entity_mut, scene_entity.position);
+            insert_if_some(&mut entity_mut, scene_entity.rotation);
+            insert_if_some(&mut entity_mut, scene_entity.linear_velocity);
+            insert_if_some(&mut entity_mut, scene_entity.angular_velocity);
+            #[cfg(all(
+                feature = "default-collider",
+                any(feature = "parry-f32", feature = "parry-f64")
+            ))]
+            insert_if_some(&mut entity_mut, scene_entity.collider.clone());
+            insert_if_some(&mut entity_mut, scene_entity.sensor.then_some(Sensor));
+            insert_if_some(&mut entity_mut, scene_entity.collision_layers);
+            insert_if_some(&mut entity_mut, scene_entity.friction);
+            insert_if_some(&mut entity_mut, scene_entity.restitution);
+            insert_if_some(&mut entity_mut, scene_entity.collider_density);
+            insert_if_some(&mut entity_mut, scene_entity.linear_damping.clone());
+            insert_if_some(&mut entity_mut, scene_entity.angular_damping.clone());
+            insert_if_some(&mut entity_mut, scene_entity.gravity_scale.clone());
+            insert_if_some(&mut entity_mut, scene_entity.locked_axes);
+            insert_if_some(&mut entity_mut, scene_entity.dominance);
+
+            if let Some(parent) = scene_entity.parent {
+                let parent = entity_map.map_entity(parent);
+                world.entity_mut(entity).set_parent(parent);
+            }
+
+            // Joints refer to other entities, so their IDs must be mapped to the spawned entities.
+            if let Some(mut joint) = scene_entity.joint {
+                let mut entity_mut = world.entity_mut(entity);
+                match &mut joint {
+                    PhysicsSceneJoint::Fixed(joint) => {
+                        joint.map_entities(&mut entity_map);
+                        entity_mut.insert(*joint);
+                    }
+                    PhysicsSceneJoint::Distance(joint) => {
+                        joint.map_entities(&mut entity_map);
+                        entity_mut.insert(*joint);
+                    }
+                    PhysicsSceneJoint::Prismatic(joint) => {
+                        joint.map_entities(&mut entity_map);
+                        entity_mut.insert(*joint);
+                    }
+                    PhysicsSceneJoint::Revolute(joint) => {
+                        joint.map_entities(&mut entity_map);
+                        entity_mut.insert(*joint);
+                    }
+                    PhysicsSceneJoint::Spherical(joint) => {
+                        joint.map_entities(&mut entity_map);
+                        entity_mut.insert(*joint);
+                    }
+                }
+            }
+        }
+
+        if let Some(gravity) = self.gravity {
+            world.insert_resource(Gravity(gravity));
+        }
+
+        entity_map.0
+    }
+
+    /// Serializes the scene into the RON format.
+    pub fn to_ron(&self) -> Result<String, PhysicsSceneError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(PhysicsSceneError::RonSerialize)
+    }
+
+    /// Deserializes a scene from the RON format.
+    ///
+    /// Returns an error if the scene is invalid or was saved with an unsupported version of the format.
+    pub fn from_ron(ron: &str) -> Result<Self, PhysicsSceneError> {
+        let header: PhysicsSceneHeader =
+            ron::from_str(ron).map_err(PhysicsSceneError::RonDeserialize)?;
+        check_version(header.version)?;
+        ron::from_str(ron).map_err(PhysicsSceneError::RonDeserialize)
+    }
+
+    /// Serializes the scene into a compact binary format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PhysicsSceneError> {
+        bincode::serialize(self).map_err(PhysicsSceneError::Binary)
+    }
+
+    /// Deserializes a scene from the binary format.
+    ///
+    /// Returns an error if the scene is invalid or was saved with an unsupported version of the format.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PhysicsSceneError> {
+        // The version is serialized first, so it can be read without knowing the layout of the rest of the scene.
+        let header: PhysicsSceneHeader =
+            bincode::deserialize(bytes).map_err(PhysicsSceneError::Binary)?;
+        check_version(header.version)?;
+        bincode::deserialize(bytes).map_err(PhysicsSceneError::Binary)
+    }
+}
+
+fn check_version(version: u32) -> Result<(), PhysicsSceneError> {
+    if version == PhysicsScene::VERSION {
+        Ok(())
+    } else {
+        Err(PhysicsSceneError::UnsupportedVersion {
+            found: version,
+            supported: PhysicsScene::VERSION,
+        })
+    }
+}
+
+/// Maps entity IDs in a [`PhysicsScene`] to spawned entities.
+struct SceneEntityMap(HashMap<Entity, Entity>);
+
+impl EntityMapper for SceneEntityMap {
+    fn map_entity(&mut self, entity: Entity) -> Entity {
+        self.0.get(&entity).copied().unwrap_or(entity)
+    }
+}
+
+fn insert_if_some<C: Component>(entity_mut: &mut EntityWorldMut, component: Option<C>) {
+    if let Some(component) = component {
+        entity_mut.insert(component);
+    }
+}
@@ -0,0 +1,194 @@
+//! Helpers for client-side prediction and reconciliation in networked games.
+//!
+//! Bodies that are simulated ahead of the server can be marked as [`Predicted`]. The state of the
+//! [island](predicted_island) of predicted bodies can be captured each tick with
+//! [`PhysicsSnapshot::capture_predicted`], and when a correction is received from the server, only that
+//! island is rolled back and resimulated using [`Physics::resimulate_partial`], instead of the entire world.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::{collections::VecDeque, time::Duration};
+//!
+//! use bevy::prelude::*;
+//! # #[cfg(feature = "2d")]
+//! # use bevy_xpbd_2d::prelude::*;
+//! # #[cfg(feature = "3d")]
+//! use bevy_xpbd_3d::prelude::*;
+//!
+//! /// The snapshots of the predicted island for the ticks that haven't been confirmed by the server yet.
+//! #[derive(Resource, Default)]
+//! struct PredictionHistory(VecDeque<PhysicsSnapshot>);
+//!
+//! fn save_prediction(world: &mut World) {
+//!     let snapshot = PhysicsSnapshot::capture_predicted(world);
+//!     world.resource_mut::<PredictionHistory>().0.push_back(snapshot);
+//! }
+//!
+//! fn reconcile(world: &mut World, entity: Entity, server_position: Position) {
+//!     world.resource_scope(|world, mut history: Mut<PredictionHistory>| {
+//!         // The oldest unconfirmed tick was confirmed by the server
+//!         let Some(mut snapshot) = history.0.pop_front() else {
+//!             return;
+//!         };
+//!
+//!         if let Some(body) = snapshot.body_mut(entity) {
+//!             if body.position == server_position {
+//!                 // The prediction was correct
+//!                 return;
+//!             }
+//!             body.position = server_position;
+//!         }
+//!
+//!         // Resimulate the island from the corrected state up to the present tick
+//!         let steps = history.0.len();
+//!         Physics::resimulate_partial(
+//!             world,
+//!             &snapshot,
+//!             steps,
+//!             Duration::from_secs_f64(1.0 / 60.0),
+//!             |_, _| {},
+//!         );
+//!     });
+//! }
+//! ```
+
+use std::time::Duration;
+
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// A marker component for rigid bodies that are simulated ahead of the server using client-side prediction.
+///
+/// See the [module-level documentation](crate::plugins::prediction) for more information.
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Predicted;
+
+/// Returns the island of [`Predicted`] bodies, sorted by entity.
+///
+/// The island contains the predicted bodies and all dynamic and kinematic bodies that are connected to them
+/// through contacts or [joints](joints), directly or through other bodies. Static bodies are not included,
+/// and they don't connect bodies to each other.
+pub fn predicted_island(world: &mut World) -> Vec<Entity> {
+    let mut query = world.query_filtered::<Entity, (With<Predicted>, With<RigidBody>)>();
+    let roots = query.iter(world).collect::<Vec<_>>();
+    find_island(world, roots)
+}
+
+/// Returns the island of rigid bodies that are connected to the given `roots` through contacts
+/// or [joints](joints), sorted by entity.
+///
+/// Static bodies are not included, and they don't connect bodies to each other.
+pub fn find_island(world: &mut World, roots: impl IntoIterator<Item = Entity>) -> Vec<Entity> {
+    let mut colliders = world.query::<&ColliderParent>();
+    let mut bodies = world.query::<&RigidBody>();
+
+    let mut edges: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    let mut add_edge = |entity1: Entity, entity2: Entity| {
+        edges.entry(entity1).or_default().push(entity2);
+        edges.entry(entity2).or_default().push(entity1);
+    };
+
+    if let Some(collisions) = world.get_resource::<Collisions>() {
+        for contacts in collisions.iter().filter(|c| c.during_current_frame) {
+            // Contacts are between colliders, so get the bodies they are attached to.
+            let body1 = colliders
+                .get(world, contacts.entity1)
+                .map_or(contacts.entity1, |parent| parent.get());
+            let body2 = colliders
+                .get(world, contacts.entity2)
+                .map_or(contacts.entity2, |parent| parent.get());
+            add_edge(body1, body2);
+        }
+    }
+
+    add_joint_edges::<FixedJoint>(world, &mut add_edge);
+    add_joint_edges::<DistanceJoint>(world, &mut add_edge);
+    add_joint_edges::<PrismaticJoint>(world, &mut add_edge);
+    add_joint_edges::<RevoluteJoint>(world, &mut add_edge);
+    add_joint_edges::<SphericalJoint>(world, &mut add_edge);
+
+    let mut is_connecting = |world: &World, entity: Entity| {
+        bodies
+            .get(world, entity)
+            .is_ok_and(|rigid_body| !rigid_body.is_static())
+    };
+
+    let mut island = vec![];
+    let mut stack = roots
+        .into_iter()
+        .filter(|entity| is_connecting(world, *entity))
+        .collect::<Vec<_>>();
+    let mut visited = stack.iter().copied().collect::<HashSet<_>>();
+
+    while let Some(entity) = stack.pop() {
+        island.push(entity);
+
+        for &neighbor in edges.get(&entity).into_iter().flatten() {
+            if is_connecting(world, neighbor) && visited.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    island.sort();
+    island
+}
+
+/// Adds the bodies connected by joints of type `J` as edges.
+fn add_joint_edges<J: Joint>(world: &mut World, add_edge: &mut impl FnMut(Entity, Entity)) {
+    let mut joints = world.query::<&J>();
+    for joint in joints.iter(world) {
+        let [entity1, entity2] = joint.entities();
+        add_edge(entity1, entity2);
+    }
+}
+
+impl PhysicsSnapshot {
+    /// Captures the state of the [island](predicted_island) of [`Predicted`] bodies.
+    ///
+    /// See [`PhysicsSnapshot::capture_entities`] for more information.
+    pub fn capture_predicted(world: &mut World) -> Self {
+        let island = predicted_island(world);
+        Self::capture_entities(world, island)
+    }
+}
+
+impl Physics {
+    /// Rewinds the bodies in the given partial `snapshot` and immediately re-steps the simulation `steps` times
+    /// with the given `delta` time, while the rest of the world is kept frozen in its current state.
+    ///
+    /// This is like [`Physics::resimulate`], but only rolls back a subset of the world, like the
+    /// [island](predicted_island) of [`Predicted`] bodies captured using [`PhysicsSnapshot::capture_predicted`].
+    ///
+    /// Bodies that aren't in the snapshot are put to sleep during the resimulation, and their current state
+    /// is restored afterwards. If resimulated bodies collide with them, they can still be woken up and pushed
+    /// during the resimulation, but they don't affect the final state of the world.
+    pub fn resimulate_partial(
+        world: &mut World,
+        snapshot: &PhysicsSnapshot,
+        steps: usize,
+        delta: Duration,
+        before_step: impl FnMut(&mut World, usize),
+    ) {
+        let mut query = world.query_filtered::<Entity, With<RigidBody>>();
+        let frozen = query
+            .iter(world)
+            .filter(|entity| snapshot.body(*entity).is_none())
+            .collect::<Vec<_>>();
+        let frozen_state = PhysicsSnapshot::capture_entities(world, frozen.iter().copied());
+
+        for entity in frozen {
+            world.entity_mut(entity).insert(Sleeping);
+        }
+
+        Self::resimulate(world, snapshot, steps, delta, before_step);
+
+        frozen_state.restore(world);
+    }
+}
@@ -6,13 +6,18 @@
 #![allow(clippy::type_complexity)]
 
 use crate::prelude::*;
-use bevy::{ecs::query::QueryFilter, prelude::*, utils::intern::Interned};
+use bevy::{
+    ecs::query::QueryFilter,
+    prelude::*,
+    utils::{intern::Interned, HashSet},
+};
 
 /// Runs systems at the start of each physics frame. Initializes [rigid bodies](RigidBody)
 /// and updates components.
 ///
 /// - Adds missing rigid body components for entities with a [`RigidBody`] component
 /// - Adds missing mass properties for entities with a [`RigidBody`] component
+/// - Handles changes to the [`RigidBody`] type of existing bodies
 /// - Updates mass properties
 /// - Clamps restitution coefficients between 0 and 1
 ///
@@ -74,7 +79,8 @@ pub enum PrepareSet {
     /// Schedule your system with this to implement custom behavior for initializing transforms.
     InitTransforms,
     /// Responsible for performing final updates after everything is initialized.
-    /// Updates mass properties and clamps collider density and restitution.
+    /// Handles rigid body type changes, updates mass properties, clamps collider density and restitution,
+    /// and resets the velocities of bodies disabled with [`RigidBodyDisabled::RESET_VELOCITY`].
     Finalize,
 }
 
@@ -129,9 +135,11 @@ impl Plugin for PreparePlugin {
         .add_systems(
             self.schedule,
             (
+                handle_rigid_body_type_changes,
                 update_mass_properties,
                 clamp_collider_density,
                 clamp_restitution,
+                reset_disabled_body_velocities,
                 // All the components we added above must exist before we can simulate the bodies.
                 apply_deferred,
             )
@@ -143,6 +151,7 @@ impl Plugin for PreparePlugin {
 
 /// Configures what is initialized by the [`PreparePlugin`] and how.
 #[derive(Resource, Reflect, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Resource)]
 pub struct PrepareConfig {
     /// Initializes [`Transform`] based on [`Position`] and [`Rotation`].
@@ -435,6 +444,7 @@ fn init_mass_properties(
             *inverse_inertia
                 .unwrap_or(&inertia.map_or(InverseInertia::ZERO, |inertia| inertia.inverse())),
             *center_of_mass.unwrap_or(&CenterOfMass::default()),
+            PreviousCenterOfMass(center_of_mass.map_or(Vector::ZERO, |com| com.0)),
         ));
     }
 }
@@ -477,6 +487,104 @@ pub fn update_mass_properties(
     }
 }
 
+/// Handles changes to the type of existing [rigid bodies](RigidBody).
+///
+/// - Bodies that become static have their velocities cleared. Velocities are kept
+/// when switching between dynamic and kinematic.
+/// - The changed bodies and the bodies they are in contact with are woken up.
+/// - The stored contact impulses of the changed bodies are reset, and contacts between
+/// bodies that are now both static are removed, as static bodies don't collide with each other.
+///
+/// The broad phase moves the colliders of the bodies between its acceleration structures separately.
+fn handle_rigid_body_type_changes(
+    mut commands: Commands,
+    mut changed_bodies: Query<
+        (
+            Entity,
+            Ref<RigidBody>,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        Changed<RigidBody>,
+    >,
+    bodies: Query<(&RigidBody, Has<Sleeping>)>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Option<ResMut<Collisions>>,
+    mut changed: Local<HashSet<Entity>>,
+) {
+    changed.clear();
+
+    for (entity, rb, mut lin_vel, mut ang_vel) in &mut changed_bodies {
+        if rb.is_added() {
+            continue;
+        }
+
+        changed.insert(entity);
+
+        if rb.is_static() {
+            if *lin_vel != LinearVelocity::ZERO {
+                *lin_vel = LinearVelocity::ZERO;
+            }
+            if *ang_vel != AngularVelocity::ZERO {
+                *ang_vel = AngularVelocity::ZERO;
+            }
+        }
+    }
+
+    if changed.is_empty() {
+        return;
+    }
+
+    let mut to_wake: Vec<Entity> = changed.iter().copied().collect();
+
+    if let Some(mut collisions) = collisions {
+        let body_of = |collider: Entity| {
+            collider_parents
+                .get(collider)
+                .map_or(collider, |parent| parent.get())
+        };
+        let is_static = |body: Entity| bodies.get(body).is_ok_and(|(rb, _)| rb.is_static());
+
+        collisions.retain(|contacts| {
+            let body1 = body_of(contacts.entity1);
+            let body2 = body_of(contacts.entity2);
+
+            if !changed.contains(&body1) && !changed.contains(&body2) {
+                return true;
+            }
+
+            if is_static(body1) && is_static(body2) {
+                return false;
+            }
+
+            // The impulses were computed for the previous body types, so they are stale.
+            contacts.total_normal_impulse = 0.0;
+            contacts.total_tangent_impulse = 0.0;
+            for contact in contacts
+                .manifolds
+                .iter_mut()
+                .flat_map(|manifold| manifold.contacts.iter_mut())
+            {
+                contact.normal_impulse = 0.0;
+                contact.tangent_impulse = 0.0;
+            }
+
+            to_wake.extend([body1, body2]);
+
+            true
+        });
+    }
+
+    for entity in to_wake {
+        if bodies.get(entity).is_ok_and(|(_, is_sleeping)| is_sleeping) {
+            commands
+                .entity(entity)
+                .remove::<Sleeping>()
+                .insert(TimeSleeping(0.0));
+        }
+    }
+}
+
 /// Clamps coefficients of [restitution](Restitution) to be between 0.0 and 1.0.
 fn clamp_restitution(mut query: Query<&mut Restitution, Changed<Restitution>>) {
     for mut restitution in &mut query {
@@ -490,3 +598,23 @@ fn clamp_collider_density(mut query: Query<&mut ColliderDensity, Changed<Collide
         density.0 = density.max(Scalar::EPSILON);
     }
 }
+
+/// Sets the velocities of bodies to zero when they are disabled using [`RigidBodyDisabled`]
+/// with [`reset_velocity`](RigidBodyDisabled::reset_velocity) set to `true`.
+fn reset_disabled_body_velocities(
+    mut bodies: Query<
+        (
+            &RigidBodyDisabled,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        Changed<RigidBodyDisabled>,
+    >,
+) {
+    for (disabled, mut lin_vel, mut ang_vel) in &mut bodies {
+        if disabled.reset_velocity {
+            *lin_vel = LinearVelocity::ZERO;
+            *ang_vel = AngularVelocity::ZERO;
+        }
+    }
+}
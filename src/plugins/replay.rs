@@ -0,0 +1,558 @@
+//! Recording the inputs of the physics simulation and replaying them for bug reproduction and kill-cams.
+//!
+//! See [`PhysicsReplayPlugin`], [`PhysicsRecorder`] and [`PhysicsReplay`].
+
+use std::time::Duration;
+
+use crate::{
+    plugins::{
+        setup::run_physics_schedule,
+        snapshot::{restore_component, BodySnapshotQueryData},
+    },
+    prelude::*,
+};
+use bevy::{
+    ecs::{entity::Entities, world::EntityWorldMut},
+    prelude::*,
+    utils::{intern::Interned, HashMap, HashSet},
+};
+
+/// Records the inputs of the physics simulation into a [`PhysicsRecording`] while the [`PhysicsRecorder`]
+/// resource exists, and re-drives the simulation from a recording while the [`PhysicsReplay`] resource exists.
+///
+/// The recorder runs in [`PhysicsSet::StepSimulation`] and at the start of the [`PhysicsSchedule`],
+/// and the player runs in [`PhysicsSet::StepSimulation`] before the [`PhysicsSchedule`] is run.
+pub struct PhysicsReplayPlugin {
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl PhysicsReplayPlugin {
+    /// Creates a [`PhysicsReplayPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for PhysicsReplayPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for PhysicsReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.schedule,
+            (
+                play_replay.run_if(resource_exists::<PhysicsReplay>),
+                record_frame.run_if(resource_exists::<PhysicsRecorder>),
+            )
+                .chain()
+                .in_set(PhysicsSet::StepSimulation)
+                .before(run_physics_schedule),
+        );
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(
+                // Record the state after remote state corrections have been applied to it.
+                record_step
+                    .before(PhysicsStepSet::BroadPhase)
+                    .after(crate::plugins::correction::apply_state_corrections)
+                    .run_if(resource_exists::<PhysicsRecorder>),
+            );
+    }
+}
+
+/// A log of the inputs of the physics simulation over a number of frames,
+/// recorded by the [`PhysicsRecorder`] and replayed by the [`PhysicsReplay`].
+///
+/// The recording contains the [initial state](Self::initial_state) of the simulation, and for each frame:
+///
+/// - The rigid bodies that were spawned and despawned before the frame was simulated
+/// - The delta time of each physics step run during the frame
+/// - The forces, impulses and kinematic targets that were applied to bodies for each physics step
+///
+/// Only inputs that changed are recorded, so the recording stays compact for scenes that
+/// are mostly driven by the simulation itself.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicsRecording {
+    /// The state of the simulation when the recording was started.
+    pub initial_state: PhysicsSnapshot,
+    /// The recorded frames.
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl PhysicsRecording {
+    /// Returns the total number of recorded physics steps.
+    pub fn step_count(&self) -> usize {
+        self.frames.iter().map(|frame| frame.steps.len()).sum()
+    }
+}
+
+/// The inputs of a single frame in a [`PhysicsRecording`].
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedFrame {
+    /// The rigid bodies that were spawned before the frame was simulated.
+    pub spawned: Vec<RecordedSpawn>,
+    /// The rigid bodies that were despawned before the frame was simulated.
+    pub despawned: Vec<Entity>,
+    /// The physics steps that were run during the frame.
+    pub steps: Vec<RecordedStep>,
+}
+
+/// A rigid body that was spawned during a [`PhysicsRecording`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedSpawn {
+    /// The entity of the body in the recorded world.
+    pub entity: Entity,
+    /// The type of the body.
+    pub rigid_body: RigidBody,
+    /// The state of the body when it was spawned.
+    pub state: BodySnapshot,
+    /// The collider attached to the body entity, if it had one.
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    pub collider: Option<Collider>,
+}
+
+/// The inputs of a single physics step in a [`PhysicsRecording`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedStep {
+    /// The delta time of the step.
+    pub delta: Duration,
+    /// The inputs applied to rigid bodies before the step.
+    pub inputs: Vec<RecordedInput>,
+}
+
+/// The inputs applied to a rigid body before a physics step.
+///
+/// Forces and impulses are only stored if they changed since the previous step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedInput {
+    /// The entity of the body in the recorded world.
+    pub entity: Entity,
+    /// The [`ExternalForce`] applied to the body.
+    pub external_force: Option<ExternalForce>,
+    /// The [`ExternalTorque`] applied to the body.
+    pub external_torque: Option<ExternalTorque>,
+    /// The [`ExternalImpulse`] applied to the body.
+    pub external_impulse: Option<ExternalImpulse>,
+    /// The [`ExternalAngularImpulse`] applied to the body.
+    pub external_angular_impulse: Option<ExternalAngularImpulse>,
+    /// The position and velocity of the body if it is [kinematic](RigidBody::Kinematic).
+    pub kinematic_target: Option<KinematicTarget>,
+}
+
+impl RecordedInput {
+    /// Returns true if the input doesn't contain any changes.
+    pub fn is_empty(&self) -> bool {
+        self.external_force.is_none()
+            && self.external_torque.is_none()
+            && self.external_impulse.is_none()
+            && self.external_angular_impulse.is_none()
+            && self.kinematic_target.is_none()
+    }
+
+    /// Applies the recorded inputs to the given rigid body.
+    fn apply(&self, entity_mut: &mut EntityWorldMut) {
+        apply_input(entity_mut, self.external_force);
+        apply_input(entity_mut, self.external_torque);
+        apply_input(entity_mut, self.external_impulse);
+        apply_input(entity_mut, self.external_angular_impulse);
+
+        if let Some(target) = self.kinematic_target {
+            restore_component(entity_mut, Some(target.position));
+            restore_component(entity_mut, Some(target.rotation));
+            restore_component(entity_mut, Some(target.linear_velocity));
+            restore_component(entity_mut, Some(target.angular_velocity));
+        }
+    }
+}
+
+/// The position and velocity of a [kinematic](RigidBody::Kinematic) body before a physics step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct KinematicTarget {
+    /// The [`Position`] of the body.
+    pub position: Position,
+    /// The [`Rotation`] of the body.
+    pub rotation: Rotation,
+    /// The [`LinearVelocity`] of the body.
+    pub linear_velocity: LinearVelocity,
+    /// The [`AngularVelocity`] of the body.
+    pub angular_velocity: AngularVelocity,
+}
+
+/// A resource that records the inputs of the physics simulation into a [`PhysicsRecording`]
+/// while it exists. Requires the [`PhysicsReplayPlugin`].
+///
+/// Use [`PhysicsRecorder::start`] to start recording and [`PhysicsRecorder::stop`]
+/// to stop recording and get the recording.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct LastRecording(PhysicsRecording);
+///
+/// fn start_recording(world: &mut World) {
+///     PhysicsRecorder::start(world);
+/// }
+///
+/// fn stop_recording(world: &mut World) {
+///     if let Some(recording) = PhysicsRecorder::stop(world) {
+///         world.insert_resource(LastRecording(recording));
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct PhysicsRecorder {
+    recording: PhysicsRecording,
+    /// The bodies that exist in the recording, used for ignoring unrelated despawns.
+    bodies: HashSet<Entity>,
+}
+
+impl PhysicsRecorder {
+    /// Captures the current state of the simulation and starts recording its inputs.
+    ///
+    /// If a recording is already in progress, it is discarded.
+    pub fn start(world: &mut World) {
+        let initial_state = PhysicsSnapshot::capture(world);
+        let bodies = initial_state.bodies().map(|(entity, _)| entity).collect();
+
+        world.insert_resource(Self {
+            recording: PhysicsRecording {
+                initial_state,
+                frames: vec![],
+            },
+            bodies,
+        });
+    }
+
+    /// Stops recording and returns the recording, or `None` if no recording was in progress.
+    pub fn stop(world: &mut World) -> Option<PhysicsRecording> {
+        world
+            .remove_resource::<Self>()
+            .map(|recorder| recorder.recording)
+    }
+
+    /// Returns the recording in progress.
+    pub fn recording(&self) -> &PhysicsRecording {
+        &self.recording
+    }
+}
+
+/// A resource that re-drives the physics simulation from a [`PhysicsRecording`] while it exists.
+/// Requires the [`PhysicsReplayPlugin`].
+///
+/// Use [`PhysicsReplay::start`] to start a replay. The state of the simulation is restored to the
+/// [initial state](PhysicsRecording::initial_state) of the recording, and each frame, the player re-applies
+/// the recorded spawns, despawns and inputs and runs the recorded physics steps with the recorded delta times.
+///
+/// The [physics clock](Physics) is paused while replaying so that the [`PhysicsPlugins`] don't step
+/// the simulation on their own. Once the replay [is finished](Self::is_finished), the simulation stays paused
+/// until the replay is [stopped](Self::stop).
+///
+/// The initial state is restored onto the same entities that it was recorded for, so a recording should be
+/// replayed in the world it was recorded in, or in a world where the same entities have been set up the same way.
+/// Bodies that were despawned during the recording can't be restored.
+///
+/// Bodies that were spawned during the recording are despawned when the replay is started, and spawned
+/// again as new entities by the player. The corresponding entities can be found using [`PhysicsReplay::map_entity`].
+/// They only contain physics components, so you may want to add meshes or other visuals to them.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct LastRecording(PhysicsRecording);
+///
+/// fn play_kill_cam(world: &mut World) {
+///     if let Some(recording) = world.remove_resource::<LastRecording>() {
+///         PhysicsReplay::start(world, recording.0);
+///     }
+/// }
+///
+/// fn stop_kill_cam(world: &mut World) {
+///     if world.get_resource::<PhysicsReplay>().is_some_and(|replay| replay.is_finished()) {
+///         PhysicsReplay::stop(world);
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct PhysicsReplay {
+    recording: PhysicsRecording,
+    frame: usize,
+    entity_map: HashMap<Entity, Entity>,
+}
+
+impl PhysicsReplay {
+    /// Restores the initial state of the given `recording` and starts replaying it.
+    ///
+    /// If a replay is already in progress, it is replaced.
+    pub fn start(world: &mut World, recording: PhysicsRecording) {
+        // Despawn bodies that didn't exist when the recording was started, since the player spawns them again.
+        for spawn in recording
+            .frames
+            .iter()
+            .flat_map(|frame| frame.spawned.iter())
+        {
+            if world.get_entity(spawn.entity).is_some() {
+                world.despawn(spawn.entity);
+            }
+        }
+
+        recording.initial_state.restore(world);
+        world.resource_mut::<Time<Physics>>().pause();
+
+        let mut entity_map = HashMap::default();
+
+        // Spawn the bodies of the first frame now so that they are initialized before the frame is simulated.
+        if let Some(frame) = recording.frames.first() {
+            spawn_bodies(world, &frame.spawned, &mut entity_map);
+        }
+
+        world.insert_resource(Self {
+            recording,
+            frame: 0,
+            entity_map,
+        });
+    }
+
+    /// Stops the replay in progress and unpauses the [physics clock](Physics).
+    /// Returns the recording, or `None` if no replay was in progress.
+    pub fn stop(world: &mut World) -> Option<PhysicsRecording> {
+        let replay = world.remove_resource::<Self>()?;
+        world.resource_mut::<Time<Physics>>().unpause();
+        Some(replay.recording)
+    }
+
+    /// Returns the recording that is being replayed.
+    pub fn recording(&self) -> &PhysicsRecording {
+        &self.recording
+    }
+
+    /// Returns the index of the next frame to be replayed.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns true if all frames of the recording have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frames.len()
+    }
+
+    /// Maps an entity in the recorded world to the corresponding entity in the replay.
+    ///
+    /// Bodies that were spawned during the recording are spawned as new entities,
+    /// while other entities are mapped to themselves.
+    pub fn map_entity(&self, entity: Entity) -> Entity {
+        self.entity_map.get(&entity).copied().unwrap_or(entity)
+    }
+}
+
+fn record_frame(
+    mut recorder: ResMut<PhysicsRecorder>,
+    spawned: Query<(BodySnapshotQueryData, &RigidBody), Added<RigidBody>>,
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    colliders: Query<&Collider>,
+    mut removed: RemovedComponents<RigidBody>,
+    entities: &Entities,
+) {
+    let recorder = &mut *recorder;
+    let mut frame = RecordedFrame::default();
+
+    for (body, rigid_body) in &spawned {
+        let (entity, state) = BodySnapshot::from_query_item(body);
+
+        // Bodies that existed when the recording was started are in the initial state.
+        if !recorder.bodies.insert(entity) {
+            continue;
+        }
+
+        frame.spawned.push(RecordedSpawn {
+            entity,
+            rigid_body: *rigid_body,
+            state,
+            #[cfg(all(
+                feature = "default-collider",
+                any(feature = "parry-f32", feature = "parry-f64")
+            ))]
+            collider: colliders.get(entity).ok().cloned(),
+        });
+    }
+
+    for entity in removed.read() {
+        if !entities.contains(entity) && recorder.bodies.remove(&entity) {
+            frame.despawned.push(entity);
+        }
+    }
+
+    // Sort for a stable order, since query iteration order isn't guaranteed.
+    frame.spawned.sort_by_key(|spawn| spawn.entity);
+    frame.despawned.sort();
+
+    recorder.recording.frames.push(frame);
+}
+
+type RecordedInputQueryData = (
+    Entity,
+    &'static RigidBody,
+    Ref<'static, ExternalForce>,
+    Ref<'static, ExternalTorque>,
+    Ref<'static, ExternalImpulse>,
+    Ref<'static, ExternalAngularImpulse>,
+    &'static Position,
+    &'static Rotation,
+    &'static LinearVelocity,
+    &'static AngularVelocity,
+);
+
+fn record_step(
+    mut recorder: ResMut<PhysicsRecorder>,
+    bodies: Query<RecordedInputQueryData>,
+    time: Res<Time>,
+) {
+    let mut step = RecordedStep {
+        delta: time.delta(),
+        inputs: vec![],
+    };
+
+    for (
+        entity,
+        rigid_body,
+        force,
+        torque,
+        impulse,
+        angular_impulse,
+        position,
+        rotation,
+        linear_velocity,
+        angular_velocity,
+    ) in &bodies
+    {
+        let input = RecordedInput {
+            entity,
+            external_force: force.is_changed().then(|| *force),
+            external_torque: torque.is_changed().then(|| *torque),
+            external_impulse: impulse.is_changed().then(|| *impulse),
+            external_angular_impulse: angular_impulse.is_changed().then(|| *angular_impulse),
+            kinematic_target: rigid_body.is_kinematic().then_some(KinematicTarget {
+                position: *position,
+                rotation: *rotation,
+                linear_velocity: *linear_velocity,
+                angular_velocity: *angular_velocity,
+            }),
+        };
+
+        if !input.is_empty() {
+            step.inputs.push(input);
+        }
+    }
+
+    step.inputs.sort_by_key(|input| input.entity);
+
+    let frames = &mut recorder.recording.frames;
+
+    // The recording may have been started in the middle of a frame.
+    if frames.is_empty() {
+        frames.push(RecordedFrame::default());
+    }
+
+    frames.last_mut().unwrap().steps.push(step);
+}
+
+fn play_replay(world: &mut World) {
+    world.resource_scope(|world, mut replay: Mut<PhysicsReplay>| {
+        let PhysicsReplay {
+            recording,
+            frame: frame_index,
+            entity_map,
+        } = &mut *replay;
+
+        let Some(frame) = recording.frames.get(*frame_index) else {
+            return;
+        };
+
+        for entity in frame.despawned.iter() {
+            let entity = entity_map.remove(entity).unwrap_or(*entity);
+            if world.get_entity(entity).is_some() {
+                world.despawn(entity);
+            }
+        }
+
+        for step in frame.steps.iter() {
+            for input in step.inputs.iter() {
+                let entity = entity_map.get(&input.entity).unwrap_or(&input.entity);
+                if let Some(mut entity_mut) = world.get_entity_mut(*entity) {
+                    input.apply(&mut entity_mut);
+                }
+            }
+
+            Physics::step(world, step.delta);
+        }
+
+        *frame_index += 1;
+
+        // Spawn the bodies of the next frame now so that they are initialized before the frame is simulated.
+        if let Some(next_frame) = recording.frames.get(*frame_index) {
+            spawn_bodies(world, &next_frame.spawned, entity_map);
+        }
+    });
+}
+
+/// Spawns the given recorded bodies and adds them to the entity map.
+fn spawn_bodies(
+    world: &mut World,
+    spawned: &[RecordedSpawn],
+    entity_map: &mut HashMap<Entity, Entity>,
+) {
+    for spawn in spawned {
+        let mut entity_mut = world.spawn(spawn.rigid_body);
+        spawn.state.restore(&mut entity_mut);
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        if let Some(collider) = &spawn.collider {
+            entity_mut.insert(collider.clone());
+        }
+
+        entity_map.insert(spawn.entity, entity_mut.id());
+    }
+}
+
+/// Applies a recorded input component if it was recorded.
+fn apply_input<C: Component + PartialEq>(entity_mut: &mut EntityWorldMut, component: Option<C>) {
+    if component.is_some() {
+        restore_component(entity_mut, component);
+    }
+}
@@ -0,0 +1,574 @@
+//! Simulates ropes and cables as chains of particles.
+//!
+//! See [`RopePlugin`] and [`Rope`].
+
+#[cfg(feature = "deformable-mesh")]
+use crate::plugins::deformable_mesh::write_deformable_mesh;
+use crate::{
+    plugins::particles::{
+        collide_particles, deform_particle_distance, integrate_particles, solve_attachment,
+        solve_particle_distance, update_particle_velocities, ParticleBodies, ParticleContacts,
+    },
+    prelude::*,
+};
+#[cfg(feature = "deformable-mesh")]
+use bevy::render::{mesh::PrimitiveTopology, render_asset::RenderAssetUsages};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::intern::Interned,
+};
+
+/// Simulates [ropes](Rope) as chains of particles connected by distance and bending constraints.
+///
+/// Ropes are much cheaper and less stretchy than chains of [rigid bodies](RigidBody) connected with
+/// [joints], because the particles have no rotation and the constraints only involve positions.
+///
+/// The ropes are simulated in the [`SubstepSchedule`]:
+///
+/// 1. The particles are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The distance, bending and [attachment](ParticleAttachment) constraints are solved after the [joints]
+/// in [`ParticleSet::Ropes`]. Attachments apply corrections to both the rope and the attached body.
+/// 3. The particles are pushed out of [colliders](Collider) in [`ParticleSet::Ropes`].
+/// 4. The velocities of the particles are updated from their change in position in [`SubstepSet::UpdateVelocities`].
+///
+/// With the `deformable-mesh` feature, the particle positions of the rope are written to
+/// the `Mesh` of the rope entity by the [`DeformableMeshPlugin`]. The mesh can be created with [`Rope::mesh`].
+pub struct RopePlugin {
+    #[cfg_attr(not(feature = "deformable-mesh"), allow(dead_code))]
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl RopePlugin {
+    /// Creates a [`RopePlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for RopePlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for RopePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Rope>()
+            .register_type::<Particle>()
+            .register_type::<ParticleAttachment>()
+            .add_event::<RopeTorn>();
+
+        #[cfg(feature = "deformable-mesh")]
+        app.add_plugins(DeformableMeshPlugin::<Rope>::new(self.schedule));
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_ropes.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(
+            (solve_rope_constraints, collide_ropes)
+                .chain()
+                .in_set(ParticleSet::Ropes),
+        );
+
+        substeps.add_systems(update_rope_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// A rope or cable simulated as a chain of [particles](Particle) by the [`RopePlugin`].
+///
+/// Consecutive particles are kept [`segment_length`](Self::segment_length) apart, and bending is resisted
+/// by keeping every other particle apart. Either end of the rope can be [attached](ParticleAttachment)
+/// to a [rigid body](RigidBody), in which case the rope and the body pull on each other.
+///
+/// The particles are simulated in world space, so the `Transform` of the rope entity has no effect.
+/// The particles collide with [colliders](Collider) whose [layers](CollisionLayers) interact with
+/// the [`layers`](Self::layers) of the rope, and dynamic [rigid bodies](RigidBody) are pushed back by the particles,
+/// so bodies can rest on ropes and nets. Ropes don't collide with themselves or each other,
+/// and colliders of attached bodies are ignored.
+///
+/// ## Rendering
+///
+/// With the `deformable-mesh` feature, [`Rope::mesh`] creates a line strip `Mesh` for the rope. When the handle
+/// of the mesh is added to the rope entity, the vertices of the mesh are updated to match the particles
+/// every frame by the [`DeformableMeshPlugin`]. The vertices are written relative to the `GlobalTransform` of the entity.
+///
+/// ## Tearing and cutting
+///
+/// When a [tear strain](Self::tear_strain) is set, a segment that is stretched too far tears. The rope keeps
+/// the particles before the segment, the rest of the particles are moved to a new rope entity, and a [`RopeTorn`]
+/// event is sent. Ropes can also be [split](Rope::split_off) or [cut](Rope::cut) manually, in which case
+/// the new pieces have to be spawned by the caller.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     let anchor = commands.spawn(RigidBody::Static).id();
+///     let weight = commands
+///         .spawn((
+///             RigidBody::Dynamic,
+#[cfg_attr(feature = "2d", doc = "             Collider::circle(0.5),")]
+#[cfg_attr(feature = "3d", doc = "             Collider::sphere(0.5),")]
+///             TransformBundle::from_transform(Transform::from_xyz(5.0, 0.0, 0.0)),
+///         ))
+///         .id();
+///
+///     // A rope with 20 segments hanging from a static anchor, with a ball at the other end
+///     commands.spawn(
+///         Rope::new(Vector::ZERO, Vector::X * 5.0, 20, 1.0)
+///             .with_start_attachment(ParticleAttachment::new(anchor))
+///             .with_end_attachment(ParticleAttachment::new(weight))
+///             .with_radius(0.05),
+///     );
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Rope {
+    /// The particles of the rope, from the start to the end.
+    pub particles: Vec<Particle>,
+    /// The rest distance between consecutive particles.
+    pub segment_length: Scalar,
+    /// The compliance of the distance constraints, the inverse of stretch stiffness. Has the unit meters / Newton.
+    ///
+    /// The default is `0.0`, which makes the rope inextensible.
+    pub stretch_compliance: Scalar,
+    /// The compliance of the bending constraints, the inverse of bending stiffness. Has the unit meters / Newton.
+    ///
+    /// The default is `0.1`, which lets the rope bend freely under small loads.
+    pub bending_compliance: Scalar,
+    /// The radius of the particles used for collisions.
+    pub radius: Scalar,
+    /// The friction of the particles against colliders, between `0.0` and `1.0`.
+    pub friction: Scalar,
+    /// The linear damping of the particles.
+    pub damping: Scalar,
+    /// The layers of the rope. The particles only collide with colliders whose layers [interact](CollisionLayers::interacts_with)
+    /// with these layers.
+    pub layers: CollisionLayers,
+    /// The attachment of the first particle.
+    pub start: Option<ParticleAttachment>,
+    /// The attachment of the last particle.
+    pub end: Option<ParticleAttachment>,
+    /// Plastic deformation of the distance and bending constraints. When the rope is stretched or bent
+    /// beyond the yield strain, it keeps part of its new shape instead of springing back.
+    ///
+    /// With plasticity, bending constraints also resist straightening, so bent wires keep their shape.
+    pub plasticity: Option<Plasticity>,
+    /// The rest lengths of the distance constraints between consecutive particles after plastic deformation.
+    /// Empty if the rope has not deformed, in which case [`segment_length`](Self::segment_length) is used.
+    pub segment_rest_lengths: Vec<Scalar>,
+    /// The rest lengths of the bending constraints between every other particle after plastic deformation.
+    /// Empty if the rope has not deformed, in which case twice the [`segment_length`](Self::segment_length) is used.
+    pub bend_rest_lengths: Vec<Scalar>,
+    /// The strain at which the distance constraints tear, as a fraction of their rest length.
+    /// When a segment tears, the rope is split in two and a [`RopeTorn`] event is sent.
+    ///
+    /// The default is `None`, which means that the rope never tears.
+    pub tear_strain: Option<Scalar>,
+}
+
+/// An event that is sent when a segment of a [`Rope`] tears because its strain has exceeded
+/// the [tear strain](Rope::tear_strain) of the rope.
+///
+/// The particles after the torn segment are moved to a new rope entity along with the end attachment.
+/// Cutting the rope with [`Rope::cut`] doesn't send events.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RopeTorn {
+    /// The rope entity that was torn. It keeps the particles before the torn segment.
+    pub rope: Entity,
+    /// The rope entity that was spawned for the particles after the torn segment.
+    pub new_rope: Entity,
+    /// The index of the torn segment in the original rope.
+    pub segment: usize,
+}
+
+impl Rope {
+    /// Creates a straight rope from `start` to `end` divided into the given number of `segments`.
+    /// The total `mass` of the rope is divided evenly between the particles.
+    pub fn new(start: Vector, end: Vector, segments: usize, mass: Scalar) -> Self {
+        let segments = segments.max(1);
+        let particle_mass = mass / (segments + 1) as Scalar;
+        let particles = (0..=segments)
+            .map(|i| {
+                let t = i as Scalar / segments as Scalar;
+                Particle::new(start.lerp(end, t), particle_mass)
+            })
+            .collect();
+
+        Self {
+            particles,
+            segment_length: start.distance(end) / segments as Scalar,
+            stretch_compliance: 0.0,
+            bending_compliance: 0.1,
+            radius: 0.05,
+            friction: 0.3,
+            damping: 0.0,
+            layers: CollisionLayers::default(),
+            start: None,
+            end: None,
+            plasticity: None,
+            segment_rest_lengths: vec![],
+            bend_rest_lengths: vec![],
+            tear_strain: None,
+        }
+    }
+
+    /// Attaches the first particle of the rope to a body.
+    pub fn with_start_attachment(mut self, attachment: ParticleAttachment) -> Self {
+        self.start = Some(attachment);
+        self
+    }
+
+    /// Attaches the last particle of the rope to a body.
+    pub fn with_end_attachment(mut self, attachment: ParticleAttachment) -> Self {
+        self.end = Some(attachment);
+        self
+    }
+
+    /// Sets the compliance of the distance constraints, the inverse of stretch stiffness.
+    pub fn with_stretch_compliance(mut self, compliance: Scalar) -> Self {
+        self.stretch_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the bending constraints, the inverse of bending stiffness.
+    pub fn with_bending_compliance(mut self, compliance: Scalar) -> Self {
+        self.bending_compliance = compliance;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the friction of the particles against colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the layers of the rope used for collisions.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Lets the rope deform permanently when it is stretched or bent beyond the yield strain
+    /// of the given [`Plasticity`].
+    pub fn with_plasticity(mut self, plasticity: Plasticity) -> Self {
+        self.plasticity = Some(plasticity);
+        self
+    }
+
+    /// Returns the rest length of the rope, including plastic deformation.
+    pub fn rest_length(&self) -> Scalar {
+        if self.segment_rest_lengths.is_empty() {
+            self.segment_length * self.particles.len().saturating_sub(1) as Scalar
+        } else {
+            self.segment_rest_lengths.iter().sum()
+        }
+    }
+
+    /// Removes the plastic deformation of the rope, restoring the original rest lengths of its constraints.
+    pub fn reset_deformation(&mut self) {
+        self.segment_rest_lengths.clear();
+        self.bend_rest_lengths.clear();
+    }
+
+    /// Lets the rope tear when the strain of a segment exceeds the given `tear_strain`,
+    /// as a fraction of its rest length.
+    pub fn with_tear_strain(mut self, tear_strain: Scalar) -> Self {
+        self.tear_strain = Some(tear_strain);
+        self
+    }
+
+    /// Splits the rope in two by removing the given segment between the particles at `segment` and `segment + 1`.
+    ///
+    /// The rope keeps the particles before the segment and the start attachment, and the particles after the segment
+    /// are returned as a new rope with the end attachment and the same properties.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rope doesn't have the given segment.
+    pub fn split_off(&mut self, segment: usize) -> Rope {
+        assert!(
+            segment + 1 < self.particles.len(),
+            "segment index {segment} out of bounds for a rope with {} particles",
+            self.particles.len()
+        );
+
+        let particles = self.particles.split_off(segment + 1);
+
+        // The deformed rest lengths of the removed segment and the bending constraints across it are dropped.
+        let segment_rest_lengths = if self.segment_rest_lengths.is_empty() {
+            vec![]
+        } else {
+            let tail = self.segment_rest_lengths.split_off(segment);
+            tail[1..].to_vec()
+        };
+        let bend_rest_lengths = if self.bend_rest_lengths.is_empty() {
+            vec![]
+        } else {
+            let head = segment.saturating_sub(1);
+            let tail = self.bend_rest_lengths.split_off(head);
+            tail.get(segment + 1 - head..)
+                .map_or(vec![], |tail| tail.to_vec())
+        };
+
+        Rope {
+            particles,
+            segment_length: self.segment_length,
+            stretch_compliance: self.stretch_compliance,
+            bending_compliance: self.bending_compliance,
+            radius: self.radius,
+            friction: self.friction,
+            damping: self.damping,
+            layers: self.layers,
+            start: None,
+            end: self.end.take(),
+            plasticity: self.plasticity,
+            segment_rest_lengths,
+            bend_rest_lengths,
+            tear_strain: self.tear_strain,
+        }
+    }
+
+    /// Cuts the rope along the plane going through the given `point` with the given `normal`,
+    /// which is a line in 2D. The rope is [split](Self::split_off) at every segment that crosses the plane.
+    ///
+    /// The rope keeps the particles before the first cut, and the other pieces are returned from the start to the end.
+    pub fn cut(&mut self, point: Vector, normal: Vector) -> Vec<Rope> {
+        let side = |particle: &Particle| (particle.position - point).dot(normal) >= 0.0;
+        let segments: Vec<usize> = self
+            .particles
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| side(&pair[0]) != side(&pair[1]))
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut pieces: Vec<Rope> = segments
+            .into_iter()
+            .rev()
+            .map(|segment| self.split_off(segment))
+            .collect();
+        pieces.reverse();
+        pieces
+    }
+
+    /// Returns the current length of the rope along its particles.
+    pub fn current_length(&self) -> Scalar {
+        self.particles
+            .windows(2)
+            .map(|pair| pair[0].position.distance(pair[1].position))
+            .sum()
+    }
+
+    /// Returns an iterator over the positions of the particles, from the start to the end.
+    pub fn positions(&self) -> impl Iterator<Item = Vector> + '_ {
+        self.particles.iter().map(|particle| particle.position)
+    }
+
+    /// Creates a line strip `Mesh` for rendering the rope, with the vertices at the current positions of the particles.
+    ///
+    /// When the handle of the mesh is added to the rope entity, the mesh is kept in sync with the particles.
+    #[cfg(feature = "deformable-mesh")]
+    pub fn mesh(&self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::LineStrip, RenderAssetUsages::default());
+        let positions: Vec<Vector> = self.positions().collect();
+        write_deformable_mesh(&mut mesh, &positions, &GlobalTransform::IDENTITY, false);
+        mesh
+    }
+}
+
+impl MapEntities for Rope {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for attachment in [&mut self.start, &mut self.end].into_iter().flatten() {
+            attachment.entity = entity_mapper.map_entity(attachment.entity);
+        }
+    }
+}
+
+/// Moves the particles of ropes by their velocities and gravity.
+fn integrate_ropes(mut ropes: Query<&mut Rope>, gravity: Res<Gravity>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut rope in &mut ropes {
+        let damping = rope.damping;
+        integrate_particles(&mut rope.particles, gravity.0, damping, delta_secs);
+    }
+}
+
+/// Solves the distance, bending and attachment constraints of ropes,
+/// and splits the ropes at the segments that are stretched beyond the tear strain.
+pub(crate) fn solve_rope_constraints(
+    mut commands: Commands,
+    mut ropes: Query<(Entity, &mut Rope)>,
+    mut bodies: ParticleBodies,
+    mut torn_events: EventWriter<RopeTorn>,
+    time: Res<Time>,
+    mut torn: Local<Vec<usize>>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for (entity, mut rope) in &mut ropes {
+        let rope = &mut *rope;
+        let segment_length = rope.segment_length;
+        let segments = rope.particles.len().saturating_sub(1);
+        let bends = rope.particles.len().saturating_sub(2);
+
+        torn.clear();
+        if let Some(tear_strain) = rope.tear_strain {
+            for i in 0..segments {
+                let rest_length = rope
+                    .segment_rest_lengths
+                    .get(i)
+                    .copied()
+                    .unwrap_or(segment_length);
+                let length = rope.particles[i]
+                    .position
+                    .distance(rope.particles[i + 1].position);
+                if length > rest_length * (1.0 + tear_strain) {
+                    torn.push(i);
+                }
+            }
+        }
+
+        if let Some(plasticity) = rope.plasticity {
+            rope.segment_rest_lengths.resize(segments, segment_length);
+            rope.bend_rest_lengths.resize(bends, 2.0 * segment_length);
+
+            for i in 0..segments {
+                rope.segment_rest_lengths[i] = deform_particle_distance(
+                    &rope.particles,
+                    [i, i + 1],
+                    rope.segment_rest_lengths[i],
+                    &plasticity,
+                    delta_secs,
+                );
+            }
+            for i in 0..bends {
+                rope.bend_rest_lengths[i] = deform_particle_distance(
+                    &rope.particles,
+                    [i, i + 2],
+                    rope.bend_rest_lengths[i],
+                    &plasticity,
+                    delta_secs,
+                );
+            }
+        }
+
+        for i in 0..segments {
+            if torn.contains(&i) {
+                continue;
+            }
+            solve_particle_distance(
+                &mut rope.particles,
+                [i, i + 1],
+                rope.segment_rest_lengths
+                    .get(i)
+                    .copied()
+                    .unwrap_or(segment_length),
+                false,
+                rope.stretch_compliance,
+                delta_secs,
+            );
+        }
+
+        // Bending is resisted by keeping every other particle apart, but not by pulling them together,
+        // since that is already handled by the distance constraints. Plastic ropes also resist straightening
+        // so that they keep their deformed shape.
+        for i in 0..bends {
+            if torn.contains(&i) || torn.contains(&(i + 1)) {
+                continue;
+            }
+            solve_particle_distance(
+                &mut rope.particles,
+                [i, i + 2],
+                rope.bend_rest_lengths
+                    .get(i)
+                    .copied()
+                    .unwrap_or(2.0 * segment_length),
+                rope.plasticity.is_none(),
+                rope.bending_compliance,
+                delta_secs,
+            );
+        }
+
+        let last = rope.particles.len().saturating_sub(1);
+        for (attachment, index) in [(&mut rope.start, 0), (&mut rope.end, last)] {
+            if let (Some(attachment), Some(particle)) = (attachment, rope.particles.get_mut(index))
+            {
+                solve_attachment(particle, attachment, &mut bodies, delta_secs);
+            }
+        }
+
+        // Split the rope starting from the end so that the indices of the remaining torn segments stay valid.
+        for &segment in torn.iter().rev() {
+            let new_rope = commands.spawn(rope.split_off(segment)).id();
+            torn_events.send(RopeTorn {
+                rope: entity,
+                new_rope,
+                segment,
+            });
+        }
+    }
+}
+
+/// Pushes the particles of ropes out of colliders and applies friction.
+pub(crate) fn collide_ropes(mut ropes: Query<&mut Rope>, mut contacts: ParticleContacts) {
+    for mut rope in &mut ropes {
+        let rope = &mut *rope;
+        let attached: Vec<Entity> = [rope.start, rope.end]
+            .into_iter()
+            .flatten()
+            .map(|attachment| attachment.entity)
+            .collect();
+
+        collide_particles(
+            &mut rope.particles,
+            rope.radius,
+            rope.friction,
+            rope.layers,
+            &attached,
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of the particles of ropes based on their change in position.
+fn update_rope_velocities(mut ropes: Query<&mut Rope>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut rope in &mut ropes {
+        update_particle_velocities(&mut rope.particles, delta_secs);
+    }
+}
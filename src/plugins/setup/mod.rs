@@ -8,13 +8,13 @@ use std::time::Duration;
 
 pub use time::*;
 
-use super::sync::PreviousGlobalTransform;
+use super::{stepper, sync::PreviousGlobalTransform};
 use crate::prelude::*;
 use bevy::{
     ecs::schedule::{ExecutorKind, ScheduleBuildSettings},
     prelude::*,
     transform::TransformSystem,
-    utils::intern::Interned,
+    utils::{intern::Interned, Instant},
 };
 
 /// Sets up the physics engine by initializing the necessary schedules, sets and resources.
@@ -69,6 +69,8 @@ impl Plugin for PhysicsSetupPlugin {
             .init_resource::<SleepingThreshold>()
             .init_resource::<DeactivationTime>()
             .init_resource::<Gravity>()
+            .init_resource::<Wind>()
+            .add_event::<PhysicsStepBudgetExceeded>()
             .register_type::<Time<Physics>>()
             .register_type::<Time<Substeps>>()
             .register_type::<SubstepCount>()
@@ -76,9 +78,13 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<SleepingThreshold>()
             .register_type::<DeactivationTime>()
             .register_type::<Gravity>()
+            .register_type::<Wind>()
+            .register_type::<PhysicsStepBudget>()
             .register_type::<RigidBody>()
             .register_type::<Sleeping>()
             .register_type::<SleepingDisabled>()
+            .register_type::<RigidBodyDisabled>()
+            .register_type::<ColliderDisabled>()
             .register_type::<TimeSleeping>()
             .register_type::<Position>()
             .register_type::<Rotation>()
@@ -90,31 +96,62 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<AngularVelocity>()
             .register_type::<PreSolveLinearVelocity>()
             .register_type::<PreSolveAngularVelocity>()
+            .register_type::<PreviousCenterOfMass>()
             .register_type::<Restitution>()
             .register_type::<Friction>()
+            .register_type::<AnisotropicFriction>()
+            .register_type::<TireFriction>()
+            .register_type::<MaterialId>()
+            .register_type::<SurfaceVelocity>()
+            .register_type::<KinematicTarget>()
             .register_type::<LinearDamping>()
             .register_type::<AngularDamping>()
+            .register_type::<Drag>()
             .register_type::<ExternalForce>()
             .register_type::<ExternalTorque>()
             .register_type::<ExternalImpulse>()
             .register_type::<ExternalAngularImpulse>()
+            .register_type::<ConstantForce>()
+            .register_type::<ConstantLocalForce>()
+            .register_type::<ConstantTorque>()
+            .register_type::<GravityVolume>()
+            .register_type::<GravityVolumeBlending>()
+            .register_type::<WindZone>()
             .register_type::<GravityScale>()
+            .register_type::<TimeScale>()
             .register_type::<Mass>()
             .register_type::<InverseMass>()
             .register_type::<Inertia>()
             .register_type::<InverseInertia>()
             .register_type::<CenterOfMass>()
+            .register_type::<RecomputeMassProperties>()
+            .register_type::<MassChange>()
             .register_type::<ColliderDensity>()
             .register_type::<ColliderMassProperties>()
             .register_type::<LockedAxes>()
+            .register_type::<LockedAxesFrame>()
             .register_type::<ColliderParent>()
             .register_type::<Dominance>()
+            .register_type::<StableId>()
             .register_type::<CollisionLayers>()
             .register_type::<CollidingEntities>()
             .register_type::<CoefficientCombine>()
             .register_type::<Sensor>()
             .register_type::<ColliderTransform>()
-            .register_type::<PreviousColliderTransform>();
+            .register_type::<PreviousColliderTransform>()
+            .register_type::<FixedJoint>()
+            .register_type::<DistanceJoint>()
+            .register_type::<PrismaticJoint>()
+            .register_type::<RevoluteJoint>()
+            .register_type::<SphericalJoint>()
+            .register_type::<Predicted>();
+
+        #[cfg(all(
+            feature = "serialize",
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        app.register_type::<Collider>();
 
         // Configure higher level system sets for the given schedule
         let schedule = self.schedule;
@@ -204,7 +241,7 @@ impl Plugin for PhysicsSetupPlugin {
 }
 
 /// True if a system is running for the first time.
-struct IsFirstRun(bool);
+pub(crate) struct IsFirstRun(bool);
 
 impl Default for IsFirstRun {
     fn default() -> Self {
@@ -213,7 +250,13 @@ impl Default for IsFirstRun {
 }
 
 /// Runs the [`PhysicsSchedule`].
-fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>) {
+pub(crate) fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>) {
+    // When step-through debugging, the simulation is only advanced when requested from the `PhysicsStepper`.
+    if stepper::is_stepping(world) {
+        stepper::run_stepped_physics_schedule(world);
+        return;
+    }
+
     let _ = world.try_schedule_scope(PhysicsSchedule, |world, schedule| {
         let real_delta = world.resource::<Time<Real>>().delta();
         let old_delta = world.resource::<Time<Physics>>().delta();
@@ -255,6 +298,8 @@ fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>)
             }
         }
 
+        let physics_clock_before_step = *world.resource::<Time<Physics>>();
+
         // Advance physics clock by timestep if not paused.
         if !is_paused {
             world.resource_mut::<Time<Physics>>().advance_by(timestep);
@@ -264,10 +309,57 @@ fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>)
             // Set generic `Time` resource to `Time<Physics>`.
             *world.resource_mut::<Time>() = world.resource::<Time<Physics>>().as_generic();
 
+            let budget = world.get_resource::<PhysicsStepBudget>().copied();
+            let start = Instant::now();
+            let mut completed_steps = 0;
+
             // Advance simulation by the number of queued steps.
-            for _ in 0..queued_steps {
+            while completed_steps < queued_steps {
+                // Stop if running another step would exceed the budget,
+                // estimated using the average duration of the steps run so far.
+                if let Some(budget) = budget {
+                    let elapsed = start.elapsed();
+                    if completed_steps > 0
+                        && elapsed + elapsed / completed_steps as u32 > budget.max_duration
+                    {
+                        break;
+                    }
+                }
+
                 trace!("running PhysicsSchedule");
                 schedule.run(world);
+                completed_steps += 1;
+            }
+
+            if let (Some(budget), true) = (budget, completed_steps < queued_steps) {
+                let elapsed = start.elapsed();
+                let remaining_steps = queued_steps - completed_steps;
+
+                if budget.policy == StepBudgetPolicy::Stretch {
+                    // Simulate the time of the remaining steps in one larger step.
+                    // The substeps are computed from the physics clock, so it is advanced
+                    // by the stretched delta for this step and restored afterwards.
+                    let physics_clock = *world.resource::<Time<Physics>>();
+                    let substep_clock = *world.resource::<Time<Substeps>>();
+                    let mut stretched_clock = physics_clock_before_step;
+                    stretched_clock.advance_by(timestep.mul_f64(remaining_steps as f64));
+                    *world.resource_mut::<Time<Physics>>() = stretched_clock;
+                    *world.resource_mut::<Time>() = stretched_clock.as_generic();
+
+                    trace!("running stretched PhysicsSchedule");
+                    schedule.run(world);
+
+                    *world.resource_mut::<Time<Physics>>() = physics_clock;
+                    *world.resource_mut::<Time<Substeps>>() = substep_clock;
+                    *world.resource_mut::<Time>() = physics_clock.as_generic();
+                }
+
+                world.send_event(PhysicsStepBudgetExceeded {
+                    queued_steps,
+                    completed_steps,
+                    policy: budget.policy,
+                    elapsed,
+                });
             }
         }
 
@@ -290,6 +382,12 @@ fn run_physics_schedule(world: &mut World, mut is_first_run: Local<IsFirstRun>)
 fn run_substep_schedule(world: &mut World) {
     let delta = world.resource::<Time<Physics>>().delta();
     let SubstepCount(substeps) = *world.resource::<SubstepCount>();
+
+    if stepper::is_running_step(world) {
+        stepper::run_stepped_substeps(world, substeps);
+        return;
+    }
+
     let sub_delta = delta.div_f64(substeps as f64);
 
     let mut sub_delta_time = world.resource_mut::<Time<Substeps>>();
@@ -458,8 +458,91 @@ impl PhysicsTime for Time<Physics> {
 /// The clock is automatically set as the generic `Time` resource for
 /// the [`SubstepSchedule`].
 #[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Substeps;
 
+/// A resource that bounds the real time spent running physics steps during a single frame
+/// when using [`TimestepMode::Fixed`].
+///
+/// If the app can't keep up with the fixed timestep, the accumulated `overstep` makes physics run
+/// more and more steps each frame, which can make the app freeze under load. When this resource exists,
+/// no more steps are started during a frame once running another step would exceed the
+/// [`max_duration`](Self::max_duration). The remaining steps are handled according to the
+/// [`StepBudgetPolicy`], and a [`PhysicsStepBudgetExceeded`] event is sent.
+///
+/// At least one step is always run per frame when a step is queued.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::{prelude::*, utils::Duration};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         // Spend at most 8 ms on physics per frame, and drop the steps that don't fit
+///         .insert_resource(PhysicsStepBudget {
+///             max_duration: Duration::from_millis(8),
+///             policy: StepBudgetPolicy::Drop,
+///         })
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct PhysicsStepBudget {
+    /// The maximum amount of real time that physics steps can take during a single frame.
+    pub max_duration: Duration,
+    /// Determines what happens to the steps that don't fit in the budget.
+    pub policy: StepBudgetPolicy,
+}
+
+impl Default for PhysicsStepBudget {
+    /// Returns a budget with no limit on the time spent on physics steps.
+    fn default() -> Self {
+        Self {
+            max_duration: Duration::MAX,
+            policy: StepBudgetPolicy::default(),
+        }
+    }
+}
+
+/// Determines what happens to the queued physics steps that don't fit in the [`PhysicsStepBudget`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepBudgetPolicy {
+    /// The steps are dropped, so the simulation falls behind real time and appears to slow down.
+    #[default]
+    Drop,
+    /// The time of the steps is simulated in one additional step with a larger delta time.
+    /// This keeps the simulation in sync with real time, but large steps can reduce
+    /// the accuracy and stability of the simulation.
+    Stretch,
+}
+
+/// An event that is sent when the queued physics steps of a frame didn't fit in the [`PhysicsStepBudget`].
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsStepBudgetExceeded {
+    /// The number of steps that were queued for the frame.
+    pub queued_steps: usize,
+    /// The number of steps that were run normally before the budget was exhausted.
+    pub completed_steps: usize,
+    /// The policy that was used for the remaining steps.
+    pub policy: StepBudgetPolicy,
+    /// The real time spent running the completed steps.
+    pub elapsed: Duration,
+}
+
+impl PhysicsStepBudgetExceeded {
+    /// Returns the number of queued steps that were dropped or merged into a stretched step.
+    pub fn remaining_steps(&self) -> usize {
+        self.queued_steps - self.completed_steps
+    }
+}
+
 pub(crate) trait TimePrecisionAdjusted {
     /// Returns how much time has advanced since the last update
     /// as [`Scalar`] seconds.
@@ -471,9 +554,6 @@ pub(crate) trait DurationPrecisionAdjusted {
     ///
     /// The returned value does include the fractional (nanosecond) part of the duration.
     fn as_secs_adjusted(&self) -> Scalar;
-
-    /// Creates a new Duration from the specified number of seconds represented as [`Scalar`].
-    fn from_secs_adjusted(secs: Scalar) -> Self;
 }
 
 impl TimePrecisionAdjusted for Time {
@@ -502,15 +582,4 @@ impl DurationPrecisionAdjusted for Duration {
             self.as_secs_f64()
         }
     }
-
-    fn from_secs_adjusted(secs: Scalar) -> Self {
-        #[cfg(feature = "f32")]
-        {
-            Self::from_secs_f32(secs)
-        }
-        #[cfg(feature = "f64")]
-        {
-            Self::from_secs_f64(secs)
-        }
-    }
 }
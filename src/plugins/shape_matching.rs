@@ -0,0 +1,519 @@
+//! Simulates deformable bodies that are pulled back towards their rest shape using shape matching.
+//!
+//! See [`ShapeMatchingPlugin`] and [`ShapeMatchingBody`].
+
+#[cfg(feature = "soft-body-mesh")]
+use crate::plugins::soft_body::merge_mesh_vertices;
+use crate::{
+    plugins::particles::{
+        collide_particles, integrate_particles, solve_attachment, update_particle_velocities,
+        ParticleBodies, ParticleContacts,
+    },
+    prelude::*,
+};
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::{intern::Interned, HashMap},
+};
+
+/// Simulates [shape matching bodies](ShapeMatchingBody), which are groups of particles that are pulled back
+/// towards the best rigid transformation of their rest shape.
+///
+/// Shape matching bodies are simulated in the [`SubstepSchedule`] in the same way as [soft bodies](SoftBody):
+///
+/// 1. The particles are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The [clusters](ShapeMatchingCluster) and [attachments](ParticleAttachment) are solved after the [joints]
+/// and soft bodies in [`ParticleSet::ShapeMatching`].
+/// 3. The particles are pushed out of [colliders](Collider) with friction in [`ParticleSet::ShapeMatching`].
+/// 4. The velocities of the particles are updated from their change in position in [`SubstepSet::UpdateVelocities`].
+///
+/// With the `deformable-mesh` feature, the particles of shape matching bodies are written back to the `Mesh`
+/// of the entity by the [`DeformableMeshPlugin`]. Bodies can be created from a `Mesh` with
+/// [`ShapeMatchingBody::from_mesh`] using the `soft-body-mesh` feature.
+pub struct ShapeMatchingPlugin {
+    #[cfg_attr(not(feature = "deformable-mesh"), allow(dead_code))]
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl ShapeMatchingPlugin {
+    /// Creates a [`ShapeMatchingPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for ShapeMatchingPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for ShapeMatchingPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ShapeMatchingBody>()
+            .register_type::<ShapeMatchingCluster>()
+            .register_type::<Particle>()
+            .register_type::<ParticleAttachment>();
+
+        #[cfg(feature = "deformable-mesh")]
+        app.add_plugins(DeformableMeshPlugin::<ShapeMatchingBody>::new(
+            self.schedule,
+        ));
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_shape_matching_bodies.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(
+            (solve_shape_matching, collide_shape_matching_bodies)
+                .chain()
+                .in_set(ParticleSet::ShapeMatching),
+        );
+
+        substeps.add_systems(update_shape_matching_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// A deformable body simulated with shape matching by the [`ShapeMatchingPlugin`].
+///
+/// The particles of the body are divided into [clusters](ShapeMatchingCluster). In each substep, the best rotation
+/// and translation of the rest shape of each cluster is found for the current particle positions, and the particles
+/// are pulled towards their positions in the transformed rest shape based on the [`stiffness`](Self::stiffness).
+///
+/// Shape matching is cheaper and more stable than the edge and volume constraints of a [`SoftBody`], since it can't
+/// tangle or invert, which makes it a good fit for squishy props like jelly, cushions and bouncy toys.
+/// A single cluster behaves like a wobbly rigid body, and [smaller overlapping clusters](Self::with_cluster_size)
+/// let the body bend and deform locally.
+///
+/// Particles can be [attached](ParticleAttachment) to [rigid bodies](RigidBody), and the body collides with
+/// [colliders](Collider) in the same way as [soft bodies](SoftBody). Pinned particles are not moved by shape matching.
+///
+/// The particles are simulated in world space, so the `Transform` of the entity has no effect on the simulation.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+///     // A jelly cube with 5 particles along each axis
+///     let mut positions = vec![];
+#[cfg_attr(feature = "2d", doc = "    for y in 0..5 {")]
+#[cfg_attr(feature = "2d", doc = "        for x in 0..5 {")]
+#[cfg_attr(
+    feature = "2d",
+    doc = "            positions.push(Vector::new(x as Scalar, y as Scalar) * 0.25);"
+)]
+#[cfg_attr(feature = "2d", doc = "        }")]
+#[cfg_attr(feature = "2d", doc = "    }")]
+#[cfg_attr(feature = "3d", doc = "    for z in 0..5 {")]
+#[cfg_attr(feature = "3d", doc = "        for y in 0..5 {")]
+#[cfg_attr(feature = "3d", doc = "            for x in 0..5 {")]
+#[cfg_attr(
+    feature = "3d",
+    doc = "                positions.push(Vector::new(x as Scalar, y as Scalar, z as Scalar) * 0.25);"
+)]
+#[cfg_attr(feature = "3d", doc = "            }")]
+#[cfg_attr(feature = "3d", doc = "        }")]
+#[cfg_attr(feature = "3d", doc = "    }")]
+///
+///     commands.spawn(
+///         ShapeMatchingBody::new(positions, 1.0)
+///             .with_cluster_size(0.5)
+///             .with_stiffness(0.2),
+///     );
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct ShapeMatchingBody {
+    /// The particles of the body.
+    pub particles: Vec<Particle>,
+    /// The positions of the particles in the rest shape.
+    pub rest_positions: Vec<Vector>,
+    /// The groups of particles whose shapes are matched separately.
+    pub clusters: Vec<ShapeMatchingCluster>,
+    /// For bodies created from a `Mesh`, the particle corresponding to each vertex of the mesh.
+    /// If empty, the vertices of the mesh correspond to the particles directly.
+    pub surface_vertices: Vec<usize>,
+    /// The fraction of the distance to the matched rest shape that the particles are moved in each substep,
+    /// between `0.0` and `1.0`. Higher values make the body return to its rest shape more strongly.
+    ///
+    /// The default is `0.1`.
+    pub stiffness: Scalar,
+    /// The radius of the particles used for collisions.
+    pub radius: Scalar,
+    /// The friction of the particles against colliders, between `0.0` and `1.0`.
+    pub friction: Scalar,
+    /// The linear damping of the particles.
+    pub damping: Scalar,
+    /// The layers of the body. The particles only collide with colliders whose layers [interact](CollisionLayers::interacts_with)
+    /// with these layers.
+    pub layers: CollisionLayers,
+    /// The particles attached to bodies, given by their indices and attachments.
+    pub attachments: Vec<(usize, ParticleAttachment)>,
+}
+
+/// A group of particles of a [`ShapeMatchingBody`] that is pulled towards the best rigid transformation
+/// of its rest shape.
+#[derive(Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeMatchingCluster {
+    /// The indices of the particles in the cluster.
+    pub particles: Vec<usize>,
+    /// The rotation of the cluster relative to its rest shape found in the last substep.
+    pub rotation: Rotation,
+}
+
+impl ShapeMatchingCluster {
+    /// Creates a cluster of the particles at the given indices.
+    pub fn new(particles: Vec<usize>) -> Self {
+        Self {
+            particles,
+            rotation: Rotation::default(),
+        }
+    }
+}
+
+impl ShapeMatchingBody {
+    /// Creates a shape matching body from particle `positions`, which are also used as the rest shape.
+    /// All particles belong to a single cluster, and the total `mass` is divided evenly between them.
+    pub fn new(positions: Vec<Vector>, mass: Scalar) -> Self {
+        let particle_mass = mass / positions.len().max(1) as Scalar;
+        let particles = positions
+            .iter()
+            .map(|&position| Particle::new(position, particle_mass))
+            .collect();
+
+        Self {
+            particles,
+            clusters: vec![ShapeMatchingCluster::new((0..positions.len()).collect())],
+            rest_positions: positions,
+            surface_vertices: vec![],
+            stiffness: 0.1,
+            radius: 0.05,
+            friction: 0.3,
+            damping: 0.0,
+            layers: CollisionLayers::default(),
+            attachments: vec![],
+        }
+    }
+
+    /// Creates a shape matching body from the vertices of a `Mesh` with the given total `mass`.
+    /// Vertices of the mesh at the same position are merged into one particle.
+    ///
+    /// Returns `None` if the mesh has no vertices or the positions aren't 3D vectors.
+    #[cfg(feature = "soft-body-mesh")]
+    pub fn from_mesh(mesh: &Mesh, mass: Scalar) -> Option<Self> {
+        let (positions, surface_vertices, _) = merge_mesh_vertices(mesh)?;
+        if positions.is_empty() {
+            return None;
+        }
+
+        let mut body = Self::new(positions, mass);
+        body.surface_vertices = surface_vertices;
+        Some(body)
+    }
+
+    /// Replaces the clusters of the body with clusters of the particles at the given indices.
+    pub fn with_clusters(mut self, clusters: Vec<Vec<usize>>) -> Self {
+        self.clusters = clusters
+            .into_iter()
+            .map(|mut particles| {
+                particles.retain(|&i| i < self.particles.len());
+                ShapeMatchingCluster::new(particles)
+            })
+            .filter(|cluster| cluster.particles.len() > 1)
+            .collect();
+        self
+    }
+
+    /// Replaces the clusters of the body with overlapping clusters based on the rest shape.
+    ///
+    /// The rest shape is divided into a grid of cells with the given `size`, and each cluster contains the particles
+    /// within a cell extended by half of its size in every direction, so neighboring clusters share particles.
+    /// Smaller clusters let the body deform more locally.
+    pub fn with_cluster_size(self, size: Scalar) -> Self {
+        if size <= Scalar::EPSILON {
+            return self;
+        }
+
+        let cell = |position: Vector| (position / size).floor();
+        let mut cells: HashMap<[i64; 3], Vector> = HashMap::default();
+        for &position in self.rest_positions.iter() {
+            let cell = cell(position);
+            #[cfg(feature = "2d")]
+            let key = [cell.x as i64, cell.y as i64, 0];
+            #[cfg(feature = "3d")]
+            let key = [cell.x as i64, cell.y as i64, cell.z as i64];
+            cells.insert(key, cell * size);
+        }
+
+        let mut cells: Vec<([i64; 3], Vector)> = cells.into_iter().collect();
+        cells.sort_by_key(|(key, _)| *key);
+
+        let clusters = cells
+            .into_iter()
+            .map(|(_, min)| {
+                let min = min - Vector::splat(0.5 * size);
+                let max = min + Vector::splat(2.0 * size);
+                (0..self.rest_positions.len())
+                    .filter(|&i| {
+                        let position = self.rest_positions[i];
+                        position.cmpge(min).all() && position.cmple(max).all()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        self.with_clusters(clusters)
+    }
+
+    /// Attaches the particle at the given index to a body.
+    pub fn with_attachment(mut self, index: usize, attachment: ParticleAttachment) -> Self {
+        self.attachments.push((index, attachment));
+        self
+    }
+
+    /// Pins the particle at the given index in place.
+    pub fn with_pinned(mut self, index: usize) -> Self {
+        if let Some(particle) = self.particles.get_mut(index) {
+            particle.inverse_mass = 0.0;
+        }
+        self
+    }
+
+    /// Sets how strongly the body returns to its rest shape, between `0.0` and `1.0`.
+    pub fn with_stiffness(mut self, stiffness: Scalar) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the friction of the particles against colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the layers of the body used for collisions.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Moves all particles of the body by the given `offset`. The rest shape is not affected.
+    pub fn with_offset(mut self, offset: Vector) -> Self {
+        for particle in self.particles.iter_mut() {
+            particle.position += offset;
+            particle.previous_position += offset;
+        }
+        self
+    }
+
+    /// Returns an iterator over the positions of the particles.
+    pub fn positions(&self) -> impl Iterator<Item = Vector> + '_ {
+        self.particles.iter().map(|particle| particle.position)
+    }
+}
+
+impl MapEntities for ShapeMatchingBody {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for (_, attachment) in self.attachments.iter_mut() {
+            attachment.entity = entity_mapper.map_entity(attachment.entity);
+        }
+    }
+}
+
+/// Pulls the particles of a cluster towards the best rigid transformation of their rest positions.
+fn match_cluster(
+    particles: &mut [Particle],
+    rest_positions: &[Vector],
+    cluster: &mut ShapeMatchingCluster,
+    stiffness: Scalar,
+) {
+    // Compute the centers of mass of the current and rest shapes. Pinned particles are ignored.
+    let mut total_mass = 0.0;
+    let mut center = Vector::ZERO;
+    let mut rest_center = Vector::ZERO;
+    for &i in cluster.particles.iter() {
+        if particles[i].inverse_mass > 0.0 {
+            let mass = 1.0 / particles[i].inverse_mass;
+            total_mass += mass;
+            center += particles[i].position * mass;
+            rest_center += rest_positions[i] * mass;
+        }
+    }
+
+    if total_mass <= Scalar::EPSILON {
+        return;
+    }
+
+    center /= total_mass;
+    rest_center /= total_mass;
+
+    // Find the rotation that best maps the rest shape onto the current shape
+    #[cfg(feature = "2d")]
+    {
+        let mut dot = 0.0;
+        let mut cross = 0.0;
+        for &i in cluster.particles.iter() {
+            if particles[i].inverse_mass > 0.0 {
+                let mass = 1.0 / particles[i].inverse_mass;
+                let p = particles[i].position - center;
+                let q = rest_positions[i] - rest_center;
+                dot += mass * q.dot(p);
+                cross += mass * q.perp_dot(p);
+            }
+        }
+        if dot.abs() > Scalar::EPSILON || cross.abs() > Scalar::EPSILON {
+            cluster.rotation = Rotation::from_radians(cross.atan2(dot));
+        }
+    }
+    #[cfg(feature = "3d")]
+    {
+        let mut a = Matrix3::ZERO;
+        for &i in cluster.particles.iter() {
+            if particles[i].inverse_mass > 0.0 {
+                let mass = 1.0 / particles[i].inverse_mass;
+                let p = particles[i].position - center;
+                let q = rest_positions[i] - rest_center;
+                a += Matrix3::from_cols(p * q.x, p * q.y, p * q.z) * mass;
+            }
+        }
+        cluster.rotation = Rotation(extract_rotation(a, cluster.rotation.0, 10));
+    }
+
+    for &i in cluster.particles.iter() {
+        let particle = &mut particles[i];
+        if particle.inverse_mass > 0.0 {
+            let goal = center + cluster.rotation.rotate(rest_positions[i] - rest_center);
+            particle.position += (goal - particle.position) * stiffness;
+        }
+    }
+}
+
+/// Extracts the rotational part of the matrix `a` using the iterative method from
+/// "A Robust Method to Extract the Rotational Part of Deformations" by Müller et al.,
+/// starting from the rotation `q`.
+#[cfg(feature = "3d")]
+fn extract_rotation(a: Matrix3, mut q: Quaternion, iterations: usize) -> Quaternion {
+    for _ in 0..iterations {
+        let r = Matrix3::from_quat(q);
+        let omega = (r.x_axis.cross(a.x_axis)
+            + r.y_axis.cross(a.y_axis)
+            + r.z_axis.cross(a.z_axis))
+            / ((r.x_axis.dot(a.x_axis) + r.y_axis.dot(a.y_axis) + r.z_axis.dot(a.z_axis)).abs()
+                + 1e-9);
+        let angle = omega.length();
+        if angle < 1e-9 {
+            break;
+        }
+        q = (Quaternion::from_axis_angle(omega / angle, angle) * q).normalize();
+    }
+    q
+}
+
+/// Moves the particles of shape matching bodies by their velocities and gravity.
+fn integrate_shape_matching_bodies(
+    mut bodies: Query<&mut ShapeMatchingBody>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut body in &mut bodies {
+        let damping = body.damping;
+        integrate_particles(&mut body.particles, gravity.0, damping, delta_secs);
+    }
+}
+
+/// Solves the clusters and attachments of shape matching bodies.
+fn solve_shape_matching(
+    mut shape_matching_bodies: Query<&mut ShapeMatchingBody>,
+    mut bodies: ParticleBodies,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut body in &mut shape_matching_bodies {
+        let body = &mut *body;
+        let stiffness = body.stiffness.clamp(0.0, 1.0);
+
+        if body.rest_positions.len() == body.particles.len() {
+            for cluster in body.clusters.iter_mut() {
+                match_cluster(
+                    &mut body.particles,
+                    &body.rest_positions,
+                    cluster,
+                    stiffness,
+                );
+            }
+        }
+
+        for (index, attachment) in body.attachments.iter_mut() {
+            if let Some(particle) = body.particles.get_mut(*index) {
+                solve_attachment(particle, attachment, &mut bodies, delta_secs);
+            }
+        }
+    }
+}
+
+/// Pushes the particles of shape matching bodies out of colliders and applies friction.
+fn collide_shape_matching_bodies(
+    mut bodies: Query<&mut ShapeMatchingBody>,
+    mut contacts: ParticleContacts,
+) {
+    for mut body in &mut bodies {
+        let body = &mut *body;
+        let attached: Vec<Entity> = body
+            .attachments
+            .iter()
+            .map(|(_, attachment)| attachment.entity)
+            .collect();
+
+        collide_particles(
+            &mut body.particles,
+            body.radius,
+            body.friction,
+            body.layers,
+            &attached,
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of the particles of shape matching bodies based on their change in position.
+fn update_shape_matching_velocities(mut bodies: Query<&mut ShapeMatchingBody>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut body in &mut bodies {
+        update_particle_velocities(&mut body.particles, delta_secs);
+    }
+}
@@ -3,15 +3,29 @@
 //! See [`SleepingPlugin`].
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{
+    ecs::{component::Tick, query::QueryData, system::SystemChangeTick},
+    prelude::*,
+    utils::HashMap,
+};
 
 /// Controls when bodies should be deactivated and marked as [`Sleeping`] to improve performance.
 ///
-/// Bodies are marked as [`Sleeping`] when their linear and angular velocities are below the [`SleepingThreshold`]
-/// for a duration indicated by [`DeactivationTime`].
+/// Dynamic bodies are grouped into [simulation islands](PhysicsIslands) of bodies that are touching
+/// each other or connected by [joints](joints). Bodies sleep and wake up together with their island:
+/// an island is marked as [`Sleeping`] once the linear and angular velocities of all of its bodies
+/// have been below the [`SleepingThreshold`] for a duration indicated by [`DeactivationTime`],
+/// and when any body in a sleeping island is woken up, the whole island is woken up.
+/// This prevents stacks from being partially asleep, which would make them sink and jitter.
 ///
-/// Bodies are woken up when an active body or constraint interacts with them, or when gravity changes,
+/// Bodies are woken up when an active body or constraint interacts with them, or when gravity or wind changes,
 /// or when the body's position, rotation, velocity, or external forces are changed.
+/// Changes made to sleeping bodies and [joints] between physics steps, like applying an [`ExternalImpulse`],
+/// wake up the affected islands at the start of the next step, so that the change takes effect in the same step
+/// and bodies attached to the changed body don't stay frozen in place.
+///
+/// Contacts between bodies that are sleeping or static are not updated by the narrow phase,
+/// and no constraints are generated for them.
 ///
 /// This plugin does *not* handle constraints waking up bodies. That is done by the [solver].
 ///
@@ -20,14 +34,24 @@ pub struct SleepingPlugin;
 
 impl Plugin for SleepingPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsIslands>();
+
         app.get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first")
+            .add_systems(
+                wake_islands_on_interaction
+                    .after(crate::plugins::correction::apply_state_corrections)
+                    .before(PhysicsStepSet::BroadPhase),
+            )
             .add_systems(wake_on_collision_ended.in_set(PhysicsStepSet::ReportContacts))
             .add_systems(
                 (
+                    update_islands,
                     mark_sleeping_bodies,
                     wake_on_changed,
-                    wake_all_sleeping_bodies.run_if(resource_changed::<Gravity>),
+                    wake_all_sleeping_bodies
+                        .run_if(resource_changed::<Gravity>.or_else(resource_changed::<Wind>)),
+                    wake_islands,
                 )
                     .chain()
                     .in_set(PhysicsStepSet::Sleeping),
@@ -35,26 +59,175 @@ impl Plugin for SleepingPlugin {
     }
 }
 
+/// Simulation islands, which are groups of dynamic bodies that are connected to each other
+/// through contacts or [joints](joints), directly or through other bodies.
+///
+/// Static and kinematic bodies are not included in islands, and they don't connect bodies to each other.
+///
+/// The islands are updated by the [`SleepingPlugin`] at the end of each physics step,
+/// and bodies sleep and wake up together with their island.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicsIslands {
+    /// The bodies of each island, sorted by entity.
+    islands: Vec<Vec<Entity>>,
+    /// The index of the island of each body.
+    body_islands: HashMap<Entity, usize>,
+}
+
+impl PhysicsIslands {
+    /// Returns an iterator over the islands. The bodies in each island are sorted by entity.
+    pub fn iter(&self) -> impl Iterator<Item = &[Entity]> {
+        self.islands.iter().map(Vec::as_slice)
+    }
+
+    /// Returns the index of the island that the given body belongs to,
+    /// or `None` if the body isn't a dynamic body.
+    pub fn island_index(&self, entity: Entity) -> Option<usize> {
+        self.body_islands.get(&entity).copied()
+    }
+
+    /// Returns the bodies in the island that the given body belongs to,
+    /// or `None` if the body isn't a dynamic body.
+    pub fn island(&self, entity: Entity) -> Option<&[Entity]> {
+        self.island_index(entity)
+            .map(|index| self.islands[index].as_slice())
+    }
+
+    /// Returns the number of islands.
+    pub fn len(&self) -> usize {
+        self.islands.len()
+    }
+
+    /// Returns `true` if there are no islands.
+    pub fn is_empty(&self) -> bool {
+        self.islands.is_empty()
+    }
+}
+
+type JointQueries<'w, 's> = (
+    Query<'w, 's, &'static FixedJoint>,
+    Query<'w, 's, &'static DistanceJoint>,
+    Query<'w, 's, &'static PrismaticJoint>,
+    Query<'w, 's, &'static RevoluteJoint>,
+    Query<'w, 's, &'static SphericalJoint>,
+);
+
+/// Groups dynamic bodies into [`PhysicsIslands`] based on their contacts and joints.
+pub fn update_islands(
+    bodies: Query<(Entity, &RigidBody)>,
+    collider_parents: Query<&ColliderParent>,
+    collisions: Res<Collisions>,
+    joints: JointQueries,
+    mut islands: ResMut<PhysicsIslands>,
+) {
+    let mut entities = bodies
+        .iter()
+        .filter(|(_, rb)| rb.is_dynamic())
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    entities.sort();
+
+    // Union-find over the indices of the bodies.
+    let mut parents = (0..entities.len()).collect::<Vec<_>>();
+    let mut link = |entity1: Entity, entity2: Entity| {
+        if let (Ok(index1), Ok(index2)) = (
+            entities.binary_search(&entity1),
+            entities.binary_search(&entity2),
+        ) {
+            union(&mut parents, index1, index2);
+        }
+    };
+
+    for contacts in collisions
+        .get_internal()
+        .values()
+        .filter(|contacts| contacts.during_current_frame)
+    {
+        // Contacts are between colliders, so get the bodies they are attached to.
+        let body1 = collider_parents
+            .get(contacts.entity1)
+            .map_or(contacts.entity1, |parent| parent.get());
+        let body2 = collider_parents
+            .get(contacts.entity2)
+            .map_or(contacts.entity2, |parent| parent.get());
+        link(body1, body2);
+    }
+
+    let (fixed_joints, distance_joints, prismatic_joints, revolute_joints, spherical_joints) =
+        &joints;
+    let joint_entities = fixed_joints
+        .iter()
+        .map(|joint| joint.entities())
+        .chain(distance_joints.iter().map(|joint| joint.entities()))
+        .chain(prismatic_joints.iter().map(|joint| joint.entities()))
+        .chain(revolute_joints.iter().map(|joint| joint.entities()))
+        .chain(spherical_joints.iter().map(|joint| joint.entities()));
+    for [entity1, entity2] in joint_entities {
+        link(entity1, entity2);
+    }
+
+    // Collect the islands in the order of their first body.
+    let islands = &mut *islands;
+    islands.islands.clear();
+    islands.body_islands.clear();
+
+    let mut root_islands = HashMap::<usize, usize>::default();
+    for (index, entity) in entities.iter().enumerate() {
+        let root = find(&mut parents, index);
+        let island_index = *root_islands.entry(root).or_insert_with(|| {
+            islands.islands.push(vec![]);
+            islands.islands.len() - 1
+        });
+        islands.islands[island_index].push(*entity);
+        islands.body_islands.insert(*entity, island_index);
+    }
+}
+
+/// Finds the root of the set containing the given index, compressing the path along the way.
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Merges the sets containing the given indices.
+fn union(parents: &mut [usize], index1: usize, index2: usize) {
+    let root1 = find(parents, index1);
+    let root2 = find(parents, index2);
+    if root1 != root2 {
+        parents[root1.max(root2)] = root1.min(root2);
+    }
+}
+
 type SleepingQueryComponents = (
     Entity,
     &'static RigidBody,
     &'static mut LinearVelocity,
     &'static mut AngularVelocity,
     &'static mut TimeSleeping,
+    Has<Sleeping>,
+    Has<SleepingDisabled>,
 );
 
-/// Adds the [`Sleeping`] component to bodies whose linear and anigular velocities have been
-/// under the [`SleepingThreshold`] for a duration indicated by [`DeactivationTime`].
+/// Adds the [`Sleeping`] component to the bodies of [islands](PhysicsIslands) whose bodies' linear and angular
+/// velocities have all been under the [`SleepingThreshold`] for a duration indicated by [`DeactivationTime`].
+///
+/// Bodies with [`RigidBodyDisabled`] are not simulated, so they never fall asleep.
 pub fn mark_sleeping_bodies(
     mut commands: Commands,
-    mut bodies: Query<SleepingQueryComponents, (Without<Sleeping>, Without<SleepingDisabled>)>,
+    mut bodies: Query<SleepingQueryComponents, Without<RigidBodyDisabled>>,
+    islands: Res<PhysicsIslands>,
     deactivation_time: Res<DeactivationTime>,
     sleep_threshold: Res<SleepingThreshold>,
     dt: Res<Time>,
 ) {
-    for (entity, rb, mut lin_vel, mut ang_vel, mut time_sleeping) in &mut bodies {
-        // Only dynamic bodies can sleep.
-        if !rb.is_dynamic() {
+    for (_, rb, lin_vel, ang_vel, mut time_sleeping, is_sleeping, sleeping_disabled) in &mut bodies
+    {
+        // Only awake dynamic bodies can fall asleep.
+        if !rb.is_dynamic() || is_sleeping || sleeping_disabled {
             continue;
         }
 
@@ -76,12 +249,38 @@ pub fn mark_sleeping_bodies(
         } else {
             time_sleeping.0 = 0.0;
         }
+    }
+
+    for island in islands.iter() {
+        // The island can only sleep if all of its bodies have been still for long enough.
+        let can_sleep = island.iter().all(|entity| {
+            bodies.get(*entity).is_ok_and(
+                |(_, _, _, _, time_sleeping, is_sleeping, sleeping_disabled)| {
+                    !sleeping_disabled && (is_sleeping || time_sleeping.0 > deactivation_time.0)
+                },
+            )
+        });
+
+        if !can_sleep {
+            continue;
+        }
+
+        // Set the bodies to sleep and reset velocities.
+        for entity in island {
+            let Ok((entity, _, mut lin_vel, mut ang_vel, _, is_sleeping, _)) =
+                bodies.get_mut(*entity)
+            else {
+                continue;
+            };
+            if is_sleeping {
+                continue;
+            }
 
-        // If the body has been still for long enough, set it to sleep and reset velocities.
-        if time_sleeping.0 > deactivation_time.0 {
             commands.entity(entity).try_insert(Sleeping);
-            *lin_vel = LinearVelocity::ZERO;
-            *ang_vel = AngularVelocity::ZERO;
+
+            // Bypass change detection so that the velocity change doesn't wake the body up again.
+            *lin_vel.bypass_change_detection() = LinearVelocity::ZERO;
+            *ang_vel.bypass_change_detection() = AngularVelocity::ZERO;
         }
     }
 }
@@ -95,24 +294,188 @@ type WokeUpFilter = Or<(
     Changed<ExternalTorque>,
     Changed<ExternalImpulse>,
     Changed<ExternalAngularImpulse>,
+    Changed<ConstantForce>,
+    Changed<ConstantLocalForce>,
+    Changed<ConstantTorque>,
     Changed<GravityScale>,
 )>;
 
+/// A `WorldQuery` for the components of a sleeping body whose changes wake it up.
+#[derive(QueryData)]
+pub struct WokeUpQuery {
+    sleeping: Ref<'static, Sleeping>,
+    position: Option<Ref<'static, Position>>,
+    rotation: Option<Ref<'static, Rotation>>,
+    linear_velocity: Option<Ref<'static, LinearVelocity>>,
+    angular_velocity: Option<Ref<'static, AngularVelocity>>,
+    external_force: Option<Ref<'static, ExternalForce>>,
+    external_torque: Option<Ref<'static, ExternalTorque>>,
+    external_impulse: Option<Ref<'static, ExternalImpulse>>,
+    external_angular_impulse: Option<Ref<'static, ExternalAngularImpulse>>,
+    constant_force: Option<Ref<'static, ConstantForce>>,
+    constant_local_force: Option<Ref<'static, ConstantLocalForce>>,
+    constant_torque: Option<Ref<'static, ConstantTorque>>,
+    gravity_scale: Option<Ref<'static, GravityScale>>,
+}
+
+impl WokeUpQueryItem<'_> {
+    /// Returns `true` if the body was changed after it fell asleep.
+    ///
+    /// Changes made by the simulation in the step where the body fell asleep, like the solver
+    /// updating its velocity, are older than the [`Sleeping`] component, so they are ignored.
+    fn changed_since_sleeping(&self, this_run: Tick) -> bool {
+        let last_changed = [
+            self.position.as_ref().map(DetectChanges::last_changed),
+            self.rotation.as_ref().map(DetectChanges::last_changed),
+            self.linear_velocity
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.angular_velocity
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.external_force
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.external_torque
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.external_impulse
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.external_angular_impulse
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.constant_force
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.constant_local_force
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.constant_torque
+                .as_ref()
+                .map(DetectChanges::last_changed),
+            self.gravity_scale.as_ref().map(DetectChanges::last_changed),
+        ];
+        last_changed
+            .into_iter()
+            .flatten()
+            .any(|tick| tick.is_newer_than(self.sleeping.last_changed(), this_run))
+    }
+}
+
 /// Removes the [`Sleeping`] component from sleeping bodies when properties like
 /// position, rotation, velocity and external forces are changed.
 #[allow(clippy::type_complexity)]
 pub fn wake_on_changed(
     mut commands: Commands,
-    mut bodies: Query<(Entity, &mut TimeSleeping), (With<Sleeping>, WokeUpFilter)>,
+    mut bodies: Query<(Entity, &mut TimeSleeping, WokeUpQuery), (With<Sleeping>, WokeUpFilter)>,
+    system_ticks: SystemChangeTick,
 ) {
-    for (entity, mut time_sleeping) in &mut bodies {
-        commands.entity(entity).remove::<Sleeping>();
-        time_sleeping.0 = 0.0;
+    for (entity, mut time_sleeping, woke_up) in &mut bodies {
+        if woke_up.changed_since_sleeping(system_ticks.this_run()) {
+            commands.entity(entity).remove::<Sleeping>();
+            time_sleeping.0 = 0.0;
+        }
+    }
+}
+
+type ChangedJointQueries<'w, 's> = (
+    Query<'w, 's, Ref<'static, FixedJoint>, Changed<FixedJoint>>,
+    Query<'w, 's, Ref<'static, DistanceJoint>, Changed<DistanceJoint>>,
+    Query<'w, 's, Ref<'static, PrismaticJoint>, Changed<PrismaticJoint>>,
+    Query<'w, 's, Ref<'static, RevoluteJoint>, Changed<RevoluteJoint>>,
+    Query<'w, 's, Ref<'static, SphericalJoint>, Changed<SphericalJoint>>,
+);
+
+/// Wakes up the [islands](PhysicsIslands) of sleeping bodies that have been interacted with since the previous
+/// physics step, for example by applying external forces or impulses, changing their position or velocity,
+/// or adding or changing a [joint](joints) attached to them.
+///
+/// This runs at the start of the physics step so that the interaction is applied in the same step,
+/// instead of for example an impulse being cleared before the body wakes up.
+/// The islands from the previous step are used, so bodies connected through contacts or joints are woken up together.
+///
+/// Only changes made after a body fell asleep count as interactions, so the changes made by the simulation
+/// in the step where the body fell asleep don't wake it up again.
+pub fn wake_islands_on_interaction(
+    mut commands: Commands,
+    interacted_bodies: Query<(Entity, WokeUpQuery), (With<Sleeping>, WokeUpFilter)>,
+    joints: ChangedJointQueries,
+    mut sleeping: Query<(&mut TimeSleeping, Ref<Sleeping>)>,
+    islands: Res<PhysicsIslands>,
+    system_ticks: SystemChangeTick,
+) {
+    let this_run = system_ticks.this_run();
+
+    let (fixed_joints, distance_joints, prismatic_joints, revolute_joints, spherical_joints) =
+        &joints;
+    let joint_bodies = fixed_joints
+        .iter()
+        .map(|joint| (joint.entities(), joint.last_changed()))
+        .chain(
+            distance_joints
+                .iter()
+                .map(|joint| (joint.entities(), joint.last_changed())),
+        )
+        .chain(
+            prismatic_joints
+                .iter()
+                .map(|joint| (joint.entities(), joint.last_changed())),
+        )
+        .chain(
+            revolute_joints
+                .iter()
+                .map(|joint| (joint.entities(), joint.last_changed())),
+        )
+        .chain(
+            spherical_joints
+                .iter()
+                .map(|joint| (joint.entities(), joint.last_changed())),
+        )
+        .flat_map(|(entities, last_changed)| entities.map(|entity| (entity, last_changed)))
+        // Joints that were last changed by the solver before the body fell asleep don't wake it up.
+        .filter(|(entity, last_changed)| {
+            sleeping.get(*entity).is_ok_and(|(_, sleeping)| {
+                last_changed.is_newer_than(sleeping.last_changed(), this_run)
+            })
+        })
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+
+    let interacted_bodies = interacted_bodies
+        .iter()
+        .filter(|(_, woke_up)| woke_up.changed_since_sleeping(this_run))
+        .map(|(entity, _)| entity);
+
+    let mut woken_islands = vec![];
+    let mut woken_bodies = vec![];
+
+    for entity in interacted_bodies.chain(joint_bodies) {
+        match islands.island_index(entity) {
+            Some(island_index) => woken_islands.push(island_index),
+            // Bodies that aren't in an island, like bodies that were added after the islands were updated,
+            // are woken up on their own.
+            None => woken_bodies.push(entity),
+        }
+    }
+
+    woken_islands.sort_unstable();
+    woken_islands.dedup();
+
+    let island_bodies = woken_islands
+        .into_iter()
+        .flat_map(|island_index| islands.islands[island_index].iter().copied());
+
+    for entity in woken_bodies.into_iter().chain(island_bodies) {
+        if let Ok((mut time_sleeping, _)) = sleeping.get_mut(entity) {
+            commands.entity(entity).remove::<Sleeping>();
+            time_sleeping.0 = 0.0;
+        }
     }
 }
 
 /// Removes the [`Sleeping`] component from all sleeping bodies.
-/// Triggered automatically when [`Gravity`] is changed.
+/// Triggered automatically when [`Gravity`] or [`Wind`] is changed.
 fn wake_all_sleeping_bodies(
     mut commands: Commands,
     mut bodies: Query<(Entity, &mut TimeSleeping), With<Sleeping>>,
@@ -123,6 +486,29 @@ fn wake_all_sleeping_bodies(
     }
 }
 
+/// Wakes up all bodies in [islands](PhysicsIslands) that contain both sleeping and awake bodies,
+/// so that islands always sleep and wake up as a whole.
+pub fn wake_islands(
+    mut commands: Commands,
+    mut bodies: Query<(&mut TimeSleeping, Has<Sleeping>)>,
+    islands: Res<PhysicsIslands>,
+) {
+    for island in islands.iter() {
+        let is_awake = |entity: &Entity| bodies.get(*entity).is_ok_and(|(_, sleeping)| !sleeping);
+
+        if island.iter().all(is_awake) || !island.iter().any(is_awake) {
+            continue;
+        }
+
+        for entity in island {
+            if let Ok((mut time_sleeping, true)) = bodies.get_mut(*entity) {
+                commands.entity(*entity).remove::<Sleeping>();
+                time_sleeping.0 = 0.0;
+            }
+        }
+    }
+}
+
 /// Wakes up bodies when they stop colliding.
 fn wake_on_collision_ended(
     mut commands: Commands,
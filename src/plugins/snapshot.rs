@@ -0,0 +1,595 @@
+//! Capturing and restoring the state of the physics simulation using [`PhysicsSnapshot`],
+//! and stepping and resimulating the simulation manually for rollback.
+//!
+//! See [`PhysicsSnapshot`] and [`Physics::resimulate`] for more information.
+
+use std::time::Duration;
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{query::QueryItem, world::EntityWorldMut},
+    prelude::*,
+};
+
+/// A snapshot of the state of the physics simulation that can be [captured](PhysicsSnapshot::capture)
+/// and [restored](PhysicsSnapshot::restore) later.
+///
+/// This is useful for things like rollback networking, save states and replays.
+///
+/// A snapshot contains:
+///
+/// - The [state](BodySnapshot) of each [rigid body](RigidBody): positions, rotations, velocities,
+/// external and constant forces and impulses, time scale, and sleeping state
+/// - The state of all [joints](joints), including their Lagrange multipliers and the forces exerted by them
+/// - The impulses and explosions queued using [`PhysicsCommands`] that haven't been applied yet
+/// - The state of [particles](Particle), [ropes](Rope), [cloth](Cloth), [soft bodies](SoftBody)
+/// and [shape matching bodies](ShapeMatchingBody)
+/// - The [`Collisions`] resource, containing the contacts of the current and previous frame
+/// - The [`PhysicsIslands`] used for sleeping
+/// - The [`ContactManifoldCaches`] used to warm start collision detection
+///
+/// The snapshot only stores the simulation state. Configuration like [colliders](Collider),
+/// [mass properties](MassPropertiesBundle) or [`Gravity`] is not included and is not modified
+/// when restoring a snapshot. Restoring a snapshot brings the simulation back to exactly
+/// the captured state, so resimulating from it reproduces the original simulation.
+///
+/// The contact manifold caches and queued explosions are opaque, so they are not serialized
+/// and are ignored when comparing snapshots.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct SaveState(PhysicsSnapshot);
+///
+/// fn save(world: &mut World) {
+///     let snapshot = PhysicsSnapshot::capture(world);
+///     world.insert_resource(SaveState(snapshot));
+/// }
+///
+/// fn load(world: &mut World) {
+///     world.resource_scope(|world, save_state: Mut<SaveState>| {
+///         save_state.0.restore(world);
+///     });
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicsSnapshot {
+    /// The states of rigid bodies, sorted by entity.
+    pub(crate) bodies: Vec<(Entity, BodySnapshot)>,
+    fixed_joints: Vec<(Entity, FixedJoint)>,
+    distance_joints: Vec<(Entity, DistanceJoint)>,
+    prismatic_joints: Vec<(Entity, PrismaticJoint)>,
+    revolute_joints: Vec<(Entity, RevoluteJoint)>,
+    spherical_joints: Vec<(Entity, SphericalJoint)>,
+    queued_impulses: Vec<QueuedImpulse>,
+    #[cfg(all(
+        feature = "default-collider",
+        any(feature = "parry-f32", feature = "parry-f64")
+    ))]
+    particles: ParticleSnapshot,
+    collisions: Option<Collisions>,
+    islands: Option<PhysicsIslands>,
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    internal: InternalState,
+}
+
+/// Engine state stored in a [`PhysicsSnapshot`] that can't be compared or serialized.
+///
+/// It is restored together with the rest of the snapshot, but two snapshots are considered equal
+/// regardless of it.
+#[derive(Clone, Default)]
+struct InternalState {
+    #[cfg(feature = "default-collider")]
+    manifold_caches: Option<ContactManifoldCaches<Collider>>,
+    explosions: Option<QueuedExplosions>,
+}
+
+impl PartialEq for InternalState {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Debug for InternalState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InternalState").finish_non_exhaustive()
+    }
+}
+
+/// The state of [particles](Particle) and the deformable bodies made of them, stored in a [`PhysicsSnapshot`].
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+struct ParticleSnapshot {
+    particles: Vec<(Entity, Particle)>,
+    ropes: Vec<(Entity, Rope)>,
+    cloths: Vec<(Entity, Cloth)>,
+    soft_bodies: Vec<(Entity, SoftBody)>,
+    shape_matching_bodies: Vec<(Entity, ShapeMatchingBody)>,
+}
+
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+impl ParticleSnapshot {
+    fn capture(world: &mut World) -> Self {
+        Self {
+            particles: capture_components(world),
+            ropes: capture_components(world),
+            cloths: capture_components(world),
+            soft_bodies: capture_components(world),
+            shape_matching_bodies: capture_components(world),
+        }
+    }
+
+    fn restore(&self, world: &mut World) {
+        restore_components(world, &self.particles);
+        restore_components(world, &self.ropes);
+        restore_components(world, &self.cloths);
+        restore_components(world, &self.soft_bodies);
+        restore_components(world, &self.shape_matching_bodies);
+    }
+}
+
+/// The state of a single [rigid body](RigidBody) stored in a [`PhysicsSnapshot`].
+///
+/// Optional components are `None` if the body didn't have them when the snapshot was captured,
+/// and they are removed from the body when the snapshot is restored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BodySnapshot {
+    /// The [`Position`] of the body.
+    pub position: Position,
+    /// The [`Rotation`] of the body.
+    pub rotation: Rotation,
+    /// The [`PreviousPosition`] of the body.
+    pub previous_position: Option<PreviousPosition>,
+    /// The [`PreviousRotation`] of the body.
+    pub previous_rotation: Option<PreviousRotation>,
+    /// The [`AccumulatedTranslation`] of the body.
+    pub accumulated_translation: Option<AccumulatedTranslation>,
+    /// The [`LinearVelocity`] of the body.
+    pub linear_velocity: Option<LinearVelocity>,
+    /// The [`AngularVelocity`] of the body.
+    pub angular_velocity: Option<AngularVelocity>,
+    /// The [`ExternalForce`] applied to the body.
+    pub external_force: Option<ExternalForce>,
+    /// The [`ExternalTorque`] applied to the body.
+    pub external_torque: Option<ExternalTorque>,
+    /// The [`ExternalImpulse`] applied to the body.
+    pub external_impulse: Option<ExternalImpulse>,
+    /// The [`ExternalAngularImpulse`] applied to the body.
+    pub external_angular_impulse: Option<ExternalAngularImpulse>,
+    /// The [`ConstantForce`] applied to the body.
+    pub constant_force: Option<ConstantForce>,
+    /// The [`ConstantLocalForce`] applied to the body.
+    pub constant_local_force: Option<ConstantLocalForce>,
+    /// The [`ConstantTorque`] applied to the body.
+    pub constant_torque: Option<ConstantTorque>,
+    /// The [`TimeScale`] of the body.
+    pub time_scale: Option<TimeScale>,
+    /// The [`TimeSleeping`] of the body.
+    pub time_sleeping: Option<TimeSleeping>,
+    /// True if the body was [`Sleeping`].
+    pub sleeping: bool,
+}
+
+pub(crate) type BodySnapshotQueryData = (
+    Entity,
+    &'static Position,
+    &'static Rotation,
+    Option<&'static PreviousPosition>,
+    Option<&'static PreviousRotation>,
+    Option<&'static AccumulatedTranslation>,
+    Option<&'static LinearVelocity>,
+    Option<&'static AngularVelocity>,
+    // Nested to stay within the maximum number of elements in a query data tuple
+    (
+        Option<&'static ExternalForce>,
+        Option<&'static ExternalTorque>,
+        Option<&'static ExternalImpulse>,
+        Option<&'static ExternalAngularImpulse>,
+        Option<&'static ConstantForce>,
+        Option<&'static ConstantLocalForce>,
+        Option<&'static ConstantTorque>,
+    ),
+    Option<&'static TimeScale>,
+    Option<&'static TimeSleeping>,
+    Has<Sleeping>,
+);
+
+impl BodySnapshot {
+    /// Creates a [`BodySnapshot`] from the components of a rigid body.
+    pub(crate) fn from_query_item(item: QueryItem<'_, BodySnapshotQueryData>) -> (Entity, Self) {
+        let (
+            entity,
+            position,
+            rotation,
+            previous_position,
+            previous_rotation,
+            accumulated_translation,
+            linear_velocity,
+            angular_velocity,
+            (
+                external_force,
+                external_torque,
+                external_impulse,
+                external_angular_impulse,
+                constant_force,
+                constant_local_force,
+                constant_torque,
+            ),
+            time_scale,
+            time_sleeping,
+            sleeping,
+        ) = item;
+
+        (
+            entity,
+            Self {
+                position: *position,
+                rotation: *rotation,
+                previous_position: previous_position.copied(),
+                previous_rotation: previous_rotation.copied(),
+                accumulated_translation: accumulated_translation.copied(),
+                linear_velocity: linear_velocity.copied(),
+                angular_velocity: angular_velocity.copied(),
+                external_force: external_force.copied(),
+                external_torque: external_torque.copied(),
+                external_impulse: external_impulse.copied(),
+                external_angular_impulse: external_angular_impulse.copied(),
+                constant_force: constant_force.copied(),
+                constant_local_force: constant_local_force.copied(),
+                constant_torque: constant_torque.copied(),
+                time_scale: time_scale.copied(),
+                time_sleeping: time_sleeping.copied(),
+                sleeping,
+            },
+        )
+    }
+
+    /// Restores the state of the given rigid body to the stored state.
+    pub(crate) fn restore(&self, entity_mut: &mut EntityWorldMut) {
+        restore_component(entity_mut, Some(self.position));
+        restore_component(entity_mut, Some(self.rotation));
+        restore_component(entity_mut, self.previous_position);
+        restore_component(entity_mut, self.previous_rotation);
+        restore_component(entity_mut, self.accumulated_translation);
+        restore_component(entity_mut, self.linear_velocity);
+        restore_component(entity_mut, self.angular_velocity);
+        restore_component(entity_mut, self.external_force);
+        restore_component(entity_mut, self.external_torque);
+        restore_component(entity_mut, self.external_impulse);
+        restore_component(entity_mut, self.external_angular_impulse);
+        restore_component(entity_mut, self.constant_force);
+        restore_component(entity_mut, self.constant_local_force);
+        restore_component(entity_mut, self.constant_torque);
+        restore_component(entity_mut, self.time_scale);
+        restore_component(entity_mut, self.time_sleeping);
+        restore_component(entity_mut, self.sleeping.then_some(Sleeping));
+    }
+}
+
+impl PhysicsSnapshot {
+    /// Captures the current state of the physics simulation in the given `world`.
+    pub fn capture(world: &mut World) -> Self {
+        let mut query = world.query_filtered::<BodySnapshotQueryData, With<RigidBody>>();
+        let mut bodies = query
+            .iter(world)
+            .map(BodySnapshot::from_query_item)
+            .collect::<Vec<_>>();
+        bodies.sort_by_key(|(entity, _)| *entity);
+
+        Self {
+            bodies,
+            fixed_joints: capture_components(world),
+            distance_joints: capture_components(world),
+            prismatic_joints: capture_components(world),
+            revolute_joints: capture_components(world),
+            spherical_joints: capture_components(world),
+            queued_impulses: world
+                .get_resource::<QueuedImpulses>()
+                .map_or(vec![], |queue| queue.iter().copied().collect()),
+            #[cfg(all(
+                feature = "default-collider",
+                any(feature = "parry-f32", feature = "parry-f64")
+            ))]
+            particles: ParticleSnapshot::capture(world),
+            collisions: world.get_resource::<Collisions>().cloned(),
+            islands: world.get_resource::<PhysicsIslands>().cloned(),
+            internal: InternalState {
+                #[cfg(feature = "default-collider")]
+                manifold_caches: world
+                    .get_resource::<ContactManifoldCaches<Collider>>()
+                    .cloned(),
+                explosions: world.get_resource::<QueuedExplosions>().cloned(),
+            },
+        }
+    }
+
+    /// Captures the state of the given rigid bodies, the [joints](joints) attached to them
+    /// and the impulses queued for them using [`PhysicsCommands`].
+    ///
+    /// Entities that aren't rigid bodies are ignored. Unlike [`PhysicsSnapshot::capture`], the [`Collisions`],
+    /// [`PhysicsIslands`] and [`ContactManifoldCaches`] are not captured, so restoring a partial snapshot
+    /// doesn't affect the contacts of other bodies.
+    ///
+    /// This is useful for things like [client-side prediction](crate::plugins::prediction),
+    /// where only a subset of the world is rolled back.
+    pub fn capture_entities(world: &mut World, entities: impl IntoIterator<Item = Entity>) -> Self {
+        let mut query = world.query_filtered::<BodySnapshotQueryData, With<RigidBody>>();
+        let mut bodies = entities
+            .into_iter()
+            .filter_map(|entity| query.get(world, entity).ok())
+            .map(BodySnapshot::from_query_item)
+            .collect::<Vec<_>>();
+        bodies.sort_by_key(|(entity, _)| *entity);
+        bodies.dedup_by_key(|(entity, _)| *entity);
+
+        let mut snapshot = Self {
+            bodies,
+            ..default()
+        };
+        snapshot.fixed_joints = snapshot.capture_attached_joints(world);
+        snapshot.distance_joints = snapshot.capture_attached_joints(world);
+        snapshot.prismatic_joints = snapshot.capture_attached_joints(world);
+        snapshot.revolute_joints = snapshot.capture_attached_joints(world);
+        snapshot.spherical_joints = snapshot.capture_attached_joints(world);
+        if let Some(queue) = world.get_resource::<QueuedImpulses>() {
+            snapshot.queued_impulses = queue
+                .iter()
+                .filter(|queued| snapshot.body(queued.entity).is_some())
+                .copied()
+                .collect();
+        }
+        snapshot
+    }
+
+    /// Clones all joints of type `J` that are attached to at least one body in the snapshot.
+    fn capture_attached_joints<J: Joint + Clone>(&self, world: &mut World) -> Vec<(Entity, J)> {
+        let mut joints = capture_components::<J>(world);
+        joints.retain(|(_, joint)| {
+            joint
+                .entities()
+                .iter()
+                .any(|entity| self.body(*entity).is_some())
+        });
+        joints
+    }
+
+    /// Restores the state of the physics simulation in the given `world` to the state stored in the snapshot.
+    ///
+    /// Entities that have been despawned since the snapshot was captured are skipped,
+    /// and bodies that have been spawned after the snapshot was captured are not modified.
+    pub fn restore(&self, world: &mut World) {
+        for (entity, body) in self.bodies.iter() {
+            let Some(mut entity_mut) = world.get_entity_mut(*entity) else {
+                continue;
+            };
+
+            body.restore(&mut entity_mut);
+        }
+
+        restore_components(world, &self.fixed_joints);
+        restore_components(world, &self.distance_joints);
+        restore_components(world, &self.prismatic_joints);
+        restore_components(world, &self.revolute_joints);
+        restore_components(world, &self.spherical_joints);
+
+        // Replace the impulses queued for the bodies in the snapshot
+        if let Some(mut queue) = world.get_resource_mut::<QueuedImpulses>() {
+            queue.retain(|queued| self.body(queued.entity).is_none());
+            for queued in self.queued_impulses.iter() {
+                queue.push(*queued);
+            }
+        }
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        self.particles.restore(world);
+
+        if let Some(collisions) = &self.collisions {
+            if let Some(mut current) = world.get_resource_mut::<Collisions>() {
+                current.set_if_neq(collisions.clone());
+            } else {
+                world.insert_resource(collisions.clone());
+            }
+        }
+
+        if let Some(islands) = &self.islands {
+            world.insert_resource(islands.clone());
+        }
+
+        #[cfg(feature = "default-collider")]
+        if let Some(manifold_caches) = &self.internal.manifold_caches {
+            world.insert_resource(manifold_caches.clone());
+        }
+
+        if let Some(explosions) = &self.internal.explosions {
+            world.insert_resource(explosions.clone());
+        }
+    }
+
+    /// Returns the stored state of the given rigid body, or `None` if the body isn't in the snapshot.
+    pub fn body(&self, entity: Entity) -> Option<&BodySnapshot> {
+        self.bodies
+            .binary_search_by_key(&entity, |(entity, _)| *entity)
+            .ok()
+            .map(|index| &self.bodies[index].1)
+    }
+
+    /// Returns a mutable reference to the stored state of the given rigid body,
+    /// or `None` if the body isn't in the snapshot.
+    ///
+    /// This can be used to apply authoritative state from a server to a snapshot before resimulating.
+    pub fn body_mut(&mut self, entity: Entity) -> Option<&mut BodySnapshot> {
+        self.bodies
+            .binary_search_by_key(&entity, |(entity, _)| *entity)
+            .ok()
+            .map(|index| &mut self.bodies[index].1)
+    }
+
+    /// Returns an iterator over the stored states of all rigid bodies, sorted by entity.
+    pub fn bodies(&self) -> impl Iterator<Item = (Entity, &BodySnapshot)> {
+        self.bodies.iter().map(|(entity, body)| (*entity, body))
+    }
+}
+
+impl Physics {
+    /// Immediately advances the physics simulation by a single step with the given `delta` time
+    /// by running the [`PhysicsSchedule`] once.
+    ///
+    /// The [`Time<Physics>`](Physics) clock is advanced by `delta`, regardless of whether
+    /// it is [paused](PhysicsTime::pause) or what its [timestep mode](TimestepMode) is.
+    /// This makes it possible to drive the simulation manually, for example for rollback networking.
+    /// In that case, it can be useful to pause the physics clock to stop the [`PhysicsPlugins`]
+    /// from also stepping the simulation.
+    ///
+    /// Note that systems outside of the [`PhysicsSchedule`], like the ones in [`PhysicsSet::Prepare`]
+    /// and [`PhysicsSet::Sync`], are not run.
+    ///
+    /// Does nothing if the [`PhysicsSchedule`] doesn't exist or is already running.
+    pub fn step(world: &mut World, delta: Duration) {
+        let _ = world.try_schedule_scope(PhysicsSchedule, |world, schedule| {
+            let old_clock = world.resource::<Time>().as_generic();
+
+            world.resource_mut::<Time<Physics>>().advance_by(delta);
+            *world.resource_mut::<Time>() = world.resource::<Time<Physics>>().as_generic();
+
+            schedule.run(world);
+
+            // If physics is paused, reset delta time so that the step isn't repeated
+            // by the `PhysicsSchedule` runner.
+            if world.resource::<Time<Physics>>().is_paused() {
+                world
+                    .resource_mut::<Time<Physics>>()
+                    .advance_by(Duration::ZERO);
+            }
+
+            // Set generic `Time` resource back to the clock that was active before physics.
+            *world.resource_mut::<Time>() = old_clock;
+        });
+    }
+
+    /// Rewinds the physics simulation to the given `snapshot` and immediately re-steps it
+    /// `steps` times with the given `delta` time.
+    ///
+    /// `before_step` is called with the world and the index of the step before each step,
+    /// which can be used to re-apply the inputs of the corresponding frame.
+    ///
+    /// All steps are run within the call, so rollback networking libraries can resimulate several
+    /// frames in a single render frame. The [`PhysicsSchedule`] and its allocations are reused between steps,
+    /// and as long as the inputs are identical, the result is deterministic on the same machine.
+    /// See [`Physics::step`] for more details.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct ConfirmedState {
+    ///     snapshot: PhysicsSnapshot,
+    ///     inputs: Vec<LinearVelocity>,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// fn rollback(world: &mut World) {
+    ///     world.resource_scope(|world, state: Mut<ConfirmedState>| {
+    ///         Physics::resimulate(
+    ///             world,
+    ///             &state.snapshot,
+    ///             state.inputs.len(),
+    ///             Duration::from_secs_f64(1.0 / 60.0),
+    ///             |world, step| {
+    ///                 // Re-apply the input of each frame
+    ///                 let mut query = world.query_filtered::<&mut LinearVelocity, With<Player>>();
+    ///                 for mut velocity in query.iter_mut(world) {
+    ///                     *velocity = state.inputs[step];
+    ///                 }
+    ///             },
+    ///         );
+    ///     });
+    /// }
+    /// ```
+    pub fn resimulate(
+        world: &mut World,
+        snapshot: &PhysicsSnapshot,
+        steps: usize,
+        delta: Duration,
+        mut before_step: impl FnMut(&mut World, usize),
+    ) {
+        snapshot.restore(world);
+
+        for step in 0..steps {
+            before_step(world, step);
+            Self::step(world, delta);
+        }
+    }
+}
+
+/// Clones all components of type `C` in the world, sorted by entity.
+fn capture_components<C: Component + Clone>(world: &mut World) -> Vec<(Entity, C)> {
+    let mut query = world.query::<(Entity, &C)>();
+    let mut components = query
+        .iter(world)
+        .map(|(entity, component)| (entity, component.clone()))
+        .collect::<Vec<_>>();
+    components.sort_by_key(|(entity, _)| *entity);
+    components
+}
+
+/// Restores the given components for entities that still exist.
+fn restore_components<C: Component + Clone + PartialEq>(
+    world: &mut World,
+    components: &[(Entity, C)],
+) {
+    for (entity, component) in components {
+        if let Some(mut entity_mut) = world.get_entity_mut(*entity) {
+            restore_component(&mut entity_mut, Some(component.clone()));
+        }
+    }
+}
+
+/// Sets, inserts or removes a component depending on the stored value.
+///
+/// Components are only modified if they differ from the stored value to avoid triggering change detection,
+/// which would for example wake up sleeping bodies.
+pub(crate) fn restore_component<C: Component + PartialEq>(
+    entity_mut: &mut EntityWorldMut,
+    component: Option<C>,
+) {
+    match component {
+        Some(component) if entity_mut.contains::<C>() => {
+            entity_mut.get_mut::<C>().unwrap().set_if_neq(component);
+        }
+        Some(component) => {
+            entity_mut.insert(component);
+        }
+        None => {
+            entity_mut.remove::<C>();
+        }
+    }
+}
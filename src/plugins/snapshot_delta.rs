@@ -0,0 +1,320 @@
+//! Compact, quantized differences between two [`PhysicsSnapshot`]s for sending physics state over the network.
+//!
+//! See [`SnapshotDelta`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+#[cfg(feature = "2d")]
+type QuantizedVector = [i32; 2];
+#[cfg(feature = "3d")]
+type QuantizedVector = [i32; 3];
+
+#[cfg(feature = "2d")]
+type QuantizedAngularVelocity = i32;
+#[cfg(feature = "3d")]
+type QuantizedAngularVelocity = [i32; 3];
+
+/// The rotation angle scaled to the range of `i16`.
+#[cfg(feature = "2d")]
+type QuantizedRotation = i16;
+/// The quaternion components scaled to the range of `i16`.
+#[cfg(feature = "3d")]
+type QuantizedRotation = [i16; 4];
+
+/// A compact difference between two [`PhysicsSnapshot`]s, containing only the rigid bodies
+/// that have moved, with their states quantized to integers.
+///
+/// Deltas are useful for networked games where a server broadcasts the state of the simulation
+/// to clients at a fixed rate. Instead of sending the full state every time, the server can
+/// [compute a delta](PhysicsSnapshot::delta_from) against the last snapshot that a client has acknowledged,
+/// and the client can [apply the delta](PhysicsSnapshot::apply_delta) to its copy of that snapshot
+/// to reconstruct the current state.
+///
+/// A delta contains:
+///
+/// - The [`Position`], [`Rotation`], [`LinearVelocity`], [`AngularVelocity`] and sleeping state
+/// of each body whose quantized state differs from the base snapshot, or that isn't in the base snapshot
+/// - The entities of the bodies that have been removed since the base snapshot
+///
+/// Positions and velocities are quantized using the [`quantization`](Self::quantization) step,
+/// and rotations are quantized to 16-bit integers. Joints and [`Collisions`] are not included,
+/// so they are kept as they are in the base snapshot.
+///
+/// Entities are stored as they are in the snapshots. If the peers have different entities,
+/// the entities should be mapped, for example using [`SnapshotDelta::map_entities`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// /// The last snapshot that the client has acknowledged.
+/// #[derive(Resource)]
+/// struct AckedSnapshot(PhysicsSnapshot);
+///
+/// // On the server
+/// fn send_state(world: &mut World) {
+///     let snapshot = PhysicsSnapshot::capture(world);
+///     let acked = world.resource::<AckedSnapshot>();
+///
+///     // Quantize positions and velocities to millimeters
+///     let delta = snapshot.delta_from(&acked.0, 0.001);
+///     // Send the delta to the client...
+/// }
+///
+/// // On the client
+/// fn receive_state(world: &mut World, delta: SnapshotDelta) {
+///     let mut snapshot = world.resource::<AckedSnapshot>().0.clone();
+///     snapshot.apply_delta(&delta);
+///     snapshot.restore(world);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotDelta {
+    /// The quantization step used for positions and velocities.
+    pub quantization: Scalar,
+    /// The bodies that have moved or been added since the base snapshot, sorted by entity.
+    bodies: Vec<BodyDelta>,
+    /// The bodies that have been removed since the base snapshot, sorted by entity.
+    removed: Vec<Entity>,
+}
+
+/// The quantized state of a rigid body stored in a [`SnapshotDelta`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BodyDelta {
+    /// The entity of the body.
+    pub entity: Entity,
+    position: QuantizedVector,
+    rotation: QuantizedRotation,
+    linear_velocity: QuantizedVector,
+    angular_velocity: QuantizedAngularVelocity,
+    sleeping: bool,
+}
+
+impl BodyDelta {
+    /// Quantizes the state of the given body.
+    fn new(entity: Entity, body: &BodySnapshot, quantization: Scalar) -> Self {
+        let quantize = |value: Scalar| (value / quantization).round() as i32;
+        let linear_velocity = body.linear_velocity.unwrap_or_default();
+        let angular_velocity = body.angular_velocity.unwrap_or_default();
+
+        Self {
+            entity,
+            position: body.position.to_array().map(quantize),
+            rotation: quantize_rotation(&body.rotation),
+            linear_velocity: linear_velocity.to_array().map(quantize),
+            #[cfg(feature = "2d")]
+            angular_velocity: quantize(angular_velocity.0),
+            #[cfg(feature = "3d")]
+            angular_velocity: angular_velocity.to_array().map(quantize),
+            sleeping: body.sleeping,
+        }
+    }
+
+    /// Returns `true` if the quantized states of the bodies are equal.
+    fn state_eq(&self, other: &Self) -> bool {
+        self.position == other.position
+            && self.rotation == other.rotation
+            && self.linear_velocity == other.linear_velocity
+            && self.angular_velocity == other.angular_velocity
+            && self.sleeping == other.sleeping
+    }
+
+    /// Returns the [`Position`] of the body.
+    pub fn position(&self, quantization: Scalar) -> Position {
+        Position(Vector::from_array(
+            self.position.map(|value| value as Scalar * quantization),
+        ))
+    }
+
+    /// Returns the [`Rotation`] of the body.
+    pub fn rotation(&self) -> Rotation {
+        dequantize_rotation(self.rotation)
+    }
+
+    /// Returns the [`LinearVelocity`] of the body.
+    pub fn linear_velocity(&self, quantization: Scalar) -> LinearVelocity {
+        LinearVelocity(Vector::from_array(
+            self.linear_velocity
+                .map(|value| value as Scalar * quantization),
+        ))
+    }
+
+    /// Returns the [`AngularVelocity`] of the body.
+    pub fn angular_velocity(&self, quantization: Scalar) -> AngularVelocity {
+        #[cfg(feature = "2d")]
+        {
+            AngularVelocity(self.angular_velocity as Scalar * quantization)
+        }
+        #[cfg(feature = "3d")]
+        {
+            AngularVelocity(Vector::from_array(
+                self.angular_velocity
+                    .map(|value| value as Scalar * quantization),
+            ))
+        }
+    }
+
+    /// Returns `true` if the body is [`Sleeping`].
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping
+    }
+
+    /// Applies the state to the given body.
+    fn apply(&self, body: &mut BodySnapshot, quantization: Scalar) {
+        body.position = self.position(quantization);
+        body.rotation = self.rotation();
+        body.linear_velocity = Some(self.linear_velocity(quantization));
+        body.angular_velocity = Some(self.angular_velocity(quantization));
+        body.sleeping = self.sleeping;
+    }
+
+    /// Creates the state of a body that isn't in the base snapshot.
+    fn to_body_snapshot(self, quantization: Scalar) -> BodySnapshot {
+        let position = self.position(quantization);
+        let rotation = self.rotation();
+
+        BodySnapshot {
+            position,
+            rotation,
+            previous_position: Some(PreviousPosition(position.0)),
+            previous_rotation: Some(PreviousRotation(rotation)),
+            accumulated_translation: Some(AccumulatedTranslation::default()),
+            linear_velocity: Some(self.linear_velocity(quantization)),
+            angular_velocity: Some(self.angular_velocity(quantization)),
+            external_force: Some(ExternalForce::default()),
+            external_torque: Some(ExternalTorque::default()),
+            external_impulse: Some(ExternalImpulse::default()),
+            external_angular_impulse: Some(ExternalAngularImpulse::default()),
+            constant_force: None,
+            constant_local_force: None,
+            constant_torque: None,
+            time_scale: None,
+            time_sleeping: Some(TimeSleeping::default()),
+            sleeping: self.sleeping,
+        }
+    }
+}
+
+impl SnapshotDelta {
+    /// Returns an iterator over the bodies that have moved or been added since the base snapshot, sorted by entity.
+    pub fn bodies(&self) -> impl Iterator<Item = &BodyDelta> {
+        self.bodies.iter()
+    }
+
+    /// Returns the entities of the bodies that have been removed since the base snapshot, sorted by entity.
+    pub fn removed(&self) -> &[Entity] {
+        &self.removed
+    }
+
+    /// Returns `true` if no bodies have moved, been added or been removed since the base snapshot.
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty() && self.removed.is_empty()
+    }
+
+    /// Maps the entities in the delta using the given function, for example to convert
+    /// server entities to client entities.
+    pub fn map_entities(&mut self, mut map: impl FnMut(Entity) -> Entity) {
+        for body in self.bodies.iter_mut() {
+            body.entity = map(body.entity);
+        }
+        for entity in self.removed.iter_mut() {
+            *entity = map(*entity);
+        }
+        self.bodies.sort_by_key(|body| body.entity);
+        self.removed.sort();
+    }
+}
+
+impl PhysicsSnapshot {
+    /// Computes a [`SnapshotDelta`] that contains the bodies whose state differs from the given `base` snapshot.
+    ///
+    /// Positions and velocities are quantized using the given `quantization` step, and bodies are only included
+    /// if their quantized state has changed. The `base` snapshot should be the one that the receiver
+    /// of the delta has, like the last snapshot acknowledged by a client.
+    pub fn delta_from(&self, base: &PhysicsSnapshot, quantization: Scalar) -> SnapshotDelta {
+        let bodies = self
+            .bodies
+            .iter()
+            .map(|(entity, body)| BodyDelta::new(*entity, body, quantization))
+            .filter(|delta| {
+                base.body(delta.entity).is_none_or(|base_body| {
+                    !delta.state_eq(&BodyDelta::new(delta.entity, base_body, quantization))
+                })
+            })
+            .collect();
+
+        let removed = base
+            .bodies
+            .iter()
+            .map(|(entity, _)| *entity)
+            .filter(|entity| self.body(*entity).is_none())
+            .collect();
+
+        SnapshotDelta {
+            quantization,
+            bodies,
+            removed,
+        }
+    }
+
+    /// Applies a [`SnapshotDelta`] to the snapshot, updating the bodies that have moved,
+    /// adding new bodies, and removing the bodies that have been removed.
+    ///
+    /// The snapshot should be the base snapshot that the delta was [computed](PhysicsSnapshot::delta_from) against.
+    pub fn apply_delta(&mut self, delta: &SnapshotDelta) {
+        self.bodies
+            .retain(|(entity, _)| delta.removed.binary_search(entity).is_err());
+
+        for body_delta in delta.bodies.iter() {
+            if let Some(body) = self.body_mut(body_delta.entity) {
+                body_delta.apply(body, delta.quantization);
+            } else {
+                self.bodies.push((
+                    body_delta.entity,
+                    body_delta.to_body_snapshot(delta.quantization),
+                ));
+            }
+        }
+
+        self.bodies.sort_by_key(|(entity, _)| *entity);
+    }
+}
+
+#[cfg(feature = "2d")]
+fn quantize_rotation(rotation: &Rotation) -> QuantizedRotation {
+    (rotation.as_radians() / PI * i16::MAX as Scalar).round() as i16
+}
+
+#[cfg(feature = "3d")]
+fn quantize_rotation(rotation: &Rotation) -> QuantizedRotation {
+    // `q` and `-q` represent the same rotation, so make `w` positive to get a unique representation.
+    let quaternion = if rotation.0.w < 0.0 {
+        -rotation.0
+    } else {
+        rotation.0
+    };
+    quaternion
+        .to_array()
+        .map(|value| (value * i16::MAX as Scalar).round() as i16)
+}
+
+#[cfg(feature = "2d")]
+fn dequantize_rotation(rotation: QuantizedRotation) -> Rotation {
+    Rotation::from_radians(rotation as Scalar / i16::MAX as Scalar * PI)
+}
+
+#[cfg(feature = "3d")]
+fn dequantize_rotation(rotation: QuantizedRotation) -> Rotation {
+    Rotation(
+        Quaternion::from_array(rotation.map(|value| value as Scalar / i16::MAX as Scalar))
+            .normalize(),
+    )
+}
@@ -0,0 +1,700 @@
+//! Simulates volumetric soft bodies as meshes of particles.
+//!
+//! See [`SoftBodyPlugin`] and [`SoftBody`].
+
+use crate::{
+    plugins::particles::{
+        collide_particles, integrate_particles, solve_attachment, solve_particle_distance,
+        update_particle_velocities, ParticleBodies, ParticleContacts,
+    },
+    prelude::*,
+};
+#[cfg(feature = "soft-body-mesh")]
+use bevy::render::mesh::VertexAttributeValues;
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::{intern::Interned, HashSet},
+};
+
+/// The number of particles in each [`SoftBodyElement`]: three for triangles in 2D and four for tetrahedra in 3D.
+#[cfg(feature = "2d")]
+pub const ELEMENT_PARTICLES: usize = 3;
+/// The number of particles in each [`SoftBodyElement`]: three for triangles in 2D and four for tetrahedra in 3D.
+#[cfg(feature = "3d")]
+pub const ELEMENT_PARTICLES: usize = 4;
+
+/// Simulates [soft bodies](SoftBody) as meshes of particles connected by edge and volume constraints.
+///
+/// Soft bodies are simulated in the [`SubstepSchedule`] in the same way as [ropes](Rope) and [cloth](Cloth):
+///
+/// 1. The particles are moved by their velocities and [`Gravity`] in [`SubstepSet::Integrate`].
+/// 2. The edge, volume, pressure and [attachment](ParticleAttachment) constraints are solved after the [joints],
+/// ropes and cloth in [`ParticleSet::SoftBodies`].
+/// 3. The particles are pushed out of [colliders](Collider) with friction in [`ParticleSet::SoftBodies`].
+/// 4. The velocities of the particles are updated from their change in position in [`SubstepSet::UpdateVelocities`].
+///
+/// With the `deformable-mesh` feature, the surface of soft bodies created with [`SoftBody::from_mesh`]
+/// is written back to the `Mesh` of the soft body entity by the [`DeformableMeshPlugin`].
+pub struct SoftBodyPlugin {
+    #[cfg_attr(not(feature = "deformable-mesh"), allow(dead_code))]
+    schedule: Interned<dyn ScheduleLabel>,
+}
+
+impl SoftBodyPlugin {
+    /// Creates a [`SoftBodyPlugin`] with the schedule that is used for running the [`PhysicsSchedule`].
+    ///
+    /// The default schedule is `PostUpdate`.
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for SoftBodyPlugin {
+    fn default() -> Self {
+        Self::new(PostUpdate)
+    }
+}
+
+impl Plugin for SoftBodyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SoftBody>()
+            .register_type::<SoftBodyElement>()
+            .register_type::<SoftBodyEdge>()
+            .register_type::<Particle>()
+            .register_type::<ParticleAttachment>();
+
+        #[cfg(feature = "deformable-mesh")]
+        app.add_plugins(DeformableMeshPlugin::<SoftBody>::new(self.schedule));
+
+        let substeps = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        ParticleSet::configure(substeps);
+
+        substeps.add_systems(integrate_soft_bodies.in_set(SubstepSet::Integrate));
+
+        substeps.add_systems(
+            (solve_soft_body_constraints, collide_soft_bodies)
+                .chain()
+                .in_set(ParticleSet::SoftBodies),
+        );
+
+        substeps.add_systems(update_soft_body_velocities.in_set(SubstepSet::UpdateVelocities));
+    }
+}
+
+/// A volumetric soft body simulated as a mesh of [particles](Particle) by the [`SoftBodyPlugin`].
+///
+/// The body is divided into [elements](SoftBodyElement), which are triangles in 2D and tetrahedra in 3D.
+/// The edges of the elements resist stretching and compression, and the elements resist changes in their volume
+/// (area in 2D), which makes the body squishy but keeps it from collapsing. Particles can be
+/// [attached](ParticleAttachment) to [rigid bodies](RigidBody), and the body collides with [colliders](Collider)
+/// in the same way as [ropes](Rope) and [cloth](Cloth).
+///
+/// Soft bodies can be built from elements directly with [`SoftBody::new`], for example using a tetrahedral mesh
+/// generated by an external tool, or from a surface `Mesh` with [`SoftBody::from_mesh`].
+///
+/// ## Inflatable bodies
+///
+/// Balloons, tires and inflatable platforms can be made by giving the soft body a [`pressure`](Self::pressure).
+/// The pressure constraint keeps the total volume of the body at [`pressure`](Self::pressure) times its rest volume,
+/// so values above `1.0` inflate the body. Since the constraint acts on the whole body instead of individual elements,
+/// the [`volume_compliance`](Self::volume_compliance) should be high so that the elements can deform freely
+/// while the total volume is preserved. The body can be punctured by setting the pressure to zero with
+/// [`SoftBody::puncture`], which disables the constraint.
+///
+/// The particles are simulated in world space, so the `Transform` of the soft body entity has no effect on the simulation.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn setup(mut commands: Commands) {
+#[cfg_attr(feature = "2d", doc = "    // A square made of two triangles")]
+#[cfg_attr(feature = "2d", doc = "    let positions = vec![")]
+#[cfg_attr(feature = "2d", doc = "        Vector::new(-0.5, -0.5),")]
+#[cfg_attr(feature = "2d", doc = "        Vector::new(0.5, -0.5),")]
+#[cfg_attr(feature = "2d", doc = "        Vector::new(0.5, 0.5),")]
+#[cfg_attr(feature = "2d", doc = "        Vector::new(-0.5, 0.5),")]
+#[cfg_attr(feature = "2d", doc = "    ];")]
+#[cfg_attr(feature = "2d", doc = "    let elements = vec![[0, 1, 2], [0, 2, 3]];")]
+#[cfg_attr(feature = "3d", doc = "    // A single tetrahedron")]
+#[cfg_attr(
+    feature = "3d",
+    doc = "    let positions = vec![Vector::ZERO, Vector::X, Vector::Y, Vector::Z];"
+)]
+#[cfg_attr(feature = "3d", doc = "    let elements = vec![[0, 1, 2, 3]];")]
+///
+///     commands.spawn(
+///         SoftBody::new(positions, elements, 1.0)
+///             .with_edge_compliance(0.001)
+///             .with_volume_compliance(0.0),
+///     );
+/// }
+/// ```
+#[derive(Component, Reflect, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct SoftBody {
+    /// The particles of the soft body.
+    pub particles: Vec<Particle>,
+    /// The triangles (2D) or tetrahedra (3D) that the soft body consists of.
+    pub elements: Vec<SoftBodyElement>,
+    /// The unique edges of the [elements](Self::elements).
+    pub edges: Vec<SoftBodyEdge>,
+    /// For soft bodies created from a `Mesh`, the particle corresponding to each vertex of the mesh.
+    pub surface_vertices: Vec<usize>,
+    /// The compliance of the edge constraints, the inverse of stiffness. Has the unit meters / Newton.
+    pub edge_compliance: Scalar,
+    /// The compliance of the volume constraints, the inverse of stiffness.
+    ///
+    /// The default is `0.0`, which keeps the volume of each element constant.
+    pub volume_compliance: Scalar,
+    /// The ratio between the target volume of the whole body and its [rest volume](Self::rest_volume).
+    /// Values above `1.0` inflate the body and values below `1.0` deflate it.
+    ///
+    /// The default is `0.0`, which disables the pressure constraint.
+    pub pressure: Scalar,
+    /// The compliance of the pressure constraint, the inverse of stiffness.
+    pub pressure_compliance: Scalar,
+    /// The radius of the particles used for collisions.
+    pub radius: Scalar,
+    /// The friction of the particles against colliders, between `0.0` and `1.0`.
+    pub friction: Scalar,
+    /// The linear damping of the particles.
+    pub damping: Scalar,
+    /// The layers of the soft body. The particles only collide with colliders whose layers [interact](CollisionLayers::interacts_with)
+    /// with these layers.
+    pub layers: CollisionLayers,
+    /// The particles attached to bodies, given by their indices and attachments.
+    pub attachments: Vec<(usize, ParticleAttachment)>,
+}
+
+/// A triangle (2D) or tetrahedron (3D) of a [`SoftBody`] that resists changes in its volume.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftBodyElement {
+    /// The indices of the particles at the corners of the element.
+    pub particles: [usize; ELEMENT_PARTICLES],
+    /// The signed volume (area in 2D) of the element at rest.
+    pub rest_volume: Scalar,
+}
+
+/// An edge of a [`SoftBody`] that resists stretching and compression.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftBodyEdge {
+    /// The indices of the particles at the ends of the edge.
+    pub particles: [usize; 2],
+    /// The length of the edge at rest.
+    pub rest_length: Scalar,
+}
+
+impl SoftBody {
+    /// Creates a soft body from particle `positions` and `elements`, which are triangles in 2D and tetrahedra in 3D
+    /// given by the indices of their corners.
+    ///
+    /// The total `mass` of the body is distributed between the particles based on the volumes of the elements around them.
+    pub fn new(
+        positions: Vec<Vector>,
+        elements: Vec<[usize; ELEMENT_PARTICLES]>,
+        mass: Scalar,
+    ) -> Self {
+        let elements: Vec<SoftBodyElement> = elements
+            .into_iter()
+            .filter(|element| element.iter().all(|&i| i < positions.len()))
+            .map(|particles| SoftBodyElement {
+                particles,
+                rest_volume: element_volume(particles.map(|i| positions[i])),
+            })
+            .collect();
+
+        // Distribute the mass based on the volumes of the elements
+        let mut particle_volumes = vec![0.0; positions.len()];
+        for element in elements.iter() {
+            for &i in element.particles.iter() {
+                particle_volumes[i] += element.rest_volume.abs() / ELEMENT_PARTICLES as Scalar;
+            }
+        }
+        let total_volume: Scalar = particle_volumes.iter().sum();
+        let particles = positions
+            .iter()
+            .zip(particle_volumes)
+            .map(|(&position, volume)| {
+                let particle_mass = if total_volume > Scalar::EPSILON && volume > 0.0 {
+                    mass * volume / total_volume
+                } else {
+                    mass / positions.len() as Scalar
+                };
+                Particle::new(position, particle_mass)
+            })
+            .collect();
+
+        let mut edge_set = HashSet::new();
+        let mut edges = vec![];
+        for element in elements.iter() {
+            for (a, &i1) in element.particles.iter().enumerate() {
+                for &i2 in element.particles.iter().skip(a + 1) {
+                    if edge_set.insert((i1.min(i2), i1.max(i2))) {
+                        edges.push(SoftBodyEdge {
+                            particles: [i1, i2],
+                            rest_length: positions[i1].distance(positions[i2]),
+                        });
+                    }
+                }
+            }
+        }
+
+        Self {
+            particles,
+            elements,
+            edges,
+            surface_vertices: vec![],
+            edge_compliance: 0.0001,
+            volume_compliance: 0.0,
+            pressure: 0.0,
+            pressure_compliance: 0.0,
+            radius: 0.05,
+            friction: 0.3,
+            damping: 0.0,
+            layers: CollisionLayers::default(),
+            attachments: vec![],
+        }
+    }
+
+    /// Creates a soft body from a closed surface `Mesh` with the given total `mass`.
+    ///
+    /// Vertices of the mesh at the same position are merged into one particle. In 2D, the triangles of the mesh
+    /// are used as the elements. In 3D, the interior is filled with tetrahedra that connect each triangle
+    /// of the surface to an extra particle at the centroid of the vertices, which works for meshes that are
+    /// star-shaped with respect to their centroid, like spheres, cubes and other convex shapes.
+    /// Use [`SoftBody::new`] with a tetrahedral mesh for more complex shapes.
+    ///
+    /// The particles are placed at the positions of the vertices as given in the mesh.
+    /// Returns `None` if the mesh has no triangles or the positions aren't 3D vectors.
+    #[cfg(feature = "soft-body-mesh")]
+    pub fn from_mesh(mesh: &Mesh, mass: Scalar) -> Option<Self> {
+        let (positions, surface_vertices, indices) = merge_mesh_vertices(mesh)?;
+
+        let triangles = indices
+            .chunks_exact(3)
+            .map(|triangle| [0, 1, 2].map(|i| surface_vertices[triangle[i]]));
+
+        #[cfg(feature = "3d")]
+        let mut positions = positions;
+        #[cfg(feature = "3d")]
+        let centroid_index = {
+            let centroid = positions.iter().sum::<Vector>() / positions.len().max(1) as Scalar;
+            positions.push(centroid);
+            positions.len() - 1
+        };
+
+        let mut elements = vec![];
+        for triangle in triangles {
+            #[cfg(feature = "2d")]
+            let mut element = triangle;
+            #[cfg(feature = "3d")]
+            let mut element = [triangle[0], triangle[1], triangle[2], centroid_index];
+
+            let volume = element_volume(element.map(|i| positions[i]));
+            if volume.abs() <= Scalar::EPSILON {
+                continue;
+            }
+            // Keep the volumes of the elements positive
+            if volume < 0.0 {
+                element.swap(0, 1);
+            }
+            elements.push(element);
+        }
+
+        if elements.is_empty() {
+            return None;
+        }
+
+        let mut soft_body = Self::new(positions, elements, mass);
+        soft_body.surface_vertices = surface_vertices;
+        Some(soft_body)
+    }
+
+    /// Attaches the particle at the given index to a body.
+    pub fn with_attachment(mut self, index: usize, attachment: ParticleAttachment) -> Self {
+        self.attachments.push((index, attachment));
+        self
+    }
+
+    /// Pins the particle at the given index in place.
+    pub fn with_pinned(mut self, index: usize) -> Self {
+        if let Some(particle) = self.particles.get_mut(index) {
+            particle.inverse_mass = 0.0;
+        }
+        self
+    }
+
+    /// Sets the compliance of the edge constraints, the inverse of stiffness.
+    pub fn with_edge_compliance(mut self, compliance: Scalar) -> Self {
+        self.edge_compliance = compliance;
+        self
+    }
+
+    /// Sets the compliance of the volume constraints, the inverse of stiffness.
+    pub fn with_volume_compliance(mut self, compliance: Scalar) -> Self {
+        self.volume_compliance = compliance;
+        self
+    }
+
+    /// Sets the ratio between the target volume of the whole body and its rest volume.
+    /// A pressure of zero disables the pressure constraint.
+    pub fn with_pressure(mut self, pressure: Scalar) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Sets the compliance of the pressure constraint, the inverse of stiffness.
+    pub fn with_pressure_compliance(mut self, compliance: Scalar) -> Self {
+        self.pressure_compliance = compliance;
+        self
+    }
+
+    /// Punctures the soft body by setting its [`pressure`](Self::pressure) to zero, which disables the pressure constraint.
+    pub fn puncture(&mut self) {
+        self.pressure = 0.0;
+    }
+
+    /// Sets the radius of the particles used for collisions.
+    pub fn with_radius(mut self, radius: Scalar) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the friction of the particles against colliders.
+    pub fn with_friction(mut self, friction: Scalar) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Sets the linear damping of the particles.
+    pub fn with_damping(mut self, damping: Scalar) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the layers of the soft body used for collisions.
+    pub fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Moves all particles of the soft body by the given `offset`.
+    pub fn with_offset(mut self, offset: Vector) -> Self {
+        for particle in self.particles.iter_mut() {
+            particle.position += offset;
+            particle.previous_position += offset;
+        }
+        self
+    }
+
+    /// Returns an iterator over the positions of the particles.
+    pub fn positions(&self) -> impl Iterator<Item = Vector> + '_ {
+        self.particles.iter().map(|particle| particle.position)
+    }
+
+    /// Computes the current total volume (area in 2D) of the elements.
+    pub fn volume(&self) -> Scalar {
+        self.elements
+            .iter()
+            .map(|element| element_volume(element.particles.map(|i| self.particles[i].position)))
+            .sum()
+    }
+
+    /// Computes the total volume (area in 2D) of the elements at rest.
+    pub fn rest_volume(&self) -> Scalar {
+        self.elements
+            .iter()
+            .map(|element| element.rest_volume)
+            .sum()
+    }
+
+    /// Computes the volume (area in 2D) that the pressure constraint keeps the body at,
+    /// or `None` if the pressure constraint is disabled.
+    pub fn target_volume(&self) -> Option<Scalar> {
+        (self.pressure > 0.0).then(|| self.pressure * self.rest_volume())
+    }
+
+    /// Computes the center of mass of the particles.
+    pub fn center_of_mass(&self) -> Vector {
+        let mut total_mass = 0.0;
+        let mut center = Vector::ZERO;
+        for particle in self.particles.iter() {
+            if particle.inverse_mass > 0.0 {
+                let mass = 1.0 / particle.inverse_mass;
+                total_mass += mass;
+                center += particle.position * mass;
+            }
+        }
+        if total_mass > 0.0 {
+            center / total_mass
+        } else {
+            Vector::ZERO
+        }
+    }
+}
+
+impl MapEntities for SoftBody {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for (_, attachment) in self.attachments.iter_mut() {
+            attachment.entity = entity_mapper.map_entity(attachment.entity);
+        }
+    }
+}
+
+/// Reads the vertex positions of a `Mesh` and merges vertices at the same position.
+///
+/// Returns the merged positions, the index of the merged position for each vertex of the mesh,
+/// and the vertex indices of the triangles. Returns `None` if the positions aren't 3D vectors.
+#[cfg(feature = "soft-body-mesh")]
+pub(crate) fn merge_mesh_vertices(mesh: &Mesh) -> Option<(Vec<Vector>, Vec<usize>, Vec<usize>)> {
+    let Some(VertexAttributeValues::Float32x3(vertices)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return None;
+    };
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(indices) => indices.iter().collect(),
+        None => (0..vertices.len()).collect(),
+    };
+
+    // Merge vertices at the same position
+    let mut particle_indices = bevy::utils::HashMap::new();
+    let mut positions = vec![];
+    let surface_vertices: Vec<usize> = vertices
+        .iter()
+        .map(|vertex| {
+            *particle_indices
+                .entry(vertex.map(f32::to_bits))
+                .or_insert_with(|| {
+                    #[cfg(feature = "2d")]
+                    positions.push(Vector::new(vertex[0] as Scalar, vertex[1] as Scalar));
+                    #[cfg(feature = "3d")]
+                    positions.push(Vector::new(
+                        vertex[0] as Scalar,
+                        vertex[1] as Scalar,
+                        vertex[2] as Scalar,
+                    ));
+                    positions.len() - 1
+                })
+        })
+        .collect();
+
+    Some((positions, surface_vertices, indices))
+}
+
+/// Computes the signed area of a triangle.
+#[cfg(feature = "2d")]
+fn element_volume([p0, p1, p2]: [Vector; 3]) -> Scalar {
+    0.5 * (p1 - p0).perp_dot(p2 - p0)
+}
+
+/// Computes the signed volume of a tetrahedron.
+#[cfg(feature = "3d")]
+fn element_volume([p0, p1, p2, p3]: [Vector; 4]) -> Scalar {
+    (p1 - p0).cross(p2 - p0).dot(p3 - p0) / 6.0
+}
+
+/// Computes the gradients of the signed area of a triangle with respect to its corners.
+#[cfg(feature = "2d")]
+fn element_volume_gradients([p0, p1, p2]: [Vector; 3]) -> [Vector; 3] {
+    let g1 = -0.5 * (p2 - p0).perp();
+    let g2 = 0.5 * (p1 - p0).perp();
+    [-(g1 + g2), g1, g2]
+}
+
+/// Computes the gradients of the signed volume of a tetrahedron with respect to its corners.
+#[cfg(feature = "3d")]
+fn element_volume_gradients([p0, p1, p2, p3]: [Vector; 4]) -> [Vector; 4] {
+    let g1 = (p2 - p0).cross(p3 - p0) / 6.0;
+    let g2 = (p3 - p0).cross(p1 - p0) / 6.0;
+    let g3 = (p1 - p0).cross(p2 - p0) / 6.0;
+    [-(g1 + g2 + g3), g1, g2, g3]
+}
+
+/// Keeps the volume of an element at its rest volume.
+fn solve_element_volume(
+    particles: &mut [Particle],
+    element: &SoftBodyElement,
+    compliance: Scalar,
+    dt: Scalar,
+) {
+    let positions = element.particles.map(|i| particles[i].position);
+    let gradients = element_volume_gradients(positions);
+    let c = element_volume(positions) - element.rest_volume;
+
+    let w_sum = element
+        .particles
+        .iter()
+        .zip(gradients)
+        .fold(compliance / dt.powi(2), |acc, (&i, gradient)| {
+            acc + particles[i].inverse_mass * gradient.length_squared()
+        });
+
+    if w_sum <= Scalar::EPSILON {
+        return;
+    }
+
+    let delta_lagrange = -c / w_sum;
+
+    for (&i, gradient) in element.particles.iter().zip(gradients) {
+        let particle = &mut particles[i];
+        particle.position += gradient * delta_lagrange * particle.inverse_mass;
+    }
+}
+
+/// Keeps the total volume of the elements at the `target_volume`.
+fn solve_pressure(
+    particles: &mut [Particle],
+    elements: &[SoftBodyElement],
+    target_volume: Scalar,
+    compliance: Scalar,
+    dt: Scalar,
+    gradients: &mut Vec<Vector>,
+) {
+    gradients.clear();
+    gradients.resize(particles.len(), Vector::ZERO);
+
+    let mut volume = 0.0;
+    for element in elements.iter() {
+        let positions = element.particles.map(|i| particles[i].position);
+        volume += element_volume(positions);
+        for (&i, gradient) in element
+            .particles
+            .iter()
+            .zip(element_volume_gradients(positions))
+        {
+            gradients[i] += gradient;
+        }
+    }
+
+    let c = volume - target_volume;
+    let w_sum = particles
+        .iter()
+        .zip(gradients.iter())
+        .fold(compliance / dt.powi(2), |acc, (particle, gradient)| {
+            acc + particle.inverse_mass * gradient.length_squared()
+        });
+
+    if w_sum <= Scalar::EPSILON {
+        return;
+    }
+
+    let delta_lagrange = -c / w_sum;
+
+    for (particle, gradient) in particles.iter_mut().zip(gradients.iter()) {
+        particle.position += *gradient * delta_lagrange * particle.inverse_mass;
+    }
+}
+
+/// Moves the particles of soft bodies by their velocities and gravity.
+fn integrate_soft_bodies(
+    mut soft_bodies: Query<&mut SoftBody>,
+    gravity: Res<Gravity>,
+    time: Res<Time>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut soft_body in &mut soft_bodies {
+        let damping = soft_body.damping;
+        integrate_particles(&mut soft_body.particles, gravity.0, damping, delta_secs);
+    }
+}
+
+/// Solves the edge, volume, pressure and attachment constraints of soft bodies.
+fn solve_soft_body_constraints(
+    mut soft_bodies: Query<&mut SoftBody>,
+    mut bodies: ParticleBodies,
+    time: Res<Time>,
+    mut gradients: Local<Vec<Vector>>,
+) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut soft_body in &mut soft_bodies {
+        let soft_body = &mut *soft_body;
+
+        for edge in soft_body.edges.iter() {
+            solve_particle_distance(
+                &mut soft_body.particles,
+                edge.particles,
+                edge.rest_length,
+                false,
+                soft_body.edge_compliance,
+                delta_secs,
+            );
+        }
+
+        for element in soft_body.elements.iter() {
+            solve_element_volume(
+                &mut soft_body.particles,
+                element,
+                soft_body.volume_compliance,
+                delta_secs,
+            );
+        }
+
+        if let Some(target_volume) = soft_body.target_volume() {
+            solve_pressure(
+                &mut soft_body.particles,
+                &soft_body.elements,
+                target_volume,
+                soft_body.pressure_compliance,
+                delta_secs,
+                &mut gradients,
+            );
+        }
+
+        for (index, attachment) in soft_body.attachments.iter_mut() {
+            if let Some(particle) = soft_body.particles.get_mut(*index) {
+                solve_attachment(particle, attachment, &mut bodies, delta_secs);
+            }
+        }
+    }
+}
+
+/// Pushes the particles of soft bodies out of colliders and applies friction.
+pub(crate) fn collide_soft_bodies(
+    mut soft_bodies: Query<&mut SoftBody>,
+    mut contacts: ParticleContacts,
+) {
+    for mut soft_body in &mut soft_bodies {
+        let soft_body = &mut *soft_body;
+        let attached: Vec<Entity> = soft_body
+            .attachments
+            .iter()
+            .map(|(_, attachment)| attachment.entity)
+            .collect();
+
+        collide_particles(
+            &mut soft_body.particles,
+            soft_body.radius,
+            soft_body.friction,
+            soft_body.layers,
+            &attached,
+            &mut contacts,
+        );
+    }
+}
+
+/// Updates the velocities of the particles of soft bodies based on their change in position.
+fn update_soft_body_velocities(mut soft_bodies: Query<&mut SoftBody>, time: Res<Time>) {
+    let delta_secs = time.delta_seconds_adjusted();
+
+    for mut soft_body in &mut soft_bodies {
+        update_particle_velocities(&mut soft_body.particles, delta_secs);
+    }
+}
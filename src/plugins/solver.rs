@@ -8,7 +8,11 @@ use crate::{
     utils::{compute_dynamic_friction, compute_restitution, get_pos_translation},
 };
 use bevy::{
-    ecs::query::{Has, QueryData},
+    ecs::{
+        component::Tick,
+        query::{Has, QueryData},
+        system::{SystemChangeTick, SystemParam},
+    },
     prelude::*,
 };
 use constraints::penetration::PenetrationConstraint;
@@ -32,16 +36,27 @@ use constraints::penetration::PenetrationConstraint;
 /// In the case of collisions, [`PenetrationConstraint`]s are created for each contact pair.
 /// The constraints are resolved by moving the bodies so that they no longer penetrate.
 /// Then, the velocities are updated, and velocity corrections caused by dynamic friction and restitution are applied.
+///
+/// The steps work on the [`SolverBodies`], which are gathered from the rigid bodies before the constraints
+/// are solved and written back to them after the velocity solve.
 pub struct SolverPlugin;
 
 impl Plugin for SolverPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PenetrationConstraints>();
+        app.init_resource::<PenetrationConstraints>()
+            .init_resource::<DominanceOverrides>()
+            .init_resource::<SolverBodies>();
 
         let substeps = app
             .get_schedule_mut(SubstepSchedule)
             .expect("add SubstepSchedule first");
 
+        substeps.add_systems(
+            gather_solver_bodies
+                .after(SubstepSet::PostProcessCollisions)
+                .before(SubstepSet::SolveConstraints),
+        );
+
         substeps.add_systems(
             (
                 penetration_constraints,
@@ -55,7 +70,15 @@ impl Plugin for SolverPlugin {
                 .in_set(SubstepSet::SolveConstraints),
         );
 
-        substeps.add_systems((update_lin_vel, update_ang_vel).in_set(SubstepSet::UpdateVelocities));
+        substeps.add_systems(
+            (
+                project_locked_axes_positions,
+                update_lin_vel,
+                update_ang_vel,
+            )
+                .chain()
+                .in_set(SubstepSet::UpdateVelocities),
+        );
 
         substeps.add_systems(
             (
@@ -65,14 +88,25 @@ impl Plugin for SolverPlugin {
                 joint_damping::<SphericalJoint>,
                 joint_damping::<PrismaticJoint>,
                 joint_damping::<DistanceJoint>,
+                project_locked_axes_velocities,
             )
                 .chain()
                 .in_set(SubstepSet::SolveVelocities),
         );
 
+        substeps.add_systems(
+            scatter_solver_bodies
+                .after(SubstepSet::SolveVelocities)
+                .before(SubstepSet::StoreImpulses),
+        );
+
         substeps.add_systems(store_contact_impulses.in_set(SubstepSet::StoreImpulses));
 
-        substeps.add_systems(apply_translation.in_set(SubstepSet::ApplyTranslation));
+        substeps.add_systems(
+            (handle_center_of_mass_changes, apply_translation)
+                .chain()
+                .in_set(SubstepSet::ApplyTranslation),
+        );
     }
 }
 
@@ -81,6 +115,11 @@ impl Plugin for SolverPlugin {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct PenetrationConstraints(pub Vec<PenetrationConstraint>);
 
+/// Entities sorted by their stable keys, used for solving constraints in a deterministic order.
+///
+/// The buffer is stored in a system's [`Local`] so that it can be reused across substeps.
+type SolveOrder = Vec<((Option<u64>, Entity), Entity)>;
+
 /// A `WorldQuery` to make code handling colliders in collisions cleaner.
 #[derive(QueryData)]
 struct ColliderQuery<'w> {
@@ -89,7 +128,12 @@ struct ColliderQuery<'w> {
     transform: Option<&'w ColliderTransform>,
     is_sensor: Has<Sensor>,
     friction: Option<&'w Friction>,
+    anisotropic_friction: Option<&'w AnisotropicFriction>,
+    tire_friction: Option<&'w TireFriction>,
     restitution: Option<&'w Restitution>,
+    surface_velocity: Option<&'w SurfaceVelocity>,
+    material_id: Option<&'w MaterialId>,
+    layers: Option<&'w CollisionLayers>,
 }
 
 /// Iterates through broad phase collision pairs, checks which ones are actually colliding, and uses [`PenetrationConstraint`]s to resolve the collisions.
@@ -97,15 +141,13 @@ struct ColliderQuery<'w> {
 #[allow(clippy::type_complexity)]
 fn penetration_constraints(
     mut commands: Commands,
-    mut bodies: Query<(
-        RigidBodyQuery,
-        Option<&Name>,
-        Option<&Sensor>,
-        Option<&Sleeping>,
-    )>,
+    mut bodies: SolverBodiesMut,
+    body_info: Query<(Option<&Name>, Has<Sensor>), With<RigidBody>>,
     colliders: Query<ColliderQuery>,
     mut penetration_constraints: ResMut<PenetrationConstraints>,
     mut collisions: ResMut<Collisions>,
+    combiner: Option<Res<CoefficientCombiner>>,
+    dominance_overrides: Res<DominanceOverrides>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
@@ -135,15 +177,23 @@ fn penetration_constraints(
         // This is set to true if any of the contacts is penetrating.
         contacts.during_current_substep = false;
 
-        if let Ok([bundle1, bundle2]) = bodies.get_many_mut([collider_parent1, collider_parent2]) {
-            let (mut body1, name1, sensor1, sleeping1) = bundle1;
-            let (mut body2, name2, sensor2, sleeping2) = bundle2;
+        let Ok([(name1, sensor1), (name2, sensor2)]) =
+            body_info.get_many([collider_parent1, collider_parent2])
+        else {
+            continue;
+        };
+
+        if let Some([(mut body1, state1), (mut body2, state2)]) =
+            bodies.get_many_mut([collider_parent1, collider_parent2])
+        {
+            let sleeping1 = state1.is_sleeping;
+            let sleeping2 = state2.is_sleeping;
 
-            let inactive1 = body1.rb.is_static() || sleeping1.is_some();
-            let inactive2 = body2.rb.is_static() || sleeping2.is_some();
+            let inactive1 = body1.rb.is_static() || sleeping1;
+            let inactive2 = body2.rb.is_static() || sleeping2;
 
-            let body1_is_sensor = contacts.entity1 == body1.entity && sensor1.is_some();
-            let body2_is_sensor = contacts.entity2 == body2.entity && sensor2.is_some();
+            let body1_is_sensor = contacts.entity1 == body1.entity && sensor1;
+            let body2_is_sensor = contacts.entity2 == body2.entity && sensor2;
 
             // No collision response if both bodies are static or sleeping
             // or if either of the colliders is a sensor collider.
@@ -157,26 +207,95 @@ fn penetration_constraints(
             }
 
             // When an active body collides with a sleeping body, wake up the sleeping body.
-            if sleeping1.is_some() {
+            if sleeping1 {
                 commands.entity(body1.entity).remove::<Sleeping>();
-            } else if sleeping2.is_some() {
+            } else if sleeping2 {
                 commands.entity(body2.entity).remove::<Sleeping>();
             }
 
             // Get combined friction and restitution coefficients of the colliders
-            // or the bodies they are attached to.
-            let friction = collider1
-                .friction
-                .unwrap_or(body1.friction)
-                .combine(*collider2.friction.unwrap_or(body2.friction));
-            let restitution = collider1
-                .restitution
-                .unwrap_or(body1.restitution)
-                .combine(*collider2.restitution.unwrap_or(body2.restitution));
+            // or the bodies they are attached to, using the custom combine rules if they exist.
+            let pair = CoefficientPair {
+                collider1: *collider_entity1,
+                collider2: *collider_entity2,
+                body1: body1.entity,
+                body2: body2.entity,
+                material1: collider1.material_id.copied(),
+                material2: collider2.material_id.copied(),
+            };
+            let combine_friction = |friction1: Friction, friction2: Friction| match &combiner {
+                Some(combiner) => combiner.0.combine_friction(&pair, friction1, friction2),
+                None => friction1.combine(friction2),
+            };
+            let friction1 = *collider1.friction.unwrap_or(body1.friction);
+            let friction2 = *collider2.friction.unwrap_or(body2.friction);
+            let (friction, friction_across, friction_axis) =
+                if let Some(anisotropic) = collider1.anisotropic_friction {
+                    let axis = collider_to_world(&collider1, &body1.rotation, anisotropic.axis);
+                    (
+                        combine_friction(anisotropic.along, friction2),
+                        combine_friction(anisotropic.across, friction2),
+                        Some(axis),
+                    )
+                } else if let Some(anisotropic) = collider2.anisotropic_friction {
+                    let axis = collider_to_world(&collider2, &body2.rotation, anisotropic.axis);
+                    (
+                        combine_friction(friction1, anisotropic.along),
+                        combine_friction(friction1, anisotropic.across),
+                        Some(axis),
+                    )
+                } else {
+                    let friction = combine_friction(friction1, friction2);
+                    (friction, friction, None)
+                };
+            let restitution1 = *collider1.restitution.unwrap_or(body1.restitution);
+            let restitution2 = *collider2.restitution.unwrap_or(body2.restitution);
+            let restitution = match &combiner {
+                Some(combiner) => combiner
+                    .0
+                    .combine_restitution(&pair, restitution1, restitution2),
+                None => restitution1.combine(restitution2),
+            };
+
+            // Use the tire model for wheel colliders. If both colliders are wheels, the first one is used.
+            let tire = if let Some(friction) = collider1.tire_friction {
+                Some(TireContact {
+                    friction: *friction,
+                    #[cfg(feature = "3d")]
+                    axle: collider_to_world(&collider1, &body1.rotation, friction.axle),
+                    wheel_is_first: true,
+                })
+            } else {
+                collider2.tire_friction.map(|friction| TireContact {
+                    friction: *friction,
+                    #[cfg(feature = "3d")]
+                    axle: collider_to_world(&collider2, &body2.rotation, friction.axle),
+                    wheel_is_first: false,
+                })
+            };
+
+            // Get the dominances of the bodies if they have been overridden for this pair
+            let dominance_override = if body1.rb.is_dynamic()
+                && body2.rb.is_dynamic()
+                && !dominance_overrides.is_empty()
+            {
+                dominance_overrides.dominances(
+                    (body1.entity, collider1.layers.copied().unwrap_or_default()),
+                    (body2.entity, collider2.layers.copied().unwrap_or_default()),
+                )
+            } else {
+                None
+            };
 
             // Create and solve penetration constraints for each contact.
             for (manifold_index, manifold) in contacts.manifolds.iter().enumerate() {
                 for contact in manifold.contacts.iter() {
+                    // Get the velocities of the surfaces of the colliders at the contact point in world space
+                    let surface_velocity1 =
+                        global_surface_velocity(&collider1, &body1.rotation, contact.point1);
+                    let surface_velocity2 =
+                        global_surface_velocity(&collider2, &body2.rotation, contact.point2);
+
                     // Add collider transforms to local contact points
                     let contact = ContactData {
                         point1: collider1.transform.map_or(contact.point1, |t| {
@@ -196,7 +315,12 @@ fn penetration_constraints(
 
                     let mut constraint = PenetrationConstraint {
                         friction,
+                        friction_axis,
+                        friction_across,
                         restitution,
+                        surface_velocity: surface_velocity1 - surface_velocity2,
+                        dominance_override,
+                        tire,
                         ..PenetrationConstraint::new(
                             &body1,
                             &body2,
@@ -246,6 +370,9 @@ fn penetration_constraints(
 /// Iterates through the constraints of a given type and solves them. Sleeping bodies are woken up when
 /// active bodies interact with them in a constraint.
 ///
+/// The constraints are solved in the order of their [`StableId`]s and entities, so the result doesn't
+/// depend on query iteration order.
+///
 /// Note that this system only works for constraints that are modeled as entities.
 /// If you store constraints in a resource, you must create your own system for solving them.
 ///
@@ -267,40 +394,71 @@ fn penetration_constraints(
 ///         .in_set(SubstepSet::SolveUserConstraints),
 /// );
 /// ```
+#[allow(clippy::type_complexity)]
 pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTITY_COUNT: usize>(
     mut commands: Commands,
-    mut bodies: Query<(RigidBodyQuery, Option<&Sleeping>)>,
-    mut constraints: Query<&mut C, Without<RigidBody>>,
+    mut bodies: SolverBodiesMut,
+    mut constraints: Query<(Entity, Option<&StableId>, &mut C), Without<RigidBody>>,
+    mut order: Local<SolveOrder>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    // Clear Lagrange multipliers
+    // Clear Lagrange multipliers. This is internal solver state, so it bypasses change detection
+    // to not make the constraints look like they were changed by the user, which would wake up their bodies.
     constraints
         .iter_mut()
-        .for_each(|mut c| c.clear_lagrange_multipliers());
+        .for_each(|(_, _, mut c)| c.bypass_change_detection().clear_lagrange_multipliers());
+
+    // Solve the constraints in the order of their stable keys instead of query iteration order.
+    order.clear();
+    order.extend(
+        constraints
+            .iter()
+            .map(|(entity, id, _)| (StableId::sort_key(id, entity), entity)),
+    );
+    order.sort_unstable();
+
+    for &(_, entity) in order.iter() {
+        let Ok((_, _, mut constraint)) = constraints.get_mut(entity) else {
+            continue;
+        };
 
-    for mut constraint in &mut constraints {
         // Get components for entities
-        if let Ok(mut bodies) = bodies.get_many_mut(constraint.entities()) {
+        if let Some(mut bodies) = bodies.get_many_mut(constraint.entities()) {
             let none_dynamic = bodies.iter().all(|(body, _)| !body.rb.is_dynamic());
             let all_inactive = bodies
                 .iter()
-                .all(|(body, sleeping)| body.rb.is_static() || sleeping.is_some());
+                .all(|(body, state)| body.rb.is_static() || state.is_sleeping);
+            let any_disabled = bodies.iter().any(|(_, state)| state.is_disabled);
 
             // No constraint solving if none of the bodies is dynamic,
-            // or if all of the bodies are either static or sleeping
-            if none_dynamic || all_inactive {
+            // if all of the bodies are either static or sleeping,
+            // or if any of the bodies has been disabled.
+            // The state of the constraint is reset so that it doesn't report stale forces.
+            if none_dynamic || all_inactive || any_disabled {
+                constraint.bypass_change_detection().reset();
                 continue;
             }
 
             // At least one of the participating bodies is active, so wake up any sleeping bodies
-            for (body, sleeping) in &bodies {
-                if sleeping.is_some() {
+            for (body, state) in &bodies {
+                if state.is_sleeping {
                     commands.entity(body.entity).remove::<Sleeping>();
                 }
             }
 
+            // Use the largest time scale of the bodies, so that the constraint can still
+            // be solved if only some of the bodies are frozen with a time scale of zero.
+            let time_scale = bodies
+                .iter()
+                .map(|(_, state)| state.time_scale)
+                .fold(0.0, Scalar::max);
+            if time_scale <= 0.0 {
+                constraint.bypass_change_detection().reset();
+                continue;
+            }
+
             // Get the bodies as an array and solve the constraint
             if let Ok(bodies) = bodies
                 .iter_mut()
@@ -308,261 +466,1106 @@ pub fn solve_constraint<C: XpbdConstraint<ENTITY_COUNT> + Component, const ENTIT
                 .collect::<Vec<&mut RigidBodyQueryItem>>()
                 .try_into()
             {
-                constraint.solve(bodies, delta_secs);
+                constraint.solve(bodies, delta_secs * time_scale);
             }
         }
     }
 }
 
-/// Updates the linear velocity of all dynamic bodies based on the change in position from the previous step.
+/// Removes the motion along and around the axes locked by [`LockedAxes`] in frames other than
+/// [`LockedAxesFrame::World`] from the positional and rotational changes of the substep.
+///
+/// Locks in world space are handled by the effective masses of the bodies instead.
 #[allow(clippy::type_complexity)]
-fn update_lin_vel(
-    mut bodies: Query<
-        (
-            &RigidBody,
-            &Position,
-            &PreviousPosition,
-            &AccumulatedTranslation,
-            &mut LinearVelocity,
-            &mut PreSolveLinearVelocity,
-        ),
-        Without<Sleeping>,
-    >,
-    time: Res<Time>,
-) {
+fn project_locked_axes_positions(mut bodies: SolverBodiesMut) {
+    bodies.for_each_mut(|mut body, state| {
+        let Some(locked_axes) = body.locked_axes else {
+            return;
+        };
+        if body.rb.is_static()
+            || state.is_sleeping
+            || state.is_disabled
+            || locked_axes.frame() == LockedAxesFrame::World
+        {
+            return;
+        }
+
+        // The frame of the locks is the rotation of the body at the start of the substep
+        let prev_rot = *body.previous_rotation;
+        let delta_pos = body.position.0 - body.previous_position.0 + body.accumulated_translation.0;
+        let projected_delta_pos = locked_axes.project_vec(delta_pos, &prev_rot.0);
+        // avoid triggering bevy's change detection unnecessarily
+        if projected_delta_pos != delta_pos {
+            body.accumulated_translation.0 += projected_delta_pos - delta_pos;
+        }
+
+        #[cfg(feature = "2d")]
+        if locked_axes.is_rotation_locked() && *body.rotation != prev_rot.0 {
+            *body.rotation = prev_rot.0;
+        }
+        #[cfg(feature = "3d")]
+        {
+            let mut delta_rot = body.rotation.mul_quat(prev_rot.inverse().0);
+            if delta_rot.w < 0.0 {
+                delta_rot = -delta_rot;
+            }
+            let scaled_axis = delta_rot.to_scaled_axis();
+            let projected_scaled_axis = locked_axes.project_angular_vec(scaled_axis, &prev_rot.0);
+            if projected_scaled_axis != scaled_axis {
+                body.rotation.0 = (Quaternion::from_scaled_axis(projected_scaled_axis)
+                    * prev_rot.0 .0)
+                    .normalize();
+            }
+        }
+    });
+}
+
+/// Removes the velocity along and around the axes locked by [`LockedAxes`] in frames other than
+/// [`LockedAxesFrame::World`].
+#[allow(clippy::type_complexity)]
+fn project_locked_axes_velocities(mut bodies: SolverBodiesMut) {
+    bodies.for_each_mut(|mut body, state| {
+        let Some(locked_axes) = body.locked_axes else {
+            return;
+        };
+        if body.rb.is_static()
+            || state.is_sleeping
+            || state.is_disabled
+            || locked_axes.frame() == LockedAxesFrame::World
+        {
+            return;
+        }
+
+        let rot = *body.rotation;
+        let projected_lin_vel = locked_axes.project_vec(body.linear_velocity.0, &rot);
+        // avoid triggering bevy's change detection unnecessarily
+        if projected_lin_vel != body.linear_velocity.0 {
+            body.linear_velocity.0 = projected_lin_vel;
+        }
+
+        #[cfg(feature = "2d")]
+        let projected_ang_vel = locked_axes.apply_to_angular_velocity(body.angular_velocity.0);
+        #[cfg(feature = "3d")]
+        let projected_ang_vel = locked_axes.project_angular_vec(body.angular_velocity.0, &rot);
+        if projected_ang_vel != body.angular_velocity.0 {
+            body.angular_velocity.0 = projected_ang_vel;
+        }
+    });
+}
+
+/// Updates the linear velocity of all dynamic bodies based on the change in position from the previous step.
+fn update_lin_vel(mut bodies: SolverBodiesMut, time: Res<Time>) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for (rb, pos, prev_pos, translation, mut lin_vel, mut pre_solve_lin_vel) in &mut bodies {
+    bodies.for_each_mut(|mut body, state| {
+        if state.is_sleeping || state.is_disabled {
+            return;
+        }
+
         // Static bodies have no velocity
-        if rb.is_static() && lin_vel.0 != Vector::ZERO {
-            lin_vel.0 = Vector::ZERO;
+        if body.rb.is_static() && body.linear_velocity.0 != Vector::ZERO {
+            body.linear_velocity.0 = Vector::ZERO;
         }
 
-        pre_solve_lin_vel.0 = lin_vel.0;
+        body.pre_solve_linear_velocity.0 = body.linear_velocity.0;
 
-        if rb.is_dynamic() {
-            // v = (x - x_prev) / h
-            let new_lin_vel = (pos.0 - prev_pos.0 + translation.0) / delta_secs;
+        if body.rb.is_dynamic() {
+            // v = (x - x_prev) / h, where h is scaled by the time scale of the body.
+            // A time scale of zero produces a non-finite velocity, so the velocity is kept.
+            let delta_secs = delta_secs * state.time_scale;
+            let new_lin_vel = (body.position.0 - body.previous_position.0
+                + body.accumulated_translation.0)
+                / delta_secs;
             // avoid triggering bevy's change detection unnecessarily
-            if new_lin_vel != lin_vel.0 && new_lin_vel.is_finite() {
-                lin_vel.0 = new_lin_vel;
+            if new_lin_vel != body.linear_velocity.0 && new_lin_vel.is_finite() {
+                body.linear_velocity.0 = new_lin_vel;
             }
         }
-    }
+    });
 }
 
 /// Updates the angular velocity of all dynamic bodies based on the change in rotation from the previous step.
 #[cfg(feature = "2d")]
-fn update_ang_vel(
-    mut bodies: Query<
-        (
-            &RigidBody,
-            &Rotation,
-            &PreviousRotation,
-            &mut AngularVelocity,
-            &mut PreSolveAngularVelocity,
-        ),
-        Without<Sleeping>,
-    >,
-    time: Res<Time>,
-) {
+fn update_ang_vel(mut bodies: SolverBodiesMut, time: Res<Time>) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for (rb, rot, prev_rot, mut ang_vel, mut pre_solve_ang_vel) in &mut bodies {
+    bodies.for_each_mut(|mut body, state| {
+        if state.is_sleeping || state.is_disabled {
+            return;
+        }
+
         // Static bodies have no velocity
-        if rb.is_static() && ang_vel.0 != 0.0 {
-            ang_vel.0 = 0.0;
+        if body.rb.is_static() && body.angular_velocity.0 != 0.0 {
+            body.angular_velocity.0 = 0.0;
         }
 
-        pre_solve_ang_vel.0 = ang_vel.0;
+        body.pre_solve_angular_velocity.0 = body.angular_velocity.0;
 
-        if rb.is_dynamic() {
-            let new_ang_vel = (rot.mul(prev_rot.inverse())).as_radians() / delta_secs;
+        if body.rb.is_dynamic() {
+            let delta_secs = delta_secs * state.time_scale;
+            let new_ang_vel =
+                (body.rotation.mul(body.previous_rotation.inverse())).as_radians() / delta_secs;
             // avoid triggering bevy's change detection unnecessarily
-            if new_ang_vel != ang_vel.0 && new_ang_vel.is_finite() {
-                ang_vel.0 = new_ang_vel;
+            if new_ang_vel != body.angular_velocity.0 && new_ang_vel.is_finite() {
+                body.angular_velocity.0 = new_ang_vel;
             }
         }
-    }
+    });
 }
 
 /// Updates the angular velocity of all dynamic bodies based on the change in rotation from the previous step.
 #[cfg(feature = "3d")]
-fn update_ang_vel(
-    mut bodies: Query<
-        (
-            &RigidBody,
-            &Rotation,
-            &PreviousRotation,
-            &mut AngularVelocity,
-            &mut PreSolveAngularVelocity,
-        ),
-        Without<Sleeping>,
-    >,
-    time: Res<Time>,
-) {
+fn update_ang_vel(mut bodies: SolverBodiesMut, time: Res<Time>) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for (rb, rot, prev_rot, mut ang_vel, mut pre_solve_ang_vel) in &mut bodies {
+    bodies.for_each_mut(|mut body, state| {
+        if state.is_sleeping || state.is_disabled {
+            return;
+        }
+
         // Static bodies have no velocity
-        if rb.is_static() && ang_vel.0 != Vector::ZERO {
-            ang_vel.0 = Vector::ZERO;
+        if body.rb.is_static() && body.angular_velocity.0 != Vector::ZERO {
+            body.angular_velocity.0 = Vector::ZERO;
         }
 
-        pre_solve_ang_vel.0 = ang_vel.0;
+        body.pre_solve_angular_velocity.0 = body.angular_velocity.0;
 
-        if rb.is_dynamic() {
-            let delta_rot = rot.mul_quat(prev_rot.inverse().0);
+        if body.rb.is_dynamic() {
+            let delta_secs = delta_secs * state.time_scale;
+            let delta_rot = body.rotation.mul_quat(body.previous_rotation.inverse().0);
             let mut new_ang_vel = 2.0 * delta_rot.xyz() / delta_secs;
             if delta_rot.w < 0.0 {
                 new_ang_vel = -new_ang_vel;
             }
             // avoid triggering bevy's change detection unnecessarily
-            if new_ang_vel != ang_vel.0 && new_ang_vel.is_finite() {
-                ang_vel.0 = new_ang_vel;
+            if new_ang_vel != body.angular_velocity.0 && new_ang_vel.is_finite() {
+                body.angular_velocity.0 = new_ang_vel;
             }
         }
+    });
+}
+
+/// The data of the rigid bodies that the solver works on, stored as a structure of arrays.
+///
+/// The data of all rigid bodies is gathered into contiguous buffers after [`SubstepSet::PostProcessCollisions`],
+/// and the [constraints], [joints], velocity updates and velocity solve read and write the buffers
+/// instead of querying the components of the bodies. The data that was changed is written back to
+/// the bodies after [`SubstepSet::SolveVelocities`].
+///
+/// Each body is stored in a dense slot, and the slot of a body is looked up using the index of its entity,
+/// so finding the data of a body doesn't require hashing.
+///
+/// Custom systems that run between [`SubstepSet::SolveConstraints`] and [`SubstepSet::SolveVelocities`]
+/// should access the bodies using [`SolverBodiesMut`]. Changes made to the components of the bodies
+/// directly are not seen by the solver, and they can be overwritten when the buffers are written back.
+#[derive(Resource, Default)]
+pub struct SolverBodies {
+    entities: Vec<Entity>,
+    /// The slot of each body, indexed by the index of its entity. Unused slots are `u32::MAX`.
+    slots: Vec<u32>,
+    states: Vec<SolverBodyState>,
+    rb: SolverColumn<RigidBody>,
+    position: SolverColumn<Position>,
+    rotation: SolverColumn<Rotation>,
+    previous_position: SolverColumn<PreviousPosition>,
+    previous_rotation: SolverColumn<PreviousRotation>,
+    accumulated_translation: SolverColumn<AccumulatedTranslation>,
+    linear_velocity: SolverColumn<LinearVelocity>,
+    pre_solve_linear_velocity: SolverColumn<PreSolveLinearVelocity>,
+    angular_velocity: SolverColumn<AngularVelocity>,
+    pre_solve_angular_velocity: SolverColumn<PreSolveAngularVelocity>,
+    mass: SolverColumn<Mass>,
+    inverse_mass: SolverColumn<InverseMass>,
+    inertia: SolverColumn<Inertia>,
+    inverse_inertia: SolverColumn<InverseInertia>,
+    center_of_mass: SolverColumn<CenterOfMass>,
+    friction: Vec<Friction>,
+    restitution: Vec<Restitution>,
+    locked_axes: Vec<Option<LockedAxes>>,
+    dominance: Vec<Option<Dominance>>,
+    /// The tick at which the bodies were gathered. Data changed after it is written back to the bodies.
+    gathered: Option<Tick>,
+}
+
+/// The state of a body in the [`SolverBodies`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolverBodyState {
+    /// `true` if the body is [`Sleeping`].
+    pub is_sleeping: bool,
+    /// `true` if the body has been disabled using [`RigidBodyDisabled`].
+    pub is_disabled: bool,
+    /// The [`TimeScale`] of the body, or 1.0 if it doesn't have one.
+    pub time_scale: Scalar,
+}
+
+impl SolverBodies {
+    /// Returns the number of bodies in the buffers.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if there are no bodies in the buffers.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the entities of the bodies in the order of their slots.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns the slot of the given body, or `None` if the body is not in the buffers.
+    pub fn slot(&self, entity: Entity) -> Option<usize> {
+        let slot = *self.slots.get(entity.index() as usize)? as usize;
+        (self.entities.get(slot) == Some(&entity)).then_some(slot)
+    }
+
+    /// Returns the state of the given body, or `None` if the body is not in the buffers.
+    pub fn state(&self, entity: Entity) -> Option<SolverBodyState> {
+        self.slot(entity).map(|slot| self.states[slot])
+    }
+
+    /// Removes all bodies from the buffers.
+    fn clear(&mut self) {
+        for entity in self.entities.drain(..) {
+            self.slots[entity.index() as usize] = u32::MAX;
+        }
+        self.states.clear();
+        self.rb.clear();
+        self.position.clear();
+        self.rotation.clear();
+        self.previous_position.clear();
+        self.previous_rotation.clear();
+        self.accumulated_translation.clear();
+        self.linear_velocity.clear();
+        self.pre_solve_linear_velocity.clear();
+        self.angular_velocity.clear();
+        self.pre_solve_angular_velocity.clear();
+        self.mass.clear();
+        self.inverse_mass.clear();
+        self.inertia.clear();
+        self.inverse_inertia.clear();
+        self.center_of_mass.clear();
+        self.friction.clear();
+        self.restitution.clear();
+        self.locked_axes.clear();
+        self.dominance.clear();
+    }
+
+    /// Returns the bodies in the given slots with change detection, or `None` if the slots are not unique.
+    fn items_mut<const N: usize>(
+        &mut self,
+        slots: [usize; N],
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Option<[(RigidBodyQueryItem<'_>, SolverBodyState); N]> {
+        let mut rb = self.rb.get_many(slots, last_run, this_run).into_iter();
+        let mut position = self
+            .position
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut rotation = self
+            .rotation
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut previous_position = self
+            .previous_position
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut previous_rotation = self
+            .previous_rotation
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut accumulated_translation = self
+            .accumulated_translation
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut linear_velocity = self
+            .linear_velocity
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut pre_solve_linear_velocity = self
+            .pre_solve_linear_velocity
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut angular_velocity = self
+            .angular_velocity
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut pre_solve_angular_velocity = self
+            .pre_solve_angular_velocity
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut mass = self
+            .mass
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut inverse_mass = self
+            .inverse_mass
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut inertia = self
+            .inertia
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut inverse_inertia = self
+            .inverse_inertia
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+        let mut center_of_mass = self
+            .center_of_mass
+            .get_many_mut(slots, last_run, this_run)?
+            .into_iter();
+
+        // The columns all have the same length, so each of the iterators yields exactly `N` items.
+        Some(std::array::from_fn(|i| {
+            let slot = slots[i];
+            let body = RigidBodyQueryItem {
+                entity: self.entities[slot],
+                rb: rb.next().unwrap(),
+                position: position.next().unwrap(),
+                rotation: rotation.next().unwrap(),
+                previous_position: previous_position.next().unwrap(),
+                previous_rotation: previous_rotation.next().unwrap(),
+                accumulated_translation: accumulated_translation.next().unwrap(),
+                linear_velocity: linear_velocity.next().unwrap(),
+                pre_solve_linear_velocity: pre_solve_linear_velocity.next().unwrap(),
+                angular_velocity: angular_velocity.next().unwrap(),
+                pre_solve_angular_velocity: pre_solve_angular_velocity.next().unwrap(),
+                mass: mass.next().unwrap(),
+                inverse_mass: inverse_mass.next().unwrap(),
+                inertia: inertia.next().unwrap(),
+                inverse_inertia: inverse_inertia.next().unwrap(),
+                center_of_mass: center_of_mass.next().unwrap(),
+                friction: &self.friction[slot],
+                restitution: &self.restitution[slot],
+                locked_axes: self.locked_axes[slot].as_ref(),
+                dominance: self.dominance[slot].as_ref(),
+            };
+            (body, self.states[slot])
+        }))
+    }
+
+    /// Returns `true` if the body in the given slot is dynamic.
+    fn is_dynamic(&self, slot: usize) -> bool {
+        self.rb.values[slot].is_dynamic()
+    }
+
+    /// Returns the [`Dominance`] of the body in the given slot. Non-dynamic bodies have the highest dominance.
+    fn dominance(&self, slot: usize) -> i8 {
+        if !self.is_dynamic(slot) {
+            i8::MAX
+        } else {
+            self.dominance[slot].map_or(0, |dominance| dominance.0)
+        }
+    }
+
+    /// Computes the effective inverse mass of the body in the given slot, taking into account any translation locking.
+    fn effective_inverse_mass(&self, slot: usize) -> Vector {
+        let inverse_mass = Vector::splat(self.inverse_mass.values[slot].0);
+        match self.locked_axes[slot] {
+            Some(locked_axes) => locked_axes.apply_to_vec(inverse_mass),
+            None => inverse_mass,
+        }
+    }
+
+    /// Computes the effective world-space inverse inertia of the body in the given slot,
+    /// taking into account any rotation locking.
+    #[cfg(feature = "2d")]
+    fn effective_inverse_inertia(&self, slot: usize) -> Scalar {
+        let inverse_inertia = self.inverse_inertia.values[slot].0;
+        match self.locked_axes[slot] {
+            Some(locked_axes) => locked_axes.apply_to_rotation(inverse_inertia),
+            None => inverse_inertia,
+        }
+    }
+
+    /// Computes the effective world-space inverse inertia tensor of the body in the given slot,
+    /// taking into account any rotation locking.
+    #[cfg(feature = "3d")]
+    fn effective_inverse_inertia(&self, slot: usize) -> Matrix3 {
+        let inverse_inertia = self.inverse_inertia.values[slot]
+            .rotated(&self.rotation.values[slot])
+            .0;
+        match self.locked_axes[slot] {
+            Some(locked_axes) => locked_axes.apply_to_rotation(inverse_inertia),
+            None => inverse_inertia,
+        }
+    }
+
+    /// Computes the generalized inverse mass of the body in the given slot when applying
+    /// a correction at point `r` along the vector `n`.
+    ///
+    /// This matches [`PositionConstraint::compute_generalized_inverse_mass`].
+    fn generalized_inverse_mass(&self, slot: usize, r: Vector, n: Vector) -> Scalar {
+        if !self.is_dynamic(slot) {
+            // Static and kinematic bodies are a special case, where 0.0 can be thought of as infinite mass.
+            return 0.0;
+        }
+
+        #[cfg(feature = "2d")]
+        {
+            self.inverse_mass.values[slot].0
+                + self.inverse_inertia.values[slot].0 * r.perp_dot(n).powi(2)
+        }
+        #[cfg(feature = "3d")]
+        {
+            let r_cross_n = r.cross(n);
+            self.inverse_mass.values[slot].0
+                + r_cross_n.dot(self.effective_inverse_inertia(slot) * r_cross_n)
+        }
     }
 }
 
-/// Applies velocity corrections caused by dynamic friction and restitution.
+/// The values of a component in the [`SolverBodies`], along with their change ticks.
+struct SolverColumn<T> {
+    values: Vec<T>,
+    added: Vec<Tick>,
+    changed: Vec<Tick>,
+}
+
+impl<T> Default for SolverColumn<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            added: Vec::new(),
+            changed: Vec::new(),
+        }
+    }
+}
+
+impl<T: Component + Copy + PartialEq> SolverColumn<T> {
+    fn clear(&mut self) {
+        self.values.clear();
+        self.added.clear();
+        self.changed.clear();
+    }
+
+    /// Adds the value of a component. The ticks are used for change detection when the value
+    /// is accessed through a [`SolverBodiesMut`].
+    fn push(&mut self, value: Ref<T>, this_run: Tick) {
+        self.values.push(*value);
+        self.added.push(if value.is_added() {
+            this_run
+        } else {
+            Tick::new(0)
+        });
+        self.changed.push(value.last_changed());
+    }
+
+    fn get_many<const N: usize>(
+        &self,
+        slots: [usize; N],
+        last_run: Tick,
+        this_run: Tick,
+    ) -> [Ref<'_, T>; N] {
+        slots.map(|slot| {
+            Ref::new(
+                &self.values[slot],
+                &self.added[slot],
+                &self.changed[slot],
+                last_run,
+                this_run,
+            )
+        })
+    }
+
+    fn get_many_mut<const N: usize>(
+        &mut self,
+        slots: [usize; N],
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Option<[Mut<'_, T>; N]> {
+        let values = self.values.get_disjoint_mut(slots).ok()?;
+        let added = self.added.get_disjoint_mut(slots).ok()?;
+        let changed = self.changed.get_disjoint_mut(slots).ok()?;
+        let mut ticks = added.into_iter().zip(changed);
+        Some(values.map(|value| {
+            let (added, changed) = ticks.next().unwrap();
+            Mut::new(value, added, changed, last_run, this_run)
+        }))
+    }
+
+    /// Sets the value in the given slot, marking it as changed if it is different from the current value.
+    fn set(&mut self, slot: usize, value: T, this_run: Tick) {
+        // avoid triggering bevy's change detection unnecessarily
+        if self.values[slot] != value {
+            self.values[slot] = value;
+            self.changed[slot] = this_run;
+        }
+    }
+
+    /// Writes the value in the given slot to `target` if it has been changed since the `since` tick.
+    fn write_back(&self, slot: usize, target: &mut Mut<T>, since: Tick, this_run: Tick) {
+        if self.changed[slot].is_newer_than(since, this_run) {
+            **target = self.values[slot];
+        }
+    }
+}
+
+/// A [`SystemParam`] for accessing the [`SolverBodies`] with change detection.
+///
+/// The bodies are returned as [`RigidBodyQueryItem`]s along with their [`SolverBodyState`],
+/// so [constraints] can be solved the same way as when the bodies are queried directly.
+#[derive(SystemParam)]
+pub struct SolverBodiesMut<'w> {
+    bodies: ResMut<'w, SolverBodies>,
+    ticks: SystemChangeTick,
+}
+
+impl SolverBodiesMut<'_> {
+    /// Returns the given body, or `None` if the body is not in the buffers.
+    pub fn get_mut(&mut self, entity: Entity) -> Option<(RigidBodyQueryItem<'_>, SolverBodyState)> {
+        self.get_many_mut([entity]).map(|[body]| body)
+    }
+
+    /// Returns the given bodies, or `None` if any of the bodies is not in the buffers
+    /// or if the same body is given more than once.
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        entities: [Entity; N],
+    ) -> Option<[(RigidBodyQueryItem<'_>, SolverBodyState); N]> {
+        let mut slots = [0; N];
+        for (slot, entity) in slots.iter_mut().zip(entities) {
+            *slot = self.bodies.slot(entity)?;
+        }
+        let (last_run, this_run) = (self.ticks.last_run(), self.ticks.this_run());
+        self.bodies
+            .bypass_change_detection()
+            .items_mut(slots, last_run, this_run)
+    }
+
+    /// Calls `f` for each body in the order of their slots.
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(RigidBodyQueryItem, SolverBodyState)) {
+        let (last_run, this_run) = (self.ticks.last_run(), self.ticks.this_run());
+        let bodies = self.bodies.bypass_change_detection();
+        for slot in 0..bodies.len() {
+            if let Some([(body, state)]) = bodies.items_mut([slot], last_run, this_run) {
+                f(body, state);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for SolverBodiesMut<'_> {
+    type Target = SolverBodies;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bodies
+    }
+}
+
+/// Gathers the data of all rigid bodies into the [`SolverBodies`] before the constraints are solved.
 #[allow(clippy::type_complexity)]
+fn gather_solver_bodies(
+    mut solver_bodies: ResMut<SolverBodies>,
+    bodies: Query<(
+        Entity,
+        (
+            Ref<RigidBody>,
+            Ref<Position>,
+            Ref<Rotation>,
+            Ref<PreviousPosition>,
+            Ref<PreviousRotation>,
+            Ref<AccumulatedTranslation>,
+        ),
+        (
+            Ref<LinearVelocity>,
+            Ref<PreSolveLinearVelocity>,
+            Ref<AngularVelocity>,
+            Ref<PreSolveAngularVelocity>,
+        ),
+        (
+            Ref<Mass>,
+            Ref<InverseMass>,
+            Ref<Inertia>,
+            Ref<InverseInertia>,
+            Ref<CenterOfMass>,
+        ),
+        (
+            &Friction,
+            &Restitution,
+            Option<&LockedAxes>,
+            Option<&Dominance>,
+        ),
+        (Has<Sleeping>, Has<RigidBodyDisabled>, Option<&TimeScale>),
+    )>,
+    ticks: SystemChangeTick,
+) {
+    let this_run = ticks.this_run();
+    let solver_bodies = &mut *solver_bodies;
+
+    solver_bodies.clear();
+    solver_bodies.gathered = Some(this_run);
+
+    for (
+        entity,
+        (rb, position, rotation, previous_position, previous_rotation, accumulated_translation),
+        (linear_velocity, pre_solve_linear_velocity, angular_velocity, pre_solve_angular_velocity),
+        (mass, inverse_mass, inertia, inverse_inertia, center_of_mass),
+        (friction, restitution, locked_axes, dominance),
+        (is_sleeping, is_disabled, time_scale),
+    ) in &bodies
+    {
+        let index = entity.index() as usize;
+        if index >= solver_bodies.slots.len() {
+            solver_bodies.slots.resize(index + 1, u32::MAX);
+        }
+        solver_bodies.slots[index] = solver_bodies.entities.len() as u32;
+        solver_bodies.entities.push(entity);
+
+        solver_bodies.states.push(SolverBodyState {
+            is_sleeping,
+            is_disabled,
+            time_scale: time_scale.map_or(1.0, |scale| scale.0),
+        });
+        solver_bodies.rb.push(rb, this_run);
+        solver_bodies.position.push(position, this_run);
+        solver_bodies.rotation.push(rotation, this_run);
+        solver_bodies
+            .previous_position
+            .push(previous_position, this_run);
+        solver_bodies
+            .previous_rotation
+            .push(previous_rotation, this_run);
+        solver_bodies
+            .accumulated_translation
+            .push(accumulated_translation, this_run);
+        solver_bodies
+            .linear_velocity
+            .push(linear_velocity, this_run);
+        solver_bodies
+            .pre_solve_linear_velocity
+            .push(pre_solve_linear_velocity, this_run);
+        solver_bodies
+            .angular_velocity
+            .push(angular_velocity, this_run);
+        solver_bodies
+            .pre_solve_angular_velocity
+            .push(pre_solve_angular_velocity, this_run);
+        solver_bodies.mass.push(mass, this_run);
+        solver_bodies.inverse_mass.push(inverse_mass, this_run);
+        solver_bodies.inertia.push(inertia, this_run);
+        solver_bodies
+            .inverse_inertia
+            .push(inverse_inertia, this_run);
+        solver_bodies.center_of_mass.push(center_of_mass, this_run);
+        solver_bodies.friction.push(*friction);
+        solver_bodies.restitution.push(*restitution);
+        solver_bodies.locked_axes.push(locked_axes.copied());
+        solver_bodies.dominance.push(dominance.copied());
+    }
+}
+
+/// Writes the data that the solver changed in the [`SolverBodies`] back to the rigid bodies.
+fn scatter_solver_bodies(
+    solver_bodies: Res<SolverBodies>,
+    mut bodies: Query<RigidBodyQuery>,
+    ticks: SystemChangeTick,
+) {
+    let Some(since) = solver_bodies.gathered else {
+        return;
+    };
+    let this_run = ticks.this_run();
+
+    for mut body in &mut bodies {
+        let Some(slot) = solver_bodies.slot(body.entity) else {
+            continue;
+        };
+        solver_bodies
+            .position
+            .write_back(slot, &mut body.position, since, this_run);
+        solver_bodies
+            .rotation
+            .write_back(slot, &mut body.rotation, since, this_run);
+        solver_bodies.previous_position.write_back(
+            slot,
+            &mut body.previous_position,
+            since,
+            this_run,
+        );
+        solver_bodies.previous_rotation.write_back(
+            slot,
+            &mut body.previous_rotation,
+            since,
+            this_run,
+        );
+        solver_bodies.accumulated_translation.write_back(
+            slot,
+            &mut body.accumulated_translation,
+            since,
+            this_run,
+        );
+        solver_bodies
+            .linear_velocity
+            .write_back(slot, &mut body.linear_velocity, since, this_run);
+        solver_bodies.pre_solve_linear_velocity.write_back(
+            slot,
+            &mut body.pre_solve_linear_velocity,
+            since,
+            this_run,
+        );
+        solver_bodies.angular_velocity.write_back(
+            slot,
+            &mut body.angular_velocity,
+            since,
+            this_run,
+        );
+        solver_bodies.pre_solve_angular_velocity.write_back(
+            slot,
+            &mut body.pre_solve_angular_velocity,
+            since,
+            this_run,
+        );
+        solver_bodies
+            .mass
+            .write_back(slot, &mut body.mass, since, this_run);
+        solver_bodies
+            .inverse_mass
+            .write_back(slot, &mut body.inverse_mass, since, this_run);
+        solver_bodies
+            .inertia
+            .write_back(slot, &mut body.inertia, since, this_run);
+        solver_bodies
+            .inverse_inertia
+            .write_back(slot, &mut body.inverse_inertia, since, this_run);
+        solver_bodies
+            .center_of_mass
+            .write_back(slot, &mut body.center_of_mass, since, this_run);
+    }
+}
+
+/// Rotates a vector from the local space of a collider to world space.
+fn collider_to_world(
+    collider: &ColliderQueryItem,
+    body_rotation: &Rotation,
+    vector: Vector,
+) -> Vector {
+    let vector = collider
+        .transform
+        .map_or(vector, |transform| transform.rotation.rotate(vector));
+    body_rotation.rotate(vector)
+}
+
+/// Computes the [`SurfaceVelocity`] of a collider at the given point in the local space of the collider,
+/// rotated to world space.
+fn global_surface_velocity(
+    collider: &ColliderQueryItem,
+    body_rotation: &Rotation,
+    point: Vector,
+) -> Vector {
+    collider
+        .surface_velocity
+        .map_or(Vector::ZERO, |surface_velocity| {
+            collider_to_world(collider, body_rotation, surface_velocity.velocity_at(point))
+        })
+}
+
+/// Applies velocity corrections caused by dynamic friction and restitution.
+///
+/// The constraints are solved against the slots of the bodies in the [`SolverBodies`].
 fn solve_vel(
-    mut bodies: Query<RigidBodyQuery, Without<Sleeping>>,
+    mut solver_bodies: ResMut<SolverBodies>,
     mut penetration_constraints: ResMut<PenetrationConstraints>,
     gravity: Res<Gravity>,
     time: Res<Time>,
+    ticks: SystemChangeTick,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
+    let this_run = ticks.this_run();
+    let solver_bodies = solver_bodies.bypass_change_detection();
 
     for constraint in penetration_constraints.0.iter_mut() {
-        if let Ok([mut body1, mut body2]) = bodies.get_many_mut(constraint.entities()) {
-            if !body1.rb.is_dynamic() && !body2.rb.is_dynamic() {
-                continue;
-            }
+        let [entity1, entity2] = constraint.entities();
+        let (Some(slot1), Some(slot2)) = (solver_bodies.slot(entity1), solver_bodies.slot(entity2))
+        else {
+            continue;
+        };
 
-            // Skip constraint if it didn't apply a correction
-            if constraint.normal_lagrange == 0.0 {
-                continue;
-            }
+        // Sleeping bodies are not moved by the velocity solve
+        if slot1 == slot2
+            || solver_bodies.states[slot1].is_sleeping
+            || solver_bodies.states[slot2].is_sleeping
+        {
+            continue;
+        }
 
-            let normal = constraint.contact.global_normal1(&body1.rotation);
-            let r1 = body1.rotation.rotate(constraint.r1);
-            let r2 = body2.rotation.rotate(constraint.r2);
+        let is_dynamic1 = solver_bodies.is_dynamic(slot1);
+        let is_dynamic2 = solver_bodies.is_dynamic(slot2);
 
-            // Compute pre-solve relative normal velocities at the contact point (used for restitution)
-            let pre_solve_contact_vel1 = compute_contact_vel(
-                body1.pre_solve_linear_velocity.0,
-                body1.pre_solve_angular_velocity.0,
-                r1,
-            );
-            let pre_solve_contact_vel2 = compute_contact_vel(
-                body2.pre_solve_linear_velocity.0,
-                body2.pre_solve_angular_velocity.0,
-                r2,
-            );
-            let pre_solve_relative_vel = pre_solve_contact_vel1 - pre_solve_contact_vel2;
-            let pre_solve_normal_speed = normal.dot(pre_solve_relative_vel);
-
-            // Compute relative normal and tangential velocities at the contact point (equation 29)
-            let contact_vel1 =
-                compute_contact_vel(body1.linear_velocity.0, body1.angular_velocity.0, r1);
-            let contact_vel2 =
-                compute_contact_vel(body2.linear_velocity.0, body2.angular_velocity.0, r2);
-            let relative_vel = contact_vel1 - contact_vel2;
-
-            let normal_speed = normal.dot(relative_vel);
-            let tangent_vel = relative_vel - normal * normal_speed;
-            let tangent_speed = tangent_vel.length();
-
-            let inv_mass1 = body1.effective_inv_mass();
-            let inv_mass2 = body2.effective_inv_mass();
-            let inv_inertia1 = body1.effective_world_inv_inertia();
-            let inv_inertia2 = body2.effective_world_inv_inertia();
-
-            let mut p = Vector::ZERO;
-
-            // Compute restitution
-            let restitution_speed = compute_restitution(
-                normal_speed,
-                pre_solve_normal_speed,
-                constraint.restitution.coefficient,
-                gravity.0,
+        if !is_dynamic1 && !is_dynamic2 {
+            continue;
+        }
+
+        // Skip constraint if it didn't apply a correction
+        if constraint.normal_lagrange == 0.0 {
+            continue;
+        }
+
+        let rotation1 = &solver_bodies.rotation.values[slot1];
+        let rotation2 = &solver_bodies.rotation.values[slot2];
+        let normal = constraint.contact.global_normal1(rotation1);
+        let r1 = rotation1.rotate(constraint.r1);
+        let r2 = rotation2.rotate(constraint.r2);
+
+        // Compute pre-solve relative normal velocities at the contact point (used for restitution)
+        let pre_solve_contact_vel1 = compute_contact_vel(
+            solver_bodies.pre_solve_linear_velocity.values[slot1].0,
+            solver_bodies.pre_solve_angular_velocity.values[slot1].0,
+            r1,
+        );
+        let pre_solve_contact_vel2 = compute_contact_vel(
+            solver_bodies.pre_solve_linear_velocity.values[slot2].0,
+            solver_bodies.pre_solve_angular_velocity.values[slot2].0,
+            r2,
+        );
+        let pre_solve_relative_vel = pre_solve_contact_vel1 - pre_solve_contact_vel2;
+        let pre_solve_normal_speed = normal.dot(pre_solve_relative_vel);
+
+        // Compute relative normal and tangential velocities at the contact point (equation 29)
+        let contact_vel1 = compute_contact_vel(
+            solver_bodies.linear_velocity.values[slot1].0,
+            solver_bodies.angular_velocity.values[slot1].0,
+            r1,
+        );
+        let contact_vel2 = compute_contact_vel(
+            solver_bodies.linear_velocity.values[slot2].0,
+            solver_bodies.angular_velocity.values[slot2].0,
+            r2,
+        );
+        let relative_vel = contact_vel1 - contact_vel2;
+
+        let normal_speed = normal.dot(relative_vel);
+        // The surface velocity only affects friction
+        let surface_vel = constraint.surface_velocity;
+        let surface_vel = surface_vel - normal * normal.dot(surface_vel);
+        let tangent_vel = relative_vel - normal * normal_speed + surface_vel;
+        let tangent_speed = tangent_vel.length();
+
+        let mut p = Vector::ZERO;
+
+        // Compute restitution
+        let restitution_speed = compute_restitution(
+            normal_speed,
+            pre_solve_normal_speed,
+            constraint.restitution.coefficient,
+            gravity.0,
+            delta_secs,
+        );
+        if restitution_speed.abs() > Scalar::EPSILON {
+            let w1 = solver_bodies.generalized_inverse_mass(slot1, r1, normal);
+            let w2 = solver_bodies.generalized_inverse_mass(slot2, r2, normal);
+            let restitution_impulse = restitution_speed / (w1 + w2);
+            p += restitution_impulse * normal;
+            constraint.contact.normal_impulse += restitution_impulse;
+        }
+
+        // Wheels use the tire model when they are moving over the ground fast enough for slip to be meaningful
+        let tire_impulse = constraint.tire.and_then(|tire| {
+            compute_tire_friction(
+                solver_bodies,
+                [slot1, slot2],
+                &tire,
+                [r1, r2],
+                normal,
+                tangent_vel,
+                constraint.friction.dynamic_coefficient,
+                constraint.normal_lagrange,
+                delta_secs,
+            )
+        });
+
+        // Compute dynamic friction
+        if let Some(impulse) = tire_impulse {
+            p += impulse;
+            constraint.contact.tangent_impulse += impulse.length();
+        } else if tangent_speed > Scalar::EPSILON {
+            let tangent = tangent_vel / tangent_speed;
+            let w1 = solver_bodies.generalized_inverse_mass(slot1, r1, tangent);
+            let w2 = solver_bodies.generalized_inverse_mass(slot2, r2, tangent);
+            let friction_impulse = compute_dynamic_friction(
+                tangent_speed,
+                w1 + w2,
+                constraint
+                    .friction_in_direction(tangent, normal)
+                    .dynamic_coefficient,
+                constraint.normal_lagrange,
                 delta_secs,
             );
-            if restitution_speed.abs() > Scalar::EPSILON {
-                let w1 = constraint.compute_generalized_inverse_mass(&body1, r1, normal);
-                let w2 = constraint.compute_generalized_inverse_mass(&body2, r2, normal);
-                let restitution_impulse = restitution_speed / (w1 + w2);
-                p += restitution_impulse * normal;
-                constraint.contact.normal_impulse += restitution_impulse;
-            }
+            p += friction_impulse * tangent;
+            constraint.contact.tangent_impulse += friction_impulse;
+        }
 
-            // Compute dynamic friction
-            if tangent_speed > Scalar::EPSILON {
-                let tangent = tangent_vel / tangent_speed;
-                let w1 = constraint.compute_generalized_inverse_mass(&body1, r1, tangent);
-                let w2 = constraint.compute_generalized_inverse_mass(&body2, r2, tangent);
-                let friction_impulse = compute_dynamic_friction(
-                    tangent_speed,
-                    w1 + w2,
-                    constraint.friction.dynamic_coefficient,
-                    constraint.normal_lagrange,
-                    delta_secs,
-                );
-                p += friction_impulse * tangent;
-                constraint.contact.tangent_impulse += friction_impulse;
-            }
+        let [dominance1, dominance2] = constraint.dominance_override.unwrap_or([
+            solver_bodies.dominance(slot1),
+            solver_bodies.dominance(slot2),
+        ]);
+
+        if is_dynamic1 && dominance1 <= dominance2 {
+            let lin_vel1 = solver_bodies.linear_velocity.values[slot1].0
+                + p * solver_bodies.effective_inverse_mass(slot1);
+            let ang_vel1 = solver_bodies.angular_velocity.values[slot1].0
+                + compute_delta_ang_vel(solver_bodies.effective_inverse_inertia(slot1), r1, p);
+            solver_bodies
+                .linear_velocity
+                .set(slot1, LinearVelocity(lin_vel1), this_run);
+            solver_bodies
+                .angular_velocity
+                .set(slot1, AngularVelocity(ang_vel1), this_run);
+        }
+        if is_dynamic2 && dominance2 <= dominance1 {
+            let lin_vel2 = solver_bodies.linear_velocity.values[slot2].0
+                - p * solver_bodies.effective_inverse_mass(slot2);
+            let ang_vel2 = solver_bodies.angular_velocity.values[slot2].0
+                - compute_delta_ang_vel(solver_bodies.effective_inverse_inertia(slot2), r2, p);
+            solver_bodies
+                .linear_velocity
+                .set(slot2, LinearVelocity(lin_vel2), this_run);
+            solver_bodies
+                .angular_velocity
+                .set(slot2, AngularVelocity(ang_vel2), this_run);
+        }
+    }
+}
 
-            if body1.rb.is_dynamic() && body1.dominance() <= body2.dominance() {
-                let delta_lin_vel = p * inv_mass1;
-                let delta_ang_vel = compute_delta_ang_vel(inv_inertia1, r1, p);
+/// Computes the friction impulse applied to the first body by the [`TireFriction`] of a wheel in a contact,
+/// or `None` if the wheel is moving too slowly for the tire model.
+///
+/// The slip ratio and slip angle are computed from the velocity of the wheel over the ground,
+/// and they are combined so that the tire can't produce its peak force in both directions at once.
+#[allow(clippy::too_many_arguments)]
+fn compute_tire_friction(
+    solver_bodies: &SolverBodies,
+    [slot1, slot2]: [usize; 2],
+    tire: &TireContact,
+    [r1, r2]: [Vector; 2],
+    normal: Vector,
+    tangent_vel: Vector,
+    coefficient: Scalar,
+    normal_lagrange: Scalar,
+    delta_secs: Scalar,
+) -> Option<Vector> {
+    // The rolling direction of the wheel along the contact surface
+    #[cfg(feature = "2d")]
+    let forward = normal.perp();
+    #[cfg(feature = "3d")]
+    let forward = tire.axle.cross(normal).try_normalize()?;
+
+    // Compute the velocity of the wheel's hub relative to the ground at the contact point
+    let (wheel, ground, r_ground) = if tire.wheel_is_first {
+        (slot1, slot2, r2)
+    } else {
+        (slot2, slot1, r1)
+    };
+    let ground_vel = compute_contact_vel(
+        solver_bodies.linear_velocity.values[ground].0,
+        solver_bodies.angular_velocity.values[ground].0,
+        r_ground,
+    );
+    let hub_vel = solver_bodies.linear_velocity.values[wheel].0 - ground_vel;
+    let hub_speed = (hub_vel - normal * normal.dot(hub_vel)).length();
+
+    if hub_speed < tire.friction.min_speed {
+        return None;
+    }
 
-                if delta_lin_vel != Vector::ZERO {
-                    body1.linear_velocity.0 += delta_lin_vel;
-                }
-                if delta_ang_vel != AngularVelocity::ZERO.0 {
-                    body1.angular_velocity.0 += delta_ang_vel;
-                }
-            }
-            if body2.rb.is_dynamic() && body2.dominance() <= body1.dominance() {
-                let delta_lin_vel = p * inv_mass2;
-                let delta_ang_vel = compute_delta_ang_vel(inv_inertia2, r2, p);
+    let hub_forward_speed = hub_vel.dot(forward).abs().max(tire.friction.min_speed);
+    let normal_impulse = (normal_lagrange / delta_secs).abs();
+    let longitudinal_curve = &tire.friction.longitudinal;
 
-                if delta_lin_vel != Vector::ZERO {
-                    body2.linear_velocity.0 -= delta_lin_vel;
-                }
-                if delta_ang_vel != AngularVelocity::ZERO.0 {
-                    body2.angular_velocity.0 -= delta_ang_vel;
-                }
-            }
+    // The slip of the contact point in the rolling direction
+    let forward_speed = tangent_vel.dot(forward);
+    let slip_ratio = forward_speed / hub_forward_speed;
+
+    #[cfg(feature = "2d")]
+    {
+        let w = solver_bodies.generalized_inverse_mass(slot1, r1, forward)
+            + solver_bodies.generalized_inverse_mass(slot2, r2, forward);
+        let multiplier = longitudinal_curve.evaluate(slip_ratio);
+        // Clamp the impulse so that it never reverses the slip
+        let magnitude = -(multiplier * coefficient * normal_impulse).min(forward_speed.abs() / w)
+            * forward_speed.signum();
+        Some(magnitude * forward)
+    }
+
+    #[cfg(feature = "3d")]
+    {
+        let lateral_curve = &tire.friction.lateral;
+
+        // The slip of the contact point perpendicular to the rolling direction
+        let side = normal.cross(forward);
+        let side_speed = tangent_vel.dot(side);
+        let slip_angle = side_speed.atan2(hub_forward_speed);
+
+        // Combine the slips relative to the peaks of the curves, so that the tire
+        // loses grip in both directions when slipping heavily in either one
+        let normalized_slip_ratio = slip_ratio / longitudinal_curve.extremum_slip;
+        let normalized_slip_angle = slip_angle / lateral_curve.extremum_slip;
+        let combined_slip = normalized_slip_ratio.hypot(normalized_slip_angle);
+        if combined_slip <= Scalar::EPSILON {
+            return None;
         }
+
+        let longitudinal = longitudinal_curve
+            .evaluate(combined_slip * longitudinal_curve.extremum_slip)
+            * normalized_slip_ratio.abs()
+            / combined_slip;
+        let lateral = lateral_curve.evaluate(combined_slip * lateral_curve.extremum_slip)
+            * normalized_slip_angle.abs()
+            / combined_slip;
+
+        // Clamp the impulses so that they never reverse the slip
+        let forward_w = solver_bodies.generalized_inverse_mass(slot1, r1, forward)
+            + solver_bodies.generalized_inverse_mass(slot2, r2, forward);
+        let side_w = solver_bodies.generalized_inverse_mass(slot1, r1, side)
+            + solver_bodies.generalized_inverse_mass(slot2, r2, side);
+        let forward_impulse = -(longitudinal * coefficient * normal_impulse)
+            .min(forward_speed.abs() / forward_w)
+            * forward_speed.signum();
+        let side_impulse = -(lateral * coefficient * normal_impulse).min(side_speed.abs() / side_w)
+            * side_speed.signum();
+
+        Some(forward_impulse * forward + side_impulse * side)
     }
 }
 
 /// Applies velocity corrections caused by joint damping.
 #[allow(clippy::type_complexity)]
 pub fn joint_damping<T: Joint>(
-    mut bodies: Query<
-        (
-            &RigidBody,
-            &mut LinearVelocity,
-            &mut AngularVelocity,
-            &InverseMass,
-            Option<&Dominance>,
-        ),
-        Without<Sleeping>,
-    >,
-    joints: Query<&T, Without<RigidBody>>,
+    mut bodies: SolverBodiesMut,
+    joints: Query<(Entity, Option<&StableId>, &T), Without<RigidBody>>,
+    mut order: Local<SolveOrder>,
     time: Res<Time>,
 ) {
     let delta_secs = time.delta_seconds_adjusted();
 
-    for joint in &joints {
-        if let Ok(
-            [(rb1, mut lin_vel1, mut ang_vel1, inv_mass1, dominance1), (rb2, mut lin_vel2, mut ang_vel2, inv_mass2, dominance2)],
-        ) = bodies.get_many_mut(joint.entities())
+    order.clear();
+    order.extend(
+        joints
+            .iter()
+            .map(|(entity, id, _)| (StableId::sort_key(id, entity), entity)),
+    );
+    order.sort_unstable();
+
+    for &(_, entity) in order.iter() {
+        let Ok((_, _, joint)) = joints.get(entity) else {
+            continue;
+        };
+
+        if let Some([(mut body1, state1), (mut body2, state2)]) =
+            bodies.get_many_mut(joint.entities())
         {
+            if state1.is_sleeping || state2.is_sleeping || state1.is_disabled || state2.is_disabled
+            {
+                continue;
+            }
+
+            let (rb1, rb2) = (*body1.rb, *body2.rb);
+            let (dominance1, dominance2) = (body1.dominance(), body2.dominance());
+            let (inv_mass1, inv_mass2) = (*body1.inverse_mass, *body2.inverse_mass);
+            let (lin_vel1, ang_vel1) = (&mut body1.linear_velocity, &mut body1.angular_velocity);
+            let (lin_vel2, ang_vel2) = (&mut body2.linear_velocity, &mut body2.angular_velocity);
+
             let delta_omega =
                 (ang_vel2.0 - ang_vel1.0) * (joint.damping_angular() * delta_secs).min(1.0);
 
@@ -585,9 +1588,6 @@ pub fn joint_damping<T: Joint>(
 
             let p = delta_v / (w1 + w2);
 
-            let dominance1 = dominance1.map_or(0, |dominance| dominance.0);
-            let dominance2 = dominance2.map_or(0, |dominance| dominance.0);
-
             if rb1.is_dynamic() && (!rb2.is_dynamic() || dominance1 <= dominance2) {
                 lin_vel1.0 += p * inv_mass1.0;
             }
@@ -608,11 +1608,20 @@ fn store_contact_impulses(
         else {
             continue;
         };
-        if let Some(Some(contact)) = collision
-            .manifolds
-            .get_mut(constraint.manifold_index)
-            .map(|m| m.contacts.get_mut(constraint.contact.index))
-        {
+        let Some(manifold) = collision.manifolds.get_mut(constraint.manifold_index) else {
+            continue;
+        };
+
+        // Match the contact by its feature IDs if they are known, and fall back to the index otherwise.
+        let contact = match constraint.contact.id() {
+            Some(id) => manifold
+                .contacts
+                .iter_mut()
+                .find(|contact| contact.id() == Some(id)),
+            None => manifold.contacts.get_mut(constraint.contact.index),
+        };
+
+        if let Some(contact) = contact {
             contact.normal_impulse = constraint.contact.normal_impulse;
             contact.tangent_impulse = constraint.contact.tangent_impulse;
 
@@ -622,6 +1631,59 @@ fn store_contact_impulses(
     }
 }
 
+/// Handles changes to the [`CenterOfMass`] of bodies, for example when it is animated at runtime.
+///
+/// The [`AccumulatedTranslation`] and [`LinearVelocity`] describe the motion of the center of mass,
+/// so when the center of mass moves within the body, they are adjusted so that the body itself
+/// doesn't jump or change its motion.
+#[allow(clippy::type_complexity)]
+fn handle_center_of_mass_changes(
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &CenterOfMass,
+            &mut PreviousCenterOfMass,
+            &Rotation,
+            &PreviousRotation,
+            &mut AccumulatedTranslation,
+            &mut LinearVelocity,
+            &AngularVelocity,
+        ),
+        Changed<CenterOfMass>,
+    >,
+) {
+    for (
+        rb,
+        center_of_mass,
+        mut previous_center_of_mass,
+        rot,
+        prev_rot,
+        mut translation,
+        mut lin_vel,
+        ang_vel,
+    ) in &mut bodies
+    {
+        let delta = center_of_mass.0 - previous_center_of_mass.0;
+        previous_center_of_mass.0 = center_of_mass.0;
+
+        if rb.is_static() || delta == Vector::ZERO {
+            continue;
+        }
+
+        // The translation was accumulated for the previous center of mass. Shift it so that
+        // the position computed from it in `apply_translation` stays the same.
+        let delta_translation = rot.rotate(delta) - prev_rot.rotate(delta);
+        if delta_translation != Vector::ZERO {
+            translation.0 += delta_translation;
+        }
+
+        // The new center of mass moves with the velocity of the body at that point
+        if rb.is_dynamic() && *ang_vel != AngularVelocity::ZERO {
+            lin_vel.0 = compute_contact_vel(lin_vel.0, ang_vel.0, rot.rotate(delta));
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 fn apply_translation(
     mut bodies: Query<
@@ -121,7 +121,8 @@
 //! ## Point projection
 //!
 //! **Point projection** is a spatial query that projects a point on the closest collider. It returns the collider's
-//! entity, the projected point, and whether the point is inside of the collider.
+//! entity, the projected point, and whether the point is inside of the collider. This can be used for things like
+//! snapping objects to surfaces, selecting the closest target for AI, or placing decals.
 //!
 //! Point projection can be done with the [`project_point`](SpatialQuery::project_point) method of the [`SpatialQuery`]
 //! system parameter. See its documentation for more information.
@@ -183,7 +184,7 @@ pub use shape_caster::*;
 ))]
 pub use system_param::*;
 
-use crate::{prelude::*, prepare::PrepareSet};
+use crate::{prelude::*, prepare::PrepareSet, sync::SyncSet};
 use bevy::{prelude::*, utils::intern::Interned};
 
 /// Initializes the [`SpatialQueryPipeline`] resource and handles component-based [spatial queries](spatial_query)
@@ -218,6 +219,9 @@ impl Plugin for SpatialQueryPlugin {
         ))]
         app.init_resource::<SpatialQueryPipeline>();
 
+        app.init_resource::<SpatialQueryPoses>()
+            .register_type::<SpatialQueryPoses>();
+
         app.add_systems(self.schedule, init_ray_hits.in_set(PrepareSet::PreInit));
 
         #[cfg(all(
@@ -226,6 +230,27 @@ impl Plugin for SpatialQueryPlugin {
         ))]
         app.add_systems(self.schedule, init_shape_hits.in_set(PrepareSet::PreInit));
 
+        // Keep the pipeline up to date when the simulation isn't being stepped,
+        // so that spatial queries don't use stale data while physics is paused.
+        // Collider scales are updated at the end of the sync, so the set runs after that.
+        app.configure_sets(
+            self.schedule,
+            SpatialQueryUpdateSet
+                .after(PhysicsSet::Sync)
+                .after(SyncSet::Last),
+        );
+
+        #[cfg(all(
+            feature = "default-collider",
+            any(feature = "parry-f32", feature = "parry-f64")
+        ))]
+        app.add_systems(
+            self.schedule,
+            (|mut spatial_query: SpatialQuery| spatial_query.update_pipeline_incremental())
+                .in_set(SpatialQueryUpdateSet)
+                .run_if(|time: Res<Time<Physics>>| time.is_paused()),
+        );
+
         let physics_schedule = app
             .get_schedule_mut(PhysicsSchedule)
             .expect("add PhysicsSchedule first");
@@ -239,6 +264,7 @@ impl Plugin for SpatialQueryPlugin {
                 ))]
                 (
                     update_shape_caster_positions,
+                    update_static_colliders,
                     |mut spatial_query: SpatialQuery| spatial_query.update_pipeline(),
                     raycast,
                     shapecast,
@@ -251,6 +277,62 @@ impl Plugin for SpatialQueryPlugin {
     }
 }
 
+/// A system set for updating the [`SpatialQueryPipeline`] outside of the [`PhysicsSchedule`].
+///
+/// The pipeline is normally updated once per physics step in [`PhysicsStepSet::SpatialQuery`].
+/// When [physics is paused](PhysicsTime::pause), the [`SpatialQueryPlugin`] instead updates the pipeline
+/// incrementally in this set so that spatial queries reflect collider changes made during the frame,
+/// which is useful for things like editor tooling and pause menus.
+///
+/// This set runs after [`PhysicsSet::Sync`] in the schedule used by the [`SpatialQueryPlugin`].
+/// If you step physics manually, you can add your own system that calls
+/// [`SpatialQuery::update_pipeline_incremental`] to this set.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SpatialQueryUpdateSet;
+
+/// A resource that determines which poses of colliders are used for [spatial queries](spatial_query).
+///
+/// By default, spatial queries test against the latest [`Position`] and [`Rotation`] computed by the physics
+/// simulation. When transforms are interpolated or extrapolated for rendering, these can differ from what
+/// is actually visible on screen, which can make hit detection feel off, for example when shooting
+/// at fast-moving targets.
+///
+/// Setting this to [`SpatialQueryPoses::Rendered`] makes the [`SpatialQueryPipeline`] use the [`GlobalTransform`]
+/// of each collider instead, so that spatial queries line up with what the player sees.
+/// Colliders without a [`GlobalTransform`] are not included in spatial queries in this mode.
+///
+/// Note that the pipeline is updated in [`PhysicsStepSet::SpatialQuery`], so the transforms are the ones
+/// that were rendered on the previous frame. While [physics is paused](PhysicsTime::pause),
+/// the physics poses are used regardless of this setting.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(SpatialQueryPoses::Rendered)
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Resource)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpatialQueryPoses {
+    /// Spatial queries use the [`Position`] and [`Rotation`] of colliders computed by the physics simulation.
+    #[default]
+    Physics,
+    /// Spatial queries use the [`GlobalTransform`] of colliders, which includes any interpolation
+    /// applied for rendering.
+    Rendered,
+}
+
 fn init_ray_hits(mut commands: Commands, rays: Query<(Entity, &RayCaster), Added<RayCaster>>) {
     for (entity, ray) in &rays {
         let max_hits = if ray.max_hits == u32::MAX {
@@ -451,6 +533,95 @@ fn update_shape_caster_positions(
     }
 }
 
+/// Updates the colliders attached to [static](RigidBody::Static) and [sleeping](Sleeping) bodies
+/// in the [`SpatialQueryPipeline`] when they have been added, changed or removed, or when bodies
+/// have fallen asleep or woken up. This way, the tree of static colliders doesn't need to be rebuilt
+/// every frame, and colliders of sleeping bodies are kept out of the tree of moving colliders until they wake up.
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn update_static_colliders(
+    colliders: Query<(
+        Entity,
+        &Position,
+        &Rotation,
+        &Collider,
+        Option<&CollisionLayers>,
+    )>,
+    changed_colliders: Query<
+        Entity,
+        (
+            With<Collider>,
+            Or<(
+                Changed<Position>,
+                Changed<Rotation>,
+                Changed<Collider>,
+                Changed<CollisionLayers>,
+                Changed<ColliderParent>,
+            )>,
+        ),
+    >,
+    mut removed_colliders: RemovedComponents<Collider>,
+    collider_parents: Query<&ColliderParent>,
+    bodies: Query<(&RigidBody, bevy::ecs::query::Has<Sleeping>)>,
+    changed_bodies: Query<Ref<RigidBody>, Changed<RigidBody>>,
+    fallen_asleep: Query<Entity, Added<Sleeping>>,
+    mut woken_bodies: RemovedComponents<Sleeping>,
+    mut query_pipeline: ResMut<SpatialQueryPipeline>,
+) {
+    let is_static_or_sleeping = |entity: &Entity| {
+        collider_parents.get(*entity).is_ok_and(|parent| {
+            bodies
+                .get(parent.get())
+                .is_ok_and(|(rb, is_sleeping)| rb.is_static() || is_sleeping)
+        })
+    };
+
+    // Colliders of bodies that have fallen asleep or woken up move between the static and dynamic colliders.
+    let sleep_changed_bodies: bevy::utils::HashSet<Entity> =
+        fallen_asleep.iter().chain(woken_bodies.read()).collect();
+
+    // If the type of an existing body has changed, colliders can move between the static
+    // and dynamic colliders, so all colliders are checked. Otherwise, only changed colliders
+    // and colliders of bodies that have fallen asleep or woken up are checked.
+    let candidates: Vec<Entity> = if changed_bodies.iter().any(|rb| !rb.is_added()) {
+        colliders.iter().map(|(entity, ..)| entity).collect()
+    } else if sleep_changed_bodies.is_empty() {
+        changed_colliders.iter().collect()
+    } else {
+        let mut candidates: Vec<Entity> = changed_colliders.iter().collect();
+        candidates.extend(
+            colliders
+                .iter()
+                .map(|(entity, ..)| entity)
+                .filter(|entity| {
+                    collider_parents
+                        .get(*entity)
+                        .is_ok_and(|parent| sleep_changed_bodies.contains(&parent.get()))
+                }),
+        );
+        candidates
+    };
+
+    let (static_colliders, non_static_colliders): (Vec<Entity>, Vec<Entity>) =
+        candidates.into_iter().partition(is_static_or_sleeping);
+
+    // Colliders that are no longer static or sleeping are moved from the static colliders
+    // back to the regular colliders.
+    let no_longer_static: Vec<Entity> = non_static_colliders
+        .into_iter()
+        .filter(|entity| query_pipeline.static_colliders.contains_key(entity))
+        .collect();
+    let removed = removed_colliders
+        .read()
+        .chain(no_longer_static.iter().copied());
+
+    query_pipeline.update_static_colliders(colliders.iter_many(&static_colliders), removed);
+    query_pipeline.update_incremental(colliders.iter_many(&no_longer_static), std::iter::empty());
+}
+
 #[cfg(all(
     feature = "default-collider",
     any(feature = "parry-f32", feature = "parry-f64")
@@ -1,11 +1,15 @@
-use std::sync::Arc;
+use std::{collections::BinaryHeap, ops::Deref, sync::Arc};
 
 use crate::prelude::*;
+#[cfg(feature = "parallel")]
+use bevy::tasks::{ComputeTaskPool, ParallelSlice};
 use bevy::{prelude::*, utils::HashMap};
+#[cfg(feature = "3d")]
+use parry::query::RayCast;
 use parry::{
-    bounding_volume::Aabb,
-    math::Isometry,
-    partitioning::Qbvh,
+    bounding_volume::{Aabb, BoundingVolume},
+    math::{Isometry, SimdReal, SIMD_WIDTH},
+    partitioning::{Qbvh, QbvhUpdateWorkspace},
     query::{
         details::{
             RayCompositeShapeToiAndNormalBestFirstVisitor, TOICompositeShapeShapeBestFirstVisitor,
@@ -14,21 +18,35 @@ use parry::{
         visitors::{
             BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
         },
-        DefaultQueryDispatcher, QueryDispatcher,
+        DefaultQueryDispatcher, QueryDispatcher, SimdRay,
     },
     shape::{Shape, TypedSimdCompositeShape},
+    simba::simd::{SimdBool as _, SimdValue},
     utils::DefaultStorage,
 };
 
+/// The colliders stored in a [`SpatialQueryPipeline`] with their isometries and collision layers.
+pub(crate) type ColliderMap = HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>;
+
 /// A resource for the spatial query pipeline.
 ///
-/// The pipeline maintains a quaternary bounding volume hierarchy `Qbvh` of the world's colliders
-/// as an acceleration structure for spatial queries.
+/// The pipeline maintains quaternary bounding volume hierarchies `Qbvh` of the world's colliders
+/// as acceleration structures for spatial queries. Colliders attached to [static](RigidBody::Static) bodies
+/// are stored in a separate tree that is only updated when static colliders are added, changed or removed,
+/// so that static geometry doesn't add a per-frame cost. Colliders attached to [sleeping](Sleeping) bodies
+/// are kept in the same tree until the bodies wake up. Queries traverse both trees.
+///
+/// The trees are never rebuilt from scratch. Added colliders are inserted as new leaves, removed colliders
+/// are removed from their leaves, and only the leaves of colliders whose poses or shapes have changed
+/// are marked dirty before the trees are refitted around them.
 #[derive(Resource, Clone)]
 pub struct SpatialQueryPipeline {
     pub(crate) qbvh: Qbvh<u32>,
+    pub(crate) static_qbvh: Qbvh<u32>,
+    pub(crate) qbvh_workspace: QbvhUpdateWorkspace,
     pub(crate) dispatcher: Arc<dyn QueryDispatcher>,
-    pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    pub(crate) colliders: ColliderMap,
+    pub(crate) static_colliders: ColliderMap,
     pub(crate) entity_generations: HashMap<u32, u32>,
 }
 
@@ -36,8 +54,11 @@ impl Default for SpatialQueryPipeline {
     fn default() -> Self {
         Self {
             qbvh: Qbvh::new(),
+            static_qbvh: Qbvh::new(),
+            qbvh_workspace: QbvhUpdateWorkspace::default(),
             dispatcher: Arc::new(DefaultQueryDispatcher),
             colliders: HashMap::default(),
+            static_colliders: HashMap::default(),
             entity_generations: HashMap::default(),
         }
     }
@@ -49,31 +70,70 @@ impl SpatialQueryPipeline {
         SpatialQueryPipeline::default()
     }
 
-    pub(crate) fn as_composite_shape(
+    /// Returns the trees of the dynamic and static colliders as composite shapes.
+    pub(crate) fn as_composite_shapes(
         &self,
         query_filter: SpatialQueryFilter,
-    ) -> QueryPipelineAsCompositeShape {
-        QueryPipelineAsCompositeShape {
-            pipeline: self,
-            colliders: &self.colliders,
-            query_filter,
-        }
+    ) -> [QueryPipelineAsCompositeShape<'_>; 2] {
+        [
+            QueryPipelineAsCompositeShape {
+                pipeline: self,
+                qbvh: &self.qbvh,
+                colliders: &self.colliders,
+                query_filter: query_filter.clone(),
+            },
+            QueryPipelineAsCompositeShape {
+                pipeline: self,
+                qbvh: &self.static_qbvh,
+                colliders: &self.static_colliders,
+                query_filter,
+            },
+        ]
     }
 
-    pub(crate) fn as_composite_shape_with_predicate<'a>(
+    /// Returns the trees of the dynamic and static colliders as composite shapes with the given predicate.
+    pub(crate) fn as_composite_shapes_with_predicate<'a>(
         &'a self,
         query_filter: SpatialQueryFilter,
         predicate: &'a dyn Fn(Entity) -> bool,
-    ) -> QueryPipelineAsCompositeShapeWithPredicate {
-        QueryPipelineAsCompositeShapeWithPredicate {
-            pipeline: self,
-            colliders: &self.colliders,
-            query_filter,
-            predicate,
-        }
+    ) -> [QueryPipelineAsCompositeShapeWithPredicate<'a, 'a>; 2] {
+        [
+            QueryPipelineAsCompositeShapeWithPredicate {
+                pipeline: self,
+                qbvh: &self.qbvh,
+                colliders: &self.colliders,
+                query_filter: query_filter.clone(),
+                predicate,
+            },
+            QueryPipelineAsCompositeShapeWithPredicate {
+                pipeline: self,
+                qbvh: &self.static_qbvh,
+                colliders: &self.static_colliders,
+                query_filter,
+                predicate,
+            },
+        ]
+    }
+
+    /// Returns the trees of the dynamic and static colliders.
+    pub(crate) fn qbvhs(&self) -> [&Qbvh<u32>; 2] {
+        [&self.qbvh, &self.static_qbvh]
+    }
+
+    /// Returns the isometry, collider and collision layers of the given entity, if it is in the pipeline.
+    pub(crate) fn collider(
+        &self,
+        entity: Entity,
+    ) -> Option<&(Isometry<Scalar>, Collider, CollisionLayers)> {
+        self.colliders
+            .get(&entity)
+            .or_else(|| self.static_colliders.get(&entity))
     }
 
     /// Updates the associated acceleration structures with a new set of entities.
+    ///
+    /// Colliders that are stored as static colliders using [`update_static_colliders`](Self::update_static_colliders)
+    /// are skipped, as they are kept in a separate tree.
     pub fn update<'a>(
         &mut self,
         colliders: impl Iterator<
@@ -88,6 +148,7 @@ impl SpatialQueryPipeline {
         added_colliders: impl Iterator<Item = Entity>,
     ) {
         let colliders = colliders
+            .filter(|(entity, ..)| !self.static_colliders.contains_key(entity))
             .map(|(entity, position, rotation, collider, layers)| {
                 (
                     entity,
@@ -103,13 +164,44 @@ impl SpatialQueryPipeline {
         self.update_internal(colliders, added_colliders)
     }
 
-    fn update_internal(
+    /// Updates the associated acceleration structures with a new set of entities, using the [`GlobalTransform`]
+    /// of each collider instead of its [`Position`] and [`Rotation`].
+    ///
+    /// This is used when the [`SpatialQueryPoses`] resource is set to [`SpatialQueryPoses::Rendered`].
+    /// Like in [`update`](Self::update), static colliders are skipped.
+    pub fn update_from_transforms<'a>(
         &mut self,
-        colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-        added: impl Iterator<Item = Entity>,
+        colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a GlobalTransform,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        added_colliders: impl Iterator<Item = Entity>,
     ) {
-        self.colliders = colliders;
+        let colliders = colliders
+            .filter(|(entity, ..)| !self.static_colliders.contains_key(entity))
+            .map(|(entity, transform, collider, layers)| {
+                (
+                    entity,
+                    (
+                        utils::make_isometry(
+                            Position::from(transform).0,
+                            Rotation::from(transform),
+                        ),
+                        collider.clone(),
+                        layers.map_or(CollisionLayers::default(), |layers| *layers),
+                    ),
+                )
+            })
+            .collect();
 
+        self.update_internal(colliders, added_colliders)
+    }
+
+    fn update_internal(&mut self, colliders: ColliderMap, added: impl Iterator<Item = Entity>) {
         // Insert or update generations of added entities
         for added in added {
             let index = added.index();
@@ -120,34 +212,260 @@ impl SpatialQueryPipeline {
             }
         }
 
-        struct DataGenerator<'a>(
-            &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+        // Only the leaves of colliders that have been removed, added or moved are updated.
+        let removed: Vec<Entity> = self
+            .colliders
+            .keys()
+            .filter(|entity| !colliders.contains_key(*entity))
+            .copied()
+            .collect();
+        let moved: Vec<Entity> = colliders
+            .iter()
+            .filter(|(entity, data)| {
+                self.colliders
+                    .get(*entity)
+                    .is_none_or(|old_data| is_collider_moved(old_data, data))
+            })
+            .map(|(entity, _)| *entity)
+            .collect();
+
+        self.colliders = colliders;
+        self.refit_qbvh(removed, moved);
+    }
+
+    /// Updates the associated acceleration structures with colliders that have been changed, added or removed
+    /// since the last update.
+    ///
+    /// Unlike [`update`](Self::update), this doesn't require iterating over and cloning every collider,
+    /// so the cost only scales with the number of changed colliders. This is how the pipeline is updated
+    /// once per physics frame, and it is also useful for keeping spatial queries up to date when
+    /// the physics simulation isn't running, for example when physics is paused or stepped manually.
+    ///
+    /// See also: [`SpatialQuery::update_pipeline_incremental`]
+    pub fn update_incremental<'a>(
+        &mut self,
+        changed_colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a Position,
+                &'a Rotation,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        removed_colliders: impl Iterator<Item = Entity>,
+    ) {
+        self.update_incremental_internal(
+            changed_colliders.map(|(entity, position, rotation, collider, layers)| {
+                (
+                    entity,
+                    (
+                        utils::make_isometry(position.0, *rotation),
+                        collider.clone(),
+                        layers.map_or(CollisionLayers::default(), |layers| *layers),
+                    ),
+                )
+            }),
+            removed_colliders,
+        );
+    }
+
+    /// Updates the associated acceleration structures with colliders that have been changed, added or removed
+    /// since the last update, using the [`GlobalTransform`] of each collider instead of its [`Position`] and [`Rotation`].
+    ///
+    /// This is used when the [`SpatialQueryPoses`] resource is set to [`SpatialQueryPoses::Rendered`].
+    /// See [`update_incremental`](Self::update_incremental) for more information.
+    pub fn update_incremental_from_transforms<'a>(
+        &mut self,
+        changed_colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a GlobalTransform,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        removed_colliders: impl Iterator<Item = Entity>,
+    ) {
+        self.update_incremental_internal(
+            changed_colliders.map(|(entity, transform, collider, layers)| {
+                (
+                    entity,
+                    (
+                        utils::make_isometry(
+                            Position::from(transform).0,
+                            Rotation::from(transform),
+                        ),
+                        collider.clone(),
+                        layers.map_or(CollisionLayers::default(), |layers| *layers),
+                    ),
+                )
+            }),
+            removed_colliders,
         );
+    }
+
+    fn update_incremental_internal(
+        &mut self,
+        changed_colliders: impl Iterator<Item = (Entity, (Isometry<Scalar>, Collider, CollisionLayers))>,
+        removed_colliders: impl Iterator<Item = Entity>,
+    ) {
+        let mut removed = vec![];
+        let mut removed_static = vec![];
 
-        impl<'a> parry::partitioning::QbvhDataGenerator<u32> for DataGenerator<'a> {
-            fn size_hint(&self) -> usize {
-                self.0.len()
+        // Added colliders are inserted as new leaves, so they are marked dirty just like colliders that moved.
+        let mut moved = vec![];
+        let mut moved_static = vec![];
+
+        for entity in removed_colliders {
+            if self.colliders.remove(&entity).is_some() {
+                removed.push(entity);
+            }
+            if self.static_colliders.remove(&entity).is_some() {
+                removed_static.push(entity);
             }
+        }
+
+        for (entity, data) in changed_colliders {
+            self.entity_generations
+                .insert(entity.index(), entity.generation());
 
-            #[inline(always)]
-            fn for_each(&mut self, mut f: impl FnMut(u32, parry::bounding_volume::Aabb)) {
-                for (entity, co) in self.0.iter() {
-                    // Compute and return AABB
-                    let (iso, shape, _) = co;
-                    let aabb = shape.shape_scaled().compute_aabb(iso);
-                    f(entity.index(), aabb)
+            // Static colliders stay in the static tree.
+            if let Some(static_data) = self.static_colliders.get_mut(&entity) {
+                if is_collider_moved(static_data, &data) {
+                    moved_static.push(entity);
                 }
+                *static_data = data;
+            } else if let Some(old_data) = self.colliders.insert(entity, data) {
+                if is_collider_moved(&old_data, &self.colliders[&entity]) {
+                    moved.push(entity);
+                }
+            } else {
+                moved.push(entity);
             }
         }
 
-        self.qbvh
-            .clear_and_rebuild(DataGenerator(&self.colliders), 0.01);
+        self.refit_qbvh(removed, moved);
+        self.refit_static_qbvh(removed_static, moved_static);
+    }
+
+    /// Updates the tree of static colliders with static colliders that have been added or changed,
+    /// and removes the given colliders from it.
+    ///
+    /// Static colliders are skipped by [`update`](Self::update), so the tree is only updated
+    /// when static colliders are actually added, changed or removed. Colliders that are no longer
+    /// static should be included in `removed_colliders`, and then added back as regular colliders
+    /// using [`update_incremental`](Self::update_incremental).
+    ///
+    /// This is done automatically for colliders attached to [static](RigidBody::Static) bodies
+    /// in [`PhysicsStepSet::SpatialQuery`].
+    pub fn update_static_colliders<'a>(
+        &mut self,
+        changed_colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a Position,
+                &'a Rotation,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        removed_colliders: impl Iterator<Item = Entity>,
+    ) {
+        let mut removed = vec![];
+        let mut removed_static = vec![];
+        let mut moved = vec![];
+
+        for entity in removed_colliders {
+            if self.static_colliders.remove(&entity).is_some() {
+                removed_static.push(entity);
+            }
+        }
+
+        for (entity, position, rotation, collider, layers) in changed_colliders {
+            self.entity_generations
+                .insert(entity.index(), entity.generation());
+
+            // Colliders that became static are removed from the tree of non-static colliders.
+            if self.colliders.remove(&entity).is_some() {
+                removed.push(entity);
+            }
+
+            let data = (
+                utils::make_isometry(position.0, *rotation),
+                collider.clone(),
+                layers.map_or(CollisionLayers::default(), |layers| *layers),
+            );
+            if let Some(old_data) = self.static_colliders.insert(entity, data) {
+                if is_collider_moved(&old_data, &self.static_colliders[&entity]) {
+                    moved.push(entity);
+                }
+            } else {
+                moved.push(entity);
+            }
+        }
+
+        self.refit_qbvh(removed, vec![]);
+        self.refit_static_qbvh(removed_static, moved);
+    }
+
+    fn refit_qbvh(&mut self, removed: Vec<Entity>, moved: Vec<Entity>) {
+        refit_qbvh(
+            &mut self.qbvh,
+            &mut self.qbvh_workspace,
+            &self.colliders,
+            &self.entity_generations,
+            removed,
+            moved,
+        );
+    }
+
+    fn refit_static_qbvh(&mut self, removed: Vec<Entity>, moved: Vec<Entity>) {
+        refit_qbvh(
+            &mut self.static_qbvh,
+            &mut self.qbvh_workspace,
+            &self.static_colliders,
+            &self.entity_generations,
+            removed,
+            moved,
+        );
+    }
+
+    /// Returns the memory allocated by the pipeline in bytes, not including the shapes of the colliders.
+    pub(crate) fn memory_usage(&self) -> usize {
+        let qbvh_memory = |qbvh: &Qbvh<u32>| {
+            std::mem::size_of_val(qbvh.raw_nodes()) + std::mem::size_of_val(qbvh.raw_proxies())
+        };
+        let map_memory = |colliders: &ColliderMap| {
+            colliders.capacity()
+                * std::mem::size_of::<(Entity, (Isometry<Scalar>, Collider, CollisionLayers))>()
+        };
+
+        qbvh_memory(&self.qbvh)
+            + qbvh_memory(&self.static_qbvh)
+            + map_memory(&self.colliders)
+            + map_memory(&self.static_colliders)
+            + self.entity_generations.capacity() * std::mem::size_of::<(u32, u32)>()
     }
 
     pub(crate) fn entity_from_index(&self, index: u32) -> Entity {
         entity_from_index_and_gen(index, *self.entity_generations.get(&index).unwrap())
     }
 
+    /// Takes an immutable [`SpatialQuerySnapshot`] of the current state of the pipeline.
+    ///
+    /// The snapshot can be sent to other threads and queried there, even while the pipeline
+    /// itself keeps being updated.
+    ///
+    /// Taking a snapshot copies the acceleration structures and the collider map of the pipeline,
+    /// so the cost grows linearly with the number of colliders. The collider shapes themselves are shared.
+    /// Snapshots should be taken when they are needed rather than every frame.
+    ///
+    /// See also: [`SpatialQuery::snapshot`]
+    pub fn snapshot(&self) -> SpatialQuerySnapshot {
+        SpatialQuerySnapshot(Arc::new(self.clone()))
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
     /// If there are no hits, `None` is returned.
     ///
@@ -169,17 +487,20 @@ impl SpatialQueryPipeline {
         solid: bool,
         query_filter: SpatialQueryFilter,
     ) -> Option<RayHitData> {
-        let pipeline_shape = self.as_composite_shape(query_filter);
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
-        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-            &pipeline_shape,
-            &ray,
-            max_time_of_impact,
-            solid,
-        );
 
-        self.qbvh
-            .traverse_best_first(&mut visitor)
+        self.as_composite_shapes(query_filter)
+            .iter()
+            .filter_map(|pipeline_shape| {
+                let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+                    pipeline_shape,
+                    &ray,
+                    max_time_of_impact,
+                    solid,
+                );
+                pipeline_shape.qbvh.traverse_best_first(&mut visitor)
+            })
+            .min_by(|(_, (_, a)), (_, (_, b))| a.toi.total_cmp(&b.toi))
             .map(|(_, (entity_index, hit))| RayHitData {
                 entity: self.entity_from_index(entity_index),
                 time_of_impact: hit.toi,
@@ -211,17 +532,20 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         predicate: &dyn Fn(Entity) -> bool,
     ) -> Option<RayHitData> {
-        let pipeline_shape = self.as_composite_shape_with_predicate(query_filter, predicate);
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
-        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-            &pipeline_shape,
-            &ray,
-            max_time_of_impact,
-            solid,
-        );
 
-        self.qbvh
-            .traverse_best_first(&mut visitor)
+        self.as_composite_shapes_with_predicate(query_filter, predicate)
+            .iter()
+            .filter_map(|pipeline_shape| {
+                let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+                    pipeline_shape,
+                    &ray,
+                    max_time_of_impact,
+                    solid,
+                );
+                pipeline_shape.qbvh.traverse_best_first(&mut visitor)
+            })
+            .min_by(|(_, (_, a)), (_, (_, b))| a.toi.total_cmp(&b.toi))
             .map(|(_, (entity_index, hit))| RayHitData {
                 entity: self.entity_from_index(entity_index),
                 time_of_impact: hit.toi,
@@ -229,6 +553,65 @@ impl SpatialQueryPipeline {
             })
     }
 
+    /// Casts a batch of [rays](spatial_query#raycasting) and computes the closest [hit](RayHitData)
+    /// for each of them. The results are in the same order as the given rays, and `None` is returned
+    /// for rays that don't hit anything.
+    ///
+    /// With the `parallel` feature enabled, the rays are cast in parallel on the `ComputeTaskPool`.
+    /// This is useful when casting hundreds of rays per frame, for example for AI vision cones or audio occlusion.
+    ///
+    /// ## Arguments
+    ///
+    /// - `rays`: The [rays](RayInput) that are cast.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::cast_rays_batch`]
+    pub fn cast_rays_batch(
+        &self,
+        rays: &[RayInput],
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<Option<RayHitData>> {
+        let pipeline_shapes = self.as_composite_shapes(query_filter);
+
+        let cast_ray = |ray: &RayInput| {
+            let parry_ray =
+                parry::query::Ray::new(ray.origin.into(), ray.direction.adjust_precision().into());
+
+            pipeline_shapes
+                .iter()
+                .filter_map(|pipeline_shape| {
+                    let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+                        pipeline_shape,
+                        &parry_ray,
+                        ray.max_time_of_impact,
+                        ray.solid,
+                    );
+                    pipeline_shape.qbvh.traverse_best_first(&mut visitor)
+                })
+                .min_by(|(_, (_, a)), (_, (_, b))| a.toi.total_cmp(&b.toi))
+                .map(|(_, (entity_index, hit))| RayHitData {
+                    entity: self.entity_from_index(entity_index),
+                    time_of_impact: hit.toi,
+                    normal: hit.normal.into(),
+                })
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            let pool = ComputeTaskPool::get();
+            rays.par_splat_map(pool, None, |chunk| {
+                chunk.iter().map(cast_ray).collect::<Vec<_>>()
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            rays.iter().map(cast_ray).collect()
+        }
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData) until `max_hits` is reached.
     ///
     /// Note that the order of the results is not guaranteed, and if there are more hits than `max_hits`,
@@ -294,13 +677,11 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         mut callback: impl FnMut(RayHitData) -> bool,
     ) {
-        let colliders = &self.colliders;
-
         let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
 
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
-            if let Some((iso, shape, layers)) = colliders.get(&entity) {
+            if let Some((iso, shape, layers)) = self.collider(entity) {
                 if query_filter.test(entity, *layers) {
                     if let Some(hit) = shape.shape_scaled().cast_ray_and_get_normal(
                         iso,
@@ -321,9 +702,210 @@ impl SpatialQueryPipeline {
             true
         };
 
-        let mut visitor =
-            RayIntersectionsVisitor::new(&ray, max_time_of_impact, &mut leaf_callback);
-        self.qbvh.traverse_depth_first(&mut visitor);
+        for qbvh in self.qbvhs() {
+            let mut visitor =
+                RayIntersectionsVisitor::new(&ray, max_time_of_impact, &mut leaf_callback);
+            // Stop if the callback ended the traversal early.
+            if !qbvh.traverse_depth_first(&mut visitor) {
+                break;
+            }
+        }
+    }
+
+    /// Casts a [ray](spatial_query#raycasting) and returns an iterator over all [hits](RayHitData)
+    /// in the order of the time of impact.
+    ///
+    /// The hits are computed lazily, so each call to `next` only finds the next closest hit.
+    /// This makes it cheap to stop early, for example when a piercing projectile runs out of energy.
+    ///
+    /// ## Arguments
+    ///
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::ray_hits_sorted`]
+    pub fn ray_hits_sorted(
+        &self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> RayHitsSortedIter<'_> {
+        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+
+        RayHitsSortedIter {
+            pipeline: self,
+            traversal: SweptAabbBestFirstTraversal::new(
+                self.qbvhs(),
+                &ray,
+                parry::math::Vector::zeros(),
+                max_time_of_impact,
+            ),
+            ray,
+            max_time_of_impact,
+            solid,
+            query_filter,
+        }
+    }
+
+    /// Casts a [ray](spatial_query#raycasting) and returns an iterator over the closest [hits](RayHitData)
+    /// in the order of the time of impact, stopping once `max_hits` hits have been found.
+    ///
+    /// ## Arguments
+    ///
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `max_hits`: The maximum number of hits. Hits further away will not be computed.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::ray_hits_sorted_max`]
+    pub fn ray_hits_sorted_max(
+        &self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        max_hits: u32,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> std::iter::Take<RayHitsSortedIter<'_>> {
+        self.ray_hits_sorted(origin, direction, max_time_of_impact, solid, query_filter)
+            .take(max_hits as usize)
+    }
+
+    /// Casts a [ray](spatial_query#raycasting) against the collider of a single entity and computes the [hit](RayHitData).
+    /// If the ray doesn't hit the collider or the entity doesn't have a collider in the pipeline, `None` is returned.
+    ///
+    /// This only tests the given entity and skips the traversal of the acceleration structure entirely.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the ray is cast against.
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    ///
+    /// See also: [`SpatialQuery::cast_ray_against`]
+    pub fn cast_ray_against(
+        &self,
+        entity: Entity,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<RayHitData> {
+        let (isometry, collider, _) = self.collider(entity)?;
+        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+
+        collider
+            .shape_scaled()
+            .cast_ray_and_get_normal(isometry, &ray, max_time_of_impact, solid)
+            .map(|hit| RayHitData {
+                entity,
+                time_of_impact: hit.toi,
+                normal: hit.normal.into(),
+            })
+    }
+
+    /// Computes the [`TriMeshRayHit`] for a [ray hit](RayHitData) on a [trimesh](Collider::trimesh) collider,
+    /// containing the index of the triangle that was hit, the barycentric coordinates of the hit point
+    /// and a smooth normal interpolated from the vertex normals of the triangle.
+    ///
+    /// `origin` and `direction` should be the same as the ones used for the raycast that produced the `hit`.
+    /// If the hit entity doesn't have a trimesh collider, `None` is returned.
+    ///
+    /// Note that the vertex normals are computed on demand by averaging the normals of all triangles
+    /// sharing a vertex, so the cost of this method scales with the number of triangles in the trimesh.
+    ///
+    /// See also: [`SpatialQuery::trimesh_ray_hit`]
+    #[cfg(feature = "3d")]
+    pub fn trimesh_ray_hit(
+        &self,
+        hit: &RayHitData,
+        origin: Vector,
+        direction: Dir,
+    ) -> Option<TriMeshRayHit> {
+        let (isometry, collider, _) = self.collider(hit.entity)?;
+        let trimesh = collider.shape_scaled().as_trimesh()?;
+
+        let ray = parry::query::Ray::new(origin.into(), direction.adjust_precision().into());
+        let local_ray = ray.inverse_transform_by(isometry);
+        let intersection = trimesh.cast_local_ray_and_get_normal(&local_ray, Scalar::MAX, true)?;
+
+        // Back face hits are reported with an offset of the number of triangles.
+        let parry::shape::FeatureId::Face(face) = intersection.feature else {
+            return None;
+        };
+        let triangle_index = face % trimesh.num_triangles() as u32;
+
+        let [ia, ib, ic] = trimesh.indices()[triangle_index as usize];
+        let vertices = trimesh.vertices();
+        let (a, b, c) = (
+            vertices[ia as usize],
+            vertices[ib as usize],
+            vertices[ic as usize],
+        );
+
+        // Compute the barycentric coordinates of the local hit point.
+        let point = local_ray.point_at(intersection.toi);
+        let (v0, v1, v2) = (b - a, c - a, point - a);
+        let (d00, d01, d11) = (v0.dot(&v0), v0.dot(&v1), v1.dot(&v1));
+        let (d20, d21) = (v2.dot(&v0), v2.dot(&v1));
+        let denominator = d00 * d11 - d01 * d01;
+        let (v, w) = if denominator.abs() > Scalar::EPSILON {
+            (
+                (d11 * d20 - d01 * d21) / denominator,
+                (d00 * d21 - d01 * d20) / denominator,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        let u = 1.0 - v - w;
+
+        // Compute area-weighted vertex normals for the vertices of the hit triangle.
+        let mut vertex_normals = [parry::math::Vector::zeros(); 3];
+        for [i1, i2, i3] in trimesh.indices() {
+            let (p1, p2, p3) = (
+                vertices[*i1 as usize],
+                vertices[*i2 as usize],
+                vertices[*i3 as usize],
+            );
+            let face_normal = (p2 - p1).cross(&(p3 - p1));
+
+            for (vertex_normal, index) in vertex_normals.iter_mut().zip([ia, ib, ic]) {
+                if index == *i1 || index == *i2 || index == *i3 {
+                    *vertex_normal += face_normal;
+                }
+            }
+        }
+
+        let mut smooth_normal =
+            vertex_normals[0] * u + vertex_normals[1] * v + vertex_normals[2] * w;
+
+        // Fall back to the face normal for degenerate cases.
+        if smooth_normal.norm_squared() <= Scalar::EPSILON {
+            smooth_normal = intersection.normal;
+        }
+
+        // Make the smooth normal face the same side as the hit face.
+        if smooth_normal.dot(&intersection.normal) < 0.0 {
+            smooth_normal = -smooth_normal;
+        }
+
+        Some(TriMeshRayHit {
+            triangle_index,
+            barycentric_coordinates: Vector::new(u, v, w),
+            smooth_normal: (isometry.rotation * smooth_normal.normalize()).into(),
+        })
     }
 
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeHits)
@@ -367,19 +949,22 @@ impl SpatialQueryPipeline {
 
         let shape_isometry = utils::make_isometry(origin, rotation);
         let shape_direction = direction.adjust_precision().into();
-        let pipeline_shape = self.as_composite_shape(query_filter);
-        let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-            &*self.dispatcher,
-            &shape_isometry,
-            &shape_direction,
-            &pipeline_shape,
-            &**shape.shape_scaled(),
-            max_time_of_impact,
-            !ignore_origin_penetration,
-        );
 
-        self.qbvh
-            .traverse_best_first(&mut visitor)
+        self.as_composite_shapes(query_filter)
+            .iter()
+            .filter_map(|pipeline_shape| {
+                let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
+                    &*self.dispatcher,
+                    &shape_isometry,
+                    &shape_direction,
+                    pipeline_shape,
+                    &**shape.shape_scaled(),
+                    max_time_of_impact,
+                    !ignore_origin_penetration,
+                );
+                pipeline_shape.qbvh.traverse_best_first(&mut visitor)
+            })
+            .min_by(|(_, (_, a)), (_, (_, b))| a.toi.total_cmp(&b.toi))
             .map(|(_, (entity_index, hit))| ShapeHitData {
                 entity: self.entity_from_index(entity_index),
                 time_of_impact: hit.toi,
@@ -390,6 +975,168 @@ impl SpatialQueryPipeline {
             })
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeCastHit)
+    /// with a collider using the given [`ShapeCastConfig`]. If there are no hits, `None` is returned.
+    ///
+    /// The configuration determines the skin width of the shape and how colliders that the shape
+    /// is already overlapping at the cast origin are handled.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::cast_shape_with_config`]
+    pub fn cast_shape_with_config(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeCastHit> {
+        let ignore_origin_penetration = config.initial_overlap == InitialOverlapPolicy::Ignore;
+        let skin_width = config.skin_width.max(0.0);
+
+        let data = self.cast_shape(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            config.max_time_of_impact + skin_width,
+            ignore_origin_penetration,
+            query_filter,
+        )?;
+
+        let mut hit = ShapeCastHit {
+            data: ShapeHitData {
+                time_of_impact: (data.time_of_impact - skin_width).max(0.0),
+                ..data
+            },
+            penetration_depth: 0.0,
+            depenetration: Vector::ZERO,
+        };
+
+        if ignore_origin_penetration || hit.data.time_of_impact > 0.0 {
+            return Some(hit);
+        }
+
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(origin, rotation);
+        let (collider_isometry, collider, _) = self.collider(data.entity)?;
+
+        // Compute the contact at the cast origin to get the penetration depth.
+        let Ok(Some(contact)) = parry::query::contact(
+            &shape_isometry,
+            &**shape.shape_scaled(),
+            collider_isometry,
+            &**collider.shape_scaled(),
+            skin_width,
+        ) else {
+            return Some(hit);
+        };
+
+        hit.penetration_depth = (skin_width - contact.dist).max(0.0);
+
+        if config.initial_overlap == InitialOverlapPolicy::Depenetrate {
+            hit.data.point1 = collider_isometry
+                .inverse_transform_point(&contact.point2)
+                .into();
+            hit.data.point2 = shape_isometry
+                .inverse_transform_point(&contact.point1)
+                .into();
+            hit.data.normal1 =
+                (*collider_isometry.inverse_transform_unit_vector(&contact.normal2)).into();
+            hit.data.normal2 =
+                (*shape_isometry.inverse_transform_unit_vector(&contact.normal1)).into();
+
+            // Move the shape along the outward normal of the collider.
+            let normal: Vector = (*contact.normal2).into();
+            hit.depenetration = normal * hit.penetration_depth;
+        }
+
+        Some(hit)
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation against the collider of a single entity
+    /// and computes the [hit](ShapeHitData). If the shape doesn't hit the collider, the entity doesn't have
+    /// a collider in the pipeline, or the shapes are not supported, `None` is returned.
+    ///
+    /// This only tests the given entity and skips the traversal of the acceleration structure entirely.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the shape is cast against.
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating the collider at the
+    /// shape origin, the hit will be ignored. Otherwise, the initial hit will be returned.
+    ///
+    /// See also: [`SpatialQuery::cast_shape_against`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_against(
+        &self,
+        entity: Entity,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+    ) -> Option<ShapeHitData> {
+        let (isometry, collider, _) = self.collider(entity)?;
+
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(origin, rotation);
+
+        parry::query::time_of_impact(
+            isometry,
+            &Vector::ZERO.into(),
+            &**collider.shape_scaled(),
+            &shape_isometry,
+            &direction.adjust_precision().into(),
+            &**shape.shape_scaled(),
+            max_time_of_impact,
+            !ignore_origin_penetration,
+        )
+        .ok()
+        .flatten()
+        .map(|hit| ShapeHitData {
+            entity,
+            time_of_impact: hit.toi,
+            point1: hit.witness1.into(),
+            point2: hit.witness2.into(),
+            normal1: hit.normal1.into(),
+            normal2: hit.normal2.into(),
+        })
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes computes all [hits](ShapeHitData)
     /// in the order of the time of impact until `max_hits` is reached.
     ///
@@ -464,9 +1211,53 @@ impl SpatialQueryPipeline {
         direction: Dir,
         max_time_of_impact: Scalar,
         ignore_origin_penetration: bool,
-        mut query_filter: SpatialQueryFilter,
+        query_filter: SpatialQueryFilter,
         mut callback: impl FnMut(ShapeHitData) -> bool,
     ) {
+        for hit in self.shape_hits_sorted(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        ) {
+            if !callback(hit) {
+                break;
+            }
+        }
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and returns an iterator over all
+    /// [hits](ShapeHitData) along the sweep in the order of the time of impact.
+    ///
+    /// The hits are computed lazily, so each call to `next` only finds the next closest hit.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating a collider at the
+    /// shape origin, the hit will be ignored and only the next hit will be computed. Otherwise, the initial
+    /// hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::shape_hits_sorted`]
+    #[allow(clippy::too_many_arguments)]
+    pub fn shape_hits_sorted<'a>(
+        &'a self,
+        shape: &'a Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> ShapeHitsSortedIter<'a> {
         let rotation: Rotation;
         #[cfg(feature = "2d")]
         {
@@ -478,72 +1269,168 @@ impl SpatialQueryPipeline {
         }
 
         let shape_isometry = utils::make_isometry(origin, rotation);
-        let shape_direction = direction.adjust_precision().into();
+        let shape_direction: parry::math::Vector<Scalar> = direction.adjust_precision().into();
 
-        loop {
-            let pipeline_shape = self.as_composite_shape(query_filter.clone());
-            let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-                &*self.dispatcher,
-                &shape_isometry,
-                &shape_direction,
-                &pipeline_shape,
-                &**shape.shape_scaled(),
+        // Only colliders whose AABBs are hit by the swept AABB of the shape are tested.
+        let shape_aabb = shape.shape_scaled().compute_aabb(&shape_isometry);
+
+        ShapeHitsSortedIter {
+            pipeline: self,
+            traversal: SweptAabbBestFirstTraversal::new(
+                self.qbvhs(),
+                &parry::query::Ray::new(shape_aabb.center(), shape_direction),
+                shape_aabb.half_extents(),
                 max_time_of_impact,
-                !ignore_origin_penetration,
-            );
+            ),
+            shape,
+            shape_isometry,
+            shape_direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        }
+    }
 
-            if let Some(hit) =
-                self.qbvh
-                    .traverse_best_first(&mut visitor)
-                    .map(|(_, (entity_index, hit))| ShapeHitData {
-                        entity: self.entity_from_index(entity_index),
-                        time_of_impact: hit.toi,
-                        point1: hit.witness1.into(),
-                        point2: hit.witness2.into(),
-                        normal1: hit.normal1.into(),
-                        normal2: hit.normal2.into(),
-                    })
-            {
-                query_filter.excluded_entities.insert(hit.entity);
-
-                if !callback(hit) {
-                    break;
-                }
-            } else {
-                break;
-            }
+    /// Finds the [projection](spatial_query#point-projection) of a given point on the closest [collider](Collider).
+    /// If one isn't found, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `point`: The point that should be projected.
+    /// - `solid`: If true and the point is inside of a collider, the projection will be at the point.
+    /// Otherwise, the collider will be treated as hollow, and the projection will be at the collider's boundary.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::project_point`]
+    pub fn project_point(
+        &self,
+        point: Vector,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<PointProjection> {
+        let point = point.into();
+
+        self.as_composite_shapes(query_filter)
+            .iter()
+            .filter_map(|pipeline_shape| {
+                let mut visitor =
+                    PointCompositeShapeProjBestFirstVisitor::new(pipeline_shape, &point, solid);
+                pipeline_shape.qbvh.traverse_best_first(&mut visitor)
+            })
+            .min_by(|(_, (a, _)), (_, (b, _))| {
+                (a.point - point)
+                    .norm_squared()
+                    .total_cmp(&(b.point - point).norm_squared())
+            })
+            .map(|(_, (projection, entity_index))| PointProjection {
+                entity: self.entity_from_index(entity_index),
+                point: projection.point.into(),
+                is_inside: projection.is_inside,
+            })
+    }
+
+    /// Computes the [closest points](contact_query::ClosestPoints) between the colliders of two entities.
+    ///
+    /// Returns `None` if either entity doesn't have a collider in the pipeline or if either
+    /// of the collider shapes is not supported.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity1`: The entity of the first collider.
+    /// - `entity2`: The entity of the second collider.
+    /// - `max_distance`: The maximum distance between the closest points. If the colliders are further apart,
+    /// [`ClosestPoints::OutsideMargin`](contact_query::ClosestPoints::OutsideMargin) is returned.
+    ///
+    /// See also: [`SpatialQuery::closest_points`]
+    pub fn closest_points(
+        &self,
+        entity1: Entity,
+        entity2: Entity,
+        max_distance: Scalar,
+    ) -> Option<contact_query::ClosestPoints> {
+        let (isometry1, collider1, _) = self.collider(entity1)?;
+        let (isometry2, collider2, _) = self.collider(entity2)?;
+
+        parry::query::closest_points(
+            isometry1,
+            &**collider1.shape_scaled(),
+            isometry2,
+            &**collider2.shape_scaled(),
+            max_distance,
+        )
+        .ok()
+        .map(contact_query::ClosestPoints::from)
+    }
+
+    /// Computes the [closest points](contact_query::ClosestPoints) between the collider of an entity
+    /// and a `shape` with a given position and rotation.
+    ///
+    /// The first point belongs to the entity's collider and the second point to the given shape.
+    ///
+    /// Returns `None` if the entity doesn't have a collider in the pipeline or if either
+    /// of the collider shapes is not supported.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity of the collider.
+    /// - `shape`: The shape represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `max_distance`: The maximum distance between the closest points. If the shapes are further apart,
+    /// [`ClosestPoints::OutsideMargin`](contact_query::ClosestPoints::OutsideMargin) is returned.
+    ///
+    /// See also: [`SpatialQuery::closest_points_to_shape`]
+    pub fn closest_points_to_shape(
+        &self,
+        entity: Entity,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        max_distance: Scalar,
+    ) -> Option<contact_query::ClosestPoints> {
+        let (collider_isometry, collider, _) = self.collider(entity)?;
+
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
         }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(shape_position, rotation);
+
+        parry::query::closest_points(
+            collider_isometry,
+            &**collider.shape_scaled(),
+            &shape_isometry,
+            &**shape.shape_scaled(),
+            max_distance,
+        )
+        .ok()
+        .map(contact_query::ClosestPoints::from)
     }
 
-    /// Finds the [projection](spatial_query#point-projection) of a given point on the closest [collider](Collider).
-    /// If one isn't found, `None` is returned.
-    ///
-    /// ## Arguments
+    /// Computes the minimum distance separating the colliders of two entities.
+    /// The distance is `0.0` if the colliders are touching or penetrating.
     ///
-    /// - `point`: The point that should be projected.
-    /// - `solid`: If true and the point is inside of a collider, the projection will be at the point.
-    /// Otherwise, the collider will be treated as hollow, and the projection will be at the collider's boundary.
-    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    /// Returns `None` if either entity doesn't have a collider in the pipeline or if either
+    /// of the collider shapes is not supported.
     ///
-    /// See also: [`SpatialQuery::project_point`]
-    pub fn project_point(
-        &self,
-        point: Vector,
-        solid: bool,
-        query_filter: SpatialQueryFilter,
-    ) -> Option<PointProjection> {
-        let point = point.into();
-        let pipeline_shape = self.as_composite_shape(query_filter);
-        let mut visitor =
-            PointCompositeShapeProjBestFirstVisitor::new(&pipeline_shape, &point, solid);
+    /// See also: [`SpatialQuery::distance`]
+    pub fn distance(&self, entity1: Entity, entity2: Entity) -> Option<Scalar> {
+        let (isometry1, collider1, _) = self.collider(entity1)?;
+        let (isometry2, collider2, _) = self.collider(entity2)?;
 
-        self.qbvh
-            .traverse_best_first(&mut visitor)
-            .map(|(_, (projection, entity_index))| PointProjection {
-                entity: self.entity_from_index(entity_index),
-                point: projection.point.into(),
-                is_inside: projection.is_inside,
-            })
+        parry::query::distance(
+            isometry1,
+            &**collider1.shape_scaled(),
+            isometry2,
+            &**collider2.shape_scaled(),
+        )
+        .ok()
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
@@ -589,7 +1476,7 @@ impl SpatialQueryPipeline {
 
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
-            if let Some((isometry, shape, layers)) = self.colliders.get(&entity) {
+            if let Some((isometry, shape, layers)) = self.collider(entity) {
                 if query_filter.test(entity, *layers)
                     && shape.shape_scaled().contains_point(isometry, &point)
                 {
@@ -599,8 +1486,12 @@ impl SpatialQueryPipeline {
             true
         };
 
-        let mut visitor = PointIntersectionsVisitor::new(&point, &mut leaf_callback);
-        self.qbvh.traverse_depth_first(&mut visitor);
+        for qbvh in self.qbvhs() {
+            let mut visitor = PointIntersectionsVisitor::new(&point, &mut leaf_callback);
+            if !qbvh.traverse_depth_first(&mut visitor) {
+                break;
+            }
+        }
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`ColliderAabb`]
@@ -631,14 +1522,51 @@ impl SpatialQueryPipeline {
             callback(entity)
         };
 
-        let mut visitor = BoundingVolumeIntersectionsVisitor::new(
-            &Aabb {
+        let aabb = Aabb {
+            mins: aabb.min.into(),
+            maxs: aabb.max.into(),
+        };
+
+        for qbvh in self.qbvhs() {
+            let mut visitor = BoundingVolumeIntersectionsVisitor::new(&aabb, &mut leaf_callback);
+            if !qbvh.traverse_depth_first(&mut visitor) {
+                break;
+            }
+        }
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that returns an iterator lazily yielding
+    /// all entities with a [`ColliderAabb`] that is intersecting the given `aabb`.
+    ///
+    /// The acceleration structure is only traversed as far as the iterator is advanced,
+    /// and no shapes or intermediate collections are allocated.
+    ///
+    /// ## Arguments
+    ///
+    /// - `aabb`: The region that intersections are tested against.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::aabb_intersections_iter`]
+    pub fn aabb_intersections_iter(
+        &self,
+        aabb: ColliderAabb,
+        query_filter: SpatialQueryFilter,
+    ) -> AabbIntersectionsIter<'_> {
+        AabbIntersectionsIter {
+            pipeline: self,
+            aabb: Aabb {
                 mins: aabb.min.into(),
                 maxs: aabb.max.into(),
             },
-            &mut leaf_callback,
-        );
-        self.qbvh.traverse_depth_first(&mut visitor);
+            query_filter,
+            qbvhs: self.qbvhs(),
+            qbvh_index: 0,
+            stack: if self.qbvh.raw_nodes().is_empty() {
+                vec![]
+            } else {
+                vec![(0, 0)]
+            },
+        }
     }
 
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
@@ -694,7 +1622,6 @@ impl SpatialQueryPipeline {
         query_filter: SpatialQueryFilter,
         mut callback: impl FnMut(Entity) -> bool,
     ) {
-        let colliders = &self.colliders;
         let rotation: Rotation;
         #[cfg(feature = "2d")]
         {
@@ -713,7 +1640,7 @@ impl SpatialQueryPipeline {
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
 
-            if let Some((collider_isometry, collider, layers)) = colliders.get(&entity) {
+            if let Some((collider_isometry, collider, layers)) = self.collider(entity) {
                 if query_filter.test(entity, *layers) {
                     let isometry = inverse_shape_isometry * collider_isometry;
 
@@ -731,13 +1658,88 @@ impl SpatialQueryPipeline {
         };
 
         let shape_aabb = shape.shape_scaled().compute_aabb(&shape_isometry);
-        let mut visitor = BoundingVolumeIntersectionsVisitor::new(&shape_aabb, &mut leaf_callback);
-        self.qbvh.traverse_depth_first(&mut visitor);
+
+        for qbvh in self.qbvhs() {
+            let mut visitor =
+                BoundingVolumeIntersectionsVisitor::new(&shape_aabb, &mut leaf_callback);
+            if !qbvh.traverse_depth_first(&mut visitor) {
+                break;
+            }
+        }
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// that is intersecting the given `shape` with a given position and rotation, and computes the
+    /// [contact manifolds](ContactManifold) for each intersection.
+    ///
+    /// Unlike [`shape_intersections`](Self::shape_intersections), this also returns the penetration depth
+    /// and contact normal of each intersection, which can be used for things like overlap recovery.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [`SpatialQuery::shape_intersections_with_manifolds`]
+    pub fn shape_intersections_with_manifolds(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<ShapeIntersection> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(shape_position, rotation);
+        let inverse_shape_isometry = shape_isometry.inverse();
+
+        let mut intersections = vec![];
+
+        let mut leaf_callback = &mut |entity_index: &u32| {
+            let entity = self.entity_from_index(*entity_index);
+
+            if let Some((collider_isometry, collider, layers)) = self.collider(entity) {
+                if query_filter.test(entity, *layers) {
+                    let isometry = inverse_shape_isometry * collider_isometry;
+                    let manifolds = contact_query::contact_manifolds_with_relative_isometry(
+                        shape, collider, &isometry, 0.0,
+                    );
+
+                    if let Some(intersection) =
+                        ShapeIntersection::from_manifolds(entity, manifolds, &rotation)
+                    {
+                        intersections.push(intersection);
+                    }
+                }
+            }
+            true
+        };
+
+        let shape_aabb = shape.shape_scaled().compute_aabb(&shape_isometry);
+
+        for qbvh in self.qbvhs() {
+            let mut visitor =
+                BoundingVolumeIntersectionsVisitor::new(&shape_aabb, &mut leaf_callback);
+            qbvh.traverse_depth_first(&mut visitor);
+        }
+
+        intersections
     }
 }
 
 pub(crate) struct QueryPipelineAsCompositeShape<'a> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    pub(crate) qbvh: &'a Qbvh<u32>,
+    colliders: &'a ColliderMap,
     pipeline: &'a SpatialQueryPipeline,
     query_filter: SpatialQueryFilter,
 }
@@ -773,12 +1775,13 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
     }
 
     fn typed_qbvh(&self) -> &parry::partitioning::GenericQbvh<Self::PartId, Self::QbvhStorage> {
-        &self.pipeline.qbvh
+        self.qbvh
     }
 }
 
 pub(crate) struct QueryPipelineAsCompositeShapeWithPredicate<'a, 'b> {
-    colliders: &'a HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
+    pub(crate) qbvh: &'a Qbvh<u32>,
+    colliders: &'a ColliderMap,
     pipeline: &'a SpatialQueryPipeline,
     query_filter: SpatialQueryFilter,
     predicate: &'b dyn Fn(Entity) -> bool,
@@ -815,10 +1818,440 @@ impl<'a, 'b> TypedSimdCompositeShape for QueryPipelineAsCompositeShapeWithPredic
     }
 
     fn typed_qbvh(&self) -> &parry::partitioning::GenericQbvh<Self::PartId, Self::QbvhStorage> {
-        &self.pipeline.qbvh
+        self.qbvh
+    }
+}
+
+/// A ray used for [batched raycasts](SpatialQuery::cast_rays_batch).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct RayInput {
+    /// Where the ray is cast from.
+    pub origin: Vector,
+    /// What direction the ray is cast in.
+    pub direction: Dir,
+    /// The maximum distance that the ray can travel.
+    pub max_time_of_impact: Scalar,
+    /// If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    pub solid: bool,
+}
+
+impl RayInput {
+    /// Creates a new [`RayInput`] with the given origin, direction, maximum time of impact,
+    /// and whether colliders are treated as solid.
+    pub fn new(origin: Vector, direction: Dir, max_time_of_impact: Scalar, solid: bool) -> Self {
+        Self {
+            origin,
+            direction,
+            max_time_of_impact,
+            solid,
+        }
+    }
+}
+
+/// An immutable snapshot of a [`SpatialQueryPipeline`] that can be queried from background threads.
+///
+/// The snapshot shares the collider shapes with the pipeline it was taken from, but owns a copy
+/// of the acceleration structures, so it is unaffected by later updates to the pipeline. This is useful
+/// for long-running tasks like pathfinding or procedural generation that need to perform spatial queries
+/// without blocking the simulation.
+///
+/// Taking a snapshot is `O(n)` in the number of colliders, as the trees and the collider map
+/// are copied, but cloning an existing snapshot is cheap, as the copied data is reference-counted.
+///
+/// A snapshot can be created with [`SpatialQueryPipeline::snapshot`] or [`SpatialQuery::snapshot`],
+/// and it supports all of the query methods of [`SpatialQueryPipeline`].
+///
+/// ## Example
+///
+/// ```
+/// use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn spawn_pathfinding_task(spatial_query: SpatialQuery) {
+///     let snapshot = spatial_query.snapshot();
+///
+///     AsyncComputeTaskPool::get()
+///         .spawn(async move {
+///             // Query the snapshot on a background thread
+///             let hit = snapshot.cast_ray(
+///                 Vec3::ZERO,
+///                 Direction3d::X,
+///                 100.0,
+///                 true,
+///                 SpatialQueryFilter::default(),
+///             );
+///             println!("{:?}", hit);
+///         })
+///         .detach();
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SpatialQuerySnapshot(Arc<SpatialQueryPipeline>);
+
+impl Deref for SpatialQuerySnapshot {
+    type Target = SpatialQueryPipeline;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
+/// An iterator over [ray hits](RayHitData) in the order of the time of impact.
+///
+/// The acceleration structure is traversed best-first, and each call to `next` only advances
+/// the traversal until the next closest hit has been found.
+///
+/// Created by [`SpatialQueryPipeline::ray_hits_sorted`] and [`SpatialQuery::ray_hits_sorted`].
+pub struct RayHitsSortedIter<'a> {
+    pipeline: &'a SpatialQueryPipeline,
+    traversal: SweptAabbBestFirstTraversal<'a, RayHitData>,
+    ray: parry::query::Ray,
+    max_time_of_impact: Scalar,
+    solid: bool,
+    query_filter: SpatialQueryFilter,
+}
+
+impl<'a> Iterator for RayHitsSortedIter<'a> {
+    type Item = RayHitData;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pipeline = self.pipeline;
+
+        self.traversal.next_hit(|entity_index| {
+            let entity = pipeline.entity_from_index(entity_index);
+            let (iso, collider, layers) = pipeline.collider(entity)?;
+
+            if !self.query_filter.test(entity, *layers) {
+                return None;
+            }
+
+            let hit = collider.shape_scaled().cast_ray_and_get_normal(
+                iso,
+                &self.ray,
+                self.max_time_of_impact,
+                self.solid,
+            )?;
+
+            Some((
+                hit.toi,
+                RayHitData {
+                    entity,
+                    time_of_impact: hit.toi,
+                    normal: hit.normal.into(),
+                },
+            ))
+        })
+    }
+}
+
+/// An iterator over [shapecast hits](ShapeHitData) in the order of the time of impact.
+///
+/// The acceleration structure is traversed best-first, and each call to `next` only advances
+/// the traversal until the next closest hit has been found.
+///
+/// Created by [`SpatialQueryPipeline::shape_hits_sorted`] and [`SpatialQuery::shape_hits_sorted`].
+pub struct ShapeHitsSortedIter<'a> {
+    pipeline: &'a SpatialQueryPipeline,
+    traversal: SweptAabbBestFirstTraversal<'a, ShapeHitData>,
+    shape: &'a Collider,
+    shape_isometry: Isometry<Scalar>,
+    shape_direction: parry::math::Vector<Scalar>,
+    max_time_of_impact: Scalar,
+    ignore_origin_penetration: bool,
+    query_filter: SpatialQueryFilter,
+}
+
+impl<'a> Iterator for ShapeHitsSortedIter<'a> {
+    type Item = ShapeHitData;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pipeline = self.pipeline;
+
+        self.traversal.next_hit(|entity_index| {
+            let entity = pipeline.entity_from_index(entity_index);
+            let (iso, collider, layers) = pipeline.collider(entity)?;
+
+            if !self.query_filter.test(entity, *layers) {
+                return None;
+            }
+
+            let hit = pipeline
+                .dispatcher
+                .time_of_impact(
+                    &iso.inv_mul(&self.shape_isometry),
+                    &iso.inverse_transform_vector(&self.shape_direction),
+                    &**collider.shape_scaled(),
+                    &**self.shape.shape_scaled(),
+                    self.max_time_of_impact,
+                    !self.ignore_origin_penetration,
+                )
+                .ok()??
+                .transform1_by(iso);
+
+            Some((
+                hit.toi,
+                ShapeHitData {
+                    entity,
+                    time_of_impact: hit.toi,
+                    point1: hit.witness1.into(),
+                    point2: hit.witness2.into(),
+                    normal1: hit.normal1.into(),
+                    normal2: hit.normal2.into(),
+                },
+            ))
+        })
+    }
+}
+
+/// A best-first traversal of the trees of a [`SpatialQueryPipeline`] that visits the colliders
+/// whose AABBs are hit by an AABB swept along a direction in the order of the time of impact.
+///
+/// Nodes and hits share a single priority queue keyed by the time of impact. The time of impact
+/// of a node is a lower bound for the time of impact of the hits inside of it, so a hit at the front
+/// of the queue is the closest remaining hit, and the traversal can be paused until the next one is needed.
+struct SweptAabbBestFirstTraversal<'a, T> {
+    qbvhs: [&'a Qbvh<u32>; 2],
+    simd_ray: SimdRay,
+    simd_half_extents: parry::math::Vector<SimdReal>,
+    max_toi: SimdReal,
+    queue: BinaryHeap<TraversalEntry<T>>,
+}
+
+impl<'a, T> SweptAabbBestFirstTraversal<'a, T> {
+    /// Creates a traversal of an AABB with the given `half_extents` swept along the given `ray`,
+    /// which starts at the center of the AABB. A ray is traversed with zero half-extents.
+    fn new(
+        qbvhs: [&'a Qbvh<u32>; 2],
+        ray: &parry::query::Ray,
+        half_extents: parry::math::Vector<Scalar>,
+        max_toi: Scalar,
+    ) -> Self {
+        let mut queue = BinaryHeap::new();
+
+        for (tree, qbvh) in qbvhs.iter().enumerate() {
+            if !qbvh.raw_nodes().is_empty() {
+                queue.push(TraversalEntry {
+                    toi: 0.0,
+                    item: TraversalItem::Node { tree, node: 0 },
+                });
+            }
+        }
+
+        Self {
+            qbvhs,
+            simd_ray: SimdRay::splat(*ray),
+            simd_half_extents: parry::math::Vector::splat(half_extents),
+            max_toi: SimdReal::splat(max_toi),
+            queue,
+        }
+    }
+
+    /// Advances the traversal until the next closest hit is found.
+    ///
+    /// `leaf_hit` computes the time of impact and the hit for the proxy data of a leaf whose AABB is hit,
+    /// or returns `None` if the collider isn't hit.
+    fn next_hit(&mut self, mut leaf_hit: impl FnMut(u32) -> Option<(Scalar, T)>) -> Option<T> {
+        while let Some(entry) = self.queue.pop() {
+            let (tree, node_index) = match entry.item {
+                TraversalItem::Hit(hit) => return Some(hit),
+                TraversalItem::Node { tree, node } => (tree, node),
+            };
+
+            let qbvh = self.qbvhs[tree];
+            let node = &qbvh.raw_nodes()[node_index as usize];
+
+            // The AABBs are expanded by the half-extents of the swept AABB, so that a ray cast
+            // from its center is equivalent to sweeping the AABB against the original AABBs.
+            let mut bv = node.simd_aabb;
+            bv.mins -= self.simd_half_extents;
+            bv.maxs += self.simd_half_extents;
+            let (mask, tois) = bv.cast_local_ray(&self.simd_ray, self.max_toi);
+            let bitmask = mask.bitmask();
+
+            for lane in 0..SIMD_WIDTH {
+                let child = node.children[lane];
+
+                if child == u32::MAX || bitmask & (1 << lane) == 0 {
+                    continue;
+                }
+
+                if node.is_leaf() {
+                    if let Some((toi, hit)) = leaf_hit(qbvh.raw_proxies()[child as usize].data) {
+                        self.queue.push(TraversalEntry {
+                            toi,
+                            item: TraversalItem::Hit(hit),
+                        });
+                    }
+                } else if (child as usize) < qbvh.raw_nodes().len() {
+                    self.queue.push(TraversalEntry {
+                        toi: tois.extract(lane),
+                        item: TraversalItem::Node { tree, node: child },
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// A node or hit in the priority queue of a [`SweptAabbBestFirstTraversal`].
+struct TraversalEntry<T> {
+    toi: Scalar,
+    item: TraversalItem<T>,
+}
+
+enum TraversalItem<T> {
+    Node { tree: usize, node: u32 },
+    Hit(T),
+}
+
+impl<T> PartialEq for TraversalEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<T> Eq for TraversalEntry<T> {}
+
+impl<T> PartialOrd for TraversalEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for TraversalEntry<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reversed so that the binary heap pops the smallest time of impact first.
+        other.toi.total_cmp(&self.toi)
+    }
+}
+
+/// An iterator over entities with a [`ColliderAabb`] that is intersecting a given region.
+///
+/// The iterator traverses the acceleration structure depth-first, and only advances the traversal
+/// when `next` is called.
+///
+/// Created by [`SpatialQueryPipeline::aabb_intersections_iter`] and [`SpatialQuery::aabb_intersections_iter`].
+pub struct AabbIntersectionsIter<'a> {
+    pipeline: &'a SpatialQueryPipeline,
+    aabb: Aabb,
+    query_filter: SpatialQueryFilter,
+    /// The trees of the dynamic and static colliders.
+    qbvhs: [&'a Qbvh<u32>; 2],
+    /// The index of the tree that is being traversed.
+    qbvh_index: usize,
+    /// The nodes that are being visited and the index of the next child lane to test for each node.
+    stack: Vec<(u32, usize)>,
+}
+
+impl<'a> Iterator for AabbIntersectionsIter<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(qbvh) = self.qbvhs.get(self.qbvh_index).copied() {
+            let nodes = qbvh.raw_nodes();
+            let proxies = qbvh.raw_proxies();
+
+            while let Some(&(node_index, lane)) = self.stack.last() {
+                if lane >= SIMD_WIDTH {
+                    self.stack.pop();
+                    continue;
+                }
+
+                // Advance to the next lane of the current node.
+                self.stack.last_mut().unwrap().1 += 1;
+
+                let node = &nodes[node_index as usize];
+                let child = node.children[lane];
+
+                if child == u32::MAX || !node.simd_aabb.extract(lane).intersects(&self.aabb) {
+                    continue;
+                }
+
+                if node.is_leaf() {
+                    let entity = self
+                        .pipeline
+                        .entity_from_index(proxies[child as usize].data);
+
+                    if let Some((_, _, layers)) = self.pipeline.collider(entity) {
+                        if self.query_filter.test(entity, *layers) {
+                            return Some(entity);
+                        }
+                    }
+                } else {
+                    self.stack.push((child, 0));
+                }
+            }
+
+            // Continue with the next tree.
+            self.qbvh_index += 1;
+            if let Some(qbvh) = self.qbvhs.get(self.qbvh_index) {
+                if !qbvh.raw_nodes().is_empty() {
+                    self.stack.push((0, 0));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Removes the leaves of the removed colliders from the given tree, inserts or refits the leaves
+/// of the moved colliders, and rebalances the tree. Only the changed leaves and their ancestors are updated,
+/// which is much cheaper than rebuilding the tree when only a few colliders have changed.
+///
+/// After the update, the tree contains exactly the colliders in `colliders`.
+fn refit_qbvh(
+    qbvh: &mut Qbvh<u32>,
+    workspace: &mut QbvhUpdateWorkspace,
+    colliders: &ColliderMap,
+    entity_generations: &HashMap<u32, u32>,
+    removed: Vec<Entity>,
+    moved: Vec<Entity>,
+) {
+    if removed.is_empty() && moved.is_empty() {
+        return;
+    }
+
+    // Removals are done first, as the index of a removed entity can be reused by a moved one.
+    for entity in removed {
+        qbvh.remove(entity.index());
+    }
+    for entity in moved {
+        qbvh.pre_update_or_insert(entity.index());
+    }
+
+    // The AABBs are loosened by a small margin so that the ancestors of colliders
+    // that only move slightly don't need to be updated.
+    let margin = 0.01;
+
+    qbvh.refit(margin, workspace, |&index| {
+        let entity = entity_from_index_and_gen(index, entity_generations[&index]);
+        let (iso, shape, _) = &colliders[&entity];
+        shape.shape_scaled().compute_aabb(iso)
+    });
+    qbvh.rebalance(margin, workspace);
+}
+
+/// Returns `true` if the pose or shape of a collider in the pipeline differs between the given collider data.
+fn is_collider_moved(
+    (old_iso, old_collider, _): &(Isometry<Scalar>, Collider, CollisionLayers),
+    (new_iso, new_collider, _): &(Isometry<Scalar>, Collider, CollisionLayers),
+) -> bool {
+    old_iso != new_iso
+        || !Arc::ptr_eq(
+            &old_collider.shape_scaled().0,
+            &new_collider.shape_scaled().0,
+        )
+}
+
 fn entity_from_index_and_gen(index: u32, generation: u32) -> bevy::prelude::Entity {
     bevy::prelude::Entity::from_bits((generation as u64) << 32 | index as u64)
 }
@@ -834,3 +2267,68 @@ pub struct PointProjection {
     /// True if the point was inside of the collider.
     pub is_inside: bool,
 }
+
+/// Triangle data for a [ray hit](RayHitData) on a [trimesh](Collider::trimesh) collider,
+/// computed by [`SpatialQuery::trimesh_ray_hit`].
+///
+/// This can be used to get smooth normals for things like decals and ricochets
+/// on smooth terrain, where the normal of the hit triangle would look faceted.
+#[cfg(feature = "3d")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriMeshRayHit {
+    /// The index of the triangle that was hit.
+    pub triangle_index: u32,
+    /// The barycentric coordinates of the hit point with respect to the vertices of the hit triangle.
+    pub barycentric_coordinates: Vector,
+    /// The world-space normal at the hit point, interpolated from the vertex normals of the hit triangle.
+    pub smooth_normal: Vector,
+}
+
+/// An intersection between a shape and a [collider](Collider), computed by
+/// [`SpatialQuery::shape_intersections_with_manifolds`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeIntersection {
+    /// The entity of the collider that is intersecting the shape.
+    pub entity: Entity,
+    /// The largest penetration depth of the intersection.
+    pub penetration: Scalar,
+    /// The world-space contact normal of the deepest contact, pointing from the shape towards the collider.
+    ///
+    /// To move the shape out of the collider, it can be moved by `-normal * penetration`.
+    pub normal: Vector,
+    /// The contact manifolds between the shape and the collider. The shape is the first entity
+    /// and the collider is the second entity, and the contact data is in their local space.
+    pub manifolds: Vec<ContactManifold>,
+}
+
+impl ShapeIntersection {
+    fn from_manifolds(
+        entity: Entity,
+        manifolds: Vec<ContactManifold>,
+        shape_rotation: &Rotation,
+    ) -> Option<Self> {
+        let (penetration, normal) = manifolds
+            .iter()
+            .flat_map(|manifold| {
+                manifold
+                    .contacts
+                    .iter()
+                    .map(move |contact| (contact.penetration, manifold.normal1))
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))?;
+
+        // Shapes that are separated are not intersecting
+        if penetration < 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            entity,
+            penetration,
+            normal: shape_rotation.rotate(normal),
+            manifolds,
+        })
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bevy::{prelude::*, utils::HashSet};
 
 use crate::prelude::*;
@@ -28,6 +30,23 @@ use crate::prelude::*;
 ///     commands.spawn(RayCaster::default().with_query_filter(query_filter));
 /// }
 /// ```
+///
+/// ## Predicates
+///
+/// For more complex rules, a custom predicate can be added with [`SpatialQueryFilter::with_predicate`].
+/// The predicate is evaluated for each collider during the traversal, and colliders for which it
+/// returns `false` are skipped.
+///
+/// ```
+/// use bevy::{prelude::*, utils::HashSet};
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// fn team_filter(allies: HashSet<Entity>) -> SpatialQueryFilter {
+///     // Ignore all colliders that belong to the same team
+///     SpatialQueryFilter::default().with_predicate(move |entity| !allies.contains(&entity))
+/// }
+/// ```
 #[derive(Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpatialQueryFilter {
@@ -35,6 +54,12 @@ pub struct SpatialQueryFilter {
     pub mask: LayerMask,
     /// Entities that will not be included in [spatial queries](crate::spatial_query).
     pub excluded_entities: HashSet<Entity>,
+    /// A custom predicate that determines if an entity should be included in [spatial queries](crate::spatial_query).
+    /// Entities for which the predicate returns `false` are ignored. Predicates are not serialized.
+    ///
+    /// See also: [`SpatialQueryFilter::with_predicate`]
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub predicate: Option<Arc<dyn Fn(Entity) -> bool + Send + Sync>>,
 }
 
 impl Default for SpatialQueryFilter {
@@ -42,6 +67,7 @@ impl Default for SpatialQueryFilter {
         Self {
             mask: LayerMask::ALL,
             excluded_entities: default(),
+            predicate: None,
         }
     }
 }
@@ -77,11 +103,38 @@ impl SpatialQueryFilter {
         self
     }
 
+    /// Sets a custom predicate that is evaluated for each entity during the traversal of
+    /// [spatial queries](crate::spatial_query). Entities for which the predicate returns `false` are ignored.
+    ///
+    /// This replaces any previously set predicate.
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(Entity) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Removes the custom predicate of the filter, if there is one.
+    pub fn without_predicate(mut self) -> Self {
+        self.predicate = None;
+        self
+    }
+
+    /// Returns true if the filter has a custom predicate.
+    pub fn has_predicate(&self) -> bool {
+        self.predicate.is_some()
+    }
+
     /// Tests if an entity should be included in [spatial queries](crate::spatial_query) based on the
     /// filter configuration.
     pub fn test(&self, entity: Entity, layers: CollisionLayers) -> bool {
         !self.excluded_entities.contains(&entity)
             && CollisionLayers::new(LayerMask::ALL, self.mask)
                 .interacts_with(CollisionLayers::new(layers.memberships, LayerMask::ALL))
+            && self
+                .predicate
+                .as_ref()
+                .is_none_or(|predicate| predicate(entity))
     }
 }
@@ -3,7 +3,6 @@ use bevy::{
     ecs::entity::{EntityMapper, MapEntities},
     prelude::*,
 };
-use parry::query::details::TOICompositeShapeShapeBestFirstVisitor;
 
 /// A component used for [shapecasting](spatial_query#shapecasting).
 ///
@@ -16,7 +15,8 @@ use parry::query::details::TOICompositeShapeShapeBestFirstVisitor;
 /// the order of the time of impact.
 ///
 /// Computing lots of hits can be expensive, especially against complex geometry, so the maximum number of hits
-/// is one by default. This can be configured through the `max_hits` property.
+/// is one by default. This can be configured through the `max_hits` property, or [`ShapeCaster::with_all_hits`]
+/// can be used to get every hit along the sweep.
 ///
 /// The [`ShapeCaster`] is the easiest way to handle simple shapecasting. If you want more control and don't want
 /// to perform shapecasts on every frame, consider using the [`SpatialQuery`] system parameter.
@@ -211,6 +211,16 @@ impl ShapeCaster {
         self
     }
 
+    /// Makes the shape caster compute all hits along the sweep instead of only the first one.
+    /// The hits are stored in [`ShapeHits`] in the order of the time of impact.
+    ///
+    /// This is equivalent to setting `max_hits` to `u32::MAX`. Note that computing
+    /// lots of hits can be expensive, so consider limiting the `max_time_of_impact` as well.
+    pub fn with_all_hits(mut self) -> Self {
+        self.max_hits = u32::MAX;
+        self
+    }
+
     /// Sets the shape caster's [query filter](SpatialQueryFilter) that controls which colliders
     /// should be included or excluded by shapecasts.
     pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
@@ -286,52 +296,26 @@ impl ShapeCaster {
 
         hits.count = 0;
 
-        let shape_rotation: Rotation;
-        #[cfg(feature = "2d")]
-        {
-            shape_rotation = Rotation::from_radians(self.global_shape_rotation());
-        }
-        #[cfg(feature = "3d")]
-        {
-            shape_rotation = Rotation::from(self.global_shape_rotation());
-        }
-
-        let shape_isometry = utils::make_isometry(self.global_origin(), shape_rotation);
-        let shape_direction = self.global_direction().adjust_precision().into();
-
-        while hits.count < self.max_hits {
-            let pipeline_shape = query_pipeline.as_composite_shape(query_filter.clone());
-            let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-                &*query_pipeline.dispatcher,
-                &shape_isometry,
-                &shape_direction,
-                &pipeline_shape,
-                &**self.shape.shape_scaled(),
+        let sorted_hits = query_pipeline
+            .shape_hits_sorted(
+                &self.shape,
+                self.global_origin(),
+                self.global_shape_rotation(),
+                self.global_direction(),
                 self.max_time_of_impact,
-                !self.ignore_origin_penetration,
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| ShapeHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.toi,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
-                },
-            ) {
-                if (hits.vector.len() as u32) < hits.count + 1 {
-                    hits.vector.push(hit);
-                } else {
-                    hits.vector[hits.count as usize] = hit;
-                }
-
-                hits.count += 1;
-                query_filter.excluded_entities.insert(hit.entity);
+                self.ignore_origin_penetration,
+                query_filter,
+            )
+            .take(self.max_hits as usize);
+
+        for hit in sorted_hits {
+            if (hits.vector.len() as u32) < hits.count + 1 {
+                hits.vector.push(hit);
             } else {
-                return;
+                hits.vector[hits.count as usize] = hit;
             }
+
+            hits.count += 1;
         }
     }
 }
@@ -433,3 +417,101 @@ impl MapEntities for ShapeHitData {
         self.entity = entity_mapper.map_entity(self.entity);
     }
 }
+
+/// Determines how [shapecasts](spatial_query#shapecasting) configured with a [`ShapeCastConfig`]
+/// handle colliders that the shape is already overlapping at the cast origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum InitialOverlapPolicy {
+    /// Colliders that are penetrating the shape at the cast origin are ignored,
+    /// and only the next hit is computed.
+    Ignore,
+    /// Initially overlapping colliders are reported as a hit with a time of impact of zero,
+    /// along with the [penetration depth](ShapeCastHit::penetration_depth).
+    #[default]
+    Report,
+    /// Like [`InitialOverlapPolicy::Report`], but the points and normals of the hit are replaced by the contact
+    /// between the shape and the collider, and the [depenetration](ShapeCastHit::depenetration) vector
+    /// that moves the shape out of the collider is computed.
+    Depenetrate,
+}
+
+/// Configuration for [shapecasts](spatial_query#shapecasting) performed with
+/// [`SpatialQuery::cast_shape_with_config`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeCastConfig {
+    /// The maximum distance that the shape can travel.
+    pub max_time_of_impact: Scalar,
+    /// The distance by which the shape is inflated. Hits are reported when the shape comes within
+    /// this distance of a collider, and the time of impact is reduced accordingly so that
+    /// the shape stops before touching the collider.
+    ///
+    /// A small skin width keeps things like character controllers from getting stuck
+    /// due to numerical errors. The default is zero.
+    pub skin_width: Scalar,
+    /// Determines how colliders that the shape is already overlapping at the cast origin are handled.
+    pub initial_overlap: InitialOverlapPolicy,
+}
+
+impl Default for ShapeCastConfig {
+    fn default() -> Self {
+        Self {
+            max_time_of_impact: Scalar::MAX,
+            skin_width: 0.0,
+            initial_overlap: InitialOverlapPolicy::default(),
+        }
+    }
+}
+
+impl ShapeCastConfig {
+    /// Creates a new [`ShapeCastConfig`] with the given maximum time of impact.
+    pub fn from_max_time_of_impact(max_time_of_impact: Scalar) -> Self {
+        Self {
+            max_time_of_impact,
+            ..default()
+        }
+    }
+
+    /// Sets the maximum distance that the shape can travel.
+    pub fn with_max_time_of_impact(mut self, max_time_of_impact: Scalar) -> Self {
+        self.max_time_of_impact = max_time_of_impact;
+        self
+    }
+
+    /// Sets the distance by which the shape is inflated.
+    pub fn with_skin_width(mut self, skin_width: Scalar) -> Self {
+        self.skin_width = skin_width;
+        self
+    }
+
+    /// Sets the [`InitialOverlapPolicy`] that determines how colliders that the shape
+    /// is already overlapping at the cast origin are handled.
+    pub fn with_initial_overlap(mut self, policy: InitialOverlapPolicy) -> Self {
+        self.initial_overlap = policy;
+        self
+    }
+}
+
+/// A hit computed by a [shapecast](spatial_query#shapecasting) configured with a [`ShapeCastConfig`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeCastHit {
+    /// Data related to the hit. The time of impact has been reduced by the
+    /// [skin width](ShapeCastConfig::skin_width).
+    pub data: ShapeHitData,
+    /// How deep the shape, inflated by the skin width, is penetrating the collider at the cast origin.
+    /// Zero unless the shape was initially overlapping the collider and the [`InitialOverlapPolicy`]
+    /// is not [`InitialOverlapPolicy::Ignore`].
+    pub penetration_depth: Scalar,
+    /// The world-space translation that moves the shape, inflated by the skin width, out of the collider.
+    /// Zero unless the shape was initially overlapping the collider and the [`InitialOverlapPolicy`]
+    /// is [`InitialOverlapPolicy::Depenetrate`].
+    pub depenetration: Vector,
+}
+
+impl MapEntities for ShapeCastHit {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.data.map_entities(entity_mapper);
+    }
+}
@@ -6,17 +6,26 @@ use bevy::{ecs::system::SystemParam, prelude::*};
 /// ## Methods
 ///
 /// - [Raycasting](spatial_query#raycasting): [`cast_ray`](SpatialQuery::cast_ray),
-/// [`ray_hits`](SpatialQuery::ray_hits), [`ray_hits_callback`](SpatialQuery::ray_hits_callback)
+/// [`ray_hits`](SpatialQuery::ray_hits), [`ray_hits_sorted`](SpatialQuery::ray_hits_sorted),
+/// [`ray_hits_callback`](SpatialQuery::ray_hits_callback), [`cast_rays_batch`](SpatialQuery::cast_rays_batch),
+/// [`cast_ray_against`](SpatialQuery::cast_ray_against)
 /// - [Shapecasting](spatial_query#shapecasting): [`cast_shape`](SpatialQuery::cast_shape),
-/// [`shape_hits`](SpatialQuery::shape_hits), [`shape_hits_callback`](SpatialQuery::shape_hits_callback)
+/// [`cast_shape_with_config`](SpatialQuery::cast_shape_with_config),
+/// [`shape_hits`](SpatialQuery::shape_hits), [`shape_hits_sorted`](SpatialQuery::shape_hits_sorted),
+/// [`shape_hits_callback`](SpatialQuery::shape_hits_callback), [`cast_shape_against`](SpatialQuery::cast_shape_against)
 /// - [Point projection](spatial_query#point-projection): [`project_point`](SpatialQuery::project_point)
+/// - Closest points: [`closest_points`](SpatialQuery::closest_points),
+/// [`closest_points_to_shape`](SpatialQuery::closest_points_to_shape), [`distance`](SpatialQuery::distance)
 /// - [Intersection tests](spatial_query#intersection-tests)
 ///     - Point intersections: [`point_intersections`](SpatialQuery::point_intersections),
 /// [`point_intersections_callback`](SpatialQuery::point_intersections_callback)
 ///     - AABB intersections: [`aabb_intersections_with_aabb`](SpatialQuery::aabb_intersections_with_aabb),
-/// [`aabb_intersections_with_aabb_callback`](SpatialQuery::aabb_intersections_with_aabb_callback)
+/// [`aabb_intersections_with_aabb_callback`](SpatialQuery::aabb_intersections_with_aabb_callback),
+/// [`aabb_intersections_iter`](SpatialQuery::aabb_intersections_iter)
 ///     - Shape intersections: [`shape_intersections`](SpatialQuery::shape_intersections)
-/// [`shape_intersections_callback`](SpatialQuery::shape_intersections_callback)
+/// [`shape_intersections_callback`](SpatialQuery::shape_intersections_callback),
+/// [`shape_intersections_with_manifolds`](SpatialQuery::shape_intersections_with_manifolds)
+/// - Background queries: [`snapshot`](SpatialQuery::snapshot)
 ///
 /// For simple raycasts and shapecasts, consider using the [`RayCaster`] and [`ShapeCaster`] components that
 /// provide a more ECS-based approach and perform casts on every frame.
@@ -73,6 +82,52 @@ pub struct SpatialQuery<'w, 's> {
         ),
     >,
     pub(crate) added_colliders: Query<'w, 's, Entity, Added<Collider>>,
+    #[allow(clippy::type_complexity)]
+    pub(crate) changed_colliders: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Position,
+            &'static Rotation,
+            &'static Collider,
+            Option<&'static CollisionLayers>,
+        ),
+        Or<(
+            Changed<Position>,
+            Changed<Rotation>,
+            Changed<Collider>,
+            Changed<CollisionLayers>,
+        )>,
+    >,
+    pub(crate) removed_colliders: RemovedComponents<'w, 's, Collider>,
+    pub(crate) collider_transforms: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static Collider,
+            Option<&'static CollisionLayers>,
+        ),
+    >,
+    #[allow(clippy::type_complexity)]
+    pub(crate) changed_collider_transforms: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static Collider,
+            Option<&'static CollisionLayers>,
+        ),
+        Or<(
+            Changed<GlobalTransform>,
+            Changed<Collider>,
+            Changed<CollisionLayers>,
+        )>,
+    >,
+    pub(crate) poses: Res<'w, SpatialQueryPoses>,
     /// The [`SpatialQueryPipeline`].
     pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
 }
@@ -81,9 +136,55 @@ impl<'w, 's> SpatialQuery<'w, 's> {
     /// Updates the colliders in the pipeline. This is done automatically once per physics frame in
     /// [`PhysicsStepSet::SpatialQuery`], but if you modify colliders or their positions before that, you can
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
+    ///
+    /// The poses used for the colliders are determined by the [`SpatialQueryPoses`] resource.
+    ///
+    /// Only colliders that have been changed, added or removed since the last time the system using
+    /// this [`SpatialQuery`] was run are updated. The first time the pipeline is updated by a system,
+    /// and whenever the [`SpatialQueryPoses`] change, all colliders are updated instead.
     pub fn update_pipeline(&mut self) {
+        if self.poses.is_changed() {
+            match *self.poses {
+                SpatialQueryPoses::Physics => self
+                    .query_pipeline
+                    .update(self.colliders.iter(), self.added_colliders.iter()),
+                SpatialQueryPoses::Rendered => self.query_pipeline.update_from_transforms(
+                    self.collider_transforms.iter(),
+                    self.added_colliders.iter(),
+                ),
+            }
+            // Removed colliders are already left out of the full update.
+            self.removed_colliders.clear();
+            return;
+        }
+
+        match *self.poses {
+            SpatialQueryPoses::Physics => self.update_pipeline_incremental(),
+            SpatialQueryPoses::Rendered => self.query_pipeline.update_incremental_from_transforms(
+                self.changed_collider_transforms.iter(),
+                self.removed_colliders.read(),
+            ),
+        }
+    }
+
+    /// Updates only the colliders in the pipeline that have been changed, added or removed since the
+    /// last time the system using this [`SpatialQuery`] was run, using their [`Position`] and [`Rotation`].
+    ///
+    /// Unlike [`update_pipeline`](Self::update_pipeline), this ignores the [`SpatialQueryPoses`] resource.
+    /// It is used for keeping spatial queries up to date when physics is paused. If you step physics
+    /// manually, you can add a system that calls this to [`SpatialQueryUpdateSet`].
+    pub fn update_pipeline_incremental(&mut self) {
         self.query_pipeline
-            .update(self.colliders.iter(), self.added_colliders.iter());
+            .update_incremental(self.changed_colliders.iter(), self.removed_colliders.read());
+    }
+
+    /// Takes an immutable [`SpatialQuerySnapshot`] of the current state of the [`SpatialQueryPipeline`].
+    ///
+    /// The snapshot can be sent to background tasks and queried there while the simulation keeps running.
+    /// Taking a snapshot copies the acceleration structures of the pipeline, so it shouldn't be done every frame
+    /// for large worlds. See [`SpatialQuerySnapshot`] for more information.
+    pub fn snapshot(&self) -> SpatialQuerySnapshot {
+        self.query_pipeline.snapshot()
     }
 
     /// Casts a [ray](spatial_query#raycasting) and computes the closest [hit](RayHitData) with a collider.
@@ -196,6 +297,150 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a batch of [rays](spatial_query#raycasting) and computes the closest [hit](RayHitData)
+    /// for each of them. The results are in the same order as the given rays, and `None` is returned
+    /// for rays that don't hit anything.
+    ///
+    /// With the `parallel` feature enabled, the rays are cast in parallel on the `ComputeTaskPool`.
+    /// This is useful when casting hundreds of rays per frame, for example for AI vision cones or audio occlusion.
+    ///
+    /// ## Arguments
+    ///
+    /// - `rays`: The [rays](RayInput) that are cast.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hits(spatial_query: SpatialQuery) {
+    ///     // Cast rays in a fan around the Y axis
+    ///     let rays = (0..100)
+    ///         .map(|i| {
+    ///             let angle = i as f32 * std::f32::consts::TAU / 100.0;
+    ///             let direction = Direction3d::new(Vec3::new(angle.cos(), 0.0, angle.sin())).unwrap();
+    ///             RayInput::new(Vec3::ZERO, direction, 100.0, true)
+    ///         })
+    ///         .collect::<Vec<_>>();
+    ///
+    ///     let hits = spatial_query.cast_rays_batch(&rays, SpatialQueryFilter::default());
+    ///
+    ///     for (ray, hit) in rays.iter().zip(hits) {
+    ///         if let Some(hit) = hit {
+    ///             println!("Ray in direction {:?} hit {:?}", ray.direction, hit.entity);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn cast_rays_batch(
+        &self,
+        rays: &[RayInput],
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<Option<RayHitData>> {
+        self.query_pipeline.cast_rays_batch(rays, query_filter)
+    }
+
+    /// Casts a [ray](spatial_query#raycasting) against the collider of a single entity and computes the [hit](RayHitData).
+    /// If the ray doesn't hit the collider or the entity doesn't have a collider, `None` is returned.
+    ///
+    /// This only tests the given entity and skips the traversal of the acceleration structure entirely,
+    /// which makes it useful for checks like "did the cursor hit this specific enemy".
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the ray is cast against.
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_enemy_hits(spatial_query: SpatialQuery, enemies: Query<Entity, With<Enemy>>) {
+    ///     for enemy in &enemies {
+    ///         if let Some(hit) = spatial_query.cast_ray_against(
+    ///             enemy,          // Entity
+    ///             Vec3::ZERO,     // Origin
+    ///             Direction3d::X, // Direction
+    ///             100.0,          // Maximum time of impact (travel distance)
+    ///             true,           // Does the ray treat colliders as "solid"
+    ///         ) {
+    ///             println!("Hit enemy {:?} at distance {}", enemy, hit.time_of_impact);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn cast_ray_against(
+        &self,
+        entity: Entity,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<RayHitData> {
+        self.query_pipeline
+            .cast_ray_against(entity, origin, direction, max_time_of_impact, solid)
+    }
+
+    /// Computes the [`TriMeshRayHit`] for a [ray hit](RayHitData) on a [trimesh](Collider::trimesh) collider,
+    /// containing the index of the triangle that was hit, the barycentric coordinates of the hit point
+    /// and a smooth normal interpolated from the vertex normals of the triangle.
+    ///
+    /// `origin` and `direction` should be the same as the ones used for the raycast that produced the `hit`.
+    /// If the hit entity doesn't have a trimesh collider, `None` is returned.
+    ///
+    /// Note that the vertex normals are computed on demand by averaging the normals of all triangles
+    /// sharing a vertex, so the cost of this method scales with the number of triangles in the trimesh.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(feature = "f32")]
+    /// fn print_smooth_normal(spatial_query: SpatialQuery) {
+    ///     let origin = Vec3::ZERO;
+    ///     let direction = Direction3d::NEG_Y;
+    ///
+    ///     if let Some(hit) =
+    ///         spatial_query.cast_ray(origin, direction, 100.0, true, SpatialQueryFilter::default())
+    ///     {
+    ///         let normal = spatial_query
+    ///             .trimesh_ray_hit(&hit, origin, direction)
+    ///             .map_or(hit.normal, |trimesh_hit| trimesh_hit.smooth_normal);
+    ///         println!("Normal: {}", normal);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "3d")]
+    pub fn trimesh_ray_hit(
+        &self,
+        hit: &RayHitData,
+        origin: Vector,
+        direction: Dir,
+    ) -> Option<TriMeshRayHit> {
+        self.query_pipeline.trimesh_ray_hit(hit, origin, direction)
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData) until `max_hits` is reached.
     ///
     /// Note that the order of the results is not guaranteed, and if there are more hits than `max_hits`,
@@ -257,6 +502,117 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a [ray](spatial_query#raycasting) and returns an iterator over all [hits](RayHitData)
+    /// in the order of the time of impact.
+    ///
+    /// The hits are computed lazily, so each call to `next` only finds the next closest hit.
+    /// This makes it cheap to stop early, for example when a piercing projectile runs out of energy.
+    ///
+    /// ## Arguments
+    ///
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hits(spatial_query: SpatialQuery) {
+    ///     // Cast ray and print hits from closest to furthest
+    ///     for hit in spatial_query.ray_hits_sorted(
+    ///         Vec3::ZERO,                    // Origin
+    ///         Direction3d::X,                // Direction
+    ///         100.0,                         // Maximum time of impact (travel distance)
+    ///         true,                          // Does the ray treat colliders as "solid"
+    ///         SpatialQueryFilter::default(), // Query filter
+    ///     ) {
+    ///         println!("Hit: {:?}", hit);
+    ///     }
+    /// }
+    /// ```
+    pub fn ray_hits_sorted(
+        &self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> RayHitsSortedIter<'_> {
+        self.query_pipeline.ray_hits_sorted(
+            origin,
+            direction,
+            max_time_of_impact,
+            solid,
+            query_filter,
+        )
+    }
+
+    /// Casts a [ray](spatial_query#raycasting) and returns an iterator over the closest [hits](RayHitData)
+    /// in the order of the time of impact, stopping once `max_hits` hits have been found.
+    ///
+    /// ## Arguments
+    ///
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `max_hits`: The maximum number of hits. Hits further away will not be computed.
+    /// - `solid`: If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hits(spatial_query: SpatialQuery) {
+    ///     // Cast ray and print the three closest hits
+    ///     for hit in spatial_query.ray_hits_sorted_max(
+    ///         Vec3::ZERO,                    // Origin
+    ///         Direction3d::X,                // Direction
+    ///         100.0,                         // Maximum time of impact (travel distance)
+    ///         3,                             // Maximum number of hits
+    ///         true,                          // Does the ray treat colliders as "solid"
+    ///         SpatialQueryFilter::default(), // Query filter
+    ///     ) {
+    ///         println!("Hit: {:?}", hit);
+    ///     }
+    /// }
+    /// ```
+    pub fn ray_hits_sorted_max(
+        &self,
+        origin: Vector,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        max_hits: u32,
+        solid: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> std::iter::Take<RayHitsSortedIter<'_>> {
+        self.query_pipeline.ray_hits_sorted_max(
+            origin,
+            direction,
+            max_time_of_impact,
+            max_hits,
+            solid,
+            query_filter,
+        )
+    }
+
     /// Casts a [ray](spatial_query#raycasting) and computes all [hits](RayHitData), calling the given `callback`
     /// for each hit. The raycast stops when `callback` returns false or all hits have been found.
     ///
@@ -387,6 +743,110 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes the closest [hit](ShapeCastHit)
+    /// with a collider using the given [`ShapeCastConfig`]. If there are no hits, `None` is returned.
+    ///
+    /// The configuration determines the skin width of the shape and how colliders that the shape
+    /// is already overlapping at the cast origin are handled, which is useful for things like
+    /// character controllers and camera collision.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `config`: A [`ShapeCastConfig`] that determines the behavior of the cast.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hit(spatial_query: SpatialQuery) {
+    ///     let config = ShapeCastConfig::from_max_time_of_impact(100.0)
+    ///         .with_skin_width(0.01)
+    ///         .with_initial_overlap(InitialOverlapPolicy::Depenetrate);
+    ///
+    ///     if let Some(hit) = spatial_query.cast_shape_with_config(
+    ///         &Collider::sphere(0.5),        // Shape
+    ///         Vec3::ZERO,                    // Origin
+    ///         Quat::default(),               // Shape rotation
+    ///         Direction3d::X,                // Direction
+    ///         &config,                       // Configuration
+    ///         SpatialQueryFilter::default(), // Query filter
+    ///     ) {
+    ///         if hit.penetration_depth > 0.0 {
+    ///             println!("Initially overlapping, move by {}", hit.depenetration);
+    ///         } else {
+    ///             println!("Hit: {:?}", hit.data);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn cast_shape_with_config(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        config: &ShapeCastConfig,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeCastHit> {
+        self.query_pipeline.cast_shape_with_config(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            config,
+            query_filter,
+        )
+    }
+
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation against the collider of a single entity
+    /// and computes the [hit](ShapeHitData). If the shape doesn't hit the collider, the entity doesn't have
+    /// a collider, or the shapes are not supported, `None` is returned.
+    ///
+    /// This only tests the given entity and skips the traversal of the acceleration structure entirely.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the shape is cast against.
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating the collider at the
+    /// shape origin, the hit will be ignored. Otherwise, the initial hit will be returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_against(
+        &self,
+        entity: Entity,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+    ) -> Option<ShapeHitData> {
+        self.query_pipeline.cast_shape_against(
+            entity,
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+        )
+    }
+
     /// Casts a [shape](spatial_query#shapecasting) with a given rotation and computes computes all [hits](ShapeHitData)
     /// in the order of the time of impact until `max_hits` is reached.
     ///
@@ -532,6 +992,74 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a [shape](spatial_query#shapecasting) with a given rotation and returns an iterator over all
+    /// [hits](ShapeHitData) along the sweep in the order of the time of impact.
+    ///
+    /// The hits are computed lazily, so each call to `next` only finds the next closest hit.
+    /// This is useful for things like wide attacks and area sweeps that should affect
+    /// everything along the path.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating a collider at the
+    /// shape origin, the hit will be ignored and only the next hit will be computed. Otherwise, the initial
+    /// hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hits(spatial_query: SpatialQuery) {
+    ///     let shape = Collider::sphere(0.5);
+    ///
+    ///     // Cast shape and print all hits along the sweep
+    ///     for hit in spatial_query.shape_hits_sorted(
+    ///         &shape,                          // Shape
+    ///         Vec3::ZERO,                      // Origin
+    ///         Quat::default(),                 // Shape rotation
+    ///         Direction3d::X,                  // Direction
+    ///         100.0,                           // Maximum time of impact (travel distance)
+    ///         true,                            // Should initial penetration at the origin be ignored
+    ///         SpatialQueryFilter::default(),   // Query filter
+    ///     ) {
+    ///         println!("Hit: {:?}", hit);
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn shape_hits_sorted<'a>(
+        &'a self,
+        shape: &'a Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Dir,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> ShapeHitsSortedIter<'a> {
+        self.query_pipeline.shape_hits_sorted(
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        )
+    }
+
     /// Finds the [projection](spatial_query#point-projection) of a given point on the closest [collider](Collider).
     /// If one isn't found, `None` is returned.
     ///
@@ -573,6 +1101,104 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             .project_point(point, solid, query_filter)
     }
 
+    /// Computes the [closest points](contact_query::ClosestPoints) between the colliders of two entities.
+    /// This can be used for things like reach checks for AI or proximity-triggered behavior.
+    ///
+    /// Returns `None` if either entity doesn't have a collider in the pipeline or if either
+    /// of the collider shapes is not supported.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity1`: The entity of the first collider.
+    /// - `entity2`: The entity of the second collider.
+    /// - `max_distance`: The maximum distance between the closest points. If the colliders are further apart,
+    /// [`ClosestPoints::OutsideMargin`](contact_query::ClosestPoints::OutsideMargin) is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::{contact_query::ClosestPoints, *};
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::{contact_query::ClosestPoints, *};
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// fn print_enemies_in_reach(
+    ///     spatial_query: SpatialQuery,
+    ///     player: Query<Entity, With<Player>>,
+    ///     enemies: Query<Entity, With<Enemy>>,
+    /// ) {
+    ///     let Ok(player) = player.get_single() else {
+    ///         return;
+    ///     };
+    ///
+    ///     for enemy in &enemies {
+    ///         if let Some(ClosestPoints::WithinMargin(point1, point2)) =
+    ///             spatial_query.closest_points(player, enemy, 2.0)
+    ///         {
+    ///             println!("Enemy {:?} is in reach: {} -> {}", enemy, point1, point2);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn closest_points(
+        &self,
+        entity1: Entity,
+        entity2: Entity,
+        max_distance: Scalar,
+    ) -> Option<contact_query::ClosestPoints> {
+        self.query_pipeline
+            .closest_points(entity1, entity2, max_distance)
+    }
+
+    /// Computes the [closest points](contact_query::ClosestPoints) between the collider of an entity
+    /// and a `shape` with a given position and rotation.
+    ///
+    /// The first point belongs to the entity's collider and the second point to the given shape.
+    ///
+    /// Returns `None` if the entity doesn't have a collider in the pipeline or if either
+    /// of the collider shapes is not supported.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity of the collider.
+    /// - `shape`: The shape represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `max_distance`: The maximum distance between the closest points. If the shapes are further apart,
+    /// [`ClosestPoints::OutsideMargin`](contact_query::ClosestPoints::OutsideMargin) is returned.
+    pub fn closest_points_to_shape(
+        &self,
+        entity: Entity,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        max_distance: Scalar,
+    ) -> Option<contact_query::ClosestPoints> {
+        self.query_pipeline.closest_points_to_shape(
+            entity,
+            shape,
+            shape_position,
+            shape_rotation,
+            max_distance,
+        )
+    }
+
+    /// Computes the minimum distance separating the colliders of two entities.
+    /// The distance is `0.0` if the colliders are touching or penetrating.
+    ///
+    /// Returns `None` if either entity doesn't have a collider in the pipeline or if either
+    /// of the collider shapes is not supported.
+    pub fn distance(&self, entity1: Entity, entity2: Entity) -> Option<Scalar> {
+        self.query_pipeline.distance(entity1, entity2)
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that contains the given point.
     ///
@@ -720,6 +1346,48 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             .aabb_intersections_with_aabb_callback(aabb, callback)
     }
 
+    /// An [intersection test](spatial_query#intersection-tests) that returns an iterator lazily yielding
+    /// all entities with a [`ColliderAabb`] that is intersecting the given `aabb`.
+    ///
+    /// The acceleration structure is only traversed as far as the iterator is advanced,
+    /// and no shapes or intermediate collections are allocated. This makes it a cheap pre-filter
+    /// for things like chunk streaming, visibility or area damage.
+    ///
+    /// ## Arguments
+    ///
+    /// - `aabb`: The region that intersections are tested against.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_first_aabb_intersections(spatial_query: SpatialQuery) {
+    ///     let aabb = ColliderAabb::new(Vec3::ZERO, Vec3::splat(10.0));
+    ///
+    ///     for entity in spatial_query
+    ///         .aabb_intersections_iter(aabb, SpatialQueryFilter::default())
+    ///         .take(5)
+    ///     {
+    ///         println!("Entity: {:?}", entity);
+    ///     }
+    /// }
+    /// ```
+    pub fn aabb_intersections_iter(
+        &self,
+        aabb: ColliderAabb,
+        query_filter: SpatialQueryFilter,
+    ) -> AabbIntersectionsIter<'_> {
+        self.query_pipeline
+            .aabb_intersections_iter(aabb, query_filter)
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
     /// that is intersecting the given `shape` with a given position and rotation.
     ///
@@ -821,4 +1489,62 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             callback,
         )
     }
+
+    /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [`Collider`]
+    /// that is intersecting the given `shape` with a given position and rotation, and computes the
+    /// [contact manifolds](ContactManifold) for each intersection.
+    ///
+    /// Unlike [`shape_intersections`](Self::shape_intersections), this also returns the penetration depth
+    /// and contact normal of each intersection, which can be used for things like overlap recovery
+    /// without having to spawn a sensor entity.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that intersections are tested against represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_shape_intersections(spatial_query: SpatialQuery) {
+    ///     let intersections = spatial_query.shape_intersections_with_manifolds(
+    ///         &Collider::sphere(0.5),          // Shape
+    ///         Vec3::ZERO,                      // Shape position
+    ///         Quat::default(),                 // Shape rotation
+    ///         SpatialQueryFilter::default(),   // Query filter
+    ///     );
+    ///
+    ///     for intersection in intersections.iter() {
+    ///         println!(
+    ///             "Entity {:?} with penetration {} along {}",
+    ///             intersection.entity,
+    ///             intersection.penetration,
+    ///             intersection.normal,
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    pub fn shape_intersections_with_manifolds(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<ShapeIntersection> {
+        self.query_pipeline.shape_intersections_with_manifolds(
+            shape,
+            shape_position,
+            shape_rotation,
+            query_filter,
+        )
+    }
 }
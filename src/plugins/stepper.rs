@@ -0,0 +1,363 @@
+//! Step-through debugging of the physics simulation one step, substep or substep set at a time.
+//!
+//! See [`PhysicsStepperPlugin`] and [`PhysicsStepper`].
+
+use std::time::Duration;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A plugin for step-through debugging of the physics simulation using the [`PhysicsStepper`] resource.
+///
+/// When the stepper is [enabled](PhysicsStepper::enabled), the simulation is paused and only advanced
+/// when a [step](PhysicsStepUnit::Step), [substep](PhysicsStepUnit::Substep) or
+/// [substep set](PhysicsStepUnit::SubstepSet) is requested. Components like [`Position`] and [`Rotation`]
+/// and the `Transform` of bodies are updated after each request, so the `PhysicsDebugPlugin`
+/// can be used to inspect the intermediate state of a step, like the substep in which
+/// a constraint explodes.
+///
+/// The stepper can be controlled with the keys configured in this plugin or by modifying the [`PhysicsStepper`]
+/// resource directly. By default, F5 toggles the stepper, F6 advances by a step, F7 advances by a substep
+/// and F8 runs the next [`SubstepSet`]. The keys are only handled if the `ButtonInput<KeyCode>` resource exists.
+///
+/// This plugin is not included in [`PhysicsPlugins`] and must be added separately.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             PhysicsStepperPlugin::default(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct PhysicsStepperPlugin {
+    /// The key that toggles the [`PhysicsStepper`]. `None` disables the key binding.
+    pub toggle_key: Option<KeyCode>,
+    /// The key that advances the simulation by a [step](PhysicsStepUnit::Step). `None` disables the key binding.
+    pub step_key: Option<KeyCode>,
+    /// The key that advances the simulation by a [substep](PhysicsStepUnit::Substep). `None` disables the key binding.
+    pub substep_key: Option<KeyCode>,
+    /// The key that runs the next [substep set](PhysicsStepUnit::SubstepSet). `None` disables the key binding.
+    pub substep_set_key: Option<KeyCode>,
+}
+
+impl Default for PhysicsStepperPlugin {
+    fn default() -> Self {
+        Self {
+            toggle_key: Some(KeyCode::F5),
+            step_key: Some(KeyCode::F6),
+            substep_key: Some(KeyCode::F7),
+            substep_set_key: Some(KeyCode::F8),
+        }
+    }
+}
+
+impl Plugin for PhysicsStepperPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PhysicsStepper>()
+            .register_type::<PhysicsStepper>();
+
+        let keys = StepperKeys {
+            toggle: self.toggle_key,
+            step: self.step_key,
+            substep: self.substep_key,
+            substep_set: self.substep_set_key,
+        };
+        app.add_systems(
+            Update,
+            move |stepper: ResMut<PhysicsStepper>, input: Option<Res<ButtonInput<KeyCode>>>| {
+                handle_stepper_keys(keys, stepper, input)
+            },
+        );
+
+        // The rest of the step is only run once all of the substeps have been run.
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .configure_sets(
+                (
+                    PhysicsStepSet::BroadPhase,
+                    PhysicsStepSet::ReportContacts,
+                    PhysicsStepSet::Sleeping,
+                    PhysicsStepSet::SpatialQuery,
+                )
+                    .run_if(|stepper: Res<PhysicsStepper>| !stepper.is_mid_step()),
+            );
+
+        let substep_schedule = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+        for (index, set) in SUBSTEP_SETS.into_iter().enumerate() {
+            substep_schedule.configure_sets(
+                set.run_if(move |stepper: Res<PhysicsStepper>| stepper.runs_substep_set(index)),
+            );
+        }
+    }
+}
+
+/// The [`SubstepSet`]s in the order that they are run in.
+const SUBSTEP_SETS: [SubstepSet; 9] = [
+    SubstepSet::Integrate,
+    SubstepSet::NarrowPhase,
+    SubstepSet::PostProcessCollisions,
+    SubstepSet::SolveConstraints,
+    SubstepSet::SolveUserConstraints,
+    SubstepSet::UpdateVelocities,
+    SubstepSet::SolveVelocities,
+    SubstepSet::StoreImpulses,
+    SubstepSet::ApplyTranslation,
+];
+
+/// The amount that the simulation is advanced by when requested using the [`PhysicsStepper`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhysicsStepUnit {
+    /// Runs the rest of the current physics step, or a whole step if no step is in progress.
+    Step,
+    /// Runs the rest of the current substep, or a whole substep if no substep is in progress.
+    /// The broad phase is run before the first substep of a step, and the rest of the step,
+    /// like contact reporting and sleeping, is run after the last substep.
+    Substep,
+    /// Runs the next [`SubstepSet`] of the current substep.
+    SubstepSet,
+}
+
+/// A resource for step-through debugging of the physics simulation, added by the [`PhysicsStepperPlugin`].
+///
+/// When [`enabled`](Self::enabled) is `true`, the simulation is only advanced when requested using
+/// [`request`](Self::request), which is useful for inspecting the exact substep or [`SubstepSet`]
+/// in which something goes wrong. Each step uses the timestep of the [`Time<Physics>`](Physics) clock.
+///
+/// The stepper only controls the [`PhysicsStepSet`]s and [`SubstepSet`]s, so systems added to
+/// the [`PhysicsSchedule`] or [`SubstepSchedule`] outside of these sets are run on every request.
+///
+/// If the stepper is disabled while a step is in progress, the rest of the step is run
+/// before the simulation continues normally.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn step_on_click(mouse: Res<ButtonInput<MouseButton>>, mut stepper: ResMut<PhysicsStepper>) {
+///     stepper.enabled = true;
+///
+///     if mouse.just_pressed(MouseButton::Left) {
+///         stepper.request(PhysicsStepUnit::Substep);
+///     }
+///     info!(
+///         "substep {}, next set: {:?}",
+///         stepper.current_substep(),
+///         stepper.next_substep_set(),
+///     );
+/// }
+/// ```
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Resource)]
+pub struct PhysicsStepper {
+    /// If `true`, the simulation is only advanced when requested. Defaults to `false`.
+    pub enabled: bool,
+    /// The requested amount to advance the simulation by during the next run of the physics schedule.
+    requested: Option<PhysicsStepUnit>,
+    /// The amount that the simulation is currently being advanced by.
+    running: Option<PhysicsStepUnit>,
+    /// The number of substeps that have been completed in the current step.
+    substep: u32,
+    /// The index of the next [`SubstepSet`] to run in the current substep.
+    set_index: usize,
+    /// The physics clock of the step in progress.
+    #[reflect(ignore)]
+    step_clock: Option<Time<Physics>>,
+}
+
+impl PhysicsStepper {
+    /// Requests the simulation to be advanced by the given `unit` during the next run of the physics schedule.
+    /// Only the latest request is handled.
+    pub fn request(&mut self, unit: PhysicsStepUnit) {
+        self.requested = Some(unit);
+    }
+
+    /// Returns the pending request to advance the simulation, if there is one.
+    pub fn requested(&self) -> Option<PhysicsStepUnit> {
+        self.requested
+    }
+
+    /// Returns `true` if a physics step has been started but not all of its substeps have been run yet.
+    pub fn is_mid_step(&self) -> bool {
+        self.substep > 0 || self.set_index > 0
+    }
+
+    /// Returns the index of the current substep within the step.
+    pub fn current_substep(&self) -> u32 {
+        self.substep
+    }
+
+    /// Returns the [`SubstepSet`] that will be run next.
+    pub fn next_substep_set(&self) -> SubstepSet {
+        SUBSTEP_SETS[self.set_index]
+    }
+
+    /// Returns `true` if the [`SubstepSet`] with the given index should be run.
+    fn runs_substep_set(&self, index: usize) -> bool {
+        match self.running {
+            Some(PhysicsStepUnit::SubstepSet) => index == self.set_index,
+            Some(_) => index >= self.set_index,
+            None => true,
+        }
+    }
+}
+
+/// The key bindings of the [`PhysicsStepperPlugin`].
+#[derive(Clone, Copy)]
+struct StepperKeys {
+    toggle: Option<KeyCode>,
+    step: Option<KeyCode>,
+    substep: Option<KeyCode>,
+    substep_set: Option<KeyCode>,
+}
+
+fn handle_stepper_keys(
+    keys: StepperKeys,
+    mut stepper: ResMut<PhysicsStepper>,
+    input: Option<Res<ButtonInput<KeyCode>>>,
+) {
+    let Some(input) = input else {
+        return;
+    };
+    let just_pressed = |key: Option<KeyCode>| key.is_some_and(|key| input.just_pressed(key));
+
+    if just_pressed(keys.toggle) {
+        stepper.enabled = !stepper.enabled;
+    }
+    if !stepper.enabled {
+        return;
+    }
+    if just_pressed(keys.step) {
+        stepper.request(PhysicsStepUnit::Step);
+    } else if just_pressed(keys.substep) {
+        stepper.request(PhysicsStepUnit::Substep);
+    } else if just_pressed(keys.substep_set) {
+        stepper.request(PhysicsStepUnit::SubstepSet);
+    }
+}
+
+/// Returns `true` if the simulation should be advanced by [`run_stepped_physics_schedule`]
+/// instead of the normal [`PhysicsSchedule`] runner.
+pub(crate) fn is_stepping(world: &World) -> bool {
+    world
+        .get_resource::<PhysicsStepper>()
+        .is_some_and(|stepper| stepper.enabled || stepper.is_mid_step())
+}
+
+/// Returns `true` if the [`PhysicsSchedule`] is being run by [`run_stepped_physics_schedule`],
+/// so the substeps should be run by [`run_stepped_substeps`].
+pub(crate) fn is_running_step(world: &World) -> bool {
+    world
+        .get_resource::<PhysicsStepper>()
+        .is_some_and(|stepper| stepper.running.is_some())
+}
+
+/// Runs the [`PhysicsSchedule`] if the [`PhysicsStepper`] has a pending request.
+///
+/// The step in progress keeps its own clock, so the delta time is the same for all of its substeps
+/// even though the [`Time<Physics>`](Physics) clock doesn't advance between requests.
+pub(crate) fn run_stepped_physics_schedule(world: &mut World) {
+    let mut stepper = world.resource_mut::<PhysicsStepper>();
+
+    // Finish the step in progress if the stepper was disabled.
+    if !stepper.enabled {
+        stepper.request(PhysicsStepUnit::Step);
+    }
+
+    let Some(unit) = stepper.requested.take() else {
+        // Reset delta time to indicate that the simulation isn't advancing.
+        world
+            .resource_mut::<Time<Physics>>()
+            .advance_by(Duration::ZERO);
+        return;
+    };
+    stepper.running = Some(unit);
+    let step_clock = stepper.step_clock;
+
+    let clock = step_clock.unwrap_or_else(|| {
+        let mut clock = *world.resource::<Time<Physics>>();
+        let timestep = match clock.timestep_mode() {
+            TimestepMode::Fixed { delta, .. } | TimestepMode::FixedOnce { delta } => delta,
+            TimestepMode::Variable { max_delta } => max_delta,
+        };
+        clock.advance_by(timestep.mul_f64(clock.relative_speed_f64()));
+        clock
+    });
+
+    let old_clock = world.resource::<Time>().as_generic();
+    *world.resource_mut::<Time<Physics>>() = clock;
+    *world.resource_mut::<Time>() = clock.as_generic();
+
+    let _ = world.try_schedule_scope(PhysicsSchedule, |world, schedule| {
+        trace!("running stepped PhysicsSchedule");
+        schedule.run(world);
+    });
+
+    let mut stepper = world.resource_mut::<PhysicsStepper>();
+    stepper.running = None;
+    stepper.step_clock = stepper.is_mid_step().then_some(clock);
+
+    // Set generic `Time` resource back to the clock that was active before physics.
+    *world.resource_mut::<Time>() = old_clock;
+}
+
+/// Runs the substeps requested from the [`PhysicsStepper`], starting from the substep in progress.
+pub(crate) fn run_stepped_substeps(world: &mut World, substeps: u32) {
+    let stepper = *world.resource::<PhysicsStepper>();
+    let Some(unit) = stepper.running else {
+        return;
+    };
+
+    // Advance the substep clock at the start of the step.
+    if !stepper.is_mid_step() {
+        let delta = world.resource::<Time<Physics>>().delta();
+        let sub_delta = delta.div_f64(substeps as f64);
+        world.resource_mut::<Time<Substeps>>().advance_by(sub_delta);
+    }
+
+    let _ = world.try_schedule_scope(SubstepSchedule, |world, schedule| loop {
+        trace!(
+            "running stepped SubstepSchedule: {}",
+            world.resource::<PhysicsStepper>().substep
+        );
+        *world.resource_mut::<Time>() = world.resource::<Time<Substeps>>().as_generic();
+        schedule.run(world);
+
+        let mut stepper = world.resource_mut::<PhysicsStepper>();
+        if unit == PhysicsStepUnit::SubstepSet && stepper.set_index + 1 < SUBSTEP_SETS.len() {
+            stepper.set_index += 1;
+            break;
+        }
+        stepper.set_index = 0;
+        stepper.substep += 1;
+        if stepper.substep >= substeps {
+            stepper.substep = 0;
+            break;
+        }
+        if unit != PhysicsStepUnit::Step {
+            break;
+        }
+    });
+
+    // Set generic `Time` resource back to `Time<Physics>`.
+    *world.resource_mut::<Time>() = world.resource::<Time<Physics>>().as_generic();
+}
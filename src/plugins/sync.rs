@@ -50,7 +50,9 @@ impl Default for SyncPlugin {
 impl Plugin for SyncPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SyncConfig>()
-            .register_type::<SyncConfig>();
+            .register_type::<SyncConfig>()
+            .init_resource::<SyncTransformEpsilon>()
+            .register_type::<SyncTransformEpsilon>();
 
         app.configure_sets(
             self.schedule,
@@ -121,6 +123,7 @@ impl Plugin for SyncPlugin {
 
 /// Configures what physics data is synchronized by the [`SyncPlugin`] and how.
 #[derive(Resource, Reflect, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Resource)]
 pub struct SyncConfig {
     /// Updates transforms based on [`Position`] and [`Rotation`] changes. Defaults to true.
@@ -139,6 +142,19 @@ impl Default for SyncConfig {
     }
 }
 
+/// The maximum difference between the translation or rotation of a `Transform` and the pose of its body
+/// that is ignored when updating transforms based on [`Position`] and [`Rotation`]. Defaults to 0.0.
+///
+/// Transforms are only written to when the pose has changed by more than this, so that bodies that haven't moved
+/// don't trigger change detection for systems like transform propagation and render extraction.
+/// A small positive value also skips writes for bodies that are only jittering slightly.
+///
+/// This is kept separate from [`SyncConfig`] so that the config can remain `Eq`.
+#[derive(Resource, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct SyncTransformEpsilon(pub f32);
+
 /// System sets for systems running in [`PhysiCsSet::Sync`].
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SyncSet {
@@ -157,6 +173,7 @@ pub enum SyncSet {
 /// The global transform of a body at the end of the previous frame.
 /// Used for detecting if the transform was modified before the start of the physics schedule.
 #[derive(Component, Reflect, Clone, Copy, Debug, Default, Deref, DerefMut, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Component)]
 pub struct PreviousGlobalTransform(pub GlobalTransform);
 
@@ -254,7 +271,13 @@ type PosToTransformComponents = (
     Option<&'static Parent>,
 );
 
-type PosToTransformFilter = (With<RigidBody>, Or<(Changed<Position>, Changed<Rotation>)>);
+// Sleeping bodies aren't moved by the solver, but bodies that fell asleep during this frame may have moved
+// before that, and sleeping bodies can still be teleported by changing their position.
+type PosToTransformFilter = (
+    With<RigidBody>,
+    Or<(Changed<Position>, Changed<Rotation>)>,
+    Or<(Without<Sleeping>, Added<Sleeping>, Changed<Position>)>,
+);
 
 type ParentComponents = (
     &'static GlobalTransform,
@@ -267,10 +290,14 @@ type ParentComponents = (
 ///
 /// Nested rigid bodies move independently of each other, so the `Transform`s of child entities are updated
 /// based on their own and their parent's [`Position`] and [`Rotation`].
+///
+/// `Transform`s are only written to if the pose has changed by more than [`SyncTransformEpsilon`],
+/// and the transforms of [sleeping](Sleeping) bodies are skipped.
 #[cfg(feature = "2d")]
 pub fn position_to_transform(
     mut query: Query<PosToTransformComponents, PosToTransformFilter>,
     parents: Query<ParentComponents, With<Children>>,
+    epsilon: Res<SyncTransformEpsilon>,
 ) {
     for (mut transform, pos, rot, parent) in &mut query {
         if let Some(parent) = parent {
@@ -299,12 +326,21 @@ pub fn position_to_transform(
                 )
                 .reparented_to(&GlobalTransform::from(parent_transform));
 
-                transform.translation = new_transform.translation;
-                transform.rotation = new_transform.rotation;
+                write_transform(
+                    &mut transform,
+                    new_transform.translation,
+                    new_transform.rotation,
+                    epsilon.0,
+                );
             }
         } else {
-            transform.translation = pos.f32().extend(transform.translation.z);
-            transform.rotation = Quaternion::from(*rot).f32();
+            let translation = pos.f32().extend(transform.translation.z);
+            write_transform(
+                &mut transform,
+                translation,
+                Quaternion::from(*rot).f32(),
+                epsilon.0,
+            );
         }
     }
 }
@@ -314,10 +350,14 @@ pub fn position_to_transform(
 ///
 /// Nested rigid bodies move independently of each other, so the `Transform`s of child entities are updated
 /// based on their own and their parent's [`Position`] and [`Rotation`].
+///
+/// `Transform`s are only written to if the pose has changed by more than [`SyncTransformEpsilon`],
+/// and the transforms of [sleeping](Sleeping) bodies are skipped.
 #[cfg(feature = "3d")]
 pub fn position_to_transform(
     mut query: Query<PosToTransformComponents, PosToTransformFilter>,
     parents: Query<ParentComponents, With<Children>>,
+    epsilon: Res<SyncTransformEpsilon>,
 ) {
     for (mut transform, pos, rot, parent) in &mut query {
         if let Some(parent) = parent {
@@ -338,16 +378,35 @@ pub fn position_to_transform(
                 )
                 .reparented_to(&GlobalTransform::from(parent_transform));
 
-                transform.translation = new_transform.translation;
-                transform.rotation = new_transform.rotation;
+                write_transform(
+                    &mut transform,
+                    new_transform.translation,
+                    new_transform.rotation,
+                    epsilon.0,
+                );
             }
         } else {
-            transform.translation = pos.f32();
-            transform.rotation = rot.f32();
+            write_transform(&mut transform, pos.f32(), rot.f32(), epsilon.0);
         }
     }
 }
 
+/// Sets the translation and rotation of the `transform` if either of them differs from the current value
+/// by more than `epsilon`. This avoids triggering change detection for bodies that haven't moved.
+fn write_transform(
+    transform: &mut Mut<Transform>,
+    translation: Vec3,
+    rotation: Quat,
+    epsilon: f32,
+) {
+    if !transform.translation.abs_diff_eq(translation, epsilon)
+        || !transform.rotation.abs_diff_eq(rotation, epsilon)
+    {
+        transform.translation = translation;
+        transform.rotation = rotation;
+    }
+}
+
 /// Updates [`PreviousGlobalTransform`] by setting it to `GlobalTransform` at the very end or start of a frame.
 pub fn update_previous_global_transforms(
     mut bodies: Query<(&GlobalTransform, &mut PreviousGlobalTransform)>,
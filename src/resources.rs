@@ -115,6 +115,9 @@ impl Default for DeactivationTime {
 /// ```
 ///
 /// You can also modify gravity while the app is running.
+///
+/// For gravity that depends on the position of the body, like planetary gravity, see [`GravityField`].
+/// To override the gravity inside specific areas, see [`GravityVolume`].
 #[derive(Reflect, Resource, Debug)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Resource)]
@@ -130,3 +133,87 @@ impl Gravity {
     /// Zero gravity.
     pub const ZERO: Gravity = Gravity(Vector::ZERO);
 }
+
+/// The global wind in the simulation.
+///
+/// Wind pushes dynamic [rigid bodies](RigidBody) that have a [`Drag`] component,
+/// because their drag is computed using their velocity relative to the wind. The force is therefore proportional
+/// to the [cross-sectional area](Drag::cross_section_area) of the body, and a body blown by steady wind approaches
+/// the velocity of the wind. Bodies without [`Drag`] are not affected.
+///
+/// The [`turbulence`](Self::turbulence) adds gusts that vary smoothly over time and space.
+/// Local wind inside specific areas can be added using [wind zones](WindZone).
+///
+/// There is no wind by default.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::prelude::*;
+#[cfg_attr(feature = "2d", doc = "use bevy_xpbd_2d::prelude::*;")]
+#[cfg_attr(feature = "3d", doc = "use bevy_xpbd_3d::prelude::*;")]
+///
+/// # #[cfg(feature = "f32")]
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+#[cfg_attr(
+    feature = "2d",
+    doc = "         .insert_resource(Wind::new(Vec2::X * 5.0).with_turbulence(2.0, 0.5))"
+)]
+#[cfg_attr(
+    feature = "3d",
+    doc = "         .insert_resource(Wind::new(Vec3::X * 5.0).with_turbulence(2.0, 0.5))"
+)]
+///         .run();
+/// }
+/// # #[cfg(not(feature = "f32"))]
+/// # fn main() {} // Doc test needs main
+/// ```
+#[derive(Reflect, Resource, Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct Wind {
+    /// The average velocity of the wind.
+    pub velocity: Vector,
+    /// The maximum speed of the gusts added to the [`velocity`](Self::velocity).
+    pub turbulence: Scalar,
+    /// How many times per second the gusts change on average.
+    pub turbulence_frequency: Scalar,
+}
+
+impl Wind {
+    /// No wind.
+    pub const ZERO: Wind = Wind {
+        velocity: Vector::ZERO,
+        turbulence: 0.0,
+        turbulence_frequency: 0.0,
+    };
+
+    /// Creates a new wind with the given velocity and no turbulence.
+    pub fn new(velocity: Vector) -> Self {
+        Self {
+            velocity,
+            ..Self::ZERO
+        }
+    }
+
+    /// Sets the maximum speed of the gusts and how many times per second they change on average.
+    pub fn with_turbulence(mut self, turbulence: Scalar, frequency: Scalar) -> Self {
+        self.turbulence = turbulence;
+        self.turbulence_frequency = frequency;
+        self
+    }
+
+    /// Returns the velocity of the wind at the given `point` at the given elapsed time in seconds,
+    /// including turbulence.
+    pub fn velocity_at(&self, point: Vector, elapsed_seconds: Scalar) -> Vector {
+        self.velocity
+            + wind_turbulence(
+                point,
+                elapsed_seconds,
+                self.turbulence,
+                self.turbulence_frequency,
+            )
+    }
+}
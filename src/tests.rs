@@ -29,7 +29,12 @@ macro_rules! setup_insta {
 
 fn create_app() -> App {
     let mut app = App::new();
-    app.add_plugins((MinimalPlugins, TransformPlugin, PhysicsPlugins::default()));
+    app.add_plugins((
+        MinimalPlugins,
+        TransformPlugin,
+        PhysicsPlugins::default(),
+        PhysicsExtrasPlugins::default(),
+    ));
     #[cfg(feature = "async-collider")]
     {
         app.add_plugins((
@@ -183,92 +188,5999 @@ fn body_with_velocity_moves() {
     }
 }
 
-#[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
-struct Id(usize);
+#[test]
+fn solver_bodies_write_back_only_changed_data() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+    app.finish();
+
+    let moving = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X),
+            MassPropertiesBundle {
+                mass: Mass(1.0),
+                inverse_mass: InverseMass(1.0),
+                ..default()
+            },
+        ))
+        .id();
+    let sleeping = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            MassPropertiesBundle {
+                mass: Mass(1.0),
+                inverse_mass: InverseMass(1.0),
+                ..default()
+            },
+        ))
+        .id();
+
+    // Let the body at rest fall asleep
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(sleeping).is_some());
+
+    let pre_solve_tick = |app: &App, entity: Entity| {
+        app.world
+            .entity(entity)
+            .get_change_ticks::<PreSolveLinearVelocity>()
+            .unwrap()
+            .last_changed_tick()
+    };
+    let moving_tick = pre_solve_tick(&app, moving);
+    let sleeping_tick = pre_solve_tick(&app, sleeping);
+
+    tick_60_fps(&mut app);
+
+    // Both bodies are gathered into their own slots
+    let solver_bodies = app.world.resource::<SolverBodies>();
+    assert_eq!(solver_bodies.len(), 2);
+    assert_ne!(solver_bodies.slot(moving), solver_bodies.slot(sleeping));
+    assert!(solver_bodies.state(sleeping).unwrap().is_sleeping);
+
+    // The velocities of the sleeping body are not updated, so they are not written back to it
+    assert_ne!(pre_solve_tick(&app, moving), moving_tick);
+    assert_eq!(pre_solve_tick(&app, sleeping), sleeping_tick);
+}
 
-#[cfg(all(feature = "3d", feature = "enhanced-determinism"))]
 #[test]
-fn cubes_simulation_is_deterministic_across_machines() {
-    setup_insta!();
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn constant_forces_are_applied_every_step() {
     let mut app = create_app();
 
-    app.add_systems(Startup, setup_cubes_simulation);
+    app.insert_resource(Gravity::ZERO);
 
-    const SECONDS: usize = 10;
-    const UPDATES: usize = 60 * SECONDS;
+    app.add_systems(Startup, |mut commands: Commands| {
+        #[cfg(feature = "2d")]
+        let mass_properties = MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0);
+        #[cfg(feature = "3d")]
+        let mass_properties = MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0);
+
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            ConstantForce(Vector::X),
+            mass_properties.clone(),
+        ));
+        // Rotated by 90 degrees, so the local X axis points along the world Y axis
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Rotation::from_degrees(90.0),
+            #[cfg(feature = "3d")]
+            Rotation(Quaternion::from_rotation_z(PI / 2.0)),
+            ConstantLocalForce(Vector::X),
+            mass_properties,
+        ));
+    });
+
+    const UPDATES: usize = 60;
 
     for _ in 0..UPDATES {
         tick_60_fps(&mut app);
     }
 
-    let mut app_query = app.world.query::<(&Id, &Transform)>();
+    // The forces are never cleared, so the bodies keep accelerating
+    let elapsed_secs = app
+        .world
+        .resource::<Time<Physics>>()
+        .elapsed_seconds_f64()
+        .adjust_precision();
+    let mut query = app.world.query::<(
+        &LinearVelocity,
+        &Mass,
+        Option<&ConstantForce>,
+        Option<&ConstantLocalForce>,
+    )>();
+    for (lin_vel, mass, constant_force, constant_local_force) in query.iter(&app.world) {
+        let expected_speed = elapsed_secs / mass.0;
+        let (along, across) = match (constant_force, constant_local_force) {
+            (Some(_), _) => (lin_vel.x, lin_vel.y),
+            (_, Some(_)) => (lin_vel.y, lin_vel.x),
+            _ => unreachable!(),
+        };
+        assert_relative_eq!(along, expected_speed, epsilon = 0.01);
+        assert_relative_eq!(across, 0.0, epsilon = 0.0001);
+    }
+}
 
-    let mut bodies: Vec<(&Id, &Transform)> = app_query.iter(&app.world).collect();
-    bodies.sort_by_key(|b| b.0);
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn queued_impulses_are_applied_at_their_substeps() {
+    let mut app = create_app();
 
-    assert_debug_snapshot!(bodies);
+    app.insert_resource(Gravity::ZERO);
+    app.insert_resource(SubstepCount(8));
+
+    let spawn_body = |app: &mut App| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id()
+    };
+    let early = spawn_body(&mut app);
+    let late = spawn_body(&mut app);
+
+    tick_60_fps(&mut app);
+
+    let mut queue = app.world.resource_mut::<QueuedImpulses>();
+    for (entity, substep) in [(early, 0), (late, 7)] {
+        queue.push(QueuedImpulse {
+            entity,
+            impulse: Vector::X,
+            point: None,
+            angular_impulse: default(),
+            substep,
+        });
+    }
+
+    // Tick until the queued impulses have been applied
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+        if app.world.resource::<QueuedImpulses>().is_empty() {
+            break;
+        }
+    }
+    assert!(app.world.resource::<QueuedImpulses>().is_empty());
+
+    let mass = app.world.get::<Mass>(early).unwrap().0;
+    for entity in [early, late] {
+        let lin_vel = app.world.get::<LinearVelocity>(entity).unwrap();
+        assert_relative_eq!(lin_vel.x, 1.0 / mass, epsilon = 0.0001);
+    }
+
+    // The body that received the impulse in an earlier substep has moved further
+    let early_x = app.world.get::<Position>(early).unwrap().x;
+    let late_x = app.world.get::<Position>(late).unwrap().x;
+    assert!(early_x > late_x);
 }
 
-#[cfg(all(feature = "3d", feature = "default-collider"))]
 #[test]
-fn cubes_simulation_is_locally_deterministic() {
-    use itertools::Itertools;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn drag_limits_speed_to_terminal_velocity() {
+    let mut app = create_app();
 
-    fn run_cubes() -> Vec<(Id, Transform)> {
-        let mut app = create_app();
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
 
-        app.add_systems(Startup, setup_cubes_simulation);
+    let drag = Drag::new(0.0, 0.5).with_angular(1.0);
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            #[cfg(feature = "2d")]
+            AngularVelocity(5.0),
+            #[cfg(feature = "3d")]
+            AngularVelocity(Vector::Z * 5.0),
+            drag,
+        ))
+        .id();
 
-        const SECONDS: usize = 5;
-        const UPDATES: usize = 60 * SECONDS;
+    for _ in 0..600 {
+        tick_60_fps(&mut app);
+    }
 
-        for _ in 0..UPDATES {
-            tick_60_fps(&mut app);
-        }
+    // The drag force cancels out gravity at the terminal velocity
+    let mass = app.world.get::<Mass>(body).unwrap().0;
+    let terminal_velocity = (mass * 10.0 / drag.quadratic_coefficient).sqrt();
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.y, -terminal_velocity, epsilon = 0.01);
 
-        let mut app_query = app.world.query::<(&Id, &Transform)>();
+    // Angular drag slows down the rotation without reversing it
+    let ang_vel = app.world.get::<AngularVelocity>(body).unwrap();
+    #[cfg(feature = "2d")]
+    assert!(ang_vel.0 >= 0.0 && ang_vel.0 < 5.0);
+    #[cfg(feature = "3d")]
+    assert!(ang_vel.0.z >= 0.0 && ang_vel.0.z < 5.0);
+}
 
-        let mut bodies: Vec<(Id, Transform)> = app_query
-            .iter(&app.world)
-            .map(|(id, transform)| (*id, *transform))
-            .collect();
-        bodies.sort_by_key(|b| b.0);
-        bodies
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn wind_pushes_bodies_with_drag() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO)
+        .insert_resource(Wind::new(Vector::X * 5.0));
+
+    // A large wind zone that adds upwards wind
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(200.0, 200.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(200.0, 200.0, 200.0),
+        Sensor,
+        WindZone::new(Vector::Y * 3.0),
+    ));
+
+    let spawn_body = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Drag::new(1.0, 0.0),
+            ))
+            .id()
+    };
+
+    let outside_zone = spawn_body(&mut app, Vector::Y * 300.0);
+    let inside_zone = spawn_body(&mut app, Vector::ZERO);
+
+    for _ in 0..600 {
+        tick_60_fps(&mut app);
     }
 
-    // run simulation and check that results are equal each time
-    for (a, b) in (0..4).map(|_| run_cubes()).tuple_windows() {
-        assert_eq!(a, b);
+    // The bodies approach the velocity of the wind
+    let lin_vel = app.world.get::<LinearVelocity>(outside_zone).unwrap();
+    assert_relative_eq!(lin_vel.x, 5.0, epsilon = 0.01);
+    assert_relative_eq!(lin_vel.y, 0.0, epsilon = 0.01);
+
+    let lin_vel = app.world.get::<LinearVelocity>(inside_zone).unwrap();
+    assert_relative_eq!(lin_vel.x, 5.0, epsilon = 0.01);
+    assert_relative_eq!(lin_vel.y, 3.0, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn explosions_push_nearby_unoccluded_bodies() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_body = |app: &mut App, rb: RigidBody, position: Vector, collider: Collider| {
+        app.world
+            .spawn((SpatialBundle::default(), rb, Position(position), collider))
+            .id()
+    };
+    #[cfg(feature = "2d")]
+    let (ball, wall) = (Collider::circle(0.5), Collider::rectangle(0.2, 4.0));
+    #[cfg(feature = "3d")]
+    let (ball, wall) = (Collider::sphere(0.5), Collider::cuboid(0.2, 4.0, 4.0));
+
+    let near = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 2.0, ball.clone());
+    let far = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * 10.0, ball.clone());
+    let occluded = spawn_body(&mut app, RigidBody::Dynamic, Vector::X * -3.0, ball);
+    spawn_body(&mut app, RigidBody::Static, Vector::X * -1.5, wall);
+
+    // Update the spatial query pipeline
+    tick_60_fps(&mut app);
+
+    let explosion =
+        Explosion::new(Vector::ZERO, 5.0, 10.0, ExplosionFalloff::Linear).with_occlusion();
+    app.world
+        .resource_mut::<QueuedExplosions>()
+        .0
+        .push(explosion);
+    tick_60_fps(&mut app);
+
+    // The closest point of the near body is 1.5 units away from the center
+    let mass = app.world.get::<Mass>(near).unwrap().0;
+    let lin_vel = app.world.get::<LinearVelocity>(near).unwrap();
+    assert_relative_eq!(lin_vel.x, 10.0 * (1.0 - 1.5 / 5.0) / mass, epsilon = 0.001);
+
+    // Bodies outside of the radius or behind static geometry are not affected
+    for entity in [far, occluded] {
+        let lin_vel = app.world.get::<LinearVelocity>(entity).unwrap();
+        assert_eq!(lin_vel.0, Vector::ZERO);
     }
 }
 
 #[test]
-fn no_ambiguity_errors() {
-    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
-    struct DeterministicSchedule;
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn force_fields_apply_forces_to_bodies() {
+    let mut app = create_app();
 
-    let mut app = App::new();
+    app.insert_resource(Gravity::ZERO);
 
-    app.add_plugins((MinimalPlugins, PhysicsPlugins::new(DeterministicSchedule)));
+    // A global field with a constant acceleration along the X axis
+    app.world
+        .spawn(ForceField::global(|_, _, body| Vector::X * body.mass));
 
-    #[cfg(feature = "async-collider")]
-    {
-        app.add_plugins((
-            bevy::asset::AssetPlugin::default(),
-            bevy::scene::ScenePlugin,
+    // A volume field that also accelerates bodies upwards
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(10.0, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(10.0, 10.0, 10.0),
+        Sensor,
+        ForceField::volume(|_, _, body| Vector::Y * 2.0 * body.mass),
+    ));
+
+    let spawn_body = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ))
+            .id()
+    };
+
+    let outside_volume = spawn_body(&mut app, Vector::Y * 100.0);
+    let inside_volume = spawn_body(&mut app, Vector::ZERO);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let lin_vel = app.world.get::<LinearVelocity>(outside_volume).unwrap();
+    assert_relative_eq!(lin_vel.x, 1.0, epsilon = 0.01);
+    assert_relative_eq!(lin_vel.y, 0.0, epsilon = 0.01);
+
+    // The body enters the volume at the end of the first step
+    let lin_vel = app.world.get::<LinearVelocity>(inside_volume).unwrap();
+    assert_relative_eq!(lin_vel.x, 1.0, epsilon = 0.01);
+    assert_relative_eq!(lin_vel.y, 2.0, epsilon = 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn magnets_attract_and_repel_each_other() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_magnet = |app: &mut App, position: Vector, magnet: Magnet| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                magnet,
+            ))
+            .id()
+    };
+
+    // Magnets with the same sign attract each other
+    let attract1 = spawn_magnet(&mut app, Vector::NEG_X * 2.0, Magnet::new(1.0, 10.0));
+    let attract2 = spawn_magnet(&mut app, Vector::X * 2.0, Magnet::new(1.0, 10.0));
+
+    // Magnets with opposite signs repel each other
+    let repel1 = spawn_magnet(
+        &mut app,
+        Vector::Y * 50.0 + Vector::NEG_X * 2.0,
+        Magnet::new(1.0, 10.0),
+    );
+    let repel2 = spawn_magnet(
+        &mut app,
+        Vector::Y * 50.0 + Vector::X * 2.0,
+        Magnet::new(-1.0, 10.0),
+    );
+
+    // Magnets with layers that don't interact ignore each other
+    let ignored1 = spawn_magnet(
+        &mut app,
+        Vector::NEG_Y * 50.0 + Vector::NEG_X * 2.0,
+        Magnet::new(1.0, 10.0).with_layers(CollisionLayers::new(0b01, 0b01)),
+    );
+    let ignored2 = spawn_magnet(
+        &mut app,
+        Vector::NEG_Y * 50.0 + Vector::X * 2.0,
+        Magnet::new(1.0, 10.0).with_layers(CollisionLayers::new(0b10, 0b10)),
+    );
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let lin_vel = |app: &App, entity: Entity| app.world.get::<LinearVelocity>(entity).unwrap().0;
+
+    assert!(lin_vel(&app, attract1).x > 0.0);
+    assert_relative_eq!(
+        lin_vel(&app, attract1).x,
+        -lin_vel(&app, attract2).x,
+        epsilon = 0.0001
+    );
+
+    assert!(lin_vel(&app, repel1).x < 0.0);
+    assert!(lin_vel(&app, repel2).x > 0.0);
+
+    assert_eq!(lin_vel(&app, ignored1), Vector::ZERO);
+    assert_eq!(lin_vel(&app, ignored2), Vector::ZERO);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn hover_settles_at_target_height() {
+    let mut app = create_app();
+
+    // The top of the ground is at y = 0.5
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(100.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(100.0, 1.0, 100.0),
+    ));
+
+    #[cfg(feature = "2d")]
+    let points = vec![Vector::new(-0.4, 0.0), Vector::new(0.4, 0.0)];
+    #[cfg(feature = "3d")]
+    let points = vec![
+        Vector::new(-0.4, 0.0, -0.4),
+        Vector::new(0.4, 0.0, -0.4),
+        Vector::new(-0.4, 0.0, 0.4),
+        Vector::new(0.4, 0.0, 0.4),
+    ];
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y * 2.0),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 0.5),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 0.5, 1.0),
+            Hover::new(
+                #[cfg(feature = "2d")]
+                Collider::circle(0.1),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.1),
+                points,
+                1.0,
+            )
+            .with_gains(100.0, 100.0, 15.0),
         ))
-        .init_resource::<Assets<Mesh>>();
+        .id();
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
     }
 
-    app.edit_schedule(DeterministicSchedule, |s| {
-        s.set_build_settings(ScheduleBuildSettings {
-            ambiguity_detection: LogLevel::Error,
-            ..default()
-        });
-    })
-    .add_systems(Update, |w: &mut World| {
-        w.run_schedule(DeterministicSchedule);
-    });
+    // The cast shapes have a radius of 0.1, so the body hovers at 0.5 + 0.1 + 1.0
+    let position = app.world.get::<Position>(body).unwrap();
+    assert_relative_eq!(position.y, 1.6, epsilon = 0.02);
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert!(lin_vel.length() < 0.05);
+    assert!(app.world.get::<Hover>(body).unwrap().is_grounded());
+}
 
-    app.update();
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn projectile_does_not_tunnel_through_thin_walls() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    // A thin wall at x = 5
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::X * 5.0),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(0.1, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(0.1, 10.0, 10.0),
+    ));
+
+    // Add the wall to the spatial query pipeline used for the sweeps
+    tick_60_fps(&mut app);
+
+    // The bullet moves 10 meters per step, much further than its own size and the thickness of the wall
+    let bullet = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            ProjectileBundle::new(
+                #[cfg(feature = "2d")]
+                Collider::circle(0.05),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.05),
+                Vector::ZERO,
+                Vector::X * 600.0,
+            ),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world.get::<Position>(bullet).unwrap();
+    assert!(position.x < 5.0);
+    let lin_vel = app.world.get::<LinearVelocity>(bullet).unwrap();
+    assert!(lin_vel.x <= 0.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn predicted_trajectory_matches_simulation() {
+    use bevy::ecs::system::SystemState;
+
+    let mut app = create_app();
+
+    app.insert_resource(Time::new_with(Physics::fixed_once_hz(60.0)))
+        .insert_resource(Wind::new(Vector::NEG_X * 3.0));
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            ProjectileBundle::new(
+                #[cfg(feature = "2d")]
+                Collider::circle(0.1),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.1),
+                Vector::ZERO,
+                Vector::X * 10.0 + Vector::Y * 10.0,
+            ),
+            Drag::new(0.1, 0.05),
+            LinearDamping(0.2),
+        ))
+        .id();
+
+    // Initialize the body
+    tick_60_fps(&mut app);
+
+    let mut system_state = SystemState::<Ballistics>::new(&mut app.world);
+    let trajectory = system_state
+        .get(&app.world)
+        .body_trajectory(body, 1.0)
+        .unwrap();
+    assert!(trajectory.len() > 60);
+
+    for point in trajectory.iter().skip(1).take(60) {
+        tick_60_fps(&mut app);
+
+        let position = app.world.get::<Position>(body).unwrap();
+        assert_relative_eq!(position.x, point.position.x, epsilon = 0.001);
+        assert_relative_eq!(position.y, point.position.y, epsilon = 0.001);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn surface_velocity_moves_bodies_like_a_conveyor_belt() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(20.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(20.0, 1.0, 20.0),
+        SurfaceVelocity::linear(Vector::X * 2.0),
+    ));
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The body is carried along with the surface of the conveyor belt
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 2.0, epsilon = 0.1);
+    assert!(app.world.get::<Position>(body).unwrap().x > 1.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn anisotropic_friction_depends_on_sliding_direction() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(100.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(100.0, 1.0, 100.0),
+        Friction::new(1.0),
+    ));
+
+    let spawn_body = |app: &mut App, position: Vector, axis: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                LinearVelocity(Vector::X * 5.0),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                AnisotropicFriction::new(
+                    axis,
+                    Friction::ZERO.with_combine_rule(CoefficientCombine::Multiply),
+                    1.0,
+                ),
+            ))
+            .id()
+    };
+
+    // Both bodies slide along the X axis
+    let sliding_along = spawn_body(&mut app, Vector::Y, Vector::X);
+    let sliding_across = spawn_body(&mut app, Vector::Y + Vector::NEG_X * 10.0, Vector::Y);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The body sliding along the axis is not slowed down, while the other one stops
+    let lin_vel = app.world.get::<LinearVelocity>(sliding_along).unwrap();
+    assert_relative_eq!(lin_vel.x, 5.0, epsilon = 0.01);
+    let lin_vel = app.world.get::<LinearVelocity>(sliding_across).unwrap();
+    assert_relative_eq!(lin_vel.x, 0.0, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn tire_friction_loses_grip_when_wheels_lock() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(100.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(100.0, 1.0, 100.0),
+        Friction::new(1.0),
+    ));
+
+    let spawn_wheel = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                LinearVelocity(Vector::X * 5.0),
+                // Locked wheels, like when braking hard
+                LockedAxes::ROTATION_LOCKED,
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                Friction::new(1.0),
+            ))
+            .id()
+    };
+
+    let plain = spawn_wheel(&mut app, Vector::Y);
+    let tire = spawn_wheel(&mut app, Vector::Y + Vector::NEG_X * 10.0);
+    #[cfg(feature = "2d")]
+    app.world.entity_mut(tire).insert(TireFriction::default());
+    #[cfg(feature = "3d")]
+    app.world
+        .entity_mut(tire)
+        .insert(TireFriction::new(Vector::Z));
+
+    for _ in 0..42 {
+        tick_60_fps(&mut app);
+    }
+
+    // A skidding tire has less grip than at its peak, so it slides further than with plain friction
+    let plain_vel = app.world.get::<LinearVelocity>(plain).unwrap().x;
+    let tire_vel = app.world.get::<LinearVelocity>(tire).unwrap().x;
+    assert!(plain_vel < 0.5);
+    assert!(tire_vel > plain_vel + 0.5);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn sliding_tire_reports_positive_tangent_impulses() {
+    let mut app = create_app();
+
+    let ground = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(100.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(100.0, 1.0, 100.0),
+            Friction::new(1.0),
+        ))
+        .id();
+
+    // A locked wheel skidding over the ground
+    let wheel = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y),
+            LinearVelocity(Vector::X * 5.0),
+            LockedAxes::ROTATION_LOCKED,
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+            Friction::new(1.0),
+            #[cfg(feature = "2d")]
+            TireFriction::default(),
+            #[cfg(feature = "3d")]
+            TireFriction::new(Vector::Z),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The tire model slows the wheel down, and the friction impulses are reported like regular friction
+    assert!(app.world.get::<LinearVelocity>(wheel).unwrap().x < 5.0);
+    let contacts = app
+        .world
+        .resource::<Collisions>()
+        .get(ground, wheel)
+        .expect("the wheel should be touching the ground");
+    assert!(contacts.total_tangent_impulse > 0.0);
+    for contact in contacts.manifolds.iter().flat_map(|m| m.contacts.iter()) {
+        assert!(contact.tangent_impulse > 0.0);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "physics-material",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn physics_materials_are_shared_and_hot_edited() {
+    let mut app = create_app();
+
+    let material = app
+        .world
+        .resource_mut::<Assets<PhysicsMaterial>>()
+        .add(PhysicsMaterial::new(0.5, 0.2).with_density(3.0));
+
+    let colliders = [(); 3].map(|_| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                material.clone(),
+            ))
+            .id()
+    });
+
+    tick_60_fps(&mut app);
+
+    for entity in colliders {
+        assert_eq!(app.world.get::<Friction>(entity), Some(&Friction::new(0.5)));
+        assert_eq!(
+            app.world.get::<Restitution>(entity),
+            Some(&Restitution::new(0.2))
+        );
+        assert_eq!(
+            app.world.get::<ColliderDensity>(entity),
+            Some(&ColliderDensity(3.0))
+        );
+    }
+
+    // Editing the material updates all colliders using it
+    app.world
+        .resource_mut::<Assets<PhysicsMaterial>>()
+        .get_mut(&material)
+        .unwrap()
+        .friction = Friction::new(0.1);
+
+    tick_60_fps(&mut app);
+
+    for entity in colliders {
+        assert_eq!(app.world.get::<Friction>(entity), Some(&Friction::new(0.1)));
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn custom_coefficient_combine_rules() {
+    // Removes friction between colliders with the given materials
+    struct Frictionless(MaterialId, MaterialId);
+
+    impl CombineCoefficients for Frictionless {
+        fn combine_friction(
+            &self,
+            pair: &CoefficientPair,
+            friction1: Friction,
+            friction2: Friction,
+        ) -> Friction {
+            match pair.materials() {
+                Some((a, b)) if (a, b) == (self.0, self.1) || (b, a) == (self.0, self.1) => {
+                    Friction::ZERO
+                }
+                _ => friction1.combine(friction2),
+            }
+        }
+    }
+
+    let mut app = create_app();
+
+    app.insert_resource(CoefficientCombiner::new(Frictionless(
+        MaterialId(0),
+        MaterialId(1),
+    )));
+
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        #[cfg(feature = "2d")]
+        Collider::rectangle(100.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(100.0, 1.0, 100.0),
+        MaterialId(0),
+    ));
+
+    let spawn_body = |app: &mut App, position: Vector, material: MaterialId| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                LinearVelocity(Vector::X * 5.0),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Friction::new(1.0),
+                material,
+            ))
+            .id()
+    };
+
+    let frictionless = spawn_body(&mut app, Vector::Y, MaterialId(1));
+    let default_friction = spawn_body(&mut app, Vector::Y + Vector::NEG_X * 10.0, MaterialId(2));
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let lin_vel = app.world.get::<LinearVelocity>(frictionless).unwrap();
+    assert_relative_eq!(lin_vel.x, 5.0, epsilon = 0.01);
+    let lin_vel = app.world.get::<LinearVelocity>(default_friction).unwrap();
+    assert!(lin_vel.x < 4.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn kinematic_targets_move_bodies_with_velocity() {
+    let mut app = create_app();
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Kinematic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            KinematicTarget::new(Vector::X),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    // The body should reach the target in one step with a velocity instead of teleporting
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 60.0, epsilon = 0.01);
+    let pos = app.world.get::<Position>(body).unwrap();
+    assert_relative_eq!(pos.x, 1.0, epsilon = 0.001);
+
+    tick_60_fps(&mut app);
+
+    // The body should stop once it has reached the target
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 0.0, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn locked_axes_in_local_frame() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    // Rotated by 90 degrees, so the local X axis points along the world Y axis
+    #[cfg(feature = "2d")]
+    let rotation = Rotation::from_degrees(90.0);
+    #[cfg(feature = "3d")]
+    let rotation = Rotation(Quaternion::from_rotation_z(PI / 2.0));
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            rotation,
+            LinearVelocity(Vector::X * 2.0 + Vector::Y * 3.0),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+            LockedAxes::new()
+                .lock_translation_x()
+                .with_frame(LockedAxesFrame::Local),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // Only the movement along the world X axis, which is the local Y axis, should remain
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap();
+    assert_relative_eq!(lin_vel.x, 2.0, epsilon = 0.001);
+    assert_relative_eq!(lin_vel.y, 0.0, epsilon = 0.001);
+    let pos = app.world.get::<Position>(body).unwrap();
+    assert_relative_eq!(pos.x, 2.0, epsilon = 0.01);
+    assert_relative_eq!(pos.y, 0.0, epsilon = 0.01);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn mass_properties_follow_moved_colliders() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_body = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ))
+            .id()
+    };
+
+    let body1 = spawn_body(&mut app, Vector::ZERO);
+    let body2 = spawn_body(&mut app, Vector::X * 5.0);
+
+    let child = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .set_parent(body1)
+        .id();
+
+    tick_60_fps(&mut app);
+
+    assert_relative_eq!(app.world.get::<Mass>(body1).unwrap().0, 2.0, epsilon = 1e-3);
+    assert_relative_eq!(app.world.get::<Mass>(body2).unwrap().0, 1.0, epsilon = 1e-3);
+
+    // Move the collider to a sleeping body
+    app.world.entity_mut(body2).insert(Sleeping);
+    app.world.entity_mut(child).set_parent(body2);
+
+    tick_60_fps(&mut app);
+
+    assert_relative_eq!(app.world.get::<Mass>(body1).unwrap().0, 1.0, epsilon = 1e-3);
+    assert_relative_eq!(app.world.get::<Mass>(body2).unwrap().0, 2.0, epsilon = 1e-3);
+    assert!(!app.world.entity(body2).contains::<Sleeping>());
+
+    // Recomputing the mass properties shouldn't change them
+    app.world.entity_mut(body2).insert(RecomputeMassProperties);
+
+    tick_60_fps(&mut app);
+
+    assert_relative_eq!(app.world.get::<Mass>(body2).unwrap().0, 2.0, epsilon = 1e-3);
+    assert!(!app
+        .world
+        .entity(body2)
+        .contains::<RecomputeMassProperties>());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn center_of_mass_can_be_changed_at_runtime() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            (Collider::rectangle(1.0, 1.0), AngularVelocity(1.0)),
+            #[cfg(feature = "3d")]
+            (Collider::cuboid(1.0, 1.0, 1.0), AngularVelocity(Vector::Z)),
+        ))
+        .id();
+
+    // Let the mass properties of the body be computed from its collider
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+
+    // Shift the center of mass of the spinning body
+    let offset = Vector::X * 0.5;
+    app.world.get_mut::<CenterOfMass>(body).unwrap().0 = offset;
+
+    tick_60_fps(&mut app);
+
+    // The new center of mass should move with the velocity of the body at that point,
+    // so that the body doesn't jump
+    let rot = *app.world.get::<Rotation>(body).unwrap();
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap().0;
+    #[cfg(feature = "2d")]
+    let expected_lin_vel = rot.rotate(offset).perp();
+    #[cfg(feature = "3d")]
+    let expected_lin_vel = Vector::Z.cross(rot.rotate(offset));
+    assert_relative_eq!(lin_vel.x, expected_lin_vel.x, epsilon = 0.02);
+    assert_relative_eq!(lin_vel.y, expected_lin_vel.y, epsilon = 0.02);
+
+    let pos = app.world.get::<Position>(body).unwrap();
+    assert!(pos.length() < 0.02);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn dominance_overrides_take_precedence_over_dominance() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_body = |app: &mut App, position: Vector, velocity: Vector, dominance: i8| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                LinearVelocity(velocity),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                Dominance(dominance),
+            ))
+            .id()
+    };
+
+    // The first body has a higher dominance, but the override makes the second body dominate it
+    let body1 = spawn_body(&mut app, Vector::NEG_X * 2.0, Vector::X * 5.0, 5);
+    let body2 = spawn_body(&mut app, Vector::ZERO, Vector::ZERO, 0);
+
+    app.world
+        .resource_mut::<DominanceOverrides>()
+        .set_dominant(body2, body1);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The dominant body shouldn't be pushed
+    let lin_vel = app.world.get::<LinearVelocity>(body2).unwrap();
+    assert_relative_eq!(lin_vel.x, 0.0, epsilon = 0.001);
+    let pos = app.world.get::<Position>(body2).unwrap();
+    assert_relative_eq!(pos.x, 0.0, epsilon = 0.001);
+
+    // The other body should be stopped
+    let lin_vel = app.world.get::<LinearVelocity>(body1).unwrap();
+    assert!(lin_vel.x < 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn disabled_bodies_and_colliders_are_not_simulated() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_body = |app: &mut App, position: Vector, velocity: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                LinearVelocity(velocity),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ))
+            .id()
+    };
+
+    let parked = spawn_body(&mut app, Vector::Y * 10.0, Vector::X * 5.0);
+    let reset = spawn_body(&mut app, Vector::Y * 20.0, Vector::X * 5.0);
+    app.world
+        .entity_mut(parked)
+        .insert(RigidBodyDisabled::KEEP_VELOCITY);
+    app.world
+        .entity_mut(reset)
+        .insert(RigidBodyDisabled::RESET_VELOCITY);
+
+    // A body moving through a wall with a disabled collider
+    let body = spawn_body(&mut app, Vector::ZERO, Vector::X * 5.0);
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::X * 2.0),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(1.0, 10.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(1.0, 10.0, 10.0),
+        ColliderDisabled,
+    ));
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // Disabled bodies shouldn't move, and only the reset body should lose its velocity
+    assert_eq!(
+        app.world.get::<Position>(parked).unwrap().0,
+        Vector::Y * 10.0
+    );
+    assert_eq!(
+        app.world.get::<Position>(reset).unwrap().0,
+        Vector::Y * 20.0
+    );
+    assert_eq!(
+        app.world.get::<LinearVelocity>(parked).unwrap().0,
+        Vector::X * 5.0
+    );
+    assert_eq!(
+        app.world.get::<LinearVelocity>(reset).unwrap().0,
+        Vector::ZERO
+    );
+
+    // The body should pass through the disabled collider
+    assert_relative_eq!(
+        app.world.get::<Position>(body).unwrap().x,
+        5.0,
+        epsilon = 0.01
+    );
+
+    // Enabling the body again should continue its motion
+    app.world.entity_mut(parked).remove::<RigidBodyDisabled>();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_relative_eq!(
+        app.world.get::<Position>(parked).unwrap().x,
+        5.0,
+        epsilon = 0.01
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn trimesh_mass_properties_match_primitive_shapes() {
+    // A box with half-extents of 1.0, 2.0 and 3.0 that is offset from the origin
+    let offset = Vector::X * 2.0;
+
+    #[cfg(feature = "2d")]
+    let (vertices, indices, primitive) = (
+        vec![
+            Vector::new(-1.0, -2.0),
+            Vector::new(1.0, -2.0),
+            Vector::new(1.0, 2.0),
+            Vector::new(-1.0, 2.0),
+        ],
+        vec![[0, 1, 2], [0, 2, 3]],
+        Collider::rectangle(2.0, 4.0),
+    );
+    #[cfg(feature = "3d")]
+    let (vertices, indices, primitive) = (
+        (0..8)
+            .map(|i| {
+                Vector::new(
+                    if i & 1 == 0 { -1.0 } else { 1.0 },
+                    if i & 2 == 0 { -2.0 } else { 2.0 },
+                    if i & 4 == 0 { -3.0 } else { 3.0 },
+                )
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            [0, 2, 1],
+            [1, 2, 3],
+            [4, 5, 6],
+            [5, 7, 6],
+            [0, 1, 4],
+            [1, 5, 4],
+            [2, 6, 3],
+            [3, 6, 7],
+            [0, 4, 2],
+            [2, 4, 6],
+            [1, 3, 5],
+            [3, 7, 5],
+        ],
+        Collider::cuboid(2.0, 4.0, 6.0),
+    );
+
+    let vertices: Vec<Vector> = vertices.into_iter().map(|v| v + offset).collect();
+    let trimesh = Collider::trimesh(vertices.clone(), indices.clone());
+
+    let expected = primitive.mass_properties(2.0);
+    let trimesh_props = trimesh.mass_properties(2.0);
+
+    assert_relative_eq!(trimesh_props.mass.0, expected.mass.0, epsilon = 1e-3);
+    assert_relative_eq!(trimesh_props.center_of_mass.0, offset, epsilon = 1e-3);
+    assert_relative_eq!(trimesh_props.inertia.0, expected.inertia.0, epsilon = 1e-2);
+
+    // The winding order shouldn't matter
+    let flipped_indices: Vec<[u32; 3]> = indices.iter().map(|[a, b, c]| [*a, *c, *b]).collect();
+    let flipped = ColliderMassProperties::from_trimesh(&vertices, &flipped_indices, 2.0).unwrap();
+    assert_relative_eq!(flipped.mass.0, expected.mass.0, epsilon = 1e-3);
+    assert_relative_eq!(flipped.inertia.0, expected.inertia.0, epsilon = 1e-2);
+
+    // Open meshes have no volume in 3D
+    #[cfg(feature = "3d")]
+    assert!(ColliderMassProperties::from_trimesh(&vertices, &indices[1..], 2.0).is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn mass_changes_conserve_velocity_or_momentum() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_body = |app: &mut App, mass_change: MassChange| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                MassPropertiesBundle {
+                    mass: Mass(10.0),
+                    inverse_mass: InverseMass(0.1),
+                    #[cfg(feature = "2d")]
+                    inertia: Inertia(1.0),
+                    #[cfg(feature = "3d")]
+                    inertia: Inertia(Matrix3::IDENTITY),
+                    ..default()
+                },
+                LinearVelocity(Vector::X * 2.0),
+                mass_change,
+            ))
+            .id()
+    };
+
+    // A body burning 2 units of mass per second, but never going below 5 units of mass
+    let rocket = spawn_body(&mut app, MassChange::new(-2.0).with_min_mass(5.0));
+
+    // A body that absorbs 10 units of mass at once
+    let absorber = spawn_body(
+        &mut app,
+        MassChange::default().with_mode(MassChangeMode::ConserveMomentum),
+    );
+    app.world.get_mut::<MassChange>(absorber).unwrap().add(10.0);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The rocket should lose mass but keep its velocity
+    assert_relative_eq!(
+        app.world.get::<Mass>(rocket).unwrap().0,
+        8.0,
+        epsilon = 0.05
+    );
+    assert_relative_eq!(
+        app.world.get::<InverseMass>(rocket).unwrap().0,
+        1.0 / app.world.get::<Mass>(rocket).unwrap().0
+    );
+    assert_relative_eq!(
+        app.world.get::<LinearVelocity>(rocket).unwrap().x,
+        2.0,
+        epsilon = 0.001
+    );
+
+    // The absorber should double its mass and halve its velocity
+    assert_relative_eq!(app.world.get::<Mass>(absorber).unwrap().0, 20.0);
+    assert_relative_eq!(
+        app.world.get::<LinearVelocity>(absorber).unwrap().x,
+        1.0,
+        epsilon = 0.001
+    );
+    assert_eq!(app.world.get::<MassChange>(absorber).unwrap().pending, 0.0);
+
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+    }
+
+    // The rocket shouldn't go below its minimum mass
+    assert_relative_eq!(app.world.get::<Mass>(rocket).unwrap().0, 5.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn rigid_body_type_can_be_changed_at_runtime() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    let anchor = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::NEG_Y),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+        ))
+        .id();
+    let joint = app
+        .world
+        .spawn(FixedJoint::new(anchor, body).with_local_anchor_2(Vector::Y))
+        .id();
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The joint holds the body against gravity
+    assert_ne!(
+        app.world.get::<FixedJoint>(joint).unwrap().force,
+        Vector::ZERO
+    );
+
+    // Kinematic bodies keep their velocity
+    app.world
+        .entity_mut(body)
+        .insert((RigidBody::Kinematic, LinearVelocity(Vector::X * 2.0)));
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_relative_eq!(
+        app.world.get::<LinearVelocity>(body).unwrap().x,
+        2.0,
+        epsilon = 0.001
+    );
+    assert!(app.world.get::<Position>(body).unwrap().x > 1.5);
+
+    // The joint is no longer solved, so it shouldn't report stale forces
+    assert_eq!(
+        app.world.get::<FixedJoint>(joint).unwrap().force,
+        Vector::ZERO
+    );
+
+    // Static bodies have their velocity cleared
+    app.world.entity_mut(body).insert(RigidBody::Static);
+
+    tick_60_fps(&mut app);
+
+    let position = app.world.get::<Position>(body).unwrap().0;
+    assert_eq!(
+        app.world.get::<LinearVelocity>(body).unwrap().0,
+        Vector::ZERO
+    );
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_eq!(app.world.get::<Position>(body).unwrap().0, position);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn time_scale_slows_down_bodies() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let spawn_body = |app: &mut App, position: Vector, time_scale: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                LinearVelocity(Vector::X * 2.0),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+                TimeScale(time_scale),
+            ))
+            .id()
+    };
+
+    let normal = spawn_body(&mut app, Vector::ZERO, 1.0);
+    let slow = spawn_body(&mut app, Vector::Y * 5.0, 0.5);
+    let frozen = spawn_body(&mut app, Vector::Y * 10.0, 0.0);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let normal_x = app.world.get::<Position>(normal).unwrap().x;
+    let slow_x = app.world.get::<Position>(slow).unwrap().x;
+    assert!(normal_x > 1.5);
+    assert_relative_eq!(slow_x, normal_x * 0.5, epsilon = 0.01);
+
+    // The velocity is expressed in the time of the body, so it shouldn't change
+    assert_relative_eq!(
+        app.world.get::<LinearVelocity>(slow).unwrap().x,
+        2.0,
+        epsilon = 0.001
+    );
+
+    // Frozen bodies shouldn't move but keep their velocity
+    assert_eq!(
+        app.world.get::<Position>(frozen).unwrap().0,
+        Vector::Y * 10.0
+    );
+    assert_eq!(
+        app.world.get::<LinearVelocity>(frozen).unwrap().0,
+        Vector::X * 2.0
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn teleport_resets_solver_state() {
+    use bevy::ecs::system::SystemState;
+
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // A body resting on the ground
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::NEG_Y),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(10.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            Collider::rectangle(1.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // Throw the body and teleport it far away from the ground
+    app.world.get_mut::<LinearVelocity>(body).unwrap().0 = Vector::X * 5.0;
+
+    let mut system_state = SystemState::<PhysicsCommands>::new(&mut app.world);
+    let mut physics_commands = system_state.get_mut(&mut app.world);
+    physics_commands.teleport_with(
+        body,
+        Teleport::new(Vector::Y * 100.0, Rotation::default()).with_reset_velocity(true),
+    );
+    system_state.apply(&mut app.world);
+
+    assert_eq!(
+        app.world.get::<Position>(body).unwrap().0,
+        Vector::Y * 100.0
+    );
+    assert_eq!(
+        app.world.get::<AccumulatedTranslation>(body).unwrap().0,
+        Vector::ZERO
+    );
+    assert_eq!(
+        app.world.get::<LinearVelocity>(body).unwrap().0,
+        Vector::ZERO
+    );
+
+    tick_60_fps(&mut app);
+
+    // The body should only be affected by gravity
+    let pos = app.world.get::<Position>(body).unwrap().0;
+    let lin_vel = app.world.get::<LinearVelocity>(body).unwrap().0;
+    assert_relative_eq!(pos.x, 0.0, epsilon = 0.001);
+    assert!(pos.y > 99.0);
+    assert_relative_eq!(lin_vel.x, 0.0, epsilon = 0.001);
+    assert!(lin_vel.y > -0.5);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn ropes_hang_from_bodies_and_collide_with_colliders() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // The ground
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::X * 10.0 - Vector::Y * 5.0),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(10.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+
+    // A ball hanging from a static anchor by a rope
+    let anchor = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let ball = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::NEG_Y * 2.0),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.25),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.25),
+        ))
+        .id();
+    let hanging_rope = app
+        .world
+        .spawn(
+            Rope::new(Vector::ZERO, Vector::NEG_Y * 2.0, 10, 0.5)
+                .with_start_attachment(ParticleAttachment::new(anchor))
+                .with_end_attachment(ParticleAttachment::new(ball)),
+        )
+        .id();
+
+    // A free rope falling onto the ground
+    let falling_rope = app
+        .world
+        .spawn(
+            Rope::new(
+                Vector::X * 9.0 - Vector::Y * 3.0,
+                Vector::X * 11.0 - Vector::Y * 3.0,
+                10,
+                0.5,
+            )
+            .with_radius(0.1),
+        )
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The ball should hang from the rope without stretching it much
+    let ball_pos = app.world.get::<Position>(ball).unwrap().0;
+    assert_relative_eq!(ball_pos.y, -2.0, epsilon = 0.1);
+
+    let rope = app.world.get::<Rope>(hanging_rope).unwrap();
+    assert_relative_eq!(rope.current_length(), rope.rest_length(), epsilon = 0.1);
+    assert!(rope.particles[0].position.length() < 0.01);
+    assert!(rope.end.unwrap().force.y > 0.0);
+
+    // The free rope should rest on top of the ground
+    let rope = app.world.get::<Rope>(falling_rope).unwrap();
+    for position in rope.positions() {
+        assert!(position.y > -4.5 + rope.radius - 0.02);
+        assert!(position.y < -4.0);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cloth_hangs_from_pins_and_drapes_over_colliders() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // A cloth hanging from its top corners
+    #[cfg(feature = "2d")]
+    let height = Vector::NEG_Y;
+    #[cfg(feature = "3d")]
+    let height = Vector::Z;
+    let hanging_cloth =
+        Cloth::new(Vector::ZERO, Vector::X * 2.0, height, 9, 5, 0.5).with_damping(2.0);
+    let pins = [hanging_cloth.index(0, 0), hanging_cloth.index(8, 0)];
+    let hanging_cloth = app
+        .world
+        .spawn(hanging_cloth.with_pinned(pins[0]).with_pinned(pins[1]))
+        .id();
+
+    // A box and a cloth falling on top of it
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::X * 10.0 - Vector::Y * 5.0),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(1.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(1.0, 1.0, 1.0),
+    ));
+    #[cfg(feature = "2d")]
+    let (corner, height) = (Vector::new(9.0, -3.0), Vector::Y * 0.1);
+    #[cfg(feature = "3d")]
+    let (corner, height) = (Vector::new(9.0, -3.0, -1.0), Vector::Z * 2.0);
+    let falling_cloth = app
+        .world
+        .spawn(Cloth::new(corner, Vector::X * 2.0, height, 9, 5, 0.5).with_radius(0.05))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The pinned corners shouldn't move, and the rest of the cloth should hang below them
+    let cloth = app.world.get::<Cloth>(hanging_cloth).unwrap();
+    assert_eq!(cloth.particles[pins[0]].position, Vector::ZERO);
+    assert_eq!(cloth.particles[pins[1]].position, Vector::X * 2.0);
+    let bottom = cloth.particles[cloth.index(4, 4)].position;
+    assert!(bottom.y < -0.5);
+    for constraint in cloth
+        .constraints
+        .iter()
+        .filter(|constraint| constraint.kind == ClothConstraintKind::Stretch)
+    {
+        let [p1, p2] = constraint.particles.map(|i| cloth.particles[i].position);
+        assert!(p1.distance(p2) < constraint.rest_length * 1.1);
+    }
+
+    // The middle of the falling cloth should rest on top of the box while the edges drape down
+    let cloth = app.world.get::<Cloth>(falling_cloth).unwrap();
+    let middle = cloth.particles[cloth.index(4, 2)].position;
+    assert!(middle.y > -4.5);
+    assert!(middle.y < -4.0);
+    let edge = cloth.particles[cloth.index(0, 2)].position;
+    assert!(edge.y < middle.y - 0.1);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn soft_bodies_keep_their_volume_and_rest_on_colliders() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // The ground
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(10.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+
+    // A unit square or cube divided into triangles or tetrahedra
+    #[cfg(feature = "2d")]
+    let (positions, elements) = (
+        vec![Vector::ZERO, Vector::X, Vector::ONE, Vector::Y],
+        vec![[0, 1, 2], [0, 2, 3]],
+    );
+    #[cfg(feature = "3d")]
+    let (positions, elements) = (
+        (0..8)
+            .map(|i| {
+                Vector::new(
+                    (i & 1) as Scalar,
+                    ((i >> 1) & 1) as Scalar,
+                    (i >> 2) as Scalar,
+                )
+            })
+            .collect(),
+        vec![
+            [0, 1, 3, 7],
+            [0, 3, 2, 7],
+            [0, 2, 6, 7],
+            [0, 6, 4, 7],
+            [0, 4, 5, 7],
+            [0, 5, 1, 7],
+        ],
+    );
+    let soft_body = SoftBody::new(positions, elements, 1.0)
+        .with_edge_compliance(0.001)
+        .with_damping(1.0)
+        .with_offset(Vector::Y);
+    assert_relative_eq!(soft_body.rest_volume().abs(), 1.0, epsilon = 0.0001);
+    let soft_body = app.world.spawn(soft_body).id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let soft_body = app.world.get::<SoftBody>(soft_body).unwrap();
+    assert_relative_eq!(soft_body.volume(), soft_body.rest_volume(), epsilon = 0.1);
+    for position in soft_body.positions() {
+        assert!(position.y > soft_body.radius - 0.02);
+        assert!(position.y < 1.2);
+    }
+    assert!(soft_body.center_of_mass().y < 0.7);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[allow(clippy::unnecessary_cast)]
+fn particles_fall_and_collide_with_colliders() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // The ground
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(10.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(10.0, 1.0, 10.0),
+    ));
+
+    let spawn_particle = |app: &mut App, friction: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                Particle::new(Vector::Y, 0.1).with_velocity(Vector::X * 2.0),
+                ParticleRadius(0.1),
+                Friction::new(friction),
+            ))
+            .id()
+    };
+    let slippery = spawn_particle(&mut app, 0.0);
+    let rough = spawn_particle(&mut app, 1.0);
+
+    // A particle that ignores the ground
+    let ghost = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            Particle::new(Vector::Y, 0.1),
+            CollisionLayers::NONE,
+        ))
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The particles should rest on the ground, and friction should stop the rough particle
+    let slippery_particle = app.world.get::<Particle>(slippery).unwrap();
+    let rough_particle = app.world.get::<Particle>(rough).unwrap();
+    assert_relative_eq!(slippery_particle.position.y, 0.1, epsilon = 0.01);
+    assert_relative_eq!(rough_particle.position.y, 0.1, epsilon = 0.01);
+    assert_relative_eq!(slippery_particle.velocity.x, 2.0, epsilon = 0.01);
+    assert!(rough_particle.velocity.x.abs() < 0.01);
+    assert!(rough_particle.position.x < slippery_particle.position.x);
+
+    assert!(app.world.get::<Particle>(ghost).unwrap().position.y < -1.0);
+
+    // The transform should follow the particle
+    assert_relative_eq!(
+        app.world.get::<Transform>(slippery).unwrap().translation.x,
+        slippery_particle.position.x as f32,
+        epsilon = 0.0001
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "2d",
+    feature = "fluid",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn fluids_stay_in_containers_and_float_bodies() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // A container with the floor at y = 0 and walls at x = -1 and x = 1
+    for (position, size) in [
+        (Vector::NEG_Y * 0.5, Vector::new(3.0, 1.0)),
+        (Vector::new(-1.5, 1.0), Vector::new(1.0, 4.0)),
+        (Vector::new(1.5, 1.0), Vector::new(1.0, 4.0)),
+    ] {
+        app.world.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(position),
+            Collider::rectangle(size.x, size.y),
+        ));
+    }
+
+    let fluid = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            Fluid::rectangle(Vector::Y * 0.5, Vector::new(0.5, 0.4), 0.1, 2.0),
+        ))
+        .id();
+
+    // A box that is lighter than the fluid
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y * 1.5),
+            Collider::rectangle(0.3, 0.3),
+        ))
+        .id();
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    // The fluid should spread out inside the container without leaking out
+    let fluid = app.world.get::<Fluid>(fluid).unwrap();
+    assert_eq!(fluid.particles.len(), 80);
+    for particle in fluid.particles.iter() {
+        assert!(particle.position.y > -0.05);
+        assert!(particle.position.x.abs() < 1.05);
+    }
+
+    // The box should float on the fluid instead of sinking to the floor
+    let position = app.world.get::<Position>(body).unwrap();
+    assert!(position.y > 0.2);
+    assert!(position.x.abs() < 1.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "deformable-mesh",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[allow(clippy::unnecessary_cast)]
+fn deformable_meshes_follow_particles() {
+    use bevy::render::mesh::VertexAttributeValues;
+
+    let mut app = create_app();
+
+    app.init_resource::<Assets<Mesh>>()
+        .insert_resource(Gravity(Vector::NEG_Y * 10.0))
+        .insert_resource(DeformableMeshConfig {
+            interpolate: false,
+            ..default()
+        });
+
+    let rope = Rope::new(Vector::ZERO, Vector::X * 2.0, 10, 1.0);
+    let handle = app.world.resource_mut::<Assets<Mesh>>().add(rope.mesh());
+    let entity = app
+        .world
+        .spawn((
+            rope,
+            SpatialBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+            #[cfg(feature = "2d")]
+            bevy::sprite::Mesh2dHandle(handle.clone()),
+            #[cfg(feature = "3d")]
+            handle.clone(),
+        ))
+        .id();
+
+    let mesh_positions = |app: &App| -> Vec<[f32; 3]> {
+        let mesh = app.world.resource::<Assets<Mesh>>().get(&handle).unwrap();
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("the mesh should have vertex positions");
+        };
+        positions.clone()
+    };
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The vertices should match the particles relative to the transform of the entity
+    let positions = mesh_positions(&app);
+    let rope = app.world.get::<Rope>(entity).unwrap();
+    assert_eq!(positions.len(), rope.particles.len());
+    for (vertex, particle) in positions.iter().zip(rope.particles.iter()) {
+        assert_relative_eq!(
+            vertex[0],
+            particle.position.x as f32 - 1.0,
+            epsilon = 0.0001
+        );
+        assert_relative_eq!(vertex[1], particle.position.y as f32, epsilon = 0.0001);
+    }
+
+    // With interpolation, the vertices should be between the previous and current step,
+    // so they lag behind the falling particles
+    app.world.resource_mut::<DeformableMeshConfig>().interpolate = true;
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    let positions = mesh_positions(&app);
+    let rope = app.world.get::<Rope>(entity).unwrap();
+    for (vertex, particle) in positions.iter().zip(rope.particles.iter()) {
+        assert!(vertex[1] >= particle.position.y as f32 - 0.0001);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn pressurized_soft_bodies_inflate_and_deflate_when_punctured() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    // A unit square or tetrahedron
+    #[cfg(feature = "2d")]
+    let (positions, elements) = (
+        vec![Vector::ZERO, Vector::X, Vector::ONE, Vector::Y],
+        vec![[0, 1, 2], [0, 2, 3]],
+    );
+    #[cfg(feature = "3d")]
+    let (positions, elements) = (
+        vec![Vector::ZERO, Vector::X, Vector::Y, Vector::Z],
+        vec![[0, 1, 2, 3]],
+    );
+
+    let soft_body = SoftBody::new(positions, elements, 1.0)
+        .with_edge_compliance(0.01)
+        .with_volume_compliance(1.0)
+        .with_pressure(2.0)
+        .with_damping(1.0);
+    let rest_volume = soft_body.rest_volume().abs();
+    assert_relative_eq!(
+        soft_body.target_volume().unwrap().abs(),
+        2.0 * rest_volume,
+        epsilon = 0.0001
+    );
+    let entity = app.world.spawn(soft_body).id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The pressure should inflate the body beyond its rest volume
+    let soft_body = app.world.get::<SoftBody>(entity).unwrap();
+    assert!(soft_body.volume().abs() > 1.5 * rest_volume);
+
+    // Once punctured, the edges should pull the body back towards its rest shape
+    app.world.get_mut::<SoftBody>(entity).unwrap().puncture();
+    assert!(app
+        .world
+        .get::<SoftBody>(entity)
+        .unwrap()
+        .target_volume()
+        .is_none());
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let soft_body = app.world.get::<SoftBody>(entity).unwrap();
+    assert!(soft_body.volume().abs() < 1.2 * rest_volume);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cloth_supports_bodies_resting_on_it() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // A horizontal hammock pinned at its corners
+    #[cfg(feature = "2d")]
+    let (corner, height, rows) = (Vector::NEG_X, Vector::Y * 0.1, 2);
+    #[cfg(feature = "3d")]
+    let (corner, height, rows) = (Vector::new(-1.0, 0.0, -1.0), Vector::Z * 2.0, 11);
+    let cloth = Cloth::new(corner, Vector::X * 2.0, height, 11, rows, 1.0).with_damping(1.0);
+    let corners = [
+        cloth.index(0, 0),
+        cloth.index(10, 0),
+        cloth.index(0, rows - 1),
+        cloth.index(10, rows - 1),
+    ];
+    let cloth = corners
+        .into_iter()
+        .fold(cloth, |cloth, index| cloth.with_pinned(index));
+    let cloth = app.world.spawn(cloth).id();
+
+    // A heavy crate dropped onto the hammock
+    let crate_body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(0.4, 0.4),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(0.4, 0.4, 0.4),
+            ColliderDensity(10.0),
+        ))
+        .id();
+
+    // Let the crate land on the hammock
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The crate should keep resting on the hammock instead of falling through it
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+        let position = app.world.get::<Position>(crate_body).unwrap();
+        assert!(position.y > -1.0);
+        assert!(position.y < 0.5);
+    }
+
+    // The weight of the crate should pull the middle of the hammock down
+    let cloth = app.world.get::<Cloth>(cloth).unwrap();
+    let middle = cloth.particles[cloth.index(5, rows / 2)].position;
+    assert!(middle.y < -0.05);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn plastic_joints_deform_permanently_under_load() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // Heavy weights hanging from compliant joints, one elastic and one plastic
+    let spawn_weight = |app: &mut App, x: Scalar, plasticity: Option<Plasticity>| {
+        let anchor = app
+            .world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                Position(Vector::X * x),
+            ))
+            .id();
+        let weight = app
+            .world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::X * x + Vector::NEG_Y),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.2),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.2),
+                ColliderDensity(20.0),
+                LinearDamping(2.0),
+            ))
+            .id();
+        let mut joint = DistanceJoint::new(anchor, weight)
+            .with_rest_length(1.0)
+            .with_compliance(0.02);
+        joint.plasticity = plasticity;
+        app.world.spawn(joint).id()
+    };
+    let elastic = spawn_weight(&mut app, -2.0, None);
+    let plastic = spawn_weight(&mut app, 2.0, Some(Plasticity::new(0.05, 10.0)));
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The elastic joint always springs back to its rest length, but the plastic joint has stretched
+    // permanently after being loaded beyond its yield strain
+    let elastic = app.world.get::<DistanceJoint>(elastic).unwrap();
+    let plastic = app.world.get::<DistanceJoint>(plastic).unwrap();
+    assert_eq!(elastic.rest_length, 1.0);
+    assert!(plastic.rest_length > 1.05);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn winches_reel_in_loads_and_slip_when_overloaded() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // Weights hanging from ropes that are reeled in at 1 m/s, one by a strong winch and one by a weak winch
+    let spawn_weight = |app: &mut App, x: Scalar, max_force: Scalar| {
+        let anchor = app
+            .world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                Position(Vector::X * x),
+            ))
+            .id();
+        let weight = app
+            .world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::X * x + Vector::NEG_Y * 2.0),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.2),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.2),
+                ColliderDensity(20.0),
+            ))
+            .id();
+        let joint = app
+            .world
+            .spawn(
+                DistanceJoint::new(anchor, weight)
+                    .with_limits(0.0, 2.0)
+                    .with_winch(Winch::new(max_force).with_speed(-1.0)),
+            )
+            .id();
+        (weight, joint)
+    };
+    let (strong_weight, _) = spawn_weight(&mut app, -2.0, 1000.0);
+    let (weak_weight, weak_joint) = spawn_weight(&mut app, 2.0, 1.0);
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The strong winch has lifted its weight by a meter
+    let position = app.world.get::<Position>(strong_weight).unwrap();
+    assert_relative_eq!(position.y, -1.0, epsilon = 0.1);
+
+    // The weak winch can't hold the weight, so it pays out rope and the weight falls
+    let position = app.world.get::<Position>(weak_weight).unwrap();
+    assert!(position.y < -2.0);
+    let joint = app.world.get::<DistanceJoint>(weak_joint).unwrap();
+    assert!(joint.length_limits.unwrap().max > 2.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn ropes_tear_under_heavy_loads() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // A very heavy ball hanging from a static anchor by a weak rope
+    let anchor = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let ball = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::NEG_Y * 2.0),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.25),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.25),
+            ColliderDensity(1000.0),
+        ))
+        .id();
+    let rope = app
+        .world
+        .spawn(
+            Rope::new(Vector::ZERO, Vector::NEG_Y * 2.0, 10, 0.1)
+                .with_start_attachment(ParticleAttachment::new(anchor))
+                .with_end_attachment(ParticleAttachment::new(ball))
+                .with_tear_strain(0.1),
+        )
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The rope should have torn and dropped the ball
+    assert!(app.world.get::<Position>(ball).unwrap().y < -3.0);
+
+    let rope = app.world.get::<Rope>(rope).unwrap();
+    assert!(rope.start.is_some());
+    assert!(rope.end.is_none());
+    assert!(rope.particles.len() < 11);
+
+    let ropes = app.world.query::<&Rope>().iter(&app.world).count();
+    assert!(ropes >= 2);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn cloth_and_ropes_can_be_cut() {
+    // A cloth with 9x5 particles, cut between the fifth and sixth columns
+    let mut cloth = Cloth::new(Vector::ZERO, Vector::X * 2.0, Vector::NEG_Y, 9, 5, 0.5);
+    let triangles = cloth.triangles.len();
+    let edges = cloth.cut(Vector::X * 1.1, Vector::X);
+
+    // The stretch constraints of each row and the shear constraints of each quad between the columns are cut
+    assert_eq!(edges.len(), 5 + 2 * 4);
+    assert_eq!(cloth.triangles.len(), triangles - 2 * 4);
+    assert!(cloth.constraints.iter().all(|constraint| {
+        let [i1, i2] = constraint.particles;
+        let [x1, x2] = [i1, i2].map(|i| cloth.particles[i].position.x);
+        (x1 < 1.1) == (x2 < 1.1)
+    }));
+
+    // A rope cut in two places
+    let mut rope = Rope::new(Vector::ZERO, Vector::X * 3.0, 6, 1.0);
+    let pieces = rope.cut(Vector::X * 1.2, Vector::X);
+    assert_eq!(rope.particles.len(), 3);
+    assert_eq!(pieces.len(), 1);
+    assert_eq!(pieces[0].particles.len(), 4);
+
+    let pieces = pieces[0].clone().cut(Vector::X * 2.2, Vector::X);
+    assert_eq!(pieces.len(), 1);
+    assert_eq!(pieces[0].particles.len(), 2);
+}
+
+#[test]
+#[cfg(all(
+    feature = "2d",
+    feature = "granular",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn granular_materials_form_piles() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // The ground
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::rectangle(20.0, 1.0),
+    ));
+
+    // Two blocks of grains, one with friction and one without
+    let half_extents = Vector::new(0.4, 0.4);
+    let sand = app
+        .world
+        .spawn(Granular::rectangle(
+            Vector::new(-3.0, 0.5),
+            half_extents,
+            0.05,
+            1600.0,
+        ))
+        .id();
+    let frictionless = app
+        .world
+        .spawn(
+            Granular::rectangle(Vector::new(3.0, 0.5), half_extents, 0.05, 1600.0)
+                .with_grain_friction(0.0, 0.0)
+                .with_friction(0.0),
+        )
+        .id();
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    let width = |granular: &Granular| {
+        let (min, max) = granular
+            .particles
+            .iter()
+            .fold((Scalar::MAX, Scalar::MIN), |(min, max), particle| {
+                (min.min(particle.position.x), max.max(particle.position.x))
+            });
+        max - min
+    };
+
+    // The grains should rest on the ground without overlapping much
+    let sand = app.world.get::<Granular>(sand).unwrap();
+    for (i, particle) in sand.particles.iter().enumerate() {
+        assert!(particle.position.y > 0.0);
+        assert!(particle.velocity.length() < 0.5);
+        for other in sand.particles[i + 1..].iter() {
+            assert!(particle.position.distance(other.position) > 1.5 * sand.radius);
+        }
+    }
+
+    // Friction should keep the sand in a pile, while the frictionless grains spread out
+    let frictionless = app.world.get::<Granular>(frictionless).unwrap();
+    assert!(width(sand) < width(frictionless));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn shape_matching_bodies_keep_their_shape() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    // The ground
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        #[cfg(feature = "2d")]
+        Collider::rectangle(20.0, 1.0),
+        #[cfg(feature = "3d")]
+        Collider::cuboid(20.0, 1.0, 20.0),
+    ));
+
+    // A jelly block with 4 particles along each axis, dropped onto the ground
+    let mut positions = vec![];
+    for y in 0..4 {
+        for x in 0..4 {
+            #[cfg(feature = "2d")]
+            positions.push(Vector::new(x as Scalar, y as Scalar) * 0.2);
+            #[cfg(feature = "3d")]
+            for z in 0..4 {
+                positions.push(Vector::new(x as Scalar, y as Scalar, z as Scalar) * 0.2);
+            }
+        }
+    }
+    let jelly = app
+        .world
+        .spawn(
+            ShapeMatchingBody::new(positions, 1.0)
+                .with_cluster_size(0.3)
+                .with_stiffness(0.2)
+                .with_offset(Vector::Y),
+        )
+        .id();
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    let jelly = app.world.get::<ShapeMatchingBody>(jelly).unwrap();
+    assert!(jelly.clusters.len() > 1);
+
+    // The block should rest on the ground and return to roughly its rest shape
+    for (i, particle) in jelly.particles.iter().enumerate() {
+        assert!(particle.position.y > -jelly.radius - 0.02);
+        assert!(particle.velocity.length() < 0.2);
+        for j in i + 1..jelly.particles.len() {
+            let distance = particle.position.distance(jelly.particles[j].position);
+            let rest_distance = jelly.rest_positions[i].distance(jelly.rest_positions[j]);
+            assert_relative_eq!(distance, rest_distance, epsilon = 0.1);
+        }
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn gravity_fields_accelerate_bodies() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    let spawn_body = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+                #[cfg(feature = "3d")]
+                MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+            ))
+            .id()
+    };
+
+    // A planet at the origin that replaces the global gravity within a radius of 10 units
+    app.world.spawn((
+        SpatialBundle::default(),
+        RigidBody::Static,
+        Position(Vector::ZERO),
+        GravityField::point(100.0, GravityFalloff::InverseSquare).with_radius(10.0),
+    ));
+
+    let in_field = spawn_body(&mut app, Vector::X * 5.0);
+    let outside_field = spawn_body(&mut app, Vector::X * 20.0);
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    // The body in the field is pulled towards the planet, and not affected by the global gravity
+    let lin_vel = app.world.get::<LinearVelocity>(in_field).unwrap();
+    assert!(lin_vel.x < 0.0);
+    assert_relative_eq!(lin_vel.y, 0.0);
+
+    // The body outside the field only falls down due to the global gravity
+    let lin_vel = app.world.get::<LinearVelocity>(outside_field).unwrap();
+    assert_relative_eq!(lin_vel.x, 0.0);
+    assert!(lin_vel.y < 0.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn gravity_volumes_override_gravity() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity(Vector::NEG_Y * 10.0));
+
+    let spawn_volume = |app: &mut App, position: Vector, size: Scalar, volume: GravityVolume| {
+        app.world.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(position),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(size, size),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(size, size, size),
+            Sensor,
+            volume,
+        ));
+    };
+    let spawn_body = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+            ))
+            .id()
+    };
+
+    // A large volume with upwards gravity, and a small volume with sideways gravity
+    // that takes precedence over it
+    spawn_volume(
+        &mut app,
+        Vector::ZERO,
+        10.0,
+        GravityVolume::new(Vector::Y * 10.0),
+    );
+    spawn_volume(
+        &mut app,
+        Vector::X * 3.0,
+        2.0,
+        GravityVolume::new(Vector::X * 10.0).with_priority(1),
+    );
+
+    let in_large_volume = spawn_body(&mut app, Vector::X * -3.0);
+    let in_both_volumes = spawn_body(&mut app, Vector::X * 3.0);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The volumes are used starting from the step after the bodies enter them,
+    // so the bodies fall down during the first step
+    let lin_vel = app.world.get::<LinearVelocity>(in_large_volume).unwrap();
+    assert_relative_eq!(lin_vel.x, 0.0);
+    assert!(lin_vel.y > 0.0);
+
+    let lin_vel = app.world.get::<LinearVelocity>(in_both_volumes).unwrap();
+    assert!(lin_vel.x > 0.0);
+    assert!(lin_vel.y < 0.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn bodies_float_in_fluid_volumes() {
+    let mut app = create_app();
+
+    // Water below the plane at the origin
+    app.world
+        .spawn(FluidVolume::surface_plane(Vector::ZERO, Vector::Y).with_density(2.0));
+
+    let spawn_body = |app: &mut App, position: Vector, density: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                #[cfg(feature = "2d")]
+                Collider::rectangle(1.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(1.0, 1.0, 1.0),
+                ColliderDensity(density),
+            ))
+            .id()
+    };
+
+    let floating = spawn_body(&mut app, Vector::X * -5.0 + Vector::NEG_Y * 2.0, 1.0);
+    let sinking = spawn_body(&mut app, Vector::X * 5.0 + Vector::NEG_Y * 2.0, 4.0);
+
+    for _ in 0..600 {
+        tick_60_fps(&mut app);
+    }
+
+    // The body with half of the density of the fluid floats half submerged
+    let position = app.world.get::<Position>(floating).unwrap();
+    assert_relative_eq!(position.y, 0.0, epsilon = 0.3);
+
+    // The body that is denser than the fluid sinks
+    let position = app.world.get::<Position>(sinking).unwrap();
+    assert!(position.y < -3.0);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn ray_hits_sorted_are_ordered_by_time_of_impact() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        for x in [6.0, 2.0, 10.0, 4.0] {
+            commands.spawn((
+                RigidBody::Static,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+            ));
+        }
+    });
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let hits = pipeline
+        .ray_hits_sorted(
+            Vector::ZERO,
+            Dir::X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .collect::<Vec<_>>();
+
+    assert_eq!(hits.len(), 4);
+    assert!(hits
+        .windows(2)
+        .all(|pair| pair[0].time_of_impact <= pair[1].time_of_impact));
+    assert_relative_eq!(hits[0].time_of_impact, 1.5, epsilon = 0.001);
+
+    let limited_hits = pipeline
+        .ray_hits_sorted_max(
+            Vector::ZERO,
+            Dir::X,
+            Scalar::MAX,
+            2,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .collect::<Vec<_>>();
+
+    assert_eq!(limited_hits.len(), 2);
+    assert_eq!(limited_hits[1].entity, hits[1].entity);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn shape_hits_sorted_are_ordered_by_time_of_impact() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // Hits in both the static and dynamic trees, and colliders beside and behind the sweep
+        for (rb, position) in [
+            (RigidBody::Static, Vector::X * 6.0),
+            (RigidBody::Dynamic, Vector::X * 2.0),
+            (RigidBody::Static, Vector::X * 10.0),
+            (RigidBody::Dynamic, Vector::X * 4.0 + Vector::Y * 0.75),
+            (RigidBody::Static, Vector::X * 4.0 + Vector::Y * 5.0),
+            (RigidBody::Dynamic, Vector::NEG_X * 4.0),
+        ] {
+            commands.spawn((
+                rb,
+                Position(position),
+                GravityScale(0.0),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+            ));
+        }
+    });
+
+    tick_60_fps(&mut app);
+
+    #[cfg(feature = "2d")]
+    let shape = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let shape = Collider::sphere(0.5);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let hits = pipeline
+        .shape_hits_sorted(
+            &shape,
+            Vector::ZERO,
+            Default::default(),
+            Dir::X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .collect::<Vec<_>>();
+
+    assert_eq!(hits.len(), 4);
+    assert!(hits
+        .windows(2)
+        .all(|pair| pair[0].time_of_impact <= pair[1].time_of_impact));
+    assert_relative_eq!(hits[0].time_of_impact, 1.0, epsilon = 0.001);
+    assert_relative_eq!(hits[3].time_of_impact, 9.0, epsilon = 0.001);
+
+    // The hits should match the closest hit of a regular shapecast.
+    let closest = pipeline
+        .cast_shape(
+            &shape,
+            Vector::ZERO,
+            Default::default(),
+            Dir::X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .expect("shape should hit a collider");
+    assert_eq!(closest.entity, hits[0].entity);
+    assert_relative_eq!(closest.point1, hits[0].point1, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn closest_hit_is_found_across_static_and_dynamic_colliders() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // The closer collider is dynamic in the positive direction and static in the negative direction.
+        for (rb, x) in [
+            (RigidBody::Static, 6.0),
+            (RigidBody::Dynamic, 3.0),
+            (RigidBody::Static, -3.0),
+            (RigidBody::Dynamic, -6.0),
+        ] {
+            commands.spawn((
+                rb,
+                Position(Vector::X * x),
+                GravityScale(0.0),
+                #[cfg(feature = "2d")]
+                Collider::circle(0.5),
+                #[cfg(feature = "3d")]
+                Collider::sphere(0.5),
+            ));
+        }
+    });
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    for direction in [Dir::X, Dir::NEG_X] {
+        let hit = pipeline
+            .cast_ray(
+                Vector::ZERO,
+                direction,
+                Scalar::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .expect("ray should hit a collider");
+        assert_relative_eq!(hit.time_of_impact, 2.5, epsilon = 0.001);
+
+        #[cfg(feature = "2d")]
+        let shape = Collider::circle(0.25);
+        #[cfg(feature = "3d")]
+        let shape = Collider::sphere(0.25);
+        let hit = pipeline
+            .cast_shape(
+                &shape,
+                Vector::ZERO,
+                Default::default(),
+                direction,
+                Scalar::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .expect("shape should hit a collider");
+        assert_relative_eq!(hit.time_of_impact, 2.25, epsilon = 0.001);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn query_filter_predicates_exclude_entities() {
+    let mut app = create_app();
+
+    let near = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 2.0),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+        ))
+        .id();
+    let far = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 4.0),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    // The predicate can be set with the builder method or directly on the field.
+    let filters = [
+        SpatialQueryFilter::default().with_predicate(move |entity| entity != near),
+        SpatialQueryFilter {
+            predicate: Some(std::sync::Arc::new(move |entity| entity != near)),
+            ..default()
+        },
+    ];
+
+    for filter in filters {
+        let hit = pipeline
+            .cast_ray(Vector::ZERO, Dir::X, Scalar::MAX, true, filter.clone())
+            .expect("ray should hit a collider");
+        assert_eq!(hit.entity, far);
+
+        #[cfg(feature = "2d")]
+        let shape = Collider::circle(10.0);
+        #[cfg(feature = "3d")]
+        let shape = Collider::sphere(10.0);
+        let intersections =
+            pipeline.shape_intersections(&shape, Vector::ZERO, Default::default(), filter);
+        assert_eq!(intersections, vec![far]);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spatial_query_snapshot_is_unaffected_by_updates() {
+    let mut app = create_app();
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            Position(Vector::X * 2.0),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let snapshot = app.world.resource::<SpatialQueryPipeline>().snapshot();
+
+    // Move the collider out of the way and add a new one.
+    app.world.get_mut::<Position>(body).unwrap().0 = Vector::Y * 10.0;
+    let new_body = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            #[cfg(feature = "2d")]
+            Collider::circle(0.5),
+            #[cfg(feature = "3d")]
+            Collider::sphere(0.5),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let hit = pipeline.cast_ray(
+        Vector::ZERO,
+        Dir::X,
+        Scalar::MAX,
+        true,
+        SpatialQueryFilter::default(),
+    );
+    assert_eq!(hit.map(|hit| hit.entity), Some(new_body));
+
+    // The snapshot still sees the world as it was when it was taken.
+    let hit = snapshot
+        .cast_ray(
+            Vector::ZERO,
+            Dir::X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .expect("snapshot should still contain the old collider");
+    assert_eq!(hit.entity, body);
+    assert_relative_eq!(hit.time_of_impact, 1.5, epsilon = 0.001);
+    assert!(snapshot
+        .cast_ray(
+            Vector::Y * 10.0,
+            Dir::X,
+            Scalar::MAX,
+            true,
+            SpatialQueryFilter::from_excluded_entities([new_body]),
+        )
+        .is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn point_is_projected_on_closest_collider() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        for x in [-5.0, 3.0] {
+            commands.spawn((
+                RigidBody::Static,
+                Position(Vector::X * x),
+                #[cfg(feature = "2d")]
+                Collider::circle(1.0),
+                #[cfg(feature = "3d")]
+                Collider::sphere(1.0),
+            ));
+        }
+    });
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    let projection = pipeline
+        .project_point(Vector::ZERO, true, SpatialQueryFilter::default())
+        .expect("point should be projected on a collider");
+    assert!(!projection.is_inside);
+    assert_relative_eq!(projection.point, Vector::X * 2.0, epsilon = 0.001);
+
+    let projection = pipeline
+        .project_point(Vector::X * 3.5, true, SpatialQueryFilter::default())
+        .expect("point should be projected on a collider");
+    assert!(projection.is_inside);
+    assert_relative_eq!(projection.point, Vector::X * 3.5, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn aabb_intersections_iter_matches_callback() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        for x in -10..10 {
+            commands.spawn((
+                RigidBody::Static,
+                Position(Vector::X * x as Scalar * 3.0),
+                #[cfg(feature = "2d")]
+                Collider::circle(1.0),
+                #[cfg(feature = "3d")]
+                Collider::sphere(1.0),
+            ));
+        }
+    });
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let aabb = ColliderAabb::new(Vector::X * 5.0, Vector::splat(8.0));
+
+    let mut expected = pipeline.aabb_intersections_with_aabb(aabb);
+    let mut intersections = pipeline
+        .aabb_intersections_iter(aabb, SpatialQueryFilter::default())
+        .collect::<Vec<_>>();
+    expected.sort();
+    intersections.sort();
+
+    assert!(!intersections.is_empty());
+    assert_eq!(intersections, expected);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn closest_points_between_colliders_are_found() {
+    let mut app = create_app();
+
+    let collider = |radius: Scalar| {
+        #[cfg(feature = "2d")]
+        {
+            Collider::circle(radius)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::sphere(radius)
+        }
+    };
+    let entity1 = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::ZERO), collider(1.0)))
+        .id();
+    let entity2 = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::X * 5.0), collider(1.0)))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    assert_relative_eq!(
+        pipeline.distance(entity1, entity2).unwrap(),
+        3.0,
+        epsilon = 0.001
+    );
+
+    let Some(contact_query::ClosestPoints::WithinMargin(point1, point2)) =
+        pipeline.closest_points(entity1, entity2, 5.0)
+    else {
+        panic!("colliders should be within the margin");
+    };
+    assert_relative_eq!(point1, Vector::X, epsilon = 0.001);
+    assert_relative_eq!(point2, Vector::X * 4.0, epsilon = 0.001);
+
+    assert!(matches!(
+        pipeline.closest_points(entity1, entity2, 1.0),
+        Some(contact_query::ClosestPoints::OutsideMargin)
+    ));
+    assert!(matches!(
+        pipeline.closest_points_to_shape(
+            entity1,
+            &collider(1.0),
+            Vector::X * 1.5,
+            Default::default(),
+            1.0
+        ),
+        Some(contact_query::ClosestPoints::Intersecting)
+    ));
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn shape_intersections_report_penetration_and_normal() {
+    let mut app = create_app();
+
+    let collider = |radius: Scalar| {
+        #[cfg(feature = "2d")]
+        {
+            Collider::circle(radius)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::sphere(radius)
+        }
+    };
+    let overlapping = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::ZERO), collider(1.0)))
+        .id();
+    app.world
+        .spawn((RigidBody::Static, Position(Vector::X * 10.0), collider(1.0)));
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let intersections = pipeline.shape_intersections_with_manifolds(
+        &collider(1.0),
+        Vector::X * 1.5,
+        Default::default(),
+        SpatialQueryFilter::default(),
+    );
+
+    assert_eq!(intersections.len(), 1);
+    let intersection = &intersections[0];
+    assert_eq!(intersection.entity, overlapping);
+    assert!(!intersection.manifolds.is_empty());
+    assert_relative_eq!(intersection.penetration, 0.5, epsilon = 0.001);
+    assert_relative_eq!(intersection.normal, Vector::NEG_X, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn batched_raycasts_match_individual_raycasts() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        for x in -5..5 {
+            commands.spawn((
+                RigidBody::Static,
+                Position(Vector::X * x as Scalar * 3.0 + Vector::Y * (10.0 + x as Scalar)),
+                #[cfg(feature = "2d")]
+                Collider::circle(1.0),
+                #[cfg(feature = "3d")]
+                Collider::sphere(1.0),
+            ));
+        }
+    });
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let rays = (-20..20)
+        .map(|x| RayInput::new(Vector::X * x as Scalar * 0.75, Dir::Y, 100.0, true))
+        .collect::<Vec<_>>();
+
+    let hits = pipeline.cast_rays_batch(&rays, SpatialQueryFilter::default());
+
+    assert_eq!(hits.len(), rays.len());
+    assert!(hits.iter().any(Option::is_some));
+    assert!(hits.iter().any(Option::is_none));
+    for (ray, hit) in rays.iter().zip(hits) {
+        let expected = pipeline.cast_ray(
+            ray.origin,
+            ray.direction,
+            ray.max_time_of_impact,
+            ray.solid,
+            SpatialQueryFilter::default(),
+        );
+        assert_eq!(hit, expected);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spatial_queries_are_updated_while_paused() {
+    let mut app = create_app();
+
+    let collider = || {
+        #[cfg(feature = "2d")]
+        {
+            Collider::circle(1.0)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::sphere(1.0)
+        }
+    };
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, Position(Vector::X * 5.0), collider()))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    app.world.resource_mut::<Time<Physics>>().pause();
+
+    // Move the existing collider and add a new one while physics is paused.
+    app.world.get_mut::<Position>(body).unwrap().0 = Vector::X * 10.0;
+    app.world.spawn((
+        RigidBody::Dynamic,
+        Position(Vector::NEG_X * 5.0),
+        collider(),
+    ));
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    for (direction, expected) in [(Dir::X, 9.0), (Dir::NEG_X, 4.0)] {
+        let hit = pipeline
+            .cast_ray(
+                Vector::ZERO,
+                direction,
+                Scalar::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .expect("ray should hit the collider");
+        assert_relative_eq!(hit.time_of_impact, expected, epsilon = 0.001);
+    }
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn casts_against_an_entity_ignore_other_colliders() {
+    let mut app = create_app();
+
+    let collider = |radius: Scalar| {
+        #[cfg(feature = "2d")]
+        {
+            Collider::circle(radius)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::sphere(radius)
+        }
+    };
+    let near = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::X * 3.0), collider(1.0)))
+        .id();
+    let far = app
+        .world
+        .spawn((RigidBody::Static, Position(Vector::X * 6.0), collider(1.0)))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    // The near collider blocks regular casts, but casts against the far collider ignore it.
+    let hit = pipeline
+        .cast_ray_against(far, Vector::ZERO, Dir::X, Scalar::MAX, true)
+        .expect("ray should hit the far collider");
+    assert_eq!(hit.entity, far);
+    assert_relative_eq!(hit.time_of_impact, 5.0, epsilon = 0.001);
+
+    let hit = pipeline
+        .cast_shape_against(
+            far,
+            &collider(0.5),
+            Vector::ZERO,
+            Default::default(),
+            Dir::X,
+            Scalar::MAX,
+            true,
+        )
+        .expect("shape should hit the far collider");
+    assert_eq!(hit.entity, far);
+    assert_relative_eq!(hit.time_of_impact, 4.5, epsilon = 0.001);
+
+    // Casts that miss the given entity don't return hits, even if they would hit other colliders.
+    assert!(pipeline
+        .cast_ray_against(near, Vector::Y * 5.0, Dir::X, Scalar::MAX, true)
+        .is_none());
+    assert!(pipeline
+        .cast_ray_against(far, Vector::ZERO, Dir::NEG_X, Scalar::MAX, true)
+        .is_none());
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn nonlinear_time_of_impact_predicts_collision() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    let collider = Collider::circle(1.0);
+    #[cfg(feature = "3d")]
+    let collider = Collider::sphere(1.0);
+
+    let motion1 = contact_query::ColliderMotion::new(Vector::NEG_X * 3.0, Rotation::default())
+        .with_linear_velocity(Vector::X);
+    let motion2 = contact_query::ColliderMotion::new(Vector::X * 3.0, Rotation::default())
+        .with_linear_velocity(Vector::NEG_X);
+
+    // The gap between the colliders is 4.0, and it closes at 2.0 units per second.
+    let toi = contact_query::nonlinear_time_of_impact(
+        &collider, &motion1, &collider, &motion2, 0.0, 5.0, true,
+    )
+    .unwrap()
+    .expect("colliders should collide");
+    assert_relative_eq!(toi.time_of_impact, 2.0, epsilon = 0.01);
+
+    // Simulate the same motion and check that the bodies come into contact at the predicted time.
+    let [entity1, entity2] = [motion1, motion2].map(|motion| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(motion.position),
+                motion.linear_velocity,
+                collider.clone(),
+            ))
+            .id()
+    });
+
+    // Step until shortly before the predicted time of impact.
+    for _ in 0..(60.0 * (toi.time_of_impact - 0.25)) as usize {
+        tick_60_fps(&mut app);
+    }
+    assert!(!app
+        .world
+        .resource::<Collisions>()
+        .contains(entity1, entity2));
+
+    // Step until shortly after the predicted time of impact. The bodies bounce apart after colliding,
+    // so check that they were in contact at some point.
+    let mut collided = false;
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+        collided |= app
+            .world
+            .resource::<Collisions>()
+            .contains(entity1, entity2);
+    }
+    assert!(collided);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn shape_casts_apply_skin_width_and_initial_overlap_policy() {
+    let mut app = create_app();
+
+    // Walls with their faces at x = 4 and x = -4
+    let wall_collider = || {
+        #[cfg(feature = "2d")]
+        {
+            Collider::rectangle(2.0, 2.0)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::cuboid(2.0, 2.0, 2.0)
+        }
+    };
+    let wall = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            wall_collider(),
+        ))
+        .id();
+    let other_wall = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_X * 5.0),
+            wall_collider(),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    #[cfg(feature = "2d")]
+    let shape = Collider::circle(0.5);
+    #[cfg(feature = "3d")]
+    let shape = Collider::sphere(0.5);
+    let cast = |origin: Vector, direction: Dir, policy: InitialOverlapPolicy| {
+        pipeline
+            .cast_shape_with_config(
+                &shape,
+                origin,
+                Default::default(),
+                direction,
+                &ShapeCastConfig::default()
+                    .with_skin_width(0.1)
+                    .with_initial_overlap(policy),
+                SpatialQueryFilter::default(),
+            )
+            .expect("shape should hit a collider")
+    };
+
+    // The shape stops at the skin width before touching the wall.
+    let hit = cast(Vector::ZERO, Dir::X, InitialOverlapPolicy::Report);
+    assert_eq!(hit.data.entity, wall);
+    assert_relative_eq!(hit.data.time_of_impact, 3.4, epsilon = 0.001);
+    assert_eq!(hit.penetration_depth, 0.0);
+
+    // The shape is initially overlapping the wall by 0.5, or 0.6 including the skin width.
+    let hit = cast(Vector::X * 4.0, Dir::X, InitialOverlapPolicy::Report);
+    assert_eq!(hit.data.entity, wall);
+    assert_eq!(hit.data.time_of_impact, 0.0);
+    assert_relative_eq!(hit.penetration_depth, 0.6, epsilon = 0.001);
+    assert_eq!(hit.depenetration, Vector::ZERO);
+
+    let hit = cast(Vector::X * 4.0, Dir::X, InitialOverlapPolicy::Depenetrate);
+    assert_eq!(hit.data.entity, wall);
+    assert_relative_eq!(hit.depenetration, Vector::NEG_X * 0.6, epsilon = 0.001);
+
+    // Moving away from the overlapped wall ignores it and hits the other wall.
+    let hit = cast(Vector::X * 4.0, Dir::NEG_X, InitialOverlapPolicy::Ignore);
+    assert_eq!(hit.data.entity, other_wall);
+    assert_relative_eq!(hit.data.time_of_impact, 7.4, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn spatial_queries_can_use_rendered_transforms() {
+    let mut app = create_app();
+
+    // Keep the transform of the body separate from its physics pose.
+    app.insert_resource(crate::plugins::sync::SyncConfig {
+        position_to_transform: false,
+        transform_to_position: false,
+    });
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            Position(Vector::X * 5.0),
+            TransformBundle::default(),
+            #[cfg(feature = "2d")]
+            Collider::circle(1.0),
+            #[cfg(feature = "3d")]
+            Collider::sphere(1.0),
+        ))
+        .id();
+
+    let cast_ray = |app: &App| {
+        app.world
+            .resource::<SpatialQueryPipeline>()
+            .cast_ray(
+                Vector::ZERO,
+                Dir::X,
+                Scalar::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .expect("ray should hit the collider")
+            .time_of_impact
+    };
+
+    tick_60_fps(&mut app);
+
+    // Move the rendered transform, like transform interpolation would.
+    app.world.get_mut::<Transform>(body).unwrap().translation.x = 10.0;
+
+    tick_60_fps(&mut app);
+    assert_relative_eq!(cast_ray(&app), 4.0, epsilon = 0.001);
+
+    app.insert_resource(SpatialQueryPoses::Rendered);
+    tick_60_fps(&mut app);
+    assert_relative_eq!(cast_ray(&app), 9.0, epsilon = 0.001);
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn reused_solver_buffers_drop_removed_contacts_and_joints() {
+    let mut app = create_app();
+
+    let collider = || {
+        #[cfg(feature = "2d")]
+        {
+            Collider::circle(0.5)
+        }
+        #[cfg(feature = "3d")]
+        {
+            Collider::sphere(0.5)
+        }
+    };
+
+    // A body resting on the ground and two bodies hanging from anchors with joints.
+    let ground = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::ZERO),
+            #[cfg(feature = "2d")]
+            Collider::rectangle(5.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(5.0, 1.0, 5.0),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, Position(Vector::Y), collider()))
+        .id();
+    let [hanging1, hanging2] = [-10.0, 10.0].map(|x| {
+        let anchor = app
+            .world
+            .spawn((
+                RigidBody::Static,
+                Position(Vector::X * x + Vector::Y * 10.0),
+            ))
+            .id();
+        let hanging = app
+            .world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::X * x + Vector::Y * 9.0),
+                collider(),
+            ))
+            .id();
+        app.world
+            .spawn(DistanceJoint::new(anchor, hanging).with_rest_length(1.0));
+        hanging
+    });
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.resource::<Collisions>().contains(ground, body));
+
+    // Move the body away from the ground and remove the joint of the first hanging body.
+    app.world.get_mut::<Position>(body).unwrap().0 = Vector::Y * 50.0;
+    let joint = app
+        .world
+        .query::<(Entity, &DistanceJoint)>()
+        .iter(&app.world)
+        .find(|(_, joint)| joint.entity2 == hanging1)
+        .unwrap()
+        .0;
+    app.world.despawn(joint);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // Contacts and joints from previous frames are not left behind in the reused buffers.
+    assert!(!app.world.resource::<Collisions>().contains(ground, body));
+    assert!(app.world.get::<Position>(hanging1).unwrap().y < 8.0);
+    assert_relative_eq!(
+        app.world.get::<Position>(hanging2).unwrap().y,
+        9.0,
+        epsilon = 0.1
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn restored_snapshot_resimulates_identically() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let snapshot = PhysicsSnapshot::capture(&mut app.world);
+
+    let run = |app: &mut App| {
+        for _ in 0..30 {
+            tick_60_fps(app);
+        }
+        let mut query = app.world.query::<(&Id, &Position, &Rotation)>();
+        let mut bodies = query
+            .iter(&app.world)
+            .map(|(id, position, rotation)| (*id, *position, *rotation))
+            .collect::<Vec<_>>();
+        bodies.sort_by_key(|(id, _, _)| *id);
+        bodies
+    };
+
+    let first = run(&mut app);
+    snapshot.restore(&mut app.world);
+    let second = run(&mut app);
+
+    assert_eq!(first, second);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn snapshot_restores_islands_and_manifold_caches() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    for _ in 0..90 {
+        tick_60_fps(&mut app);
+    }
+
+    let snapshot = PhysicsSnapshot::capture(&mut app.world);
+    let islands = app.world.resource::<PhysicsIslands>().clone();
+    let cached_pairs = app
+        .world
+        .resource::<ContactManifoldCaches<Collider>>()
+        .len();
+    assert!(cached_pairs > 0);
+
+    // Clear the state that is rebuilt during the step
+    app.world.insert_resource(PhysicsIslands::default());
+    app.world
+        .resource_mut::<ContactManifoldCaches<Collider>>()
+        .clear();
+
+    snapshot.restore(&mut app.world);
+
+    assert_eq!(*app.world.resource::<PhysicsIslands>(), islands);
+    assert_eq!(
+        app.world
+            .resource::<ContactManifoldCaches<Collider>>()
+            .len(),
+        cached_pairs
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn resimulation_matches_original_simulation() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // Drive the simulation manually
+    app.world.resource_mut::<Time<Physics>>().pause();
+
+    let mut cubes = app.world.query::<(Entity, &Id)>();
+    let pushed = cubes
+        .iter(&app.world)
+        .find(|(_, id)| id.0 == 0)
+        .map(|(entity, _)| entity)
+        .unwrap();
+
+    // Push a cube using queued impulses on every step as the input of the frame
+    let push = move |world: &mut World, step: usize| {
+        world.resource_mut::<QueuedImpulses>().push(QueuedImpulse {
+            entity: pushed,
+            impulse: Vector::X * (1.0 + step as Scalar * 0.1),
+            point: None,
+            angular_impulse: Torque::default(),
+            substep: step as u32 % 4,
+        });
+    };
+
+    // An impulse that is still queued when the snapshot is captured
+    app.world
+        .resource_mut::<QueuedImpulses>()
+        .push(QueuedImpulse {
+            entity: pushed,
+            impulse: Vector::Z * 5.0,
+            point: None,
+            angular_impulse: Torque::default(),
+            substep: 2,
+        });
+
+    let delta = Duration::from_secs_f64(1.0 / 60.0);
+    let snapshot = PhysicsSnapshot::capture(&mut app.world);
+
+    for step in 0..20 {
+        push(&mut app.world, step);
+        Physics::step(&mut app.world, delta);
+    }
+    let original = PhysicsSnapshot::capture(&mut app.world);
+
+    Physics::resimulate(&mut app.world, &snapshot, 20, delta, push);
+    let resimulated = PhysicsSnapshot::capture(&mut app.world);
+
+    assert_eq!(
+        original.bodies().collect::<Vec<_>>(),
+        resimulated.bodies().collect::<Vec<_>>()
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn manual_step_advances_paused_physics_once() {
+    let mut app = create_app();
+
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, Collider::sphere(0.5)))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    app.world.resource_mut::<Time<Physics>>().pause();
+    let elapsed = app.world.resource::<Time<Physics>>().elapsed();
+    let position = app.world.get::<Position>(body).unwrap().0;
+
+    let delta = Duration::from_secs_f64(1.0 / 60.0);
+    Physics::step(&mut app.world, delta);
+
+    let stepped_position = app.world.get::<Position>(body).unwrap().0;
+    assert!(stepped_position.y < position.y);
+    assert_eq!(
+        app.world.resource::<Time<Physics>>().elapsed(),
+        elapsed + delta
+    );
+
+    // Updating the app while paused must not repeat the manual step
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    assert_eq!(app.world.get::<Position>(body).unwrap().0, stepped_position);
+    assert_eq!(
+        app.world.resource::<Time<Physics>>().elapsed(),
+        elapsed + delta
+    );
+}
+
+#[test]
+#[cfg(all(
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+fn state_correction_blends_body_to_remote_state() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    let entity = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            #[cfg(feature = "2d")]
+            MassPropertiesBundle::new_computed(&Collider::circle(0.5), 1.0),
+            #[cfg(feature = "3d")]
+            MassPropertiesBundle::new_computed(&Collider::sphere(0.5), 1.0),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let target = RemoteBodyState::new(
+        Position(Vector::X),
+        Rotation::default(),
+        LinearVelocity::ZERO,
+        AngularVelocity::ZERO,
+    );
+    app.world
+        .entity_mut(entity)
+        .insert(StateCorrection::new(target).with_blend_duration(0.5));
+
+    tick_60_fps(&mut app);
+
+    // The body should not be teleported.
+    let position = app.world.get::<Position>(entity).unwrap().x;
+    assert!(position > 0.0 && position < 0.5);
+
+    for _ in 0..40 {
+        tick_60_fps(&mut app);
+    }
+
+    // The correction should be finished.
+    assert!(app.world.get::<StateCorrection>(entity).is_none());
+    assert_relative_eq!(
+        app.world.get::<Position>(entity).unwrap().x,
+        1.0,
+        epsilon = 1e-4
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn applied_snapshot_delta_matches_target_snapshot() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+    let base = PhysicsSnapshot::capture(&mut app.world);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+    let target = PhysicsSnapshot::capture(&mut app.world);
+
+    let quantization = 0.001;
+    let delta = target.delta_from(&base, quantization);
+
+    // The static floor hasn't moved, so only the cubes are included.
+    assert_eq!(delta.bodies().count(), 64);
+    assert!(delta.removed().is_empty());
+
+    let mut applied = base.clone();
+    applied.apply_delta(&delta);
+
+    for (entity, body) in target.bodies() {
+        let applied_body = applied.body(entity).unwrap();
+        assert!(body
+            .position
+            .abs_diff_eq(applied_body.position.0, quantization));
+        assert!(body.rotation.abs_diff_eq(applied_body.rotation.0, 1e-3));
+    }
+
+    // Snapshots don't differ from themselves.
+    assert!(target.delta_from(&target, quantization).is_empty());
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn replay_matches_recorded_simulation() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    PhysicsRecorder::start(&mut app.world);
+
+    for i in 0..30 {
+        if i == 5 {
+            // Push a cube and spawn a new one during the recording
+            let mut query = app.world.query::<(&Id, &mut ExternalImpulse)>();
+            for (_, mut impulse) in query.iter_mut(&mut app.world).filter(|(id, _)| id.0 == 0) {
+                impulse.apply_impulse(Vector::X * 10.0);
+            }
+            app.world.spawn((
+                RigidBody::Dynamic,
+                Position(Vector::Y * 30.0),
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ));
+        }
+        tick_60_fps(&mut app);
+    }
+
+    let recording = PhysicsRecorder::stop(&mut app.world).unwrap();
+    let original = PhysicsSnapshot::capture(&mut app.world);
+
+    PhysicsReplay::start(&mut app.world, recording);
+
+    while !app.world.resource::<PhysicsReplay>().is_finished() {
+        tick_60_fps(&mut app);
+    }
+
+    let replay = app.world.resource::<PhysicsReplay>().clone();
+    let replayed = PhysicsSnapshot::capture(&mut app.world);
+
+    for (entity, body) in original.bodies() {
+        let replayed_body = replayed.body(replay.map_entity(entity)).unwrap();
+        assert_eq!(body.position, replayed_body.position);
+        assert_eq!(body.rotation, replayed_body.rotation);
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn predicted_island_contains_touching_bodies() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(40.0, 1.0, 40.0),
+    ));
+    let predicted = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 0.5),
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Predicted,
+        ))
+        .id();
+    let stacked = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 1.5),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+    app.world.spawn((
+        RigidBody::Dynamic,
+        Position(Vector::new(10.0, 0.5, 0.0)),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    ));
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The floor and the separate cube are not in the island.
+    let island = predicted_island(&mut app.world);
+    assert_eq!(island, vec![predicted, stacked]);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn resting_contacts_keep_their_feature_ids() {
+    let mut app = create_app();
+
+    let floor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            Collider::cuboid(40.0, 1.0, 40.0),
+        ))
+        .id();
+    let cube = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 0.5),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+
+    let contact_ids = |app: &App| {
+        let contacts = app.world.resource::<Collisions>().get(floor, cube).unwrap();
+        let mut ids = contacts
+            .manifolds
+            .iter()
+            .flat_map(|manifold| manifold.contacts.iter().map(|contact| contact.id()))
+            .collect::<Option<Vec<_>>>()
+            .expect("contacts should have known feature IDs");
+        ids.sort();
+        ids
+    };
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+    let ids = contact_ids(&app);
+
+    tick_60_fps(&mut app);
+
+    assert!(!ids.is_empty());
+    assert_eq!(ids, contact_ids(&app));
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider", feature = "serialize"))]
+#[test]
+fn colliders_and_joints_round_trip_through_ron() {
+    let mut app = create_app();
+
+    let cube = Collider::convex_hull(
+        [-0.5, 0.5]
+            .into_iter()
+            .flat_map(|x| [-0.5, 0.5].map(|y| (x, y)))
+            .flat_map(|(x, y)| [-0.5, 0.5].map(|z| Vector::new(x, y, z)))
+            .collect(),
+    )
+    .unwrap();
+    let floor = Collider::trimesh(
+        vec![
+            Vector::new(-10.0, 0.0, -10.0),
+            Vector::new(10.0, 0.0, -10.0),
+            Vector::new(10.0, 0.0, 10.0),
+            Vector::new(-10.0, 0.0, 10.0),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+    );
+
+    let anchor = app.world.spawn(RigidBody::Static).id();
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, Position(Vector::X), cube.clone()))
+        .id();
+    app.world.spawn(
+        RevoluteJoint::new(anchor, body)
+            .with_local_anchor_2(Vector::NEG_X)
+            .with_aligned_axis(Vector::Z),
+    );
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // Joints keep their state, like the forces exerted by them.
+    let joint = *app.world.query::<&RevoluteJoint>().single(&app.world);
+    let deserialized: RevoluteJoint = ron::from_str(&ron::to_string(&joint).unwrap()).unwrap();
+    assert_eq!(joint, deserialized);
+
+    // Convex and trimesh colliders keep their shapes.
+    let round_trip = |collider: &Collider| -> Collider {
+        ron::from_str(&ron::to_string(collider).unwrap()).unwrap()
+    };
+    let cube = round_trip(&cube);
+    let floor = round_trip(&floor);
+    assert!(cube.shape().as_convex_polyhedron().is_some());
+    assert!(floor.shape().as_trimesh().is_some());
+
+    // The deserialized colliders still collide with each other.
+    let mut app = create_app();
+    app.world.spawn((RigidBody::Static, floor));
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, Position(Vector::Y * 3.0), cube))
+        .id();
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = app.world.get::<Position>(body).unwrap();
+    assert_relative_eq!(position.y, 0.5, epsilon = 0.05);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider", feature = "serialize"))]
+#[test]
+fn physics_scene_round_trips_through_ron_and_bytes() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    let scene = PhysicsScene::capture(&mut app.world);
+    let from_ron = PhysicsScene::from_ron(&scene.to_ron().unwrap()).unwrap();
+    let from_bytes = PhysicsScene::from_bytes(&scene.to_bytes().unwrap()).unwrap();
+
+    let positions = |scene: &PhysicsScene| {
+        scene
+            .entities
+            .iter()
+            .map(|entity| entity.position)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(positions(&scene), positions(&from_ron));
+    assert_eq!(positions(&scene), positions(&from_bytes));
+
+    // Spawn the scene in a new world.
+    let mut new_app = create_app();
+    let entity_map = from_bytes.spawn(&mut new_app.world);
+    tick_60_fps(&mut new_app);
+
+    assert_eq!(entity_map.len(), scene.entities.len());
+    for entity in scene.entities.iter() {
+        let spawned = entity_map[&entity.id];
+        assert_eq!(
+            new_app.world.get::<RigidBody>(spawned).copied(),
+            entity.rigid_body
+        );
+        assert!(new_app.world.get::<Collider>(spawned).is_some());
+    }
+
+    // Scenes with other versions are rejected.
+    let mut old_scene = scene.clone();
+    old_scene.version = PhysicsScene::VERSION + 1;
+    assert!(matches!(
+        PhysicsScene::from_ron(&old_scene.to_ron().unwrap()),
+        Err(PhysicsSceneError::UnsupportedVersion { .. })
+    ));
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn stacked_bodies_sleep_and_wake_as_an_island() {
+    let mut app = create_app();
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(40.0, 1.0, 40.0),
+    ));
+    let stack = (0..3)
+        .map(|i| {
+            app.world
+                .spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::Y * (0.5 + i as Scalar)),
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..180 {
+        tick_60_fps(&mut app);
+    }
+
+    // The stack forms a single island that is sleeping as a whole.
+    let islands = app.world.resource::<PhysicsIslands>();
+    assert_eq!(islands.island(stack[0]), Some(stack.as_slice()));
+    assert!(stack
+        .iter()
+        .all(|entity| app.world.get::<Sleeping>(*entity).is_some()));
+
+    // Waking up the top body wakes up the whole stack.
+    app.world.get_mut::<LinearVelocity>(stack[2]).unwrap().0 = Vector::X;
+    tick_60_fps(&mut app);
+
+    assert!(stack
+        .iter()
+        .all(|entity| app.world.get::<Sleeping>(*entity).is_none()));
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn impulse_wakes_up_jointed_bodies() {
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    let body1 = app
+        .world
+        .spawn((RigidBody::Dynamic, Collider::sphere(0.4)))
+        .id();
+    let body2 = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X),
+            Collider::sphere(0.4),
+        ))
+        .id();
+    app.world
+        .spawn(FixedJoint::new(body1, body2).with_local_anchor_1(Vector::X));
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world.get::<Sleeping>(body1).is_some());
+    assert!(app.world.get::<Sleeping>(body2).is_some());
+
+    let body2_position = app.world.get::<Position>(body2).unwrap().0;
+
+    // Applying an impulse to one of the bodies wakes up both of them, and the impulse isn't lost.
+    app.world
+        .get_mut::<ExternalImpulse>(body1)
+        .unwrap()
+        .apply_impulse(Vector::Y);
+    tick_60_fps(&mut app);
+
+    assert!(app.world.get::<Sleeping>(body1).is_none());
+    assert!(app.world.get::<Sleeping>(body2).is_none());
+    assert!(app.world.get::<Position>(body1).unwrap().y > 0.0);
+    assert_ne!(app.world.get::<Position>(body2).unwrap().0, body2_position);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn sleeping_colliders_are_stored_with_static_colliders() {
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::X * 2.0),
+            Collider::sphere(0.5),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    assert!(app.world.get::<Sleeping>(body).is_some());
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    assert!(pipeline.static_colliders.contains_key(&body));
+    assert!(!pipeline.colliders.contains_key(&body));
+    assert!(app
+        .world
+        .resource::<crate::plugins::collision::broad_phase::AabbIntervals>()
+        .0
+        .iter()
+        .all(|interval| interval.0 != body));
+
+    // Sleeping colliders can still be hit by spatial queries.
+    let hit = pipeline.cast_ray(
+        Vector::ZERO,
+        Dir::X,
+        Scalar::MAX,
+        true,
+        SpatialQueryFilter::default(),
+    );
+    assert_eq!(hit.map(|hit| hit.entity), Some(body));
+
+    // Waking the body moves the collider back to the moving colliders.
+    app.world
+        .get_mut::<ExternalImpulse>(body)
+        .unwrap()
+        .apply_impulse(Vector::Y);
+    tick_60_fps(&mut app);
+
+    assert!(app.world.get::<Sleeping>(body).is_none());
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    assert!(!pipeline.static_colliders.contains_key(&body));
+    assert!(pipeline.colliders.contains_key(&body));
+    assert!(app
+        .world
+        .resource::<crate::plugins::collision::broad_phase::AabbIntervals>()
+        .0
+        .iter()
+        .any(|interval| interval.0 == body));
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn waking_bodies_only_update_their_static_tree_leaves() {
+    use crate::plugins::collision::broad_phase::StaticAabbTree;
+
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    for i in 0..9 {
+        app.world.spawn((
+            RigidBody::Static,
+            Position(Vector::new(i as Scalar * 2.0, -5.0, 0.0)),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ));
+    }
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 5.0),
+            Collider::sphere(0.5),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The 9 static colliders and the sleeping collider are stored in 10 leaves and 9 internal nodes.
+    assert!(app.world.get::<Sleeping>(body).is_some());
+    let tree = app.world.resource::<StaticAabbTree>();
+    assert!(tree.contains(body));
+    assert_eq!(tree.allocated_nodes(), 19);
+    let static_proxies = app
+        .world
+        .resource::<SpatialQueryPipeline>()
+        .static_qbvh
+        .raw_proxies()
+        .len();
+
+    app.world
+        .get_mut::<ExternalImpulse>(body)
+        .unwrap()
+        .apply_impulse(Vector::Y);
+    tick_60_fps(&mut app);
+
+    // Only the leaf of the woken collider and its parent are freed.
+    // Rebuilding the tree of the 9 static colliders would only allocate 17 nodes.
+    assert!(app.world.get::<Sleeping>(body).is_none());
+    let tree = app.world.resource::<StaticAabbTree>();
+    assert!(!tree.contains(body));
+    assert_eq!(tree.allocated_nodes(), 19);
+
+    // The leaf of the woken collider is also removed from the static spatial query tree without rebuilding it.
+    let static_qbvh = &app.world.resource::<SpatialQueryPipeline>().static_qbvh;
+    assert_eq!(static_qbvh.raw_proxies().len(), static_proxies);
+    assert_eq!(
+        static_qbvh.raw_proxies()[body.index() as usize].node.index,
+        u32::MAX
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn spatial_queries_see_moved_colliders_after_refit() {
+    let mut app = create_app();
+
+    let bodies: Vec<Entity> = [2.0, 4.0, 6.0]
+        .into_iter()
+        .map(|x| {
+            app.world
+                .spawn((
+                    RigidBody::Kinematic,
+                    Position(Vector::X * x),
+                    Collider::sphere(0.5),
+                ))
+                .id()
+        })
+        .collect();
+
+    tick_60_fps(&mut app);
+
+    let first_hit = |app: &App| {
+        app.world
+            .resource::<SpatialQueryPipeline>()
+            .cast_ray(
+                Vector::ZERO,
+                Dir::X,
+                Scalar::MAX,
+                true,
+                SpatialQueryFilter::default(),
+            )
+            .map(|hit| (hit.entity, hit.time_of_impact))
+    };
+
+    let (entity, time_of_impact) = first_hit(&app).unwrap();
+    assert_eq!(entity, bodies[0]);
+    assert_relative_eq!(time_of_impact, 1.5, epsilon = 0.001);
+
+    // Move the closest body out of the way of the ray. The set of colliders is the same,
+    // so the tree is refitted instead of rebuilt.
+    app.world.get_mut::<Position>(bodies[0]).unwrap().0 = Vector::Y * 10.0;
+    tick_60_fps(&mut app);
+
+    let (entity, time_of_impact) = first_hit(&app).unwrap();
+    assert_eq!(entity, bodies[1]);
+    assert_relative_eq!(time_of_impact, 3.5, epsilon = 0.001);
+
+    // Move the body slightly closer to the ray origin.
+    app.world.get_mut::<Position>(bodies[1]).unwrap().0 = Vector::X * 3.9;
+    tick_60_fps(&mut app);
+
+    let (entity, time_of_impact) = first_hit(&app).unwrap();
+    assert_eq!(entity, bodies[1]);
+    assert_relative_eq!(time_of_impact, 3.4, epsilon = 0.001);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn cached_contact_manifolds_match_uncached() {
+    let collider1 = Collider::cuboid(1.0, 1.0, 1.0);
+    let collider2 = Collider::cuboid(1.0, 1.0, 1.0);
+    let mut cache = ContactManifoldCache::default();
+
+    for i in 0..20 {
+        let position2 = Vector::new(0.05 * i as Scalar, 0.9, 0.0);
+        let rotation2 = Quaternion::from_rotation_y(0.02 * i as Scalar);
+
+        let uncached = collider1.contact_manifolds(
+            &collider2,
+            Vector::ZERO,
+            Quaternion::IDENTITY,
+            position2,
+            rotation2,
+            0.0,
+        );
+        let cached = collider1.contact_manifolds_with_cache(
+            &collider2,
+            Vector::ZERO,
+            Quaternion::IDENTITY,
+            position2,
+            rotation2,
+            0.0,
+            &mut cache,
+        );
+
+        assert_eq!(cached.len(), uncached.len());
+        for (cached, uncached) in cached.iter().zip(uncached.iter()) {
+            assert_relative_eq!(cached.normal1, uncached.normal1, epsilon = 1e-4);
+            assert_eq!(cached.contacts.len(), uncached.contacts.len());
+            let max_penetration = |manifold: &ContactManifold| {
+                manifold
+                    .contacts
+                    .iter()
+                    .map(|contact| contact.penetration)
+                    .fold(Scalar::MIN, Scalar::max)
+            };
+            assert_relative_eq!(
+                max_penetration(cached),
+                max_penetration(uncached),
+                epsilon = 1e-4
+            );
+        }
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn memory_usage_is_reported() {
+    let mut app = create_app();
+    app.insert_resource(PhysicsMemoryUsage::default());
+
+    let shape = Collider::cuboid(1.0, 1.0, 1.0);
+    app.world
+        .spawn((RigidBody::Static, Collider::cuboid(10.0, 1.0, 10.0)));
+    for i in 0..3 {
+        app.world.spawn((
+            RigidBody::Dynamic,
+            Position(Vector::new(2.0 * i as Scalar, 0.9, 0.0)),
+            shape.clone(),
+        ));
+    }
+
+    tick_60_fps(&mut app);
+
+    let memory_usage = *app.world.resource::<PhysicsMemoryUsage>();
+
+    // At least the floor and the shared cuboid shape
+    assert!(memory_usage.shape_count >= 2);
+    assert!(memory_usage.shapes > 0);
+    assert!(memory_usage.contacts > 0);
+    assert!(memory_usage.broad_phase > 0);
+    assert_eq!(memory_usage.joints, 0);
+    assert!(memory_usage.total() >= memory_usage.shapes + memory_usage.contacts);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn diagnostics_count_bodies_and_contacts() {
+    use bevy::diagnostic::DiagnosticsStore;
+
+    let mut app = create_app();
+    app.add_plugins(PhysicsDiagnosticsPlugin::default());
+
+    app.world
+        .spawn((RigidBody::Static, Collider::cuboid(10.0, 1.0, 10.0)));
+    app.world.spawn((
+        RigidBody::Dynamic,
+        Position(Vector::Y),
+        Collider::cuboid(1.0, 1.0, 1.0),
+    ));
+
+    // Let the body settle on the floor
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    let value = |path| {
+        app.world
+            .resource::<DiagnosticsStore>()
+            .get(&path)
+            .and_then(|diagnostic| diagnostic.value())
+            .unwrap()
+    };
+
+    assert_eq!(value(PhysicsDiagnosticsPlugin::BODIES), 2.0);
+    assert_eq!(value(PhysicsDiagnosticsPlugin::ACTIVE_BODIES), 1.0);
+    assert_eq!(value(PhysicsDiagnosticsPlugin::PAIRS), 1.0);
+    assert!(value(PhysicsDiagnosticsPlugin::CONTACTS) > 0.0);
+    assert!(value(PhysicsDiagnosticsPlugin::STEP) > 0.0);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn constraint_graph_contains_contacts_and_joints() {
+    let mut app = create_app();
+
+    let floor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Collider::cuboid(10.0, 1.0, 10.0),
+            Name::new("Floor \"1\""),
+        ))
+        .id();
+    let body1 = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+    let body2 = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::new(0.0, 1.0, 3.0)),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+    app.world
+        .spawn(DistanceJoint::new(body1, body2).with_rest_length(3.0));
+
+    // Let the bodies settle on the floor
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    let graph = ConstraintGraph::capture(&mut app.world);
+
+    let nodes = graph
+        .nodes
+        .iter()
+        .map(|node| node.entity)
+        .collect::<Vec<_>>();
+    assert_eq!(nodes, vec![floor, body1, body2]);
+
+    let contacts = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.kind == ConstraintKind::Contact)
+        .collect::<Vec<_>>();
+    assert_eq!(contacts.len(), 2);
+    assert!(contacts.iter().all(|edge| edge.impulse > 0.0));
+    assert!(graph
+        .edges
+        .iter()
+        .any(|edge| edge.kind == ConstraintKind::DistanceJoint
+            && edge.entity1 == body1
+            && edge.entity2 == body2));
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("graph constraints {"));
+    assert!(dot.contains(&format!("\"{body1:?}\" -- \"{body2:?}\"")));
+    assert!(dot.contains("Floor \\\"1\\\""));
+
+    let json = graph.to_json();
+    assert!(json.contains(&format!("{{\"entity\":{},", floor.to_bits())));
+    assert!(json.contains("\"kind\":\"distance_joint\""));
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn stepper_advances_by_steps_substeps_and_substep_sets() {
+    let run = |requests: &[PhysicsStepUnit]| {
+        let mut app = create_app();
+        app.add_plugins(PhysicsStepperPlugin::default())
+            .insert_resource(Gravity(Vector::ZERO))
+            .insert_resource(SubstepCount(4));
+        app.world.resource_mut::<PhysicsStepper>().enabled = true;
+
+        let body = app
+            .world
+            .spawn((
+                RigidBody::Dynamic,
+                Collider::sphere(0.5),
+                LinearVelocity(Vector::X),
+            ))
+            .id();
+
+        tick_60_fps(&mut app);
+        for unit in requests {
+            app.world.resource_mut::<PhysicsStepper>().request(*unit);
+            tick_60_fps(&mut app);
+        }
+
+        let stepper = app.world.resource::<PhysicsStepper>();
+        (
+            stepper.is_mid_step(),
+            app.world.get::<Position>(body).unwrap().x,
+        )
+    };
+
+    // Nothing is simulated unless requested.
+    let (mid_step, initial) = run(&[]);
+    assert!(!mid_step);
+    assert_eq!(initial, 0.0);
+
+    let (mid_step, stepped) = run(&[PhysicsStepUnit::Step]);
+    assert!(!mid_step);
+    assert!(stepped > initial);
+
+    // The intermediate state of a step can be inspected.
+    let (mid_step, half_stepped) = run(&[PhysicsStepUnit::Substep; 2]);
+    assert!(mid_step);
+    assert!(half_stepped > initial && half_stepped < stepped);
+
+    // Running all substeps or substep sets one by one produces the same result as a whole step.
+    let (mid_step, substepped) = run(&[PhysicsStepUnit::Substep; 4]);
+    assert!(!mid_step);
+    assert_relative_eq!(substepped, stepped, epsilon = 1e-6);
+
+    let (mid_step, set_stepped) = run(&[PhysicsStepUnit::SubstepSet; 4 * 9]);
+    assert!(!mid_step);
+    assert_relative_eq!(set_stepped, stepped, epsilon = 1e-6);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn static_geometry_is_updated_when_changed() {
+    let mut app = create_app();
+
+    let tiles = (-5..5)
+        .map(|x| {
+            app.world
+                .spawn((
+                    RigidBody::Static,
+                    Position(Vector::new(x as Scalar, -0.5, 0.0)),
+                    Collider::cuboid(0.9, 1.0, 0.9),
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+    let ball = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 2.0),
+            Collider::sphere(0.4),
+            SleepingDisabled,
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    // The ball rests on the static tiles.
+    let position = app.world.get::<Position>(ball).unwrap();
+    assert_relative_eq!(position.y, 0.4, epsilon = 0.05);
+
+    let cast_down = |app: &App, x: Scalar| {
+        app.world
+            .resource::<SpatialQueryPipeline>()
+            .cast_ray(
+                Vector::new(x, 10.0, 0.0),
+                Dir::NEG_Y,
+                Scalar::MAX,
+                true,
+                SpatialQueryFilter::from_excluded_entities([ball]),
+            )
+            .map(|hit| hit.entity)
+    };
+    assert_eq!(cast_down(&app, -5.0), Some(tiles[0]));
+
+    // Moving a static tile updates both the broad phase and spatial queries.
+    app.world.get_mut::<Position>(tiles[0]).unwrap().0 = Vector::new(-20.0, -0.5, 0.0);
+    tick_60_fps(&mut app);
+    assert_eq!(cast_down(&app, -5.0), None);
+    assert_eq!(cast_down(&app, -20.0), Some(tiles[0]));
+
+    // Making the tile under the ball dynamic lets the ball and the tile fall.
+    *app.world.get_mut::<RigidBody>(tiles[5]).unwrap() = RigidBody::Dynamic;
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Position>(ball).unwrap().y < -1.0);
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn headless_app_steps_once_per_tick() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, PhysicsPlugins::headless()));
+    #[cfg(feature = "async-collider")]
+    {
+        app.add_plugins((
+            bevy::asset::AssetPlugin::default(),
+            bevy::scene::ScenePlugin,
+        ))
+        .init_resource::<Assets<Mesh>>();
+    }
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::cuboid(1.0, 1.0, 1.0),
+            Transform::default(),
+        ))
+        .id();
+
+    let delta = Duration::from_secs_f64(1.0 / 60.0);
+    for step in 1..=10 {
+        app.step_physics(delta);
+        assert_eq!(
+            app.world.resource::<Time<Physics>>().elapsed(),
+            delta * step
+        );
+    }
+
+    // The body has moved, but the transform isn't synchronized in headless apps.
+    assert!(app.world.get::<Position>(body).unwrap().y < 0.0);
+    assert_eq!(
+        app.world.get::<Transform>(body),
+        Some(&Transform::default())
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn mass_properties_of_many_colliders_are_summed() {
+    let mut app = create_app();
+
+    // Spawn a large batch of child colliders in a single frame, like the pieces of a fractured object.
+    let body = app
+        .world
+        .spawn((RigidBody::Dynamic, TransformBundle::default()))
+        .with_children(|children| {
+            for i in 0..200 {
+                children.spawn((
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                    TransformBundle::from_transform(Transform::from_xyz(i as f32, 0.0, 0.0)),
+                ));
+            }
+        })
+        .id();
+
+    tick_60_fps(&mut app);
+
+    assert_relative_eq!(
+        app.world.get::<Mass>(body).unwrap().0,
+        200.0,
+        epsilon = 1e-3
+    );
+    assert_relative_eq!(
+        app.world.get::<CenterOfMass>(body).unwrap().x,
+        99.5,
+        epsilon = 1e-3
+    );
+
+    // Double the density of all colliders at once.
+    let children = app.world.get::<Children>(body).unwrap().to_vec();
+    for child in children {
+        app.world.entity_mut(child).insert(ColliderDensity(2.0));
+    }
+
+    tick_60_fps(&mut app);
+
+    assert_relative_eq!(
+        app.world.get::<Mass>(body).unwrap().0,
+        400.0,
+        epsilon = 1e-3
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn unchanged_transforms_are_not_written() {
+    let mut app = create_app();
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::sphere(0.5),
+            GravityScale(0.0),
+            SleepingDisabled,
+            TransformBundle::default(),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    let last_changed = app
+        .world
+        .entity(body)
+        .get_ref::<Transform>()
+        .unwrap()
+        .last_changed();
+
+    // The body is stationary, so its position is integrated to the same value every step.
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+    assert_eq!(
+        app.world
+            .entity(body)
+            .get_ref::<Transform>()
+            .unwrap()
+            .last_changed(),
+        last_changed
+    );
+
+    // Moving the body writes to the transform again.
+    app.world.get_mut::<LinearVelocity>(body).unwrap().x = 1.0;
+    tick_60_fps(&mut app);
+    assert_ne!(
+        app.world
+            .entity(body)
+            .get_ref::<Transform>()
+            .unwrap()
+            .last_changed(),
+        last_changed
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn teleported_sleeping_bodies_write_transforms() {
+    let mut app = create_app();
+    app.insert_resource(Gravity::ZERO);
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::sphere(0.5),
+            TransformBundle::default(),
+        ))
+        .id();
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+    assert!(app.world.get::<Sleeping>(body).is_some());
+
+    // Pause the simulation so that the body isn't woken up by the teleport before the transform is written.
+    // The step of the previous frame is still finished on the first paused frame.
+    app.world.resource_mut::<Time<Physics>>().pause();
+    tick_60_fps(&mut app);
+    app.world.get_mut::<Position>(body).unwrap().0 = Vector::X * 5.0;
+    tick_60_fps(&mut app);
+
+    assert!(app.world.entity(body).contains::<Sleeping>());
+    assert_eq!(
+        app.world.get::<Transform>(body).unwrap().translation,
+        Vec3::X * 5.0
+    );
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn cubes_simulation_passes_determinism_check() {
+    let create_cubes_app = || {
+        let mut app = create_app();
+        app.add_systems(Startup, setup_cubes_simulation);
+        app
+    };
+
+    let result = crate::plugins::checksum::check_determinism(
+        create_cubes_app,
+        3,
+        120,
+        Duration::from_secs_f64(1.0 / 60.0),
+    );
+
+    assert_eq!(result.err(), None);
+}
+
+#[test]
+fn checksum_detects_single_ulp_changes() {
+    let mut app = create_app();
+    app.insert_resource(PhysicsChecksum::default());
+
+    let bodies = [Vector::X, Vector::Y * 2.0].map(|position| {
+        app.world
+            .spawn((RigidBody::Kinematic, Position(position)))
+            .id()
+    });
+
+    tick_60_fps(&mut app);
+    let checksum = app.world.resource::<PhysicsChecksum>().value;
+
+    tick_60_fps(&mut app);
+    assert_eq!(app.world.resource::<PhysicsChecksum>().value, checksum);
+
+    // Change the position of a body by a single ulp.
+    let mut position = app.world.get_mut::<Position>(bodies[0]).unwrap();
+    position.x = Scalar::from_bits(position.x.to_bits() + 1);
+
+    tick_60_fps(&mut app);
+    assert_ne!(app.world.resource::<PhysicsChecksum>().value, checksum);
+
+    // Swapping the states of the bodies also changes the checksum.
+    let mut app = create_app();
+    app.insert_resource(PhysicsChecksum::default());
+    for position in [Vector::Y * 2.0, Vector::X] {
+        app.world.spawn((RigidBody::Kinematic, Position(position)));
+    }
+
+    tick_60_fps(&mut app);
+    assert_ne!(app.world.resource::<PhysicsChecksum>().value, checksum);
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+struct Id(usize);
+
+#[cfg(all(feature = "3d", feature = "enhanced-determinism"))]
+#[test]
+fn cubes_simulation_is_deterministic_across_machines() {
+    setup_insta!();
+    let mut app = create_app();
+
+    app.add_systems(Startup, setup_cubes_simulation);
+
+    const SECONDS: usize = 10;
+    const UPDATES: usize = 60 * SECONDS;
+
+    for _ in 0..UPDATES {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Transform)>();
+
+    let mut bodies: Vec<(&Id, &Transform)> = app_query.iter(&app.world).collect();
+    bodies.sort_by_key(|b| b.0);
+
+    assert_debug_snapshot!(bodies);
+}
+
+#[cfg(all(
+    feature = "3d",
+    feature = "default-collider",
+    feature = "gpu-broad-phase"
+))]
+#[test]
+fn gpu_broad_phase_falls_back_to_cpu_without_render_device() {
+    fn run_cubes(gpu_broad_phase: bool) -> Vec<(Id, Position)> {
+        let mut app = create_app();
+
+        if gpu_broad_phase {
+            app.add_plugins(GpuBroadPhasePlugin);
+        }
+        app.add_systems(Startup, setup_cubes_simulation);
+
+        for _ in 0..120 {
+            tick_60_fps(&mut app);
+        }
+
+        let mut bodies = app
+            .world
+            .query::<(&Id, &Position)>()
+            .iter(&app.world)
+            .map(|(id, position)| (*id, *position))
+            .collect::<Vec<_>>();
+        bodies.sort_by_key(|(id, _)| *id);
+        bodies
+    }
+
+    // Without a render device, the GPU broad phase collects the same pairs as the CPU broad phase.
+    let bodies = run_cubes(true);
+    assert!(bodies.iter().all(|(_, position)| position.y > -1.0));
+    assert_eq!(bodies, run_cubes(false));
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn cubes_simulation_is_locally_deterministic() {
+    use itertools::Itertools;
+
+    fn run_cubes() -> Vec<(Id, Transform)> {
+        let mut app = create_app();
+
+        app.add_systems(Startup, setup_cubes_simulation);
+
+        const SECONDS: usize = 5;
+        const UPDATES: usize = 60 * SECONDS;
+
+        for _ in 0..UPDATES {
+            tick_60_fps(&mut app);
+        }
+
+        let mut app_query = app.world.query::<(&Id, &Transform)>();
+
+        let mut bodies: Vec<(Id, Transform)> = app_query
+            .iter(&app.world)
+            .map(|(id, transform)| (*id, *transform))
+            .collect();
+        bodies.sort_by_key(|b| b.0);
+        bodies
+    }
+
+    // run simulation and check that results are equal each time
+    for (a, b) in (0..4).map(|_| run_cubes()).tuple_windows() {
+        assert_eq!(a, b);
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "default-collider"))]
+#[test]
+fn simulation_is_independent_of_spawn_order() {
+    fn run_stack(reverse: bool) -> Vec<(StableId, Position)> {
+        let mut app = create_app();
+
+        let mut bodies = vec![(
+            StableId(0),
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            Collider::cuboid(40.0, 1.0, 40.0),
+        )];
+        for i in 1..=8 {
+            bodies.push((
+                StableId(i),
+                RigidBody::Dynamic,
+                Position(Vector::new(0.1 * i as Scalar, i as Scalar * 1.1, 0.0)),
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ));
+        }
+        if reverse {
+            bodies.reverse();
+        }
+        app.world.spawn_batch(bodies);
+
+        for _ in 0..120 {
+            tick_60_fps(&mut app);
+        }
+
+        let mut query = app.world.query::<(&StableId, &Position)>();
+        let mut bodies = query
+            .iter(&app.world)
+            .map(|(id, position)| (*id, *position))
+            .collect::<Vec<_>>();
+        bodies.sort_by_key(|(id, _)| *id);
+        bodies
+    }
+
+    assert_eq!(run_stack(false), run_stack(true));
+}
+
+#[test]
+fn step_budget_drops_or_stretches_steps() {
+    #[derive(Resource, Default)]
+    struct StepCount(usize);
+
+    /// The time simulated by the physics steps and substeps.
+    #[derive(Resource, Default)]
+    struct SimulatedTime {
+        steps: Duration,
+        substeps: Duration,
+    }
+
+    fn advance_time(app: &mut App, duration: Duration) {
+        let mut update_strategy = app.world.resource_mut::<TimeUpdateStrategy>();
+        let TimeUpdateStrategy::ManualInstant(prev_time) = *update_strategy else {
+            unimplemented!()
+        };
+        *update_strategy = TimeUpdateStrategy::ManualInstant(prev_time + duration);
+        app.update();
+    }
+
+    fn last_event(app: &App) -> PhysicsStepBudgetExceeded {
+        let events = app.world.resource::<Events<PhysicsStepBudgetExceeded>>();
+        *events.get_reader().read(events).last().unwrap()
+    }
+
+    let mut app = create_app();
+    app.insert_resource(Time::new_with(Physics::from_timestep(
+        TimestepMode::Fixed {
+            delta: Duration::from_secs_f64(1.0 / 60.0),
+            overstep: Duration::ZERO,
+            max_delta_overstep: Duration::from_secs(1),
+        },
+    )))
+    .insert_resource(PhysicsStepBudget {
+        max_duration: Duration::ZERO,
+        policy: StepBudgetPolicy::Drop,
+    })
+    .init_resource::<StepCount>()
+    .init_resource::<SimulatedTime>()
+    .add_systems(
+        PhysicsSchedule,
+        (|mut count: ResMut<StepCount>,
+          mut simulated: ResMut<SimulatedTime>,
+          physics_time: Res<Time<Physics>>,
+          substep_time: Res<Time<Substeps>>,
+          substep_count: Res<SubstepCount>| {
+            count.0 += 1;
+            simulated.steps += physics_time.delta();
+            simulated.substeps += substep_time.delta() * substep_count.0;
+        })
+        .in_set(PhysicsStepSet::SpatialQuery),
+    );
+
+    tick_60_fps(&mut app);
+    app.world.resource_mut::<StepCount>().0 = 0;
+
+    // Four steps are queued, but only one fits in the budget.
+    advance_time(&mut app, Duration::from_secs_f64(4.5 / 60.0));
+
+    assert_eq!(app.world.resource::<StepCount>().0, 1);
+    let event = last_event(&app);
+    assert_eq!(event.queued_steps, 4);
+    assert_eq!(event.completed_steps, 1);
+    assert_eq!(event.remaining_steps(), 3);
+
+    // With the stretch policy, the remaining steps are simulated in one additional step.
+    app.world.resource_mut::<PhysicsStepBudget>().policy = StepBudgetPolicy::Stretch;
+    app.world.resource_mut::<StepCount>().0 = 0;
+    *app.world.resource_mut::<SimulatedTime>() = SimulatedTime::default();
+    let physics_time = *app.world.resource::<Time<Physics>>();
+
+    advance_time(&mut app, Duration::from_secs_f64(4.0 / 60.0));
+
+    assert_eq!(app.world.resource::<StepCount>().0, 2);
+    assert_eq!(last_event(&app).policy, StepBudgetPolicy::Stretch);
+
+    // All four steps worth of time were simulated by the steps and their substeps
+    let simulated = app.world.resource::<SimulatedTime>();
+    assert!((simulated.steps.as_secs_f64() - 4.0 / 60.0).abs() < 1e-6);
+    assert!((simulated.substeps.as_secs_f64() - 4.0 / 60.0).abs() < 1e-6);
+
+    // The stretched delta is only used for the stretched step
+    let timestep = Duration::from_secs_f64(1.0 / 60.0);
+    assert_eq!(app.world.resource::<Time<Physics>>().delta(), timestep);
+    assert_eq!(
+        app.world.resource::<Time<Physics>>().elapsed(),
+        physics_time.elapsed() + timestep
+    );
+}
+
+#[test]
+fn no_ambiguity_errors() {
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct DeterministicSchedule;
+
+    let mut app = App::new();
+
+    app.add_plugins((
+        MinimalPlugins,
+        PhysicsPlugins::new(DeterministicSchedule),
+        PhysicsExtrasPlugins::new(DeterministicSchedule),
+    ));
+
+    #[cfg(feature = "async-collider")]
+    {
+        app.add_plugins((
+            bevy::asset::AssetPlugin::default(),
+            bevy::scene::ScenePlugin,
+        ))
+        .init_resource::<Assets<Mesh>>();
+    }
+
+    app.edit_schedule(DeterministicSchedule, |s| {
+        s.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..default()
+        });
+    })
+    .add_systems(Update, |w: &mut World| {
+        w.run_schedule(DeterministicSchedule);
+    });
+
+    app.update();
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+fn create_debug_app(config: PhysicsGizmos) -> App {
+    let mut app = create_app();
+    if !app.is_plugin_added::<bevy::asset::AssetPlugin>() {
+        app.add_plugins(bevy::asset::AssetPlugin::default());
+    }
+    app.init_asset::<bevy::render::render_resource::Shader>()
+        .add_plugins((bevy::gizmos::GizmoPlugin, PhysicsDebugPlugin::default()));
+    #[cfg(feature = "debug-mesh")]
+    app.init_asset::<Mesh>().init_asset::<StandardMaterial>();
+    app.world
+        .resource_mut::<GizmoConfigStore>()
+        .insert(GizmoConfig::default(), config);
+    app
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn contact_impulse_gizmos_use_contact_forces() {
+    let mut app = create_debug_app(PhysicsGizmos::contact_impulses(
+        Some(Color::RED),
+        Some(Color::GREEN),
+    ));
+
+    let floor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            Collider::cuboid(20.0, 1.0, 20.0),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 0.5),
+            LinearVelocity(Vector::X * 2.0),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+
+    // Let the box slide on the floor while its contact impulses are rendered
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // The impulse arrows are scaled by the contact forces, which should hold up the box
+    // and slow it down while it slides.
+    let delta_secs = app
+        .world
+        .resource::<Time<Substeps>>()
+        .delta_seconds_f64()
+        .adjust_precision();
+    let weight =
+        app.world.get::<Mass>(body).unwrap().0 * app.world.resource::<Gravity>().0.length();
+    let contacts = app.world.resource::<Collisions>().get(body, floor).unwrap();
+    let contact_points = || contacts.manifolds.iter().flat_map(|m| m.contacts.iter());
+
+    // The impulses are applied to the first body, so the normal force pushing the box up is negative
+    let normal_force: Scalar = -contact_points()
+        .map(|c| c.normal_force(delta_secs))
+        .sum::<Scalar>();
+    let tangent_force: Scalar = contact_points()
+        .map(|c| c.tangent_force(delta_secs).abs())
+        .sum();
+
+    assert_relative_eq!(normal_force, weight, max_relative = 0.1);
+    assert!(tangent_force > 0.0);
+    assert!(app.world.get::<LinearVelocity>(body).unwrap().x < 2.0);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn joint_gizmos_render_anchors_and_limits_of_all_joints() {
+    let mut app = create_debug_app(
+        PhysicsGizmos::joints(Some(Color::PINK), Some(Color::RED))
+            .with_joint_frame_length(0.5)
+            .with_joint_limit_color(Color::YELLOW),
+    );
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    let spawn_pair = |app: &mut App, x: Scalar| {
+        let anchor = app
+            .world
+            .spawn((RigidBody::Static, Position(Vector::X * x)))
+            .id();
+        let body = app
+            .world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::new(x, -1.0, 0.0)),
+                AngularVelocity(Vector::new(2.0, 0.0, 3.0)),
+                Collider::sphere(0.25),
+            ))
+            .id();
+        (anchor, body)
+    };
+
+    let (anchor, body) = spawn_pair(&mut app, 0.0);
+    let revolute = RevoluteJoint::new(anchor, body)
+        .with_local_anchor_2(Vector::Y)
+        .with_aligned_axis(Vector::Z)
+        .with_angle_limits(-0.5, 0.5);
+    let revolute = app.world.spawn(revolute).id();
+
+    let (anchor, body) = spawn_pair(&mut app, 2.0);
+    let spherical = SphericalJoint::new(anchor, body)
+        .with_local_anchor_2(Vector::Y)
+        .with_swing_limits(-0.5, 0.5)
+        .with_twist_limits(-0.5, 0.5);
+    let spherical = app.world.spawn(spherical).id();
+
+    let (anchor, body) = spawn_pair(&mut app, 4.0);
+    let fixed = app
+        .world
+        .spawn(FixedJoint::new(anchor, body).with_local_anchor_2(Vector::Y))
+        .id();
+
+    let (anchor, body) = spawn_pair(&mut app, 6.0);
+    app.world.spawn(
+        PrismaticJoint::new(anchor, body)
+            .with_local_anchor_2(Vector::Y)
+            .with_free_axis(Vector::X)
+            .with_limits(-0.5, 0.5),
+    );
+
+    let (anchor, body) = spawn_pair(&mut app, 8.0);
+    app.world.spawn(
+        DistanceJoint::new(anchor, body)
+            .with_rest_length(1.0)
+            .with_limits(0.5, 1.5),
+    );
+
+    // Let the bodies swing against the limits of their joints
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    // The anchors of joints with correctly configured local anchors are rendered at the same point
+    fn separation<T: Joint>(app: &App, joint: Entity) -> Scalar {
+        let joint = app.world.get::<T>(joint).unwrap();
+        let [anchor1, anchor2] = [
+            (joint.entities()[0], joint.local_anchor_1()),
+            (joint.entities()[1], joint.local_anchor_2()),
+        ]
+        .map(|(entity, local_anchor)| {
+            let position = app.world.get::<Position>(entity).unwrap();
+            let rotation = app.world.get::<Rotation>(entity).unwrap();
+            position.0 + rotation.rotate(local_anchor)
+        });
+        anchor1.distance(anchor2)
+    }
+
+    assert!(separation::<RevoluteJoint>(&app, revolute) < 0.01);
+    assert!(separation::<SphericalJoint>(&app, spherical) < 0.01);
+    assert!(separation::<FixedJoint>(&app, fixed) < 0.01);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn body_state_colors_follow_the_state_of_bodies() {
+    let colors = BodyStateColors::default();
+    let mut app = create_debug_app(PhysicsGizmos::default().with_body_state_colors(colors));
+
+    let floor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            Collider::cuboid(20.0, 1.0, 20.0),
+        ))
+        .id();
+    let kinematic = app
+        .world
+        .spawn((
+            RigidBody::Kinematic,
+            Position(Vector::new(5.0, 5.0, 0.0)),
+            LinearVelocity(Vector::X),
+            Collider::sphere(0.5),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 2.0),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+    let sensor = app
+        .world
+        .spawn((Position(Vector::new(-5.0, 5.0, 0.0)), Collider::sphere(0.5)))
+        .id();
+
+    fn color_of(app: &mut App, colors: &BodyStateColors, entity: Entity) -> Color {
+        let mut bodies = app
+            .world
+            .query::<(Option<&RigidBody>, Has<Sleeping>, Option<&TimeSleeping>)>();
+        let (rb, is_sleeping, time_sleeping) = bodies.get(&app.world, entity).unwrap();
+        colors.color(rb, is_sleeping, time_sleeping)
+    }
+
+    // The box falls onto the floor, comes to rest, and eventually falls asleep
+    let mut body_colors = vec![];
+    for _ in 0..300 {
+        tick_60_fps(&mut app);
+
+        let color = color_of(&mut app, &colors, body);
+        if body_colors.last() != Some(&color) {
+            body_colors.push(color);
+        }
+    }
+
+    assert_eq!(body_colors.first(), Some(&colors.awake));
+    assert!(body_colors.contains(&colors.sleeping_pending));
+    assert_eq!(body_colors.last(), Some(&colors.sleeping));
+
+    assert_eq!(color_of(&mut app, &colors, floor), colors.static_body);
+    assert_eq!(color_of(&mut app, &colors, kinematic), colors.kinematic);
+    assert_eq!(color_of(&mut app, &colors, sensor), colors.static_body);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn broad_phase_gizmos_render_the_static_aabb_tree() {
+    use crate::plugins::collision::broad_phase::StaticAabbTree;
+
+    let mut app = create_debug_app(PhysicsGizmos::broad_phase(
+        Some(Color::GREEN),
+        Some(Color::RED),
+    ));
+
+    let static_colliders: Vec<Entity> = (0..16)
+        .map(|i| {
+            app.world
+                .spawn((
+                    RigidBody::Static,
+                    Position(Vector::new(i as Scalar * 2.0, 0.0, 0.0)),
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                ))
+                .id()
+        })
+        .collect();
+    for i in 0..4 {
+        app.world.spawn((
+            RigidBody::Dynamic,
+            Position(Vector::new(i as Scalar * 2.0, 1.0, 0.0)),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ));
+    }
+
+    for _ in 0..5 {
+        tick_60_fps(&mut app);
+    }
+
+    // The root of the rendered tree covers all static colliders, and it is split into deeper levels
+    let mut nodes = vec![];
+    app.world
+        .resource::<StaticAabbTree>()
+        .for_each_node(|aabb, depth| nodes.push((*aabb, depth)));
+    let root = nodes.iter().find(|(_, depth)| *depth == 0).unwrap().0;
+
+    for entity in static_colliders {
+        let aabb = app.world.get::<ColliderAabb>(entity).unwrap();
+        assert_eq!(root.merged(*aabb), root);
+    }
+    assert!(nodes.iter().any(|(_, depth)| *depth > 1));
+
+    // The pair grid is rendered from the pairs of the boxes resting on the static colliders
+    assert!(!app.world.resource::<BroadCollisionPairs>().0.is_empty());
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn velocity_gizmos_can_be_configured_per_entity() {
+    let mut app = create_debug_app(
+        PhysicsGizmos::none()
+            .with_velocity_colors(Some(Color::GREEN), Some(Color::PURPLE))
+            .with_velocity_scale(0.5),
+    );
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            LinearVelocity(Vector::X),
+            // Fast enough that the rendered arc would cover more than a full turn
+            AngularVelocity(Vector::Y * 20.0),
+            Collider::sphere(0.5),
+        ))
+        .id();
+    let hidden = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Z * 5.0),
+            LinearVelocity(Vector::X),
+            Collider::sphere(0.5),
+            DebugRender::default().without_velocities(),
+        ))
+        .id();
+
+    for _ in 0..10 {
+        tick_60_fps(&mut app);
+    }
+
+    // Rendering the velocities doesn't affect the motion of the bodies
+    assert_relative_eq!(app.world.get::<LinearVelocity>(body).unwrap().0, Vector::X);
+    // The angular velocity only decreases slightly due to angular damping
+    assert_relative_eq!(
+        app.world.get::<AngularVelocity>(body).unwrap().0,
+        Vector::Y * 20.0,
+        max_relative = 0.05
+    );
+
+    let render_config = app.world.get::<DebugRender>(hidden).unwrap();
+    assert!(render_config.linear_velocity_color.is_none());
+    assert!(render_config.angular_velocity_color.is_none());
+}
+
+#[cfg(feature = "egui")]
+#[test]
+fn physics_inspector_does_not_change_resources_without_input() {
+    let mut app = create_app();
+    if !app.is_plugin_added::<bevy::asset::AssetPlugin>() {
+        app.add_plugins(bevy::asset::AssetPlugin::default());
+    }
+    // Run the inspector in a headless primary window
+    app.init_asset::<bevy::render::render_resource::Shader>()
+        .init_asset::<Image>()
+        .add_plugins((
+            bevy::input::InputPlugin,
+            bevy::window::WindowPlugin {
+                primary_window: None,
+                ..default()
+            },
+            PhysicsInspectorPlugin,
+        ));
+    app.world
+        .spawn((Window::default(), bevy::window::PrimaryWindow));
+
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+
+    // The resources are only written when a value is changed in the window
+    app.add_systems(
+        PostUpdate,
+        |gravity: Res<Gravity>,
+         substeps: Res<SubstepCount>,
+         threshold: Res<SleepingThreshold>,
+         mut first_run: Local<bool>| {
+            // Everything counts as changed on the first run of a system
+            if !std::mem::replace(&mut *first_run, true) {
+                return;
+            }
+            assert!(!gravity.is_changed());
+            assert!(!substeps.is_changed());
+            assert!(!threshold.is_changed());
+        },
+    );
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn debug_render_filters_use_layers_and_debug_render_entities() {
+    use crate::plugins::debug::passes_filters;
+
+    let mut app = create_debug_app(PhysicsGizmos::default().with_layer_filter(LayerMask(0b01)));
+
+    let included = app
+        .world
+        .spawn((
+            Collider::sphere(0.5),
+            CollisionLayers::new(LayerMask(0b01), LayerMask::ALL),
+        ))
+        .id();
+    let excluded = app
+        .world
+        .spawn((
+            Collider::sphere(0.5),
+            CollisionLayers::new(LayerMask(0b10), LayerMask::ALL),
+        ))
+        .id();
+    let ignoring_filters = app
+        .world
+        .spawn((
+            Collider::sphere(0.5),
+            CollisionLayers::new(LayerMask(0b10), LayerMask::ALL),
+            DebugRender::default().ignoring_filters(),
+        ))
+        .id();
+    let without_layers = app.world.spawn(Collider::sphere(0.5)).id();
+
+    tick_60_fps(&mut app);
+
+    let is_rendered = |app: &mut App, entity: Entity| {
+        let mut entities = app
+            .world
+            .query::<(Option<&DebugRender>, Option<&CollisionLayers>)>();
+        let (render_config, layers) = entities.get(&app.world, entity).unwrap();
+        let store = app.world.resource::<GizmoConfigStore>();
+        passes_filters(store.config::<PhysicsGizmos>().1, render_config, layers)
+    };
+
+    assert!(is_rendered(&mut app, included));
+    assert!(!is_rendered(&mut app, excluded));
+    assert!(is_rendered(&mut app, ignoring_filters));
+    // Colliders without collision layers belong to the first layer by default
+    assert!(is_rendered(&mut app, without_layers));
+
+    // Only render entities with a `DebugRender` component
+    let mut store = app.world.resource_mut::<GizmoConfigStore>();
+    let config = store.config_mut::<PhysicsGizmos>().1;
+    *config = PhysicsGizmos::default().with_only_debug_render_entities();
+    tick_60_fps(&mut app);
+
+    assert!(!is_rendered(&mut app, included));
+    assert!(is_rendered(&mut app, ignoring_filters));
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn inertia_gizmos_use_the_principal_axes_of_inertia() {
+    use crate::plugins::debug::principal_inertia;
+
+    let mut app = create_debug_app(
+        PhysicsGizmos::none()
+            .with_center_of_mass_color(Color::RED)
+            .with_inertia_color(Color::YELLOW),
+    );
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    // A box that is rotated relative to its body, so its inertia tensor isn't diagonal
+    let rotation = Quaternion::from_rotation_z(0.5) * Quaternion::from_rotation_x(0.3);
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Collider::compound(vec![(Vector::X, rotation, Collider::cuboid(1.0, 2.0, 3.0))]),
+        ))
+        .id();
+
+    // Let the mass properties of the body be computed from its collider
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+
+    // The center of mass is at the center of the box
+    assert_relative_eq!(
+        app.world.get::<CenterOfMass>(body).unwrap().0,
+        Vector::X,
+        epsilon = 0.0001
+    );
+
+    // I_x = 1/12 * m * (h^2 + d^2), and similarly for the other axes
+    let mass = app.world.get::<Mass>(body).unwrap().0;
+    let expected = Vector::new(4.0 + 9.0, 1.0 + 9.0, 1.0 + 4.0) * mass / 12.0;
+
+    let inertia = app.world.get::<Inertia>(body).unwrap().0;
+    let (principal, axes) = principal_inertia(inertia);
+
+    // The principal axes are the axes of the box, with the corresponding moments of inertia
+    for (local_axis, moment) in [Vector::X, Vector::Y, Vector::Z]
+        .into_iter()
+        .zip(expected.to_array())
+    {
+        let axis = rotation * local_axis;
+        let index = (0..3)
+            .max_by(|&i, &j| {
+                axes.col(i)
+                    .dot(axis)
+                    .abs()
+                    .total_cmp(&axes.col(j).dot(axis).abs())
+            })
+            .unwrap();
+        assert_relative_eq!(axes.col(index).dot(axis).abs(), 1.0, epsilon = 0.001);
+        assert_relative_eq!(principal[index], moment, max_relative = 0.001);
+    }
+    assert_relative_eq!(axes.determinant(), 1.0, epsilon = 0.001);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn debug_spatial_query_matches_spatial_query() {
+    #[derive(Resource, Default)]
+    struct Hits {
+        debug_ray: Option<RayHitData>,
+        ray: Option<RayHitData>,
+        debug_shape: Option<ShapeHitData>,
+        shape: Option<ShapeHitData>,
+    }
+
+    let mut app = create_debug_app(PhysicsGizmos::default());
+    app.init_resource::<Hits>().add_systems(
+        Update,
+        |mut spatial_query: DebugSpatialQuery, mut hits: ResMut<Hits>| {
+            let filter = SpatialQueryFilter::default();
+            let shape = Collider::sphere(0.25);
+            hits.debug_ray =
+                spatial_query.cast_ray(Vector::ZERO, Dir::X, 100.0, true, filter.clone());
+            hits.ray = spatial_query.spatial_query.cast_ray(
+                Vector::ZERO,
+                Dir::X,
+                100.0,
+                true,
+                filter.clone(),
+            );
+            hits.debug_shape = spatial_query.cast_shape(
+                &shape,
+                Vector::ZERO,
+                Quaternion::IDENTITY,
+                Dir::X,
+                100.0,
+                true,
+                filter.clone(),
+            );
+            hits.shape = spatial_query.spatial_query.cast_shape(
+                &shape,
+                Vector::ZERO,
+                Quaternion::IDENTITY,
+                Dir::X,
+                100.0,
+                true,
+                filter,
+            );
+        },
+    );
+
+    let wall = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            Collider::cuboid(1.0, 4.0, 4.0),
+        ))
+        .id();
+
+    // Add the wall to the spatial query pipeline
+    for _ in 0..2 {
+        tick_60_fps(&mut app);
+    }
+
+    // The debug spatial query returns the same hits as the regular spatial query
+    let hits = app.world.resource::<Hits>();
+    assert_eq!(hits.debug_ray.map(|hit| hit.entity), Some(wall));
+    assert_eq!(hits.debug_ray, hits.ray);
+    assert_eq!(hits.debug_shape.map(|hit| hit.entity), Some(wall));
+    assert_eq!(hits.debug_shape, hits.shape);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn island_colors_are_stable_when_islands_change() {
+    use crate::plugins::debug::island_color;
+
+    let mut app = create_debug_app(
+        PhysicsGizmos::default()
+            .with_island_colors()
+            .with_island_sleep_timer_color(Color::WHITE),
+    );
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(40.0, 1.0, 40.0),
+    ));
+    let spawn_box = |app: &mut App, x: Scalar| {
+        app.world
+            .spawn((
+                RigidBody::Dynamic,
+                Position(Vector::new(x, 0.5, 0.0)),
+                Collider::cuboid(1.0, 1.0, 1.0),
+            ))
+            .id()
+    };
+    let box1 = spawn_box(&mut app, -5.0);
+    let box2 = spawn_box(&mut app, 5.0);
+
+    tick_60_fps(&mut app);
+
+    let color_of = |app: &App, entity: Entity| {
+        let islands = app.world.resource::<PhysicsIslands>();
+        island_color(islands.island(entity).unwrap())
+    };
+
+    // Bodies that only touch the static floor are in separate islands with different colors
+    let color1 = color_of(&app, box1);
+    let color2 = color_of(&app, box2);
+    assert_ne!(color1, color2);
+
+    // Adding a new island doesn't change the colors of the existing islands
+    let box3 = spawn_box(&mut app, 0.0);
+    tick_60_fps(&mut app);
+
+    assert_eq!(color_of(&app, box1), color1);
+    assert_eq!(color_of(&app, box2), color2);
+    assert_ne!(color_of(&app, box3), color1);
+    assert_ne!(color_of(&app, box3), color2);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn stress_heatmap_colors_bodies_by_contact_stress() {
+    let heatmap = StressHeatmap::default();
+    let mut app = create_debug_app(PhysicsGizmos::default().with_stress_heatmap(heatmap));
+
+    let floor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            Collider::cuboid(20.0, 1.0, 20.0),
+        ))
+        .id();
+    let boxes: Vec<Entity> = (0..3)
+        .map(|i| {
+            app.world
+                .spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::Y * (0.5 + i as Scalar)),
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                ))
+                .id()
+        })
+        .collect();
+
+    // Let the stack settle
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // The stress is the contact force acting on a body divided by its weight
+    let delta_secs = app
+        .world
+        .resource::<Time<Substeps>>()
+        .delta_seconds_f64()
+        .adjust_precision();
+    let weight =
+        app.world.get::<Mass>(boxes[0]).unwrap().0 * app.world.resource::<Gravity>().0.length();
+    let collisions = app.world.resource::<Collisions>();
+    let contact_force = |entity1: Entity, entity2: Entity| {
+        collisions
+            .get(entity1, entity2)
+            .map_or(0.0, |contacts| contacts.total_normal_force(delta_secs))
+    };
+
+    // The top box is pushed by the box below it with a force equal to its own weight
+    let top_stress = contact_force(boxes[1], boxes[2]) / weight;
+    // The bottom box is pushed by the floor and the box above it
+    let bottom_stress =
+        (contact_force(floor, boxes[0]) + contact_force(boxes[0], boxes[1])) / weight;
+
+    assert_relative_eq!(top_stress, 1.0, max_relative = 0.2);
+    assert_relative_eq!(bottom_stress, 5.0, max_relative = 0.2);
+
+    // Bodies under more stress are closer to the high color
+    let high = heatmap.high.as_rgba_f32();
+    let distance_to_high = |stress: Scalar| {
+        let color = heatmap.color(stress).as_rgba_f32();
+        (0..4).map(|i| (color[i] - high[i]).abs()).sum::<f32>()
+    };
+    assert!(distance_to_high(bottom_stress) < distance_to_high(top_stress));
+    assert_eq!(heatmap.color(bottom_stress), heatmap.high);
+}
+
+#[cfg(all(
+    feature = "3d",
+    feature = "debug-mesh",
+    feature = "default-collider",
+    any(feature = "parry-f32", feature = "parry-f64")
+))]
+#[test]
+fn collider_debug_meshes_are_spawned_and_despawned() {
+    let mut app = create_debug_app(PhysicsGizmos::default().with_collider_mesh_color(Color::BLUE));
+
+    app.world.spawn((
+        RigidBody::Static,
+        Position(Vector::NEG_Y * 0.5),
+        Collider::cuboid(20.0, 1.0, 20.0),
+    ));
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 2.0),
+            Collider::convex_hull(vec![Vector::ZERO, Vector::X, Vector::Y, Vector::Z]).unwrap(),
+        ))
+        .id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // Each collider has a translucent debug mesh that follows it
+    let mut debug_meshes = app
+        .world
+        .query_filtered::<&Transform, (With<Handle<Mesh>>, With<Handle<StandardMaterial>>)>();
+    assert_eq!(debug_meshes.iter(&app.world).count(), 2);
+
+    let position = app.world.get::<Position>(body).unwrap().0;
+    assert!(debug_meshes
+        .iter(&app.world)
+        .any(|transform| transform.translation.distance(position.f32()) < 0.001));
+
+    // Disabling the debug meshes despawns them
+    let mut store = app.world.resource_mut::<GizmoConfigStore>();
+    store.config_mut::<PhysicsGizmos>().1.collider_mesh_color = None;
+    tick_60_fps(&mut app);
+
+    assert_eq!(debug_meshes.iter(&app.world).count(), 0);
+}
+
+#[cfg(all(feature = "3d", feature = "debug-plugin", feature = "default-collider"))]
+#[test]
+fn debug_recording_records_and_replays_physics_steps() {
+    let mut app = create_debug_app(PhysicsGizmos::default());
+
+    let floor = app
+        .world
+        .spawn((
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            Collider::cuboid(20.0, 1.0, 20.0),
+        ))
+        .id();
+    let body = app
+        .world
+        .spawn((
+            RigidBody::Dynamic,
+            Position(Vector::Y * 0.5),
+            LinearVelocity(Vector::X),
+            Collider::cuboid(1.0, 1.0, 1.0),
+        ))
+        .id();
+
+    app.world.resource_mut::<PhysicsDebugRecording>().record(10);
+
+    for _ in 0..20 {
+        tick_60_fps(&mut app);
+    }
+
+    // Only the requested number of steps is recorded
+    let recording = app.world.resource::<PhysicsDebugRecording>();
+    assert!(!recording.is_recording());
+    assert_eq!(recording.frames().len(), 10);
+
+    // The frames contain the state of the body and its contacts with the floor at each step
+    let positions: Vec<Vector> = recording
+        .frames()
+        .iter()
+        .map(|frame| {
+            assert!(frame.contacts.iter().any(|contact| {
+                [contact.entity1, contact.entity2].contains(&floor) && contact.normal_force > 0.0
+            }));
+            frame
+                .bodies
+                .iter()
+                .find(|recorded| recorded.entity == body)
+                .unwrap()
+                .position
+        })
+        .collect();
+    assert!(positions.windows(2).all(|pair| pair[1].x > pair[0].x));
+    assert!(
+        recording.frames().last().unwrap().elapsed_seconds > recording.frames()[0].elapsed_seconds
+    );
+
+    // Replaying and scrubbing is clamped to the recorded frames
+    let mut recording = app.world.resource_mut::<PhysicsDebugRecording>();
+    recording.replay(100);
+    assert_eq!(recording.replayed_frame_index(), Some(9));
+    recording.scrub(-3);
+    assert_eq!(recording.replayed_frame_index(), Some(6));
+    recording.scrub(-10);
+    assert_eq!(recording.replayed_frame_index(), Some(0));
+
+    // Replaying a frame renders the recording without changing the simulation
+    let position = app.world.get::<Position>(body).unwrap().0;
+    tick_60_fps(&mut app);
+    assert!(app.world.resource::<PhysicsDebugRecording>().is_replaying());
+    assert!(app.world.get::<Position>(body).unwrap().x > position.x);
+
+    app.world
+        .resource_mut::<PhysicsDebugRecording>()
+        .stop_replay();
+    assert!(!app.world.resource::<PhysicsDebugRecording>().is_replaying());
 }